@@ -0,0 +1,8 @@
+//! Factory for AuthOauth2Token plugin.
+
+use super::AuthOauth2Token;
+
+/// Creates a new AuthOauth2Token instance.
+pub fn create() -> AuthOauth2Token {
+    AuthOauth2Token::new()
+}
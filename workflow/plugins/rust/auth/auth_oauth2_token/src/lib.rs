@@ -0,0 +1,392 @@
+//! Workflow plugin: obtain an OAuth2 access token.
+//!
+//! Supports the client-credentials and refresh-token grants. Tokens are kept
+//! in the runtime cache until shortly before they expire, so a workflow can
+//! put this node in front of every API call without hitting the token
+//! endpoint each time.
+
+use base64::Engine as _;
+use metabuilder_core::Runtime;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// AuthOauth2Token implements the NodeExecutor trait for OAuth2 token management.
+pub struct AuthOauth2Token {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl AuthOauth2Token {
+    /// Creates a new AuthOauth2Token instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "auth.oauth2_token",
+            category: "auth",
+            description: "Fetch and cache OAuth2 access tokens",
+        }
+    }
+}
+
+impl Default for AuthOauth2Token {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Token endpoint settings taken from the node inputs.
+struct TokenRequest {
+    token_url: String,
+    grant_type: String,
+    client_id: String,
+    client_secret: Option<String>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
+    audience: Option<String>,
+    basic_auth: bool,
+    timeout: Duration,
+}
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0)
+}
+
+fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+    inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+fn token_request(inputs: &HashMap<String, Value>, rt: Option<&Runtime>) -> Result<TokenRequest, String> {
+    let token_url: String = input(inputs, "token_url").ok_or("token_url is required")?;
+    let client_id: String = input(inputs, "client_id").ok_or("client_id is required")?;
+    let grant_type: String = input(inputs, "grant_type").unwrap_or_else(|| "client_credentials".to_string());
+    let auth_method: String = input(inputs, "auth_method").unwrap_or_else(|| "body".to_string());
+    let timeout_ms: u64 = input(inputs, "timeout_ms").unwrap_or(30_000);
+
+    let secret = |key: &str| -> Result<Option<String>, String> {
+        match input::<String>(inputs, key) {
+            Some(name) => rt
+                .ok_or("runtime with secrets provider is required")?
+                .secret(&name)
+                .map(Some)
+                .ok_or_else(|| format!("secret not found: {}", name)),
+            None => Ok(None),
+        }
+    };
+
+    let client_secret = secret("client_secret_secret")?;
+    let refresh_token = secret("refresh_token_secret")?;
+    match grant_type.as_str() {
+        "client_credentials" if client_secret.is_none() => {
+            return Err("client_secret_secret is required for client_credentials".to_string())
+        }
+        "client_credentials" => {}
+        "refresh_token" if refresh_token.is_none() => {
+            return Err("refresh_token_secret is required for refresh_token".to_string())
+        }
+        "refresh_token" => {}
+        other => {
+            return Err(format!(
+                "unsupported grant_type: {} (expected client_credentials or refresh_token)",
+                other
+            ))
+        }
+    }
+    let basic_auth = match auth_method.as_str() {
+        "body" => false,
+        "basic" => true,
+        other => return Err(format!("unknown auth_method: {} (expected body or basic)", other)),
+    };
+
+    Ok(TokenRequest {
+        token_url,
+        grant_type,
+        client_id,
+        client_secret,
+        refresh_token,
+        scope: input(inputs, "scope"),
+        audience: input(inputs, "audience"),
+        basic_auth,
+        timeout: Duration::from_millis(timeout_ms),
+    })
+}
+
+impl TokenRequest {
+    /// Tokens are cached per endpoint, client, grant, and requested scope.
+    fn cache_key(&self) -> String {
+        format!(
+            "auth.oauth2_token:{}|{}|{}|{}|{}",
+            self.token_url,
+            self.client_id,
+            self.grant_type,
+            self.scope.as_deref().unwrap_or(""),
+            self.audience.as_deref().unwrap_or("")
+        )
+    }
+
+    /// Calls the token endpoint with `grant_type`, refreshing with `refresh_token` when given.
+    fn fetch(&self, grant_type: &str, refresh_token: Option<&str>) -> Result<Value, String> {
+        let mut form: Vec<(&str, &str)> = vec![("grant_type", grant_type)];
+        if let Some(token) = refresh_token {
+            form.push(("refresh_token", token));
+        }
+        if let Some(scope) = &self.scope {
+            form.push(("scope", scope));
+        }
+        if let Some(audience) = &self.audience {
+            form.push(("audience", audience));
+        }
+        if !self.basic_auth {
+            form.push(("client_id", &self.client_id));
+            if let Some(secret) = &self.client_secret {
+                form.push(("client_secret", secret));
+            }
+        }
+
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(self.timeout))
+            .build()
+            .into();
+        let mut request = agent.post(&self.token_url).header("Accept", "application/json");
+        if self.basic_auth {
+            let credentials = format!("{}:{}", self.client_id, self.client_secret.as_deref().unwrap_or(""));
+            let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+            request = request.header("Authorization", &format!("Basic {}", encoded));
+        }
+        let mut response = request.send_form(form).map_err(|e| e.to_string())?;
+
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+        if status >= 400 {
+            return Err(format!("token endpoint returned {}: {}", status, body));
+        }
+        let body: Value = serde_json::from_str(&body).map_err(|e| format!("invalid token response: {}", e))?;
+        let access_token = body
+            .get("access_token")
+            .and_then(Value::as_str)
+            .ok_or("token response has no access_token")?;
+
+        Ok(json!({
+            "access_token": access_token,
+            "token_type": body.get("token_type").and_then(Value::as_str).unwrap_or("Bearer"),
+            "expires_at": body.get("expires_in").and_then(Value::as_i64).map(|s| now() + s),
+            // Servers that do not rotate refresh tokens omit them; keep using the old one.
+            "refresh_token": body.get("refresh_token").and_then(Value::as_str).or(refresh_token),
+            "scope": body.get("scope").cloned().unwrap_or_else(|| json!(self.scope)),
+        }))
+    }
+}
+
+/// A cached token is usable until `margin` seconds before it expires.
+fn is_fresh(token: &Value, margin: i64) -> bool {
+    match token.get("expires_at").and_then(Value::as_i64) {
+        Some(expires_at) => expires_at - margin > now(),
+        None => true,
+    }
+}
+
+/// Returns `(token, cached)`.
+fn obtain(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<(Value, bool), String> {
+    let rt = Runtime::from_any(runtime);
+    let request = token_request(inputs, rt)?;
+    let margin: i64 = input(inputs, "refresh_margin_seconds").unwrap_or(60);
+    let force_refresh: bool = input(inputs, "force_refresh").unwrap_or(false);
+    let key = request.cache_key();
+
+    let cached = rt.and_then(|rt| rt.cache.get(&key));
+    if let Some(token) = &cached {
+        if !force_refresh && is_fresh(token, margin) {
+            return Ok((token.clone(), true));
+        }
+    }
+
+    // Prefer the newest refresh token we know of; fall back to the configured grant.
+    let cached_refresh = cached
+        .as_ref()
+        .and_then(|t| t.get("refresh_token"))
+        .and_then(Value::as_str);
+    let token = match cached_refresh {
+        Some(refresh) => request
+            .fetch("refresh_token", Some(refresh))
+            .or_else(|_| request.fetch(&request.grant_type, request.refresh_token.as_deref()))?,
+        None => request.fetch(&request.grant_type, request.refresh_token.as_deref())?,
+    };
+
+    if let Some(rt) = rt {
+        rt.cache.insert(key, token.clone());
+    }
+    Ok((token, false))
+}
+
+impl NodeExecutor for AuthOauth2Token {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match obtain(&inputs, runtime) {
+            Ok((token, cached)) => {
+                let access_token = token["access_token"].as_str().unwrap_or_default();
+                let authorization = format!("Bearer {}", access_token);
+                output.insert("success".to_string(), json!(true));
+                output.insert("access_token".to_string(), json!(access_token));
+                output.insert("token_type".to_string(), token["token_type"].clone());
+                output.insert("expires_at".to_string(), token["expires_at"].clone());
+                output.insert("scope".to_string(), token["scope"].clone());
+                output.insert("cached".to_string(), json!(cached));
+                output.insert("headers".to_string(), json!({ "Authorization": authorization }));
+                output.insert("authorization".to_string(), json!(authorization));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), json!(false));
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new AuthOauth2Token instance.
+pub fn create() -> AuthOauth2Token {
+    AuthOauth2Token::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::runtime;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers one HTTP request per body in `bodies` and reports each request head and form body.
+    fn fake_server(bodies: Vec<&'static str>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for body in bodies {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut head = String::new();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    head.push_str(&line);
+                    line.clear();
+                }
+                let length: usize = head
+                    .lines()
+                    .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|n| n.trim().parse().unwrap()))
+                    .unwrap_or(0);
+                let mut form = vec![0; length];
+                reader.read_exact(&mut form).unwrap();
+                tx.send(format!("{}\n{}", head, String::from_utf8(form).unwrap())).unwrap();
+                let mut stream = stream;
+                write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+            }
+        });
+        (format!("http://{}/token", addr), rx)
+    }
+
+    const SECRETS: &[(&str, &str)] = &[("api_client_secret", "s3cret"), ("api_refresh_token", "refresh-1")];
+
+    fn inputs(token_url: &str) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("token_url".to_string(), json!(token_url));
+        inputs.insert("client_id".to_string(), json!("app"));
+        inputs.insert("client_secret_secret".to_string(), json!("api_client_secret"));
+        inputs.insert("scope".to_string(), json!("read"));
+        inputs
+    }
+
+    #[test]
+    fn test_client_credentials_cached() {
+        let (token_url, requests) = fake_server(vec![r#"{"access_token":"tok-1","token_type":"Bearer","expires_in":3600}"#]);
+        let executor = AuthOauth2Token::new();
+        let runtime = runtime(SECRETS);
+
+        let first = executor.execute(inputs(&token_url), Some(&runtime));
+        assert_eq!(first.get("access_token"), Some(&json!("tok-1")));
+        assert_eq!(first.get("cached"), Some(&json!(false)));
+        assert_eq!(first.get("headers"), Some(&json!({"Authorization": "Bearer tok-1"})));
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /token"));
+        assert!(request.contains("grant_type=client_credentials"));
+        assert!(request.contains("client_secret=s3cret"));
+
+        // Served from the cache: the fake server would not answer a second request.
+        let second = executor.execute(inputs(&token_url), Some(&runtime));
+        assert_eq!(second.get("access_token"), Some(&json!("tok-1")));
+        assert_eq!(second.get("cached"), Some(&json!(true)));
+    }
+
+    #[test]
+    fn test_expired_token_is_refreshed() {
+        let (token_url, requests) = fake_server(vec![
+            r#"{"access_token":"tok-1","expires_in":30,"refresh_token":"refresh-2"}"#,
+            r#"{"access_token":"tok-2","expires_in":3600}"#,
+        ]);
+        let executor = AuthOauth2Token::new();
+        let runtime = runtime(SECRETS);
+
+        // expires_in 30 is inside the default 60 second margin, so the next call refreshes.
+        let first = executor.execute(inputs(&token_url), Some(&runtime));
+        assert_eq!(first.get("access_token"), Some(&json!("tok-1")));
+        requests.recv().unwrap();
+
+        let second = executor.execute(inputs(&token_url), Some(&runtime));
+        assert_eq!(second.get("access_token"), Some(&json!("tok-2")));
+        let request = requests.recv().unwrap();
+        assert!(request.contains("grant_type=refresh_token"));
+        assert!(request.contains("refresh_token=refresh-2"));
+    }
+
+    #[test]
+    fn test_refresh_grant_with_basic_auth() {
+        let (token_url, requests) = fake_server(vec![r#"{"access_token":"tok-3"}"#]);
+        let executor = AuthOauth2Token::new();
+        let mut inputs = inputs(&token_url);
+        inputs.insert("grant_type".to_string(), json!("refresh_token"));
+        inputs.insert("refresh_token_secret".to_string(), json!("api_refresh_token"));
+        inputs.insert("auth_method".to_string(), json!("basic"));
+
+        let result = executor.execute(inputs, Some(&runtime(SECRETS)));
+        assert_eq!(result.get("access_token"), Some(&json!("tok-3")));
+        assert_eq!(result.get("expires_at"), Some(&Value::Null));
+
+        let request = requests.recv().unwrap();
+        assert!(request.contains("refresh_token=refresh-1"));
+        assert!(request.contains("YXBwOnMzY3JldA=="));
+        assert!(!request.contains("client_secret="));
+    }
+
+    #[test]
+    fn test_missing_client_secret() {
+        let executor = AuthOauth2Token::new();
+        let mut inputs = inputs("http://127.0.0.1:1/token");
+        inputs.remove("client_secret_secret");
+
+        let result = executor.execute(inputs, Some(&runtime(SECRETS)));
+        assert_eq!(
+            result.get("error"),
+            Some(&json!("client_secret_secret is required for client_credentials"))
+        );
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "auth.oauth2_token");
+        assert_eq!(executor.category, "auth");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for BytesConcat plugin.
+
+use super::BytesConcat;
+
+/// Creates a new BytesConcat instance.
+pub fn create() -> BytesConcat {
+    BytesConcat::new()
+}
@@ -0,0 +1,109 @@
+//! Workflow plugin: join bytes values.
+//!
+//! `items` is a list of tagged bytes values or strings (taken as UTF-8),
+//! concatenated in order.
+
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// BytesConcat implements the NodeExecutor trait for joining bytes.
+pub struct BytesConcat {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl BytesConcat {
+    /// Creates a new BytesConcat instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "bytes.concat",
+            category: "bytes",
+            description: "Concatenate bytes values",
+        }
+    }
+}
+
+impl Default for BytesConcat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn concat(inputs: &HashMap<String, Value>) -> Result<Vec<u8>, String> {
+    let items = match inputs.get("items") {
+        Some(Value::Array(items)) => items,
+        _ => return Err("items must be a list".to_string()),
+    };
+    let mut joined = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        joined.extend(bytes::from_value(item).map_err(|e| format!("item {}: {}", i, e))?);
+    }
+    Ok(joined)
+}
+
+impl NodeExecutor for BytesConcat {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match concat(&inputs) {
+            Ok(joined) => {
+                output.insert("result".to_string(), bytes::to_value(&joined));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new BytesConcat instance.
+pub fn create() -> BytesConcat {
+    BytesConcat::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concat() {
+        let executor = BytesConcat::new();
+        let mut inputs = HashMap::new();
+        inputs.insert(
+            "items".to_string(),
+            serde_json::json!([bytes::to_value(&[0xff, 0]), "ab", bytes::to_value(&[])]),
+        );
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&bytes::to_value(&[0xff, 0, b'a', b'b'])));
+    }
+
+    #[test]
+    fn test_concat_invalid_item() {
+        let executor = BytesConcat::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("items".to_string(), serde_json::json!(["a", 1]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("item 1: expected bytes or a string, got 1")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "bytes.concat");
+        assert_eq!(executor.category, "bytes");
+    }
+}
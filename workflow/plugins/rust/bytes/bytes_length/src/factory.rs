@@ -0,0 +1,8 @@
+//! Factory for BytesLength plugin.
+
+use super::BytesLength;
+
+/// Creates a new BytesLength instance.
+pub fn create() -> BytesLength {
+    BytesLength::new()
+}
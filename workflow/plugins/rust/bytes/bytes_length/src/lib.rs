@@ -0,0 +1,93 @@
+//! Workflow plugin: length of a bytes value.
+//!
+//! `bytes` is a tagged bytes value or a string, counted as UTF-8 bytes.
+
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// BytesLength implements the NodeExecutor trait for measuring bytes.
+pub struct BytesLength {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl BytesLength {
+    /// Creates a new BytesLength instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "bytes.length",
+            category: "bytes",
+            description: "Number of bytes in a binary value",
+        }
+    }
+}
+
+impl Default for BytesLength {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for BytesLength {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match bytes::from_value(inputs.get("bytes").unwrap_or(&Value::Null)) {
+            Ok(data) => {
+                output.insert("result".to_string(), serde_json::json!(data.len()));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new BytesLength instance.
+pub fn create() -> BytesLength {
+    BytesLength::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length() {
+        let executor = BytesLength::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("bytes".to_string(), bytes::to_value(&[1, 2, 3]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(3)));
+    }
+
+    #[test]
+    fn test_length_of_string() {
+        let executor = BytesLength::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("bytes".to_string(), serde_json::json!("né"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(3)));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "bytes.length");
+        assert_eq!(executor.category, "bytes");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for BytesSlice plugin.
+
+use super::BytesSlice;
+
+/// Creates a new BytesSlice instance.
+pub fn create() -> BytesSlice {
+    BytesSlice::new()
+}
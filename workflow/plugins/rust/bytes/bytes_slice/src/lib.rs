@@ -0,0 +1,113 @@
+//! Workflow plugin: slice a bytes value.
+//!
+//! `start` and `end` are byte offsets; negative values count from the end,
+//! and out-of-range values are clamped, as in `list.slice`.
+
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// BytesSlice implements the NodeExecutor trait for slicing bytes.
+pub struct BytesSlice {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl BytesSlice {
+    /// Creates a new BytesSlice instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "bytes.slice",
+            category: "bytes",
+            description: "Extract a range of bytes",
+        }
+    }
+}
+
+impl Default for BytesSlice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolves a possibly negative offset against `len`.
+fn index(offset: i64, len: usize) -> usize {
+    let len = len as i64;
+    if offset < 0 {
+        (len + offset).max(0) as usize
+    } else {
+        offset.min(len) as usize
+    }
+}
+
+impl NodeExecutor for BytesSlice {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let data = match bytes::from_value(inputs.get("bytes").unwrap_or(&Value::Null)) {
+            Ok(data) => data,
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+                return output;
+            }
+        };
+        let start = index(inputs.get("start").and_then(Value::as_i64).unwrap_or(0), data.len());
+        let end = inputs
+            .get("end")
+            .and_then(Value::as_i64)
+            .map_or(data.len(), |end| index(end, data.len()));
+
+        let slice = if start < end { &data[start..end] } else { &[][..] };
+        output.insert("result".to_string(), bytes::to_value(slice));
+        output
+    }
+}
+
+/// Creates a new BytesSlice instance.
+pub fn create() -> BytesSlice {
+    BytesSlice::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice() {
+        let executor = BytesSlice::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("bytes".to_string(), bytes::to_value(&[0, 1, 2, 3, 4, 5]));
+        inputs.insert("start".to_string(), serde_json::json!(1));
+        inputs.insert("end".to_string(), serde_json::json!(-2));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&bytes::to_value(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn test_slice_out_of_range() {
+        let executor = BytesSlice::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("bytes".to_string(), serde_json::json!("abc"));
+        inputs.insert("start".to_string(), serde_json::json!(5));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&bytes::to_value(&[])));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "bytes.slice");
+        assert_eq!(executor.category, "bytes");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for BytesToString plugin.
+
+use super::BytesToString;
+
+/// Creates a new BytesToString instance.
+pub fn create() -> BytesToString {
+    BytesToString::new()
+}
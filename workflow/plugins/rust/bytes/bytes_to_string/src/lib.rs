@@ -0,0 +1,115 @@
+//! Workflow plugin: decode bytes to a string.
+//!
+//! `encoding` is any WHATWG encoding label (`utf-8` by default, `utf-16le`,
+//! `shift_jis`, `latin1`, ...). Invalid sequences are an error unless
+//! `errors` is `"replace"`, which substitutes U+FFFD.
+
+use encoding_rs::Encoding;
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// BytesToString implements the NodeExecutor trait for decoding bytes.
+pub struct BytesToString {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl BytesToString {
+    /// Creates a new BytesToString instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "bytes.to_string",
+            category: "bytes",
+            description: "Decode bytes to a string in a given encoding",
+        }
+    }
+}
+
+impl Default for BytesToString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn decode(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let data = bytes::from_value(inputs.get("bytes").unwrap_or(&Value::Null))?;
+    let label = inputs.get("encoding").and_then(Value::as_str).unwrap_or("utf-8");
+    let encoding = Encoding::for_label(label.as_bytes()).ok_or_else(|| format!("unknown encoding: {}", label))?;
+
+    match inputs.get("errors").and_then(Value::as_str).unwrap_or("strict") {
+        "strict" => encoding
+            .decode_without_bom_handling_and_without_replacement(&data)
+            .map(|text| text.into_owned())
+            .ok_or_else(|| format!("bytes are not valid {}", encoding.name())),
+        "replace" => Ok(encoding.decode_without_bom_handling(&data).0.into_owned()),
+        other => Err(format!("unknown errors mode: {} (expected strict or replace)", other)),
+    }
+}
+
+impl NodeExecutor for BytesToString {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match decode(&inputs) {
+            Ok(text) => {
+                output.insert("result".to_string(), serde_json::json!(text));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new BytesToString instance.
+pub fn create() -> BytesToString {
+    BytesToString::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_with(data: &[u8], encoding: &str, errors: &str) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("bytes".to_string(), bytes::to_value(data));
+        inputs.insert("encoding".to_string(), serde_json::json!(encoding));
+        inputs.insert("errors".to_string(), serde_json::json!(errors));
+        BytesToString::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_to_string_encodings() {
+        assert_eq!(decode_with("héllo".as_bytes(), "utf-8", "strict")["result"], "héllo");
+        assert_eq!(decode_with(&[0x68, 0, 0xe9, 0], "utf-16le", "strict")["result"], "hé");
+        assert_eq!(decode_with(&[0x82, 0xa0], "shift_jis", "strict")["result"], "あ");
+    }
+
+    #[test]
+    fn test_to_string_invalid_bytes() {
+        let result = decode_with(&[b'a', 0xff], "utf-8", "strict");
+        assert_eq!(result.get("error"), Some(&serde_json::json!("bytes are not valid UTF-8")));
+
+        let result = decode_with(&[b'a', 0xff], "utf-8", "replace");
+        assert_eq!(result.get("result"), Some(&serde_json::json!("a\u{fffd}")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "bytes.to_string");
+        assert_eq!(executor.category, "bytes");
+    }
+}
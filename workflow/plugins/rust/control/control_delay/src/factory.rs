@@ -0,0 +1,8 @@
+//! Factory for ControlDelay plugin.
+
+use super::ControlDelay;
+
+/// Creates a new ControlDelay instance.
+pub fn create() -> ControlDelay {
+    ControlDelay::new()
+}
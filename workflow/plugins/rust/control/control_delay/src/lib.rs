@@ -0,0 +1,177 @@
+//! Workflow plugin: pause for a duration or until a timestamp.
+//!
+//! Waits `duration_ms`, or until the RFC 3339 timestamp `until` (a time in
+//! the past does not wait), then passes `value` through as `result`. The
+//! wait ends early with an error when the run is cancelled, or at the run's
+//! deadline (`Engine::with_timeout`) if that comes first.
+//!
+//! The workflow engine does not run this executor: it parks the node for the
+//! `duration` it asks for, holding no thread, and keeps running the nodes
+//! that do not depend on it. Run directly, the executor blocks its thread
+//! for the whole wait.
+
+use chrono::{DateTime, Utc};
+use metabuilder_core::{Cancellation, Runtime};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// ControlDelay implements the NodeExecutor trait for pausing a workflow.
+pub struct ControlDelay {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl ControlDelay {
+    /// Creates a new ControlDelay instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "control.delay",
+            category: "control",
+            description: "Pause for a duration or until a timestamp",
+        }
+    }
+}
+
+impl Default for ControlDelay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How long to wait, from `duration_ms` or `until`. The engine parks delays
+/// for this long rather than running them.
+pub fn duration(inputs: &HashMap<String, Value>) -> Result<Duration, String> {
+    match (inputs.get("duration_ms"), inputs.get("until").and_then(Value::as_str)) {
+        (Some(ms), None) => ms
+            .as_u64()
+            .map(Duration::from_millis)
+            .ok_or_else(|| format!("duration_ms must be a non-negative integer, got {}", ms)),
+        (None, Some(until)) => {
+            let until = DateTime::parse_from_rfc3339(until)
+                .map_err(|e| format!("invalid until timestamp {}: {}", until, e))?
+                .with_timezone(&Utc);
+            Ok((until - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+        }
+        (Some(_), Some(_)) => Err("set only one of duration_ms and until".to_string()),
+        (None, None) => Err("duration_ms or until is required".to_string()),
+    }
+}
+
+impl NodeExecutor for ControlDelay {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let wait = match duration(&inputs) {
+            Ok(wait) => wait,
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+                return output;
+            }
+        };
+        let rt = Runtime::from_any(runtime);
+        let cancel = rt.map(|rt| rt.cancel.clone()).unwrap_or_else(|| Arc::new(Cancellation::new()));
+
+        let started = Instant::now();
+        let left = rt
+            .and_then(|rt| rt.deadline)
+            .map(|deadline| deadline.saturating_duration_since(started))
+            .filter(|&left| left < wait);
+        if !cancel.sleep(left.unwrap_or(wait)) {
+            output.insert("result".to_string(), Value::Null);
+            output.insert("error".to_string(), serde_json::json!("cancelled"));
+        } else if left.is_some() {
+            output.insert("result".to_string(), Value::Null);
+            output.insert("error".to_string(), serde_json::json!("run deadline reached"));
+        } else {
+            output.insert("result".to_string(), inputs.get("value").cloned().unwrap_or(Value::Null));
+        }
+        output.insert("waited_ms".to_string(), serde_json::json!(started.elapsed().as_millis() as u64));
+        output
+    }
+}
+
+/// Creates a new ControlDelay instance.
+pub fn create() -> ControlDelay {
+    ControlDelay::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_duration() {
+        let executor = ControlDelay::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("duration_ms".to_string(), serde_json::json!(20));
+        inputs.insert("value".to_string(), serde_json::json!({"id": 7}));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!({"id": 7})));
+        assert!(result["waited_ms"].as_u64().unwrap() >= 20);
+    }
+
+    #[test]
+    fn test_delay_until_past() {
+        let executor = ControlDelay::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("until".to_string(), serde_json::json!("2000-01-01T00:00:00Z"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), None);
+        assert!(result["waited_ms"].as_u64().unwrap() < 1000);
+    }
+
+    #[test]
+    fn test_delay_cancelled() {
+        let runtime = Runtime::new();
+        let cancel = runtime.cancel.clone();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            cancel.cancel();
+        });
+        let executor = ControlDelay::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("duration_ms".to_string(), serde_json::json!(60_000));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        canceller.join().unwrap();
+        assert_eq!(result.get("error"), Some(&serde_json::json!("cancelled")));
+    }
+
+    #[test]
+    fn test_delay_deadline() {
+        let mut runtime = Runtime::new();
+        runtime.deadline = Some(Instant::now() + Duration::from_millis(20));
+        let executor = ControlDelay::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("duration_ms".to_string(), serde_json::json!(60_000));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("run deadline reached")));
+        assert!(result["waited_ms"].as_u64().unwrap() < 10_000);
+
+        // A delay that ends before the deadline is unaffected.
+        runtime.deadline = Some(Instant::now() + Duration::from_secs(60));
+        let inputs = HashMap::from([("duration_ms".to_string(), serde_json::json!(1))]);
+        assert_eq!(executor.execute(inputs, Some(&runtime)).get("error"), None);
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "control.delay");
+        assert_eq!(executor.category, "control");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for ControlIf plugin.
+
+use super::ControlIf;
+
+/// Creates a new ControlIf instance.
+pub fn create() -> ControlIf {
+    ControlIf::new()
+}
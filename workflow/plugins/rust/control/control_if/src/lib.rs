@@ -0,0 +1,109 @@
+//! Workflow plugin: if/else branching.
+//!
+//! Only the taken branch port (`then` or `else`) is emitted, and `branch`
+//! names it. The engine skips nodes wired to the port that was not emitted.
+
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// Helper to convert Value to bool.
+fn to_bool(v: &Value) -> bool {
+    match v {
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().map(|f| f != 0.0).unwrap_or(false),
+        Value::String(s) => !s.is_empty(),
+        Value::Null => false,
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+    }
+}
+
+/// ControlIf implements the NodeExecutor trait for conditional branching.
+pub struct ControlIf {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl ControlIf {
+    /// Creates a new ControlIf instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "control.if",
+            category: "control",
+            description: "Route execution to a then or else branch",
+        }
+    }
+}
+
+impl Default for ControlIf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for ControlIf {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let condition = inputs.get("condition").unwrap_or(&Value::Null);
+        let result = to_bool(condition);
+        let branch = if result { "then" } else { "else" };
+        // The taken port carries `value`, or the condition itself when no value is given.
+        let value = inputs.get("value").unwrap_or(condition).clone();
+
+        let mut output = HashMap::new();
+        output.insert("result".to_string(), serde_json::json!(result));
+        output.insert("branch".to_string(), serde_json::json!(branch));
+        output.insert(branch.to_string(), value);
+        output
+    }
+}
+
+/// Creates a new ControlIf instance.
+pub fn create() -> ControlIf {
+    ControlIf::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_if_then() {
+        let executor = ControlIf::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("condition".to_string(), serde_json::json!(true));
+        inputs.insert("value".to_string(), serde_json::json!({"id": 1}));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("branch"), Some(&serde_json::json!("then")));
+        assert_eq!(result.get("then"), Some(&serde_json::json!({"id": 1})));
+        assert!(!result.contains_key("else"));
+    }
+
+    #[test]
+    fn test_if_else() {
+        let executor = ControlIf::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("condition".to_string(), serde_json::json!(""));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(false)));
+        assert_eq!(result.get("branch"), Some(&serde_json::json!("else")));
+        assert_eq!(result.get("else"), Some(&serde_json::json!("")));
+        assert!(!result.contains_key("then"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "control.if");
+        assert_eq!(executor.category, "control");
+    }
+}
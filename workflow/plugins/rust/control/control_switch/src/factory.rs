@@ -0,0 +1,8 @@
+//! Factory for ControlSwitch plugin.
+
+use super::ControlSwitch;
+
+/// Creates a new ControlSwitch instance.
+pub fn create() -> ControlSwitch {
+    ControlSwitch::new()
+}
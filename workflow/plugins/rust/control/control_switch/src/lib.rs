@@ -0,0 +1,151 @@
+//! Workflow plugin: multi-way branching.
+//!
+//! `value` is compared with each entry of `cases` in order. A case is either
+//! a bare value, whose port is `case_<index>`, or `{"port": name, "value": v}`.
+//! Only the port of the first matching case is emitted, or `default` when
+//! nothing matches (unless `default` is `false`); `branch` names it. The
+//! engine skips nodes wired to ports that were not emitted.
+
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// ControlSwitch implements the NodeExecutor trait for case routing.
+pub struct ControlSwitch {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl ControlSwitch {
+    /// Creates a new ControlSwitch instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "control.switch",
+            category: "control",
+            description: "Route execution to the branch of the matching case",
+        }
+    }
+}
+
+impl Default for ControlSwitch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits a case into its port name and the value it matches.
+fn case(index: usize, case: &Value) -> Result<(String, &Value), String> {
+    match case.as_object() {
+        Some(object) if object.len() == 2 && object.contains_key("value") => match object.get("port") {
+            Some(Value::String(port)) => Ok((port.clone(), &object["value"])),
+            _ => Err(format!("case {} has a port that is not a string", index)),
+        },
+        _ => Ok((format!("case_{}", index), case)),
+    }
+}
+
+fn route(inputs: &HashMap<String, Value>) -> Result<(Option<String>, Option<usize>), String> {
+    let value = inputs.get("value").unwrap_or(&Value::Null);
+    let cases = match inputs.get("cases") {
+        Some(Value::Array(cases)) => cases.as_slice(),
+        Some(_) => return Err("cases must be a list".to_string()),
+        None => return Err("cases is required".to_string()),
+    };
+
+    for (index, entry) in cases.iter().enumerate() {
+        let (port, expected) = case(index, entry)?;
+        if expected == value {
+            return Ok((Some(port), Some(index)));
+        }
+    }
+    let default = inputs.get("default").and_then(Value::as_bool).unwrap_or(true);
+    Ok((default.then(|| "default".to_string()), None))
+}
+
+impl NodeExecutor for ControlSwitch {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match route(&inputs) {
+            Ok((branch, matched)) => {
+                // The taken port carries `data`, or the switched value when no data is given.
+                let data = inputs.get("data").or(inputs.get("value")).cloned().unwrap_or(Value::Null);
+                if let Some(port) = &branch {
+                    output.insert(port.clone(), data);
+                }
+                output.insert("branch".to_string(), serde_json::json!(branch));
+                output.insert("matched".to_string(), serde_json::json!(matched));
+            }
+            Err(e) => {
+                output.insert("branch".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new ControlSwitch instance.
+pub fn create() -> ControlSwitch {
+    ControlSwitch::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_named_case() {
+        let executor = ControlSwitch::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), serde_json::json!("eu"));
+        inputs.insert(
+            "cases".to_string(),
+            serde_json::json!([{"port": "us", "value": "us"}, {"port": "europe", "value": "eu"}]),
+        );
+        inputs.insert("data".to_string(), serde_json::json!({"order": 7}));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("branch"), Some(&serde_json::json!("europe")));
+        assert_eq!(result.get("matched"), Some(&serde_json::json!(1)));
+        assert_eq!(result.get("europe"), Some(&serde_json::json!({"order": 7})));
+        assert!(!result.contains_key("us"));
+    }
+
+    #[test]
+    fn test_switch_bare_cases_and_default() {
+        let executor = ControlSwitch::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), serde_json::json!(3));
+        inputs.insert("cases".to_string(), serde_json::json!([1, 2, 3]));
+
+        let result = executor.execute(inputs.clone(), None);
+        assert_eq!(result.get("branch"), Some(&serde_json::json!("case_2")));
+        assert_eq!(result.get("case_2"), Some(&serde_json::json!(3)));
+
+        inputs.insert("value".to_string(), serde_json::json!(4));
+        let result = executor.execute(inputs.clone(), None);
+        assert_eq!(result.get("branch"), Some(&serde_json::json!("default")));
+        assert_eq!(result.get("matched"), Some(&Value::Null));
+
+        inputs.insert("default".to_string(), serde_json::json!(false));
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("branch"), Some(&Value::Null));
+        assert!(!result.contains_key("default"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "control.switch");
+        assert_eq!(executor.category, "control");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for ConvertAvroDecode plugin.
+
+use super::ConvertAvroDecode;
+
+/// Creates a new ConvertAvroDecode instance.
+pub fn create() -> ConvertAvroDecode {
+    ConvertAvroDecode::new()
+}
@@ -0,0 +1,235 @@
+//! Workflow plugin: decode Avro binary to JSON.
+//!
+//! The schema is given inline in `schema`, or looked up in a Confluent
+//! schema registry by the id in the message's registry header. Registry
+//! schemas are kept in the runtime cache, since an id never changes.
+
+use base64::Engine as _;
+use metabuilder_core::avro::{self, AvroSchema};
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// ConvertAvroDecode implements the NodeExecutor trait for Avro decoding.
+pub struct ConvertAvroDecode {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl ConvertAvroDecode {
+    /// Creates a new ConvertAvroDecode instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "convert.avro_decode",
+            category: "convert",
+            description: "Decode Avro binary to JSON",
+        }
+    }
+}
+
+impl Default for ConvertAvroDecode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+    inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Parses `schema`, which may be the schema itself or its JSON text.
+fn inline_schema(schema: &Value) -> Result<AvroSchema, String> {
+    match schema {
+        Value::String(text) if text.trim_start().starts_with(['{', '[']) => AvroSchema::parse_str(text),
+        other => AvroSchema::parse(other),
+    }
+}
+
+/// Fetches the schema text registered under `id`.
+fn registry_schema(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>, url: &str, id: u32) -> Result<String, String> {
+    let rt = Runtime::from_any(runtime);
+    let cache_key = format!("convert.avro_decode:{}|{}", url, id);
+    if let Some(Value::String(schema)) = rt.and_then(|rt| rt.cache.get(&cache_key)) {
+        return Ok(schema);
+    }
+
+    let timeout_ms: u64 = input(inputs, "timeout_ms").unwrap_or(30_000);
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .build()
+        .into();
+    let path = format!("{}/schemas/ids/{}", url.trim_end_matches('/'), id);
+    let mut request = agent.get(&path).header("Accept", "application/vnd.schemaregistry.v1+json");
+    if let Some(username) = input::<String>(inputs, "registry_username") {
+        let password = match input::<String>(inputs, "registry_password_secret") {
+            Some(name) => rt
+                .ok_or("runtime with secrets provider is required")?
+                .secret(&name)
+                .ok_or_else(|| format!("secret not found: {}", name))?,
+            None => String::new(),
+        };
+        let encoded = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+        request = request.header("Authorization", &format!("Basic {}", encoded));
+    }
+    let mut response = request.call().map_err(|e| e.to_string())?;
+
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+    if status >= 400 {
+        return Err(format!("schema registry returned {}: {}", status, body));
+    }
+    let body: Value = serde_json::from_str(&body).map_err(|e| format!("invalid schema registry response: {}", e))?;
+    let schema = body
+        .get("schema")
+        .and_then(Value::as_str)
+        .ok_or("registry response has no schema")?
+        .to_string();
+    if let Some(rt) = rt {
+        rt.cache.insert(cache_key, Value::String(schema.clone()));
+    }
+    Ok(schema)
+}
+
+/// Returns the decoded value and the schema id from the registry header, if any.
+fn decode(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<(Value, Option<u32>), String> {
+    let data: String = input(inputs, "data").ok_or("data is required")?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| format!("data is not valid base64: {}", e))?;
+    let registry_url: Option<String> = input(inputs, "registry_url");
+    // Registry lookups need the header; inline schemas only strip it on request.
+    let framed: bool = input(inputs, "framed").unwrap_or(registry_url.is_some());
+    let (schema_id, payload) = match framed {
+        true => avro::unframe(&bytes).map(|(id, payload)| (Some(id), payload))?,
+        false => (None, bytes.as_slice()),
+    };
+
+    let schema = match (inputs.get("schema"), registry_url, schema_id) {
+        (Some(schema), _, _) => inline_schema(schema)?,
+        (None, Some(url), Some(id)) => AvroSchema::parse_str(&registry_schema(inputs, runtime, &url, id)?)?,
+        (None, Some(_), None) => return Err("registry_url needs framed data".to_string()),
+        (None, None, _) => return Err("schema or registry_url is required".to_string()),
+    };
+    Ok((schema.decode(payload)?, schema_id))
+}
+
+impl NodeExecutor for ConvertAvroDecode {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match decode(&inputs, runtime) {
+            Ok((value, schema_id)) => {
+                output.insert("result".to_string(), value);
+                output.insert("schema_id".to_string(), serde_json::json!(schema_id));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new ConvertAvroDecode instance.
+pub fn create() -> ConvertAvroDecode {
+    ConvertAvroDecode::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves one registry response and reports the request line.
+    fn fake_registry(body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let head = String::from_utf8_lossy(&buf[..n]).to_string();
+            tx.send(head.lines().next().unwrap_or_default().to_string()).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        (url, rx)
+    }
+
+    #[test]
+    fn test_avro_decode() {
+        let executor = ConvertAvroDecode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert(
+            "schema".to_string(),
+            serde_json::json!(r#"{"type": "record", "name": "Point", "fields": [
+                {"name": "x", "type": "int"},
+                {"name": "label", "type": "string"}
+            ]}"#),
+        );
+        inputs.insert("data".to_string(), serde_json::json!("AQJh"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!({"x": -1, "label": "a"})));
+    }
+
+    #[test]
+    fn test_avro_decode_registry_cached() {
+        let (url, requests) = fake_registry(r#"{"schema":"\"long\""}"#);
+        let runtime = Runtime::new();
+        let executor = ConvertAvroDecode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("registry_url".to_string(), serde_json::json!(url));
+        inputs.insert("data".to_string(), serde_json::json!("AAAAAAcC"));
+
+        let result = executor.execute(inputs.clone(), Some(&runtime));
+        assert_eq!(requests.recv().unwrap(), "GET /schemas/ids/7 HTTP/1.1");
+        assert_eq!(result.get("result"), Some(&serde_json::json!(1)));
+        assert_eq!(result.get("schema_id"), Some(&serde_json::json!(7)));
+
+        // The fake registry only answers once.
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("result"), Some(&serde_json::json!(1)));
+    }
+
+    #[test]
+    fn test_avro_decode_unframed_registry_data() {
+        let executor = ConvertAvroDecode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("registry_url".to_string(), serde_json::json!("http://127.0.0.1:1"));
+        inputs.insert("data".to_string(), serde_json::json!("Ag=="));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(
+            result.get("error"),
+            Some(&serde_json::json!("data is not in schema-registry format (missing magic byte and schema id)"))
+        );
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "convert.avro_decode");
+        assert_eq!(executor.category, "convert");
+    }
+}
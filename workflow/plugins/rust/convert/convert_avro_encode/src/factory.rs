@@ -0,0 +1,8 @@
+//! Factory for ConvertAvroEncode plugin.
+
+use super::ConvertAvroEncode;
+
+/// Creates a new ConvertAvroEncode instance.
+pub fn create() -> ConvertAvroEncode {
+    ConvertAvroEncode::new()
+}
@@ -0,0 +1,246 @@
+//! Workflow plugin: encode JSON as Avro binary.
+//!
+//! The schema is given inline in `schema`, or looked up in a Confluent
+//! schema registry by `subject` (and `version`, `"latest"` by default). With
+//! a registry, or an explicit `schema_id`, the payload is framed with the
+//! registry header that Kafka consumers expect.
+
+use base64::Engine as _;
+use metabuilder_core::avro::{self, AvroSchema};
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// ConvertAvroEncode implements the NodeExecutor trait for Avro encoding.
+pub struct ConvertAvroEncode {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl ConvertAvroEncode {
+    /// Creates a new ConvertAvroEncode instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "convert.avro_encode",
+            category: "convert",
+            description: "Encode JSON as Avro binary",
+        }
+    }
+}
+
+impl Default for ConvertAvroEncode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+    inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Parses `schema`, which may be the schema itself or its JSON text.
+fn inline_schema(schema: &Value) -> Result<AvroSchema, String> {
+    match schema {
+        Value::String(text) if text.trim_start().starts_with(['{', '[']) => AvroSchema::parse_str(text),
+        other => AvroSchema::parse(other),
+    }
+}
+
+/// Fetches `{"id", "schema"}` for a subject version from the registry.
+fn registry_schema(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>, url: &str) -> Result<(u32, String), String> {
+    let subject: String = input(inputs, "subject").ok_or("subject is required with registry_url")?;
+    let version: Value = inputs.get("version").cloned().unwrap_or_else(|| Value::from("latest"));
+    let version = version.as_str().map(String::from).unwrap_or_else(|| version.to_string());
+    let rt = Runtime::from_any(runtime);
+
+    // Numbered versions never change, so only those are cached.
+    let cache_key = format!("convert.avro_encode:{}|{}|{}", url, subject, version);
+    let cached = rt.filter(|_| version != "latest").and_then(|rt| rt.cache.get(&cache_key));
+    let body = match cached {
+        Some(body) => body,
+        None => {
+            let path = format!("{}/subjects/{}/versions/{}", url.trim_end_matches('/'), subject, version);
+            let body = registry_get(inputs, rt, &path)?;
+            if let Some(rt) = rt.filter(|_| version != "latest") {
+                rt.cache.insert(cache_key, body.clone());
+            }
+            body
+        }
+    };
+
+    let id = body
+        .get("id")
+        .and_then(Value::as_u64)
+        .and_then(|id| u32::try_from(id).ok())
+        .ok_or("registry response has no schema id")?;
+    let schema = body.get("schema").and_then(Value::as_str).ok_or("registry response has no schema")?;
+    Ok((id, schema.to_string()))
+}
+
+fn registry_get(inputs: &HashMap<String, Value>, rt: Option<&Runtime>, url: &str) -> Result<Value, String> {
+    let timeout_ms: u64 = input(inputs, "timeout_ms").unwrap_or(30_000);
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .build()
+        .into();
+    let mut request = agent.get(url).header("Accept", "application/vnd.schemaregistry.v1+json");
+    if let Some(username) = input::<String>(inputs, "registry_username") {
+        let password = match input::<String>(inputs, "registry_password_secret") {
+            Some(name) => rt
+                .ok_or("runtime with secrets provider is required")?
+                .secret(&name)
+                .ok_or_else(|| format!("secret not found: {}", name))?,
+            None => String::new(),
+        };
+        let encoded = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+        request = request.header("Authorization", &format!("Basic {}", encoded));
+    }
+    let mut response = request.call().map_err(|e| e.to_string())?;
+
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+    if status >= 400 {
+        return Err(format!("schema registry returned {}: {}", status, body));
+    }
+    serde_json::from_str(&body).map_err(|e| format!("invalid schema registry response: {}", e))
+}
+
+/// Returns the encoded bytes and the schema id they were framed with.
+fn encode(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<(Vec<u8>, Option<u32>), String> {
+    let (schema, schema_id) = match (inputs.get("schema"), input::<String>(inputs, "registry_url")) {
+        (Some(schema), _) => (inline_schema(schema)?, input::<u32>(inputs, "schema_id")),
+        (None, Some(url)) => {
+            let (id, text) = registry_schema(inputs, runtime, &url)?;
+            (AvroSchema::parse_str(&text)?, Some(id))
+        }
+        (None, None) => return Err("schema or registry_url is required".to_string()),
+    };
+
+    let value = inputs.get("value").unwrap_or(&Value::Null);
+    let payload = schema.encode(value)?;
+    Ok(match schema_id {
+        Some(id) => (avro::frame(id, &payload), Some(id)),
+        None => (payload, None),
+    })
+}
+
+impl NodeExecutor for ConvertAvroEncode {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match encode(&inputs, runtime) {
+            Ok((bytes, schema_id)) => {
+                output.insert(
+                    "result".to_string(),
+                    serde_json::json!(base64::engine::general_purpose::STANDARD.encode(bytes)),
+                );
+                output.insert("schema_id".to_string(), serde_json::json!(schema_id));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new ConvertAvroEncode instance.
+pub fn create() -> ConvertAvroEncode {
+    ConvertAvroEncode::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves one registry response and reports the request line.
+    fn fake_registry(body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            let head = String::from_utf8_lossy(&buf[..n]).to_string();
+            tx.send(head.lines().next().unwrap_or_default().to_string()).unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        (url, rx)
+    }
+
+    #[test]
+    fn test_avro_encode() {
+        let executor = ConvertAvroEncode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert(
+            "schema".to_string(),
+            serde_json::json!({"type": "record", "name": "Point", "fields": [
+                {"name": "x", "type": "int"},
+                {"name": "label", "type": "string"}
+            ]}),
+        );
+        inputs.insert("value".to_string(), serde_json::json!({"x": -1, "label": "a"}));
+
+        let result = executor.execute(inputs, None);
+        // x=-1 (zigzag 1), "a"
+        assert_eq!(result.get("result"), Some(&serde_json::json!("AQJh")));
+        assert_eq!(result.get("schema_id"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_avro_encode_registry() {
+        let (url, requests) = fake_registry(r#"{"subject":"points-value","version":3,"id":7,"schema":"\"long\""}"#);
+        let executor = ConvertAvroEncode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("registry_url".to_string(), serde_json::json!(url));
+        inputs.insert("subject".to_string(), serde_json::json!("points-value"));
+        inputs.insert("value".to_string(), serde_json::json!(1));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), None);
+        assert_eq!(requests.recv().unwrap(), "GET /subjects/points-value/versions/latest HTTP/1.1");
+        // magic byte, id 7, long 1 (zigzag 2)
+        assert_eq!(result.get("result"), Some(&serde_json::json!("AAAAAAcC")));
+        assert_eq!(result.get("schema_id"), Some(&serde_json::json!(7)));
+    }
+
+    #[test]
+    fn test_avro_encode_mismatch() {
+        let executor = ConvertAvroEncode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("schema".to_string(), serde_json::json!("int"));
+        inputs.insert("value".to_string(), serde_json::json!("seven"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("expected int, got \"seven\"")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "convert.avro_encode");
+        assert_eq!(executor.category, "convert");
+    }
+}
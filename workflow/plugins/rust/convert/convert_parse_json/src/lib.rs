@@ -1,4 +1,11 @@
 //! Workflow plugin: parse JSON string.
+//!
+//! `mode: "json5"` relaxes the syntax to JSON5, accepting comments, trailing
+//! commas, single-quoted strings, and unquoted keys; the default `"strict"`
+//! mode accepts only standard JSON.
+//!
+//! On failure, `line` and `column` (1-based), the byte `offset`, and a
+//! `snippet` of the text around the error are emitted next to `error`.
 
 use serde_json::Value;
 use std::any::Any;
@@ -34,22 +41,92 @@ impl Default for ConvertParseJson {
     }
 }
 
+/// Characters of context kept on each side of the error in `snippet`.
+const SNIPPET_RADIUS: usize = 20;
+
+/// Where parsing failed.
+struct Failure {
+    message: String,
+    line: usize,
+    column: usize,
+}
+
+impl Failure {
+    fn strict(e: serde_json::Error) -> Self {
+        Failure {
+            message: e.to_string(),
+            line: e.line(),
+            column: e.column(),
+        }
+    }
+
+    fn json5(e: json5::Error) -> Self {
+        let json5::Error::Message { msg, location } = e;
+        let (line, column) = location.map_or((0, 0), |l| (l.line, l.column));
+        Failure {
+            message: msg,
+            line,
+            column,
+        }
+    }
+}
+
+/// Byte offset of 1-based `line` and `column`, where the column counts
+/// characters; serde_json's byte columns agree for ASCII text.
+fn offset(text: &str, line: usize, column: usize) -> usize {
+    let start: usize = text.split_inclusive('\n').take(line.saturating_sub(1)).map(str::len).sum();
+    let rest = &text[start.min(text.len())..];
+    let within = rest
+        .char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(rest.len(), |(i, _)| i);
+    start.min(text.len()) + within
+}
+
+/// Up to `SNIPPET_RADIUS` characters either side of `offset`.
+fn snippet(text: &str, offset: usize) -> String {
+    let before = text[..offset].char_indices().rev().nth(SNIPPET_RADIUS - 1).map_or(0, |(i, _)| i);
+    let after = text[offset..]
+        .char_indices()
+        .nth(SNIPPET_RADIUS)
+        .map_or(text.len(), |(i, _)| offset + i);
+    text[before..after].to_string()
+}
+
 impl NodeExecutor for ConvertParseJson {
     fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
         let string: String = inputs
             .get("string")
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or_default();
+        let mode = inputs.get("mode").and_then(Value::as_str).unwrap_or("strict");
 
         let mut output = HashMap::new();
 
-        match serde_json::from_str::<Value>(&string) {
+        let parsed = match mode {
+            "strict" => serde_json::from_str::<Value>(&string).map_err(Failure::strict),
+            "json5" => json5::from_str::<Value>(&string).map_err(Failure::json5),
+            other => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert(
+                    "error".to_string(),
+                    serde_json::json!(format!("unknown mode: {} (expected strict or json5)", other)),
+                );
+                return output;
+            }
+        };
+        match parsed {
             Ok(value) => {
                 output.insert("result".to_string(), value);
             }
-            Err(e) => {
+            Err(failure) => {
+                let offset = offset(&string, failure.line, failure.column);
                 output.insert("result".to_string(), Value::Null);
-                output.insert("error".to_string(), serde_json::json!(e.to_string()));
+                output.insert("error".to_string(), serde_json::json!(failure.message));
+                output.insert("line".to_string(), serde_json::json!(failure.line));
+                output.insert("column".to_string(), serde_json::json!(failure.column));
+                output.insert("offset".to_string(), serde_json::json!(offset));
+                output.insert("snippet".to_string(), serde_json::json!(snippet(&string, offset)));
             }
         }
 
@@ -84,7 +161,45 @@ mod tests {
 
         let result = executor.execute(inputs, None);
         assert_eq!(result.get("result"), Some(&Value::Null));
-        assert!(result.get("error").is_some());
+        assert!(result.contains_key("error"));
+    }
+
+    #[test]
+    fn test_parse_json_error_details() {
+        let executor = ConvertParseJson::new();
+        let text = "{\n  \"name\": \"svc\",\n  \"ports\": [80 443]\n}";
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!(text));
+
+        let result = executor.execute(inputs.clone(), None);
+        assert_eq!(result.get("line"), Some(&serde_json::json!(3)));
+        assert_eq!(result.get("column"), Some(&serde_json::json!(16)));
+        assert_eq!(result.get("offset"), Some(&serde_json::json!(34)));
+        assert_eq!(&text[34..], "443]\n}");
+        assert_eq!(result.get("snippet"), Some(&serde_json::json!("vc\",\n  \"ports\": [80 443]\n}")));
+
+        inputs.insert("mode".to_string(), serde_json::json!("json5"));
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("line"), Some(&serde_json::json!(3)));
+        // The JSON5 parser reports the start of the array it could not finish.
+        assert_eq!(result.get("offset"), Some(&serde_json::json!(31)));
+    }
+
+    #[test]
+    fn test_parse_json5() {
+        let executor = ConvertParseJson::new();
+        let text = "{\n  // retries\n  name: 'svc',\n  ports: [80, 443,],\n  ratio: .5,\n}";
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!(text));
+        inputs.insert("mode".to_string(), serde_json::json!("json5"));
+
+        let result = executor.execute(inputs.clone(), None);
+        assert_eq!(result.get("error"), None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!({"name": "svc", "ports": [80, 443], "ratio": 0.5})));
+
+        inputs.remove("mode");
+        let result = executor.execute(inputs, None);
+        assert!(result.contains_key("error"));
     }
 
     #[test]
@@ -0,0 +1,8 @@
+//! Factory for ConvertProtoDecode plugin.
+
+use super::ConvertProtoDecode;
+
+/// Creates a new ConvertProtoDecode instance.
+pub fn create() -> ConvertProtoDecode {
+    ConvertProtoDecode::new()
+}
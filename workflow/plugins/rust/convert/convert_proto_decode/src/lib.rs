@@ -0,0 +1,209 @@
+//! Workflow plugin: decode a protobuf message to JSON.
+//!
+//! Message types come from a compiled descriptor set (`protoc
+//! --descriptor_set_out --include_imports`), given as base64 in
+//! `descriptor_set` or as a file in `descriptor_set_path`.
+
+use base64::Engine as _;
+use prost_reflect::{DescriptorPool, DynamicMessage, SerializeOptions};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// ConvertProtoDecode implements the NodeExecutor trait for protobuf decoding.
+pub struct ConvertProtoDecode {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl ConvertProtoDecode {
+    /// Creates a new ConvertProtoDecode instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "convert.proto_decode",
+            category: "convert",
+            description: "Decode a protobuf message to JSON",
+        }
+    }
+}
+
+impl Default for ConvertProtoDecode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Loads the descriptor pool from `descriptor_set` (base64) or `descriptor_set_path`.
+fn descriptor_pool(inputs: &HashMap<String, Value>) -> Result<DescriptorPool, String> {
+    let bytes = match (
+        inputs.get("descriptor_set").and_then(Value::as_str),
+        inputs.get("descriptor_set_path").and_then(Value::as_str),
+    ) {
+        (Some(text), _) => base64::engine::general_purpose::STANDARD
+            .decode(text.trim())
+            .map_err(|e| format!("descriptor_set is not valid base64: {}", e))?,
+        (None, Some(path)) => std::fs::read(path).map_err(|e| format!("cannot read {}: {}", path, e))?,
+        (None, None) => return Err("descriptor_set or descriptor_set_path is required".to_string()),
+    };
+    DescriptorPool::decode(bytes.as_slice()).map_err(|e| format!("invalid descriptor set: {}", e))
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>, String> {
+    if !text.len().is_multiple_of(2) {
+        return Err("hex data has an odd number of digits".to_string());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|_| format!("invalid hex at offset {}", i)))
+        .collect()
+}
+
+fn decode(inputs: &HashMap<String, Value>) -> Result<Value, String> {
+    let pool = descriptor_pool(inputs)?;
+    let name = inputs
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or("message is required")?;
+    let descriptor = pool
+        .get_message_by_name(name)
+        .ok_or_else(|| format!("unknown message type: {}", name))?;
+    let data = inputs.get("data").and_then(Value::as_str).ok_or("data is required")?;
+    let bytes = match inputs.get("encoding").and_then(Value::as_str).unwrap_or("base64") {
+        "base64" => base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map_err(|e| format!("data is not valid base64: {}", e))?,
+        "hex" => decode_hex(data.trim())?,
+        other => return Err(format!("unknown encoding: {} (expected base64 or hex)", other)),
+    };
+
+    let message = DynamicMessage::decode(descriptor, bytes.as_slice()).map_err(|e| format!("cannot decode {}: {}", name, e))?;
+    let emit_defaults = inputs.get("emit_defaults").and_then(Value::as_bool).unwrap_or(false);
+    let proto_names = inputs.get("proto_field_names").and_then(Value::as_bool).unwrap_or(false);
+    let options = SerializeOptions::new()
+        .skip_default_fields(!emit_defaults)
+        .use_proto_field_name(proto_names);
+    message
+        .serialize_with_options(serde_json::value::Serializer, &options)
+        .map_err(|e| e.to_string())
+}
+
+impl NodeExecutor for ConvertProtoDecode {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match decode(&inputs) {
+            Ok(value) => {
+                output.insert("result".to_string(), value);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new ConvertProtoDecode instance.
+pub fn create() -> ConvertProtoDecode {
+    ConvertProtoDecode::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_reflect::prost::Message;
+    use prost_reflect::prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+
+    /// `package test; message Person { string name = 1; int32 id = 2; repeated string tags = 3; }`
+    fn descriptor_set() -> String {
+        let field = |name: &str, number: i32, kind: Type, label: Label| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            r#type: Some(kind as i32),
+            label: Some(label as i32),
+            json_name: Some(name.to_string()),
+            ..Default::default()
+        };
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("person.proto".to_string()),
+                package: Some("test".to_string()),
+                syntax: Some("proto3".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Person".to_string()),
+                    field: vec![
+                        field("name", 1, Type::String, Label::Optional),
+                        field("id", 2, Type::Int32, Label::Optional),
+                        field("tags", 3, Type::String, Label::Repeated),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        base64::engine::general_purpose::STANDARD.encode(set.encode_to_vec())
+    }
+
+    #[test]
+    fn test_proto_decode() {
+        let executor = ConvertProtoDecode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("descriptor_set".to_string(), serde_json::json!(descriptor_set()));
+        inputs.insert("message".to_string(), serde_json::json!("test.Person"));
+        inputs.insert("data".to_string(), serde_json::json!("0a0341646110071a0161"));
+        inputs.insert("encoding".to_string(), serde_json::json!("hex"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(
+            result.get("result"),
+            Some(&serde_json::json!({"name": "Ada", "id": 7, "tags": ["a"]}))
+        );
+    }
+
+    #[test]
+    fn test_proto_decode_emit_defaults() {
+        let executor = ConvertProtoDecode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("descriptor_set".to_string(), serde_json::json!(descriptor_set()));
+        inputs.insert("message".to_string(), serde_json::json!("test.Person"));
+        inputs.insert("data".to_string(), serde_json::json!(""));
+        inputs.insert("emit_defaults".to_string(), serde_json::json!(true));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(
+            result.get("result"),
+            Some(&serde_json::json!({"name": "", "id": 0, "tags": []}))
+        );
+    }
+
+    #[test]
+    fn test_proto_decode_unknown_message() {
+        let executor = ConvertProtoDecode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("descriptor_set".to_string(), serde_json::json!(descriptor_set()));
+        inputs.insert("message".to_string(), serde_json::json!("test.Nope"));
+        inputs.insert("data".to_string(), serde_json::json!(""));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("unknown message type: test.Nope")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "convert.proto_decode");
+        assert_eq!(executor.category, "convert");
+    }
+}
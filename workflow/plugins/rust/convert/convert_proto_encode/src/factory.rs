@@ -0,0 +1,8 @@
+//! Factory for ConvertProtoEncode plugin.
+
+use super::ConvertProtoEncode;
+
+/// Creates a new ConvertProtoEncode instance.
+pub fn create() -> ConvertProtoEncode {
+    ConvertProtoEncode::new()
+}
@@ -0,0 +1,173 @@
+//! Workflow plugin: encode JSON as a protobuf message.
+//!
+//! Message types come from a compiled descriptor set (`protoc
+//! --descriptor_set_out --include_imports`), given as base64 in
+//! `descriptor_set` or as a file in `descriptor_set_path`.
+
+use base64::Engine as _;
+use prost_reflect::prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// ConvertProtoEncode implements the NodeExecutor trait for protobuf encoding.
+pub struct ConvertProtoEncode {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl ConvertProtoEncode {
+    /// Creates a new ConvertProtoEncode instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "convert.proto_encode",
+            category: "convert",
+            description: "Encode JSON as a protobuf message",
+        }
+    }
+}
+
+impl Default for ConvertProtoEncode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Loads the descriptor pool from `descriptor_set` (base64) or `descriptor_set_path`.
+fn descriptor_pool(inputs: &HashMap<String, Value>) -> Result<DescriptorPool, String> {
+    let bytes = match (
+        inputs.get("descriptor_set").and_then(Value::as_str),
+        inputs.get("descriptor_set_path").and_then(Value::as_str),
+    ) {
+        (Some(text), _) => base64::engine::general_purpose::STANDARD
+            .decode(text.trim())
+            .map_err(|e| format!("descriptor_set is not valid base64: {}", e))?,
+        (None, Some(path)) => std::fs::read(path).map_err(|e| format!("cannot read {}: {}", path, e))?,
+        (None, None) => return Err("descriptor_set or descriptor_set_path is required".to_string()),
+    };
+    DescriptorPool::decode(bytes.as_slice()).map_err(|e| format!("invalid descriptor set: {}", e))
+}
+
+fn encode(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let pool = descriptor_pool(inputs)?;
+    let name = inputs
+        .get("message")
+        .and_then(Value::as_str)
+        .ok_or("message is required")?;
+    let descriptor = pool
+        .get_message_by_name(name)
+        .ok_or_else(|| format!("unknown message type: {}", name))?;
+    let value = inputs.get("value").cloned().unwrap_or_else(|| serde_json::json!({}));
+
+    let message = DynamicMessage::deserialize(descriptor, value).map_err(|e| format!("value does not match {}: {}", name, e))?;
+    let bytes = message.encode_to_vec();
+
+    match inputs.get("encoding").and_then(Value::as_str).unwrap_or("base64") {
+        "base64" => Ok(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        "hex" => Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect()),
+        other => Err(format!("unknown encoding: {} (expected base64 or hex)", other)),
+    }
+}
+
+impl NodeExecutor for ConvertProtoEncode {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match encode(&inputs) {
+            Ok(encoded) => {
+                output.insert("result".to_string(), serde_json::json!(encoded));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new ConvertProtoEncode instance.
+pub fn create() -> ConvertProtoEncode {
+    ConvertProtoEncode::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_reflect::prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+
+    /// `package test; message Person { string name = 1; int32 id = 2; repeated string tags = 3; }`
+    fn descriptor_set() -> String {
+        let field = |name: &str, number: i32, kind: Type, label: Label| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            r#type: Some(kind as i32),
+            label: Some(label as i32),
+            json_name: Some(name.to_string()),
+            ..Default::default()
+        };
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("person.proto".to_string()),
+                package: Some("test".to_string()),
+                syntax: Some("proto3".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Person".to_string()),
+                    field: vec![
+                        field("name", 1, Type::String, Label::Optional),
+                        field("id", 2, Type::Int32, Label::Optional),
+                        field("tags", 3, Type::String, Label::Repeated),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        base64::engine::general_purpose::STANDARD.encode(set.encode_to_vec())
+    }
+
+    #[test]
+    fn test_proto_encode() {
+        let executor = ConvertProtoEncode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("descriptor_set".to_string(), serde_json::json!(descriptor_set()));
+        inputs.insert("message".to_string(), serde_json::json!("test.Person"));
+        inputs.insert("value".to_string(), serde_json::json!({"name": "Ada", "id": 7}));
+        inputs.insert("encoding".to_string(), serde_json::json!("hex"));
+
+        let result = executor.execute(inputs, None);
+        // field 1 (len 3) "Ada", field 2 varint 7
+        assert_eq!(result.get("result"), Some(&serde_json::json!("0a034164611007")));
+    }
+
+    #[test]
+    fn test_proto_encode_unknown_field() {
+        let executor = ConvertProtoEncode::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("descriptor_set".to_string(), serde_json::json!(descriptor_set()));
+        inputs.insert("message".to_string(), serde_json::json!("test.Person"));
+        inputs.insert("value".to_string(), serde_json::json!({"nickname": "A"}));
+
+        let result = executor.execute(inputs, None);
+        assert!(result.get("error").unwrap().as_str().unwrap().starts_with("value does not match test.Person"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "convert.proto_encode");
+        assert_eq!(executor.category, "convert");
+    }
+}
@@ -1,6 +1,17 @@
 //! Workflow plugin: convert to JSON string.
+//!
+//! With `canonical: true` the output is RFC 8785 canonical JSON (sorted keys,
+//! normalized numbers, no whitespace), suitable for signing; `pretty` is
+//! ignored in that mode.
+//!
+//! Pretty output is configured with `indent` (a number of spaces, or `"tab"`;
+//! setting it implies `pretty`), `sort_keys`, and `ascii`, which escapes
+//! every non-ASCII character as `\uXXXX`.
 
-use serde_json::Value;
+use metabuilder_core::canonical;
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::{Map, Value};
 use std::any::Any;
 use std::collections::HashMap;
 
@@ -34,22 +45,94 @@ impl Default for ConvertToJson {
     }
 }
 
-impl NodeExecutor for ConvertToJson {
-    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
-        let value = inputs.get("value").unwrap_or(&Value::Null);
-        let pretty: bool = inputs
-            .get("pretty")
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
-            .unwrap_or(false);
-
-        let result = if pretty {
-            serde_json::to_string_pretty(value).unwrap_or_default()
+/// Returns the indentation unit for `indent`: a number of spaces or `"tab"`.
+fn indentation(indent: &Value) -> Result<String, String> {
+    match indent {
+        Value::Number(n) => match n.as_u64() {
+            Some(width) if width <= 16 => Ok(" ".repeat(width as usize)),
+            _ => Err(format!("indent must be between 0 and 16, got {}", n)),
+        },
+        Value::String(s) if s == "tab" || s == "\t" => Ok("\t".to_string()),
+        other => Err(format!("indent must be a number of spaces or \"tab\", got {}", other)),
+    }
+}
+
+/// Recursively sorts object keys, whatever map order serde_json was built with.
+fn sort_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            Value::Object(entries.into_iter().map(|(k, v)| (k.clone(), sort_keys(v))).collect::<Map<_, _>>())
+        }
+        Value::Array(items) => Value::Array(items.iter().map(sort_keys).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Escapes non-ASCII characters, which JSON only allows inside strings.
+fn escape_non_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii() {
+            out.push(c);
         } else {
-            serde_json::to_string(value).unwrap_or_default()
-        };
+            let mut units = [0u16; 2];
+            for unit in c.encode_utf16(&mut units) {
+                out.push_str(&format!("\\u{:04x}", unit));
+            }
+        }
+    }
+    out
+}
+
+fn to_json(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let value = inputs.get("value").unwrap_or(&Value::Null);
+    let flag = |name: &str| inputs.get(name).and_then(Value::as_bool).unwrap_or(false);
+    if flag("canonical") {
+        return Ok(canonical::to_string(value));
+    }
+
+    let sorted;
+    let value = if flag("sort_keys") {
+        sorted = sort_keys(value);
+        &sorted
+    } else {
+        value
+    };
+    let indent = match inputs.get("indent").filter(|v| !v.is_null()) {
+        Some(indent) => Some(indentation(indent)?),
+        None if flag("pretty") => Some("  ".to_string()),
+        None => None,
+    };
 
+    let result = match indent {
+        Some(indent) => {
+            let mut buffer = Vec::new();
+            let mut serializer =
+                serde_json::Serializer::with_formatter(&mut buffer, PrettyFormatter::with_indent(indent.as_bytes()));
+            value.serialize(&mut serializer).map_err(|e| e.to_string())?;
+            String::from_utf8(buffer).map_err(|e| e.to_string())?
+        }
+        None => serde_json::to_string(value).map_err(|e| e.to_string())?,
+    };
+    Ok(if flag("ascii") { escape_non_ascii(&result) } else { result })
+}
+
+impl NodeExecutor for ConvertToJson {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
         let mut output = HashMap::new();
-        output.insert("result".to_string(), serde_json::json!(result));
+
+        match to_json(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
         output
     }
 }
@@ -85,6 +168,46 @@ mod tests {
         assert!(json_str.contains('\n'));
     }
 
+    #[test]
+    fn test_to_json_canonical() {
+        let executor = ConvertToJson::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), serde_json::json!({"b": [1.0, 1e21], "a": "\u{20ac}"}));
+        inputs.insert("canonical".to_string(), serde_json::json!(true));
+        inputs.insert("pretty".to_string(), serde_json::json!(true));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!("{\"a\":\"\u{20ac}\",\"b\":[1,1e+21]}")));
+    }
+
+    #[test]
+    fn test_to_json_indent_options() {
+        let executor = ConvertToJson::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), serde_json::json!({"b": ["\u{e9}\u{1f600}"], "a": 1}));
+        inputs.insert("indent".to_string(), serde_json::json!("tab"));
+        inputs.insert("sort_keys".to_string(), serde_json::json!(true));
+        inputs.insert("ascii".to_string(), serde_json::json!(true));
+
+        let result = executor.execute(inputs.clone(), None);
+        assert_eq!(
+            result.get("result"),
+            Some(&serde_json::json!("{\n\t\"a\": 1,\n\t\"b\": [\n\t\t\"\\u00e9\\ud83d\\ude00\"\n\t]\n}"))
+        );
+
+        inputs.insert("indent".to_string(), serde_json::json!(4));
+        inputs.insert("ascii".to_string(), serde_json::json!(false));
+        let result = executor.execute(inputs.clone(), None);
+        assert!(result["result"].as_str().unwrap().starts_with("{\n    \"a\": 1"));
+
+        inputs.insert("indent".to_string(), serde_json::json!("wide"));
+        let result = executor.execute(inputs, None);
+        assert_eq!(
+            result.get("error"),
+            Some(&serde_json::json!("indent must be a number of spaces or \"tab\", got \"wide\""))
+        );
+    }
+
     #[test]
     fn test_factory() {
         let executor = create();
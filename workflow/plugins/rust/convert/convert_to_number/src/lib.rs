@@ -41,7 +41,8 @@ impl NodeExecutor for ConvertToNumber {
         let result = match value {
             Value::Number(n) => n.as_f64().unwrap_or(0.0),
             Value::String(s) => s.parse::<f64>().unwrap_or(0.0),
-            Value::Bool(b) => if *b { 1.0 } else { 0.0 },
+            Value::Bool(true) => 1.0,
+            Value::Bool(false) => 0.0,
             _ => 0.0,
         };
 
@@ -0,0 +1,168 @@
+//! HTTP authentication profiles.
+//!
+//! A profile is a secret holding a JSON object that says how to authenticate
+//! to one service, so a workflow names the profile (`auth: "github"`) and
+//! the token never appears in it:
+//!
+//! - `{"type": "bearer", "token": "..."}` sends `Authorization: Bearer ...`
+//! - `{"type": "basic", "username": "...", "password": "..."}` sends
+//!   `Authorization: Basic ...`
+//! - `{"type": "api_key", "key": "...", "header": "X-API-Key"}` sends the key
+//!   in a header (`X-API-Key` unless named), or with `"query": "api_key"`
+//!   instead of `header`, as a query parameter.
+
+use crate::runtime::Runtime;
+use base64::Engine as _;
+use serde_json::Value;
+
+/// Where an API key is sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyPlacement {
+    /// In the header of this name
+    Header(String),
+    /// In the query parameter of this name
+    Query(String),
+}
+
+/// Credentials for one service, read from a secret.
+#[derive(Clone, PartialEq, Eq)]
+pub enum AuthProfile {
+    Bearer { token: String },
+    Basic { username: String, password: String },
+    ApiKey { key: String, placement: KeyPlacement },
+}
+
+impl std::fmt::Debug for AuthProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Credentials stay out of logs.
+        match self {
+            AuthProfile::Bearer { .. } => f.write_str("AuthProfile::Bearer"),
+            AuthProfile::Basic { username, .. } => write!(f, "AuthProfile::Basic({})", username),
+            AuthProfile::ApiKey { placement, .. } => write!(f, "AuthProfile::ApiKey({:?})", placement),
+        }
+    }
+}
+
+fn field(object: &serde_json::Map<String, Value>, name: &str) -> Result<String, String> {
+    object
+        .get(name)
+        .and_then(Value::as_str)
+        .map(String::from)
+        .ok_or_else(|| format!("{} is required", name))
+}
+
+impl AuthProfile {
+    /// Parses a profile from its JSON `source`.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(source).map_err(|_| "profile must be a JSON object".to_string())?;
+        let object = value.as_object().ok_or("profile must be a JSON object")?;
+        match object.get("type").and_then(Value::as_str) {
+            Some("bearer") => Ok(AuthProfile::Bearer {
+                token: field(object, "token")?,
+            }),
+            Some("basic") => Ok(AuthProfile::Basic {
+                username: field(object, "username")?,
+                password: field(object, "password")?,
+            }),
+            Some("api_key") => {
+                let placement = match (object.get("header"), object.get("query")) {
+                    (Some(_), Some(_)) => return Err("header and query cannot both be given".to_string()),
+                    (None, Some(_)) => KeyPlacement::Query(field(object, "query")?),
+                    (Some(_), None) => KeyPlacement::Header(field(object, "header")?),
+                    (None, None) => KeyPlacement::Header("X-API-Key".to_string()),
+                };
+                Ok(AuthProfile::ApiKey {
+                    key: field(object, "key")?,
+                    placement,
+                })
+            }
+            Some(other) => Err(format!("unknown type {}", other)),
+            None => Err("type is required".to_string()),
+        }
+    }
+
+    /// Reads the profile stored in the secret `name`.
+    pub fn resolve(runtime: &Runtime, name: &str) -> Result<Self, String> {
+        let source = runtime
+            .secret(name)
+            .ok_or_else(|| format!("auth profile not found: {}", name))?;
+        Self::parse(&source).map_err(|e| format!("invalid auth profile {}: {}", name, e))
+    }
+
+    /// The header this profile adds, if it authenticates by header.
+    pub fn header(&self) -> Option<(String, String)> {
+        match self {
+            AuthProfile::Bearer { token } => Some(("Authorization".to_string(), format!("Bearer {}", token))),
+            AuthProfile::Basic { username, password } => {
+                let credentials = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+                Some(("Authorization".to_string(), format!("Basic {}", credentials)))
+            }
+            AuthProfile::ApiKey {
+                key,
+                placement: KeyPlacement::Header(name),
+            } => Some((name.clone(), key.clone())),
+            AuthProfile::ApiKey { .. } => None,
+        }
+    }
+
+    /// The query parameter this profile adds, if it authenticates by query.
+    pub fn query(&self) -> Option<(String, String)> {
+        match self {
+            AuthProfile::ApiKey {
+                key,
+                placement: KeyPlacement::Query(name),
+            } => Some((name.clone(), key.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::MemorySecrets;
+
+    #[test]
+    fn test_profiles() {
+        let bearer = AuthProfile::parse(r#"{"type": "bearer", "token": "t0k"}"#).unwrap();
+        assert_eq!(bearer.header(), Some(("Authorization".to_string(), "Bearer t0k".to_string())));
+        assert_eq!(bearer.query(), None);
+
+        let basic = AuthProfile::parse(r#"{"type": "basic", "username": "ada", "password": "pw"}"#).unwrap();
+        assert_eq!(basic.header().unwrap().1, "Basic YWRhOnB3");
+
+        let header = AuthProfile::parse(r#"{"type": "api_key", "key": "k"}"#).unwrap();
+        assert_eq!(header.header(), Some(("X-API-Key".to_string(), "k".to_string())));
+
+        let query = AuthProfile::parse(r#"{"type": "api_key", "key": "k", "query": "api_key"}"#).unwrap();
+        assert_eq!(query.header(), None);
+        assert_eq!(query.query(), Some(("api_key".to_string(), "k".to_string())));
+        assert_eq!(format!("{:?}", bearer), "AuthProfile::Bearer");
+    }
+
+    #[test]
+    fn test_invalid_profiles() {
+        assert_eq!(AuthProfile::parse("token").unwrap_err(), "profile must be a JSON object");
+        assert_eq!(AuthProfile::parse(r#"{"token": "t"}"#).unwrap_err(), "type is required");
+        assert_eq!(AuthProfile::parse(r#"{"type": "digest"}"#).unwrap_err(), "unknown type digest");
+        assert_eq!(AuthProfile::parse(r#"{"type": "basic", "username": "a"}"#).unwrap_err(), "password is required");
+        assert_eq!(
+            AuthProfile::parse(r#"{"type": "api_key", "key": "k", "header": "H", "query": "q"}"#).unwrap_err(),
+            "header and query cannot both be given"
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("github", r#"{"type": "bearer", "token": "t"}"#);
+        secrets.insert("broken", "{}");
+        let runtime = Runtime::with_secrets(secrets);
+        assert!(AuthProfile::resolve(&runtime, "github").is_ok());
+        assert_eq!(AuthProfile::resolve(&runtime, "gitlab").unwrap_err(), "auth profile not found: gitlab");
+        assert_eq!(
+            AuthProfile::resolve(&runtime, "broken").unwrap_err(),
+            "invalid auth profile broken: type is required"
+        );
+    }
+}
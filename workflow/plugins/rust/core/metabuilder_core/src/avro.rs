@@ -0,0 +1,553 @@
+//! Avro binary encoding driven by a JSON schema.
+//!
+//! Values map to JSON the way nodes expect to handle them: records and maps
+//! are objects, enums are their symbol, `bytes` and `fixed` are base64
+//! strings, and unions are the plain value of whichever branch matches (the
+//! first one, when several do). Logical types are encoded as their
+//! underlying type.
+//!
+//! Also handles the Confluent schema-registry framing used on Kafka topics:
+//! a zero byte, then the big-endian schema id, then the Avro payload.
+
+use base64::Engine as _;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// A parsed Avro schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Array(Box<Schema>),
+    Map(Box<Schema>),
+    Union(Vec<Schema>),
+    Record { name: String, fields: Vec<Field> },
+    Enum { name: String, symbols: Vec<String> },
+    Fixed { name: String, size: usize },
+    /// Reference to a named type defined elsewhere in the schema
+    Named(String),
+}
+
+/// A record field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub schema: Schema,
+    /// Used when encoding a value that lacks the field
+    pub default: Option<Value>,
+}
+
+/// A schema together with the named types it defines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvroSchema {
+    pub root: Schema,
+    named: HashMap<String, Schema>,
+}
+
+impl AvroSchema {
+    /// Parses a schema from its JSON form.
+    pub fn parse(json: &Value) -> Result<Self, String> {
+        let mut named = HashMap::new();
+        let root = parse_schema(json, None, &mut named)?;
+        Ok(AvroSchema { root, named })
+    }
+
+    /// Parses a schema from JSON text, as returned by a schema registry.
+    pub fn parse_str(text: &str) -> Result<Self, String> {
+        let json: Value = serde_json::from_str(text).map_err(|e| format!("schema is not valid JSON: {}", e))?;
+        Self::parse(&json)
+    }
+
+    /// Encodes `value` in Avro binary form.
+    pub fn encode(&self, value: &Value) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        self.encode_value(&self.root, value, &mut out)?;
+        Ok(out)
+    }
+
+    /// Decodes one Avro binary value. Trailing bytes are an error.
+    pub fn decode(&self, bytes: &[u8]) -> Result<Value, String> {
+        let mut reader = Reader { bytes, position: 0 };
+        let value = self.decode_value(&self.root, &mut reader)?;
+        if reader.position != bytes.len() {
+            return Err(format!("{} trailing bytes after the value", bytes.len() - reader.position));
+        }
+        Ok(value)
+    }
+
+    fn resolve<'a>(&'a self, schema: &'a Schema) -> Result<&'a Schema, String> {
+        match schema {
+            Schema::Named(name) => self.named.get(name).ok_or_else(|| format!("unknown type: {}", name)),
+            other => Ok(other),
+        }
+    }
+
+    /// Whether `value` can be encoded as `schema`, used to pick a union branch.
+    fn matches(&self, schema: &Schema, value: &Value) -> bool {
+        let Ok(schema) = self.resolve(schema) else { return false };
+        match (schema, value) {
+            (Schema::Null, Value::Null) => true,
+            (Schema::Boolean, Value::Bool(_)) => true,
+            (Schema::Int | Schema::Long, Value::Number(n)) => n.is_i64(),
+            (Schema::Float | Schema::Double, Value::Number(_)) => true,
+            (Schema::Bytes | Schema::String | Schema::Fixed { .. }, Value::String(_)) => true,
+            (Schema::Enum { symbols, .. }, Value::String(s)) => symbols.contains(s),
+            (Schema::Array(_), Value::Array(_)) => true,
+            (Schema::Map(_), Value::Object(_)) => true,
+            (Schema::Record { fields, .. }, Value::Object(object)) => object
+                .keys()
+                .all(|key| fields.iter().any(|f| &f.name == key)),
+            _ => false,
+        }
+    }
+
+    fn encode_value(&self, schema: &Schema, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+        let mismatch = |expected: &str| format!("expected {}, got {}", expected, value);
+        match self.resolve(schema)? {
+            Schema::Null => value.is_null().then_some(()).ok_or_else(|| mismatch("null")),
+            Schema::Boolean => {
+                out.push(value.as_bool().ok_or_else(|| mismatch("boolean"))? as u8);
+                Ok(())
+            }
+            Schema::Int => {
+                let n = value.as_i64().and_then(|n| i32::try_from(n).ok()).ok_or_else(|| mismatch("int"))?;
+                write_long(n as i64, out);
+                Ok(())
+            }
+            Schema::Long => {
+                write_long(value.as_i64().ok_or_else(|| mismatch("long"))?, out);
+                Ok(())
+            }
+            Schema::Float => {
+                out.extend_from_slice(&(value.as_f64().ok_or_else(|| mismatch("float"))? as f32).to_le_bytes());
+                Ok(())
+            }
+            Schema::Double => {
+                out.extend_from_slice(&value.as_f64().ok_or_else(|| mismatch("double"))?.to_le_bytes());
+                Ok(())
+            }
+            Schema::Bytes => {
+                let bytes = decode_base64(value.as_str().ok_or_else(|| mismatch("base64 bytes"))?)?;
+                write_long(bytes.len() as i64, out);
+                out.extend_from_slice(&bytes);
+                Ok(())
+            }
+            Schema::String => {
+                let s = value.as_str().ok_or_else(|| mismatch("string"))?;
+                write_long(s.len() as i64, out);
+                out.extend_from_slice(s.as_bytes());
+                Ok(())
+            }
+            Schema::Array(items) => {
+                let values = value.as_array().ok_or_else(|| mismatch("array"))?;
+                if !values.is_empty() {
+                    write_long(values.len() as i64, out);
+                    for item in values {
+                        self.encode_value(items, item, out)?;
+                    }
+                }
+                write_long(0, out);
+                Ok(())
+            }
+            Schema::Map(values) => {
+                let object = value.as_object().ok_or_else(|| mismatch("map"))?;
+                if !object.is_empty() {
+                    write_long(object.len() as i64, out);
+                    for (key, item) in object {
+                        write_long(key.len() as i64, out);
+                        out.extend_from_slice(key.as_bytes());
+                        self.encode_value(values, item, out)?;
+                    }
+                }
+                write_long(0, out);
+                Ok(())
+            }
+            Schema::Union(branches) => {
+                let index = branches
+                    .iter()
+                    .position(|branch| self.matches(branch, value))
+                    .ok_or_else(|| format!("{} matches no branch of the union", value))?;
+                write_long(index as i64, out);
+                self.encode_value(&branches[index], value, out)
+            }
+            Schema::Record { name, fields } => {
+                let object = value.as_object().ok_or_else(|| mismatch(name))?;
+                for field in fields {
+                    let item = object
+                        .get(&field.name)
+                        .or(field.default.as_ref())
+                        .ok_or_else(|| format!("{} is missing field {}", name, field.name))?;
+                    self.encode_value(&field.schema, item, out)
+                        .map_err(|e| format!("{}.{}: {}", name, field.name, e))?;
+                }
+                Ok(())
+            }
+            Schema::Enum { name, symbols } => {
+                let symbol = value.as_str().ok_or_else(|| mismatch(name))?;
+                let index = symbols
+                    .iter()
+                    .position(|s| s == symbol)
+                    .ok_or_else(|| format!("{} is not a symbol of {}", symbol, name))?;
+                write_long(index as i64, out);
+                Ok(())
+            }
+            Schema::Fixed { name, size } => {
+                let bytes = decode_base64(value.as_str().ok_or_else(|| mismatch(name))?)?;
+                if bytes.len() != *size {
+                    return Err(format!("{} needs {} bytes, got {}", name, size, bytes.len()));
+                }
+                out.extend_from_slice(&bytes);
+                Ok(())
+            }
+            Schema::Named(name) => Err(format!("unknown type: {}", name)),
+        }
+    }
+
+    fn decode_value(&self, schema: &Schema, reader: &mut Reader) -> Result<Value, String> {
+        match self.resolve(schema)? {
+            Schema::Null => Ok(Value::Null),
+            Schema::Boolean => Ok(Value::Bool(reader.take(1)?[0] != 0)),
+            Schema::Int | Schema::Long => Ok(Value::from(reader.long()?)),
+            Schema::Float => {
+                let bytes: [u8; 4] = reader.take(4)?.try_into().expect("four bytes");
+                Ok(serde_json::json!(f32::from_le_bytes(bytes) as f64))
+            }
+            Schema::Double => {
+                let bytes: [u8; 8] = reader.take(8)?.try_into().expect("eight bytes");
+                Ok(serde_json::json!(f64::from_le_bytes(bytes)))
+            }
+            Schema::Bytes => {
+                let len = reader.length()?;
+                Ok(Value::String(base64::engine::general_purpose::STANDARD.encode(reader.take(len)?)))
+            }
+            Schema::String => {
+                let len = reader.length()?;
+                let s = std::str::from_utf8(reader.take(len)?).map_err(|_| "string is not valid UTF-8".to_string())?;
+                Ok(Value::String(s.to_string()))
+            }
+            Schema::Array(items) => {
+                let mut values = Vec::new();
+                while let Some(count) = reader.block()? {
+                    for _ in 0..count {
+                        values.push(self.decode_value(items, reader)?);
+                    }
+                }
+                Ok(Value::Array(values))
+            }
+            Schema::Map(values) => {
+                let mut object = Map::new();
+                while let Some(count) = reader.block()? {
+                    for _ in 0..count {
+                        let len = reader.length()?;
+                        let key = std::str::from_utf8(reader.take(len)?)
+                            .map_err(|_| "map key is not valid UTF-8".to_string())?
+                            .to_string();
+                        object.insert(key, self.decode_value(values, reader)?);
+                    }
+                }
+                Ok(Value::Object(object))
+            }
+            Schema::Union(branches) => {
+                let index = reader.long()?;
+                let branch = usize::try_from(index)
+                    .ok()
+                    .and_then(|i| branches.get(i))
+                    .ok_or_else(|| format!("union branch {} out of range", index))?;
+                self.decode_value(branch, reader)
+            }
+            Schema::Record { fields, .. } => {
+                let mut object = Map::new();
+                for field in fields {
+                    object.insert(field.name.clone(), self.decode_value(&field.schema, reader)?);
+                }
+                Ok(Value::Object(object))
+            }
+            Schema::Enum { name, symbols } => {
+                let index = reader.long()?;
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|i| symbols.get(i))
+                    .map(|s| Value::String(s.clone()))
+                    .ok_or_else(|| format!("{} has no symbol {}", name, index))
+            }
+            Schema::Fixed { size, .. } => Ok(Value::String(
+                base64::engine::general_purpose::STANDARD.encode(reader.take(*size)?),
+            )),
+            Schema::Named(name) => Err(format!("unknown type: {}", name)),
+        }
+    }
+}
+
+/// Prefixes an Avro payload with the schema-registry header.
+pub fn frame(schema_id: u32, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 5);
+    out.push(0);
+    out.extend_from_slice(&schema_id.to_be_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Splits a schema-registry framed message into its schema id and payload.
+pub fn unframe(bytes: &[u8]) -> Result<(u32, &[u8]), String> {
+    match bytes {
+        [0, a, b, c, d, payload @ ..] => Ok((u32::from_be_bytes([*a, *b, *c, *d]), payload)),
+        _ => Err("data is not in schema-registry format (missing magic byte and schema id)".to_string()),
+    }
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    base64::engine::general_purpose::STANDARD
+        .decode(text)
+        .map_err(|e| format!("invalid base64: {}", e))
+}
+
+fn write_long(n: i64, out: &mut Vec<u8>) {
+    let mut zigzag = ((n << 1) ^ (n >> 63)) as u64;
+    while zigzag >= 0x80 {
+        out.push((zigzag as u8) | 0x80);
+        zigzag >>= 7;
+    }
+    out.push(zigzag as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.position.checked_add(len).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or_else(|| "unexpected end of data".to_string())?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn long(&mut self) -> Result<i64, String> {
+        let mut zigzag: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            zigzag |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((zigzag >> 1) as i64 ^ -((zigzag & 1) as i64));
+            }
+        }
+        Err("varint is too long".to_string())
+    }
+
+    fn length(&mut self) -> Result<usize, String> {
+        let len = self.long()?;
+        usize::try_from(len).map_err(|_| format!("negative length {}", len))
+    }
+
+    /// Item count of the next array or map block, or `None` at the end.
+    fn block(&mut self) -> Result<Option<u64>, String> {
+        match self.long()? {
+            0 => Ok(None),
+            n if n < 0 => {
+                // A negative count is followed by the block's size in bytes.
+                self.long()?;
+                Ok(Some(n.unsigned_abs()))
+            }
+            n => Ok(Some(n as u64)),
+        }
+    }
+}
+
+fn full_name(object: &Map<String, Value>, namespace: Option<&str>) -> Result<(String, Option<String>), String> {
+    let name = object
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("named type is missing a name")?;
+    if name.contains('.') {
+        let namespace = name.rsplit_once('.').map(|(ns, _)| ns.to_string());
+        return Ok((name.to_string(), namespace));
+    }
+    let namespace = object
+        .get("namespace")
+        .and_then(Value::as_str)
+        .or(namespace)
+        .filter(|ns| !ns.is_empty());
+    Ok(match namespace {
+        Some(ns) => (format!("{}.{}", ns, name), Some(ns.to_string())),
+        None => (name.to_string(), None),
+    })
+}
+
+fn parse_schema(json: &Value, namespace: Option<&str>, named: &mut HashMap<String, Schema>) -> Result<Schema, String> {
+    match json {
+        Value::String(name) => parse_name(name, namespace, named),
+        Value::Array(branches) => branches
+            .iter()
+            .map(|branch| parse_schema(branch, namespace, named))
+            .collect::<Result<_, _>>()
+            .map(Schema::Union),
+        Value::Object(object) => {
+            let kind = object.get("type").ok_or("schema object is missing a type")?;
+            let kind = match kind {
+                Value::String(kind) => kind.as_str(),
+                // e.g. {"type": {"type": "array", ...}}
+                other => return parse_schema(other, namespace, named),
+            };
+            match kind {
+                "array" => {
+                    let items = object.get("items").ok_or("array schema is missing items")?;
+                    Ok(Schema::Array(Box::new(parse_schema(items, namespace, named)?)))
+                }
+                "map" => {
+                    let values = object.get("values").ok_or("map schema is missing values")?;
+                    Ok(Schema::Map(Box::new(parse_schema(values, namespace, named)?)))
+                }
+                "record" | "error" => {
+                    let (name, namespace) = full_name(object, namespace)?;
+                    // Registered before the fields so they can refer to the record itself.
+                    named.insert(name.clone(), Schema::Named(name.clone()));
+                    let fields = object
+                        .get("fields")
+                        .and_then(Value::as_array)
+                        .ok_or_else(|| format!("record {} is missing fields", name))?
+                        .iter()
+                        .map(|field| {
+                            let field_name = field
+                                .get("name")
+                                .and_then(Value::as_str)
+                                .ok_or_else(|| format!("record {} has a field without a name", name))?;
+                            let schema = field
+                                .get("type")
+                                .ok_or_else(|| format!("field {}.{} is missing a type", name, field_name))?;
+                            Ok(Field {
+                                name: field_name.to_string(),
+                                schema: parse_schema(schema, namespace.as_deref(), named)?,
+                                default: field.get("default").cloned(),
+                            })
+                        })
+                        .collect::<Result<_, String>>()?;
+                    let record = Schema::Record { name: name.clone(), fields };
+                    named.insert(name, record.clone());
+                    Ok(record)
+                }
+                "enum" => {
+                    let (name, _) = full_name(object, namespace)?;
+                    let symbols = object
+                        .get("symbols")
+                        .and_then(|s| serde_json::from_value::<Vec<String>>(s.clone()).ok())
+                        .ok_or_else(|| format!("enum {} is missing symbols", name))?;
+                    let schema = Schema::Enum { name: name.clone(), symbols };
+                    named.insert(name, schema.clone());
+                    Ok(schema)
+                }
+                "fixed" => {
+                    let (name, _) = full_name(object, namespace)?;
+                    let size = object
+                        .get("size")
+                        .and_then(Value::as_u64)
+                        .ok_or_else(|| format!("fixed {} is missing a size", name))?;
+                    let schema = Schema::Fixed { name: name.clone(), size: size as usize };
+                    named.insert(name, schema.clone());
+                    Ok(schema)
+                }
+                other => parse_name(other, namespace, named),
+            }
+        }
+        other => Err(format!("invalid schema: {}", other)),
+    }
+}
+
+fn parse_name(name: &str, namespace: Option<&str>, named: &HashMap<String, Schema>) -> Result<Schema, String> {
+    Ok(match name {
+        "null" => Schema::Null,
+        "boolean" => Schema::Boolean,
+        "int" => Schema::Int,
+        "long" => Schema::Long,
+        "float" => Schema::Float,
+        "double" => Schema::Double,
+        "bytes" => Schema::Bytes,
+        "string" => Schema::String,
+        _ => {
+            let qualified = namespace.filter(|_| !name.contains('.')).map(|ns| format!("{}.{}", ns, name));
+            match qualified {
+                Some(full) if named.contains_key(&full) => Schema::Named(full),
+                _ if named.contains_key(name) => Schema::Named(name.to_string()),
+                _ => return Err(format!("unknown type: {}", name)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user_schema() -> AvroSchema {
+        AvroSchema::parse(&json!({
+            "type": "record",
+            "name": "User",
+            "namespace": "example",
+            "fields": [
+                {"name": "id", "type": "long"},
+                {"name": "name", "type": "string"},
+                {"name": "email", "type": ["null", "string"], "default": null},
+                {"name": "role", "type": {"type": "enum", "name": "Role", "symbols": ["ADMIN", "USER"]}},
+                {"name": "tags", "type": {"type": "array", "items": "string"}},
+                {"name": "manager", "type": ["null", "User"], "default": null}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encode_known_bytes() {
+        let schema = user_schema();
+        let bytes = schema
+            .encode(&json!({"id": 1, "name": "Ada", "role": "USER", "tags": ["x"]}))
+            .unwrap();
+        // id=1 (zigzag 2), "Ada", email null branch 0, role 1, one tag "x", end, manager null
+        assert_eq!(bytes, vec![2, 6, b'A', b'd', b'a', 0, 2, 2, 2, b'x', 0, 0]);
+    }
+
+    #[test]
+    fn test_round_trip_recursive() {
+        let schema = user_schema();
+        let value = json!({
+            "id": -300,
+            "name": "Bo",
+            "email": "bo@example.com",
+            "role": "ADMIN",
+            "tags": [],
+            "manager": {"id": 1, "name": "Ada", "email": null, "role": "USER", "tags": ["a", "b"], "manager": null}
+        });
+        let bytes = schema.encode(&value).unwrap();
+        assert_eq!(schema.decode(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn test_errors() {
+        let schema = user_schema();
+        assert_eq!(
+            schema.encode(&json!({"id": 1})).unwrap_err(),
+            "example.User is missing field name"
+        );
+        assert_eq!(
+            schema.encode(&json!({"id": 1, "name": "A", "role": "X", "tags": []})).unwrap_err(),
+            "example.User.role: X is not a symbol of example.Role"
+        );
+        assert_eq!(schema.decode(&[2, 6, b'A']).unwrap_err(), "unexpected end of data");
+        assert!(AvroSchema::parse(&json!("Nope")).is_err());
+    }
+
+    #[test]
+    fn test_framing() {
+        let framed = frame(42, &[1, 2]);
+        assert_eq!(framed, vec![0, 0, 0, 0, 42, 1, 2]);
+        assert_eq!(unframe(&framed).unwrap(), (42, &[1u8, 2][..]));
+        assert!(unframe(&[1, 2]).is_err());
+    }
+}
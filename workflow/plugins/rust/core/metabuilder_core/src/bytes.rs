@@ -0,0 +1,84 @@
+//! Binary values in JSON.
+//!
+//! Binary payloads travel between nodes as a tagged object,
+//! `{"$bytes": "<standard base64>"}`, so they cannot be mistaken for text.
+//! Nodes that take bytes also accept a plain string, read as its UTF-8 bytes.
+
+use base64::Engine as _;
+use serde_json::Value;
+
+/// Key of the tagged bytes object.
+pub const BYTES_KEY: &str = "$bytes";
+
+/// Wraps `bytes` as a tagged bytes value.
+pub fn to_value(bytes: &[u8]) -> Value {
+    serde_json::json!({ BYTES_KEY: base64::engine::general_purpose::STANDARD.encode(bytes) })
+}
+
+/// Returns true if `value` is a tagged bytes object.
+pub fn is_bytes(value: &Value) -> bool {
+    value.as_object().is_some_and(|o| o.len() == 1 && o.get(BYTES_KEY).is_some_and(Value::is_string))
+}
+
+/// Reads a tagged bytes object, or a string as UTF-8.
+pub fn from_value(value: &Value) -> Result<Vec<u8>, String> {
+    match value {
+        Value::String(s) => Ok(s.as_bytes().to_vec()),
+        _ if is_bytes(value) => base64::engine::general_purpose::STANDARD
+            .decode(value[BYTES_KEY].as_str().unwrap_or_default())
+            .map_err(|e| format!("invalid base64 in bytes value: {}", e)),
+        other => Err(format!("expected bytes or a string, got {}", other)),
+    }
+}
+
+/// Encodes `bytes` as hexadecimal, two digits per byte.
+pub fn to_hex(bytes: &[u8], uppercase: bool) -> String {
+    let digits: &[u8; 16] = if uppercase { b"0123456789ABCDEF" } else { b"0123456789abcdef" };
+    bytes
+        .iter()
+        .flat_map(|b| [digits[(b >> 4) as usize] as char, digits[(b & 0xf) as usize] as char])
+        .collect()
+}
+
+/// Decodes hexadecimal in either case. Whitespace between digits and a
+/// leading `0x` are ignored.
+pub fn from_hex(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    let text = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")).unwrap_or(text);
+    let digits = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16).map(|d| d as u8).ok_or_else(|| format!("invalid hex digit: {:?}", c)))
+        .collect::<Result<Vec<u8>, String>>()?;
+    if digits.len() % 2 != 0 {
+        return Err("hex must have an even number of digits".to_string());
+    }
+    Ok(digits.chunks(2).map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip() {
+        let value = to_value(&[0, 159, 146, 150]);
+        assert_eq!(value, json!({"$bytes": "AJ+Slg=="}));
+        assert!(is_bytes(&value));
+        assert_eq!(from_value(&value).unwrap(), vec![0, 159, 146, 150]);
+        assert_eq!(from_value(&json!("hé")).unwrap(), "hé".as_bytes());
+        assert_eq!(from_value(&json!(3)).unwrap_err(), "expected bytes or a string, got 3");
+        assert!(!is_bytes(&json!({"$bytes": "AA==", "extra": 1})));
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex(&[0, 159, 146, 150], false), "009f9296");
+        assert_eq!(to_hex(&[0xab], true), "AB");
+        assert_eq!(from_hex("0x009F 9296").unwrap(), vec![0, 159, 146, 150]);
+        assert_eq!(from_hex("").unwrap(), Vec::<u8>::new());
+        assert_eq!(from_hex("abc").unwrap_err(), "hex must have an even number of digits");
+        assert_eq!(from_hex("zz").unwrap_err(), "invalid hex digit: 'z'");
+    }
+}
@@ -0,0 +1,61 @@
+//! Shared cache for values that outlive a single node, such as access tokens.
+//!
+//! Unlike the var store, the cache is reachable through `&Runtime` and can be
+//! shared between runs, so plugins can reuse expensive results.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Thread-safe key/value cache.
+#[derive(Default)]
+pub struct Cache {
+    values: Mutex<HashMap<String, Value>>,
+}
+
+impl Cache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the value under `key`.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.lock().get(key).cloned()
+    }
+
+    /// Stores `value` under `key`, replacing any previous value.
+    pub fn insert(&self, key: impl Into<String>, value: Value) {
+        self.lock().insert(key.into(), value);
+    }
+
+    /// Removes and returns the value under `key`.
+    pub fn remove(&self, key: &str) -> Option<Value> {
+        self.lock().remove(key)
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Value>> {
+        // A panic while holding the lock cannot leave a HashMap half-updated.
+        self.values.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let cache = Cache::new();
+        cache.insert("token", json!({"access_token": "abc"}));
+        assert_eq!(cache.get("token"), Some(json!({"access_token": "abc"})));
+        assert_eq!(cache.remove("token"), Some(json!({"access_token": "abc"})));
+        assert_eq!(cache.get("token"), None);
+    }
+}
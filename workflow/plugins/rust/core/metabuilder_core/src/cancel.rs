@@ -0,0 +1,75 @@
+//! Cooperative cancellation for long-running nodes.
+
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// A flag that a host sets to stop a run, which waiting nodes observe at once.
+#[derive(Debug, Default)]
+pub struct Cancellation {
+    cancelled: Mutex<bool>,
+    signal: Condvar,
+}
+
+impl Cancellation {
+    /// Creates a flag that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels, waking every node waiting in `sleep`.
+    pub fn cancel(&self) {
+        *self.cancelled.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        self.signal.notify_all();
+    }
+
+    /// Clears the flag so later runs proceed.
+    pub fn reset(&self) {
+        *self.cancelled.lock().unwrap_or_else(|e| e.into_inner()) = false;
+    }
+
+    /// Whether `cancel` has been called since the last `reset`.
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Waits for `duration`, returning `false` early if cancelled.
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        let mut cancelled = self.cancelled.lock().unwrap_or_else(|e| e.into_inner());
+        while !*cancelled {
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            cancelled = self
+                .signal
+                .wait_timeout(cancelled, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sleep_and_cancel() {
+        let cancel = Arc::new(Cancellation::new());
+        assert!(cancel.sleep(Duration::from_millis(5)));
+
+        let waiter = cancel.clone();
+        let started = Instant::now();
+        let handle = std::thread::spawn(move || waiter.sleep(Duration::from_secs(30)));
+        std::thread::sleep(Duration::from_millis(20));
+        cancel.cancel();
+        assert!(!handle.join().unwrap());
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        cancel.reset();
+        assert!(!cancel.is_cancelled());
+    }
+}
@@ -0,0 +1,117 @@
+//! Canonical JSON (RFC 8785, JSON Canonicalization Scheme).
+//!
+//! Object keys are sorted by UTF-16 code units and numbers are written the
+//! way ECMAScript prints doubles, so `1.0` and `1` serialize identically.
+//! Two equal values always produce the same bytes, which makes the output
+//! suitable for hashing and signing.
+
+use serde_json::Value;
+
+/// Serializes `value` as canonical JSON.
+pub fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    write(value, &mut out);
+    out
+}
+
+fn write(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&number(n.as_f64().unwrap_or(0.0))),
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(key, out);
+                out.push(':');
+                write(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(s: &str, out: &mut String) {
+    // serde_json's escaping (short escapes, lowercase \u00xx, no `/` escaping) matches RFC 8785.
+    out.push_str(&serde_json::to_string(s).expect("strings always serialize"));
+}
+
+/// Formats a double like ECMAScript's `Number.prototype.toString`.
+pub fn number(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        // JSON has no NaN or infinity; serde_json never produces them.
+        return "0".to_string();
+    }
+
+    // `{:e}` gives the shortest round-trip digits, e.g. "1.2345e-7".
+    let formatted = format!("{:e}", value.abs());
+    let (mantissa, exponent) = formatted.split_once('e').expect("exponent format");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    // value = 0.d1d2...dk * 10^n
+    let n = exponent.parse::<i32>().expect("integer exponent") + 1;
+
+    let body = if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        match k {
+            1 => format!("{}e{}{}", digits, sign, (n - 1).abs()),
+            _ => format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, (n - 1).abs()),
+        }
+    };
+    if value < 0.0 {
+        format!("-{}", body)
+    } else {
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sorted_keys_and_spacing() {
+        let value = json!({"b": [1, {"z": null, "a": true}], "a": "x\u{1}\"/é"});
+        assert_eq!(to_string(&value), r#"{"a":"x\u0001\"/é","b":[1,{"a":true,"z":null}]}"#);
+        // U+1F600 is the surrogate pair D83D DE00 in UTF-16, so it sorts before U+E000.
+        assert_eq!(to_string(&json!({"\u{e000}": 1, "\u{1f600}": 2})), "{\"\u{1f600}\":2,\"\u{e000}\":1}");
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(number(1.0), "1");
+        assert_eq!(number(-0.0), "0");
+        assert_eq!(number(0.1), "0.1");
+        assert_eq!(number(123.456), "123.456");
+        assert_eq!(number(1e21), "1e+21");
+        assert_eq!(number(1e20), "100000000000000000000");
+        assert_eq!(number(0.000001), "0.000001");
+        assert_eq!(number(1e-7), "1e-7");
+        assert_eq!(number(-1.5e-9), "-1.5e-9");
+        assert_eq!(number(4.5e300), "4.5e+300");
+        assert_eq!(to_string(&json!([1.0, 1, 2.50])), "[1,1,2.5]");
+    }
+}
@@ -0,0 +1,76 @@
+//! Long-lived clients shared between nodes, such as HTTP connection pools.
+//!
+//! Opening a connection (and a TLS session) costs more than most requests
+//! sent over it. A node that talks to other hosts keeps its client here,
+//! under a key naming its kind, and every node using the same kind reuses
+//! it, along with the connections it has open. The engine shares one
+//! `Clients` across all its runs, and each tenant has its own. Clients hold
+//! no credentials; nodes add those per request.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Thread-safe store of type-erased clients keyed by name.
+#[derive(Default)]
+pub struct Clients {
+    values: Mutex<HashMap<String, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl Clients {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the client under `key`, first storing the one `make` returns
+    /// if there is none of type `T`.
+    pub fn get_or_insert_with<T: Any + Send + Sync>(&self, key: &str, make: impl FnOnce() -> T) -> Arc<T> {
+        let mut values = self.lock();
+        if let Some(client) = values.get(key).cloned().and_then(|value| value.downcast::<T>().ok()) {
+            return client;
+        }
+        let client = Arc::new(make());
+        values.insert(key.to_string(), client.clone());
+        client
+    }
+
+    /// Drops the client under `key`, closing its connections once no node
+    /// holds it. Returns whether it existed.
+    pub fn remove(&self, key: &str) -> bool {
+        self.lock().remove(key).is_some()
+    }
+
+    /// Number of stored clients.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<dyn Any + Send + Sync>>> {
+        // A panic while holding the lock cannot leave a HashMap half-updated.
+        self.values.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let clients = Clients::new();
+        let first = clients.get_or_insert_with("pool", || vec![1]);
+        let second = clients.get_or_insert_with("pool", || vec![2]);
+        assert!(Arc::ptr_eq(&first, &second));
+        // A client of another type under the same key is replaced.
+        assert_eq!(*clients.get_or_insert_with("pool", || "other"), "other");
+        assert_eq!(clients.len(), 1);
+        assert!(clients.remove("pool"));
+        assert!(clients.is_empty());
+    }
+}
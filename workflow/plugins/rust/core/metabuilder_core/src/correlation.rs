@@ -0,0 +1,78 @@
+//! Run and execution ids of the node running on the current thread.
+//!
+//! The engine gives every run an id unique across processes, and every
+//! execution of a node an id made of the run id, the node id, and a count, so
+//! a node re-run by a loop gets a new one. While a node runs, `current`
+//! returns both, so nodes can pass them on, such as in the headers of an HTTP
+//! request, and logs on both sides of a call can be joined. The ids are kept
+//! per thread rather than in the `Runtime` because nodes of one level run in
+//! parallel against the same runtime.
+
+use std::cell::RefCell;
+
+/// Header that carries the run id on outgoing HTTP requests.
+pub const RUN_ID_HEADER: &str = "X-Run-Id";
+/// Header that carries the execution id on outgoing HTTP requests.
+pub const EXECUTION_ID_HEADER: &str = "X-Execution-Id";
+
+/// Ids of one execution of a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correlation {
+    pub run_id: String,
+    pub execution_id: String,
+}
+
+impl Correlation {
+    /// The ids of an execution from its id alone, which starts with the run
+    /// id and a colon.
+    pub fn from_execution_id(execution_id: &str) -> Self {
+        Correlation {
+            run_id: execution_id.split(':').next().unwrap_or_default().to_string(),
+            execution_id: execution_id.to_string(),
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Correlation>> = const { RefCell::new(None) };
+}
+
+/// The ids of the node running on this thread, if any.
+pub fn current() -> Option<Correlation> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Calls `f` with `correlation` as the current ids, restoring the previous
+/// ones afterwards, so nested runs unwind back to the calling node's.
+pub fn scope<R>(correlation: Correlation, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Correlation>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(CURRENT.with(|current| current.borrow_mut().replace(correlation)));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope() {
+        assert_eq!(current(), None);
+        let outer = Correlation::from_execution_id("run1:a:0");
+        assert_eq!(outer.run_id, "run1");
+        scope(outer.clone(), || {
+            assert_eq!(current().as_ref(), Some(&outer));
+            scope(Correlation::from_execution_id("run1:b:1"), || {
+                assert_eq!(current().unwrap().execution_id, "run1:b:1");
+            });
+            assert_eq!(current().as_ref(), Some(&outer));
+            // Other threads run other nodes.
+            assert_eq!(std::thread::spawn(current).join().unwrap(), None);
+        });
+        assert_eq!(current(), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! Datetime helpers shared by the `datetime.*` nodes.
+//!
+//! Nodes pass datetimes to each other as RFC 3339 strings, which keep the
+//! UTC offset. Formats are `"rfc3339"`, `"rfc2822"`, or a strftime-style
+//! pattern such as `"%d/%m/%Y %H:%M"`; a pattern without an offset reads the
+//! time as UTC, and one without a time reads midnight. Where a node takes a
+//! datetime input, a number is also accepted as seconds since the epoch.
+
+use chrono::format::ParseErrorKind;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde_json::{Map, Value};
+
+/// Why a datetime did not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFailure {
+    pub message: String,
+    /// `"invalid"`, `"too_short"`, `"too_long"`, `"out_of_range"`,
+    /// `"impossible"`, `"not_enough"`, `"bad_format"`, `"missing"`, or a
+    /// code a node adds, such as `"unknown_timezone"`
+    pub reason: &'static str,
+}
+
+impl From<chrono::ParseError> for ParseFailure {
+    fn from(e: chrono::ParseError) -> Self {
+        let reason = match e.kind() {
+            ParseErrorKind::OutOfRange => "out_of_range",
+            ParseErrorKind::Impossible => "impossible",
+            ParseErrorKind::NotEnough => "not_enough",
+            ParseErrorKind::TooShort => "too_short",
+            ParseErrorKind::TooLong => "too_long",
+            ParseErrorKind::BadFormat => "bad_format",
+            _ => "invalid",
+        };
+        ParseFailure {
+            message: e.to_string(),
+            reason,
+        }
+    }
+}
+
+/// Parses `text` in `format`.
+pub fn parse(text: &str, format: &str) -> Result<DateTime<FixedOffset>, ParseFailure> {
+    match format {
+        "rfc3339" => return Ok(DateTime::parse_from_rfc3339(text)?),
+        "rfc2822" => return Ok(DateTime::parse_from_rfc2822(text)?),
+        _ => {}
+    }
+    // Fall back to less complete forms only when the pattern lacks the fields.
+    let e = match DateTime::parse_from_str(text, format) {
+        Ok(datetime) => return Ok(datetime),
+        Err(e) if e.kind() != ParseErrorKind::NotEnough => return Err(e.into()),
+        Err(e) => e,
+    };
+    let e = match NaiveDateTime::parse_from_str(text, format) {
+        Ok(naive) => return Ok(naive.and_utc().fixed_offset()),
+        Err(inner) if inner.kind() != ParseErrorKind::NotEnough => return Err(inner.into()),
+        Err(_) => e,
+    };
+    match NaiveDate::parse_from_str(text, format) {
+        Ok(date) => Ok(date.and_time(NaiveTime::MIN).and_utc().fixed_offset()),
+        Err(inner) if inner.kind() != ParseErrorKind::NotEnough => Err(inner.into()),
+        Err(_) => Err(e.into()),
+    }
+}
+
+/// Reads a datetime input: a string in `format`, or seconds since the epoch.
+pub fn from_value(value: Option<&Value>, format: &str) -> Result<DateTime<FixedOffset>, ParseFailure> {
+    let failure = |message: &str, reason| ParseFailure {
+        message: message.to_string(),
+        reason,
+    };
+    match value {
+        None | Some(Value::Null) => Err(failure("no datetime given", "missing")),
+        Some(Value::String(text)) => parse(text, format),
+        Some(Value::Number(n)) => {
+            let seconds = n.as_f64().unwrap_or(f64::NAN);
+            let nanos = (seconds.fract().abs() * 1e9).round() as u32;
+            DateTime::from_timestamp(seconds.floor() as i64, nanos.min(999_999_999))
+                .filter(|_| seconds.is_finite())
+                .map(|datetime| datetime.fixed_offset())
+                .ok_or_else(|| failure("epoch seconds out of range", "out_of_range"))
+        }
+        Some(_) => Err(failure("expected a string or epoch seconds", "invalid")),
+    }
+}
+
+/// The fields of `datetime` as output ports: `year`, `month`, `day`, `hour`,
+/// `minute`, `second`, `nanosecond`, `weekday` (1 for Monday to 7),
+/// `ordinal` (day of the year), `offset` (seconds east of UTC), and `epoch`
+/// and `epoch_ms` (since 1970-01-01 UTC).
+pub fn components(datetime: &DateTime<FixedOffset>) -> Map<String, Value> {
+    let mut fields = Map::new();
+    fields.insert("year".to_string(), Value::from(datetime.year()));
+    fields.insert("month".to_string(), Value::from(datetime.month()));
+    fields.insert("day".to_string(), Value::from(datetime.day()));
+    fields.insert("hour".to_string(), Value::from(datetime.hour()));
+    fields.insert("minute".to_string(), Value::from(datetime.minute()));
+    fields.insert("second".to_string(), Value::from(datetime.second()));
+    fields.insert("nanosecond".to_string(), Value::from(datetime.nanosecond()));
+    fields.insert("weekday".to_string(), Value::from(datetime.weekday().number_from_monday()));
+    fields.insert("ordinal".to_string(), Value::from(datetime.ordinal()));
+    fields.insert("offset".to_string(), Value::from(datetime.offset().local_minus_utc()));
+    fields.insert("epoch".to_string(), Value::from(datetime.timestamp()));
+    fields.insert("epoch_ms".to_string(), Value::from(datetime.timestamp_millis()));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_presets_and_patterns() {
+        let rfc3339 = parse("2024-02-29T13:45:30.5+02:00", "rfc3339").unwrap();
+        assert_eq!(rfc3339.to_rfc3339(), "2024-02-29T13:45:30.500+02:00");
+        let rfc2822 = parse("Thu, 29 Feb 2024 13:45:30 +0200", "rfc2822").unwrap();
+        assert_eq!(rfc2822.timestamp(), rfc3339.timestamp());
+        assert_eq!(parse("29/02/2024 13:45", "%d/%m/%Y %H:%M").unwrap().to_rfc3339(), "2024-02-29T13:45:00+00:00");
+        assert_eq!(parse("2024-02-29", "%Y-%m-%d").unwrap().to_rfc3339(), "2024-02-29T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_failures() {
+        assert_eq!(parse("2023-02-29", "%Y-%m-%d").unwrap_err().reason, "out_of_range");
+        assert_eq!(parse("2024-02", "%Y-%m-%d").unwrap_err().reason, "too_short");
+        assert_eq!(parse("13:45", "%H:%M").unwrap_err().reason, "not_enough");
+        assert_eq!(parse("2024-02-29T13:45:30Z!", "rfc3339").unwrap_err().reason, "too_long");
+        assert_eq!(parse("noon", "%Y-%m-%d").unwrap_err().reason, "invalid");
+    }
+
+    #[test]
+    fn test_from_value() {
+        let read = |value: Value| from_value(Some(&value), "rfc3339").map(|d| d.to_rfc3339());
+        assert_eq!(read(Value::from("2024-02-29T13:45:30Z")).unwrap(), "2024-02-29T13:45:30+00:00");
+        assert_eq!(read(Value::from(1709214330)).unwrap(), "2024-02-29T13:45:30+00:00");
+        assert_eq!(read(Value::from(-0.5)).unwrap(), "1969-12-31T23:59:59.500+00:00");
+        assert_eq!(read(Value::from(1e20)).unwrap_err().reason, "out_of_range");
+        assert_eq!(read(Value::Bool(true)).unwrap_err().reason, "invalid");
+        assert_eq!(from_value(None, "rfc3339").unwrap_err().reason, "missing");
+    }
+
+    #[test]
+    fn test_components() {
+        let fields = components(&parse("2024-02-29T13:45:30+02:00", "rfc3339").unwrap());
+        assert_eq!(fields["year"], 2024);
+        assert_eq!(fields["weekday"], 4);
+        assert_eq!(fields["ordinal"], 60);
+        assert_eq!(fields["offset"], 7200);
+        assert_eq!(fields["epoch"], 1709207130);
+        assert_eq!(fields["epoch_ms"], 1709207130000i64);
+    }
+}
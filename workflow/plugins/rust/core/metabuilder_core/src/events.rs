@@ -0,0 +1,137 @@
+//! In-process event bus shared by the nodes of a run.
+//!
+//! `event.emit` publishes a payload on a topic and `event.on` reads the
+//! payloads published so far, optionally waiting for more, so branches of a
+//! workflow can talk without edges between them. Hosts can subscribe to see
+//! events as they happen, e.g. to report progress.
+
+use crate::cancel::Cancellation;
+use serde_json::Value;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// A published event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub topic: String,
+    pub payload: Value,
+}
+
+/// A host callback for events on a topic.
+pub type Listener = Arc<dyn Fn(&Event) + Send + Sync>;
+
+/// How often waiters check for cancellation.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Events published during a run, in order.
+#[derive(Default)]
+pub struct EventBus {
+    events: Mutex<Vec<Event>>,
+    published: Condvar,
+    listeners: Mutex<Vec<(String, Listener)>>,
+}
+
+impl EventBus {
+    /// Creates an empty bus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `listener` for every event on `topic`, or on every topic for
+    /// `"*"`. Listeners run on the publishing thread.
+    pub fn subscribe(&self, topic: impl Into<String>, listener: Listener) {
+        self.listeners.lock().unwrap_or_else(|e| e.into_inner()).push((topic.into(), listener));
+    }
+
+    /// Publishes `payload` on `topic`, waking nodes waiting for it.
+    pub fn emit(&self, topic: impl Into<String>, payload: Value) {
+        let event = Event {
+            topic: topic.into(),
+            payload,
+        };
+        let listeners: Vec<Listener> = self
+            .listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(topic, _)| topic == "*" || *topic == event.topic)
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        for listener in listeners {
+            listener(&event);
+        }
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+        self.published.notify_all();
+    }
+
+    /// Payloads published on `topic` so far.
+    pub fn payloads(&self, topic: &str) -> Vec<Value> {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        matching(&events, topic)
+    }
+
+    /// Waits until at least `count` payloads have been published on `topic`,
+    /// for up to `timeout`, and returns them all. Returns `None` if the
+    /// timeout passes or the run is cancelled first.
+    pub fn wait(&self, topic: &str, count: usize, timeout: Duration, cancel: &Cancellation) -> Option<Vec<Value>> {
+        let deadline = Instant::now() + timeout;
+        let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let payloads = matching(&events, topic);
+            if payloads.len() >= count {
+                return Some(payloads);
+            }
+            let left = deadline.checked_duration_since(Instant::now())?;
+            if cancel.is_cancelled() {
+                return None;
+            }
+            events = self
+                .published
+                .wait_timeout(events, left.min(POLL))
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
+
+fn matching(events: &[Event], topic: &str) -> Vec<Value> {
+    events.iter().filter(|e| e.topic == topic).map(|e| e.payload.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_emit_and_subscribe() {
+        let bus = EventBus::new();
+        let seen = Arc::new(AtomicUsize::new(0));
+        let counter = seen.clone();
+        bus.subscribe("*", Arc::new(move |_: &Event| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        bus.emit("progress", json!(1));
+        bus.emit("done", json!(null));
+        bus.emit("progress", json!(2));
+
+        assert_eq!(bus.payloads("progress"), vec![json!(1), json!(2)]);
+        assert_eq!(seen.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_wait() {
+        let bus = Arc::new(EventBus::new());
+        let cancel = Cancellation::new();
+        assert_eq!(bus.wait("tick", 1, Duration::from_millis(10), &cancel), None);
+
+        let publisher = bus.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            publisher.emit("tick", json!("a"));
+        });
+        assert_eq!(bus.wait("tick", 1, Duration::from_secs(5), &cancel), Some(vec![json!("a")]));
+        handle.join().unwrap();
+    }
+}
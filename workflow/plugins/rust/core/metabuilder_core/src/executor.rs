@@ -0,0 +1,49 @@
+//! The executor trait shared by the engine and embedding hosts.
+
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::any::Any;
+use core::future::Future;
+use core::pin::Pin;
+use serde_json::Value;
+
+/// A node's input or output ports by name. Without `std` there is no
+/// `HashMap`, so it is a `BTreeMap` there.
+#[cfg(feature = "std")]
+pub type Ports = std::collections::HashMap<String, Value>;
+/// A node's input or output ports by name. Without `std` there is no
+/// `HashMap`, so it is a `BTreeMap` there.
+#[cfg(not(feature = "std"))]
+pub type Ports = alloc::collections::BTreeMap<String, Value>;
+
+/// Trait for workflow node executors.
+///
+/// Mirrors the per-plugin `NodeExecutor` traits so any plugin can be adapted
+/// with a closure, and adds `Send + Sync` so executors can be shared across runs.
+pub trait NodeExecutor: Send + Sync {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: Ports, runtime: Option<&dyn Any>) -> Ports;
+}
+
+impl<F> NodeExecutor for F
+where
+    F: Fn(Ports, Option<&dyn Any>) -> Ports + Send + Sync,
+{
+    fn execute(&self, inputs: Ports, runtime: Option<&dyn Any>) -> Ports {
+        self(inputs, runtime)
+    }
+}
+
+/// The outputs an `AsyncNodeExecutor` is producing.
+pub type PortsFuture<'a> = Pin<Box<dyn Future<Output = Ports> + 'a>>;
+
+/// Trait for node executors that wait on I/O without blocking a thread.
+///
+/// Hosts already in a tokio runtime await `execute`; the engine and other
+/// synchronous hosts run the executor with `reactor::Blocking` (with the
+/// `async` feature).
+pub trait AsyncNodeExecutor: Send + Sync {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute<'a>(&'a self, inputs: Ports, runtime: Option<&'a dyn Any>) -> PortsFuture<'a>;
+}
@@ -0,0 +1,163 @@
+//! Filesystem helpers shared by the `file` nodes.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+use std::fs::{self, File, Metadata, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A file as the `file` nodes output it: its `path` (with `/` separators),
+/// `name`, `kind` (`"file"` or `"dir"`), `size` in bytes, and `modified`, an
+/// RFC 3339 UTC timestamp, or `null` where the platform has none.
+pub fn describe(path: &Path, metadata: &Metadata) -> Map<String, Value> {
+    let modified = metadata
+        .modified()
+        .ok()
+        .map(|time| DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Millis, true));
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut entry = Map::new();
+    entry.insert("path".to_string(), json!(crate::paths::normalize(&path.to_string_lossy())));
+    entry.insert("name".to_string(), json!(name));
+    entry.insert("kind".to_string(), json!(if metadata.is_dir() { "dir" } else { "file" }));
+    entry.insert("size".to_string(), json!(metadata.len()));
+    entry.insert("modified".to_string(), json!(modified));
+    entry
+}
+
+/// Creates the missing parent directories of `path`.
+pub fn create_parent_dirs(path: &Path) -> io::Result<()> {
+    match path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+/// Replaces the file at `path` with one holding `existing` (the first bytes,
+/// if any, such as the old contents for an append) and then `data`, by
+/// writing a temporary file beside it and renaming that over it, so readers
+/// see either the old file or the new one, never a partial write. The
+/// replacement keeps the old file's permissions, and the rename is synced to
+/// the directory, so it survives a crash once this returns.
+pub fn write_atomic(path: &Path, existing: Option<&Path>, data: &[u8]) -> io::Result<()> {
+    let dir = match path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        Some(parent) => parent,
+        None => Path::new("."),
+    };
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let prefix = format!(".{}.", name);
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(&prefix).suffix(".tmp");
+    #[cfg(unix)]
+    {
+        // New files get the usual mode less the umask, not the temp file's 0600.
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(fs::Permissions::from_mode(0o666));
+    }
+    // Dropping the temp file on an error removes it.
+    let mut temp = builder.tempfile_in(dir)?;
+    if let Some(existing) = existing {
+        io::copy(&mut File::open(existing)?, temp.as_file_mut())?;
+    }
+    temp.write_all(data)?;
+    match fs::metadata(path) {
+        Ok(metadata) => temp.as_file().set_permissions(metadata.permissions())?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    temp.as_file().sync_all()?;
+    temp.persist(path).map_err(|e| e.error)?;
+    sync_dir(dir)
+}
+
+/// Flushes `dir`'s entries, such as a rename into it, to disk.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened for syncing here; renames are durable once
+/// the file system flushes its metadata.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Writes `data` to `path`, appending to it if `append`, creating it if it
+/// does not exist.
+pub fn write(path: &Path, data: &[u8], append: bool) -> io::Result<()> {
+    let mut file = match append {
+        true => OpenOptions::new().create(true).append(true).open(path)?,
+        false => File::create(path)?,
+    };
+    file.write_all(data)?;
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::scratch;
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = scratch("atomic");
+        let path = dir.join("a/b/out.txt");
+        create_parent_dirs(&path).unwrap();
+        write_atomic(&path, None, b"one").unwrap();
+        write_atomic(&path, Some(&path), b" two").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one two");
+        // Only the file is left behind.
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_keeps_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = scratch("mode");
+        let path = dir.join("run.sh");
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o750)).unwrap();
+        write_atomic(&path, None, b"#!/bin/sh\n").unwrap();
+        write_atomic(&path, Some(&path), b"echo hi\n").unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o750);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/bin/sh\necho hi\n");
+
+        // New files get the same mode as any other file created here.
+        let fresh = dir.join("new.txt");
+        write_atomic(&fresh, None, b"x").unwrap();
+        fs::write(dir.join("plain.txt"), "x").unwrap();
+        let mode = |name: &str| fs::metadata(dir.join(name)).unwrap().permissions().mode();
+        assert_eq!(mode("new.txt"), mode("plain.txt"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_describe() {
+        let dir = scratch("describe");
+        let path = dir.join("out.txt");
+        fs::write(&path, "abc").unwrap();
+        let entry = describe(&path, &fs::metadata(&path).unwrap());
+        assert_eq!(entry["name"], "out.txt");
+        assert_eq!(entry["kind"], "file");
+        assert_eq!(entry["size"], 3);
+        assert!(entry["modified"].as_str().unwrap().ends_with('Z'));
+        assert_eq!(describe(&dir, &fs::metadata(&dir).unwrap())["kind"], "dir");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = scratch("plain");
+        let path = dir.join("out.txt");
+        write(&path, b"a", true).unwrap();
+        write(&path, b"b", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "ab");
+        write(&path, b"c", false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "c");
+        assert!(write_atomic(&dir.join("missing/out.txt"), None, b"x").is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
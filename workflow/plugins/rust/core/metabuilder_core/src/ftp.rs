@@ -0,0 +1,227 @@
+//! Minimal FTP/FTPS client for the `remote.ftp_*` nodes: passive mode,
+//! binary transfers, explicit TLS.
+//!
+//! Commands are lines, so arguments containing control characters (such as
+//! a path with CR/LF, which would smuggle in a command of its own) are
+//! rejected before anything is sent.
+
+use crate::net::{self, Stream};
+use native_tls::TlsConnector;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::time::Duration;
+
+/// An authenticated FTP session.
+pub struct FtpClient {
+    control: BufReader<Stream>,
+    host: String,
+    tls: Option<TlsConnector>,
+    timeout: Duration,
+}
+
+impl FtpClient {
+    /// Connects to the server, upgrading the control channel with `AUTH TLS` when `secure` is set.
+    pub fn connect(host: &str, port: u16, secure: bool, timeout: Duration) -> Result<Self, String> {
+        let tcp = net::connect(host, port, timeout)?;
+        let mut client = FtpClient {
+            control: BufReader::new(Stream::Plain(tcp)),
+            host: host.to_string(),
+            tls: None,
+            timeout,
+        };
+        client.expect_reply(&[220])?;
+
+        if secure {
+            client.command("AUTH TLS", &[234])?;
+            let connector = TlsConnector::new().map_err(|e| e.to_string())?;
+            let tcp = match client.control.into_inner() {
+                Stream::Plain(tcp) => tcp,
+                Stream::Tls(_) => unreachable!("control channel is upgraded once"),
+            };
+            let tls = connector.connect(host, tcp).map_err(|e| e.to_string())?;
+            client.control = BufReader::new(Stream::Tls(Box::new(tls)));
+            client.tls = Some(connector);
+        }
+        Ok(client)
+    }
+
+    /// Logs in and switches to binary mode.
+    pub fn login(&mut self, username: &str, password: &str) -> Result<(), String> {
+        let (code, _) = self.command(&format!("USER {}", argument("username", username)?), &[230, 331])?;
+        if code == 331 {
+            self.command(&format!("PASS {}", argument("password", password)?), &[230])?;
+        }
+        if self.tls.is_some() {
+            self.command("PBSZ 0", &[200])?;
+            self.command("PROT P", &[200])?;
+        }
+        self.command("TYPE I", &[200])?;
+        Ok(())
+    }
+
+    /// Downloads `path` into memory.
+    pub fn retrieve(&mut self, path: &str) -> Result<Vec<u8>, String> {
+        let path = argument("path", path)?;
+        let mut data = self.passive()?;
+        self.command(&format!("RETR {}", path), &[125, 150])?;
+        let mut buf = Vec::new();
+        data.read_to_end(&mut buf).map_err(|e| e.to_string())?;
+        drop(data);
+        self.expect_reply(&[226, 250])?;
+        Ok(buf)
+    }
+
+    /// Uploads `data` to `path`, replacing any existing file.
+    pub fn store(&mut self, path: &str, data: &[u8]) -> Result<(), String> {
+        let path = argument("path", path)?;
+        let mut stream = self.passive()?;
+        self.command(&format!("STOR {}", path), &[125, 150])?;
+        stream.write_all(data).and_then(|_| stream.flush()).map_err(|e| e.to_string())?;
+        if let Stream::Tls(tls) = &mut stream {
+            tls.shutdown().map_err(|e| e.to_string())?;
+        }
+        drop(stream);
+        self.expect_reply(&[226, 250])?;
+        Ok(())
+    }
+
+    /// Ends the session. Errors are ignored since the transfer already completed.
+    pub fn quit(mut self) {
+        let _ = self.command("QUIT", &[221]);
+    }
+
+    /// Opens a passive-mode data connection.
+    fn passive(&mut self) -> Result<Stream, String> {
+        let (_, text) = self.command("PASV", &[227])?;
+        let port = parse_pasv(&text).ok_or_else(|| format!("malformed PASV reply: {}", text))?;
+        // Connect to the control host rather than the advertised address, which is
+        // frequently a private IP behind NAT.
+        let tcp = net::connect(&self.host, port, self.timeout)?;
+        match &self.tls {
+            Some(connector) => connector
+                .connect(&self.host, tcp)
+                .map(|s| Stream::Tls(Box::new(s)))
+                .map_err(|e| e.to_string()),
+            None => Ok(Stream::Plain(tcp)),
+        }
+    }
+
+    fn command(&mut self, cmd: &str, expected: &[u32]) -> Result<(u32, String), String> {
+        let stream = self.control.get_mut();
+        stream
+            .write_all(format!("{}\r\n", cmd).as_bytes())
+            .and_then(|_| stream.flush())
+            .map_err(|e| e.to_string())?;
+        self.expect_reply(expected)
+    }
+
+    fn expect_reply(&mut self, expected: &[u32]) -> Result<(u32, String), String> {
+        let (code, text) = read_reply(&mut self.control)?;
+        if expected.contains(&code) {
+            Ok((code, text))
+        } else {
+            Err(format!("unexpected FTP reply: {} {}", code, text))
+        }
+    }
+}
+
+/// `value` as a command argument, unless it has control characters.
+fn argument<'a>(name: &str, value: &'a str) -> Result<&'a str, String> {
+    match value.chars().any(char::is_control) {
+        true => Err(format!("{} must not contain control characters", name)),
+        false => Ok(value),
+    }
+}
+
+/// Reads a possibly multi-line reply (`123-...` continued until `123 ...`).
+fn read_reply(reader: &mut impl BufRead) -> Result<(u32, String), String> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+        return Err("connection closed by server".to_string());
+    }
+    let code: u32 = line
+        .get(..3)
+        .and_then(|c| c.parse().ok())
+        .ok_or_else(|| format!("malformed FTP reply: {}", line.trim_end()))?;
+    let mut text = line.get(4..).unwrap_or("").trim_end().to_string();
+
+    if line.as_bytes().get(3) == Some(&b'-') {
+        let terminator = format!("{} ", code);
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+                return Err("connection closed by server".to_string());
+            }
+            let done = line.starts_with(&terminator);
+            text.push('\n');
+            text.push_str(line.get(4..).filter(|_| done).unwrap_or(&line).trim_end());
+            if done {
+                break;
+            }
+        }
+    }
+    Ok((code, text))
+}
+
+/// Extracts the data port from `227 Entering Passive Mode (h1,h2,h3,h4,p1,p2)`.
+fn parse_pasv(text: &str) -> Option<u16> {
+    let start = text.find('(')?;
+    let end = text[start..].find(')')? + start;
+    let parts: Vec<u16> = text[start + 1..end]
+        .split(',')
+        .map(|p| p.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    if parts.len() != 6 || parts[4] > 255 || parts[5] > 255 {
+        return None;
+    }
+    Some(parts[4] * 256 + parts[5])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_parse_pasv() {
+        assert_eq!(parse_pasv("Entering Passive Mode (127,0,0,1,195,80)."), Some(50000));
+        assert_eq!(parse_pasv("Entering Passive Mode"), None);
+    }
+
+    #[test]
+    fn test_read_multiline_reply() {
+        let mut reader = "220-Welcome\r\n220-to FTP\r\n220 Ready\r\n".as_bytes();
+        let (code, text) = read_reply(&mut reader).unwrap();
+        assert_eq!(code, 220);
+        assert_eq!(text, "Welcome\n220-to FTP\nReady");
+    }
+
+    #[test]
+    fn test_rejects_command_injection() {
+        // A server that greets, then records every line the client sends.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut tcp, _) = listener.accept().unwrap();
+            tcp.write_all(b"220 ready\r\n").unwrap();
+            let mut received = String::new();
+            tcp.read_to_string(&mut received).unwrap();
+            received
+        });
+
+        let mut client = FtpClient::connect("127.0.0.1", port, false, Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            client.retrieve("report.csv\r\nDELE important.csv").unwrap_err(),
+            "path must not contain control characters"
+        );
+        assert_eq!(
+            client.store("upload.csv\nRMD /", b"data").unwrap_err(),
+            "path must not contain control characters"
+        );
+        assert_eq!(
+            client.login("anonymous\r\nSITE EXEC", "").unwrap_err(),
+            "username must not contain control characters"
+        );
+        drop(client);
+        assert_eq!(server.join().unwrap(), "");
+    }
+}
@@ -0,0 +1,73 @@
+//! Opaque values passed between nodes by reference.
+//!
+//! Some data, such as Arrow tables, is too large or too structured to copy
+//! through JSON on every edge. A node stores it here and outputs a small JSON
+//! handle instead; downstream nodes look the value up by id. Handles belong
+//! to one run's `Runtime` and are dropped with it.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Thread-safe store of type-erased values keyed by numeric id.
+#[derive(Default)]
+pub struct Handles {
+    next: AtomicU64,
+    values: Mutex<HashMap<u64, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl Handles {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores `value` and returns its id.
+    pub fn insert<T: Any + Send + Sync>(&self, value: T) -> u64 {
+        let id = self.next.fetch_add(1, Ordering::Relaxed) + 1;
+        self.lock().insert(id, Arc::new(value));
+        id
+    }
+
+    /// Returns the value under `id` if it exists and has type `T`.
+    pub fn get<T: Any + Send + Sync>(&self, id: u64) -> Option<Arc<T>> {
+        self.lock().get(&id).cloned().and_then(|value| value.downcast::<T>().ok())
+    }
+
+    /// Drops the value under `id`. Returns whether it existed.
+    pub fn remove(&self, id: u64) -> bool {
+        self.lock().remove(&id).is_some()
+    }
+
+    /// Number of stored values.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, Arc<dyn Any + Send + Sync>>> {
+        // A panic while holding the lock cannot leave a HashMap half-updated.
+        self.values.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        let handles = Handles::new();
+        let id = handles.insert(vec![1, 2, 3]);
+        assert_eq!(handles.get::<Vec<i32>>(id).as_deref(), Some(&vec![1, 2, 3]));
+        assert!(handles.get::<String>(id).is_none());
+        assert!(handles.remove(id));
+        assert!(handles.get::<Vec<i32>>(id).is_none());
+        assert!(handles.is_empty());
+    }
+}
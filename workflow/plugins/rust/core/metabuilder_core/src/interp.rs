@@ -0,0 +1,159 @@
+//! A minimal interpreter for hosts that cannot carry the workflow engine.
+//!
+//! Embedded controllers without threads, files, or the JSON loader can
+//! still chain nodes. An `Interpreter` holds executors by node type and runs
+//! a list of `Step`s in the order given, feeding each its params plus the
+//! ports wired to it from earlier steps. There are no branches, loops, or
+//! var store, and the first step that reports an `error` output stops the
+//! run. It builds without `std`.
+
+use crate::executor::{NodeExecutor, Ports};
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, format, string::String, string::ToString, vec::Vec};
+use alloc::collections::BTreeMap;
+use core::fmt;
+use serde_json::Value;
+
+/// One node to run, with its params and the ports wired into it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub id: String,
+    pub node_type: String,
+    pub params: Ports,
+    /// `(from step, from port, to port)` for each wired input
+    pub wires: Vec<(String, String, String)>,
+}
+
+impl Step {
+    /// A step running a node of `node_type`, with no params or wires.
+    pub fn new(id: impl Into<String>, node_type: impl Into<String>) -> Self {
+        Step {
+            id: id.into(),
+            node_type: node_type.into(),
+            params: Ports::new(),
+            wires: Vec::new(),
+        }
+    }
+
+    /// Sets the input `name` to `value`.
+    pub fn param(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.params.insert(name.into(), value);
+        self
+    }
+
+    /// Feeds `from_port` of the earlier step `from` to the input `to_port`,
+    /// replacing any param of that name.
+    pub fn wire(mut self, from: impl Into<String>, from_port: impl Into<String>, to_port: impl Into<String>) -> Self {
+        self.wires.push((from.into(), from_port.into(), to_port.into()));
+        self
+    }
+}
+
+/// Why a run stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// Id of the step that failed
+    pub node: String,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Node {} failed: {}", self.node, self.message)
+    }
+}
+
+/// Runs steps with the executors registered by node type.
+#[derive(Default)]
+pub struct Interpreter {
+    executors: BTreeMap<String, Box<dyn NodeExecutor>>,
+}
+
+impl Interpreter {
+    /// Creates an interpreter with no executors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers (or replaces) the executor for `node_type`.
+    pub fn register(&mut self, node_type: impl Into<String>, executor: impl NodeExecutor + 'static) {
+        self.executors.insert(node_type.into(), Box::new(executor));
+    }
+
+    /// Runs `steps` in order, returning the output ports of each by id.
+    pub fn run(&self, steps: &[Step]) -> Result<BTreeMap<String, Ports>, Failure> {
+        let mut outputs: BTreeMap<String, Ports> = BTreeMap::new();
+        for step in steps {
+            let failed = |message: String| Failure {
+                node: step.id.clone(),
+                message,
+            };
+            if outputs.contains_key(&step.id) {
+                return Err(failed("duplicate step id".to_string()));
+            }
+            let executor = self
+                .executors
+                .get(&step.node_type)
+                .ok_or_else(|| failed(format!("unknown node type {}", step.node_type)))?;
+            let mut inputs = step.params.clone();
+            for (from, from_port, to_port) in &step.wires {
+                let ports = outputs
+                    .get(from)
+                    .ok_or_else(|| failed(format!("reads {}, which has not run", from)))?;
+                inputs.insert(to_port.clone(), ports.get(from_port).cloned().unwrap_or(Value::Null));
+            }
+            let ports = executor.execute(inputs, None);
+            if let Some(error) = ports.get("error").filter(|e| !e.is_null()) {
+                return Err(failed(error.as_str().map(String::from).unwrap_or_else(|| error.to_string())));
+            }
+            outputs.insert(step.id.clone(), ports);
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn add(inputs: Ports, _: Option<&dyn core::any::Any>) -> Ports {
+        let sum: i64 = inputs.values().filter_map(Value::as_i64).sum();
+        Ports::from([("result".to_string(), json!(sum))])
+    }
+
+    fn check(inputs: Ports, _: Option<&dyn core::any::Any>) -> Ports {
+        match inputs["value"].as_i64() {
+            Some(value) if value < 10 => Ports::from([("result".to_string(), json!(value))]),
+            _ => Ports::from([("error".to_string(), json!("too large"))]),
+        }
+    }
+
+    #[test]
+    fn test_run_wired_steps() {
+        let mut interpreter = Interpreter::new();
+        interpreter.register("math.add", add);
+        interpreter.register("test.check", check);
+        let steps = [
+            Step::new("sum", "math.add").param("a", json!(2)).param("b", json!(3)),
+            Step::new("twice", "math.add").wire("sum", "result", "a").wire("sum", "result", "b"),
+            Step::new("check", "test.check").wire("sum", "result", "value"),
+        ];
+        let outputs = interpreter.run(&steps).unwrap();
+        assert_eq!(outputs["twice"]["result"], json!(10));
+        assert_eq!(outputs["check"]["result"], json!(5));
+
+        let failing = [Step::new("check", "test.check").param("value", json!(12))];
+        assert_eq!(interpreter.run(&failing).unwrap_err().to_string(), "Node check failed: too large");
+    }
+
+    #[test]
+    fn test_run_rejects_bad_steps() {
+        let mut interpreter = Interpreter::new();
+        interpreter.register("math.add", add);
+        let early = [Step::new("twice", "math.add").wire("sum", "result", "a")];
+        assert_eq!(interpreter.run(&early).unwrap_err().message, "reads sum, which has not run");
+        let unknown = [Step::new("x", "math.sqrt")];
+        assert_eq!(interpreter.run(&unknown).unwrap_err().message, "unknown node type math.sqrt");
+    }
+}
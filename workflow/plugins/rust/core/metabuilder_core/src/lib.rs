@@ -0,0 +1,104 @@
+//! MetaBuilder workflow core.
+//!
+//! Shared runtime types and helpers used by plugins that need more than
+//! their inputs, such as access to secrets, HTTP auth profiles, a shared
+//! cache, pooled clients, cancellation, run and execution ids, events,
+//! Unicode segmentation, binary values, datetimes, paths, atomic file
+//! writes, compiled regular expressions, random numbers, canonical JSON,
+//! YAML, TOML, Avro encoding, TCP connections with timeouts, optionally in
+//! TLS (with the `tls` feature), SSH sessions (with the `ssh` feature), an
+//! FTP client (with the `ftp` feature), a shared async reactor for
+//! `AsyncNodeExecutor`s (with the `async` feature), or values passed by
+//! handle or streamed in chunks.
+//!
+//! With default features off, the crate builds without `std` (needing only
+//! `alloc`) and keeps the executor traits, the `text` helpers, and the
+//! `interp` interpreter, so pure nodes can run on embedded controllers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod auth;
+#[cfg(feature = "std")]
+pub mod avro;
+#[cfg(feature = "std")]
+pub mod bytes;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod cancel;
+#[cfg(feature = "std")]
+pub mod canonical;
+#[cfg(feature = "std")]
+pub mod clients;
+#[cfg(feature = "std")]
+pub mod correlation;
+#[cfg(feature = "std")]
+pub mod datetime;
+#[cfg(feature = "std")]
+pub mod events;
+#[cfg(feature = "std")]
+pub mod files;
+#[cfg(feature = "ftp")]
+pub mod ftp;
+#[cfg(feature = "std")]
+pub mod handles;
+pub mod interp;
+#[cfg(feature = "std")]
+pub mod net;
+#[cfg(feature = "std")]
+pub mod paths;
+#[cfg(feature = "std")]
+pub mod patterns;
+pub mod executor;
+#[cfg(feature = "std")]
+pub mod random;
+#[cfg(feature = "async")]
+pub mod reactor;
+#[cfg(feature = "std")]
+pub mod runtime;
+#[cfg(feature = "std")]
+pub mod secrets;
+#[cfg(feature = "ssh")]
+pub mod ssh;
+#[cfg(feature = "std")]
+pub mod stream;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod text;
+#[cfg(feature = "std")]
+pub mod toml;
+#[cfg(feature = "std")]
+pub mod yaml;
+
+#[cfg(feature = "std")]
+pub use auth::AuthProfile;
+#[cfg(feature = "std")]
+pub use avro::AvroSchema;
+#[cfg(feature = "std")]
+pub use cache::Cache;
+#[cfg(feature = "std")]
+pub use cancel::Cancellation;
+#[cfg(feature = "std")]
+pub use clients::Clients;
+#[cfg(feature = "std")]
+pub use correlation::Correlation;
+#[cfg(feature = "std")]
+pub use events::{Event, EventBus, Listener};
+#[cfg(feature = "std")]
+pub use handles::Handles;
+pub use executor::{AsyncNodeExecutor, NodeExecutor, Ports, PortsFuture};
+pub use interp::{Interpreter, Step};
+#[cfg(feature = "std")]
+pub use patterns::Patterns;
+#[cfg(feature = "std")]
+pub use random::Random;
+#[cfg(feature = "std")]
+pub use runtime::Runtime;
+#[cfg(feature = "std")]
+pub use secrets::{EnvSecrets, MemorySecrets, SecretsProvider};
+#[cfg(feature = "std")]
+pub use stream::Stream;
+pub use text::TextUnit;
@@ -0,0 +1,83 @@
+//! TCP connections for nodes that speak line protocols (FTP, IMAP, SSH).
+//!
+//! `connect` bounds the connect and then every read and write by a timeout,
+//! so an unreachable or stalled host fails the node instead of hanging the
+//! run. With the `tls` feature, `Stream` carries either a plain connection
+//! or one wrapped in TLS.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+#[cfg(feature = "tls")]
+use native_tls::TlsStream;
+#[cfg(feature = "tls")]
+use std::io::{Read, Write};
+
+/// Connects to the first address of `host` that accepts within `timeout`,
+/// which then also bounds each read and write.
+pub fn connect(host: &str, port: u16, timeout: Duration) -> Result<TcpStream, String> {
+    let addrs = (host, port).to_socket_addrs().map_err(|e| format!("cannot resolve {}: {}", host, e))?;
+    let mut error = format!("{} has no addresses", host);
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(tcp) => {
+                tcp.set_read_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+                tcp.set_write_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+                return Ok(tcp);
+            }
+            Err(e) => error = format!("cannot connect to {}: {}", addr, e),
+        }
+    }
+    Err(error)
+}
+
+/// A connection, optionally wrapped in TLS.
+#[cfg(feature = "tls")]
+pub enum Stream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+#[cfg(feature = "tls")]
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Stream::Plain(s) => s.read(buf),
+            Stream::Tls(s) => s.read(buf),
+        }
+    }
+}
+
+#[cfg(feature = "tls")]
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Stream::Plain(s) => s.write(buf),
+            Stream::Tls(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Stream::Plain(s) => s.flush(),
+            Stream::Tls(s) => s.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let tcp = connect("127.0.0.1", port, Duration::from_millis(200)).unwrap();
+        assert_eq!(tcp.read_timeout().unwrap(), Some(Duration::from_millis(200)));
+
+        drop(listener);
+        assert!(connect("127.0.0.1", port, Duration::from_millis(200)).unwrap_err().starts_with("cannot connect to"));
+    }
+}
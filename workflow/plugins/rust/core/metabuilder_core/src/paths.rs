@@ -0,0 +1,216 @@
+//! OS-independent path helpers.
+//!
+//! Paths are plain strings using `/` as the separator on every platform; `\`
+//! is accepted as a separator on input. A path is absolute if it starts with
+//! a separator or a drive letter (`C:`), which is kept as its first
+//! component.
+
+/// A path split into its root and components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parts {
+    /// `""` for relative paths, `"/"`, or a drive such as `"C:/"`
+    pub root: String,
+    pub components: Vec<String>,
+}
+
+fn is_separator(c: char) -> bool {
+    c == '/' || c == '\\'
+}
+
+/// Splits `path` into its root and non-empty components (`.` and `..` kept).
+pub fn parts(path: &str) -> Parts {
+    let bytes = path.as_bytes();
+    let (root, rest) = if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        let rest = &path[2..];
+        (format!("{}/", &path[..2]), rest.trim_start_matches(is_separator))
+    } else if path.starts_with(is_separator) {
+        ("/".to_string(), path.trim_start_matches(is_separator))
+    } else {
+        (String::new(), path)
+    };
+    let components = rest.split(is_separator).filter(|c| !c.is_empty()).map(String::from).collect();
+    Parts { root, components }
+}
+
+/// Joins a root and components back into a path.
+pub fn format(parts: &Parts) -> String {
+    let body = parts.components.join("/");
+    match (parts.root.is_empty(), body.is_empty()) {
+        (true, true) => ".".to_string(),
+        (_, true) => parts.root.clone(),
+        _ => format!("{}{}", parts.root, body),
+    }
+}
+
+/// Resolves `.` and `..` lexically and cleans up separators. `..` above the
+/// root of an absolute path is dropped; in a relative path it is kept.
+pub fn normalize(path: &str) -> String {
+    let Parts { root, components } = parts(path);
+    let mut kept: Vec<String> = Vec::new();
+    for component in components {
+        match component.as_str() {
+            "." => {}
+            ".." => match kept.last().map(String::as_str) {
+                Some(last) if last != ".." => {
+                    kept.pop();
+                }
+                _ if !root.is_empty() => {}
+                _ => kept.push(component),
+            },
+            _ => kept.push(component),
+        }
+    }
+    format(&Parts { root, components: kept })
+}
+
+/// Joins segments in order; an absolute segment replaces everything before it.
+pub fn join<'a>(segments: impl IntoIterator<Item = &'a str>) -> String {
+    let mut joined = Parts {
+        root: String::new(),
+        components: Vec::new(),
+    };
+    for segment in segments {
+        let next = parts(segment);
+        if !next.root.is_empty() {
+            joined = next;
+        } else {
+            joined.components.extend(next.components);
+        }
+    }
+    format(&joined)
+}
+
+/// The last component, or `""` for a root or empty path.
+pub fn file_name(path: &str) -> String {
+    let parts = parts(path);
+    match parts.components.last().map(String::as_str) {
+        Some(".") | Some("..") | None => String::new(),
+        Some(name) => name.to_string(),
+    }
+}
+
+/// The path without its last component: `"."` for a bare name, and the
+/// root itself for a root.
+pub fn dir_name(path: &str) -> String {
+    let mut parts = parts(path);
+    if !file_name(path).is_empty() {
+        parts.components.pop();
+    }
+    format(&parts)
+}
+
+/// Splits a file name into stem and extension. Leading dots (`.bashrc`) are
+/// part of the stem, and only the last extension is split off.
+pub fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(i) if i > 0 && !name[..i].chars().all(|c| c == '.') => (&name[..i], &name[i + 1..]),
+        _ => (name, ""),
+    }
+}
+
+/// Matches `path` against a glob `pattern`, component by component.
+///
+/// `*` matches within a component, `?` one character, `[abc]`, `[a-z]`, and
+/// `[!abc]` a character class, and a `**` component any number of
+/// components. Both sides are normalized first.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = parts(pattern);
+    let path = parts(&normalize(path));
+    if !pattern.root.eq_ignore_ascii_case(&path.root) {
+        return false;
+    }
+    match_components(&pattern.components, &path.components)
+}
+
+fn match_components(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => (0..=path.len()).any(|skip| match_components(rest, &path[skip..])),
+        Some((first, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                let pattern: Vec<char> = first.chars().collect();
+                let name: Vec<char> = name.chars().collect();
+                match_name(&pattern, &name) && match_components(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_name(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => (0..=name.len()).any(|skip| match_name(&pattern[1..], &name[skip..])),
+        Some('?') => !name.is_empty() && match_name(&pattern[1..], &name[1..]),
+        Some('[') => match (class(pattern), name.first()) {
+            (Some((matches, len)), Some(&c)) => matches(c) && match_name(&pattern[len..], &name[1..]),
+            (None, Some(&c)) => c == '[' && match_name(&pattern[1..], &name[1..]),
+            (_, None) => false,
+        },
+        Some(&p) => name.first() == Some(&p) && match_name(&pattern[1..], &name[1..]),
+    }
+}
+
+/// Parses a `[...]` class at the start of `pattern`, returning a matcher and
+/// the class length, or `None` if the bracket is not closed.
+fn class(pattern: &[char]) -> Option<(impl Fn(char) -> bool, usize)> {
+    let negated = matches!(pattern.get(1), Some('!') | Some('^'));
+    let start = if negated { 2 } else { 1 };
+    // A `]` right after the opening bracket is a literal member.
+    let close = start + pattern[start..].iter().skip(1).position(|&c| c == ']')? + 1;
+    let members: Vec<char> = pattern[start..close].to_vec();
+    let matcher = move |c: char| {
+        let mut i = 0;
+        let mut found = false;
+        while i < members.len() {
+            if i + 2 < members.len() && members[i + 1] == '-' {
+                found |= members[i] <= c && c <= members[i + 2];
+                i += 3;
+            } else {
+                found |= members[i] == c;
+                i += 1;
+            }
+        }
+        found != negated
+    };
+    Some((matcher, close + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_join() {
+        assert_eq!(normalize("a//b/./c/../d/"), "a/b/d");
+        assert_eq!(normalize("C:\\Users\\..\\tmp"), "C:/tmp");
+        assert_eq!(normalize("/../x"), "/x");
+        assert_eq!(normalize("../a/.."), "..");
+        assert_eq!(normalize("a/.."), ".");
+        assert_eq!(join(["a", "b/c", "../d"]), "a/b/c/../d");
+        assert_eq!(join(["a", "/etc", "hosts"]), "/etc/hosts");
+    }
+
+    #[test]
+    fn test_extension() {
+        assert_eq!(split_extension("report.tar.gz"), ("report.tar", "gz"));
+        assert_eq!(split_extension(".bashrc"), (".bashrc", ""));
+        assert_eq!(split_extension("README"), ("README", ""));
+        assert_eq!(file_name("dir/sub/"), "sub");
+        assert_eq!(dir_name("dir/sub/"), "dir");
+        assert_eq!(dir_name("notes.txt"), ".");
+        assert_eq!(dir_name("C:\\"), "C:/");
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("src/**/*.rs", "src/lib.rs"));
+        assert!(glob_match("src/**/*.rs", "src/a/b/mod.rs"));
+        assert!(!glob_match("src/*.rs", "src/a/mod.rs"));
+        assert!(glob_match("data/file-?.[ct]sv", "data\\file-1.csv"));
+        assert!(!glob_match("data/file-?.[!ct]sv", "data/file-1.csv"));
+        assert!(glob_match("logs/[0-9][0-9].log", "logs/42.log"));
+        assert!(glob_match("/var/**", "/var"));
+        assert!(!glob_match("var/**", "/var/log"));
+    }
+}
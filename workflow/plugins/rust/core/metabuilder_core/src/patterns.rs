@@ -0,0 +1,141 @@
+//! Compiled regular expressions shared between nodes.
+//!
+//! Compiling a pattern costs far more than matching a short string with it,
+//! and a `regex.*` node inside a loop sees the same pattern every iteration.
+//! `Patterns` keeps the most recently used compiled patterns, keyed by
+//! pattern and flags, and is reachable through `Runtime::patterns`, which an
+//! engine shares across all its runs.
+//!
+//! Flags are letters: `i` (case-insensitive), `m` (`^` and `$` match at line
+//! breaks), `s` (`.` matches newlines), and `x` (whitespace and `#` comments
+//! in the pattern are ignored).
+
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// How many compiled patterns a cache keeps by default.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// Compiles `pattern` with `flags`, without caching.
+pub fn compile(pattern: &str, flags: &str) -> Result<Regex, String> {
+    let mut builder = RegexBuilder::new(pattern);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            other => return Err(format!("unknown flag {}", other)),
+        };
+    }
+    builder.build().map_err(|e| format!("invalid pattern: {}", e))
+}
+
+/// Thread-safe cache of compiled patterns, evicting the least recently used.
+pub struct Patterns {
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Compiled patterns by (pattern, flags), with the tick of their last use
+    entries: HashMap<(String, String), (Arc<Regex>, u64)>,
+    tick: u64,
+}
+
+impl Patterns {
+    /// Creates a cache holding at most `capacity` patterns.
+    pub fn new(capacity: usize) -> Self {
+        Patterns {
+            capacity: capacity.max(1),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Returns `pattern` compiled with `flags`, compiling it on a miss.
+    /// Invalid patterns are not cached.
+    pub fn get(&self, pattern: &str, flags: &str) -> Result<Arc<Regex>, String> {
+        let key = (pattern.to_string(), flags.to_string());
+        {
+            let mut state = self.lock();
+            state.tick += 1;
+            let tick = state.tick;
+            if let Some((regex, used)) = state.entries.get_mut(&key) {
+                *used = tick;
+                return Ok(regex.clone());
+            }
+        }
+        // Compile outside the lock so a slow pattern does not block others.
+        let regex = Arc::new(compile(pattern, flags)?);
+        let mut state = self.lock();
+        if state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+            let oldest = state.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+        let tick = state.tick;
+        state.entries.insert(key, (regex.clone(), tick));
+        Ok(regex)
+    }
+
+    /// Number of cached patterns.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panic while holding the lock cannot leave a HashMap half-updated.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for Patterns {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reuses_compiled_patterns() {
+        let patterns = Patterns::new(8);
+        let first = patterns.get(r"\d+", "").unwrap();
+        assert!(Arc::ptr_eq(&first, &patterns.get(r"\d+", "").unwrap()));
+        assert!(!Arc::ptr_eq(&first, &patterns.get(r"\d+", "i").unwrap()));
+        assert!(patterns.get("HELLO", "i").unwrap().is_match("hello"));
+        assert_eq!(patterns.len(), 3);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let patterns = Patterns::new(2);
+        let a = patterns.get("a", "").unwrap();
+        patterns.get("b", "").unwrap();
+        patterns.get("a", "").unwrap();
+        patterns.get("c", "").unwrap();
+        assert_eq!(patterns.len(), 2);
+        assert!(Arc::ptr_eq(&a, &patterns.get("a", "").unwrap()));
+        // "b" was evicted, so it compiles again and pushes out "c".
+        patterns.get("b", "").unwrap();
+        assert_eq!(patterns.len(), 2);
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let patterns = Patterns::default();
+        assert!(patterns.get("(a", "").unwrap_err().starts_with("invalid pattern: "));
+        assert_eq!(patterns.get("a", "g").unwrap_err(), "unknown flag g");
+        assert!(patterns.is_empty());
+    }
+}
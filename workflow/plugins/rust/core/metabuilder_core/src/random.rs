@@ -0,0 +1,95 @@
+//! Random numbers shared between the nodes of a run.
+//!
+//! Nodes that need randomness, such as tokens or sampling, draw it from
+//! `Runtime::random` rather than their own generator, so a host can make a
+//! whole run reproducible: `Random::seeded` yields the same sequence for the
+//! same seed, which `Engine::with_seed` uses to replay runs in tests. Either
+//! way the generator is ChaCha20, so its output is fit for secrets; only the
+//! seed makes it predictable.
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::sync::Mutex;
+
+/// Thread-safe random number generator.
+pub struct Random {
+    rng: Mutex<ChaCha20Rng>,
+}
+
+impl Random {
+    /// A generator seeded from the operating system.
+    pub fn new() -> Self {
+        Random {
+            rng: Mutex::new(ChaCha20Rng::from_entropy()),
+        }
+    }
+
+    /// A generator that yields the same sequence for the same `seed`.
+    pub fn seeded(seed: u64) -> Self {
+        Random {
+            rng: Mutex::new(ChaCha20Rng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Fills `buf` with random bytes.
+    pub fn fill(&self, buf: &mut [u8]) {
+        self.lock().fill_bytes(buf);
+    }
+
+    /// `len` random bytes.
+    pub fn bytes(&self, len: usize) -> Vec<u8> {
+        let mut buf = vec![0; len];
+        self.fill(&mut buf);
+        buf
+    }
+
+    /// A uniformly distributed index below `n`, which must not be zero.
+    pub fn below(&self, n: usize) -> usize {
+        self.lock().gen_range(0..n)
+    }
+
+    /// Shuffles `items` in place, every order being equally likely.
+    pub fn shuffle<T>(&self, items: &mut [T]) {
+        let mut rng = self.lock();
+        for i in (1..items.len()).rev() {
+            items.swap(i, rng.gen_range(0..=i));
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ChaCha20Rng> {
+        // A generator cannot be left half-updated by a panic.
+        self.rng.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_is_reproducible() {
+        let (a, b) = (Random::seeded(7), Random::seeded(7));
+        assert_eq!(a.bytes(16), b.bytes(16));
+        assert_eq!(a.below(1000), b.below(1000));
+        let (mut x, mut y) = ((0..20).collect::<Vec<_>>(), (0..20).collect::<Vec<_>>());
+        a.shuffle(&mut x);
+        b.shuffle(&mut y);
+        assert_eq!(x, y);
+        assert_ne!(Random::seeded(8).bytes(16), Random::seeded(7).bytes(16));
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut items: Vec<u32> = (0..50).collect();
+        Random::new().shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+        assert!((0..100).all(|_| Random::new().below(3) < 3));
+    }
+}
@@ -0,0 +1,107 @@
+//! The async runtime that drives `AsyncNodeExecutor`s for synchronous hosts.
+//!
+//! One multi-threaded tokio runtime serves the whole process. Its workers
+//! keep pooled connections alive between nodes, while each node's own future
+//! is polled on the thread that runs the node, so thread-local context such
+//! as `correlation::current` stays visible to it.
+//!
+//! `block_on` must not be called from a task of another tokio runtime;
+//! async hosts call the engine from `spawn_blocking`, as the webhook server
+//! and scheduler do.
+
+use crate::cancel::Cancellation;
+use crate::executor::{AsyncNodeExecutor, NodeExecutor, Ports};
+use std::any::Any;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::runtime::{Builder, Runtime};
+
+/// Worker threads of the shared runtime, which only drive I/O and timers.
+const WORKERS: usize = 2;
+
+/// How often `sleep` checks for cancellation.
+const TICK: Duration = Duration::from_millis(50);
+
+static REACTOR: OnceLock<Runtime> = OnceLock::new();
+
+fn reactor() -> &'static Runtime {
+    REACTOR.get_or_init(|| {
+        Builder::new_multi_thread()
+            .worker_threads(WORKERS)
+            .thread_name("metabuilder-reactor")
+            .enable_all()
+            .build()
+            .expect("the async runtime could not start")
+    })
+}
+
+/// Runs `future` to completion on the current thread, with the shared
+/// runtime's I/O and timers.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    reactor().block_on(future)
+}
+
+/// Waits for `duration`, returning `false` early if `cancel` is cancelled.
+pub async fn sleep(cancel: &Cancellation, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if cancel.is_cancelled() {
+            return false;
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        tokio::time::sleep(left.min(TICK)).await;
+    }
+}
+
+/// Adapts an `AsyncNodeExecutor` for hosts that call `NodeExecutor`s.
+pub struct Blocking<E>(pub E);
+
+impl<E: AsyncNodeExecutor> NodeExecutor for Blocking<E> {
+    fn execute(&self, inputs: Ports, runtime: Option<&dyn Any>) -> Ports {
+        block_on(self.0.execute(inputs, runtime))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::PortsFuture;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    struct Wait;
+
+    impl AsyncNodeExecutor for Wait {
+        fn execute<'a>(&'a self, inputs: Ports, _: Option<&'a dyn Any>) -> PortsFuture<'a> {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ports::from([("result".to_string(), inputs["value"].clone())])
+            })
+        }
+    }
+
+    #[test]
+    fn test_blocking() {
+        let outputs = Blocking(Wait).execute(Ports::from([("value".to_string(), json!(1))]), None);
+        assert_eq!(outputs["result"], json!(1));
+    }
+
+    #[test]
+    fn test_sleep_and_cancel() {
+        let cancel = Arc::new(Cancellation::new());
+        assert!(block_on(sleep(&cancel, Duration::from_millis(5))));
+
+        let canceller = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            canceller.cancel();
+        });
+        let started = Instant::now();
+        assert!(!block_on(sleep(&cancel, Duration::from_secs(30))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
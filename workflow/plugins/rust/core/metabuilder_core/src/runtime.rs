@@ -0,0 +1,104 @@
+//! Runtime context handed to plugins during execution.
+
+use crate::cache::Cache;
+use crate::cancel::Cancellation;
+use crate::clients::Clients;
+use crate::events::EventBus;
+use crate::handles::Handles;
+use crate::patterns::Patterns;
+use crate::random::Random;
+use crate::secrets::{MemorySecrets, SecretsProvider};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Runtime context for plugin execution.
+pub struct Runtime {
+    /// Workflow state storage
+    pub store: HashMap<String, Value>,
+    /// Shared context (clients, config)
+    pub context: HashMap<String, Value>,
+    /// Secrets provider for credentials that must not live in the store
+    pub secrets: Arc<dyn SecretsProvider>,
+    /// Cache shared across nodes and, when the host reuses it, across runs
+    pub cache: Arc<Cache>,
+    /// Large values (e.g. Arrow tables) that nodes pass to each other by handle
+    pub handles: Arc<Handles>,
+    /// Set by the host to stop the run; long waits should observe it
+    pub cancel: Arc<Cancellation>,
+    /// Events published by nodes of the run and any runs nested in it
+    pub events: Arc<EventBus>,
+    /// Compiled regular expressions, shared like `cache`
+    pub patterns: Arc<Patterns>,
+    /// Clients such as HTTP connection pools, shared like `cache`
+    pub clients: Arc<Clients>,
+    /// Random numbers for the nodes of the run, seeded for replays
+    pub random: Arc<Random>,
+    /// When the run times out, if the engine has a timeout; long waits
+    /// should end by it
+    pub deadline: Option<Instant>,
+}
+
+impl Runtime {
+    /// Creates a runtime with an empty store and no secrets.
+    pub fn new() -> Self {
+        Self::with_secrets(MemorySecrets::new())
+    }
+
+    /// Creates a runtime backed by the given secrets provider.
+    pub fn with_secrets(secrets: impl SecretsProvider + 'static) -> Self {
+        Self::with_shared_secrets(Arc::new(secrets))
+    }
+
+    /// Creates a runtime sharing a secrets provider with other runtimes.
+    pub fn with_shared_secrets(secrets: Arc<dyn SecretsProvider>) -> Self {
+        Runtime {
+            store: HashMap::new(),
+            context: HashMap::new(),
+            secrets,
+            cache: Arc::new(Cache::new()),
+            handles: Arc::new(Handles::new()),
+            cancel: Arc::new(Cancellation::new()),
+            events: Arc::new(EventBus::new()),
+            patterns: Arc::new(Patterns::default()),
+            clients: Arc::new(Clients::new()),
+            random: Arc::new(Random::new()),
+            deadline: None,
+        }
+    }
+
+    /// Downcasts the opaque runtime passed to `NodeExecutor::execute`.
+    pub fn from_any(runtime: Option<&dyn Any>) -> Option<&Runtime> {
+        runtime.and_then(|rt| rt.downcast_ref::<Runtime>())
+    }
+
+    /// Looks up a secret by name.
+    pub fn secret(&self, name: &str) -> Option<String> {
+        self.secrets.get(name)
+    }
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_any() {
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("token", "abc");
+        let runtime = Runtime::with_secrets(secrets);
+
+        let any: &dyn Any = &runtime;
+        let rt = Runtime::from_any(Some(any)).unwrap();
+        assert_eq!(rt.secret("token"), Some("abc".to_string()));
+        assert!(Runtime::from_any(None).is_none());
+    }
+}
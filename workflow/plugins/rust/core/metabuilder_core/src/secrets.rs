@@ -0,0 +1,96 @@
+//! Secrets providers.
+//!
+//! Plugins reference credentials by name (e.g. `key_secret: "deploy_key"`)
+//! and resolve them through the runtime's provider, so key material never
+//! appears in workflow JSON or the variable store.
+
+use std::collections::HashMap;
+
+/// Source of named secrets.
+pub trait SecretsProvider: Send + Sync {
+    /// Returns the secret value for `name`, if known.
+    fn get(&self, name: &str) -> Option<String>;
+}
+
+/// In-memory secrets, mainly for embedding hosts and tests.
+#[derive(Default)]
+pub struct MemorySecrets {
+    values: HashMap<String, String>,
+}
+
+impl MemorySecrets {
+    /// Creates an empty provider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a secret under `name`.
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+}
+
+impl SecretsProvider for MemorySecrets {
+    fn get(&self, name: &str) -> Option<String> {
+        self.values.get(name).cloned()
+    }
+}
+
+/// Reads secrets from environment variables.
+///
+/// A secret named `deploy_key` is read from `METABUILDER_SECRET_DEPLOY_KEY`
+/// with the default prefix.
+pub struct EnvSecrets {
+    prefix: String,
+}
+
+impl EnvSecrets {
+    /// Creates a provider using the `METABUILDER_SECRET_` prefix.
+    pub fn new() -> Self {
+        Self::with_prefix("METABUILDER_SECRET_")
+    }
+
+    /// Creates a provider using a custom variable prefix.
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self { prefix: prefix.into() }
+    }
+
+    fn var_name(&self, name: &str) -> String {
+        let suffix: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+            .collect();
+        format!("{}{}", self.prefix, suffix)
+    }
+}
+
+impl Default for EnvSecrets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretsProvider for EnvSecrets {
+    fn get(&self, name: &str) -> Option<String> {
+        std::env::var(self.var_name(name)).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_secrets() {
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("api_key", "secret");
+        assert_eq!(secrets.get("api_key"), Some("secret".to_string()));
+        assert_eq!(secrets.get("missing"), None);
+    }
+
+    #[test]
+    fn test_env_var_name() {
+        let secrets = EnvSecrets::new();
+        assert_eq!(secrets.var_name("deploy-key"), "METABUILDER_SECRET_DEPLOY_KEY");
+    }
+}
@@ -0,0 +1,120 @@
+//! SSH sessions shared by the `remote` nodes.
+
+use crate::runtime::Runtime;
+use serde_json::Value;
+use ssh2::Session;
+use std::any::Any;
+use std::collections::HashMap;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Opens an SSH session to `host` (on `port`, default 22) as `username`,
+/// authenticated with the private key in the secret named `key_secret` and,
+/// if set, the passphrase in the secret named `passphrase_secret`.
+///
+/// `timeout_ms` (default 30 seconds) bounds the TCP connect and then each
+/// blocking SSH operation on the session, so an unreachable or stalled host
+/// fails the node instead of hanging the run.
+pub fn connect(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Session, String> {
+    let host: String = inputs
+        .get("host")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("host is required")?;
+    let port: u16 = inputs
+        .get("port")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(22);
+    let username: String = inputs
+        .get("username")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("username is required")?;
+    let key_secret: String = inputs
+        .get("key_secret")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("key_secret is required")?;
+    let timeout_ms: u64 = inputs
+        .get("timeout_ms")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(30_000)
+        .clamp(1, u32::MAX as u64);
+
+    let rt = Runtime::from_any(runtime).ok_or("runtime with secrets provider is required")?;
+    let private_key = rt
+        .secret(&key_secret)
+        .ok_or_else(|| format!("secret not found: {}", key_secret))?;
+    let passphrase = inputs
+        .get("passphrase_secret")
+        .and_then(|v| v.as_str())
+        .and_then(|name| rt.secret(name));
+
+    let tcp = connect_tcp(&host, port, Duration::from_millis(timeout_ms))?;
+    let mut session = Session::new().map_err(|e| e.to_string())?;
+    session.set_timeout(timeout_ms as u32);
+    session.set_tcp_stream(tcp);
+    session.handshake().map_err(|e| e.to_string())?;
+    session
+        .userauth_pubkey_memory(&username, None, &private_key, passphrase.as_deref())
+        .map_err(|e| e.to_string())?;
+    Ok(session)
+}
+
+/// Connects to the first address of `host` that accepts within `timeout`.
+fn connect_tcp(host: &str, port: u16, timeout: Duration) -> Result<TcpStream, String> {
+    let addrs = (host, port).to_socket_addrs().map_err(|e| format!("cannot resolve {}: {}", host, e))?;
+    let mut error = format!("{} has no addresses", host);
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(tcp) => return Ok(tcp),
+            Err(e) => error = format!("cannot connect to {}: {}", addr, e),
+        }
+    }
+    Err(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemorySecrets;
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    fn inputs(port: u16) -> HashMap<String, Value> {
+        HashMap::from([
+            ("host".to_string(), json!("127.0.0.1")),
+            ("port".to_string(), json!(port)),
+            ("username".to_string(), json!("deploy")),
+            ("key_secret".to_string(), json!("deploy_key")),
+            ("timeout_ms".to_string(), json!(200)),
+        ])
+    }
+
+    #[test]
+    fn test_connect_errors() {
+        let error = |inputs: &HashMap<String, Value>, runtime: &Runtime| connect(inputs, Some(runtime)).err().unwrap();
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("deploy_key", "not a key");
+        let runtime = Runtime::with_secrets(secrets);
+
+        let mut missing = inputs(22);
+        missing.remove("username");
+        assert_eq!(error(&missing, &runtime), "username is required");
+        assert_eq!(error(&inputs(22), &Runtime::new()), "secret not found: deploy_key");
+
+        // A port nothing listens on refuses the connection.
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        assert!(error(&inputs(port), &runtime).starts_with("cannot connect to 127.0.0.1:"));
+    }
+
+    #[test]
+    fn test_handshake_timeout() {
+        // A server that accepts but never speaks SSH fails within timeout_ms.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("deploy_key", "not a key");
+        let started = std::time::Instant::now();
+        assert!(connect(&inputs(port), Some(&Runtime::with_secrets(secrets))).is_err());
+        assert!(started.elapsed() < Duration::from_secs(10));
+        drop(listener);
+    }
+}
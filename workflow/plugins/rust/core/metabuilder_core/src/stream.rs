@@ -0,0 +1,64 @@
+//! Chunked values streamed between nodes.
+//!
+//! A node producing a long sequence, such as the lines of a large file, can
+//! output a stream handle instead of a list: `{"stream": id}`, naming a
+//! `Stream` in the run's `Handles`. Over a streaming edge the engine pulls
+//! one chunk at a time and runs the downstream nodes on each, so the whole
+//! sequence is never held in memory or in the var store.
+
+use crate::handles::Handles;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+type Chunks = Box<dyn Iterator<Item = Result<Value, String>> + Send>;
+
+/// A lazily produced sequence of chunks, each read once.
+pub struct Stream {
+    chunks: Mutex<Chunks>,
+}
+
+impl Stream {
+    /// Creates a stream yielding the items of `chunks`; an `Err` item ends
+    /// the stream with that error.
+    pub fn new(chunks: impl Iterator<Item = Result<Value, String>> + Send + 'static) -> Self {
+        Stream {
+            chunks: Mutex::new(Box::new(chunks)),
+        }
+    }
+
+    /// Stores the stream in `handles` and returns its JSON handle.
+    pub fn into_handle(self, handles: &Handles) -> Value {
+        serde_json::json!({ "stream": handles.insert(self) })
+    }
+
+    /// The stream named by `handle`, if it is a stream handle stored in
+    /// `handles`.
+    pub fn from_handle(handles: &Handles, handle: &Value) -> Option<Arc<Stream>> {
+        let id = handle.as_object().filter(|h| h.len() == 1)?.get("stream")?.as_u64()?;
+        handles.get::<Stream>(id)
+    }
+
+    /// Pulls the next chunk, or `None` once the stream is exhausted.
+    pub fn next_chunk(&self) -> Option<Result<Value, String>> {
+        self.chunks.lock().unwrap_or_else(|e| e.into_inner()).next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_handle_round_trip() {
+        let handles = Handles::new();
+        let handle = Stream::new((1..=2).map(|i| Ok(json!(i)))).into_handle(&handles);
+        let stream = Stream::from_handle(&handles, &handle).unwrap();
+
+        assert_eq!(stream.next_chunk(), Some(Ok(json!(1))));
+        assert_eq!(stream.next_chunk(), Some(Ok(json!(2))));
+        assert_eq!(stream.next_chunk(), None);
+        assert!(Stream::from_handle(&handles, &json!({"stream": 99})).is_none());
+        assert!(Stream::from_handle(&handles, &json!([1, 2])).is_none());
+    }
+}
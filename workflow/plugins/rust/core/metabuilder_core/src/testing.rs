@@ -0,0 +1,67 @@
+//! Helpers for plugin tests, with the `testing` feature.
+//!
+//! Plugin crates enable it on their `metabuilder_core` dev-dependency so
+//! their tests can write inputs as `json!` objects, work in a scratch
+//! directory, and hold secrets without repeating the setup in every crate.
+
+use crate::runtime::Runtime;
+use crate::secrets::MemorySecrets;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Node inputs from a JSON object: `inputs(json!({"path": "a.txt"}))`.
+///
+/// # Panics
+///
+/// Panics if `value` is not an object.
+pub fn inputs(value: Value) -> HashMap<String, Value> {
+    match value {
+        Value::Object(ports) => ports.into_iter().collect(),
+        other => panic!("inputs must be an object, got {}", other),
+    }
+}
+
+/// An empty directory for the test `name`, under the system temp directory
+/// and unique to this process. Anything left from an earlier run is removed.
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("metabuilder-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("cannot create scratch directory");
+    dir
+}
+
+/// A runtime whose secrets provider holds `secrets`, as name and value pairs.
+pub fn runtime(secrets: &[(&str, &str)]) -> Runtime {
+    let mut provider = MemorySecrets::new();
+    for (name, value) in secrets {
+        provider.insert(*name, *value);
+    }
+    Runtime::with_secrets(provider)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_inputs() {
+        let ports = inputs(json!({"path": "a.txt", "limit": 2}));
+        assert_eq!(ports.len(), 2);
+        assert_eq!(ports["path"], "a.txt");
+        assert!(std::panic::catch_unwind(|| inputs(json!([1]))).is_err());
+    }
+
+    #[test]
+    fn test_scratch_and_runtime() {
+        let dir = scratch("testing");
+        std::fs::write(dir.join("left.txt"), "x").unwrap();
+        assert_eq!(scratch("testing").read_dir().unwrap().count(), 0);
+        std::fs::remove_dir_all(dir).unwrap();
+
+        let runtime = runtime(&[("token", "s3cret")]);
+        assert_eq!(runtime.secret("token").as_deref(), Some("s3cret"));
+        assert_eq!(runtime.secret("other"), None);
+    }
+}
@@ -0,0 +1,130 @@
+//! Unicode-aware string measurement and slicing.
+//!
+//! String nodes accept a `unit` input selecting how positions are counted:
+//! UTF-8 bytes, Unicode scalar values (`chars`), or extended grapheme
+//! clusters, which keep emoji sequences and combining marks intact.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+use core::str::FromStr;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Unit used to count string positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextUnit {
+    Bytes,
+    Chars,
+    Graphemes,
+}
+
+impl FromStr for TextUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bytes" => Ok(TextUnit::Bytes),
+            "chars" => Ok(TextUnit::Chars),
+            "graphemes" => Ok(TextUnit::Graphemes),
+            other => Err(format!("unknown unit: {} (expected bytes, chars, or graphemes)", other)),
+        }
+    }
+}
+
+/// Byte offsets of every unit boundary in `s`, including `0` and `s.len()`.
+fn boundaries(s: &str, unit: TextUnit) -> Vec<usize> {
+    let mut offsets: Vec<usize> = match unit {
+        TextUnit::Bytes => (0..s.len()).collect(),
+        TextUnit::Chars => s.char_indices().map(|(i, _)| i).collect(),
+        TextUnit::Graphemes => s.grapheme_indices(true).map(|(i, _)| i).collect(),
+    };
+    offsets.push(s.len());
+    offsets
+}
+
+/// Resolves a possibly negative index against `len`, clamping to `0..=len`.
+fn clamp_index(index: i64, len: usize) -> usize {
+    let len = len as i64;
+    let resolved = if index < 0 { len + index } else { index };
+    resolved.clamp(0, len) as usize
+}
+
+/// Number of units in `s`.
+pub fn length(s: &str, unit: TextUnit) -> usize {
+    match unit {
+        TextUnit::Bytes => s.len(),
+        TextUnit::Chars => s.chars().count(),
+        TextUnit::Graphemes => s.graphemes(true).count(),
+    }
+}
+
+/// Slices `s` by unit positions. Negative indices count from the end.
+///
+/// Fails only for byte slices that would split a multi-byte character.
+pub fn slice(s: &str, start: i64, end: Option<i64>, unit: TextUnit) -> Result<&str, String> {
+    let offsets = boundaries(s, unit);
+    let count = offsets.len() - 1;
+    let start = clamp_index(start, count);
+    let end = end.map_or(count, |e| clamp_index(e, count));
+    if start >= end {
+        return Ok("");
+    }
+
+    let (from, to) = (offsets[start], offsets[end]);
+    if !s.is_char_boundary(from) || !s.is_char_boundary(to) {
+        return Err(format!("byte range {}..{} splits a multi-byte character", from, to));
+    }
+    Ok(&s[from..to])
+}
+
+/// The unit at `index` (negative counts from the end), or `None` when out of range.
+///
+/// In `bytes` mode the result is `None` unless the byte is a whole character.
+pub fn at(s: &str, index: i64, unit: TextUnit) -> Option<&str> {
+    let offsets = boundaries(s, unit);
+    let count = (offsets.len() - 1) as i64;
+    let index = if index < 0 { count + index } else { index };
+    if index < 0 || index >= count {
+        return None;
+    }
+    let (from, to) = (offsets[index as usize], offsets[index as usize + 1]);
+    s.get(from..to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAMILY: &str = "a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}e\u{301}";
+
+    #[test]
+    fn test_length_units() {
+        assert_eq!(length(FAMILY, TextUnit::Bytes), 22);
+        assert_eq!(length(FAMILY, TextUnit::Chars), 8);
+        assert_eq!(length(FAMILY, TextUnit::Graphemes), 3);
+    }
+
+    #[test]
+    fn test_slice_graphemes() {
+        assert_eq!(slice(FAMILY, 1, Some(2), TextUnit::Graphemes).unwrap(), "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}");
+        assert_eq!(slice(FAMILY, -1, None, TextUnit::Graphemes).unwrap(), "e\u{301}");
+    }
+
+    #[test]
+    fn test_slice_bytes_rejects_split_char() {
+        assert_eq!(slice("héllo", 0, Some(1), TextUnit::Bytes).unwrap(), "h");
+        assert!(slice("héllo", 0, Some(2), TextUnit::Bytes).is_err());
+    }
+
+    #[test]
+    fn test_at() {
+        assert_eq!(at(FAMILY, -1, TextUnit::Graphemes), Some("e\u{301}"));
+        assert_eq!(at(FAMILY, -1, TextUnit::Chars), Some("\u{301}"));
+        assert_eq!(at(FAMILY, 3, TextUnit::Graphemes), None);
+    }
+
+    #[test]
+    fn test_parse_unit() {
+        assert_eq!("graphemes".parse::<TextUnit>(), Ok(TextUnit::Graphemes));
+        assert!("words".parse::<TextUnit>().is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! TOML documents as JSON values, parsed with the `toml` crate.
+//!
+//! Tables become objects and arrays of tables become lists of objects.
+//! Dates and times have no JSON equivalent and are kept as RFC 3339
+//! strings; `inf` and `nan` floats have none either and are an error.
+//! Errors end with `at line N`.
+//!
+//! `to_string` writes an object back as TOML with the same crate: plain keys
+//! first, then a `[table]` section per nested object and a `[[table]]`
+//! section per item of a list of objects. TOML has no null, so values
+//! holding one are refused, naming where, and date strings are written as
+//! strings.
+
+use serde_json::{Map, Number, Value};
+
+/// Parses a TOML document into an object.
+pub fn parse(text: &str) -> Result<Value, String> {
+    let text = text.trim_start_matches('\u{feff}');
+    let table: toml::Table = text.parse().map_err(|e: toml::de::Error| {
+        let line = e.span().map_or(1, |span| text[..span.start].matches('\n').count() + 1);
+        format!("{} at line {}", e.message().trim_end(), line)
+    })?;
+    json(toml::Value::Table(table), "")
+}
+
+/// A TOML value as JSON; `path` names it in errors.
+fn json(value: toml::Value, path: &str) -> Result<Value, String> {
+    Ok(match value {
+        toml::Value::String(text) => Value::String(text),
+        toml::Value::Integer(n) => Value::from(n),
+        toml::Value::Float(n) => match Number::from_f64(n) {
+            Some(n) => Value::Number(n),
+            None => return Err(format!("{} has no JSON value, found at {}", n, path)),
+        },
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(items) => {
+            Value::Array(items.into_iter().map(|item| json(item, path)).collect::<Result<_, _>>()?)
+        }
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| {
+                    let path = match path.is_empty() {
+                        true => key.clone(),
+                        false => format!("{}.{}", path, key),
+                    };
+                    json(value, &path).map(|value| (key, value))
+                })
+                .collect::<Result<Map<_, _>, _>>()?,
+        ),
+    })
+}
+
+/// Writes an object as a TOML document.
+pub fn to_string(value: &Value) -> Result<String, String> {
+    let toml::Value::Table(table) = value_of(value, &mut Vec::new())? else {
+        return Err("a TOML document must be an object".to_string());
+    };
+    toml::to_string(&table).map_err(|e| e.to_string())
+}
+
+/// A JSON value as TOML; `path` names it in errors.
+fn value_of(value: &Value, path: &mut Vec<String>) -> Result<toml::Value, String> {
+    Ok(match value {
+        Value::Null => return Err(format!("TOML has no null value, found at {}", path.join("."))),
+        Value::Bool(b) => toml::Value::Boolean(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(n) => toml::Value::Integer(n),
+            None => toml::Value::Float(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(text) => toml::Value::String(text.clone()),
+        Value::Array(items) => {
+            toml::Value::Array(items.iter().map(|item| value_of(item, path)).collect::<Result<_, _>>()?)
+        }
+        Value::Object(map) => {
+            let mut table = toml::Table::new();
+            for (key, value) in map {
+                path.push(key.clone());
+                table.insert(key.clone(), value_of(value, path)?);
+                path.pop();
+            }
+            toml::Value::Table(table)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tables_and_keys() {
+        let text = r#"
+# front matter
+title = "Hello"
+tags = ["a", 'b',
+  "c", # trailing comma
+]
+site.url = "https://example.com"
+
+[author]
+name = "Ada"
+"quoted key" = 1
+
+[[posts]]
+id = 1
+[[posts]]
+id = 2
+[posts.meta]
+draft = true
+
+[owner.contact]
+inline = { email = "a@b.c", points = { x = 1, y = 2 } }
+"#;
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({
+                "title": "Hello",
+                "tags": ["a", "b", "c"],
+                "site": {"url": "https://example.com"},
+                "author": {"name": "Ada", "quoted key": 1},
+                "posts": [{"id": 1}, {"id": 2, "meta": {"draft": true}}],
+                "owner": {"contact": {"inline": {"email": "a@b.c", "points": {"x": 1, "y": 2}}}},
+            })
+        );
+    }
+
+    #[test]
+    fn test_values() {
+        let text = "a = 1_000\nb = -0x10\nc = 0b101\nd = 6.02e23\ne = -1.5\nf = 1979-05-27T07:32:00Z\ng = 1979-05-27 07:32:00\nh = 07:32:00\ni = \"tab\\t\\u00e9\"\nj = 'C:\\path'\n";
+        // Hex numbers take no sign in TOML.
+        assert!(parse(text).is_err());
+        let text = text.replace("-0x10", "0x10");
+        assert_eq!(
+            parse(&text).unwrap(),
+            json!({
+                "a": 1000, "b": 16, "c": 5, "d": 6.02e23, "e": -1.5,
+                "f": "1979-05-27T07:32:00Z", "g": "1979-05-27T07:32:00", "h": "07:32:00",
+                "i": "tab\té", "j": "C:\\path",
+            })
+        );
+    }
+
+    #[test]
+    fn test_multiline_strings() {
+        let text = "a = \"\"\"\nline one\n  line two\"\"\"\nb = \"\"\"joined \\\n    here\"\"\"\nc = '''\nraw \\n'''\n";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({"a": "line one\n  line two", "b": "joined here", "c": "raw \\n"})
+        );
+    }
+
+    #[test]
+    fn test_to_string() {
+        let value = json!({
+            "package": {"name": "app", "version": "0.1.0", "authors": ["Ada <a@b.c>"]},
+            "dependencies": {"serde": {"version": "1", "features": ["derive"]}, "regex": "1"},
+            "bin": [{"name": "a", "path": "src/a.rs"}, {"name": "b", "meta": {"tab\tkey": 1.5}}],
+            "ratio": 0.25,
+            "empty": {},
+            "mixed": [1, {"x": true}, []],
+        });
+        let text = to_string(&value).unwrap();
+        assert_eq!(
+            text,
+            "mixed = [1, { x = true }, []]\nratio = 0.25\n\n[[bin]]\nname = \"a\"\npath = \"src/a.rs\"\n\n\
+             [[bin]]\nname = \"b\"\n\n[bin.meta]\n\"tab\\tkey\" = 1.5\n\n[dependencies]\nregex = \"1\"\n\n\
+             [dependencies.serde]\nfeatures = [\"derive\"]\nversion = \"1\"\n\n[empty]\n\n\
+             [package]\nauthors = [\"Ada <a@b.c>\"]\nname = \"app\"\nversion = \"0.1.0\"\n"
+        );
+        assert_eq!(parse(&text).unwrap(), value);
+        // Tables holding only tables are implied.
+        assert_eq!(to_string(&json!({"a": {"b": {"c": 1}}})).unwrap(), "[a.b]\nc = 1\n");
+    }
+
+    #[test]
+    fn test_to_string_errors() {
+        assert_eq!(to_string(&json!([1])).unwrap_err(), "a TOML document must be an object");
+        assert_eq!(
+            to_string(&json!({"a": {"b": [1, null]}})).unwrap_err(),
+            "TOML has no null value, found at a.b"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse("a = 1\na = 2\n").unwrap_err(), "duplicate key at line 2");
+        assert_eq!(parse("[t]\n[t]\n").unwrap_err(), "duplicate key at line 2");
+        assert_eq!(parse("a = 01\n").unwrap_err(), "unexpected leading zero, expected nothing at line 1");
+        assert_eq!(parse("a = \"open\n").unwrap_err(), "invalid basic string, expected `\"` at line 1");
+        assert!(parse("a = 1 b = 2\n").unwrap_err().ends_with("at line 1"));
+        assert_eq!(parse("[t]\na = [inf]\n").unwrap_err(), "inf has no JSON value, found at t.a");
+        assert_eq!(parse("a = nan\n").unwrap_err(), "NaN has no JSON value, found at a");
+    }
+}
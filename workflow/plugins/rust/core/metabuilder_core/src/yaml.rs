@@ -0,0 +1,397 @@
+//! YAML documents as JSON values, parsed with `yaml-rust2`.
+//!
+//! `parse` reads one YAML 1.2 document: block and flow collections, every
+//! scalar style, comments, anchors and aliases, and `<<` merge keys. Plain
+//! scalars are resolved with the core schema: `null`/`~`, `true`/`false`,
+//! integers, and floats; anything else is a string. Scalar keys are kept as
+//! written, so `1: a` has the key `"1"`. What JSON cannot hold is an error
+//! rather than a guess: more than one document, collections as keys, tags
+//! other than the core `!!str`, `!!int`, `!!float`, `!!bool`, `!!null`,
+//! `!!seq`, and `!!map`, and `.inf` or `.nan`. Errors end with
+//! `at line N`.
+//!
+//! `to_string` writes block style with `yaml-rust2`'s emitter, quoting the
+//! strings that would otherwise read as something else, YAML 1.1 booleans
+//! such as `on` included.
+
+use serde_json::{Map, Number, Value};
+use std::collections::{HashMap, HashSet};
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser, Tag};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+use yaml_rust2::{Yaml, YamlEmitter};
+
+/// The handle `!!` tags expand to.
+const CORE: &str = "tag:yaml.org,2002:";
+
+/// Parses a YAML document. An empty document is `null`.
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut builder = Builder::default();
+    Parser::new_from_str(text.trim_start_matches('\u{feff}'))
+        .load(&mut builder, true)
+        .map_err(|e| format!("{} at line {}", e.info(), e.marker().line()))?;
+    if let Some(error) = builder.error {
+        return Err(error);
+    }
+    match builder.documents.len() {
+        0 => Ok(Value::Null),
+        1 => Ok(builder.documents.remove(0)),
+        _ => Err("multi-document streams are not supported".to_string()),
+    }
+}
+
+/// Writes `value` as a block-style YAML document.
+pub fn to_string(value: &Value) -> String {
+    let mut out = String::new();
+    YamlEmitter::new(&mut out).dump(&yaml(value)).expect("writing to a String cannot fail");
+    // The emitter starts every document with `---` and ends without a newline.
+    let mut out = out.strip_prefix("---\n").map(String::from).unwrap_or(out);
+    out.push('\n');
+    out
+}
+
+/// A JSON value as a YAML node.
+fn yaml(value: &Value) -> Yaml {
+    match value {
+        Value::Null => Yaml::Null,
+        Value::Bool(b) => Yaml::Boolean(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(n) => Yaml::Integer(n),
+            None => Yaml::Real(n.to_string()),
+        },
+        Value::String(text) => Yaml::String(text.clone()),
+        Value::Array(items) => Yaml::Array(items.iter().map(yaml).collect()),
+        Value::Object(map) => {
+            Yaml::Hash(map.iter().map(|(key, value)| (Yaml::String(key.clone()), yaml(value))).collect())
+        }
+    }
+}
+
+/// A collection being read.
+enum Frame {
+    Sequence {
+        items: Vec<Value>,
+        anchor: usize,
+    },
+    Mapping {
+        map: Map<String, Value>,
+        /// The key read, awaiting its value.
+        key: Option<String>,
+        /// Keys that came from `<<` merges, which explicit keys override.
+        merged: HashSet<String>,
+        anchor: usize,
+    },
+}
+
+/// Builds JSON values from parser events.
+#[derive(Default)]
+struct Builder {
+    stack: Vec<Frame>,
+    anchors: HashMap<usize, Value>,
+    /// The current document's root, once read.
+    root: Option<Value>,
+    documents: Vec<Value>,
+    error: Option<String>,
+}
+
+impl MarkedEventReceiver for Builder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        if self.error.is_none() {
+            if let Err(message) = self.event(event) {
+                self.error = Some(format!("{} at line {}", message, mark.line()));
+            }
+        }
+    }
+}
+
+impl Builder {
+    fn event(&mut self, event: Event) -> Result<(), String> {
+        match event {
+            Event::DocumentEnd => {
+                let root = self.root.take().unwrap_or(Value::Null);
+                self.documents.push(root);
+            }
+            Event::Alias(id) => {
+                let value = self.anchors.get(&id).cloned().ok_or("unknown alias")?;
+                self.value(value, 0)?;
+            }
+            Event::Scalar(text, style, anchor, tag) => {
+                let awaiting_key = matches!(self.stack.last(), Some(Frame::Mapping { key: None, .. }));
+                let value = match awaiting_key {
+                    true => Value::String(text),
+                    false => scalar(text, style, tag)?,
+                };
+                self.value(value, anchor)?;
+            }
+            Event::SequenceStart(anchor, tag) => {
+                collection_tag(tag, "seq")?;
+                self.key_is_scalar()?;
+                self.stack.push(Frame::Sequence {
+                    items: Vec::new(),
+                    anchor,
+                });
+            }
+            Event::MappingStart(anchor, tag) => {
+                collection_tag(tag, "map")?;
+                self.key_is_scalar()?;
+                self.stack.push(Frame::Mapping {
+                    map: Map::new(),
+                    key: None,
+                    merged: HashSet::new(),
+                    anchor,
+                });
+            }
+            Event::SequenceEnd | Event::MappingEnd => match self.stack.pop() {
+                Some(Frame::Sequence { items, anchor }) => self.value(Value::Array(items), anchor)?,
+                Some(Frame::Mapping { map, anchor, .. }) => self.value(Value::Object(map), anchor)?,
+                None => {}
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Fails when a collection starts where a mapping key belongs.
+    fn key_is_scalar(&self) -> Result<(), String> {
+        match self.stack.last() {
+            Some(Frame::Mapping { key: None, .. }) => Err("mapping keys must be scalars".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds a finished node to the collection being read, or makes it the root.
+    fn value(&mut self, value: Value, anchor: usize) -> Result<(), String> {
+        if anchor > 0 {
+            self.anchors.insert(anchor, value.clone());
+        }
+        match self.stack.last_mut() {
+            None => self.root = Some(value),
+            Some(Frame::Sequence { items, .. }) => items.push(value),
+            Some(Frame::Mapping { key: key @ None, .. }) => match value {
+                Value::String(text) => *key = Some(text),
+                Value::Array(_) | Value::Object(_) => return Err("mapping keys must be scalars".to_string()),
+                other => *key = Some(other.to_string()),
+            },
+            Some(Frame::Mapping { map, key, merged, .. }) => {
+                let key = key.take().unwrap_or_default();
+                if key == "<<" {
+                    return merge(map, merged, value);
+                }
+                if map.contains_key(&key) && !merged.remove(&key) {
+                    return Err(format!("duplicate key {}", key));
+                }
+                map.insert(key, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Applies a `<<` merge key: the entries of a mapping, or of each mapping in
+/// a sequence, that `map` does not already have.
+fn merge(map: &mut Map<String, Value>, merged: &mut HashSet<String>, value: Value) -> Result<(), String> {
+    let sources = match value {
+        Value::Object(source) => vec![source],
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Value::Object(source) => Ok(source),
+                _ => Err("<< merges mappings only".to_string()),
+            })
+            .collect::<Result<_, _>>()?,
+        _ => return Err("<< merges mappings only".to_string()),
+    };
+    for (key, value) in sources.into_iter().flatten() {
+        if !map.contains_key(&key) {
+            merged.insert(key.clone());
+            map.insert(key, value);
+        }
+    }
+    Ok(())
+}
+
+/// Fails on a collection tag other than the core one for its kind.
+fn collection_tag(tag: Option<Tag>, kind: &str) -> Result<(), String> {
+    match tag {
+        Some(tag) if !(tag.handle == CORE && tag.suffix == kind) => Err(format!("unsupported tag {}", show(&tag))),
+        _ => Ok(()),
+    }
+}
+
+fn show(tag: &Tag) -> String {
+    match tag.handle == CORE {
+        true => format!("!!{}", tag.suffix),
+        false => format!("{}{}", tag.handle, tag.suffix),
+    }
+}
+
+/// A scalar's value: a string if quoted or tagged `!!str`, else resolved
+/// with the core schema, or as its core tag says.
+fn scalar(text: String, style: TScalarStyle, tag: Option<Tag>) -> Result<Value, String> {
+    let Some(tag) = tag else {
+        return match style {
+            TScalarStyle::Plain => resolve(&text),
+            _ => Ok(Value::String(text)),
+        };
+    };
+    let value = match (tag.handle == CORE, tag.suffix.as_str()) {
+        (true, "str") => return Ok(Value::String(text)),
+        (true, "int") => text.parse::<i64>().ok().map(Value::from),
+        (true, "float") => Yaml::Real(text.clone()).as_f64().and_then(Number::from_f64).map(Value::Number),
+        (true, "bool") => resolve(&text).ok().filter(Value::is_boolean),
+        (true, "null") => resolve(&text).ok().filter(Value::is_null),
+        // The non-specific tag `!` marks a string.
+        (false, _) if show(&tag) == "!" => return Ok(Value::String(text)),
+        _ => return Err(format!("unsupported tag {}", show(&tag))),
+    };
+    value.ok_or_else(|| format!("{} is not a valid {}", text, show(&tag)))
+}
+
+/// Resolves a plain scalar with the core schema.
+fn resolve(text: &str) -> Result<Value, String> {
+    Ok(match Yaml::from_str(text) {
+        Yaml::Null => Value::Null,
+        Yaml::Boolean(b) => Value::Bool(b),
+        Yaml::Integer(n) => Value::from(n),
+        Yaml::Real(real) => {
+            let n = Yaml::Real(real).as_f64().and_then(Number::from_f64);
+            Value::Number(n.ok_or_else(|| format!("{} has no JSON value", text))?)
+        }
+        _ => Value::String(text.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_block_collections() {
+        let text = "\
+# site config
+title: My Site   # inline comment
+port: 8080
+ratio: 0.5
+draft: false
+empty:
+tags:
+  - rust
+  - \"a: b\"
+authors:
+- name: Ada
+  roles: [admin, 'editor']
+- name: Grace
+nested:
+  deep:
+    key: ~
+";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({
+                "title": "My Site",
+                "port": 8080,
+                "ratio": 0.5,
+                "draft": false,
+                "empty": null,
+                "tags": ["rust", "a: b"],
+                "authors": [{"name": "Ada", "roles": ["admin", "editor"]}, {"name": "Grace"}],
+                "nested": {"deep": {"key": null}},
+            })
+        );
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(
+            parse("a: 0x1f\nb: -3\nc: 1e3\nd: 1.2.3\ne: 'it''s'\nf: \"tab\\there \\u00e9\"\ng: {x: 1, y: [2, 3]}\nh: http://x.io/a#b\n")
+                .unwrap(),
+            json!({
+                "a": 31, "b": -3, "c": 1000.0, "d": "1.2.3", "e": "it's",
+                "f": "tab\there é", "g": {"x": 1, "y": [2, 3]}, "h": "http://x.io/a#b",
+            })
+        );
+        assert_eq!(parse("").unwrap(), Value::Null);
+        assert_eq!(parse("---\n- - 1\n  - 2\n- 3\n").unwrap(), json!([[1, 2], 3]));
+    }
+
+    #[test]
+    fn test_block_scalars() {
+        let text = "literal: |\n  line one\n    indented\n\n  line three\nfolded: >-\n  a\n  b\n\n  c\nnext: 1\n";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({"literal": "line one\n  indented\n\nline three\n", "folded": "a b\nc", "next": 1})
+        );
+    }
+
+    #[test]
+    fn test_to_string() {
+        let value = json!({
+            "name": "My Site",
+            "port": 8080,
+            "ratio": 0.5,
+            "none": null,
+            "empty": [],
+            "tags": ["rust", "a: b", "- dash", "true", "", "yes", "x #y"],
+            "authors": [{"name": "Ada", "roles": ["admin"]}, {"name": "Grace", "meta": {}}],
+            "grid": [[1, 2], [3]],
+            "text": "line one\n\"two\"\ttab",
+        });
+        let text = to_string(&value);
+        assert!(text.starts_with("authors:\n  - name: Ada\n    roles:\n      - admin\n  - meta: {}\n    name: Grace\n"));
+        assert!(text.contains("grid:\n  - - 1\n    - 2\n  - - 3\n"));
+        assert!(text.contains("  - \"a: b\"\n  - \"- dash\"\n  - \"true\"\n  - \"\"\n  - \"yes\"\n"));
+        assert!(text.contains("text: \"line one\\n\\\"two\\\"\\ttab\"\n"));
+        assert_eq!(parse(&text).unwrap(), value);
+        assert_eq!(to_string(&json!("plain")), "plain\n");
+        assert_eq!(parse(&to_string(&json!([]))).unwrap(), json!([]));
+    }
+
+    #[test]
+    fn test_anchors_and_merges() {
+        let text = "\
+defaults: &defaults
+  adapter: postgres
+  host: localhost
+development:
+  <<: *defaults
+  host: dev.local
+ports: &ports [80, 443]
+copy: *ports
+multi:
+  <<: [{a: 1}, {a: 2, b: 2}]
+";
+        assert_eq!(
+            parse(text).unwrap(),
+            json!({
+                "defaults": {"adapter": "postgres", "host": "localhost"},
+                "development": {"adapter": "postgres", "host": "dev.local"},
+                "ports": [80, 443],
+                "copy": [80, 443],
+                "multi": {"a": 1, "b": 2},
+            })
+        );
+    }
+
+    #[test]
+    fn test_documents_and_flow() {
+        assert_eq!(parse("---\na: 1\n...\n").unwrap(), json!({"a": 1}));
+        assert_eq!(parse("a: [1,\n  2,\n  3]\nb: {x: 1,\n  y: 2}\n").unwrap(), json!({"a": [1, 2, 3], "b": {"x": 1, "y": 2}}));
+        assert_eq!(parse("1: a\ntrue: b\n").unwrap(), json!({"1": "a", "true": "b"}));
+        assert_eq!(parse("a: !!str 1\nb: !!float 2\nc: ! 3\n").unwrap(), json!({"a": "1", "b": 2.0, "c": "3"}));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse("a: 1\na: 2\n").unwrap_err(), "duplicate key a at line 2");
+        assert!(parse("a: [1, 2\n").unwrap_err().contains("at line"));
+        assert!(parse("a: 1\n  b: 2\n").is_err());
+        assert!(parse("a:\n\tb: 1\n").is_err());
+        assert!(parse("a: *missing\n").is_err());
+        assert_eq!(parse("a: 1\n---\nb: 2\n").unwrap_err(), "multi-document streams are not supported");
+        assert_eq!(parse("a: !custom 1\n").unwrap_err(), "unsupported tag !custom at line 1");
+        assert_eq!(parse("a: !!binary aGk=\n").unwrap_err(), "unsupported tag !!binary at line 1");
+        assert_eq!(parse("a: .inf\n").unwrap_err(), ".inf has no JSON value at line 1");
+        assert_eq!(parse("? [a, b]\n: c\n").unwrap_err(), "mapping keys must be scalars at line 1");
+        assert_eq!(parse("a: !!int x\n").unwrap_err(), "x is not a valid !!int at line 1");
+    }
+}
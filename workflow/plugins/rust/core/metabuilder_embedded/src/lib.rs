@@ -0,0 +1,130 @@
+//! The MetaBuilder nodes that build without `std`, ready to run.
+//!
+//! `interpreter()` returns a `metabuilder_core::interp::Interpreter` with
+//! every `math`, `logic`, `list`, and `string` node registered (except
+//! `string.transcode`, which needs `std`). With default features off the
+//! crate and everything it pulls in builds for `no_std` targets with an
+//! allocator. The `check` feature's panic handler makes that a checked
+//! build: `cargo test` builds it and fails if anything links `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::any::Any;
+use metabuilder_core::{Interpreter, Ports};
+
+/// Registers each node type with the `create()` of its plugin crate.
+macro_rules! register {
+    ($interpreter:ident, $($node_type:literal => $plugin:ident,)*) => {
+        $(
+            $interpreter.register($node_type, |inputs: Ports, runtime: Option<&dyn Any>| {
+                use $plugin::NodeExecutor;
+                $plugin::create().execute(inputs, runtime)
+            });
+        )*
+    };
+}
+
+/// An interpreter with every node that builds without `std` registered.
+pub fn interpreter() -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    register! {
+        interpreter,
+        "math.abs" => math_abs,
+        "math.add" => math_add,
+        "math.ceil" => math_ceil,
+        "math.divide" => math_divide,
+        "math.floor" => math_floor,
+        "math.modulo" => math_modulo,
+        "math.multiply" => math_multiply,
+        "math.power" => math_power,
+        "math.round" => math_round,
+        "math.subtract" => math_subtract,
+        "logic.and" => logic_and,
+        "logic.equals" => logic_equals,
+        "logic.gt" => logic_gt,
+        "logic.gte" => logic_gte,
+        "logic.lt" => logic_lt,
+        "logic.lte" => logic_lte,
+        "logic.not" => logic_not,
+        "logic.or" => logic_or,
+        "logic.xor" => logic_xor,
+        "list.at" => list_at,
+        "list.concat" => list_concat,
+        "list.contains" => list_contains,
+        "list.first" => list_first,
+        "list.index_of" => list_index_of,
+        "list.last" => list_last,
+        "list.length" => list_length,
+        "list.reverse" => list_reverse,
+        "list.slice" => list_slice,
+        "list.sort" => list_sort,
+        "list.unique" => list_unique,
+        "string.at" => string_at,
+        "string.concat" => string_concat,
+        "string.contains" => string_contains,
+        "string.ends_with" => string_ends_with,
+        "string.length" => string_length,
+        "string.lower" => string_lower,
+        "string.normalize_newlines" => string_normalize_newlines,
+        "string.replace" => string_replace,
+        "string.split" => string_split,
+        "string.starts_with" => string_starts_with,
+        "string.substring" => string_substring,
+        "string.trim" => string_trim,
+        "string.upper" => string_upper,
+    }
+    interpreter.register("logic.in", |inputs: Ports, _runtime: Option<&dyn Any>| {
+        logic_in::run(&mut Ports::new(), &inputs).unwrap_or_else(|e| {
+            let mut output = Ports::new();
+            output.insert("error".into(), serde_json::json!(e));
+            output
+        })
+    });
+    interpreter
+}
+
+/// Without `std` the final binary supplies the panic handler; defining one
+/// here fails the build if `std`, which has its own, is linked at all.
+#[cfg(all(feature = "check", not(feature = "std")))]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::Step;
+    use serde_json::json;
+
+    #[test]
+    fn test_interpreter() {
+        let steps = [
+            Step::new("words", "string.split").param("string", json!("b a b")).param("separator", json!(" ")),
+            Step::new("unique", "list.unique").wire("words", "result", "list"),
+            Step::new("count", "list.length").wire("unique", "result", "list"),
+            Step::new("check", "logic.equals").wire("count", "result", "a").param("b", json!(2)),
+            Step::new("member", "logic.in").wire("unique", "result", "list").param("value", json!("a")),
+        ];
+        let outputs = interpreter().run(&steps).unwrap();
+        assert_eq!(outputs["unique"]["result"], json!(["b", "a"]));
+        assert_eq!(outputs["check"]["result"], json!(true));
+        assert_eq!(outputs["member"]["result"], json!(true));
+    }
+
+    #[test]
+    fn test_builds_without_std() {
+        // Static linking drops the plugins' `cdylib` crate type, as targets
+        // without dynamic linking do, so only the rlibs are built.
+        let output = std::process::Command::new(env!("CARGO"))
+            .args(["build", "-p", "metabuilder_embedded", "--no-default-features", "--features", "check"])
+            .arg("--target-dir")
+            .arg(std::env::temp_dir().join("metabuilder-embedded-check"))
+            .env("RUSTFLAGS", "-C target-feature=+crt-static")
+            .env_remove("CARGO_ENCODED_RUSTFLAGS")
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
@@ -0,0 +1,133 @@
+//! Arrow-backed tables for `table.*` plugins.
+//!
+//! A `Table` is a list of Arrow `RecordBatch`es sharing one schema. Between
+//! table nodes it travels as a handle, a small JSON object such as
+//! `{"$table": 3, "rows": 1000000, "columns": ["id", "name"]}` that names a
+//! value in the run's `Runtime::handles`, so the batches are never copied or
+//! converted. Converting to and from JSON rows happens only where a workflow
+//! needs plain values.
+
+use arrow_array::RecordBatch;
+use arrow_json::reader::infer_json_schema_from_iterator;
+use arrow_json::writer::JsonArray;
+use arrow_json::{ReaderBuilder, WriterBuilder};
+use arrow_schema::SchemaRef;
+use metabuilder_core::Runtime;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Key identifying a JSON object as a table handle.
+pub const HANDLE_KEY: &str = "$table";
+
+/// Record batches sharing one schema.
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub schema: SchemaRef,
+    pub batches: Vec<RecordBatch>,
+}
+
+impl Table {
+    /// Creates a table from batches that all use `schema`.
+    pub fn new(schema: SchemaRef, batches: Vec<RecordBatch>) -> Self {
+        Table { schema, batches }
+    }
+
+    /// Builds a table from JSON objects, inferring the column types.
+    pub fn from_rows(rows: &[Value]) -> Result<Table, String> {
+        if let Some(index) = rows.iter().position(|row| !row.is_object()) {
+            return Err(format!("row {} is not an object", index));
+        }
+        let schema = infer_json_schema_from_iterator(rows.iter().map(Ok)).map_err(|e| e.to_string())?;
+        let schema = Arc::new(schema);
+        let mut decoder = ReaderBuilder::new(schema.clone()).build_decoder().map_err(|e| e.to_string())?;
+        decoder.serialize(rows).map_err(|e| e.to_string())?;
+        let batches = decoder.flush().map_err(|e| e.to_string())?.into_iter().collect();
+        Ok(Table { schema, batches })
+    }
+
+    /// Converts the table to JSON objects, with an explicit `null` for every
+    /// missing value so each row has every column.
+    pub fn to_rows(&self) -> Result<Vec<Value>, String> {
+        let mut writer = WriterBuilder::new()
+            .with_explicit_nulls(true)
+            .build::<_, JsonArray>(Vec::new());
+        for batch in &self.batches {
+            writer.write(batch).map_err(|e| e.to_string())?;
+        }
+        writer.finish().map_err(|e| e.to_string())?;
+        let json = writer.into_inner();
+        match json.is_empty() {
+            true => Ok(Vec::new()),
+            false => serde_json::from_slice(&json).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Total number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.batches.iter().map(RecordBatch::num_rows).sum()
+    }
+
+    /// Column names in schema order.
+    pub fn column_names(&self) -> Vec<String> {
+        self.schema.fields().iter().map(|f| f.name().clone()).collect()
+    }
+
+    /// Stores the table in the run's handles and returns its JSON handle.
+    pub fn into_handle(self, runtime: &Runtime) -> Value {
+        let (rows, columns) = (self.num_rows(), self.column_names());
+        let id = runtime.handles.insert(self);
+        json!({ HANDLE_KEY: id, "rows": rows, "columns": columns })
+    }
+
+    /// Resolves a node input that is either a table handle or a list of row objects.
+    pub fn from_value(value: &Value, runtime: Option<&Runtime>) -> Result<Arc<Table>, String> {
+        match value {
+            Value::Object(object) if object.contains_key(HANDLE_KEY) => {
+                let id = object[HANDLE_KEY].as_u64().ok_or("table handle id must be a number")?;
+                runtime
+                    .ok_or("runtime is required for table handles")?
+                    .handles
+                    .get::<Table>(id)
+                    .ok_or_else(|| format!("unknown table handle: {}", id))
+            }
+            Value::Array(rows) => Table::from_rows(rows).map(Arc::new),
+            _ => Err("table must be a table handle or a list of row objects".to_string()),
+        }
+    }
+}
+
+/// Whether `value` is a table handle.
+pub fn is_handle(value: &Value) -> bool {
+    value.as_object().is_some_and(|object| object.contains_key(HANDLE_KEY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_round_trip() {
+        let rows = vec![json!({"id": 1, "name": "ada"}), json!({"id": 2, "name": null})];
+        let table = Table::from_rows(&rows).unwrap();
+        assert_eq!(table.num_rows(), 2);
+        assert_eq!(table.column_names(), vec!["id", "name"]);
+        assert_eq!(table.to_rows().unwrap(), rows);
+        assert_eq!(Table::new(table.schema.clone(), Vec::new()).to_rows().unwrap(), Vec::<Value>::new());
+    }
+
+    #[test]
+    fn test_handle_round_trip() {
+        let runtime = Runtime::new();
+        let table = Table::from_rows(&[json!({"x": 1.5})]).unwrap();
+        let handle = table.into_handle(&runtime);
+        assert!(is_handle(&handle));
+        assert_eq!(handle, json!({"$table": 1, "rows": 1, "columns": ["x"]}));
+
+        let resolved = Table::from_value(&handle, Some(&runtime)).unwrap();
+        assert_eq!(resolved.to_rows().unwrap(), vec![json!({"x": 1.5})]);
+        assert_eq!(
+            Table::from_value(&json!({"$table": 9}), Some(&runtime)).unwrap_err(),
+            "unknown table handle: 9"
+        );
+    }
+}
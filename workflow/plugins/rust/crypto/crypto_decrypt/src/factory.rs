@@ -0,0 +1,8 @@
+//! Factory for CryptoDecrypt plugin.
+
+use super::CryptoDecrypt;
+
+/// Creates a new CryptoDecrypt instance.
+pub fn create() -> CryptoDecrypt {
+    CryptoDecrypt::new()
+}
@@ -0,0 +1,192 @@
+//! Workflow plugin: decrypt what `crypto.encrypt` produced.
+//!
+//! `data` is the output of `crypto.encrypt`: a bytes value, or base64 text
+//! (a string here is always read as base64, since ciphertext is not text).
+//! `key_secret` names the secret holding the base64-encoded 256-bit key, and
+//! `aad` must match the additional data given to encrypt, if any. Outputs
+//! `result`, the plaintext as a bytes value or, with `as_text`, as a UTF-8
+//! string. A wrong key or any change to the ciphertext is an error.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use metabuilder_core::{bytes, Runtime};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CryptoDecrypt implements the NodeExecutor trait for AES-GCM decryption.
+pub struct CryptoDecrypt {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CryptoDecrypt {
+    /// Creates a new CryptoDecrypt instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "crypto.decrypt",
+            category: "crypto",
+            description: "Decrypt AES-256-GCM ciphertext from crypto.encrypt",
+        }
+    }
+}
+
+impl Default for CryptoDecrypt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The cipher for the base64 key in the secret `name`.
+fn cipher(runtime: Option<&dyn Any>, name: &str) -> Result<Aes256Gcm, String> {
+    let key = Runtime::from_any(runtime)
+        .ok_or("runtime with secrets provider is required")?
+        .secret(name)
+        .ok_or_else(|| format!("secret not found: {}", name))?;
+    let key = STANDARD.decode(key.trim()).map_err(|e| format!("invalid key in {}: {}", name, e))?;
+    if key.len() != 32 {
+        return Err(format!("key in {} must be 32 bytes, got {}", name, key.len()));
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
+
+fn decrypt(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Value, String> {
+    let sealed = match inputs.get("data") {
+        Some(Value::String(text)) => STANDARD
+            .decode(text.trim())
+            .map_err(|e| format!("invalid base64 ciphertext: {}", e))?,
+        Some(data) => bytes::from_value(data)?,
+        None => return Err("data is required".to_string()),
+    };
+    let name = inputs.get("key_secret").and_then(Value::as_str).ok_or("key_secret is required")?;
+    let aad = match inputs.get("aad") {
+        Some(aad) => bytes::from_value(aad)?,
+        None => Vec::new(),
+    };
+    if sealed.len() < NONCE_LEN + TAG_LEN {
+        return Err("ciphertext is too short".to_string());
+    }
+    let (nonce, msg) = sealed.split_at(NONCE_LEN);
+    let plaintext = cipher(runtime, name)?
+        .decrypt(Nonce::from_slice(nonce), Payload { msg, aad: &aad })
+        .map_err(|_| "wrong key or tampered ciphertext".to_string())?;
+    match inputs.get("as_text").and_then(Value::as_bool).unwrap_or(false) {
+        true => String::from_utf8(plaintext)
+            .map(Value::String)
+            .map_err(|_| "plaintext is not valid UTF-8".to_string()),
+        false => Ok(bytes::to_value(&plaintext)),
+    }
+}
+
+impl NodeExecutor for CryptoDecrypt {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match decrypt(&inputs, runtime) {
+            Ok(plaintext) => {
+                output.insert("result".to_string(), plaintext);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CryptoDecrypt instance.
+pub fn create() -> CryptoDecrypt {
+    CryptoDecrypt::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::{inputs, runtime};
+    use aes_gcm::aead::{AeadCore, OsRng};
+
+    const KEY: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    const SECRETS: &[(&str, &str)] = &[("key", KEY), ("other", "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=")];
+
+    /// Seals `data` as `crypto.encrypt` does.
+    fn seal(data: &[u8], aad: &[u8]) -> Vec<u8> {
+        let key = STANDARD.decode(KEY).unwrap();
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let mut sealed = nonce.to_vec();
+        sealed.extend(cipher.encrypt(&nonce, Payload { msg: data, aad }).unwrap());
+        sealed
+    }
+
+    #[test]
+    fn test_decrypt() {
+        let runtime = runtime(SECRETS);
+        let executor = CryptoDecrypt::new();
+        let sealed = seal(b"hello", b"");
+        let output = executor.execute(
+            inputs(json!({"data": bytes::to_value(&sealed), "key_secret": "key"})),
+            Some(&runtime),
+        );
+        assert_eq!(bytes::from_value(&output["result"]).unwrap(), b"hello");
+        let output = executor.execute(
+            inputs(json!({"data": STANDARD.encode(&sealed), "key_secret": "key", "as_text": true})),
+            Some(&runtime),
+        );
+        assert_eq!(output["result"], "hello");
+    }
+
+    #[test]
+    fn test_aad() {
+        let runtime = runtime(SECRETS);
+        let executor = CryptoDecrypt::new();
+        let sealed = bytes::to_value(&seal(b"hello", b"invoice-7"));
+        let output = executor.execute(
+            inputs(json!({"data": sealed, "key_secret": "key", "aad": "invoice-7", "as_text": true})),
+            Some(&runtime),
+        );
+        assert_eq!(output["result"], "hello");
+        let output = executor.execute(
+            inputs(json!({"data": sealed, "key_secret": "key", "aad": "invoice-8"})),
+            Some(&runtime),
+        );
+        assert_eq!(output["error"], "wrong key or tampered ciphertext");
+    }
+
+    #[test]
+    fn test_errors() {
+        let runtime = runtime(SECRETS);
+        let executor = CryptoDecrypt::new();
+        let mut sealed = seal(b"hello", b"");
+        let error = |params: Value| executor.execute(inputs(params), Some(&runtime))["error"].clone();
+        let data = bytes::to_value(&sealed);
+        assert_eq!(error(json!({"data": data, "key_secret": "other"})), "wrong key or tampered ciphertext");
+        sealed[NONCE_LEN] ^= 1;
+        let data = bytes::to_value(&sealed);
+        assert_eq!(error(json!({"data": data, "key_secret": "key"})), "wrong key or tampered ciphertext");
+        let data = bytes::to_value(&[0; 8]);
+        assert_eq!(error(json!({"data": data, "key_secret": "key"})), "ciphertext is too short");
+        assert_eq!(error(json!({"key_secret": "key"})), "data is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "crypto.decrypt");
+        assert_eq!(executor.category, "crypto");
+    }
+}
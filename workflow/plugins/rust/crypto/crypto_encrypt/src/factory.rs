@@ -0,0 +1,8 @@
+//! Factory for CryptoEncrypt plugin.
+
+use super::CryptoEncrypt;
+
+/// Creates a new CryptoEncrypt instance.
+pub fn create() -> CryptoEncrypt {
+    CryptoEncrypt::new()
+}
@@ -0,0 +1,156 @@
+//! Workflow plugin: encrypt a string or bytes with AES-256-GCM.
+//!
+//! `data` is a bytes value or a string, read as UTF-8. `key_secret` names
+//! the secret holding the base64-encoded 256-bit key, so keys never appear
+//! in the workflow. Every call draws a fresh random 96-bit nonce, which is
+//! stored in front of the ciphertext, so `result` is the nonce, the
+//! ciphertext, and the 16-byte tag, as a bytes value or, with `encoding`
+//! `"base64"`, as standard base64 text. `aad`, optional additional data, is
+//! authenticated but not encrypted, and must be given again to decrypt.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use metabuilder_core::{bytes, Runtime};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CryptoEncrypt implements the NodeExecutor trait for AES-GCM encryption.
+pub struct CryptoEncrypt {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CryptoEncrypt {
+    /// Creates a new CryptoEncrypt instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "crypto.encrypt",
+            category: "crypto",
+            description: "Encrypt a string or bytes with AES-256-GCM",
+        }
+    }
+}
+
+impl Default for CryptoEncrypt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The cipher for the base64 key in the secret `name`.
+fn cipher(runtime: Option<&dyn Any>, name: &str) -> Result<Aes256Gcm, String> {
+    let key = Runtime::from_any(runtime)
+        .ok_or("runtime with secrets provider is required")?
+        .secret(name)
+        .ok_or_else(|| format!("secret not found: {}", name))?;
+    let key = STANDARD.decode(key.trim()).map_err(|e| format!("invalid key in {}: {}", name, e))?;
+    if key.len() != 32 {
+        return Err(format!("key in {} must be 32 bytes, got {}", name, key.len()));
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
+
+fn encrypt(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Value, String> {
+    let data = bytes::from_value(inputs.get("data").unwrap_or(&Value::Null))?;
+    let name = inputs.get("key_secret").and_then(Value::as_str).ok_or("key_secret is required")?;
+    let aad = match inputs.get("aad") {
+        Some(aad) => bytes::from_value(aad)?,
+        None => Vec::new(),
+    };
+    let encoding = inputs.get("encoding").and_then(Value::as_str).unwrap_or("bytes");
+    if encoding != "bytes" && encoding != "base64" {
+        return Err(format!("unknown encoding: {} (expected bytes or base64)", encoding));
+    }
+    let cipher = cipher(runtime, name)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let sealed = cipher
+        .encrypt(&nonce, Payload { msg: &data, aad: &aad })
+        .map_err(|_| "encryption failed".to_string())?;
+    let mut result = nonce.to_vec();
+    result.extend(sealed);
+    Ok(match encoding {
+        "base64" => json!(STANDARD.encode(result)),
+        _ => bytes::to_value(&result),
+    })
+}
+
+impl NodeExecutor for CryptoEncrypt {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match encrypt(&inputs, runtime) {
+            Ok(sealed) => {
+                output.insert("result".to_string(), sealed);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CryptoEncrypt instance.
+pub fn create() -> CryptoEncrypt {
+    CryptoEncrypt::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::{inputs, runtime};
+
+    const SECRETS: &[(&str, &str)] = &[("key", "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8="), ("short", "c2hvcnQ=")];
+
+    #[test]
+    fn test_encrypt() {
+        let runtime = runtime(SECRETS);
+        let executor = CryptoEncrypt::new();
+        let first = executor.execute(inputs(json!({"data": "hello", "key_secret": "key"})), Some(&runtime));
+        let sealed = bytes::from_value(&first["result"]).unwrap();
+        // Nonce, ciphertext, and tag.
+        assert_eq!(sealed.len(), 12 + 5 + 16);
+        let second = executor.execute(inputs(json!({"data": "hello", "key_secret": "key"})), Some(&runtime));
+        assert_ne!(first["result"], second["result"]);
+        let text = executor.execute(
+            inputs(json!({"data": "hello", "key_secret": "key", "encoding": "base64"})),
+            Some(&runtime),
+        );
+        assert_eq!(STANDARD.decode(text["result"].as_str().unwrap()).unwrap().len(), 33);
+    }
+
+    #[test]
+    fn test_errors() {
+        let runtime = runtime(SECRETS);
+        let executor = CryptoEncrypt::new();
+        let error = |params: Value| executor.execute(inputs(params), Some(&runtime))["error"].clone();
+        assert_eq!(error(json!({"data": "x", "key_secret": "none"})), "secret not found: none");
+        assert_eq!(error(json!({"data": "x", "key_secret": "short"})), "key in short must be 32 bytes, got 5");
+        assert_eq!(error(json!({"data": "x"})), "key_secret is required");
+        let output = executor.execute(
+            inputs(json!({"data": "x", "key_secret": "key", "encoding": "hex"})),
+            Some(&runtime),
+        );
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "unknown encoding: hex (expected bytes or base64)");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "crypto.encrypt");
+        assert_eq!(executor.category, "crypto");
+    }
+}
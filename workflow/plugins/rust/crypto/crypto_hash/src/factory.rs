@@ -0,0 +1,8 @@
+//! Factory for CryptoHash plugin.
+
+use super::CryptoHash;
+
+/// Creates a new CryptoHash instance.
+pub fn create() -> CryptoHash {
+    CryptoHash::new()
+}
@@ -0,0 +1,122 @@
+//! Workflow plugin: hash a string or bytes.
+//!
+//! `data` is a bytes value or a string, read as UTF-8. `algorithm` is one of
+//! `sha256` (the default), `sha512`, `sha1`, `md5`, or `blake3`. Outputs
+//! `result`, the digest in lowercase hex, and `base64`, the same digest in
+//! standard base64. SHA-1 and MD5 are for checksums and legacy APIs only.
+
+use base64::Engine as _;
+use metabuilder_core::bytes;
+use serde_json::{json, Value};
+use sha2::Digest;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CryptoHash implements the NodeExecutor trait for hashing.
+pub struct CryptoHash {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CryptoHash {
+    /// Creates a new CryptoHash instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "crypto.hash",
+            category: "crypto",
+            description: "Hash a string or bytes (SHA-256, SHA-512, SHA-1, MD5, BLAKE3)",
+        }
+    }
+}
+
+impl Default for CryptoHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn digest(inputs: &HashMap<String, Value>) -> Result<Vec<u8>, String> {
+    let data = bytes::from_value(inputs.get("data").unwrap_or(&Value::Null))?;
+    match inputs.get("algorithm").and_then(Value::as_str).unwrap_or("sha256") {
+        "sha256" => Ok(sha2::Sha256::digest(&data).to_vec()),
+        "sha512" => Ok(sha2::Sha512::digest(&data).to_vec()),
+        "sha1" => Ok(sha1::Sha1::digest(&data).to_vec()),
+        "md5" => Ok(md5::Md5::digest(&data).to_vec()),
+        "blake3" => Ok(blake3::hash(&data).as_bytes().to_vec()),
+        other => Err(format!(
+            "unknown algorithm: {} (expected sha256, sha512, sha1, md5, or blake3)",
+            other
+        )),
+    }
+}
+
+impl NodeExecutor for CryptoHash {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match digest(&inputs) {
+            Ok(digest) => {
+                output.insert("result".to_string(), json!(bytes::to_hex(&digest, false)));
+                output.insert(
+                    "base64".to_string(),
+                    json!(base64::engine::general_purpose::STANDARD.encode(&digest)),
+                );
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CryptoHash instance.
+pub fn create() -> CryptoHash {
+    CryptoHash::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+
+    fn hex(algorithm: &str) -> Value {
+        CryptoHash::new().execute(inputs(json!({"data": "abc", "algorithm": algorithm})), None)["result"].clone()
+    }
+
+    #[test]
+    fn test_algorithms() {
+        assert_eq!(hex("sha256"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(hex("sha1"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(hex("md5"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(hex("blake3"), "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85");
+        assert!(hex("sha512").as_str().unwrap().starts_with("ddaf35a193617aba"));
+    }
+
+    #[test]
+    fn test_outputs() {
+        let executor = CryptoHash::new();
+        let output = executor.execute(inputs(json!({"data": bytes::to_value(b"abc"), "algorithm": "md5"})), None);
+        assert_eq!(output["result"], "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(output["base64"], "kAFQmDzST7DWlj99KOF/cg==");
+        let output = executor.execute(inputs(json!({"data": "abc", "algorithm": "crc32"})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].as_str().unwrap().starts_with("unknown algorithm: crc32"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "crypto.hash");
+        assert_eq!(executor.category, "crypto");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for CryptoJwtSign plugin.
+
+use super::CryptoJwtSign;
+
+/// Creates a new CryptoJwtSign instance.
+pub fn create() -> CryptoJwtSign {
+    CryptoJwtSign::new()
+}
@@ -0,0 +1,8 @@
+//! Factory for CryptoJwtVerify plugin.
+
+use super::CryptoJwtVerify;
+
+/// Creates a new CryptoJwtVerify instance.
+pub fn create() -> CryptoJwtVerify {
+    CryptoJwtVerify::new()
+}
@@ -0,0 +1,8 @@
+//! Factory for CryptoPasswordHash plugin.
+
+use super::CryptoPasswordHash;
+
+/// Creates a new CryptoPasswordHash instance.
+pub fn create() -> CryptoPasswordHash {
+    CryptoPasswordHash::new()
+}
@@ -0,0 +1,134 @@
+//! Workflow plugin: hash a password for storage.
+//!
+//! `algorithm` is `argon2id` (the default, with the OWASP-recommended
+//! parameters and a random salt) or `bcrypt`, whose work factor is `cost`
+//! (4 to 31, 12 by default). Outputs `result`, the hash in its standard
+//! self-describing form (`$argon2id$...` or `$2b$...`), which is all
+//! `crypto.password_verify` needs, so the password itself is never stored.
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHasher, SaltString};
+use argon2::Argon2;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CryptoPasswordHash implements the NodeExecutor trait for password hashing.
+pub struct CryptoPasswordHash {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CryptoPasswordHash {
+    /// Creates a new CryptoPasswordHash instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "crypto.password_hash",
+            category: "crypto",
+            description: "Hash a password for storage (argon2id or bcrypt)",
+        }
+    }
+}
+
+impl Default for CryptoPasswordHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn hash(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let password = inputs.get("password").and_then(Value::as_str).ok_or("password is required")?;
+    match inputs.get("algorithm").and_then(Value::as_str).unwrap_or("argon2id") {
+        "argon2id" => {
+            let salt = SaltString::generate(&mut OsRng);
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|e| format!("cannot hash password: {}", e))
+        }
+        "bcrypt" => {
+            let cost = inputs.get("cost").map_or(Some(bcrypt::DEFAULT_COST), |cost| cost.as_u64().map(|c| c as u32));
+            let cost = cost.filter(|cost| (4..=31).contains(cost)).ok_or("cost must be an integer from 4 to 31")?;
+            bcrypt::hash(password, cost).map_err(|e| format!("cannot hash password: {}", e))
+        }
+        other => Err(format!("unknown algorithm: {} (expected argon2id or bcrypt)", other)),
+    }
+}
+
+impl NodeExecutor for CryptoPasswordHash {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match hash(&inputs) {
+            Ok(hash) => {
+                output.insert("result".to_string(), json!(hash));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CryptoPasswordHash instance.
+pub fn create() -> CryptoPasswordHash {
+    CryptoPasswordHash::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use argon2::password_hash::{PasswordHash, PasswordVerifier};
+
+    #[test]
+    fn test_argon2id() {
+        let executor = CryptoPasswordHash::new();
+        let first = executor.execute(
+            inputs(json!({"password": "hunter2"})),
+            None,
+        )["result"].as_str().unwrap().to_string();
+        assert!(first.starts_with("$argon2id$"));
+        let parsed = PasswordHash::new(&first).unwrap();
+        assert!(Argon2::default().verify_password(b"hunter2", &parsed).is_ok());
+        // Salted, so the same password hashes differently each time.
+        assert_ne!(executor.execute(inputs(json!({"password": "hunter2"})), None)["result"], json!(first));
+    }
+
+    #[test]
+    fn test_bcrypt() {
+        let executor = CryptoPasswordHash::new();
+        let output = executor.execute(inputs(json!({"password": "hunter2", "algorithm": "bcrypt", "cost": 4})), None);
+        let hash = output["result"].as_str().unwrap();
+        assert!(hash.starts_with("$2b$04$"));
+        assert!(bcrypt::verify("hunter2", hash).unwrap());
+        let output = executor.execute(inputs(json!({"password": "hunter2", "algorithm": "bcrypt", "cost": 3})), None);
+        assert_eq!(output["error"], "cost must be an integer from 4 to 31");
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = CryptoPasswordHash::new();
+        let output = executor.execute(inputs(json!({"password": "x", "algorithm": "md5"})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "unknown algorithm: md5 (expected argon2id or bcrypt)");
+        assert_eq!(executor.execute(inputs(json!({})), None)["error"], "password is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "crypto.password_hash");
+        assert_eq!(executor.category, "crypto");
+    }
+}
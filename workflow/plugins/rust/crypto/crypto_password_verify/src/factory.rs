@@ -0,0 +1,8 @@
+//! Factory for CryptoPasswordVerify plugin.
+
+use super::CryptoPasswordVerify;
+
+/// Creates a new CryptoPasswordVerify instance.
+pub fn create() -> CryptoPasswordVerify {
+    CryptoPasswordVerify::new()
+}
@@ -0,0 +1,123 @@
+//! Workflow plugin: check a password against a stored hash.
+//!
+//! `hash` is the output of `crypto.password_hash`, or any argon2 PHC string
+//! (`$argon2id$...`, `$argon2i$...`, `$argon2d$...`) or bcrypt hash (`$2a$`,
+//! `$2b$`, `$2y$`); the algorithm and its parameters are read from it.
+//! Outputs `valid`, whether `password` matches. A hash in neither form is an
+//! `error`.
+
+use argon2::password_hash::{Error, PasswordHash, PasswordVerifier};
+use argon2::Argon2;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CryptoPasswordVerify implements the NodeExecutor trait for password checks.
+pub struct CryptoPasswordVerify {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CryptoPasswordVerify {
+    /// Creates a new CryptoPasswordVerify instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "crypto.password_verify",
+            category: "crypto",
+            description: "Check a password against an argon2 or bcrypt hash",
+        }
+    }
+}
+
+impl Default for CryptoPasswordVerify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn verify(inputs: &HashMap<String, Value>) -> Result<bool, String> {
+    let password = inputs.get("password").and_then(Value::as_str).ok_or("password is required")?;
+    let hash = inputs.get("hash").and_then(Value::as_str).ok_or("hash is required")?;
+    if hash.starts_with("$argon2") {
+        let parsed = PasswordHash::new(hash).map_err(|e| format!("invalid argon2 hash: {}", e))?;
+        match Argon2::default().verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(Error::Password) => Ok(false),
+            Err(e) => Err(format!("invalid argon2 hash: {}", e)),
+        }
+    } else if ["$2a$", "$2b$", "$2x$", "$2y$"].iter().any(|prefix| hash.starts_with(prefix)) {
+        bcrypt::verify(password, hash).map_err(|e| format!("invalid bcrypt hash: {}", e))
+    } else {
+        Err("hash is neither argon2 nor bcrypt".to_string())
+    }
+}
+
+impl NodeExecutor for CryptoPasswordVerify {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match verify(&inputs) {
+            Ok(valid) => {
+                output.insert("valid".to_string(), json!(valid));
+            }
+            Err(e) => {
+                output.insert("valid".to_string(), json!(false));
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CryptoPasswordVerify instance.
+pub fn create() -> CryptoPasswordVerify {
+    CryptoPasswordVerify::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "hunter2" hashed with argon2id (m=19456, t=2, p=1) and bcrypt (cost 4).
+    const ARGON2: &str =
+        "$argon2id$v=19$m=19456,t=2,p=1$c29tZXNhbHRzb21lc2FsdA$leF08Fu/gOi7XGf5NvDjbfR9GY+siUuPd+cKkq57H/c";
+    const BCRYPT: &str = "$2b$04$jjNwM2s35kuCSLiKd6SxMOPYlRuwt2y1TCzBLX1dNFe2XbwSVojzi";
+
+    fn run(password: &str, hash: &str) -> HashMap<String, Value> {
+        let inputs = HashMap::from([("password".to_string(), json!(password)), ("hash".to_string(), json!(hash))]);
+        CryptoPasswordVerify::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(run("hunter2", ARGON2)["valid"], true);
+        assert_eq!(run("hunter3", ARGON2)["valid"], false);
+        assert_eq!(run("hunter2", BCRYPT)["valid"], true);
+        assert_eq!(run("hunter3", BCRYPT)["valid"], false);
+    }
+
+    #[test]
+    fn test_errors() {
+        let output = run("hunter2", "5f4dcc3b5aa765d61d8327deb882cf99");
+        assert_eq!(output["valid"], false);
+        assert_eq!(output["error"], "hash is neither argon2 nor bcrypt");
+        let broken = ARGON2.replace("$leF08", "$!!");
+        assert!(run("hunter2", &broken)["error"].as_str().unwrap().starts_with("invalid argon2 hash"));
+        assert!(run("hunter2", "$2b$04$short")["error"].as_str().unwrap().starts_with("invalid bcrypt hash"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "crypto.password_verify");
+        assert_eq!(executor.category, "crypto");
+    }
+}
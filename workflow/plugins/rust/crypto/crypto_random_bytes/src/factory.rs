@@ -0,0 +1,8 @@
+//! Factory for CryptoRandomBytes plugin.
+
+use super::CryptoRandomBytes;
+
+/// Creates a new CryptoRandomBytes instance.
+pub fn create() -> CryptoRandomBytes {
+    CryptoRandomBytes::new()
+}
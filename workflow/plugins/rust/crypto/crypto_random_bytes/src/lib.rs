@@ -0,0 +1,158 @@
+//! Workflow plugin: generate secure random bytes or a token.
+//!
+//! Draws `length` random bytes (32 by default, at most 65536) and outputs
+//! them in `result` as `format`: `hex` (the default), `base64`, `base64url`
+//! (unpadded, safe in URLs), or `bytes` (a bytes value). With `alphanumeric`,
+//! `result` is instead a string of `length` letters and digits. The bytes
+//! come from the runtime's generator, so a seeded engine replays the same
+//! tokens; without a runtime they come from the operating system.
+
+use base64::Engine as _;
+use metabuilder_core::{bytes, Random, Runtime};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Most bytes (or characters) one execution generates.
+const MAX_LENGTH: u64 = 65536;
+
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CryptoRandomBytes implements the NodeExecutor trait for random tokens.
+pub struct CryptoRandomBytes {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CryptoRandomBytes {
+    /// Creates a new CryptoRandomBytes instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "crypto.random_bytes",
+            category: "crypto",
+            description: "Generate secure random bytes or a token (hex, base64, alphanumeric)",
+        }
+    }
+}
+
+impl Default for CryptoRandomBytes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn generate(inputs: &HashMap<String, Value>, random: &Random) -> Result<Value, String> {
+    let length = match inputs.get("length") {
+        None | Some(Value::Null) => 32,
+        Some(length) => length
+            .as_u64()
+            .filter(|n| (1..=MAX_LENGTH).contains(n))
+            .ok_or_else(|| format!("length must be an integer from 1 to {}", MAX_LENGTH))?,
+    } as usize;
+    let format = inputs.get("format").and_then(Value::as_str).unwrap_or("hex");
+    if format == "alphanumeric" {
+        let token: String = (0..length).map(|_| ALPHANUMERIC[random.below(ALPHANUMERIC.len())] as char).collect();
+        return Ok(json!(token));
+    }
+    let data = random.bytes(length);
+    match format {
+        "hex" => Ok(json!(bytes::to_hex(&data, false))),
+        "base64" => Ok(json!(base64::engine::general_purpose::STANDARD.encode(&data))),
+        "base64url" => Ok(json!(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&data))),
+        "bytes" => Ok(bytes::to_value(&data)),
+        other => Err(format!(
+            "unknown format: {} (expected hex, base64, base64url, alphanumeric, or bytes)",
+            other
+        )),
+    }
+}
+
+impl NodeExecutor for CryptoRandomBytes {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let result = match Runtime::from_any(runtime) {
+            Some(runtime) => generate(&inputs, &runtime.random),
+            None => generate(&inputs, &Random::new()),
+        };
+        match result {
+            Ok(result) => {
+                output.insert("result".to_string(), result);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CryptoRandomBytes instance.
+pub fn create() -> CryptoRandomBytes {
+    CryptoRandomBytes::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use std::sync::Arc;
+
+    fn seeded(params: Value, seed: u64) -> Value {
+        let mut runtime = Runtime::new();
+        runtime.random = Arc::new(Random::seeded(seed));
+        CryptoRandomBytes::new().execute(inputs(params), Some(&runtime))["result"].clone()
+    }
+
+    #[test]
+    fn test_formats() {
+        let executor = CryptoRandomBytes::new();
+        let result = |params: Value| executor.execute(inputs(params), None)["result"].clone();
+        let hex = result(json!({})).as_str().unwrap().to_string();
+        assert_eq!(hex.len(), 64);
+        assert!(hex.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+        assert_ne!(result(json!({})), json!(hex));
+        assert_eq!(result(json!({"length": 16, "format": "base64"})).as_str().unwrap().len(), 24);
+        let url = result(json!({"length": 16, "format": "base64url"})).as_str().unwrap().to_string();
+        assert_eq!(url.len(), 22);
+        assert!(!url.contains(['+', '/', '=']));
+        let token = result(json!({"length": 40, "format": "alphanumeric"})).as_str().unwrap().to_string();
+        assert_eq!(token.len(), 40);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+        let data = result(json!({"length": 5, "format": "bytes"}));
+        assert_eq!(bytes::from_value(&data).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_seeded_runtime_replays() {
+        let params = json!({"length": 12, "format": "alphanumeric"});
+        assert_eq!(seeded(params.clone(), 42), seeded(params.clone(), 42));
+        assert_ne!(seeded(params.clone(), 42), seeded(params, 43));
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = CryptoRandomBytes::new();
+        let output = executor.execute(inputs(json!({"length": 0})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "length must be an integer from 1 to 65536");
+        let output = executor.execute(inputs(json!({"format": "base32"})), None);
+        assert!(output["error"].as_str().unwrap().starts_with("unknown format: base32"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "crypto.random_bytes");
+        assert_eq!(executor.category, "crypto");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for CsvParse plugin.
+
+use super::CsvParse;
+
+/// Creates a new CsvParse instance.
+pub fn create() -> CsvParse {
+    CsvParse::new()
+}
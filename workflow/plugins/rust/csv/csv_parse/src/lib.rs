@@ -0,0 +1,231 @@
+//! Workflow plugin: parse CSV or TSV text into rows.
+//!
+//! `delimiter` (`,` by default; `\t` for TSV) and `quote` (`"`) are single
+//! ASCII characters. With `header` (the default), the first row names the
+//! columns and every other row becomes an object keyed by them; otherwise
+//! every row is an array. With `infer_types`, fields that read as `true`,
+//! `false`, or a number become one, and empty fields become `null`; numbers
+//! with leading zeros, such as postal codes, stay strings.
+//!
+//! Outputs `result`, the rows, `headers`, the column names (empty without a
+//! header row), and `count`. A row whose field count differs from the
+//! header's is left out and reported in `errors` as `{"line", "message"}`,
+//! `line` being where the row starts, so one bad row does not lose the
+//! rest; with `strict`, it fails the node instead.
+
+use serde_json::{json, Map, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CsvParse implements the NodeExecutor trait for parsing CSV.
+pub struct CsvParse {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CsvParse {
+    /// Creates a new CsvParse instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "csv.parse",
+            category: "csv",
+            description: "Parse CSV or TSV text into rows",
+        }
+    }
+}
+
+impl Default for CsvParse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rows parsed, column names, and rows left out with why.
+struct Parsed {
+    rows: Vec<Value>,
+    headers: Vec<String>,
+    errors: Vec<Value>,
+}
+
+fn byte(inputs: &HashMap<String, Value>, name: &str, default: u8) -> Result<u8, String> {
+    match inputs.get(name).and_then(Value::as_str) {
+        None => Ok(default),
+        Some(text) if text.len() == 1 && text.is_ascii() => Ok(text.as_bytes()[0]),
+        Some(_) => Err(format!("{} must be a single ASCII character", name)),
+    }
+}
+
+fn flag(inputs: &HashMap<String, Value>, name: &str, default: bool) -> bool {
+    inputs.get(name).and_then(Value::as_bool).unwrap_or(default)
+}
+
+/// `field` as a bool, number, or null if it reads as one, else a string.
+fn infer(field: &str) -> Value {
+    match field {
+        "" => return Value::Null,
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    let digits = field.trim_start_matches('-');
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if !leading_zero {
+        if let Ok(n) = field.parse::<i64>() {
+            return json!(n);
+        }
+        // Rust also parses "inf" and "NaN", which are not numbers in JSON.
+        if field.bytes().all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b)) {
+            if let Ok(n) = field.parse::<f64>() {
+                return json!(n);
+            }
+        }
+    }
+    Value::String(field.to_string())
+}
+
+fn parse(inputs: &HashMap<String, Value>) -> Result<Parsed, String> {
+    let text = inputs.get("text").and_then(Value::as_str).ok_or("text is required")?;
+    let header = flag(inputs, "header", true);
+    let infer_types = flag(inputs, "infer_types", false);
+    let strict = flag(inputs, "strict", false);
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(byte(inputs, "delimiter", b',')?)
+        .quote(byte(inputs, "quote", b'"')?)
+        .from_reader(text.as_bytes());
+
+    let mut parsed = Parsed {
+        rows: Vec::new(),
+        headers: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut width = None;
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+        let line = record.position().map_or(0, |position| position.line());
+        if header && width.is_none() {
+            parsed.headers = record.iter().map(String::from).collect();
+            width = Some(record.len());
+            continue;
+        }
+        let expected = *width.get_or_insert(record.len());
+        if record.len() != expected {
+            let message = format!("expected {} fields, found {}", expected, record.len());
+            if strict {
+                return Err(format!("line {}: {}", line, message));
+            }
+            parsed.errors.push(json!({"line": line, "message": message}));
+            continue;
+        }
+        let field = |text: &str| match infer_types {
+            true => infer(text),
+            false => Value::String(text.to_string()),
+        };
+        parsed.rows.push(match header {
+            true => Value::Object(parsed.headers.iter().cloned().zip(record.iter().map(field)).collect::<Map<_, _>>()),
+            false => Value::Array(record.iter().map(field).collect()),
+        });
+    }
+    Ok(parsed)
+}
+
+impl NodeExecutor for CsvParse {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match parse(&inputs) {
+            Ok(parsed) => {
+                output.insert("count".to_string(), json!(parsed.rows.len()));
+                output.insert("result".to_string(), Value::Array(parsed.rows));
+                output.insert("headers".to_string(), json!(parsed.headers));
+                output.insert("errors".to_string(), Value::Array(parsed.errors));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CsvParse instance.
+pub fn create() -> CsvParse {
+    CsvParse::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+
+    #[test]
+    fn test_header_rows() {
+        let executor = CsvParse::new();
+        let output = executor.execute(
+            inputs(json!({"text": "name,note\nAda,\"says \"\"hi\"\", then, leaves\"\nBob,\"two\nlines\"\n"})),
+            None,
+        );
+        assert_eq!(output["headers"], json!(["name", "note"]));
+        assert_eq!(
+            output["result"],
+            json!([{"name": "Ada", "note": "says \"hi\", then, leaves"}, {"name": "Bob", "note": "two\nlines"}])
+        );
+        assert_eq!(output["count"], 2);
+        assert_eq!(output["errors"], json!([]));
+    }
+
+    #[test]
+    fn test_arrays_and_options() {
+        let executor = CsvParse::new();
+        let output = executor.execute(
+            inputs(json!({"text": "a\t'b\tc'\n1\t2\n", "delimiter": "\t", "quote": "'", "header": false})),
+            None,
+        );
+        assert_eq!(output["result"], json!([["a", "b\tc"], ["1", "2"]]));
+        assert_eq!(output["headers"], json!([]));
+    }
+
+    #[test]
+    fn test_infer_types() {
+        let executor = CsvParse::new();
+        let output = executor.execute(
+            inputs(json!({"text": "n,x,ok,zip,empty,word\n-3,2.5e1,true,02134,,nan\n", "infer_types": true})),
+            None,
+        );
+        assert_eq!(
+            output["result"],
+            json!([{"n": -3, "x": 25.0, "ok": true, "zip": "02134", "empty": null, "word": "nan"}])
+        );
+    }
+
+    #[test]
+    fn test_row_errors() {
+        let executor = CsvParse::new();
+        let text = "a,b\n1,2\n3\n4,5\n";
+        let output = executor.execute(inputs(json!({"text": text})), None);
+        assert_eq!(output["result"], json!([{"a": "1", "b": "2"}, {"a": "4", "b": "5"}]));
+        assert_eq!(output["errors"], json!([{"line": 3, "message": "expected 2 fields, found 1"}]));
+        let output = executor.execute(inputs(json!({"text": text, "strict": true})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "line 3: expected 2 fields, found 1");
+        let output = executor.execute(inputs(json!({"text": "a", "delimiter": ";;"})), None);
+        assert_eq!(output["error"], "delimiter must be a single ASCII character");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "csv.parse");
+        assert_eq!(executor.category, "csv");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for CsvSerialize plugin.
+
+use super::CsvSerialize;
+
+/// Creates a new CsvSerialize instance.
+pub fn create() -> CsvSerialize {
+    CsvSerialize::new()
+}
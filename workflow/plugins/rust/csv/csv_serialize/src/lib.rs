@@ -0,0 +1,197 @@
+//! Workflow plugin: write rows as CSV or TSV text.
+//!
+//! `rows` is a list of objects, such as `csv.parse` outputs, or of arrays.
+//! For objects, `columns` selects and orders the columns written, and
+//! defaults to every key of the rows in alphabetical order; a row missing a
+//! column gets an empty field. Arrays are written as they are, with
+//! `columns` as their header. The header row is written unless `header` is
+//! `false`.
+//!
+//! Fields holding the delimiter, the quote, or a line break are quoted, and
+//! every field is with `quote_all`. Strings are written as they are, numbers
+//! and booleans as their text, `null` as an empty field, and lists and
+//! objects as JSON. `delimiter` and `quote` work as in `csv.parse`. Outputs
+//! `result`, the text, and `count`, the number of rows written.
+
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::{BTreeSet, HashMap};
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// CsvSerialize implements the NodeExecutor trait for writing CSV.
+pub struct CsvSerialize {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl CsvSerialize {
+    /// Creates a new CsvSerialize instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "csv.serialize",
+            category: "csv",
+            description: "Write rows as CSV or TSV text",
+        }
+    }
+}
+
+impl Default for CsvSerialize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn byte(inputs: &HashMap<String, Value>, name: &str, default: u8) -> Result<u8, String> {
+    match inputs.get(name).and_then(Value::as_str) {
+        None => Ok(default),
+        Some(text) if text.len() == 1 && text.is_ascii() => Ok(text.as_bytes()[0]),
+        Some(_) => Err(format!("{} must be a single ASCII character", name)),
+    }
+}
+
+/// The text of a field holding `value`.
+fn field(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn serialize(inputs: &HashMap<String, Value>) -> Result<(String, usize), String> {
+    let rows = inputs.get("rows").and_then(Value::as_array).ok_or("rows is required")?;
+    let columns: Option<Vec<String>> = match inputs.get("columns") {
+        None | Some(Value::Null) => None,
+        Some(columns) => Some(serde_json::from_value(columns.clone()).map_err(|_| "columns must be a list of strings")?),
+    };
+    let objects = rows.iter().all(Value::is_object);
+    if !objects && !rows.iter().all(Value::is_array) {
+        return Err("rows must all be objects or all be lists".to_string());
+    }
+    let columns = columns.unwrap_or_else(|| match objects {
+        true => {
+            let keys: BTreeSet<&String> = rows.iter().filter_map(Value::as_object).flat_map(|row| row.keys()).collect();
+            keys.into_iter().cloned().collect()
+        }
+        false => Vec::new(),
+    });
+    let quote_style = match inputs.get("quote_all").and_then(Value::as_bool).unwrap_or(false) {
+        true => csv::QuoteStyle::Always,
+        false => csv::QuoteStyle::Necessary,
+    };
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .delimiter(byte(inputs, "delimiter", b',')?)
+        .quote(byte(inputs, "quote", b'"')?)
+        .quote_style(quote_style)
+        .from_writer(Vec::new());
+
+    let write_error = |e: csv::Error| e.to_string();
+    if inputs.get("header").and_then(Value::as_bool).unwrap_or(true) && !columns.is_empty() {
+        writer.write_record(&columns).map_err(write_error)?;
+    }
+    for row in rows {
+        let fields: Vec<String> = match row {
+            Value::Object(row) => columns.iter().map(|column| field(row.get(column))).collect(),
+            _ => row.as_array().into_iter().flatten().map(|value| field(Some(value))).collect(),
+        };
+        writer.write_record(&fields).map_err(write_error)?;
+    }
+    let text = writer.into_inner().map_err(|e| e.to_string())?;
+    let text = String::from_utf8(text).map_err(|e| e.to_string())?;
+    Ok((text, rows.len()))
+}
+
+impl NodeExecutor for CsvSerialize {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match serialize(&inputs) {
+            Ok((text, count)) => {
+                output.insert("result".to_string(), json!(text));
+                output.insert("count".to_string(), json!(count));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new CsvSerialize instance.
+pub fn create() -> CsvSerialize {
+    CsvSerialize::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+
+    #[test]
+    fn test_objects() {
+        let executor = CsvSerialize::new();
+        let rows = json!([
+            {"name": "Ada", "note": "says \"hi\", then leaves", "age": 36},
+            {"name": "Bob", "tags": ["a", "b"], "active": true, "age": null}
+        ]);
+        let output = executor.execute(inputs(json!({"rows": rows})), None);
+        assert_eq!(
+            output["result"],
+            "active,age,name,note,tags\n,36,Ada,\"says \"\"hi\"\", then leaves\",\n\
+             true,,Bob,,\"[\"\"a\"\",\"\"b\"\"]\"\n"
+        );
+        assert_eq!(output["count"], 2);
+    }
+
+    #[test]
+    fn test_columns_and_options() {
+        let executor = CsvSerialize::new();
+        let rows = json!([{"id": 1, "name": "Ada", "secret": "x"}, {"id": 2, "name": "B\tob"}]);
+        let output = executor.execute(
+            inputs(json!({"rows": rows, "columns": ["name", "id"], "delimiter": "\t"})),
+            None,
+        );
+        assert_eq!(output["result"], "name\tid\nAda\t1\n\"B\tob\"\t2\n");
+        let output = executor.execute(
+            inputs(json!({"rows": rows, "columns": ["id"], "header": false, "quote_all": true})),
+            None,
+        );
+        assert_eq!(output["result"], "\"1\"\n\"2\"\n");
+    }
+
+    #[test]
+    fn test_arrays() {
+        let executor = CsvSerialize::new();
+        let output = executor.execute(inputs(json!({"rows": [[1, "a,b"], [2, "c"]], "columns": ["n", "s"]})), None);
+        assert_eq!(output["result"], "n,s\n1,\"a,b\"\n2,c\n");
+        assert_eq!(executor.execute(inputs(json!({"rows": [[1], [2, 3]]})), None)["result"], "1\n2,3\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = CsvSerialize::new();
+        let output = executor.execute(inputs(json!({"rows": [{"a": 1}, [1]]})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "rows must all be objects or all be lists");
+        let output = executor.execute(inputs(json!({"rows": [], "columns": "a"})), None);
+        assert_eq!(output["error"], "columns must be a list of strings");
+        assert_eq!(executor.execute(inputs(json!({})), None)["error"], "rows is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "csv.serialize");
+        assert_eq!(executor.category, "csv");
+    }
+}
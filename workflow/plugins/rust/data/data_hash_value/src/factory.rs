@@ -0,0 +1,8 @@
+//! Factory for DataHashValue plugin.
+
+use super::DataHashValue;
+
+/// Creates a new DataHashValue instance.
+pub fn create() -> DataHashValue {
+    DataHashValue::new()
+}
@@ -0,0 +1,124 @@
+//! Workflow plugin: deterministic hash of a JSON value.
+//!
+//! The value is serialized as canonical JSON (RFC 8785: sorted keys, numbers
+//! normalized so `1.0` equals `1`) before hashing, so equal values always
+//! hash the same regardless of key order or how numbers were written.
+
+use metabuilder_core::canonical;
+use serde_json::Value;
+use sha2::{Digest, Sha256, Sha512};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// DataHashValue implements the NodeExecutor trait for hashing values.
+pub struct DataHashValue {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl DataHashValue {
+    /// Creates a new DataHashValue instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "data.hash_value",
+            category: "data",
+            description: "Deterministically hash any JSON value",
+        }
+    }
+}
+
+impl Default for DataHashValue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl NodeExecutor for DataHashValue {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let value = inputs.get("value").unwrap_or(&Value::Null);
+        let algorithm = inputs.get("algorithm").and_then(Value::as_str).unwrap_or("sha256");
+        let canonical = canonical::to_string(value);
+
+        let mut output = HashMap::new();
+        let digest = match algorithm {
+            "sha256" => to_hex(&Sha256::digest(canonical.as_bytes())),
+            "sha512" => to_hex(&Sha512::digest(canonical.as_bytes())),
+            other => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert(
+                    "error".to_string(),
+                    serde_json::json!(format!("unknown algorithm: {} (expected sha256 or sha512)", other)),
+                );
+                return output;
+            }
+        };
+
+        output.insert("result".to_string(), serde_json::json!(digest));
+        output.insert("canonical".to_string(), serde_json::json!(canonical));
+        output
+    }
+}
+
+/// Creates a new DataHashValue instance.
+pub fn create() -> DataHashValue {
+    DataHashValue::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(value: Value) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), value);
+        DataHashValue::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_hash_value() {
+        let result = hash(serde_json::json!({"b": 1.0, "a": [true, null]}));
+        assert_eq!(result.get("canonical"), Some(&serde_json::json!(r#"{"a":[true,null],"b":1}"#)));
+        // sha256 of `{"a":[true,null],"b":1}`
+        assert_eq!(
+            result.get("result"),
+            Some(&serde_json::json!(to_hex(&Sha256::digest(br#"{"a":[true,null],"b":1}"#))))
+        );
+    }
+
+    #[test]
+    fn test_hash_ignores_key_order_and_number_form() {
+        let a = hash(serde_json::from_str(r#"{"x": 10, "y": {"q": 0.5, "p": "s"}}"#).unwrap());
+        let b = hash(serde_json::from_str(r#"{"y": {"p": "s", "q": 5e-1}, "x": 1e1}"#).unwrap());
+        assert_eq!(a.get("result"), b.get("result"));
+        assert_ne!(a.get("result"), hash(serde_json::json!({"x": 11})).get("result"));
+    }
+
+    #[test]
+    fn test_unknown_algorithm() {
+        let mut inputs = HashMap::new();
+        inputs.insert("algorithm".to_string(), serde_json::json!("md5"));
+        let result = DataHashValue::new().execute(inputs, None);
+        assert_eq!(
+            result.get("error"),
+            Some(&serde_json::json!("unknown algorithm: md5 (expected sha256 or sha512)"))
+        );
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "data.hash_value");
+        assert_eq!(executor.category, "data");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for DatetimeExtract plugin.
+
+use super::DatetimeExtract;
+
+/// Creates a new DatetimeExtract instance.
+pub fn create() -> DatetimeExtract {
+    DatetimeExtract::new()
+}
@@ -0,0 +1,170 @@
+//! Workflow plugin: extract calendar facts from a datetime.
+//!
+//! Reads `value`, a string in `format` (`"rfc3339"` by default; see
+//! `metabuilder_core::datetime`) or seconds since the epoch, in its own
+//! offset, and outputs the facts reports group by: `weekday` (1 for Monday
+//! to 7), `weekday_name` (such as `"Monday"`), `iso_week` and `iso_year` (ISO
+//! 8601 week numbering, where the first days of January may belong to the
+//! previous year's last week), `day_of_year`, `quarter` (1 to 4), and
+//! `is_weekend`. `result` holds them all as one object. Invalid input outputs
+//! `error` and `reason`.
+
+use chrono::{DateTime, Datelike, FixedOffset, Weekday};
+use metabuilder_core::datetime::{self, ParseFailure};
+use serde_json::{Map, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// DatetimeExtract implements the NodeExecutor trait for calendar facts.
+pub struct DatetimeExtract {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl DatetimeExtract {
+    /// Creates a new DatetimeExtract instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "datetime.extract",
+            category: "datetime",
+            description: "Extract the weekday, ISO week, quarter, and other calendar facts from a datetime",
+        }
+    }
+}
+
+impl Default for DatetimeExtract {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+fn facts(value: &DateTime<FixedOffset>) -> Map<String, Value> {
+    let weekday = value.weekday();
+    let week = value.iso_week();
+    let mut facts = Map::new();
+    facts.insert("weekday".to_string(), Value::from(weekday.number_from_monday()));
+    facts.insert("weekday_name".to_string(), Value::from(weekday_name(weekday)));
+    facts.insert("iso_week".to_string(), Value::from(week.week()));
+    facts.insert("iso_year".to_string(), Value::from(week.year()));
+    facts.insert("day_of_year".to_string(), Value::from(value.ordinal()));
+    facts.insert("quarter".to_string(), Value::from(value.month0() / 3 + 1));
+    facts.insert("is_weekend".to_string(), Value::from(matches!(weekday, Weekday::Sat | Weekday::Sun)));
+    facts
+}
+
+fn extract(inputs: &HashMap<String, Value>) -> Result<HashMap<String, Value>, ParseFailure> {
+    let format = inputs.get("format").and_then(Value::as_str).unwrap_or("rfc3339");
+    let value = datetime::from_value(inputs.get("value"), format).map_err(|e| ParseFailure {
+        message: format!("value: {}", e.message),
+        reason: e.reason,
+    })?;
+    let facts = facts(&value);
+    let mut output: HashMap<String, Value> = facts.clone().into_iter().collect();
+    output.insert("result".to_string(), Value::Object(facts));
+    Ok(output)
+}
+
+impl NodeExecutor for DatetimeExtract {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        match extract(&inputs) {
+            Ok(output) => output,
+            Err(e) => {
+                let mut output = HashMap::new();
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e.message));
+                output.insert("reason".to_string(), serde_json::json!(e.reason));
+                output
+            }
+        }
+    }
+}
+
+/// Creates a new DatetimeExtract instance.
+pub fn create() -> DatetimeExtract {
+    DatetimeExtract::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract_value(value: Value) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), value);
+        DatetimeExtract::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_extract() {
+        let result = extract_value(serde_json::json!("2024-08-17T10:00:00+02:00"));
+        assert_eq!(
+            result["result"],
+            serde_json::json!({
+                "weekday": 6,
+                "weekday_name": "Saturday",
+                "iso_week": 33,
+                "iso_year": 2024,
+                "day_of_year": 230,
+                "quarter": 3,
+                "is_weekend": true
+            })
+        );
+        assert_eq!(result["quarter"], serde_json::json!(3));
+    }
+
+    #[test]
+    fn test_iso_week_of_previous_year() {
+        // 1 January 2021 was a Friday, in week 53 of 2020.
+        let result = extract_value(serde_json::json!("2021-01-01T12:00:00Z"));
+        assert_eq!(result["iso_week"], serde_json::json!(53));
+        assert_eq!(result["iso_year"], serde_json::json!(2020));
+        assert_eq!(result["quarter"], serde_json::json!(1));
+        assert_eq!(result["is_weekend"], serde_json::json!(false));
+    }
+
+    #[test]
+    fn test_offset_decides_the_day() {
+        // Sunday evening in New York is Monday in UTC.
+        let result = extract_value(serde_json::json!("2024-03-31T22:00:00-04:00"));
+        assert_eq!(result["weekday_name"], serde_json::json!("Sunday"));
+        assert_eq!(result["quarter"], serde_json::json!(1));
+        let result = extract_value(serde_json::json!(1711936800));
+        assert_eq!(result["weekday_name"], serde_json::json!("Monday"));
+        assert_eq!(result["quarter"], serde_json::json!(2));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let result = extract_value(serde_json::json!("2024-02-30T00:00:00Z"));
+        assert_eq!(result["result"], Value::Null);
+        assert_eq!(result["reason"], serde_json::json!("out_of_range"));
+        let result = DatetimeExtract::new().execute(HashMap::new(), None);
+        assert_eq!(result["reason"], serde_json::json!("missing"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "datetime.extract");
+        assert_eq!(executor.category, "datetime");
+    }
+}
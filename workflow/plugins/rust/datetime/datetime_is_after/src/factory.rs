@@ -0,0 +1,8 @@
+//! Factory for DatetimeIsAfter plugin.
+
+use super::DatetimeIsAfter;
+
+/// Creates a new DatetimeIsAfter instance.
+pub fn create() -> DatetimeIsAfter {
+    DatetimeIsAfter::new()
+}
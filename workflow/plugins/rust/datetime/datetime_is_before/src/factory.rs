@@ -0,0 +1,8 @@
+//! Factory for DatetimeIsBefore plugin.
+
+use super::DatetimeIsBefore;
+
+/// Creates a new DatetimeIsBefore instance.
+pub fn create() -> DatetimeIsBefore {
+    DatetimeIsBefore::new()
+}
@@ -0,0 +1,110 @@
+//! Workflow plugin: check if a datetime is before another.
+//!
+//! Outputs `result`, whether `a` is strictly earlier than `b`. Both are
+//! compared as instants, so their UTC offsets may differ. Each is a string in
+//! `format` (`"rfc3339"` by default; see `metabuilder_core::datetime`) or
+//! seconds since the epoch. An invalid input outputs `error`, naming the
+//! input, and `reason`.
+
+use metabuilder_core::datetime::{self, ParseFailure};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// DatetimeIsBefore implements the NodeExecutor trait for datetime comparison.
+pub struct DatetimeIsBefore {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl DatetimeIsBefore {
+    /// Creates a new DatetimeIsBefore instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "datetime.is_before",
+            category: "datetime",
+            description: "Check if datetime a is before datetime b",
+        }
+    }
+}
+
+impl Default for DatetimeIsBefore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_before(inputs: &HashMap<String, Value>) -> Result<bool, ParseFailure> {
+    let format = inputs.get("format").and_then(Value::as_str).unwrap_or("rfc3339");
+    let read = |name: &str| {
+        datetime::from_value(inputs.get(name), format).map_err(|e| ParseFailure {
+            message: format!("{}: {}", name, e.message),
+            reason: e.reason,
+        })
+    };
+    Ok(read("a")? < read("b")?)
+}
+
+impl NodeExecutor for DatetimeIsBefore {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match is_before(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e.message));
+                output.insert("reason".to_string(), serde_json::json!(e.reason));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new DatetimeIsBefore instance.
+pub fn create() -> DatetimeIsBefore {
+    DatetimeIsBefore::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(a: Value, b: Value) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("a".to_string(), a);
+        inputs.insert("b".to_string(), b);
+        DatetimeIsBefore::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_is_before() {
+        let result = compare(serde_json::json!("2024-03-01T09:00:00+01:00"), serde_json::json!("2024-03-01T08:30:00Z"));
+        assert_eq!(result.get("result"), Some(&serde_json::json!(true)));
+        let result = compare(serde_json::json!("2024-03-01T08:00:00Z"), serde_json::json!(1709280000));
+        assert_eq!(result.get("result"), Some(&serde_json::json!(false)));
+    }
+
+    #[test]
+    fn test_is_before_invalid() {
+        let result = compare(serde_json::json!("2024-03-01T08:00:00Z"), serde_json::json!("tomorrow"));
+        assert_eq!(result.get("result"), Some(&Value::Null));
+        assert!(result["error"].as_str().unwrap().starts_with("b: "));
+        assert!(result.contains_key("reason"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "datetime.is_before");
+        assert_eq!(executor.category, "datetime");
+    }
+}
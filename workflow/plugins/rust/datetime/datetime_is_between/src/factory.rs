@@ -0,0 +1,8 @@
+//! Factory for DatetimeIsBetween plugin.
+
+use super::DatetimeIsBetween;
+
+/// Creates a new DatetimeIsBetween instance.
+pub fn create() -> DatetimeIsBetween {
+    DatetimeIsBetween::new()
+}
@@ -0,0 +1,132 @@
+//! Workflow plugin: check if a datetime falls within a range.
+//!
+//! Outputs `result`, whether `value` lies between `start` and `end`,
+//! including both ends unless `inclusive` is false. All three are compared as
+//! instants and may be strings in `format` (`"rfc3339"` by default; see
+//! `metabuilder_core::datetime`) or seconds since the epoch. A `start` later
+//! than `end` is an error, as is an invalid input: the node outputs `error`,
+//! naming the input, and `reason`.
+
+use metabuilder_core::datetime::{self, ParseFailure};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// DatetimeIsBetween implements the NodeExecutor trait for datetime range checks.
+pub struct DatetimeIsBetween {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl DatetimeIsBetween {
+    /// Creates a new DatetimeIsBetween instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "datetime.is_between",
+            category: "datetime",
+            description: "Check if a datetime falls between a start and an end",
+        }
+    }
+}
+
+impl Default for DatetimeIsBetween {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_between(inputs: &HashMap<String, Value>) -> Result<bool, ParseFailure> {
+    let format = inputs.get("format").and_then(Value::as_str).unwrap_or("rfc3339");
+    let inclusive = inputs.get("inclusive").and_then(Value::as_bool).unwrap_or(true);
+    let read = |name: &str| {
+        datetime::from_value(inputs.get(name), format).map_err(|e| ParseFailure {
+            message: format!("{}: {}", name, e.message),
+            reason: e.reason,
+        })
+    };
+    let (value, start, end) = (read("value")?, read("start")?, read("end")?);
+    if start > end {
+        return Err(ParseFailure {
+            message: "start is after end".to_string(),
+            reason: "impossible",
+        });
+    }
+    Ok(match inclusive {
+        true => start <= value && value <= end,
+        false => start < value && value < end,
+    })
+}
+
+impl NodeExecutor for DatetimeIsBetween {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match is_between(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e.message));
+                output.insert("reason".to_string(), serde_json::json!(e.reason));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new DatetimeIsBetween instance.
+pub fn create() -> DatetimeIsBetween {
+    DatetimeIsBetween::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(value: &str, inclusive: Option<bool>) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), serde_json::json!(value));
+        inputs.insert("start".to_string(), serde_json::json!("2024-03-01T09:00:00+01:00"));
+        inputs.insert("end".to_string(), serde_json::json!("2024-03-01T17:00:00Z"));
+        if let Some(inclusive) = inclusive {
+            inputs.insert("inclusive".to_string(), serde_json::json!(inclusive));
+        }
+        DatetimeIsBetween::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_is_between() {
+        assert_eq!(check("2024-03-01T12:00:00Z", None).get("result"), Some(&serde_json::json!(true)));
+        assert_eq!(check("2024-03-01T08:00:00Z", None).get("result"), Some(&serde_json::json!(true)));
+        assert_eq!(check("2024-03-01T08:00:00Z", Some(false)).get("result"), Some(&serde_json::json!(false)));
+        assert_eq!(check("2024-03-01T17:00:01Z", None).get("result"), Some(&serde_json::json!(false)));
+    }
+
+    #[test]
+    fn test_is_between_invalid() {
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), serde_json::json!(0));
+        inputs.insert("start".to_string(), serde_json::json!(10));
+        inputs.insert("end".to_string(), serde_json::json!(5));
+        let result = DatetimeIsBetween::new().execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("start is after end")));
+
+        let result = check("noon", None);
+        assert_eq!(result.get("result"), Some(&Value::Null));
+        assert!(result["error"].as_str().unwrap().starts_with("value: "));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "datetime.is_between");
+        assert_eq!(executor.category, "datetime");
+    }
+}
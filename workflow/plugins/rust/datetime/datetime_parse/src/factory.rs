@@ -0,0 +1,8 @@
+//! Factory for DatetimeParse plugin.
+
+use super::DatetimeParse;
+
+/// Creates a new DatetimeParse instance.
+pub fn create() -> DatetimeParse {
+    DatetimeParse::new()
+}
@@ -0,0 +1,126 @@
+//! Workflow plugin: parse a datetime.
+//!
+//! Parses `string` in `format`: `"rfc3339"` (the default), `"rfc2822"`, or a
+//! strftime-style pattern such as `"%d/%m/%Y %H:%M"` (see
+//! `metabuilder_core::datetime`). Outputs `result`, the datetime as an
+//! RFC 3339 string, along with its components (`year`, `month`, `day`,
+//! `hour`, `minute`, `second`, `nanosecond`, `weekday`, `ordinal`, `offset`)
+//! and the `epoch` and `epoch_ms` timestamps. Invalid input outputs `error`
+//! with the parser's message and `reason`, a stable code such as
+//! `"out_of_range"` or `"too_short"`.
+
+use metabuilder_core::datetime::{self, ParseFailure};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// DatetimeParse implements the NodeExecutor trait for parsing datetimes.
+pub struct DatetimeParse {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl DatetimeParse {
+    /// Creates a new DatetimeParse instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "datetime.parse",
+            category: "datetime",
+            description: "Parse a string into a datetime and its components",
+        }
+    }
+}
+
+impl Default for DatetimeParse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse(inputs: &HashMap<String, Value>) -> Result<HashMap<String, Value>, ParseFailure> {
+    let text = inputs.get("string").and_then(Value::as_str).ok_or(ParseFailure {
+        message: "string is required".to_string(),
+        reason: "missing",
+    })?;
+    let format = inputs.get("format").and_then(Value::as_str).unwrap_or("rfc3339");
+    let parsed = datetime::parse(text, format)?;
+    let mut output: HashMap<String, Value> = datetime::components(&parsed).into_iter().collect();
+    output.insert("result".to_string(), serde_json::json!(parsed.to_rfc3339()));
+    Ok(output)
+}
+
+impl NodeExecutor for DatetimeParse {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        match parse(&inputs) {
+            Ok(output) => output,
+            Err(e) => {
+                let mut output = HashMap::new();
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e.message));
+                output.insert("reason".to_string(), serde_json::json!(e.reason));
+                output
+            }
+        }
+    }
+}
+
+/// Creates a new DatetimeParse instance.
+pub fn create() -> DatetimeParse {
+    DatetimeParse::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(string: &str, format: Option<&str>) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!(string));
+        if let Some(format) = format {
+            inputs.insert("format".to_string(), serde_json::json!(format));
+        }
+        inputs
+    }
+
+    #[test]
+    fn test_parse_rfc3339() {
+        let result = DatetimeParse::new().execute(inputs("2024-02-29T13:45:30+02:00", None), None);
+        assert_eq!(result["result"], serde_json::json!("2024-02-29T13:45:30+02:00"));
+        assert_eq!(result["month"], serde_json::json!(2));
+        assert_eq!(result["hour"], serde_json::json!(13));
+        assert_eq!(result["offset"], serde_json::json!(7200));
+        assert_eq!(result["epoch"], serde_json::json!(1709207130));
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        let result = DatetimeParse::new().execute(inputs("29/02/2024", Some("%d/%m/%Y")), None);
+        assert_eq!(result["result"], serde_json::json!("2024-02-29T00:00:00+00:00"));
+        assert_eq!(result["weekday"], serde_json::json!(4));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let result = DatetimeParse::new().execute(inputs("30/02/2024", Some("%d/%m/%Y")), None);
+        assert_eq!(result["result"], Value::Null);
+        assert_eq!(result["reason"], serde_json::json!("out_of_range"));
+        assert!(result["error"].is_string());
+
+        let result = DatetimeParse::new().execute(HashMap::new(), None);
+        assert_eq!(result["reason"], serde_json::json!("missing"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "datetime.parse");
+        assert_eq!(executor.category, "datetime");
+    }
+}
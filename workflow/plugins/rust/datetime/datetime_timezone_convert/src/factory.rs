@@ -0,0 +1,8 @@
+//! Factory for DatetimeTimezoneConvert plugin.
+
+use super::DatetimeTimezoneConvert;
+
+/// Creates a new DatetimeTimezoneConvert instance.
+pub fn create() -> DatetimeTimezoneConvert {
+    DatetimeTimezoneConvert::new()
+}
@@ -0,0 +1,171 @@
+//! Workflow plugin: convert a datetime between timezones.
+//!
+//! Converts `value` to the IANA timezone `to` (such as `"Asia/Tokyo"`), using
+//! the tz database built into the plugin, so no system zone files are
+//! needed. `value` is a string in `format` (`"rfc3339"` by default; see
+//! `metabuilder_core::datetime`) or seconds since the epoch. With `from`, its
+//! date and time are read as wall-clock time in that zone, replacing any
+//! offset it had; a time skipped by a daylight saving change is an error,
+//! and a repeated one reads as the earlier instant.
+//!
+//! Outputs `result`, the same instant as an RFC 3339 string with the offset
+//! of `to`, its components in `to` (see `datetime.parse`), and `abbreviation`,
+//! such as `"JST"`. Invalid input outputs `error` and `reason`, which is
+//! `"unknown_timezone"` for zone names not in the database.
+
+use chrono::{DateTime, FixedOffset, LocalResult, TimeZone};
+use chrono_tz::{OffsetName, Tz};
+use metabuilder_core::datetime::{self, ParseFailure};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// DatetimeTimezoneConvert implements the NodeExecutor trait for timezone conversion.
+pub struct DatetimeTimezoneConvert {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl DatetimeTimezoneConvert {
+    /// Creates a new DatetimeTimezoneConvert instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "datetime.timezone_convert",
+            category: "datetime",
+            description: "Convert a datetime between IANA timezones",
+        }
+    }
+}
+
+impl Default for DatetimeTimezoneConvert {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn zone(inputs: &HashMap<String, Value>, name: &str) -> Result<Option<Tz>, ParseFailure> {
+    match inputs.get(name).and_then(Value::as_str) {
+        None => Ok(None),
+        Some(zone) => zone.parse::<Tz>().map(Some).map_err(|_| ParseFailure {
+            message: format!("{}: unknown timezone {}", name, zone),
+            reason: "unknown_timezone",
+        }),
+    }
+}
+
+fn convert(inputs: &HashMap<String, Value>) -> Result<HashMap<String, Value>, ParseFailure> {
+    let format = inputs.get("format").and_then(Value::as_str).unwrap_or("rfc3339");
+    let to = zone(inputs, "to")?.ok_or(ParseFailure {
+        message: "to is required".to_string(),
+        reason: "missing",
+    })?;
+    let value = datetime::from_value(inputs.get("value"), format).map_err(|e| ParseFailure {
+        message: format!("value: {}", e.message),
+        reason: e.reason,
+    })?;
+    let instant: DateTime<FixedOffset> = match zone(inputs, "from")? {
+        None => value,
+        Some(from) => match from.from_local_datetime(&value.naive_local()) {
+            LocalResult::Single(local) | LocalResult::Ambiguous(local, _) => local.fixed_offset(),
+            LocalResult::None => {
+                return Err(ParseFailure {
+                    message: format!("value: {} does not exist in {}", value.naive_local(), from),
+                    reason: "impossible",
+                })
+            }
+        },
+    };
+    let converted = instant.with_timezone(&to);
+    let mut output: HashMap<String, Value> = datetime::components(&converted.fixed_offset()).into_iter().collect();
+    output.insert("result".to_string(), serde_json::json!(converted.to_rfc3339()));
+    output.insert("abbreviation".to_string(), serde_json::json!(converted.offset().abbreviation()));
+    Ok(output)
+}
+
+impl NodeExecutor for DatetimeTimezoneConvert {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        match convert(&inputs) {
+            Ok(output) => output,
+            Err(e) => {
+                let mut output = HashMap::new();
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e.message));
+                output.insert("reason".to_string(), serde_json::json!(e.reason));
+                output
+            }
+        }
+    }
+}
+
+/// Creates a new DatetimeTimezoneConvert instance.
+pub fn create() -> DatetimeTimezoneConvert {
+    DatetimeTimezoneConvert::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(pairs: &[(&str, Value)]) -> HashMap<String, Value> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.clone())).collect()
+    }
+
+    #[test]
+    fn test_convert() {
+        let result = DatetimeTimezoneConvert::new().execute(
+            inputs(&[("value", serde_json::json!("2024-07-01T09:00:00-04:00")), ("to", serde_json::json!("Asia/Tokyo"))]),
+            None,
+        );
+        assert_eq!(result["result"], serde_json::json!("2024-07-01T22:00:00+09:00"));
+        assert_eq!(result["hour"], serde_json::json!(22));
+        assert_eq!(result["abbreviation"], serde_json::json!("JST"));
+    }
+
+    #[test]
+    fn test_convert_wall_clock() {
+        let result = DatetimeTimezoneConvert::new().execute(
+            inputs(&[
+                ("value", serde_json::json!("2024-01-15 09:00")),
+                ("format", serde_json::json!("%Y-%m-%d %H:%M")),
+                ("from", serde_json::json!("America/New_York")),
+                ("to", serde_json::json!("Europe/London")),
+            ]),
+            None,
+        );
+        assert_eq!(result["result"], serde_json::json!("2024-01-15T14:00:00+00:00"));
+        assert_eq!(result["abbreviation"], serde_json::json!("GMT"));
+
+        let result = DatetimeTimezoneConvert::new().execute(
+            inputs(&[
+                ("value", serde_json::json!("2024-03-10T02:30:00Z")),
+                ("from", serde_json::json!("America/New_York")),
+                ("to", serde_json::json!("UTC")),
+            ]),
+            None,
+        );
+        assert_eq!(result["reason"], serde_json::json!("impossible"));
+    }
+
+    #[test]
+    fn test_unknown_timezone() {
+        let result = DatetimeTimezoneConvert::new()
+            .execute(inputs(&[("value", serde_json::json!(0)), ("to", serde_json::json!("Mars/Olympus"))]), None);
+        assert_eq!(result["result"], Value::Null);
+        assert_eq!(result["error"], serde_json::json!("to: unknown timezone Mars/Olympus"));
+        assert_eq!(result["reason"], serde_json::json!("unknown_timezone"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "datetime.timezone_convert");
+        assert_eq!(executor.category, "datetime");
+    }
+}
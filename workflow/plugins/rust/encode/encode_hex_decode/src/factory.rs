@@ -0,0 +1,8 @@
+//! Factory for EncodeHexDecode plugin.
+
+use super::EncodeHexDecode;
+
+/// Creates a new EncodeHexDecode instance.
+pub fn create() -> EncodeHexDecode {
+    EncodeHexDecode::new()
+}
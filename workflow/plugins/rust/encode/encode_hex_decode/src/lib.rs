@@ -0,0 +1,92 @@
+//! Workflow plugin: decode hexadecimal to bytes.
+//!
+//! `hex` may use either case; whitespace between digits and a leading `0x`
+//! are ignored. Returns a bytes value; `bytes.to_string` turns it into text.
+
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// EncodeHexDecode implements the NodeExecutor trait for hex decoding.
+pub struct EncodeHexDecode {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl EncodeHexDecode {
+    /// Creates a new EncodeHexDecode instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "encode.hex_decode",
+            category: "encode",
+            description: "Decode hexadecimal to bytes",
+        }
+    }
+}
+
+impl Default for EncodeHexDecode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn decode(inputs: &HashMap<String, Value>) -> Result<Value, String> {
+    let hex = inputs.get("hex").and_then(Value::as_str).ok_or("hex is required")?;
+    Ok(bytes::to_value(&bytes::from_hex(hex)?))
+}
+
+impl NodeExecutor for EncodeHexDecode {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match decode(&inputs) {
+            Ok(data) => {
+                output.insert("result".to_string(), data);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new EncodeHexDecode instance.
+pub fn create() -> EncodeHexDecode {
+    EncodeHexDecode::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_decode() {
+        let executor = EncodeHexDecode::new();
+        let output = executor.execute(inputs(json!({"hex": "0x009F 9296"})), None);
+        assert_eq!(bytes::from_value(&output["result"]).unwrap(), vec![0, 159, 146, 150]);
+        let output = executor.execute(inputs(json!({"hex": "0g"})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "invalid hex digit: 'g'");
+        assert_eq!(executor.execute(inputs(json!({})), None)["error"], "hex is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "encode.hex_decode");
+        assert_eq!(executor.category, "encode");
+    }
+}
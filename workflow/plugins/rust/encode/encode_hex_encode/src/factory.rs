@@ -0,0 +1,8 @@
+//! Factory for EncodeHexEncode plugin.
+
+use super::EncodeHexEncode;
+
+/// Creates a new EncodeHexEncode instance.
+pub fn create() -> EncodeHexEncode {
+    EncodeHexEncode::new()
+}
@@ -0,0 +1,92 @@
+//! Workflow plugin: encode bytes as hexadecimal.
+//!
+//! `bytes` is a bytes value or a string, read as UTF-8. Returns two
+//! lowercase digits per byte, or uppercase with `uppercase`.
+
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// EncodeHexEncode implements the NodeExecutor trait for hex encoding.
+pub struct EncodeHexEncode {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl EncodeHexEncode {
+    /// Creates a new EncodeHexEncode instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "encode.hex_encode",
+            category: "encode",
+            description: "Encode bytes as hexadecimal",
+        }
+    }
+}
+
+impl Default for EncodeHexEncode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn encode(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let data = bytes::from_value(inputs.get("bytes").unwrap_or(&Value::Null))?;
+    let uppercase = inputs.get("uppercase").and_then(Value::as_bool).unwrap_or(false);
+    Ok(bytes::to_hex(&data, uppercase))
+}
+
+impl NodeExecutor for EncodeHexEncode {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match encode(&inputs) {
+            Ok(hex) => {
+                output.insert("result".to_string(), serde_json::json!(hex));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new EncodeHexEncode instance.
+pub fn create() -> EncodeHexEncode {
+    EncodeHexEncode::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_encode() {
+        let executor = EncodeHexEncode::new();
+        let data = bytes::to_value(&[0, 159, 146, 150]);
+        assert_eq!(executor.execute(inputs(json!({"bytes": data})), None)["result"], "009f9296");
+        assert_eq!(executor.execute(inputs(json!({"bytes": "hi", "uppercase": true})), None)["result"], "6869");
+        assert_eq!(executor.execute(inputs(json!({"bytes": [0xfe], "uppercase": true})), None)["result"], Value::Null);
+        assert_eq!(executor.execute(inputs(json!({})), None)["error"], "expected bytes or a string, got null");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "encode.hex_encode");
+        assert_eq!(executor.category, "encode");
+    }
+}
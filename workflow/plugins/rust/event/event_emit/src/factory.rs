@@ -0,0 +1,8 @@
+//! Factory for EventEmit plugin.
+
+use super::EventEmit;
+
+/// Creates a new EventEmit instance.
+pub fn create() -> EventEmit {
+    EventEmit::new()
+}
@@ -0,0 +1,102 @@
+//! Workflow plugin: publish an event.
+//!
+//! Publishes `payload` on `topic` to the run's event bus, where `event.on`
+//! nodes and host listeners see it, and passes `payload` through as
+//! `result`. Runs nested in `workflow.call` and loop bodies share the bus of
+//! the run that started them.
+
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// EventEmit implements the NodeExecutor trait for publishing events.
+pub struct EventEmit {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl EventEmit {
+    /// Creates a new EventEmit instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "event.emit",
+            category: "event",
+            description: "Publish an event on a topic",
+        }
+    }
+}
+
+impl Default for EventEmit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn emit(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Value, String> {
+    let topic = inputs.get("topic").and_then(Value::as_str).ok_or("topic is required")?;
+    let events = &Runtime::from_any(runtime).ok_or("runtime with an event bus is required")?.events;
+    let payload = inputs.get("payload").cloned().unwrap_or(Value::Null);
+    events.emit(topic, payload.clone());
+    Ok(payload)
+}
+
+impl NodeExecutor for EventEmit {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match emit(&inputs, runtime) {
+            Ok(payload) => {
+                output.insert("result".to_string(), payload);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new EventEmit instance.
+pub fn create() -> EventEmit {
+    EventEmit::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit() {
+        let runtime = Runtime::new();
+        let executor = EventEmit::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("topic".to_string(), serde_json::json!("progress"));
+        inputs.insert("payload".to_string(), serde_json::json!({"done": 3}));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("result"), Some(&serde_json::json!({"done": 3})));
+        assert_eq!(runtime.events.payloads("progress"), vec![serde_json::json!({"done": 3})]);
+    }
+
+    #[test]
+    fn test_emit_without_topic() {
+        let executor = EventEmit::new();
+        let result = executor.execute(HashMap::new(), Some(&Runtime::new()));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("topic is required")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "event.emit");
+        assert_eq!(executor.category, "event");
+    }
+}
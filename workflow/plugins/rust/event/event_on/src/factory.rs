@@ -0,0 +1,8 @@
+//! Factory for EventOn plugin.
+
+use super::EventOn;
+
+/// Creates a new EventOn instance.
+pub fn create() -> EventOn {
+    EventOn::new()
+}
@@ -0,0 +1,153 @@
+//! Workflow plugin: read the events published on a topic.
+//!
+//! Returns the payloads published on `topic` so far as `events`, with their
+//! `count` and the last one as `result`. With `count`, waits until at least
+//! that many have been published, for up to `timeout_ms` (default 30 s),
+//! failing if the timeout passes or the run is cancelled first. Since nodes
+//! of one level run side by side with parallel execution, a waiting
+//! `event.on` can consume events from a branch still running.
+
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// EventOn implements the NodeExecutor trait for reading events.
+pub struct EventOn {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl EventOn {
+    /// Creates a new EventOn instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "event.on",
+            category: "event",
+            description: "Read or wait for the events published on a topic",
+        }
+    }
+}
+
+impl Default for EventOn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+fn events(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Vec<Value>, String> {
+    let topic = inputs.get("topic").and_then(Value::as_str).ok_or("topic is required")?;
+    let rt = Runtime::from_any(runtime).ok_or("runtime with an event bus is required")?;
+    let Some(count) = inputs.get("count") else {
+        return Ok(rt.events.payloads(topic));
+    };
+    let count = count
+        .as_u64()
+        .ok_or_else(|| format!("count must be a non-negative integer, got {}", count))?;
+    let timeout = match inputs.get("timeout_ms") {
+        None => DEFAULT_TIMEOUT_MS,
+        Some(ms) => ms
+            .as_u64()
+            .ok_or_else(|| format!("timeout_ms must be a non-negative integer, got {}", ms))?,
+    };
+    rt.events
+        .wait(topic, count as usize, Duration::from_millis(timeout), &rt.cancel)
+        .ok_or_else(|| match rt.cancel.is_cancelled() {
+            true => "cancelled".to_string(),
+            false => format!("timed out waiting for {} events on {}", count, topic),
+        })
+}
+
+impl NodeExecutor for EventOn {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match events(&inputs, runtime) {
+            Ok(events) => {
+                output.insert("result".to_string(), events.last().cloned().unwrap_or(Value::Null));
+                output.insert("count".to_string(), serde_json::json!(events.len()));
+                output.insert("events".to_string(), Value::Array(events));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new EventOn instance.
+pub fn create() -> EventOn {
+    EventOn::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(topic: &str, count: Option<u64>) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("topic".to_string(), serde_json::json!(topic));
+        if let Some(count) = count {
+            inputs.insert("count".to_string(), serde_json::json!(count));
+            inputs.insert("timeout_ms".to_string(), serde_json::json!(5_000));
+        }
+        inputs
+    }
+
+    #[test]
+    fn test_on_published() {
+        let runtime = Runtime::new();
+        runtime.events.emit("progress", serde_json::json!(1));
+        runtime.events.emit("other", serde_json::json!("x"));
+        runtime.events.emit("progress", serde_json::json!(2));
+
+        let result = EventOn::new().execute(inputs("progress", None), Some(&runtime));
+        assert_eq!(result.get("events"), Some(&serde_json::json!([1, 2])));
+        assert_eq!(result.get("count"), Some(&serde_json::json!(2)));
+        assert_eq!(result.get("result"), Some(&serde_json::json!(2)));
+    }
+
+    #[test]
+    fn test_on_waits() {
+        let runtime = Runtime::new();
+        let events = runtime.events.clone();
+        let publisher = std::thread::spawn(move || {
+            for i in 0..3 {
+                std::thread::sleep(Duration::from_millis(10));
+                events.emit("tick", serde_json::json!(i));
+            }
+        });
+
+        let result = EventOn::new().execute(inputs("tick", Some(3)), Some(&runtime));
+        publisher.join().unwrap();
+        assert_eq!(result.get("events"), Some(&serde_json::json!([0, 1, 2])));
+    }
+
+    #[test]
+    fn test_on_timeout() {
+        let runtime = Runtime::new();
+        let mut inputs = inputs("tick", Some(1));
+        inputs.insert("timeout_ms".to_string(), serde_json::json!(10));
+
+        let result = EventOn::new().execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("timed out waiting for 1 events on tick")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "event.on");
+        assert_eq!(executor.category, "event");
+    }
+}
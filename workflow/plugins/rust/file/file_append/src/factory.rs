@@ -0,0 +1,8 @@
+//! Factory for FileAppend plugin.
+
+use super::FileAppend;
+
+/// Creates a new FileAppend instance.
+pub fn create() -> FileAppend {
+    FileAppend::new()
+}
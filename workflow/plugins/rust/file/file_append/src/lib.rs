@@ -0,0 +1,145 @@
+//! Workflow plugin: append content to a file.
+//!
+//! Appends `content`, a string or a bytes value (see
+//! `metabuilder_core::bytes`), to the file at `path`, creating it if it does
+//! not exist. With `atomic`, the old contents and the new are written to a
+//! temporary file beside it that is then renamed over it, so readers never
+//! see a partial append; this copies the file, so it suits small files. With
+//! `create_dirs`, missing parent directories are created first.
+//!
+//! Outputs `result`, the number of bytes appended.
+
+use metabuilder_core::{bytes, files};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileAppend implements the NodeExecutor trait for appending to files.
+pub struct FileAppend {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileAppend {
+    /// Creates a new FileAppend instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.append",
+            category: "file",
+            description: "Append content to a file",
+        }
+    }
+}
+
+impl Default for FileAppend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn append(inputs: &HashMap<String, Value>) -> Result<usize, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let content = inputs.get("content").ok_or("content is required")?;
+    let data = bytes::from_value(content).map_err(|_| "content must be a string or bytes".to_string())?;
+    let atomic = inputs.get("atomic").and_then(Value::as_bool).unwrap_or(false);
+    let create_dirs = inputs.get("create_dirs").and_then(Value::as_bool).unwrap_or(false);
+
+    let file = Path::new(path);
+    if create_dirs {
+        files::create_parent_dirs(file).map_err(|e| format!("cannot create directories for {}: {}", path, e))?;
+    }
+    let written = match atomic {
+        true => files::write_atomic(file, Some(file).filter(|f| f.exists()), &data),
+        false => files::write(file, &data, true),
+    };
+    written.map_err(|e| format!("cannot append to {}: {}", path, e))?;
+    Ok(data.len())
+}
+
+impl NodeExecutor for FileAppend {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match append(&inputs) {
+            Ok(written) => {
+                output.insert("result".to_string(), serde_json::json!(written));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileAppend instance.
+pub fn create() -> FileAppend {
+    FileAppend::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::{inputs, scratch};
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_append() {
+        let executor = FileAppend::new();
+        let dir = scratch("plain");
+        let path = dir.join("logs/app.log");
+        let output = executor.execute(inputs(json!({"path": path, "content": "one\n", "create_dirs": true})), None);
+        assert_eq!(output["result"], json!(4));
+        assert_eq!(executor.execute(inputs(json!({"path": path, "content": "two\n"})), None)["result"], json!(4));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_atomic() {
+        let executor = FileAppend::new();
+        let dir = scratch("atomic");
+        let path = dir.join("app.log");
+        let output = executor.execute(
+            inputs(json!({"path": path, "content": "one\n", "atomic": true, "create_dirs": true})),
+            None,
+        );
+        assert_eq!(output["result"], json!(4), "{:?}", output.get("error"));
+        let output = executor.execute(
+            inputs(json!({"path": path, "content": bytes::to_value(b"two\n"), "atomic": true})),
+            None,
+        );
+        assert_eq!(output["result"], json!(4), "{:?}", output.get("error"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = FileAppend::new();
+        let dir = scratch("errors");
+        let output = executor.execute(inputs(json!({"path": dir.join("missing/app.log"), "content": "x"})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].as_str().unwrap().starts_with("cannot append to"));
+        let output = executor.execute(inputs(json!({"path": "x", "content": [1]})), None);
+        assert_eq!(output["error"], "content must be a string or bytes");
+        assert_eq!(executor.execute(inputs(json!({"path": "x"})), None)["error"], "content is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.append");
+        assert_eq!(executor.category, "file");
+    }
+}
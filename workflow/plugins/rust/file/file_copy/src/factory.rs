@@ -0,0 +1,8 @@
+//! Factory for FileCopy plugin.
+
+use super::FileCopy;
+
+/// Creates a new FileCopy instance.
+pub fn create() -> FileCopy {
+    FileCopy::new()
+}
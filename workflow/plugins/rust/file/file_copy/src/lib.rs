@@ -0,0 +1,135 @@
+//! Workflow plugin: copy a file.
+//!
+//! Copies the file at `from` to `to`. An existing `to` is an error unless
+//! `overwrite` is set, and `create_dirs` creates the missing parent
+//! directories of `to`. Directories are not copied. Outputs `result`, the
+//! number of bytes copied.
+
+use metabuilder_core::files;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileCopy implements the NodeExecutor trait for copying files.
+pub struct FileCopy {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileCopy {
+    /// Creates a new FileCopy instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.copy",
+            category: "file",
+            description: "Copy a file",
+        }
+    }
+}
+
+impl Default for FileCopy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn copy(inputs: &HashMap<String, Value>) -> Result<u64, String> {
+    let from = inputs.get("from").and_then(Value::as_str).ok_or("from is required")?;
+    let to = inputs.get("to").and_then(Value::as_str).ok_or("to is required")?;
+    let overwrite = inputs.get("overwrite").and_then(Value::as_bool).unwrap_or(false);
+    let create_dirs = inputs.get("create_dirs").and_then(Value::as_bool).unwrap_or(false);
+    let metadata = fs::metadata(from).map_err(|e| format!("cannot read {}: {}", from, e))?;
+    if metadata.is_dir() {
+        return Err(format!("cannot copy a directory: {}", from));
+    }
+    let to = Path::new(to);
+    if !overwrite && to.exists() {
+        return Err(format!("{} already exists", to.display()));
+    }
+    if create_dirs {
+        files::create_parent_dirs(to).map_err(|e| format!("cannot create directories for {}: {}", to.display(), e))?;
+    }
+    fs::copy(from, to).map_err(|e| format!("cannot copy {} to {}: {}", from, to.display(), e))
+}
+
+impl NodeExecutor for FileCopy {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match copy(&inputs) {
+            Ok(copied) => {
+                output.insert("result".to_string(), json!(copied));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileCopy instance.
+pub fn create() -> FileCopy {
+    FileCopy::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::scratch;
+
+    fn run(from: &Path, to: &Path, extra: Value) -> HashMap<String, Value> {
+        let mut inputs = HashMap::from([("from".to_string(), json!(from)), ("to".to_string(), json!(to))]);
+        inputs.extend(serde_json::from_value::<HashMap<String, Value>>(extra).unwrap());
+        FileCopy::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_copy() {
+        let dir = scratch("copy");
+        let from = dir.join("a.txt");
+        fs::write(&from, "hello").unwrap();
+        let to = dir.join("sub/b.txt");
+        let output = run(&from, &to, json!({}));
+        assert!(output["error"].as_str().unwrap().starts_with("cannot copy"));
+        let output = run(&from, &to, json!({"create_dirs": true}));
+        assert_eq!(output["result"], 5);
+        assert_eq!(fs::read_to_string(&to).unwrap(), "hello");
+        assert_eq!(fs::read_to_string(&from).unwrap(), "hello");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let dir = scratch("overwrite");
+        let from = dir.join("a.txt");
+        let to = dir.join("b.txt");
+        fs::write(&from, "new").unwrap();
+        fs::write(&to, "old").unwrap();
+        let output = run(&from, &to, json!({}));
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].as_str().unwrap().ends_with("already exists"));
+        assert_eq!(fs::read_to_string(&to).unwrap(), "old");
+        run(&from, &to, json!({"overwrite": true}));
+        assert_eq!(fs::read_to_string(&to).unwrap(), "new");
+        let output = run(&dir, &dir.join("c"), json!({}));
+        assert!(output["error"].as_str().unwrap().starts_with("cannot copy a directory"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.copy");
+        assert_eq!(executor.category, "file");
+    }
+}
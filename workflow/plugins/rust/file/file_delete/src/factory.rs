@@ -0,0 +1,8 @@
+//! Factory for FileDelete plugin.
+
+use super::FileDelete;
+
+/// Creates a new FileDelete instance.
+pub fn create() -> FileDelete {
+    FileDelete::new()
+}
@@ -0,0 +1,124 @@
+//! Workflow plugin: delete a file or directory.
+//!
+//! Deletes the file at `path`. Directories are deleted only with
+//! `recursive`, along with everything in them. A missing path is an error
+//! unless `missing_ok` is set. Outputs `result`, whether anything was
+//! deleted.
+
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileDelete implements the NodeExecutor trait for deleting files.
+pub struct FileDelete {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileDelete {
+    /// Creates a new FileDelete instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.delete",
+            category: "file",
+            description: "Delete a file or directory",
+        }
+    }
+}
+
+impl Default for FileDelete {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn delete(inputs: &HashMap<String, Value>) -> Result<bool, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let recursive = inputs.get("recursive").and_then(Value::as_bool).unwrap_or(false);
+    let missing_ok = inputs.get("missing_ok").and_then(Value::as_bool).unwrap_or(false);
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == ErrorKind::NotFound && missing_ok => return Ok(false),
+        Err(e) => return Err(format!("cannot read {}: {}", path, e)),
+    };
+    let deleted = match (metadata.is_dir(), recursive) {
+        (false, _) => fs::remove_file(path),
+        (true, true) => fs::remove_dir_all(path),
+        (true, false) => return Err(format!("{} is a directory; set recursive to delete it", path)),
+    };
+    deleted.map_err(|e| format!("cannot delete {}: {}", path, e))?;
+    Ok(true)
+}
+
+impl NodeExecutor for FileDelete {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match delete(&inputs) {
+            Ok(deleted) => {
+                output.insert("result".to_string(), json!(deleted));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileDelete instance.
+pub fn create() -> FileDelete {
+    FileDelete::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::scratch;
+    use std::path::Path;
+
+    fn run(path: &Path, extra: Value) -> HashMap<String, Value> {
+        let mut inputs = HashMap::from([("path".to_string(), json!(path))]);
+        inputs.extend(serde_json::from_value::<HashMap<String, Value>>(extra).unwrap());
+        FileDelete::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_delete_file() {
+        let dir = scratch("file");
+        let path = dir.join("a.txt");
+        fs::write(&path, "x").unwrap();
+        assert_eq!(run(&path, json!({}))["result"], true);
+        assert!(!path.exists());
+        assert!(run(&path, json!({}))["error"].as_str().unwrap().starts_with("cannot read"));
+        assert_eq!(run(&path, json!({"missing_ok": true}))["result"], false);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_delete_dir() {
+        let dir = scratch("dir");
+        fs::write(dir.join("a.txt"), "x").unwrap();
+        let output = run(&dir, json!({}));
+        assert!(output["error"].as_str().unwrap().ends_with("set recursive to delete it"));
+        assert!(dir.exists());
+        assert_eq!(run(&dir, json!({"recursive": true}))["result"], true);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.delete");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for FileDetectMime plugin.
+
+use super::FileDetectMime;
+
+/// Creates a new FileDetectMime instance.
+pub fn create() -> FileDetectMime {
+    FileDetectMime::new()
+}
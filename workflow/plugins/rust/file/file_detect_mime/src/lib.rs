@@ -0,0 +1,310 @@
+//! Workflow plugin: detect the MIME type of a file.
+//!
+//! Looks at the leading magic bytes of `bytes` (a tagged bytes value or a
+//! string) and at the extension of `filename`; either may be omitted. Content
+//! wins over the extension, except that a generic result from content (a zip
+//! container, XML, or plain text) is refined by the extension, so `.docx`,
+//! `.svg`, and `.csv` files get their specific types.
+//!
+//! Outputs `result`, the MIME type (`application/octet-stream` if nothing
+//! matched), and `source`: `"content"`, `"extension"`, or `"default"`.
+
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileDetectMime implements the NodeExecutor trait for MIME type detection.
+pub struct FileDetectMime {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileDetectMime {
+    /// Creates a new FileDetectMime instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.detect_mime",
+            category: "file",
+            description: "Detect a MIME type from magic bytes and file extension",
+        }
+    }
+}
+
+impl Default for FileDetectMime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Content types that only say what container a file uses.
+const GENERIC: [&str; 3] = ["application/zip", "application/xml", "text/plain"];
+
+/// Signatures checked in order: offset, magic bytes, MIME type.
+const MAGIC: &[(usize, &[u8], &str)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", "image/png"),
+    (0, b"\xff\xd8\xff", "image/jpeg"),
+    (0, b"GIF87a", "image/gif"),
+    (0, b"GIF89a", "image/gif"),
+    (0, b"BM", "image/bmp"),
+    (0, b"II*\x00", "image/tiff"),
+    (0, b"MM\x00*", "image/tiff"),
+    (0, b"\x00\x00\x01\x00", "image/vnd.microsoft.icon"),
+    (0, b"%PDF-", "application/pdf"),
+    (0, b"%!PS", "application/postscript"),
+    (0, b"{\\rtf", "application/rtf"),
+    (0, b"PK\x03\x04", "application/zip"),
+    (0, b"PK\x05\x06", "application/zip"),
+    (0, b"\x1f\x8b", "application/gzip"),
+    (0, b"BZh", "application/x-bzip2"),
+    (0, b"\xfd7zXZ\x00", "application/x-xz"),
+    (0, b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (0, b"Rar!\x1a\x07", "application/vnd.rar"),
+    (0, b"\x28\xb5\x2f\xfd", "application/zstd"),
+    (257, b"ustar", "application/x-tar"),
+    (0, b"\x7fELF", "application/x-elf"),
+    (0, b"MZ", "application/vnd.microsoft.portable-executable"),
+    (0, b"\x00asm", "application/wasm"),
+    (0, b"PAR1", "application/vnd.apache.parquet"),
+    (0, b"SQLite format 3\x00", "application/vnd.sqlite3"),
+    (0, b"wOFF", "font/woff"),
+    (0, b"wOF2", "font/woff2"),
+    (0, b"OggS", "audio/ogg"),
+    (0, b"fLaC", "audio/flac"),
+    (0, b"ID3", "audio/mpeg"),
+    (0, b"\xff\xfb", "audio/mpeg"),
+    (0, b"\x1aE\xdf\xa3", "video/webm"),
+];
+
+/// Known extensions, lowercase.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("bmp", "image/bmp"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("svg", "image/svg+xml"),
+    ("avif", "image/avif"),
+    ("heic", "image/heic"),
+    ("pdf", "application/pdf"),
+    ("ps", "application/postscript"),
+    ("rtf", "application/rtf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("bz2", "application/x-bzip2"),
+    ("xz", "application/x-xz"),
+    ("7z", "application/x-7z-compressed"),
+    ("rar", "application/vnd.rar"),
+    ("zst", "application/zstd"),
+    ("tar", "application/x-tar"),
+    ("wasm", "application/wasm"),
+    ("parquet", "application/vnd.apache.parquet"),
+    ("sqlite", "application/vnd.sqlite3"),
+    ("jar", "application/java-archive"),
+    ("epub", "application/epub+zip"),
+    ("docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+    ("xlsx", "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+    ("pptx", "application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("toml", "application/toml"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("tsv", "text/tab-separated-values"),
+    ("md", "text/markdown"),
+    ("txt", "text/plain"),
+    ("log", "text/plain"),
+    ("ics", "text/calendar"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("ttf", "font/ttf"),
+    ("otf", "font/otf"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("flac", "audio/flac"),
+    ("mp4", "video/mp4"),
+    ("mov", "video/quicktime"),
+    ("webm", "video/webm"),
+    ("avi", "video/x-msvideo"),
+];
+
+fn from_content(data: &[u8]) -> Option<&'static str> {
+    let signature = MAGIC
+        .iter()
+        .find(|(offset, magic, _)| data.get(*offset..*offset + magic.len()) == Some(*magic))
+        .map(|(_, _, mime)| *mime);
+    if signature.is_some() {
+        return signature;
+    }
+    match (data.get(..4), data.get(8..12)) {
+        (Some(b"RIFF"), Some(b"WEBP")) => return Some("image/webp"),
+        (Some(b"RIFF"), Some(b"WAVE")) => return Some("audio/wav"),
+        (Some(b"RIFF"), Some(b"AVI ")) => return Some("video/x-msvideo"),
+        _ => {}
+    }
+    if data.get(4..8) == Some(b"ftyp") {
+        return Some(match data.get(8..12) {
+            Some(b"avif") => "image/avif",
+            Some(b"heic") | Some(b"heix") => "image/heic",
+            Some(b"qt  ") => "video/quicktime",
+            _ => "video/mp4",
+        });
+    }
+    from_text(data)
+}
+
+/// Recognizes markup and plain text, which have no fixed signature.
+fn from_text(data: &[u8]) -> Option<&'static str> {
+    let text = std::str::from_utf8(data).ok()?;
+    if text.is_empty() || text.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+        return None;
+    }
+    let head = text.trim_start_matches('\u{feff}').trim_start();
+    let lower = head.get(..head.len().min(64)).unwrap_or(head).to_ascii_lowercase();
+    Some(if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+        "text/html"
+    } else if lower.starts_with("<?xml") {
+        "application/xml"
+    } else {
+        "text/plain"
+    })
+}
+
+fn from_extension(filename: &str) -> Option<&'static str> {
+    let name = filename.rsplit(['/', '\\']).next().unwrap_or(filename);
+    let (_, extension) = name.rsplit_once('.').filter(|(stem, _)| !stem.is_empty())?;
+    let extension = extension.to_ascii_lowercase();
+    EXTENSIONS.iter().find(|(known, _)| *known == extension).map(|(_, mime)| *mime)
+}
+
+fn detect(inputs: &HashMap<String, Value>) -> Result<(&'static str, &'static str), String> {
+    let filename = match inputs.get("filename") {
+        None | Some(Value::Null) => None,
+        Some(filename) => Some(filename.as_str().ok_or("filename must be a string")?),
+    };
+    let data = match inputs.get("bytes") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(bytes::from_value(value)?),
+    };
+    if filename.is_none() && data.is_none() {
+        return Err("filename or bytes is required".to_string());
+    }
+
+    let content = data.as_deref().and_then(from_content);
+    let extension = filename.and_then(from_extension);
+    Ok(match (content, extension) {
+        (Some(content), Some(extension)) if GENERIC.contains(&content) => (extension, "extension"),
+        (Some(content), _) => (content, "content"),
+        (None, Some(extension)) => (extension, "extension"),
+        (None, None) => (OCTET_STREAM, "default"),
+    })
+}
+
+impl NodeExecutor for FileDetectMime {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match detect(&inputs) {
+            Ok((mime, source)) => {
+                output.insert("result".to_string(), serde_json::json!(mime));
+                output.insert("source".to_string(), serde_json::json!(source));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new FileDetectMime instance.
+pub fn create() -> FileDetectMime {
+    FileDetectMime::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(filename: Option<&str>, data: Option<&[u8]>) -> (Value, Value) {
+        let mut inputs = HashMap::new();
+        if let Some(filename) = filename {
+            inputs.insert("filename".to_string(), serde_json::json!(filename));
+        }
+        if let Some(data) = data {
+            inputs.insert("bytes".to_string(), bytes::to_value(data));
+        }
+        let output = FileDetectMime::new().execute(inputs, None);
+        (output["result"].clone(), output.get("source").cloned().unwrap_or(Value::Null))
+    }
+
+    #[test]
+    fn test_magic_bytes() {
+        assert_eq!(run(None, Some(b"\x89PNG\r\n\x1a\n\x00\x00")).0, "image/png");
+        assert_eq!(run(None, Some(b"RIFF\x00\x00\x00\x00WEBPVP8 ")).0, "image/webp");
+        assert_eq!(run(None, Some(b"\x00\x00\x00\x18ftypheic")).0, "image/heic");
+        assert_eq!(run(None, Some(b"  <!DOCTYPE html><html>")).0, "text/html");
+        // Content wins over a misleading extension.
+        assert_eq!(
+            run(Some("photo.png"), Some(b"%PDF-1.7\n")),
+            (serde_json::json!("application/pdf"), serde_json::json!("content"))
+        );
+    }
+
+    #[test]
+    fn test_extension_refines_generic_content() {
+        assert_eq!(
+            run(Some("Report.DOCX"), Some(b"PK\x03\x04\x14\x00")).0,
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        );
+        assert_eq!(run(Some("data.csv"), Some(b"a,b\n1,2\n")).0, "text/csv");
+        assert_eq!(run(Some("icon.svg"), Some(b"<?xml version=\"1.0\"?><svg/>")).0, "image/svg+xml");
+        assert_eq!(run(None, Some(b"a,b\n1,2\n")).0, "text/plain");
+    }
+
+    #[test]
+    fn test_extension_and_default() {
+        assert_eq!(
+            run(Some("C:\\uploads\\clip.mov"), None),
+            (serde_json::json!("video/quicktime"), serde_json::json!("extension"))
+        );
+        assert_eq!(run(Some(".bashrc"), None).0, "application/octet-stream");
+        assert_eq!(
+            run(Some("blob"), Some(b"\x00\x01\x02")),
+            (serde_json::json!("application/octet-stream"), serde_json::json!("default"))
+        );
+        let output = FileDetectMime::new().execute(HashMap::new(), None);
+        assert_eq!(output["error"], "filename or bytes is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.detect_mime");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for FileExists plugin.
+
+use super::FileExists;
+
+/// Creates a new FileExists instance.
+pub fn create() -> FileExists {
+    FileExists::new()
+}
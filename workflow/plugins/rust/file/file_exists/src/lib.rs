@@ -0,0 +1,87 @@
+//! Workflow plugin: check whether a path exists.
+//!
+//! Outputs `result`, whether anything exists at `path` (following symbolic
+//! links), and `kind`, `"file"`, `"dir"`, or `null` if nothing does.
+
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileExists implements the NodeExecutor trait for existence checks.
+pub struct FileExists {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileExists {
+    /// Creates a new FileExists instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.exists",
+            category: "file",
+            description: "Check whether a path exists",
+        }
+    }
+}
+
+impl Default for FileExists {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for FileExists {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        let Some(path) = inputs.get("path").and_then(Value::as_str) else {
+            output.insert("result".to_string(), Value::Null);
+            output.insert("error".to_string(), json!("path is required"));
+            return output;
+        };
+        let kind = fs::metadata(path).ok().map(|m| if m.is_dir() { "dir" } else { "file" });
+        output.insert("result".to_string(), json!(kind.is_some()));
+        output.insert("kind".to_string(), json!(kind));
+        output
+    }
+}
+
+/// Creates a new FileExists instance.
+pub fn create() -> FileExists {
+    FileExists::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(path: &str) -> HashMap<String, Value> {
+        FileExists::new().execute(HashMap::from([("path".to_string(), json!(path))]), None)
+    }
+
+    #[test]
+    fn test_exists() {
+        let dir = std::env::temp_dir();
+        let output = run(dir.to_str().unwrap());
+        assert_eq!(output["result"], json!(true));
+        assert_eq!(output["kind"], json!("dir"));
+        let output = run("/no/such/file");
+        assert_eq!(output["result"], json!(false));
+        assert_eq!(output["kind"], Value::Null);
+        assert_eq!(FileExists::new().execute(HashMap::new(), None)["error"], "path is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.exists");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for FileList plugin.
+
+use super::FileList;
+
+/// Creates a new FileList instance.
+pub fn create() -> FileList {
+    FileList::new()
+}
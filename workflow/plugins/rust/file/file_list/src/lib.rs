@@ -0,0 +1,224 @@
+//! Workflow plugin: list the entries of a directory.
+//!
+//! Lists the directory at `path`, descending `depth` levels (1, the default,
+//! lists only its own entries; a `pattern` with a `**` component lifts the
+//! limit unless `depth` is given). `pattern` is a glob (see
+//! `metabuilder_core::paths::glob_match`) matched against each entry's path
+//! relative to `path`, `kind` keeps only `"file"` or `"dir"` entries, and
+//! `extensions` only files with one of the given extensions (a string or a
+//! list, without the dot, matched case-insensitively). Symbolic links are
+//! listed as what they point to but not descended into.
+//!
+//! Outputs `result`, the entries sorted by relative path, each described as
+//! by `metabuilder_core::files::describe` plus its `relative` path; and
+//! `count`, the number of entries.
+
+use metabuilder_core::{files, paths};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileList implements the NodeExecutor trait for directory listings.
+pub struct FileList {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileList {
+    /// Creates a new FileList instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.list",
+            category: "file",
+            description: "List the entries of a directory",
+        }
+    }
+}
+
+impl Default for FileList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which entries to keep.
+struct Filter {
+    pattern: Option<String>,
+    kind: Option<String>,
+    extensions: Vec<String>,
+}
+
+impl Filter {
+    fn keeps(&self, relative: &str, name: &str, is_dir: bool) -> bool {
+        let kind = if is_dir { "dir" } else { "file" };
+        if self.kind.as_deref().is_some_and(|wanted| wanted != kind) {
+            return false;
+        }
+        if !self.extensions.is_empty() {
+            let (_, extension) = paths::split_extension(name);
+            let extension = extension.trim_start_matches('.').to_lowercase();
+            if is_dir || !self.extensions.contains(&extension) {
+                return false;
+            }
+        }
+        self.pattern.as_deref().is_none_or(|pattern| paths::glob_match(pattern, relative))
+    }
+}
+
+fn walk(dir: &Path, prefix: &str, depth: usize, filter: &Filter, entries: &mut Vec<Value>) -> Result<(), String> {
+    let listing = fs::read_dir(dir).map_err(|e| format!("cannot list {}: {}", dir.display(), e))?;
+    for entry in listing {
+        let entry = entry.map_err(|e| format!("cannot list {}: {}", dir.display(), e))?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let relative = if prefix.is_empty() { name.clone() } else { format!("{}/{}", prefix, name) };
+        let path = entry.path();
+        // A broken link has no target to describe.
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if filter.keeps(&relative, &name, metadata.is_dir()) {
+            let mut entry = files::describe(&path, &metadata);
+            entry.insert("relative".to_string(), json!(relative));
+            entries.push(Value::Object(entry));
+        }
+        let is_link = entry.file_type().map(|t| t.is_symlink()).unwrap_or(false);
+        if metadata.is_dir() && !is_link && depth > 1 {
+            walk(&path, &relative, depth - 1, filter, entries)?;
+        }
+    }
+    Ok(())
+}
+
+fn list(inputs: &HashMap<String, Value>) -> Result<Vec<Value>, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let pattern = match inputs.get("pattern").filter(|v| !v.is_null()) {
+        Some(pattern) => Some(pattern.as_str().ok_or("pattern must be a string")?.to_string()),
+        None => None,
+    };
+    let depth = match inputs.get("depth").filter(|v| !v.is_null()) {
+        Some(depth) => depth.as_u64().filter(|d| *d > 0).ok_or("depth must be a positive integer")? as usize,
+        None if pattern.as_deref().is_some_and(|p| paths::parts(p).components.iter().any(|c| c == "**")) => usize::MAX,
+        None => 1,
+    };
+    let kind = match inputs.get("kind").and_then(Value::as_str) {
+        None | Some("any") => None,
+        Some(kind @ ("file" | "dir")) => Some(kind.to_string()),
+        Some(other) => return Err(format!("kind must be file, dir, or any, not {}", other)),
+    };
+    let extensions = match inputs.get("extensions") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::String(extension)) => vec![extension.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(String::from).ok_or("extensions must be strings"))
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err("extensions must be a string or a list of strings".to_string()),
+    };
+    let extensions = extensions.iter().map(|e| e.trim_start_matches('.').to_lowercase()).collect();
+
+    let filter = Filter { pattern, kind, extensions };
+    let mut entries = Vec::new();
+    walk(Path::new(path), "", depth, &filter, &mut entries)?;
+    entries.sort_by(|a, b| a["relative"].as_str().cmp(&b["relative"].as_str()));
+    Ok(entries)
+}
+
+impl NodeExecutor for FileList {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match list(&inputs) {
+            Ok(entries) => {
+                output.insert("count".to_string(), json!(entries.len()));
+                output.insert("result".to_string(), Value::Array(entries));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileList instance.
+pub fn create() -> FileList {
+    FileList::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A tree of `a.txt`, `b.CSV`, `sub/c.txt`, and `sub/deep/d.txt`.
+    fn tree(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("metabuilder-file-list-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub/deep")).unwrap();
+        fs::write(dir.join("a.txt"), "aaa").unwrap();
+        fs::write(dir.join("b.CSV"), "b").unwrap();
+        fs::write(dir.join("sub/c.txt"), "c").unwrap();
+        fs::write(dir.join("sub/deep/d.txt"), "d").unwrap();
+        dir
+    }
+
+    fn relative(dir: &Path, inputs: Value) -> Vec<String> {
+        let mut inputs: HashMap<String, Value> = serde_json::from_value(inputs).unwrap();
+        inputs.insert("path".to_string(), json!(dir));
+        let output = FileList::new().execute(inputs, None);
+        let entries = output["result"].as_array().unwrap_or_else(|| panic!("{:?}", output.get("error")));
+        entries.iter().map(|e| e["relative"].as_str().unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn test_list() {
+        let dir = tree("list");
+        let output = FileList::new().execute(HashMap::from([("path".to_string(), json!(dir))]), None);
+        assert_eq!(output["count"], json!(3));
+        let first = &output["result"][0];
+        assert_eq!(first["name"], "a.txt");
+        assert_eq!(first["kind"], "file");
+        assert_eq!(first["size"], json!(3));
+        assert!(first["path"].as_str().unwrap().ends_with("/a.txt"));
+        assert!(first["modified"].as_str().unwrap().ends_with('Z'));
+        assert_eq!(output["result"][2]["kind"], "dir");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_depth_and_filters() {
+        let dir = tree("filters");
+        assert_eq!(relative(&dir, json!({"depth": 2, "kind": "file"})), ["a.txt", "b.CSV", "sub/c.txt"]);
+        assert_eq!(relative(&dir, json!({"depth": 3, "kind": "dir"})), ["sub", "sub/deep"]);
+        assert_eq!(relative(&dir, json!({"extensions": ["csv"]})), ["b.CSV"]);
+        assert_eq!(relative(&dir, json!({"pattern": "*.txt"})), ["a.txt"]);
+        assert_eq!(relative(&dir, json!({"pattern": "**/*.txt"})), ["a.txt", "sub/c.txt", "sub/deep/d.txt"]);
+        assert_eq!(relative(&dir, json!({"pattern": "**/*.txt", "depth": 2})), ["a.txt", "sub/c.txt"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let run = |inputs: Value| FileList::new().execute(serde_json::from_value(inputs).unwrap(), None);
+        assert!(run(json!({"path": "/no/such/dir"}))["error"].as_str().unwrap().starts_with("cannot list /no/such/dir"));
+        assert_eq!(run(json!({"path": ".", "depth": 0}))["error"], "depth must be a positive integer");
+        assert_eq!(run(json!({"path": ".", "kind": "pipe"}))["error"], "kind must be file, dir, or any, not pipe");
+        assert_eq!(run(json!({}))["error"], "path is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.list");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for FileMove plugin.
+
+use super::FileMove;
+
+/// Creates a new FileMove instance.
+pub fn create() -> FileMove {
+    FileMove::new()
+}
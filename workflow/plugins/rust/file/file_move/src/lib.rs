@@ -0,0 +1,138 @@
+//! Workflow plugin: move or rename a file or directory.
+//!
+//! Moves `from` to `to`. An existing `to` is an error unless `overwrite` is
+//! set, and `create_dirs` creates the missing parent directories of `to`.
+//! Files moved across filesystems, which cannot be renamed, are copied and
+//! then removed. Outputs `result`, the path moved to.
+
+use metabuilder_core::files;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileMove implements the NodeExecutor trait for moving files.
+pub struct FileMove {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileMove {
+    /// Creates a new FileMove instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.move",
+            category: "file",
+            description: "Move or rename a file or directory",
+        }
+    }
+}
+
+impl Default for FileMove {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn move_path(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let from = inputs.get("from").and_then(Value::as_str).ok_or("from is required")?;
+    let to = inputs.get("to").and_then(Value::as_str).ok_or("to is required")?;
+    let overwrite = inputs.get("overwrite").and_then(Value::as_bool).unwrap_or(false);
+    let create_dirs = inputs.get("create_dirs").and_then(Value::as_bool).unwrap_or(false);
+    let metadata = fs::symlink_metadata(from).map_err(|e| format!("cannot read {}: {}", from, e))?;
+    if !overwrite && Path::new(to).exists() {
+        return Err(format!("{} already exists", to));
+    }
+    if create_dirs {
+        files::create_parent_dirs(Path::new(to)).map_err(|e| format!("cannot create directories for {}: {}", to, e))?;
+    }
+    let moved = fs::rename(from, to).or_else(|e| match metadata.is_file() {
+        // Renames fail across filesystems; files can be copied instead.
+        true => fs::copy(from, to).and_then(|_| fs::remove_file(from)),
+        false => Err(e),
+    });
+    moved.map_err(|e| format!("cannot move {} to {}: {}", from, to, e))?;
+    Ok(to.to_string())
+}
+
+impl NodeExecutor for FileMove {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match move_path(&inputs) {
+            Ok(to) => {
+                output.insert("result".to_string(), json!(to));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileMove instance.
+pub fn create() -> FileMove {
+    FileMove::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::scratch;
+
+    fn run(from: &Path, to: &Path, extra: Value) -> HashMap<String, Value> {
+        let mut inputs = HashMap::from([("from".to_string(), json!(from)), ("to".to_string(), json!(to))]);
+        inputs.extend(serde_json::from_value::<HashMap<String, Value>>(extra).unwrap());
+        FileMove::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_move() {
+        let dir = scratch("move");
+        let from = dir.join("a.txt");
+        fs::write(&from, "hello").unwrap();
+        let to = dir.join("sub/b.txt");
+        let output = run(&from, &to, json!({"create_dirs": true}));
+        assert_eq!(output["result"], json!(to));
+        assert_eq!(fs::read_to_string(&to).unwrap(), "hello");
+        assert!(!from.exists());
+        // Directories move too.
+        let output = run(&dir.join("sub"), &dir.join("renamed"), json!({}));
+        assert!(!output.contains_key("error"));
+        assert!(dir.join("renamed/b.txt").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let dir = scratch("overwrite");
+        let from = dir.join("a.txt");
+        let to = dir.join("b.txt");
+        fs::write(&from, "new").unwrap();
+        fs::write(&to, "old").unwrap();
+        let output = run(&from, &to, json!({}));
+        assert!(output["error"].as_str().unwrap().ends_with("already exists"));
+        assert!(from.exists());
+        run(&from, &to, json!({"overwrite": true}));
+        assert_eq!(fs::read_to_string(&to).unwrap(), "new");
+        let output = run(&from, &to, json!({}));
+        assert!(output["error"].as_str().unwrap().starts_with("cannot read"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.move");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for FileReadLines plugin.
+
+use super::FileReadLines;
+
+/// Creates a new FileReadLines instance.
+pub fn create() -> FileReadLines {
+    FileReadLines::new()
+}
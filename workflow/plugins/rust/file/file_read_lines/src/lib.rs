@@ -0,0 +1,113 @@
+//! Workflow plugin: stream the lines of a text file.
+//!
+//! Opens the file at `path` and outputs `result`, a stream handle (see
+//! `metabuilder_core::stream`) yielding one string per line, without the line
+//! ending. Lines are read as the stream is pulled, so a streaming edge from
+//! `result` processes files of any size in constant memory. Only opening the
+//! file fails the node; a read error later fails the stream.
+
+use metabuilder_core::{Runtime, Stream};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileReadLines implements the NodeExecutor trait for streaming file lines.
+pub struct FileReadLines {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileReadLines {
+    /// Creates a new FileReadLines instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.read_lines",
+            category: "file",
+            description: "Stream the lines of a text file",
+        }
+    }
+}
+
+impl Default for FileReadLines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_lines(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Value, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let rt = Runtime::from_any(runtime).ok_or("runtime is required for stream handles")?;
+    let file = File::open(path).map_err(|e| format!("cannot open {}: {}", path, e))?;
+    let path = path.to_string();
+    let lines = BufReader::new(file).lines().map(move |line| match line {
+        Ok(line) => Ok(Value::from(line.strip_suffix('\r').unwrap_or(&line))),
+        Err(e) => Err(format!("cannot read {}: {}", path, e)),
+    });
+    Ok(Stream::new(lines).into_handle(&rt.handles))
+}
+
+impl NodeExecutor for FileReadLines {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match read_lines(&inputs, runtime) {
+            Ok(handle) => {
+                output.insert("result".to_string(), handle);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileReadLines instance.
+pub fn create() -> FileReadLines {
+    FileReadLines::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lines() {
+        let path = std::env::temp_dir().join(format!("file_read_lines_{}.txt", std::process::id()));
+        std::fs::write(&path, "first\r\nsecond\n\nlast").unwrap();
+        let runtime = Runtime::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("path".to_string(), serde_json::json!(path.to_str().unwrap()));
+
+        let result = FileReadLines::new().execute(inputs, Some(&runtime));
+        let stream = Stream::from_handle(&runtime.handles, &result["result"]).unwrap();
+        let lines: Vec<Value> = std::iter::from_fn(|| stream.next_chunk()).map(Result::unwrap).collect();
+        assert_eq!(lines, vec![serde_json::json!("first"), serde_json::json!("second"), serde_json::json!(""), serde_json::json!("last")]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_lines_missing_file() {
+        let mut inputs = HashMap::new();
+        inputs.insert("path".to_string(), serde_json::json!("/nonexistent/file.txt"));
+
+        let result = FileReadLines::new().execute(inputs, Some(&Runtime::new()));
+        assert!(result["error"].as_str().unwrap().starts_with("cannot open /nonexistent/file.txt"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.read_lines");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for FileStat plugin.
+
+use super::FileStat;
+
+/// Creates a new FileStat instance.
+pub fn create() -> FileStat {
+    FileStat::new()
+}
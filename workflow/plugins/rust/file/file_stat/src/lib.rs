@@ -0,0 +1,108 @@
+//! Workflow plugin: describe a file or directory.
+//!
+//! Outputs `result`, the file at `path` described as by
+//! `metabuilder_core::files::describe` (`path`, `name`, `kind`, `size`, and
+//! `modified`) plus `readonly`. Following symbolic links, the target is
+//! described; a missing path outputs `error`.
+
+use metabuilder_core::files;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileStat implements the NodeExecutor trait for file metadata.
+pub struct FileStat {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileStat {
+    /// Creates a new FileStat instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.stat",
+            category: "file",
+            description: "Describe a file or directory",
+        }
+    }
+}
+
+impl Default for FileStat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn stat(inputs: &HashMap<String, Value>) -> Result<Value, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let metadata = fs::metadata(path).map_err(|e| format!("cannot stat {}: {}", path, e))?;
+    let mut entry = files::describe(Path::new(path), &metadata);
+    entry.insert("readonly".to_string(), json!(metadata.permissions().readonly()));
+    Ok(Value::Object(entry))
+}
+
+impl NodeExecutor for FileStat {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match stat(&inputs) {
+            Ok(entry) => {
+                output.insert("result".to_string(), entry);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileStat instance.
+pub fn create() -> FileStat {
+    FileStat::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(path: &Path) -> HashMap<String, Value> {
+        FileStat::new().execute(HashMap::from([("path".to_string(), json!(path))]), None)
+    }
+
+    #[test]
+    fn test_stat() {
+        let path = std::env::temp_dir().join(format!("metabuilder-file-stat-{}.txt", std::process::id()));
+        fs::write(&path, "hello").unwrap();
+        let output = run(&path);
+        fs::remove_file(&path).unwrap();
+        let entry = &output["result"];
+        assert_eq!(entry["kind"], "file");
+        assert_eq!(entry["size"], 5);
+        assert_eq!(entry["readonly"], false);
+        assert!(entry["name"].as_str().unwrap().starts_with("metabuilder-file-stat-"));
+    }
+
+    #[test]
+    fn test_missing() {
+        let output = run(Path::new("/no/such/file"));
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].as_str().unwrap().starts_with("cannot stat /no/such/file"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.stat");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for FileWrite plugin.
+
+use super::FileWrite;
+
+/// Creates a new FileWrite instance.
+pub fn create() -> FileWrite {
+    FileWrite::new()
+}
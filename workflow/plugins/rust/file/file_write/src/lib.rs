@@ -0,0 +1,140 @@
+//! Workflow plugin: write content to a file.
+//!
+//! Writes `content`, a string or a bytes value (see
+//! `metabuilder_core::bytes`), to the file at `path`, replacing it if it
+//! exists. With `atomic`, the content goes to a temporary file beside it
+//! that is then renamed over it, so readers never see a partial file. With
+//! `create_dirs`, missing parent directories are created first.
+//!
+//! Outputs `result`, the number of bytes written.
+
+use metabuilder_core::{bytes, files};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// FileWrite implements the NodeExecutor trait for writing files.
+pub struct FileWrite {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl FileWrite {
+    /// Creates a new FileWrite instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "file.write",
+            category: "file",
+            description: "Write content to a file",
+        }
+    }
+}
+
+impl Default for FileWrite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn write(inputs: &HashMap<String, Value>) -> Result<usize, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let content = inputs.get("content").ok_or("content is required")?;
+    let data = bytes::from_value(content).map_err(|_| "content must be a string or bytes".to_string())?;
+    let atomic = inputs.get("atomic").and_then(Value::as_bool).unwrap_or(false);
+    let create_dirs = inputs.get("create_dirs").and_then(Value::as_bool).unwrap_or(false);
+
+    let file = Path::new(path);
+    if create_dirs {
+        files::create_parent_dirs(file).map_err(|e| format!("cannot create directories for {}: {}", path, e))?;
+    }
+    let written = match atomic {
+        true => files::write_atomic(file, None, &data),
+        false => files::write(file, &data, false),
+    };
+    written.map_err(|e| format!("cannot write {}: {}", path, e))?;
+    Ok(data.len())
+}
+
+impl NodeExecutor for FileWrite {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match write(&inputs) {
+            Ok(written) => {
+                output.insert("result".to_string(), serde_json::json!(written));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new FileWrite instance.
+pub fn create() -> FileWrite {
+    FileWrite::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::{inputs, scratch};
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_write() {
+        let executor = FileWrite::new();
+        let dir = scratch("plain");
+        let path = dir.join("nested/out.txt");
+        let output = executor.execute(inputs(json!({"path": path, "content": "héllo", "create_dirs": true})), None);
+        assert_eq!(output["result"], json!(6));
+        let output = executor.execute(inputs(json!({"path": path, "content": "bye"})), None);
+        assert_eq!(output["result"], json!(3));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "bye");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_atomic_bytes() {
+        let executor = FileWrite::new();
+        let dir = scratch("atomic");
+        let path = dir.join("out.bin");
+        let content = bytes::to_value(&[0, 159, 255]);
+        let params = json!({"path": path, "content": content, "atomic": true, "create_dirs": true});
+        let output = executor.execute(inputs(params), None);
+        assert_eq!(output["result"], json!(3));
+        assert_eq!(fs::read(&path).unwrap(), [0, 159, 255]);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = FileWrite::new();
+        let dir = scratch("errors");
+        let path = dir.join("missing/out.txt");
+        let output = executor.execute(inputs(json!({"path": path, "content": "x"})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].as_str().unwrap().starts_with("cannot write"));
+        let output = executor.execute(inputs(json!({"path": path, "content": 5})), None);
+        assert_eq!(output["error"], "content must be a string or bytes");
+        assert_eq!(executor.execute(inputs(json!({"content": "x"})), None)["error"], "path is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "file.write");
+        assert_eq!(executor.category, "file");
+    }
+}
@@ -0,0 +1,229 @@
+//! Dynamic gRPC client: JSON in, JSON out, with descriptors from a
+//! descriptor set or server reflection.
+
+use base64::Engine as _;
+use prost::Message;
+use prost_reflect::prost_types::FileDescriptorProto;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, SerializeOptions};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{MetadataKey, MetadataValue};
+use tonic::transport::{Channel, Endpoint};
+use tonic::Status;
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+
+/// A failed call: the gRPC status code name (if the server answered) and a message.
+pub struct CallError {
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl From<String> for CallError {
+    fn from(message: String) -> Self {
+        CallError { code: None, message }
+    }
+}
+
+impl From<&str> for CallError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<Status> for CallError {
+    fn from(status: Status) -> Self {
+        CallError {
+            code: Some(format!("{:?}", status.code())),
+            message: status.message().to_string(),
+        }
+    }
+}
+
+/// Everything needed to make one call.
+pub struct CallOptions {
+    pub url: String,
+    /// `package.Service/Method`
+    pub method: String,
+    pub request: Value,
+    /// Encoded `FileDescriptorSet`; server reflection is used when absent
+    pub descriptor_set: Option<Vec<u8>>,
+    pub metadata: HashMap<String, String>,
+    pub timeout: Duration,
+    pub emit_defaults: bool,
+}
+
+/// Reads a descriptor set from base64 text or a file path.
+pub fn load_descriptor_set(base64_text: Option<&str>, path: Option<&str>) -> Result<Option<Vec<u8>>, String> {
+    match (base64_text, path) {
+        (Some(text), _) => base64::engine::general_purpose::STANDARD
+            .decode(text.trim())
+            .map(Some)
+            .map_err(|e| format!("descriptor_set is not valid base64: {}", e)),
+        (None, Some(path)) => std::fs::read(path)
+            .map(Some)
+            .map_err(|e| format!("cannot read {}: {}", path, e)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Runs the call on a private single-threaded tokio runtime.
+pub fn call(options: CallOptions) -> Result<Value, CallError> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+    runtime.block_on(call_async(options))
+}
+
+async fn call_async(options: CallOptions) -> Result<Value, CallError> {
+    let (service, method) = options
+        .method
+        .trim_start_matches('/')
+        .rsplit_once('/')
+        .ok_or_else(|| format!("method must look like package.Service/Method: {}", options.method))?;
+
+    let channel = Endpoint::from_shared(options.url.clone())
+        .map_err(|e| format!("invalid url {}: {}", options.url, e))?
+        .connect_timeout(options.timeout)
+        .timeout(options.timeout)
+        .connect()
+        .await
+        .map_err(|e| format!("cannot connect to {}: {}", options.url, e))?;
+
+    let pool = match &options.descriptor_set {
+        Some(bytes) => DescriptorPool::decode(bytes.as_slice()).map_err(|e| format!("invalid descriptor set: {}", e))?,
+        None => reflect(channel.clone(), service).await?,
+    };
+    let service_desc = pool
+        .get_service_by_name(service)
+        .ok_or_else(|| format!("unknown service: {}", service))?;
+    let method_desc = service_desc
+        .methods()
+        .find(|m| m.name() == method)
+        .ok_or_else(|| format!("service {} has no method {}", service, method))?;
+    if method_desc.is_client_streaming() || method_desc.is_server_streaming() {
+        return Err(format!("{} is a streaming method; only unary calls are supported", options.method).into());
+    }
+
+    let message = DynamicMessage::deserialize(method_desc.input(), options.request)
+        .map_err(|e| format!("request does not match {}: {}", method_desc.input().full_name(), e))?;
+    let mut request = tonic::Request::new(message);
+    for (key, value) in &options.metadata {
+        let key = MetadataKey::from_bytes(key.to_lowercase().as_bytes()).map_err(|_| format!("invalid metadata key: {}", key))?;
+        let value = MetadataValue::try_from(value.as_str()).map_err(|_| format!("invalid metadata value for {}", key))?;
+        request.metadata_mut().insert(key, value);
+    }
+
+    let path = PathAndQuery::try_from(format!("/{}/{}", service, method)).map_err(|e| e.to_string())?;
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready().await.map_err(|e| format!("channel not ready: {}", e))?;
+    let response = grpc
+        .unary(request, path, DynamicCodec(method_desc.output()))
+        .await?
+        .into_inner();
+
+    let options = SerializeOptions::new().skip_default_fields(!options.emit_defaults);
+    response
+        .serialize_with_options(serde_json::value::Serializer, &options)
+        .map_err(|e| CallError::from(e.to_string()))
+}
+
+/// Fetches the file defining `symbol`, and all its imports, through server reflection.
+async fn reflect(channel: Channel, symbol: &str) -> Result<DescriptorPool, CallError> {
+    let mut client = ServerReflectionClient::new(channel);
+    let mut files: HashMap<String, FileDescriptorProto> = HashMap::new();
+    let mut requested: HashSet<String> = HashSet::new();
+    let mut pending = vec![MessageRequest::FileContainingSymbol(symbol.to_string())];
+
+    while let Some(message_request) = pending.pop() {
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(message_request),
+        };
+        let mut stream = client
+            .server_reflection_info(tokio_stream::once(request))
+            .await
+            .map_err(|s| format!("server reflection failed: {}", s.message()))?
+            .into_inner();
+        let response = stream
+            .message()
+            .await
+            .map_err(|s| format!("server reflection failed: {}", s.message()))?
+            .ok_or("server reflection returned no response")?;
+
+        match response.message_response {
+            Some(MessageResponse::FileDescriptorResponse(found)) => {
+                for bytes in found.file_descriptor_proto {
+                    let file = FileDescriptorProto::decode(bytes.as_slice()).map_err(|e| e.to_string())?;
+                    files.insert(file.name().to_string(), file);
+                }
+            }
+            Some(MessageResponse::ErrorResponse(e)) => {
+                return Err(format!("server reflection: {}", e.error_message).into());
+            }
+            _ => return Err("unexpected server reflection response".into()),
+        }
+
+        // Servers may omit imports they assume the client already has.
+        for file in files.values() {
+            for dependency in &file.dependency {
+                if !files.contains_key(dependency) && requested.insert(dependency.clone()) {
+                    pending.push(MessageRequest::FileByFilename(dependency.clone()));
+                }
+            }
+        }
+    }
+
+    let mut pool = DescriptorPool::new();
+    pool.add_file_descriptor_protos(files.into_values())
+        .map_err(|e| format!("invalid descriptors from server reflection: {}", e))?;
+    Ok(pool)
+}
+
+/// Encodes requests and decodes responses as `DynamicMessage`s.
+struct DynamicCodec(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.0.clone())
+    }
+}
+
+struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: DynamicMessage, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        item.encode(dst).map_err(|e| Status::internal(e.to_string()))
+    }
+}
+
+struct DynamicDecoder(MessageDescriptor);
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<DynamicMessage>, Status> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+}
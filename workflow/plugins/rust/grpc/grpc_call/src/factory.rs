@@ -0,0 +1,8 @@
+//! Factory for GrpcCall plugin.
+
+use super::GrpcCall;
+
+/// Creates a new GrpcCall instance.
+pub fn create() -> GrpcCall {
+    GrpcCall::new()
+}
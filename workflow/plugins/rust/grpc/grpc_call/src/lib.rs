@@ -0,0 +1,191 @@
+//! Workflow plugin: call a unary gRPC method.
+//!
+//! The request is given as JSON and encoded with descriptors from
+//! `descriptor_set` (base64 `FileDescriptorSet`), `descriptor_set_path`, or,
+//! when neither is set, the server's reflection service. Requires the `grpc`
+//! feature (on by default).
+
+#[cfg(feature = "grpc")]
+mod client;
+
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// GrpcCall implements the NodeExecutor trait for unary gRPC calls.
+pub struct GrpcCall {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl GrpcCall {
+    /// Creates a new GrpcCall instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "grpc.call",
+            category: "grpc",
+            description: "Call a unary gRPC method with JSON request and response",
+        }
+    }
+}
+
+impl Default for GrpcCall {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "grpc")]
+fn call(inputs: &HashMap<String, Value>) -> Result<Value, client::CallError> {
+    fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+        inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    let url: String = input(inputs, "url").ok_or("url is required")?;
+    let method: String = input(inputs, "method").ok_or("method is required")?;
+    let descriptor_set = client::load_descriptor_set(
+        input::<String>(inputs, "descriptor_set").as_deref(),
+        input::<String>(inputs, "descriptor_set_path").as_deref(),
+    )?;
+    let timeout_ms: u64 = input(inputs, "timeout_ms").unwrap_or(30_000);
+
+    client::call(client::CallOptions {
+        url,
+        method,
+        request: inputs.get("request").cloned().unwrap_or_else(|| json!({})),
+        descriptor_set,
+        metadata: input(inputs, "metadata").unwrap_or_default(),
+        timeout: std::time::Duration::from_millis(timeout_ms),
+        emit_defaults: input(inputs, "emit_defaults").unwrap_or(false),
+    })
+}
+
+impl NodeExecutor for GrpcCall {
+    #[cfg(feature = "grpc")]
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match call(&inputs) {
+            Ok(response) => {
+                output.insert("success".to_string(), json!(true));
+                output.insert("code".to_string(), json!("Ok"));
+                output.insert("response".to_string(), response);
+            }
+            Err(e) => {
+                let error = match &e.code {
+                    Some(code) => format!("gRPC {}: {}", code, e.message),
+                    None => e.message,
+                };
+                output.insert("success".to_string(), json!(false));
+                output.insert("code".to_string(), json!(e.code));
+                output.insert("response".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(error));
+            }
+        }
+
+        output
+    }
+
+    #[cfg(not(feature = "grpc"))]
+    fn execute(&self, _inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        output.insert("success".to_string(), json!(false));
+        output.insert("error".to_string(), json!("grpc.call was built without the grpc feature"));
+        output
+    }
+}
+
+/// Creates a new GrpcCall instance.
+pub fn create() -> GrpcCall {
+    GrpcCall::new()
+}
+
+#[cfg(all(test, feature = "grpc"))]
+mod tests {
+    use super::*;
+    use base64::Engine as _;
+    use std::net::SocketAddr;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Starts a server with the standard health service and reflection.
+    fn health_server() -> SocketAddr {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let (_reporter, health) = tonic_health::server::health_reporter();
+                let reflection = tonic_reflection::server::Builder::configure()
+                    .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+                    .build_v1()
+                    .unwrap();
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+                tx.send(listener.local_addr().unwrap()).unwrap();
+                tonic::transport::Server::builder()
+                    .add_service(health)
+                    .add_service(reflection)
+                    .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                    .await
+                    .unwrap();
+            });
+        });
+        rx.recv().unwrap()
+    }
+
+    fn inputs(addr: SocketAddr, service: &str) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(format!("http://{}", addr)));
+        inputs.insert("method".to_string(), json!("grpc.health.v1.Health/Check"));
+        inputs.insert("request".to_string(), json!({ "service": service }));
+        inputs
+    }
+
+    #[test]
+    fn test_call_with_reflection() {
+        let addr = health_server();
+        let result = GrpcCall::new().execute(inputs(addr, ""), None);
+        assert_eq!(result.get("success"), Some(&json!(true)), "{:?}", result.get("error"));
+        assert_eq!(result.get("response"), Some(&json!({"status": "SERVING"})));
+    }
+
+    #[test]
+    fn test_call_with_descriptor_set_and_status_error() {
+        let addr = health_server();
+        let mut inputs = inputs(addr, "no.such.Service");
+        inputs.insert(
+            "descriptor_set".to_string(),
+            json!(base64::engine::general_purpose::STANDARD.encode(tonic_health::pb::FILE_DESCRIPTOR_SET)),
+        );
+
+        let result = GrpcCall::new().execute(inputs, None);
+        assert_eq!(result.get("code"), Some(&json!("NotFound")));
+        assert!(result.get("error").unwrap().as_str().unwrap().starts_with("gRPC NotFound"));
+    }
+
+    #[test]
+    fn test_unknown_method() {
+        let addr = health_server();
+        let mut inputs = inputs(addr, "");
+        inputs.insert("method".to_string(), json!("grpc.health.v1.Health/Nope"));
+
+        let result = GrpcCall::new().execute(inputs, None);
+        assert_eq!(
+            result.get("error"),
+            Some(&json!("service grpc.health.v1.Health has no method Nope"))
+        );
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "grpc.call");
+        assert_eq!(executor.category, "grpc");
+    }
+}
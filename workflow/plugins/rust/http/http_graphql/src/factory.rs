@@ -0,0 +1,8 @@
+//! Factory for HttpGraphql plugin.
+
+use super::HttpGraphql;
+
+/// Creates a new HttpGraphql instance.
+pub fn create() -> HttpGraphql {
+    HttpGraphql::new()
+}
@@ -0,0 +1,219 @@
+//! Workflow plugin: send a GraphQL request.
+//!
+//! Transport failures (connection errors, non-GraphQL HTTP errors, invalid
+//! JSON) are reported through `error` and stop the workflow. Errors returned
+//! by the GraphQL server are surfaced in `errors` alongside any partial
+//! `data`, so workflows can inspect them instead of failing outright.
+
+use metabuilder_core::Runtime;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// HttpGraphql implements the NodeExecutor trait for GraphQL requests.
+pub struct HttpGraphql {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl HttpGraphql {
+    /// Creates a new HttpGraphql instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "http.graphql",
+            category: "http",
+            description: "Send a GraphQL query or mutation",
+        }
+    }
+}
+
+impl Default for HttpGraphql {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+    inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Sends the request and returns `(http_status, response_body)`.
+fn send(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<(u16, Value), String> {
+    let url: String = input(inputs, "url").ok_or("url is required")?;
+    let query: String = input(inputs, "query").ok_or("query is required")?;
+    let variables: Option<serde_json::Map<String, Value>> = input(inputs, "variables");
+    let operation_name: Option<String> = input(inputs, "operation_name");
+    let mut headers: HashMap<String, String> = input(inputs, "headers").unwrap_or_default();
+    let timeout_ms: u64 = input(inputs, "timeout_ms").unwrap_or(30_000);
+
+    if let Some(name) = input::<String>(inputs, "token_secret") {
+        let token = Runtime::from_any(runtime)
+            .ok_or("runtime with secrets provider is required")?
+            .secret(&name)
+            .ok_or_else(|| format!("secret not found: {}", name))?;
+        headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+    }
+
+    let mut payload = json!({ "query": query });
+    if let Some(variables) = variables {
+        payload["variables"] = Value::Object(variables);
+    }
+    if let Some(operation_name) = operation_name {
+        payload["operationName"] = json!(operation_name);
+    }
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .build()
+        .into();
+    let mut request = agent.post(&url).header("Accept", "application/json");
+    for (name, value) in &headers {
+        request = request.header(name, value);
+    }
+    let mut response = request
+        .content_type("application/json")
+        .send(payload.to_string().as_str())
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+    match serde_json::from_str::<Value>(&body) {
+        // Servers commonly answer validation failures with 400 and a GraphQL `errors` body.
+        Ok(parsed) if parsed.get("data").is_some() || parsed.get("errors").is_some() => Ok((status, parsed)),
+        _ if status >= 400 => Err(format!("GraphQL endpoint returned {}: {}", status, body)),
+        Ok(_) => Err("response has neither data nor errors".to_string()),
+        Err(e) => Err(format!("invalid GraphQL response: {}", e)),
+    }
+}
+
+impl NodeExecutor for HttpGraphql {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match send(&inputs, runtime) {
+            Ok((status, body)) => {
+                let errors = body.get("errors").cloned().unwrap_or_else(|| json!([]));
+                let ok = errors.as_array().is_none_or(Vec::is_empty);
+                output.insert("success".to_string(), json!(ok));
+                output.insert("status".to_string(), json!(status));
+                output.insert("data".to_string(), body.get("data").cloned().unwrap_or(Value::Null));
+                output.insert("errors".to_string(), errors);
+                output.insert("extensions".to_string(), body.get("extensions").cloned().unwrap_or(Value::Null));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), json!(false));
+                output.insert("data".to_string(), Value::Null);
+                output.insert("errors".to_string(), json!([]));
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new HttpGraphql instance.
+pub fn create() -> HttpGraphql {
+    HttpGraphql::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers one request with `status` and `body`, reporting the request body.
+    fn fake_server(status: &'static str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(n) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = n.trim().parse().unwrap();
+                }
+                line.clear();
+            }
+            let mut request = vec![0; length];
+            reader.read_exact(&mut request).unwrap();
+            tx.send(String::from_utf8(request).unwrap()).unwrap();
+            let mut stream = stream;
+            write!(stream, "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body).unwrap();
+        });
+        (format!("http://{}/graphql", addr), rx)
+    }
+
+    #[test]
+    fn test_query() {
+        let (url, requests) = fake_server("200 OK", r#"{"data":{"user":{"name":"Ada"}}}"#);
+        let executor = HttpGraphql::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(url));
+        inputs.insert("query".to_string(), json!("query User($id: ID!) { user(id: $id) { name } }"));
+        inputs.insert("variables".to_string(), json!({"id": "1"}));
+        inputs.insert("operation_name".to_string(), json!("User"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&json!(true)));
+        assert_eq!(result.get("data"), Some(&json!({"user": {"name": "Ada"}})));
+        assert_eq!(result.get("errors"), Some(&json!([])));
+
+        let sent: Value = serde_json::from_str(&requests.recv().unwrap()).unwrap();
+        assert_eq!(sent["variables"], json!({"id": "1"}));
+        assert_eq!(sent["operationName"], json!("User"));
+    }
+
+    #[test]
+    fn test_graphql_errors_are_not_transport_errors() {
+        let (url, _requests) = fake_server(
+            "400 Bad Request",
+            r#"{"errors":[{"message":"Cannot query field \"nope\" on type \"Query\"."}]}"#,
+        );
+        let executor = HttpGraphql::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(url));
+        inputs.insert("query".to_string(), json!("{ nope }"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&json!(false)));
+        assert_eq!(result.get("status"), Some(&json!(400)));
+        assert_eq!(result.get("errors").unwrap()[0]["message"], json!("Cannot query field \"nope\" on type \"Query\"."));
+        assert!(!result.contains_key("error"));
+    }
+
+    #[test]
+    fn test_transport_error() {
+        let (url, _requests) = fake_server("502 Bad Gateway", "upstream down");
+        let executor = HttpGraphql::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(url));
+        inputs.insert("query".to_string(), json!("{ ping }"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&json!("GraphQL endpoint returned 502: upstream down")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "http.graphql");
+        assert_eq!(executor.category, "http");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for HttpPaginate plugin.
+
+use super::HttpPaginate;
+
+/// Creates a new HttpPaginate instance.
+pub fn create() -> HttpPaginate {
+    HttpPaginate::new()
+}
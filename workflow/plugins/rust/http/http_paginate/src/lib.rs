@@ -0,0 +1,351 @@
+//! Workflow plugin: follow REST API pagination and collect every item.
+//!
+//! Strategies:
+//! - `cursor`: read the next cursor from `cursor_path` in each response and
+//!   send it back as the `cursor_param` query parameter
+//! - `offset`: send `offset_param`/`limit_param`, stopping on a short page
+//! - `link`: follow the `rel="next"` URL of the `Link` header (GitHub style)
+
+use metabuilder_core::Runtime;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// HttpPaginate implements the NodeExecutor trait for paginated API reads.
+pub struct HttpPaginate {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl HttpPaginate {
+    /// Creates a new HttpPaginate instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "http.paginate",
+            category: "http",
+            description: "Fetch every page of a paginated REST endpoint",
+        }
+    }
+}
+
+impl Default for HttpPaginate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Items gathered across pages.
+struct Collected {
+    items: Vec<Value>,
+    pages: usize,
+    truncated: bool,
+}
+
+fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+    inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Looks up a dotted path such as `meta.next_cursor`; an empty path is the value itself.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.').try_fold(value, |v, key| match v {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => v.get(key),
+    })
+}
+
+/// Extracts the `rel="next"` target from a `Link` header.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        let is_next = params
+            .split(';')
+            .any(|p| p.trim().replace(' ', "").eq_ignore_ascii_case("rel=\"next\"") || p.trim() == "rel=next");
+        is_next.then(|| target.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+/// Resolves a root-relative link against the scheme and host of `base`.
+fn resolve(base: &str, link: &str) -> String {
+    if !link.starts_with('/') {
+        return link.to_string();
+    }
+    let host_end = base
+        .find("://")
+        .and_then(|i| base[i + 3..].find('/').map(|j| i + 3 + j))
+        .unwrap_or(base.len());
+    format!("{}{}", &base[..host_end], link)
+}
+
+fn collect(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Collected, String> {
+    let url: String = input(inputs, "url").ok_or("url is required")?;
+    let strategy: String = input(inputs, "strategy").unwrap_or_else(|| "link".to_string());
+    let items_path: String = input(inputs, "items_path").unwrap_or_default();
+    let query: HashMap<String, Value> = input(inputs, "query").unwrap_or_default();
+    let mut headers: HashMap<String, String> = input(inputs, "headers").unwrap_or_default();
+    let max_pages: usize = input(inputs, "max_pages").unwrap_or(100);
+    let timeout_ms: u64 = input(inputs, "timeout_ms").unwrap_or(30_000);
+
+    if let Some(name) = input::<String>(inputs, "token_secret") {
+        let token = Runtime::from_any(runtime)
+            .ok_or("runtime with secrets provider is required")?
+            .secret(&name)
+            .ok_or_else(|| format!("secret not found: {}", name))?;
+        headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+    }
+
+    let cursor_param: String = input(inputs, "cursor_param").unwrap_or_else(|| "cursor".to_string());
+    let cursor_path: Option<String> = input(inputs, "cursor_path");
+    let offset_param: String = input(inputs, "offset_param").unwrap_or_else(|| "offset".to_string());
+    let limit_param: String = input(inputs, "limit_param").unwrap_or_else(|| "limit".to_string());
+    let page_size: Option<u64> = input(inputs, "page_size");
+    match strategy.as_str() {
+        "cursor" if cursor_path.is_none() => return Err("cursor_path is required for cursor pagination".to_string()),
+        "offset" if page_size.is_none() => return Err("page_size is required for offset pagination".to_string()),
+        "cursor" | "offset" | "link" => {}
+        other => return Err(format!("unknown strategy: {} (expected cursor, offset, or link)", other)),
+    }
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .build()
+        .into();
+
+    let mut collected = Collected {
+        items: Vec::new(),
+        pages: 0,
+        truncated: false,
+    };
+    let mut next_url = url.clone();
+    let mut cursor: Option<String> = None;
+    let mut offset: u64 = input(inputs, "offset_start").unwrap_or(0);
+
+    loop {
+        if collected.pages == max_pages {
+            collected.truncated = true;
+            break;
+        }
+
+        let mut request = agent.get(&next_url);
+        // Link URLs already carry their query; every other page repeats the base query.
+        if strategy != "link" || collected.pages == 0 {
+            for (key, value) in &query {
+                let value = value.as_str().map(String::from).unwrap_or_else(|| value.to_string());
+                request = request.query(key, &value);
+            }
+        }
+        match strategy.as_str() {
+            "cursor" => {
+                if let Some(cursor) = &cursor {
+                    request = request.query(&cursor_param, cursor);
+                }
+            }
+            "offset" => {
+                request = request
+                    .query(&offset_param, offset.to_string())
+                    .query(&limit_param, page_size.unwrap_or_default().to_string());
+            }
+            _ => {}
+        }
+        for (name, value) in &headers {
+            request = request.header(name, value);
+        }
+
+        let mut response = request.call().map_err(|e| e.to_string())?;
+        let status = response.status().as_u16();
+        let link = response
+            .headers()
+            .get("link")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+        if status >= 400 {
+            return Err(format!("page {} returned {}: {}", collected.pages + 1, status, body));
+        }
+        let body: Value = serde_json::from_str(&body).map_err(|e| format!("invalid JSON on page {}: {}", collected.pages + 1, e))?;
+        let items = lookup(&body, &items_path)
+            .and_then(Value::as_array)
+            .ok_or_else(|| format!("no item list at '{}' on page {}", items_path, collected.pages + 1))?;
+        let page_len = items.len() as u64;
+        collected.items.extend(items.iter().cloned());
+        collected.pages += 1;
+
+        match strategy.as_str() {
+            "cursor" => {
+                let next = lookup(&body, cursor_path.as_deref().unwrap_or_default())
+                    .and_then(|v| match v {
+                        Value::String(s) if !s.is_empty() => Some(s.clone()),
+                        Value::Number(n) => Some(n.to_string()),
+                        _ => None,
+                    });
+                // A repeated cursor would loop forever.
+                if next.is_none() || next == cursor || page_len == 0 {
+                    break;
+                }
+                cursor = next;
+            }
+            "offset" => {
+                if page_len < page_size.unwrap_or_default() || page_len == 0 {
+                    break;
+                }
+                offset += page_len;
+            }
+            _ => match link.as_deref().and_then(next_link) {
+                Some(link) => next_url = resolve(&next_url, &link),
+                None => break,
+            },
+        }
+    }
+
+    Ok(collected)
+}
+
+impl NodeExecutor for HttpPaginate {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match collect(&inputs, runtime) {
+            Ok(collected) => {
+                output.insert("success".to_string(), json!(true));
+                output.insert("count".to_string(), json!(collected.items.len()));
+                output.insert("pages".to_string(), json!(collected.pages));
+                output.insert("truncated".to_string(), json!(collected.truncated));
+                output.insert("items".to_string(), json!(collected.items));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), json!(false));
+                output.insert("items".to_string(), json!([]));
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new HttpPaginate instance.
+pub fn create() -> HttpPaginate {
+    HttpPaginate::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves one response per `(extra_headers, body)` pair and reports each request line.
+    fn fake_server(pages: Vec<(String, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (extra, body) in pages {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                tx.send(request_line.trim().to_string()).unwrap();
+                let mut stream = stream;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\n\r\n{}",
+                    extra,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (format!("http://{}", addr), rx)
+    }
+
+    #[test]
+    fn test_cursor() {
+        let (base, requests) = fake_server(vec![
+            (String::new(), r#"{"data":[1,2],"meta":{"next":"abc"}}"#),
+            (String::new(), r#"{"data":[3],"meta":{"next":null}}"#),
+        ]);
+        let executor = HttpPaginate::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(format!("{}/items", base)));
+        inputs.insert("strategy".to_string(), json!("cursor"));
+        inputs.insert("items_path".to_string(), json!("data"));
+        inputs.insert("cursor_path".to_string(), json!("meta.next"));
+        inputs.insert("query".to_string(), json!({"status": "open"}));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("items"), Some(&json!([1, 2, 3])));
+        assert_eq!(result.get("pages"), Some(&json!(2)));
+        assert_eq!(requests.recv().unwrap(), "GET /items?status=open HTTP/1.1");
+        assert_eq!(requests.recv().unwrap(), "GET /items?status=open&cursor=abc HTTP/1.1");
+    }
+
+    #[test]
+    fn test_offset_stops_on_short_page() {
+        let (base, requests) = fake_server(vec![(String::new(), r#"[1,2]"#), (String::new(), r#"[3]"#)]);
+        let executor = HttpPaginate::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(format!("{}/items", base)));
+        inputs.insert("strategy".to_string(), json!("offset"));
+        inputs.insert("page_size".to_string(), json!(2));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("items"), Some(&json!([1, 2, 3])));
+        assert_eq!(requests.recv().unwrap(), "GET /items?offset=0&limit=2 HTTP/1.1");
+        assert_eq!(requests.recv().unwrap(), "GET /items?offset=2&limit=2 HTTP/1.1");
+    }
+
+    #[test]
+    fn test_link_header_and_page_limit() {
+        let listener_pages = vec![
+            ("Link: </items?page=2>; rel=\"next\", </items?page=9>; rel=\"last\"\r\n".to_string(), r#"[1]"#),
+            ("Link: </items?page=3>; rel=\"next\"\r\n".to_string(), r#"[2]"#),
+        ];
+        let (base, requests) = fake_server(listener_pages);
+        let executor = HttpPaginate::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(format!("{}/items", base)));
+        inputs.insert("max_pages".to_string(), json!(2));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("items"), Some(&json!([1, 2])));
+        assert_eq!(result.get("truncated"), Some(&json!(true)));
+        assert_eq!(requests.recv().unwrap(), "GET /items HTTP/1.1");
+        assert_eq!(requests.recv().unwrap(), "GET /items?page=2 HTTP/1.1");
+    }
+
+    #[test]
+    fn test_next_link() {
+        assert_eq!(
+            next_link(r#"<https://api.example.com/r?page=2>; rel="next", <https://api.example.com/r?page=5>; rel="last""#),
+            Some("https://api.example.com/r?page=2".to_string())
+        );
+        assert_eq!(next_link(r#"<https://api.example.com/r?page=1>; rel="prev""#), None);
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "http.paginate");
+        assert_eq!(executor.category, "http");
+    }
+}
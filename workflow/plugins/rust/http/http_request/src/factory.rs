@@ -0,0 +1,8 @@
+//! Factory for HttpRequest plugin.
+
+use super::HttpRequest;
+
+/// Creates a new HttpRequest instance.
+pub fn create() -> HttpRequest {
+    HttpRequest::new()
+}
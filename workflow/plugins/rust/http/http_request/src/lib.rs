@@ -0,0 +1,647 @@
+//! Workflow plugin: send an HTTP request.
+//!
+//! Sends `method` (`GET` by default) to `url`, with `query` pairs appended
+//! to the URL (a list value repeats its key), `headers`, and either `body`,
+//! sent as JSON, or `form`, sent URL-encoded. `timeout_ms` bounds the whole
+//! exchange (30 seconds by default) and `connect_timeout_ms` connecting
+//! alone. `auth` names an auth profile in the
+//! runtime's secrets (see `metabuilder_core::auth`), whose header or query
+//! parameter is added to the request.
+//!
+//! The run and execution ids of the node (see
+//! `metabuilder_core::correlation`) are sent as `X-Run-Id` and
+//! `X-Execution-Id`, unless `headers` sets them.
+//!
+//! With `retries`, a request answered with 429 or a 5xx status, or failing to
+//! connect, is sent again up to that many times, waiting `backoff_ms` (500 by
+//! default) before the first retry and twice as long before each next one,
+//! unless the response's `Retry-After` gives the seconds to wait. With
+//! `rate_limit`, requests to the URL's host are spaced to at most that many
+//! per second, counting the requests of every node of the engine (or, in a
+//! tenant's runs, of the tenant) that limits its rate.
+//!
+//! Outputs `status`, `ok` (whether the status is 2xx), `headers` by lowercase
+//! name (repeated headers joined with `", "`), and `body`, parsed when the
+//! response is JSON and as text otherwise. Error statuses are outputs, not
+//! errors, so workflows can branch on them; failing to connect or to read
+//! the response outputs `error`. `attempts` counts the requests sent.
+//!
+//! The node is an `AsyncNodeExecutor`: it waits on connections, responses,
+//! retries, and rate limits without blocking a thread, and the engine runs
+//! it on the shared reactor (see `metabuilder_core::reactor`). HTTP/1.1
+//! connections are pooled in the runtime's clients, so requests to the same
+//! host from any node of the engine, or of the same tenant, reuse them.
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::client::conn::http1::{self, SendRequest};
+use hyper::header::HOST;
+use hyper::{Method, Request, Response, Uri};
+use hyper_util::rt::TokioIo;
+use metabuilder_core::correlation::{self, EXECUTION_ID_HEADER, RUN_ID_HEADER};
+use metabuilder_core::reactor;
+use metabuilder_core::{AuthProfile, Cancellation, PortsFuture, Runtime};
+use serde_json::{json, Map, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+/// Key of the connection pool in `Runtime::clients`.
+const POOL: &str = "http.pool";
+
+/// Key of the per-host rate limits in `Runtime::clients`.
+const RATE_LIMITS: &str = "http.rate_limits";
+
+/// Idle connections kept per host.
+const MAX_IDLE: usize = 16;
+
+/// The sending half of a connection; its other half runs on the reactor.
+type Connection = SendRequest<Full<Bytes>>;
+
+/// Idle connections by scheme and authority.
+#[derive(Default)]
+struct Pool {
+    idle: Mutex<HashMap<String, Vec<Connection>>>,
+}
+
+impl Pool {
+    /// An idle connection to `origin` that is still open, if any.
+    fn take(&self, origin: &str) -> Option<Connection> {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        let connections = idle.get_mut(origin)?;
+        while let Some(connection) = connections.pop() {
+            if connection.is_ready() {
+                return Some(connection);
+            }
+        }
+        None
+    }
+
+    /// Keeps `connection` to `origin` for the next request.
+    fn put(&self, origin: String, connection: Connection) {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        let connections = idle.entry(origin).or_default();
+        if connections.len() < MAX_IDLE {
+            connections.push(connection);
+        }
+    }
+}
+
+/// When the next rate-limited request to each host may be sent.
+#[derive(Default)]
+struct RateLimits {
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimits {
+    /// Reserves the next slot for `host`, `interval` after the one before,
+    /// returning how long to wait for it.
+    fn reserve(&self, host: &str, interval: Duration) -> Duration {
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let slot = next.get(host).copied().filter(|slot| *slot > now).unwrap_or(now);
+        next.insert(host.to_string(), slot + interval);
+        slot - now
+    }
+}
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// HttpRequest implements the NodeExecutor trait for HTTP requests.
+pub struct HttpRequest {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl HttpRequest {
+    /// Creates a new HttpRequest instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "http.request",
+            category: "http",
+            description: "Send an HTTP request",
+        }
+    }
+}
+
+impl Default for HttpRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Percent-encodes `text` for a query string or form body.
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// `name=value` pairs of an object input, a list value repeating its name.
+fn pairs(inputs: &HashMap<String, Value>, name: &str) -> Result<Vec<String>, String> {
+    let Some(object) = inputs.get(name).filter(|v| !v.is_null()) else {
+        return Ok(Vec::new());
+    };
+    let object = object.as_object().ok_or_else(|| format!("{} must be an object", name))?;
+    let mut pairs = Vec::new();
+    for (key, value) in object {
+        let values = match value {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            let text = match value {
+                Value::String(text) => text.clone(),
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                _ => return Err(format!("{}.{} must be a string, number, or boolean", name, key)),
+            };
+            pairs.push(format!("{}={}", encode(key), encode(&text)));
+        }
+    }
+    Ok(pairs)
+}
+
+/// `error` followed by the errors that caused it.
+fn describe(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+async fn send(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<HashMap<String, Value>, String> {
+    let url = inputs.get("url").and_then(Value::as_str).ok_or("url is required")?;
+    let method = inputs.get("method").and_then(Value::as_str).unwrap_or("GET").to_ascii_uppercase();
+    let method = Method::from_bytes(method.as_bytes()).map_err(|_| format!("invalid method {}", method))?;
+    let timeout = Duration::from_millis(inputs.get("timeout_ms").and_then(Value::as_u64).unwrap_or(30_000));
+    let connect_timeout = inputs.get("connect_timeout_ms").and_then(Value::as_u64).map(Duration::from_millis);
+    let retries = inputs.get("retries").and_then(Value::as_u64).unwrap_or(0);
+    let backoff = Duration::from_millis(inputs.get("backoff_ms").and_then(Value::as_u64).unwrap_or(500));
+    let interval = match inputs.get("rate_limit").filter(|v| !v.is_null()) {
+        Some(rate) => {
+            let rate = rate.as_f64().filter(|rate| *rate > 0.0).ok_or("rate_limit must be a positive number")?;
+            Some(Duration::from_secs_f64(1.0 / rate))
+        }
+        None => None,
+    };
+
+    let profile = match inputs.get("auth").filter(|v| !v.is_null()) {
+        Some(name) => {
+            let name = name.as_str().ok_or("auth must be a string")?;
+            let runtime = Runtime::from_any(runtime).ok_or("runtime with secrets provider is required")?;
+            Some(AuthProfile::resolve(runtime, name)?)
+        }
+        None => None,
+    };
+
+    let mut query = pairs(inputs, "query")?;
+    if let Some((name, key)) = profile.as_ref().and_then(AuthProfile::query) {
+        query.push(format!("{}={}", encode(&name), encode(&key)));
+    }
+    let uri = url.parse::<Uri>().map_err(|e| format!("invalid url {}: {}", url, e))?;
+    let host = uri.authority().map(|a| a.to_string()).ok_or_else(|| format!("url {} has no host", url))?;
+    // Requests go out in origin form, with the authority in `Host`.
+    let mut target = uri.path_and_query().map_or_else(|| "/".to_string(), |target| target.to_string());
+    if !query.is_empty() {
+        target.push(if target.contains('?') { '&' } else { '?' });
+        target.push_str(&query.join("&"));
+    }
+    let mut headers = Vec::new();
+    if let Some(object) = inputs.get("headers").filter(|v| !v.is_null()) {
+        for (name, value) in object.as_object().ok_or("headers must be an object")? {
+            let value = value.as_str().ok_or_else(|| format!("headers.{} must be a string", name))?;
+            headers.push((name.clone(), value.to_string()));
+        }
+    }
+    headers.extend(profile.as_ref().and_then(AuthProfile::header));
+    if let Some(ids) = correlation::current() {
+        for (name, id) in [(RUN_ID_HEADER, ids.run_id), (EXECUTION_ID_HEADER, ids.execution_id)] {
+            if !headers.iter().any(|(set, _)| set.eq_ignore_ascii_case(name)) {
+                headers.push((name.to_string(), id));
+            }
+        }
+    }
+    if !headers.iter().any(|(set, _)| set.eq_ignore_ascii_case(HOST.as_str())) {
+        headers.push((HOST.to_string(), host.clone()));
+    }
+    let body = match (inputs.get("body").filter(|v| !v.is_null()), inputs.get("form").filter(|v| !v.is_null())) {
+        (Some(_), Some(_)) => return Err("body and form cannot both be given".to_string()),
+        (Some(body), None) => {
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
+            Bytes::from(body.to_string())
+        }
+        (None, Some(_)) => {
+            headers.push(("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()));
+            Bytes::from(pairs(inputs, "form")?.join("&"))
+        }
+        (None, None) => Bytes::new(),
+    };
+
+    let (pool, cancel) = match Runtime::from_any(runtime) {
+        Some(runtime) => (runtime.clients.get_or_insert_with(POOL, Pool::default), runtime.cancel.clone()),
+        None => (Arc::new(Pool::default()), Arc::new(Cancellation::new())),
+    };
+    let limits = interval.map(|interval| {
+        let limits = match Runtime::from_any(runtime) {
+            Some(runtime) => runtime.clients.get_or_insert_with(RATE_LIMITS, RateLimits::default),
+            None => Arc::new(RateLimits::default()),
+        };
+        (limits, interval)
+    });
+
+    let mut attempt = 0;
+    loop {
+        if let Some((limits, interval)) = &limits {
+            if !reactor::sleep(&cancel, limits.reserve(&host, *interval)).await {
+                return Err("cancelled".to_string());
+            }
+        }
+        let mut request = Request::builder().method(method.clone()).uri(target.as_str());
+        for (name, value) in &headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let request = request.body(Full::new(body.clone())).map_err(|e| e.to_string())?;
+        let result = match tokio::time::timeout(timeout, run(&pool, &uri, request, connect_timeout)).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("timed out after {} ms", timeout.as_millis())),
+        };
+        // Retry-After only in its delay-seconds form; dates fall back to backoff.
+        let retry = match &result {
+            Ok(output) => match output["status"].as_u64().unwrap_or_default() {
+                429 | 500..=599 => Some(output["headers"]["retry-after"].as_str().and_then(|s| s.trim().parse().ok())),
+                _ => None,
+            },
+            Err(_) => Some(None),
+        };
+        match retry {
+            Some(retry_after) if attempt < retries => {
+                let wait = match retry_after {
+                    Some(seconds) => Duration::from_secs(seconds),
+                    None => backoff.saturating_mul(1 << attempt.min(16)),
+                };
+                if !reactor::sleep(&cancel, wait).await {
+                    return Err("cancelled".to_string());
+                }
+                attempt += 1;
+            }
+            _ => {
+                return result.map(|mut output| {
+                    output.insert("attempts".to_string(), json!(attempt + 1));
+                    output
+                })
+            }
+        }
+    }
+}
+
+/// Opens a connection to the host of `uri`, in TLS for `https`, and starts
+/// driving it on the reactor.
+async fn connect(uri: &Uri) -> Result<Connection, String> {
+    let tls = match uri.scheme_str() {
+        Some("http") => false,
+        Some("https") => true,
+        _ => return Err(format!("unsupported url scheme in {}", uri)),
+    };
+    let host = uri.host().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+    let port = uri.port_u16().unwrap_or(if tls { 443 } else { 80 });
+    let tcp = TcpStream::connect((host, port)).await.map_err(|e| format!("cannot connect to {}: {}", host, e))?;
+    tcp.set_nodelay(true).map_err(|e| e.to_string())?;
+    match tls {
+        false => handshake(TokioIo::new(tcp)).await,
+        true => {
+            let connector = native_tls::TlsConnector::new().map_err(|e| e.to_string())?;
+            let stream = tokio_native_tls::TlsConnector::from(connector)
+                .connect(host, tcp)
+                .await
+                .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+            handshake(TokioIo::new(stream)).await
+        }
+    }
+}
+
+async fn handshake<T>(io: T) -> Result<Connection, String>
+where
+    T: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
+{
+    let (connection, driver) = http1::handshake(io).await.map_err(|e| describe(&e))?;
+    tokio::spawn(driver);
+    Ok(connection)
+}
+
+/// Sends `request` to the host of `uri` over a pooled connection, or a new
+/// one if none is idle, and reads the response.
+async fn run(
+    pool: &Pool,
+    uri: &Uri,
+    request: Request<Full<Bytes>>,
+    connect_timeout: Option<Duration>,
+) -> Result<HashMap<String, Value>, String> {
+    let origin = format!("{}://{}", uri.scheme_str().unwrap_or_default(), uri.authority().map_or("", |a| a.as_str()));
+    let mut connection = match pool.take(&origin) {
+        Some(connection) => connection,
+        None => match connect_timeout {
+            Some(limit) => tokio::time::timeout(limit, connect(uri))
+                .await
+                .map_err(|_| format!("connecting to {} timed out after {} ms", origin, limit.as_millis()))??,
+            None => connect(uri).await?,
+        },
+    };
+    let response = connection.send_request(request).await.map_err(|e| describe(&e))?;
+    let (response, body) = response.into_parts();
+    let body = body.collect().await.map_err(|e| describe(&e))?.to_bytes();
+    // Once its response is read, the connection can carry the next request,
+    // unless either side is closing it.
+    if connection.ready().await.is_ok() {
+        pool.put(origin, connection);
+    }
+    output(Response::from_parts(response, body))
+}
+
+fn output(response: Response<Bytes>) -> Result<HashMap<String, Value>, String> {
+    let status = response.status().as_u16();
+    let mut headers = Map::new();
+    for (name, value) in response.headers() {
+        let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+        match headers.get_mut(name.as_str()) {
+            Some(Value::String(joined)) => {
+                joined.push_str(", ");
+                joined.push_str(&value);
+            }
+            _ => {
+                headers.insert(name.as_str().to_string(), json!(value));
+            }
+        }
+    }
+    let is_json = headers
+        .get("content-type")
+        .and_then(Value::as_str)
+        .is_some_and(|t| t.contains("json"));
+    let text = String::from_utf8(response.into_body().to_vec()).map_err(|e| e.to_string())?;
+    let body = match is_json && !text.is_empty() {
+        true => serde_json::from_str(&text).map_err(|e| format!("invalid JSON response: {}", e))?,
+        false => json!(text),
+    };
+
+    let mut output = HashMap::new();
+    output.insert("status".to_string(), json!(status));
+    output.insert("ok".to_string(), json!((200..300).contains(&status)));
+    output.insert("headers".to_string(), Value::Object(headers));
+    output.insert("body".to_string(), body);
+    Ok(output)
+}
+
+impl metabuilder_core::AsyncNodeExecutor for HttpRequest {
+    fn execute<'a>(&'a self, inputs: HashMap<String, Value>, runtime: Option<&'a dyn Any>) -> PortsFuture<'a> {
+        Box::pin(async move {
+            send(&inputs, runtime).await.unwrap_or_else(|e| {
+                let mut output = HashMap::new();
+                output.insert("ok".to_string(), json!(false));
+                output.insert("body".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+                output
+            })
+        })
+    }
+}
+
+impl NodeExecutor for HttpRequest {
+    /// Runs the node on the shared reactor, blocking until it finishes.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        reactor::block_on(metabuilder_core::AsyncNodeExecutor::execute(self, inputs, runtime))
+    }
+}
+
+/// Creates a new HttpRequest instance.
+pub fn create() -> HttpRequest {
+    HttpRequest::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers requests on a single connection with `responses` in turn,
+    /// reporting each request's head and body.
+    fn fake_server(responses: Vec<(&'static str, &'static str, &'static str)>) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut stream = stream;
+            for (status, content_type, body) in responses {
+                let mut head = String::new();
+                let mut length = 0;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if let Some(n) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = n.trim().parse().unwrap();
+                    }
+                    head.push_str(&line);
+                    line.clear();
+                }
+                let mut request = vec![0; length];
+                reader.read_exact(&mut request).unwrap();
+                tx.send((head, String::from_utf8(request).unwrap())).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nSet-Cookie: a=1\r\nSet-Cookie: b=2\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    content_type,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (format!("http://{}", addr), rx)
+    }
+
+    fn inputs(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_json_request() {
+        let (url, requests) = fake_server(vec![("201 Created", "application/json", r#"{"id":7}"#)]);
+        let output = HttpRequest::new().execute(
+            inputs(json!({
+                "method": "post",
+                "url": format!("{}/items", url),
+                "query": {"tag": ["a b", "c&d"], "draft": true},
+                "headers": {"X-Trace": "t1"},
+                "body": {"name": "widget"},
+            })),
+            None,
+        );
+        assert_eq!(output["status"], 201);
+        assert_eq!(output["ok"], true);
+        assert_eq!(output["body"], json!({"id": 7}));
+        assert_eq!(output["headers"]["set-cookie"], "a=1, b=2");
+
+        let (head, body) = requests.recv().unwrap();
+        assert!(head.starts_with("POST /items?draft=true&tag=a%20b&tag=c%26d HTTP/1.1\r\n"), "{}", head);
+        assert!(head.to_lowercase().contains("x-trace: t1\r\n"));
+        assert!(head.to_lowercase().contains("content-type: application/json\r\n"));
+        assert_eq!(body, r#"{"name":"widget"}"#);
+    }
+
+    #[test]
+    fn test_form_and_error_status() {
+        let (url, requests) = fake_server(vec![("404 Not Found", "text/plain", "no such user")]);
+        let output = HttpRequest::new().execute(
+            inputs(json!({"method": "PUT", "url": url, "form": {"name": "Ada L", "age": 36}})),
+            None,
+        );
+        assert_eq!(output["status"], 404);
+        assert_eq!(output["ok"], false);
+        assert_eq!(output["body"], "no such user");
+        assert!(!output.contains_key("error"));
+        assert_eq!(requests.recv().unwrap().1, "age=36&name=Ada%20L");
+    }
+
+    #[test]
+    fn test_pooled_connection() {
+        let (url, requests) = fake_server(vec![("200 OK", "text/plain", "one"), ("200 OK", "text/plain", "two")]);
+        let runtime = Runtime::new();
+        for expected in ["one", "two"] {
+            let output = HttpRequest::new()
+                .execute(inputs(json!({"url": url, "timeout_ms": 2000})), Some(&runtime as &dyn Any));
+            // The server accepts one connection, so the second request only
+            // succeeds if it reuses the first one's.
+            assert_eq!(output["body"], expected, "{:?}", output.get("error"));
+        }
+        assert_eq!(requests.iter().take(2).count(), 2);
+        assert_eq!(runtime.clients.len(), 1);
+    }
+
+    #[test]
+    fn test_async_executor() {
+        let (url, requests) = fake_server(vec![("200 OK", "text/plain", "one")]);
+        // A host already in a tokio runtime awaits the node without the reactor.
+        let host = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let node = HttpRequest::new();
+        let output = host.block_on(metabuilder_core::AsyncNodeExecutor::execute(&node, inputs(json!({"url": url})), None));
+        assert_eq!(output["body"], "one", "{:?}", output.get("error"));
+        assert!(requests.recv().unwrap().0.starts_with("GET / HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_auth_profiles() {
+        let (url, requests) = fake_server(vec![("200 OK", "text/plain", "a"), ("200 OK", "text/plain", "b")]);
+        let mut secrets = metabuilder_core::MemorySecrets::new();
+        secrets.insert("github", r#"{"type": "bearer", "token": "t0k"}"#);
+        secrets.insert("maps", r#"{"type": "api_key", "key": "k 1", "query": "key"}"#);
+        let runtime = Runtime::with_secrets(secrets);
+        let runtime = Some(&runtime as &dyn Any);
+
+        let output = HttpRequest::new().execute(inputs(json!({"url": url, "auth": "github"})), runtime);
+        assert_eq!(output["body"], "a", "{:?}", output.get("error"));
+        assert!(requests.recv().unwrap().0.to_lowercase().contains("authorization: bearer t0k\r\n"));
+
+        let output = HttpRequest::new().execute(inputs(json!({"url": url, "query": {"q": 1}, "auth": "maps"})), runtime);
+        assert_eq!(output["body"], "b", "{:?}", output.get("error"));
+        assert!(requests.recv().unwrap().0.starts_with("GET /?q=1&key=k%201 HTTP/1.1\r\n"));
+
+        let output = HttpRequest::new().execute(inputs(json!({"url": url, "auth": "gitlab"})), runtime);
+        assert_eq!(output["error"], "auth profile not found: gitlab");
+        let output = HttpRequest::new().execute(inputs(json!({"url": url, "auth": "github"})), None);
+        assert_eq!(output["error"], "runtime with secrets provider is required");
+    }
+
+    #[test]
+    fn test_correlation_headers() {
+        let (url, requests) = fake_server(vec![("200 OK", "text/plain", "a"), ("200 OK", "text/plain", "b")]);
+        let ids = correlation::Correlation::from_execution_id("r1:fetch:0");
+        // The server accepts one connection, so both requests share a pool.
+        let runtime = Runtime::new();
+        let runtime = Some(&runtime as &dyn Any);
+        correlation::scope(ids, || {
+            HttpRequest::new().execute(inputs(json!({"url": url})), runtime);
+            HttpRequest::new().execute(inputs(json!({"url": url, "headers": {"x-run-id": "upstream"}})), runtime);
+        });
+        let head = requests.recv().unwrap().0.to_lowercase();
+        assert!(head.contains("x-run-id: r1\r\n") && head.contains("x-execution-id: r1:fetch:0\r\n"), "{}", head);
+        let head = requests.recv().unwrap().0.to_lowercase();
+        assert!(head.contains("x-run-id: upstream\r\n") && !head.contains("x-run-id: r1"), "{}", head);
+    }
+
+    #[test]
+    fn test_retry() {
+        let (url, requests) = fake_server(vec![
+            ("503 Service Unavailable", "text/plain", "busy"),
+            ("429 Too Many Requests", "text/plain", "slow down"),
+            ("200 OK", "text/plain", "done"),
+        ]);
+        let output = HttpRequest::new().execute(inputs(json!({"url": url, "retries": 3, "backoff_ms": 10})), None);
+        assert_eq!(output["body"], "done", "{:?}", output.get("error"));
+        assert_eq!(output["attempts"], 3);
+        assert_eq!(requests.iter().take(3).count(), 3);
+
+        // Out of retries, the last response is the output.
+        let (url, _requests) = fake_server(vec![("500 Internal Server Error", "text/plain", "a"), ("502 Bad Gateway", "text/plain", "b")]);
+        let output = HttpRequest::new().execute(inputs(json!({"url": url, "retries": 1, "backoff_ms": 10})), None);
+        assert_eq!(output["status"], 502);
+        assert_eq!(output["attempts"], 2);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let limits = RateLimits::default();
+        let interval = Duration::from_millis(100);
+        assert_eq!(limits.reserve("a.test", interval), Duration::ZERO);
+        assert!(limits.reserve("a.test", interval) > Duration::from_millis(90));
+        assert_eq!(limits.reserve("b.test", interval), Duration::ZERO);
+
+        let (url, _requests) = fake_server(vec![("200 OK", "text/plain", "one"), ("200 OK", "text/plain", "two")]);
+        let runtime = Runtime::new();
+        let started = Instant::now();
+        for expected in ["one", "two"] {
+            let output = HttpRequest::new()
+                .execute(inputs(json!({"url": url, "rate_limit": 10})), Some(&runtime as &dyn Any));
+            assert_eq!(output["body"], expected, "{:?}", output.get("error"));
+        }
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        let output = HttpRequest::new().execute(inputs(json!({"url": url, "rate_limit": 0})), None);
+        assert_eq!(output["error"], "rate_limit must be a positive number");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let output = HttpRequest::new().execute(inputs(json!({"url": "http://127.0.0.1:1", "body": {}, "form": {}})), None);
+        assert_eq!(output["error"], "body and form cannot both be given");
+        let output = HttpRequest::new().execute(inputs(json!({"url": "http://127.0.0.1:1", "query": {"a": {"b": 1}}})), None);
+        assert_eq!(output["error"], "query.a must be a string, number, or boolean");
+        assert_eq!(HttpRequest::new().execute(HashMap::new(), None)["error"], "url is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "http.request");
+        assert_eq!(executor.category, "http");
+    }
+}
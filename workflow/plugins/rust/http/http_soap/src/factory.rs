@@ -0,0 +1,8 @@
+//! Factory for HttpSoap plugin.
+
+use super::HttpSoap;
+
+/// Creates a new HttpSoap instance.
+pub fn create() -> HttpSoap {
+    HttpSoap::new()
+}
@@ -0,0 +1,298 @@
+//! Workflow plugin: call a SOAP or XML-RPC service.
+//!
+//! Builds the request envelope from JSON params, posts it, and unwraps the
+//! response back into JSON. Faults are reported through `error` with the
+//! decoded fault also available on the `fault` output.
+
+mod soap;
+mod xmlrpc;
+
+use base64::Engine as _;
+use metabuilder_core::Runtime;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// HttpSoap implements the NodeExecutor trait for SOAP and XML-RPC calls.
+pub struct HttpSoap {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl HttpSoap {
+    /// Creates a new HttpSoap instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "http.soap",
+            category: "http",
+            description: "Call SOAP or XML-RPC services",
+        }
+    }
+}
+
+impl Default for HttpSoap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Outcome of a call that reached the service.
+struct Reply {
+    status: u16,
+    raw: String,
+    result: Value,
+    fault: Option<Value>,
+}
+
+fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+    inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Builds `(content_type, body, soap_action_header)` for the request.
+fn build_request(inputs: &HashMap<String, Value>, protocol: &str) -> Result<(String, String, Option<String>), String> {
+    match protocol {
+        "soap" => {
+            let version = soap::Version::parse(&input::<String>(inputs, "version").unwrap_or_else(|| "1.1".to_string()))?;
+            let action: Option<String> = input(inputs, "action");
+            let body_xml = match input::<String>(inputs, "body_xml") {
+                Some(xml) => xml,
+                None => {
+                    let operation: String = input(inputs, "operation").ok_or("operation or body_xml is required")?;
+                    let namespace: Option<String> = input(inputs, "namespace");
+                    let params: serde_json::Map<String, Value> = input(inputs, "params").unwrap_or_default();
+                    soap::operation_xml(&operation, namespace.as_deref(), &params)
+                }
+            };
+            let header_xml: Option<String> = input(inputs, "header_xml");
+            let envelope = soap::envelope(version, header_xml.as_deref(), &body_xml);
+            // SOAP 1.1 carries the action in its own header; 1.2 puts it in the content type.
+            let soap_action = match version {
+                soap::Version::V11 => Some(format!("\"{}\"", action.clone().unwrap_or_default())),
+                soap::Version::V12 => None,
+            };
+            Ok((version.content_type(action.as_deref()), envelope, soap_action))
+        }
+        "xmlrpc" => {
+            let method: String = input(inputs, "method").ok_or("method is required for xmlrpc")?;
+            let params: Vec<Value> = input(inputs, "params").unwrap_or_default();
+            Ok(("text/xml".to_string(), xmlrpc::method_call(&method, &params), None))
+        }
+        other => Err(format!("unknown protocol: {} (expected soap or xmlrpc)", other)),
+    }
+}
+
+fn call(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Reply, String> {
+    let url: String = input(inputs, "url").ok_or("url is required")?;
+    let protocol: String = input(inputs, "protocol").unwrap_or_else(|| "soap".to_string());
+    let headers: HashMap<String, String> = input(inputs, "headers").unwrap_or_default();
+    let timeout_ms: u64 = input(inputs, "timeout_ms").unwrap_or(30_000);
+    let (content_type, body, soap_action) = build_request(inputs, &protocol)?;
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .build()
+        .into();
+    let mut request = agent.post(&url);
+    if let Some(action) = &soap_action {
+        request = request.header("SOAPAction", action);
+    }
+    if let Some(username) = input::<String>(inputs, "username") {
+        let password = match input::<String>(inputs, "password_secret") {
+            Some(name) => Runtime::from_any(runtime)
+                .ok_or("runtime with secrets provider is required")?
+                .secret(&name)
+                .ok_or_else(|| format!("secret not found: {}", name))?,
+            None => String::new(),
+        };
+        let encoded = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+        request = request.header("Authorization", &format!("Basic {}", encoded));
+    }
+    for (name, value) in &headers {
+        request = request.header(name, value);
+    }
+    let mut response = request
+        .content_type(&content_type)
+        .send(body.as_str())
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status().as_u16();
+    let raw = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+
+    // Faults usually arrive with HTTP 500, so parse before looking at the status.
+    let parsed = match protocol.as_str() {
+        "soap" => soap::parse_response(&raw).map(|r| match r {
+            soap::Response::Result { element, value } => (json!({ element: value }), None),
+            soap::Response::Fault(fault) => (
+                Value::Null,
+                Some(json!({ "code": fault.code, "message": fault.message, "detail": fault.detail })),
+            ),
+        }),
+        _ => xmlrpc::parse_response(&raw).map(|r| match r {
+            xmlrpc::Response::Result(value) => (value, None),
+            xmlrpc::Response::Fault { code, message } => (Value::Null, Some(json!({ "code": code, "message": message }))),
+        }),
+    };
+    match parsed {
+        Ok((result, fault)) => Ok(Reply {
+            status,
+            raw,
+            result,
+            fault,
+        }),
+        Err(_) if status >= 400 => Err(format!("service returned {}: {}", status, raw)),
+        Err(e) => Err(e),
+    }
+}
+
+impl NodeExecutor for HttpSoap {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match call(&inputs, runtime) {
+            Ok(reply) => {
+                output.insert("success".to_string(), json!(reply.fault.is_none()));
+                output.insert("status".to_string(), json!(reply.status));
+                output.insert("result".to_string(), reply.result);
+                output.insert("raw".to_string(), json!(reply.raw));
+                if let Some(fault) = &reply.fault {
+                    let code = fault["code"].as_str().map(String::from).unwrap_or_else(|| fault["code"].to_string());
+                    output.insert(
+                        "error".to_string(),
+                        json!(format!("fault {}: {}", code, fault["message"].as_str().unwrap_or_default())),
+                    );
+                }
+                output.insert("fault".to_string(), reply.fault.unwrap_or(Value::Null));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), json!(false));
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new HttpSoap instance.
+pub fn create() -> HttpSoap {
+    HttpSoap::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers one request with `status` and `body`, reporting the request head and body.
+    fn fake_server(status: &'static str, body: &'static str) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            let mut length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(n) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = n.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+                line.clear();
+            }
+            let mut request = vec![0; length];
+            reader.read_exact(&mut request).unwrap();
+            tx.send((head, String::from_utf8(request).unwrap())).unwrap();
+            let mut stream = stream;
+            write!(stream, "HTTP/1.1 {}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body).unwrap();
+        });
+        (format!("http://{}/service", addr), rx)
+    }
+
+    #[test]
+    fn test_soap_call() {
+        let (url, requests) = fake_server(
+            "200 OK",
+            r#"<?xml version="1.0"?><soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body>
+                <m:GetWeatherResponse xmlns:m="urn:weather"><temp unit="C">12</temp><summary>Rain</summary></m:GetWeatherResponse>
+            </soap:Body></soap:Envelope>"#,
+        );
+        let executor = HttpSoap::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(url));
+        inputs.insert("operation".to_string(), json!("GetWeather"));
+        inputs.insert("namespace".to_string(), json!("urn:weather"));
+        inputs.insert("action".to_string(), json!("urn:weather#GetWeather"));
+        inputs.insert("params".to_string(), json!({"city": "Oslo"}));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&json!(true)));
+        assert_eq!(
+            result.get("result"),
+            Some(&json!({"GetWeatherResponse": {"temp": {"@unit": "C", "#text": "12"}, "summary": "Rain"}}))
+        );
+
+        let (head, body) = requests.recv().unwrap();
+        assert!(head.to_lowercase().contains("soapaction: \"urn:weather#getweather\""));
+        assert!(body.contains("<soap:Body><m:GetWeather xmlns:m=\"urn:weather\"><city>Oslo</city></m:GetWeather></soap:Body>"));
+    }
+
+    #[test]
+    fn test_soap_fault() {
+        let (url, _requests) = fake_server(
+            "500 Internal Server Error",
+            r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body><soap:Fault>
+                <faultcode>soap:Client</faultcode><faultstring>Unknown city</faultstring>
+            </soap:Fault></soap:Body></soap:Envelope>"#,
+        );
+        let executor = HttpSoap::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(url));
+        inputs.insert("operation".to_string(), json!("GetWeather"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&json!("fault soap:Client: Unknown city")));
+        assert_eq!(result.get("fault").unwrap()["message"], json!("Unknown city"));
+    }
+
+    #[test]
+    fn test_xmlrpc_call() {
+        let (url, requests) = fake_server(
+            "200 OK",
+            "<?xml version=\"1.0\"?><methodResponse><params><param><value><struct><member><name>sum</name><value><int>7</int></value></member></struct></value></param></params></methodResponse>",
+        );
+        let executor = HttpSoap::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("url".to_string(), json!(url));
+        inputs.insert("protocol".to_string(), json!("xmlrpc"));
+        inputs.insert("method".to_string(), json!("calc.add"));
+        inputs.insert("params".to_string(), json!([3, 4]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&json!({"sum": 7})));
+        let (_, body) = requests.recv().unwrap();
+        assert!(body.contains("<methodName>calc.add</methodName><params><param><value><int>3</int></value></param>"));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "http.soap");
+        assert_eq!(executor.category, "http");
+    }
+}
@@ -0,0 +1,214 @@
+//! SOAP envelopes: building requests from JSON and unwrapping responses.
+
+use serde_json::{Map, Value};
+use xmltree::{Element, XMLNode};
+
+const SOAP11_NS: &str = "http://schemas.xmlsoap.org/soap/envelope/";
+const SOAP12_NS: &str = "http://www.w3.org/2003/05/soap-envelope";
+
+/// SOAP protocol version, which decides the envelope namespace and content type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Version {
+    V11,
+    V12,
+}
+
+impl Version {
+    pub fn parse(s: &str) -> Result<Version, String> {
+        match s {
+            "1.1" => Ok(Version::V11),
+            "1.2" => Ok(Version::V12),
+            other => Err(format!("unsupported SOAP version: {} (expected 1.1 or 1.2)", other)),
+        }
+    }
+
+    fn namespace(self) -> &'static str {
+        match self {
+            Version::V11 => SOAP11_NS,
+            Version::V12 => SOAP12_NS,
+        }
+    }
+
+    /// Content type, carrying the action for SOAP 1.2.
+    pub fn content_type(self, action: Option<&str>) -> String {
+        match (self, action) {
+            (Version::V11, _) => "text/xml; charset=utf-8".to_string(),
+            (Version::V12, Some(action)) => format!("application/soap+xml; charset=utf-8; action=\"{}\"", action),
+            (Version::V12, None) => "application/soap+xml; charset=utf-8".to_string(),
+        }
+    }
+}
+
+/// Escapes text for element content and attribute values.
+pub fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Writes `value` as the content of `<name>`: objects become child elements,
+/// arrays repeat the element, and scalars become text.
+fn write_element(out: &mut String, name: &str, value: &Value) {
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| write_element(out, name, item)),
+        Value::Object(map) => {
+            out.push_str(&format!("<{}>", name));
+            for (key, child) in map {
+                write_element(out, key, child);
+            }
+            out.push_str(&format!("</{}>", name));
+        }
+        Value::Null => out.push_str(&format!("<{}/>", name)),
+        Value::String(s) => out.push_str(&format!("<{}>{}</{}>", name, escape(s), name)),
+        other => out.push_str(&format!("<{}>{}</{}>", name, other, name)),
+    }
+}
+
+/// Builds the operation element `<m:Operation xmlns:m="namespace">...</m:Operation>`.
+pub fn operation_xml(operation: &str, namespace: Option<&str>, params: &Map<String, Value>) -> String {
+    let mut children = String::new();
+    for (key, value) in params {
+        write_element(&mut children, key, value);
+    }
+    match namespace {
+        Some(ns) => format!("<m:{op} xmlns:m=\"{}\">{}</m:{op}>", escape(ns), children, op = operation),
+        None => format!("<{op}>{}</{op}>", children, op = operation),
+    }
+}
+
+/// Wraps body (and optional header) XML in a SOAP envelope.
+pub fn envelope(version: Version, header_xml: Option<&str>, body_xml: &str) -> String {
+    let header = header_xml.map(|h| format!("<soap:Header>{}</soap:Header>", h)).unwrap_or_default();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?><soap:Envelope xmlns:soap=\"{}\">{}<soap:Body>{}</soap:Body></soap:Envelope>",
+        version.namespace(),
+        header,
+        body_xml
+    )
+}
+
+fn child_elements(element: &Element) -> impl Iterator<Item = &Element> {
+    element.children.iter().filter_map(XMLNode::as_element)
+}
+
+/// Converts an element to JSON: text-only elements become strings, others
+/// objects keyed by child name (repeated names become arrays). Attributes
+/// are kept under `@name` keys.
+pub fn element_to_json(element: &Element) -> Value {
+    let mut map = Map::new();
+    for (name, value) in &element.attributes {
+        // Namespace declarations are plumbing, not data.
+        if name != "xmlns" && !name.starts_with("xmlns:") {
+            map.insert(format!("@{}", name), Value::String(value.clone()));
+        }
+    }
+    for child in child_elements(element) {
+        let value = element_to_json(child);
+        match map.get_mut(&child.name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+            None => {
+                map.insert(child.name.clone(), value);
+            }
+        }
+    }
+
+    let text = element.get_text().map(|t| t.trim().to_string()).unwrap_or_default();
+    match (map.is_empty(), text.is_empty()) {
+        (true, true) => Value::Null,
+        (true, false) => Value::String(text),
+        (false, false) => {
+            map.insert("#text".to_string(), Value::String(text));
+            Value::Object(map)
+        }
+        (false, true) => Value::Object(map),
+    }
+}
+
+/// A SOAP fault, normalised across 1.1 (`faultcode`/`faultstring`) and 1.2 (`Code`/`Reason`).
+pub struct Fault {
+    pub code: String,
+    pub message: String,
+    pub detail: Value,
+}
+
+/// The unwrapped response: the first element inside `Body`, or a fault.
+pub enum Response {
+    Result { element: String, value: Value },
+    Fault(Fault),
+}
+
+fn text_at(element: &Element, path: &[&str]) -> Option<String> {
+    path.iter()
+        .try_fold(element, |e, name| e.get_child(*name))
+        .and_then(|e| e.get_text())
+        .map(|t| t.trim().to_string())
+}
+
+/// Parses a SOAP response envelope.
+pub fn parse_response(xml: &str) -> Result<Response, String> {
+    let root = Element::parse(xml.as_bytes()).map_err(|e| format!("invalid XML response: {}", e))?;
+    if root.name != "Envelope" {
+        return Err(format!("expected a SOAP Envelope, got <{}>", root.name));
+    }
+    let body = root.get_child("Body").ok_or("SOAP envelope has no Body")?;
+    let Some(first) = child_elements(body).next() else {
+        return Ok(Response::Result {
+            element: String::new(),
+            value: Value::Null,
+        });
+    };
+
+    if first.name == "Fault" {
+        let code = text_at(first, &["faultcode"]).or_else(|| text_at(first, &["Code", "Value"]));
+        let message = text_at(first, &["faultstring"]).or_else(|| text_at(first, &["Reason", "Text"]));
+        let detail = first
+            .get_child("detail")
+            .or_else(|| first.get_child("Detail"))
+            .map(element_to_json)
+            .unwrap_or(Value::Null);
+        return Ok(Response::Fault(Fault {
+            code: code.unwrap_or_default(),
+            message: message.unwrap_or_default(),
+            detail,
+        }));
+    }
+
+    Ok(Response::Result {
+        element: first.name.clone(),
+        value: element_to_json(first),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_operation_xml() {
+        let params = json!({"city": "Oslo & Bergen", "tags": ["a", "b"]});
+        assert_eq!(
+            operation_xml("GetWeather", Some("urn:weather"), params.as_object().unwrap()),
+            "<m:GetWeather xmlns:m=\"urn:weather\"><city>Oslo &amp; Bergen</city><tags>a</tags><tags>b</tags></m:GetWeather>"
+        );
+    }
+
+    #[test]
+    fn test_parse_fault_12() {
+        let xml = r#"<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope"><env:Body><env:Fault>
+            <env:Code><env:Value>env:Sender</env:Value></env:Code>
+            <env:Reason><env:Text xml:lang="en">Bad city</env:Text></env:Reason>
+        </env:Fault></env:Body></env:Envelope>"#;
+
+        match parse_response(xml).unwrap() {
+            Response::Fault(fault) => {
+                assert_eq!(fault.code, "env:Sender");
+                assert_eq!(fault.message, "Bad city");
+            }
+            Response::Result { .. } => panic!("expected a fault"),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! XML-RPC method calls and responses.
+
+use crate::soap::escape;
+use serde_json::{json, Map, Value};
+use xmltree::{Element, XMLNode};
+
+/// Encodes a JSON value as an XML-RPC `<value>`.
+fn write_value(out: &mut String, value: &Value) {
+    out.push_str("<value>");
+    match value {
+        Value::Null => out.push_str("<nil/>"),
+        Value::Bool(b) => out.push_str(&format!("<boolean>{}</boolean>", u8::from(*b))),
+        Value::Number(n) if n.is_i64() && i32::try_from(n.as_i64().unwrap_or_default()).is_ok() => {
+            out.push_str(&format!("<int>{}</int>", n))
+        }
+        Value::Number(n) if n.is_i64() || n.is_u64() => out.push_str(&format!("<i8>{}</i8>", n)),
+        Value::Number(n) => out.push_str(&format!("<double>{}</double>", n)),
+        Value::String(s) => out.push_str(&format!("<string>{}</string>", escape(s))),
+        Value::Array(items) => {
+            out.push_str("<array><data>");
+            items.iter().for_each(|item| write_value(out, item));
+            out.push_str("</data></array>");
+        }
+        Value::Object(map) => {
+            out.push_str("<struct>");
+            for (name, member) in map {
+                out.push_str(&format!("<member><name>{}</name>", escape(name)));
+                write_value(out, member);
+                out.push_str("</member>");
+            }
+            out.push_str("</struct>");
+        }
+    }
+    out.push_str("</value>");
+}
+
+/// Builds a `<methodCall>` document.
+pub fn method_call(method: &str, params: &[Value]) -> String {
+    let mut out = format!(
+        "<?xml version=\"1.0\"?><methodCall><methodName>{}</methodName><params>",
+        escape(method)
+    );
+    for param in params {
+        out.push_str("<param>");
+        write_value(&mut out, param);
+        out.push_str("</param>");
+    }
+    out.push_str("</params></methodCall>");
+    out
+}
+
+fn child_elements(element: &Element) -> impl Iterator<Item = &Element> {
+    element.children.iter().filter_map(XMLNode::as_element)
+}
+
+fn text(element: &Element) -> String {
+    element.get_text().map(|t| t.to_string()).unwrap_or_default()
+}
+
+/// Decodes an XML-RPC `<value>` element.
+fn read_value(value: &Element) -> Result<Value, String> {
+    // A bare `<value>text</value>` is a string.
+    let Some(typed) = child_elements(value).next() else {
+        return Ok(Value::String(text(value)));
+    };
+    let content = text(typed);
+    match typed.name.as_str() {
+        "int" | "i4" | "i8" => content
+            .trim()
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("invalid {}: {}", typed.name, content)),
+        "double" => content
+            .trim()
+            .parse::<f64>()
+            .map(|d| json!(d))
+            .map_err(|_| format!("invalid double: {}", content)),
+        "boolean" => match content.trim() {
+            "1" => Ok(Value::Bool(true)),
+            "0" => Ok(Value::Bool(false)),
+            other => Err(format!("invalid boolean: {}", other)),
+        },
+        "string" | "dateTime.iso8601" | "base64" => Ok(Value::String(content)),
+        "nil" => Ok(Value::Null),
+        "array" => typed
+            .get_child("data")
+            .map(|data| child_elements(data).map(read_value).collect::<Result<Vec<_>, _>>())
+            .unwrap_or_else(|| Ok(Vec::new()))
+            .map(Value::Array),
+        "struct" => {
+            let mut map = Map::new();
+            for member in child_elements(typed) {
+                let name = member.get_child("name").map(text).ok_or("struct member has no name")?;
+                let value = member.get_child("value").ok_or("struct member has no value")?;
+                map.insert(name, read_value(value)?);
+            }
+            Ok(Value::Object(map))
+        }
+        other => Err(format!("unknown XML-RPC type: {}", other)),
+    }
+}
+
+/// A decoded `<methodResponse>`: the return value or `(faultCode, faultString)`.
+pub enum Response {
+    Result(Value),
+    Fault { code: Value, message: String },
+}
+
+/// Parses a `<methodResponse>` document.
+pub fn parse_response(xml: &str) -> Result<Response, String> {
+    let root = Element::parse(xml.as_bytes()).map_err(|e| format!("invalid XML response: {}", e))?;
+    if root.name != "methodResponse" {
+        return Err(format!("expected methodResponse, got <{}>", root.name));
+    }
+    if let Some(fault) = root.get_child("fault") {
+        let value = fault.get_child("value").map(read_value).transpose()?.unwrap_or(Value::Null);
+        return Ok(Response::Fault {
+            code: value.get("faultCode").cloned().unwrap_or(Value::Null),
+            message: value.get("faultString").and_then(Value::as_str).unwrap_or_default().to_string(),
+        });
+    }
+    let value = root
+        .get_child("params")
+        .and_then(|p| p.get_child("param"))
+        .and_then(|p| p.get_child("value"))
+        .map(read_value)
+        .transpose()?
+        .unwrap_or(Value::Null);
+    Ok(Response::Result(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_values() {
+        let params = vec![json!({"name": "a<b", "ids": [1, 5_000_000_000i64], "ok": true, "ratio": 0.5, "none": null})];
+        let call = method_call("demo.echo", &params);
+        assert!(call.contains("<name>name</name><value><string>a&lt;b</string></value>"));
+        assert!(call.contains("<i8>5000000000</i8>"));
+
+        // Echo the encoded param back as a response.
+        let start = call.find("<value>").unwrap();
+        let end = call.rfind("</param>").unwrap();
+        let response = format!("<methodResponse><params><param>{}</param></params></methodResponse>", &call[start..end]);
+        match parse_response(&response).unwrap() {
+            Response::Result(value) => assert_eq!(value, params[0]),
+            Response::Fault { .. } => panic!("unexpected fault"),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for HttpVerifySignature plugin.
+
+use super::HttpVerifySignature;
+
+/// Creates a new HttpVerifySignature instance.
+pub fn create() -> HttpVerifySignature {
+    HttpVerifySignature::new()
+}
@@ -0,0 +1,342 @@
+//! Workflow plugin: verify inbound webhook signatures.
+//!
+//! For the timestamped schemes (Stripe and Slack), requests signed more than
+//! `tolerance_seconds` (default 300) from now are rejected to stop replays.
+//! The tolerance must be at least one second; replay protection cannot be
+//! turned off.
+
+use hmac::{Hmac, Mac};
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use sha2::Sha256;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HttpVerifySignature implements the NodeExecutor trait for webhook authentication.
+pub struct HttpVerifySignature {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl HttpVerifySignature {
+    /// Creates a new HttpVerifySignature instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "http.verify_signature",
+            category: "http",
+            description: "Verify Stripe, GitHub, Slack, or plain HMAC webhook signatures",
+        }
+    }
+}
+
+impl Default for HttpVerifySignature {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What to check for a given scheme: the signed message, candidate signatures, and timestamp.
+struct SignedRequest {
+    message: Vec<u8>,
+    signatures: Vec<String>,
+    timestamp: Option<i64>,
+}
+
+/// Case-insensitive header lookup.
+fn header<'a>(headers: &'a serde_json::Map<String, Value>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .and_then(|(_, v)| v.as_str())
+}
+
+fn signed_request(
+    scheme: &str,
+    payload: &str,
+    headers: &serde_json::Map<String, Value>,
+    signature: Option<&str>,
+) -> Result<SignedRequest, String> {
+    match scheme {
+        "stripe" => {
+            // Stripe-Signature: t=1492774577,v1=5257a8...,v1=...
+            let value = signature
+                .or_else(|| header(headers, "Stripe-Signature"))
+                .ok_or("missing Stripe-Signature header")?;
+            let mut timestamp = None;
+            let mut signatures = Vec::new();
+            for part in value.split(',') {
+                match part.trim().split_once('=') {
+                    Some(("t", t)) => timestamp = t.parse().ok(),
+                    Some(("v1", sig)) => signatures.push(sig.to_string()),
+                    _ => {}
+                }
+            }
+            let timestamp = timestamp.ok_or("Stripe-Signature has no timestamp")?;
+            Ok(SignedRequest {
+                message: format!("{}.{}", timestamp, payload).into_bytes(),
+                signatures,
+                timestamp: Some(timestamp),
+            })
+        }
+        "github" => {
+            let value = signature
+                .or_else(|| header(headers, "X-Hub-Signature-256"))
+                .ok_or("missing X-Hub-Signature-256 header")?;
+            Ok(SignedRequest {
+                message: payload.as_bytes().to_vec(),
+                signatures: vec![value.trim_start_matches("sha256=").to_string()],
+                timestamp: None,
+            })
+        }
+        "slack" => {
+            let value = signature
+                .or_else(|| header(headers, "X-Slack-Signature"))
+                .ok_or("missing X-Slack-Signature header")?;
+            let timestamp: i64 = header(headers, "X-Slack-Request-Timestamp")
+                .and_then(|t| t.parse().ok())
+                .ok_or("missing X-Slack-Request-Timestamp header")?;
+            Ok(SignedRequest {
+                message: format!("v0:{}:{}", timestamp, payload).into_bytes(),
+                signatures: vec![value.trim_start_matches("v0=").to_string()],
+                timestamp: Some(timestamp),
+            })
+        }
+        "hmac_sha256" => {
+            let value = signature
+                .or_else(|| header(headers, "X-Signature"))
+                .ok_or("missing signature")?;
+            Ok(SignedRequest {
+                message: payload.as_bytes().to_vec(),
+                signatures: vec![value.trim_start_matches("sha256=").to_string()],
+                timestamp: None,
+            })
+        }
+        other => Err(format!("unknown scheme: {} (expected stripe, github, slack, or hmac_sha256)", other)),
+    }
+}
+
+/// Constant-time comparison of each candidate against the expected HMAC.
+fn matches(secret: &[u8], message: &[u8], signatures: &[String]) -> bool {
+    signatures.iter().any(|sig| {
+        let Some(expected) = decode_hex(sig) else {
+            return false;
+        };
+        let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+        mac.update(message);
+        mac.verify_slice(&expected).is_ok()
+    })
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn verify(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<(Option<i64>, Option<String>), String> {
+    let scheme: String = inputs
+        .get("scheme")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("scheme is required")?;
+    let payload: String = inputs
+        .get("payload")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("payload is required")?;
+    let secret_name: String = inputs
+        .get("secret_name")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("secret_name is required")?;
+    let headers = inputs
+        .get("headers")
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default();
+    let signature = inputs.get("signature").and_then(|v| v.as_str());
+    let tolerance: u64 = match inputs.get("tolerance_seconds") {
+        None | Some(Value::Null) => 300,
+        Some(v) => v
+            .as_u64()
+            .filter(|&t| t > 0)
+            .ok_or("tolerance_seconds must be a whole number of seconds, at least 1")?,
+    };
+
+    let secret = Runtime::from_any(runtime)
+        .ok_or("runtime with secrets provider is required")?
+        .secret(&secret_name)
+        .ok_or_else(|| format!("secret not found: {}", secret_name))?;
+
+    let request = signed_request(&scheme, &payload, &headers, signature)?;
+
+    if let Some(ts) = request.timestamp {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        if now.abs_diff(ts) > tolerance {
+            return Ok((Some(ts), Some("timestamp outside tolerance".to_string())));
+        }
+    }
+
+    if matches(secret.as_bytes(), &request.message, &request.signatures) {
+        Ok((request.timestamp, None))
+    } else {
+        Ok((request.timestamp, Some("signature mismatch".to_string())))
+    }
+}
+
+impl NodeExecutor for HttpVerifySignature {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match verify(&inputs, runtime) {
+            Ok((timestamp, reason)) => {
+                output.insert("valid".to_string(), serde_json::json!(reason.is_none()));
+                output.insert("timestamp".to_string(), serde_json::json!(timestamp));
+                if let Some(r) = reason {
+                    output.insert("reason".to_string(), serde_json::json!(r));
+                }
+            }
+            Err(e) => {
+                output.insert("valid".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new HttpVerifySignature instance.
+pub fn create() -> HttpVerifySignature {
+    HttpVerifySignature::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::runtime;
+
+    fn sign(secret: &str, message: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(message.as_bytes());
+        mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn now() -> i64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+    }
+
+    const SECRETS: &[(&str, &str)] = &[("webhook_secret", "whsec_test")];
+
+    fn inputs(scheme: &str, payload: &str, headers: Value) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("scheme".to_string(), serde_json::json!(scheme));
+        inputs.insert("payload".to_string(), serde_json::json!(payload));
+        inputs.insert("secret_name".to_string(), serde_json::json!("webhook_secret"));
+        inputs.insert("headers".to_string(), headers);
+        inputs
+    }
+
+    #[test]
+    fn test_stripe() {
+        let payload = r#"{"id":"evt_1"}"#;
+        let t = now();
+        let sig = sign("whsec_test", &format!("{}.{}", t, payload));
+        let headers = serde_json::json!({"stripe-signature": format!("t={},v1=deadbeef,v1={}", t, sig)});
+
+        let result = HttpVerifySignature::new().execute(inputs("stripe", payload, headers), Some(&runtime(SECRETS)));
+        assert_eq!(result.get("valid"), Some(&serde_json::json!(true)));
+        assert_eq!(result.get("timestamp"), Some(&serde_json::json!(t)));
+    }
+
+    #[test]
+    fn test_stripe_expired() {
+        let payload = "{}";
+        let t = now() - 3600;
+        let sig = sign("whsec_test", &format!("{}.{}", t, payload));
+        let headers = serde_json::json!({"Stripe-Signature": format!("t={},v1={}", t, sig)});
+
+        let result = HttpVerifySignature::new().execute(inputs("stripe", payload, headers), Some(&runtime(SECRETS)));
+        assert_eq!(result.get("valid"), Some(&serde_json::json!(false)));
+        assert_eq!(result.get("reason"), Some(&serde_json::json!("timestamp outside tolerance")));
+    }
+
+    #[test]
+    fn test_extreme_timestamp() {
+        let payload = "{}";
+        let t = i64::MIN;
+        let sig = sign("whsec_test", &format!("{}.{}", t, payload));
+        let headers = serde_json::json!({"Stripe-Signature": format!("t={},v1={}", t, sig)});
+
+        let result = HttpVerifySignature::new().execute(inputs("stripe", payload, headers), Some(&runtime(SECRETS)));
+        assert_eq!(result.get("valid"), Some(&serde_json::json!(false)));
+        assert_eq!(result.get("reason"), Some(&serde_json::json!("timestamp outside tolerance")));
+    }
+
+    #[test]
+    fn test_tolerance_required() {
+        let payload = "{}";
+        let t = now() - 3600;
+        let sig = sign("whsec_test", &format!("{}.{}", t, payload));
+        let headers = serde_json::json!({"Stripe-Signature": format!("t={},v1={}", t, sig)});
+        for tolerance in [serde_json::json!(0), serde_json::json!(-5), serde_json::json!("off")] {
+            let mut inputs = inputs("stripe", payload, headers.clone());
+            inputs.insert("tolerance_seconds".to_string(), tolerance);
+            let result = HttpVerifySignature::new().execute(inputs, Some(&runtime(SECRETS)));
+            assert_eq!(result.get("valid"), Some(&serde_json::json!(false)));
+            assert_eq!(
+                result.get("error"),
+                Some(&serde_json::json!("tolerance_seconds must be a whole number of seconds, at least 1"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_github() {
+        let payload = r#"{"action":"opened"}"#;
+        let headers = serde_json::json!({"X-Hub-Signature-256": format!("sha256={}", sign("whsec_test", payload))});
+
+        let result = HttpVerifySignature::new().execute(inputs("github", payload, headers), Some(&runtime(SECRETS)));
+        assert_eq!(result.get("valid"), Some(&serde_json::json!(true)));
+    }
+
+    #[test]
+    fn test_slack_tampered() {
+        let t = now();
+        let sig = sign("whsec_test", &format!("v0:{}:{}", t, "token=abc"));
+        let headers = serde_json::json!({
+            "X-Slack-Signature": format!("v0={}", sig),
+            "X-Slack-Request-Timestamp": t.to_string(),
+        });
+
+        let result = HttpVerifySignature::new().execute(inputs("slack", "token=xyz", headers), Some(&runtime(SECRETS)));
+        assert_eq!(result.get("valid"), Some(&serde_json::json!(false)));
+        assert_eq!(result.get("reason"), Some(&serde_json::json!("signature mismatch")));
+    }
+
+    #[test]
+    fn test_missing_header() {
+        let result = HttpVerifySignature::new().execute(inputs("github", "{}", serde_json::json!({})), Some(&runtime(SECRETS)));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("missing X-Hub-Signature-256 header")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "http.verify_signature");
+        assert_eq!(executor.category, "http");
+    }
+}
@@ -1,7 +1,14 @@
 //! Workflow plugin: concatenate lists.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 use serde_json::Value;
-use std::any::Any;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -1,7 +1,14 @@
 //! Workflow plugin: sort a list.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 use serde_json::Value;
-use std::any::Any;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -47,18 +54,18 @@ impl NodeExecutor for ListSort {
                 (Value::Number(n1), Value::Number(n2)) => {
                     let f1 = n1.as_f64().unwrap_or(0.0);
                     let f2 = n2.as_f64().unwrap_or(0.0);
-                    f1.partial_cmp(&f2).unwrap_or(std::cmp::Ordering::Equal)
+                    f1.partial_cmp(&f2).unwrap_or(core::cmp::Ordering::Equal)
                 }
                 // Strings
                 (Value::String(s1), Value::String(s2)) => s1.cmp(s2),
                 // Booleans (false < true)
                 (Value::Bool(b1), Value::Bool(b2)) => b1.cmp(b2),
                 // Null is smallest
-                (Value::Null, Value::Null) => std::cmp::Ordering::Equal,
-                (Value::Null, _) => std::cmp::Ordering::Less,
-                (_, Value::Null) => std::cmp::Ordering::Greater,
+                (Value::Null, Value::Null) => core::cmp::Ordering::Equal,
+                (Value::Null, _) => core::cmp::Ordering::Less,
+                (_, Value::Null) => core::cmp::Ordering::Greater,
                 // Mixed types: compare by type name as fallback
-                _ => std::cmp::Ordering::Equal,
+                _ => core::cmp::Ordering::Equal,
             }
         });
 
@@ -0,0 +1,8 @@
+//! Factory for MailImapFetch plugin.
+
+use super::MailImapFetch;
+
+/// Creates a new MailImapFetch instance.
+pub fn create() -> MailImapFetch {
+    MailImapFetch::new()
+}
@@ -0,0 +1,176 @@
+//! Minimal IMAP4rev1 client covering search, fetch, flagging, and moves.
+
+use metabuilder_core::net::{self, Stream};
+use native_tls::TlsConnector;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::time::Duration;
+
+/// One untagged server response: its text with literals replaced by
+/// placeholders, plus the literal payloads in order.
+pub struct Untagged {
+    pub text: String,
+    pub literals: Vec<Vec<u8>>,
+}
+
+/// An IMAP session.
+pub struct ImapClient {
+    stream: BufReader<Stream>,
+    next_tag: u32,
+}
+
+impl ImapClient {
+    /// Connects using implicit TLS (port 993) when `secure` is set.
+    pub fn connect(host: &str, port: u16, secure: bool, timeout: Duration) -> Result<Self, String> {
+        let tcp = net::connect(host, port, timeout)?;
+        let stream = if secure {
+            let connector = TlsConnector::new().map_err(|e| e.to_string())?;
+            Stream::Tls(Box::new(connector.connect(host, tcp).map_err(|e| e.to_string())?))
+        } else {
+            Stream::Plain(tcp)
+        };
+
+        let mut client = ImapClient {
+            stream: BufReader::new(stream),
+            next_tag: 1,
+        };
+        let (greeting, _) = client.read_line()?;
+        if !greeting.starts_with("* OK") && !greeting.starts_with("* PREAUTH") {
+            return Err(format!("unexpected IMAP greeting: {}", greeting));
+        }
+        Ok(client)
+    }
+
+    /// Authenticates with a plain LOGIN.
+    pub fn login(&mut self, username: &str, password: &str) -> Result<(), String> {
+        self.command(&format!("LOGIN {} {}", quote(username), quote(password)))
+            .map(|_| ())
+    }
+
+    /// Opens `mailbox` read-write.
+    pub fn select(&mut self, mailbox: &str) -> Result<(), String> {
+        self.command(&format!("SELECT {}", quote(mailbox))).map(|_| ())
+    }
+
+    /// Returns the UIDs matching `criteria` (e.g. `UNSEEN FROM "billing@"`).
+    pub fn uid_search(&mut self, criteria: &str) -> Result<Vec<u32>, String> {
+        let responses = self.command(&format!("UID SEARCH {}", criteria))?;
+        Ok(responses
+            .iter()
+            .filter_map(|r| r.text.strip_prefix("* SEARCH"))
+            .flat_map(|ids| ids.split_whitespace().filter_map(|id| id.parse().ok()))
+            .collect())
+    }
+
+    /// Fetches the full RFC 822 message without setting `\Seen`.
+    pub fn uid_fetch(&mut self, uid: u32) -> Result<Vec<u8>, String> {
+        let responses = self.command(&format!("UID FETCH {} BODY.PEEK[]", uid))?;
+        responses
+            .into_iter()
+            .find(|r| r.text.contains("FETCH"))
+            .and_then(|r| r.literals.into_iter().next())
+            .ok_or_else(|| format!("message {} not returned by server", uid))
+    }
+
+    /// Adds the `\Seen` flag to the given messages.
+    pub fn uid_mark_seen(&mut self, uids: &[u32]) -> Result<(), String> {
+        self.command(&format!("UID STORE {} +FLAGS.SILENT (\\Seen)", uid_set(uids)))
+            .map(|_| ())
+    }
+
+    /// Moves messages, falling back to COPY + `\Deleted` + EXPUNGE on servers without MOVE.
+    pub fn uid_move(&mut self, uids: &[u32], mailbox: &str) -> Result<(), String> {
+        let set = uid_set(uids);
+        if self.command(&format!("UID MOVE {} {}", set, quote(mailbox))).is_ok() {
+            return Ok(());
+        }
+        self.command(&format!("UID COPY {} {}", set, quote(mailbox)))?;
+        self.command(&format!("UID STORE {} +FLAGS.SILENT (\\Deleted)", set))?;
+        self.command("EXPUNGE").map(|_| ())
+    }
+
+    /// Ends the session. Errors are ignored since the work already completed.
+    pub fn logout(mut self) {
+        let _ = self.command("LOGOUT");
+    }
+
+    /// Sends a tagged command and collects untagged responses until its completion.
+    fn command(&mut self, cmd: &str) -> Result<Vec<Untagged>, String> {
+        let tag = format!("A{:04}", self.next_tag);
+        self.next_tag += 1;
+
+        let stream = self.stream.get_mut();
+        stream
+            .write_all(format!("{} {}\r\n", tag, cmd).as_bytes())
+            .and_then(|_| stream.flush())
+            .map_err(|e| e.to_string())?;
+
+        let mut responses = Vec::new();
+        loop {
+            let (text, literals) = self.read_line()?;
+            if let Some(status) = text.strip_prefix(&tag) {
+                let status = status.trim_start();
+                return if status.starts_with("OK") {
+                    Ok(responses)
+                } else {
+                    Err(format!("IMAP {} failed: {}", cmd.split(' ').next().unwrap_or(cmd), status))
+                };
+            }
+            responses.push(Untagged { text, literals });
+        }
+    }
+
+    /// Reads one logical response line, consuming any `{n}` literals it announces.
+    fn read_line(&mut self) -> Result<(String, Vec<Vec<u8>>), String> {
+        let mut text = String::new();
+        let mut literals = Vec::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+                return Err("connection closed by server".to_string());
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            match literal_size(line) {
+                Some(size) => {
+                    let mut literal = vec![0; size];
+                    self.stream.read_exact(&mut literal).map_err(|e| e.to_string())?;
+                    text.push_str(line);
+                    literals.push(literal);
+                }
+                None => {
+                    text.push_str(line);
+                    return Ok((text, literals));
+                }
+            }
+        }
+    }
+}
+
+/// Parses the trailing `{123}` literal marker of a response line.
+fn literal_size(line: &str) -> Option<usize> {
+    let open = line.rfind('{')?;
+    line[open + 1..].strip_suffix('}')?.parse().ok()
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn uid_set(uids: &[u32]) -> String {
+    uids.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_size() {
+        assert_eq!(literal_size("* 1 FETCH (UID 7 BODY[] {342}"), Some(342));
+        assert_eq!(literal_size("* 1 FETCH (FLAGS (\\Seen))"), None);
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+}
@@ -0,0 +1,267 @@
+//! Workflow plugin: fetch messages from an IMAP mailbox.
+
+mod imap;
+
+use imap::ImapClient;
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// MailImapFetch implements the NodeExecutor trait for mailbox polling.
+pub struct MailImapFetch {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl MailImapFetch {
+    /// Creates a new MailImapFetch instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "mail.imap_fetch",
+            category: "mail",
+            description: "Fetch messages matching search criteria from an IMAP mailbox",
+        }
+    }
+}
+
+impl Default for MailImapFetch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the unfolded value of the first `name` header, if present.
+fn header(raw: &str, name: &str) -> Option<String> {
+    let head = raw.split("\r\n\r\n").next().unwrap_or(raw);
+    let mut value: Option<String> = None;
+    for line in head.lines() {
+        let line = line.trim_end_matches('\r');
+        match &mut value {
+            Some(v) if line.starts_with([' ', '\t']) => {
+                v.push(' ');
+                v.push_str(line.trim());
+            }
+            Some(_) => break,
+            None => {
+                if let Some((key, rest)) = line.split_once(':') {
+                    if key.eq_ignore_ascii_case(name) {
+                        value = Some(rest.trim().to_string());
+                    }
+                }
+            }
+        }
+    }
+    value
+}
+
+fn fetch(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Vec<Value>, String> {
+    let host: String = inputs
+        .get("host")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("host is required")?;
+    let secure: bool = inputs
+        .get("secure")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(true);
+    let port: u16 = inputs
+        .get("port")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(if secure { 993 } else { 143 });
+    let username: String = inputs
+        .get("username")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("username is required")?;
+    let password_secret: String = inputs
+        .get("password_secret")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("password_secret is required")?;
+    let mailbox: String = inputs
+        .get("mailbox")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| "INBOX".to_string());
+    let criteria: String = inputs
+        .get("criteria")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| "UNSEEN".to_string());
+    let limit: Option<usize> = inputs
+        .get("limit")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+    let mark_read: bool = inputs
+        .get("mark_read")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(true);
+    let move_to: Option<String> = inputs
+        .get("move_to")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+    let timeout_ms: u64 = inputs
+        .get("timeout_ms")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(30_000);
+
+    let password = Runtime::from_any(runtime)
+        .ok_or("runtime with secrets provider is required")?
+        .secret(&password_secret)
+        .ok_or_else(|| format!("secret not found: {}", password_secret))?;
+
+    let mut client = ImapClient::connect(&host, port, secure, Duration::from_millis(timeout_ms))?;
+    client.login(&username, &password)?;
+    client.select(&mailbox)?;
+
+    let mut uids = client.uid_search(&criteria)?;
+    if let Some(limit) = limit {
+        uids.truncate(limit);
+    }
+
+    let mut messages = Vec::with_capacity(uids.len());
+    for &uid in &uids {
+        let raw = String::from_utf8_lossy(&client.uid_fetch(uid)?).into_owned();
+        messages.push(serde_json::json!({
+            "uid": uid,
+            "subject": header(&raw, "Subject"),
+            "from": header(&raw, "From"),
+            "to": header(&raw, "To"),
+            "date": header(&raw, "Date"),
+            "message_id": header(&raw, "Message-ID"),
+            "size": raw.len(),
+            "raw": raw,
+        }));
+    }
+
+    if !uids.is_empty() {
+        if mark_read {
+            client.uid_mark_seen(&uids)?;
+        }
+        if let Some(dest) = &move_to {
+            client.uid_move(&uids, dest)?;
+        }
+    }
+    client.logout();
+    Ok(messages)
+}
+
+impl NodeExecutor for MailImapFetch {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match fetch(&inputs, runtime) {
+            Ok(messages) => {
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert("count".to_string(), serde_json::json!(messages.len()));
+                output.insert("messages".to_string(), Value::Array(messages));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("messages".to_string(), serde_json::json!([]));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new MailImapFetch instance.
+pub fn create() -> MailImapFetch {
+    MailImapFetch::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::MemorySecrets;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    const MESSAGE: &str = "From: alice@example.com\r\nSubject: Invoice\r\n March\r\n\r\nSee attached.\r\n";
+
+    /// Serves one plaintext IMAP session with a single unseen message (UID 7).
+    fn fake_server() -> (u16, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            writer.write_all(b"* OK ready\r\n").unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let (tag, cmd) = line.trim_end().split_once(' ').unwrap();
+                tx.send(cmd.to_string()).unwrap();
+                let untagged = if cmd.starts_with("UID SEARCH") {
+                    "* SEARCH 7\r\n".to_string()
+                } else if cmd.starts_with("UID FETCH") {
+                    format!("* 1 FETCH (UID 7 BODY[] {{{}}}\r\n{})\r\n", MESSAGE.len(), MESSAGE)
+                } else {
+                    String::new()
+                };
+                writer.write_all(format!("{}{} OK done\r\n", untagged, tag).as_bytes()).unwrap();
+                line.clear();
+            }
+        });
+        (port, rx)
+    }
+
+    #[test]
+    fn test_imap_fetch() {
+        let (port, commands) = fake_server();
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("imap_password", "hunter2");
+        let runtime = Runtime::with_secrets(secrets);
+
+        let executor = MailImapFetch::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("127.0.0.1"));
+        inputs.insert("port".to_string(), serde_json::json!(port));
+        inputs.insert("secure".to_string(), serde_json::json!(false));
+        inputs.insert("username".to_string(), serde_json::json!("ops"));
+        inputs.insert("password_secret".to_string(), serde_json::json!("imap_password"));
+        inputs.insert("move_to".to_string(), serde_json::json!("Processed"));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("success"), Some(&serde_json::json!(true)));
+        assert_eq!(result.get("count"), Some(&serde_json::json!(1)));
+
+        let message = &result.get("messages").unwrap()[0];
+        assert_eq!(message["uid"], serde_json::json!(7));
+        assert_eq!(message["subject"], serde_json::json!("Invoice March"));
+        assert_eq!(message["raw"], serde_json::json!(MESSAGE));
+
+        let sent: Vec<String> = commands.try_iter().collect();
+        assert_eq!(sent[0], "LOGIN \"ops\" \"hunter2\"");
+        assert!(sent.contains(&"UID STORE 7 +FLAGS.SILENT (\\Seen)".to_string()));
+        assert!(sent.contains(&"UID MOVE 7 \"Processed\"".to_string()));
+    }
+
+    #[test]
+    fn test_missing_secret() {
+        let executor = MailImapFetch::new();
+        let runtime = Runtime::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("127.0.0.1"));
+        inputs.insert("username".to_string(), serde_json::json!("ops"));
+        inputs.insert("password_secret".to_string(), serde_json::json!("imap_password"));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("secret not found: imap_password")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "mail.imap_fetch");
+        assert_eq!(executor.category, "mail");
+    }
+}
@@ -1,7 +1,14 @@
 //! Workflow plugin: add numbers.
 
-use serde_json::Value;
-use std::any::Any;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use serde_json::{Number, Value};
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -34,17 +41,28 @@ impl Default for MathAdd {
     }
 }
 
+/// Sums the inputs, keeping i64 precision when every input is an integer
+/// and falling back to f64 on fractional inputs or overflow.
+fn sum(numbers: &[Number]) -> Value {
+    let exact = numbers
+        .iter()
+        .map(Number::as_i64)
+        .try_fold(0i64, |acc, n| acc.checked_add(n?));
+    match exact {
+        Some(total) => serde_json::json!(total),
+        None => serde_json::json!(numbers.iter().filter_map(Number::as_f64).sum::<f64>()),
+    }
+}
+
 impl NodeExecutor for MathAdd {
     fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
-        let numbers: Vec<f64> = inputs
+        let numbers: Vec<Number> = inputs
             .get("numbers")
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or_default();
 
-        let sum: f64 = numbers.iter().sum();
-
         let mut result = HashMap::new();
-        result.insert("result".to_string(), serde_json::json!(sum));
+        result.insert("result".to_string(), sum(&numbers));
         result
     }
 }
@@ -68,6 +86,36 @@ mod tests {
         assert_eq!(result.get("result"), Some(&serde_json::json!(6.0)));
     }
 
+    #[test]
+    fn test_add_integers() {
+        let executor = MathAdd::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([9007199254740993i64, 0, 1]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(9007199254740994i64)));
+    }
+
+    #[test]
+    fn test_add_mixed_falls_back_to_float() {
+        let executor = MathAdd::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([1, 2.5]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(3.5)));
+    }
+
+    #[test]
+    fn test_add_overflow_falls_back_to_float() {
+        let executor = MathAdd::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([i64::MAX, 1]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(i64::MAX as f64 + 1.0)));
+    }
+
     #[test]
     fn test_factory() {
         let executor = create();
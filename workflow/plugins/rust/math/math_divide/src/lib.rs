@@ -1,7 +1,14 @@
 //! Workflow plugin: divide numbers.
 
-use serde_json::Value;
-use std::any::Any;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use serde_json::{Number, Value};
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -34,9 +41,31 @@ impl Default for MathDivide {
     }
 }
 
+/// Divides the first input by the rest. The result stays an i64 only when
+/// every input is an integer and each step divides exactly; otherwise f64.
+fn quotient(numbers: &[Number]) -> Value {
+    let exact = numbers[0].as_i64().and_then(|first| {
+        numbers[1..].iter().map(Number::as_i64).try_fold(first, |acc, n| {
+            let n = n?;
+            if acc.checked_rem(n)? == 0 {
+                acc.checked_div(n)
+            } else {
+                None
+            }
+        })
+    });
+    match exact {
+        Some(total) => serde_json::json!(total),
+        None => {
+            let floats: Vec<f64> = numbers.iter().filter_map(Number::as_f64).collect();
+            serde_json::json!(floats.iter().skip(1).fold(floats[0], |acc, x| acc / x))
+        }
+    }
+}
+
 impl NodeExecutor for MathDivide {
     fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
-        let numbers: Vec<f64> = inputs
+        let numbers: Vec<Number> = inputs
             .get("numbers")
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or_default();
@@ -49,16 +78,15 @@ impl NodeExecutor for MathDivide {
             return result;
         }
 
-        for &n in &numbers[1..] {
-            if n == 0.0 {
+        for n in &numbers[1..] {
+            if n.as_f64() == Some(0.0) {
                 result.insert("result".to_string(), serde_json::json!(0));
                 result.insert("error".to_string(), serde_json::json!("division by zero"));
                 return result;
             }
         }
 
-        let quotient = numbers.iter().skip(1).fold(numbers[0], |acc, x| acc / x);
-        result.insert("result".to_string(), serde_json::json!(quotient));
+        result.insert("result".to_string(), quotient(&numbers));
         result
     }
 }
@@ -82,6 +110,26 @@ mod tests {
         assert_eq!(result.get("result"), Some(&serde_json::json!(4.0)));
     }
 
+    #[test]
+    fn test_divide_integers() {
+        let executor = MathDivide::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([24, 3, 2]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(4)));
+    }
+
+    #[test]
+    fn test_divide_inexact_falls_back_to_float() {
+        let executor = MathDivide::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([7, 2]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(3.5)));
+    }
+
     #[test]
     fn test_factory() {
         let executor = create();
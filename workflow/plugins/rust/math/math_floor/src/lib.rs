@@ -1,7 +1,14 @@
 //! Workflow plugin: floor a number.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 use serde_json::Value;
-use std::any::Any;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -42,7 +49,7 @@ impl NodeExecutor for MathFloor {
             .unwrap_or(0.0);
 
         let mut result = HashMap::new();
-        result.insert("result".to_string(), serde_json::json!(value.floor()));
+        result.insert("result".to_string(), serde_json::json!(libm::floor(value)));
         result
     }
 }
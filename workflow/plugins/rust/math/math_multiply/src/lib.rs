@@ -1,7 +1,14 @@
 //! Workflow plugin: multiply numbers.
 
-use serde_json::Value;
-use std::any::Any;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use serde_json::{Number, Value};
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -34,9 +41,22 @@ impl Default for MathMultiply {
     }
 }
 
+/// Multiplies the inputs, keeping i64 precision when every input is an
+/// integer and falling back to f64 on fractional inputs or overflow.
+fn product(numbers: &[Number]) -> Value {
+    let exact = numbers
+        .iter()
+        .map(Number::as_i64)
+        .try_fold(1i64, |acc, n| acc.checked_mul(n?));
+    match exact {
+        Some(total) => serde_json::json!(total),
+        None => serde_json::json!(numbers.iter().filter_map(Number::as_f64).product::<f64>()),
+    }
+}
+
 impl NodeExecutor for MathMultiply {
     fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
-        let numbers: Vec<f64> = inputs
+        let numbers: Vec<Number> = inputs
             .get("numbers")
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or_default();
@@ -48,8 +68,7 @@ impl NodeExecutor for MathMultiply {
             return result;
         }
 
-        let product: f64 = numbers.iter().product();
-        result.insert("result".to_string(), serde_json::json!(product));
+        result.insert("result".to_string(), product(&numbers));
         result
     }
 }
@@ -73,6 +92,26 @@ mod tests {
         assert_eq!(result.get("result"), Some(&serde_json::json!(24.0)));
     }
 
+    #[test]
+    fn test_multiply_integers() {
+        let executor = MathMultiply::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([3, 4]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(12)));
+    }
+
+    #[test]
+    fn test_multiply_overflow_falls_back_to_float() {
+        let executor = MathMultiply::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([i64::MAX, 2]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(i64::MAX as f64 * 2.0)));
+    }
+
     #[test]
     fn test_factory() {
         let executor = create();
@@ -1,7 +1,14 @@
 //! Workflow plugin: power operation.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 use serde_json::Value;
-use std::any::Any;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -34,6 +41,17 @@ impl Default for MathPower {
     }
 }
 
+#[cfg(feature = "std")]
+fn pow(base: f64, exponent: f64) -> f64 {
+    base.powf(exponent)
+}
+
+/// `f64::powf` needs `std`; without it, `libm` computes the power.
+#[cfg(not(feature = "std"))]
+fn pow(base: f64, exponent: f64) -> f64 {
+    libm::pow(base, exponent)
+}
+
 impl NodeExecutor for MathPower {
     fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
         let base: f64 = inputs
@@ -46,7 +64,7 @@ impl NodeExecutor for MathPower {
             .unwrap_or(1.0);
 
         let mut result = HashMap::new();
-        result.insert("result".to_string(), serde_json::json!(base.powf(exp)));
+        result.insert("result".to_string(), serde_json::json!(pow(base, exp)));
         result
     }
 }
@@ -1,7 +1,14 @@
 //! Workflow plugin: round a number.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 use serde_json::Value;
-use std::any::Any;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -34,6 +41,17 @@ impl Default for MathRound {
     }
 }
 
+#[cfg(feature = "std")]
+fn power_of_ten(exponent: i32) -> f64 {
+    10_f64.powi(exponent)
+}
+
+/// `f64::powi` needs `std`; without it, `libm` computes the power.
+#[cfg(not(feature = "std"))]
+fn power_of_ten(exponent: i32) -> f64 {
+    libm::pow(10.0, exponent as f64)
+}
+
 impl NodeExecutor for MathRound {
     fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
         let value: f64 = inputs
@@ -45,8 +63,8 @@ impl NodeExecutor for MathRound {
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or(0);
 
-        let factor = 10_f64.powi(decimals);
-        let rounded = (value * factor).round() / factor;
+        let factor = power_of_ten(decimals);
+        let rounded = libm::round(value * factor) / factor;
 
         let mut result = HashMap::new();
         result.insert("result".to_string(), serde_json::json!(rounded));
@@ -67,11 +85,11 @@ mod tests {
     fn test_round() {
         let executor = MathRound::new();
         let mut inputs = HashMap::new();
-        inputs.insert("value".to_string(), serde_json::json!(3.14159));
+        inputs.insert("value".to_string(), serde_json::json!(1.23456));
         inputs.insert("decimals".to_string(), serde_json::json!(2));
 
         let result = executor.execute(inputs, None);
-        assert_eq!(result.get("result"), Some(&serde_json::json!(3.14)));
+        assert_eq!(result.get("result"), Some(&serde_json::json!(1.23)));
     }
 
     #[test]
@@ -1,7 +1,14 @@
 //! Workflow plugin: subtract numbers.
 
-use serde_json::Value;
-use std::any::Any;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use serde_json::{Number, Value};
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -34,9 +41,27 @@ impl Default for MathSubtract {
     }
 }
 
+/// Subtracts the remaining inputs from the first, keeping i64 precision when
+/// every input is an integer and falling back to f64 on fractional inputs or overflow.
+fn difference(numbers: &[Number]) -> Value {
+    let exact = numbers[0].as_i64().and_then(|first| {
+        numbers[1..]
+            .iter()
+            .map(Number::as_i64)
+            .try_fold(first, |acc, n| acc.checked_sub(n?))
+    });
+    match exact {
+        Some(total) => serde_json::json!(total),
+        None => {
+            let floats: Vec<f64> = numbers.iter().filter_map(Number::as_f64).collect();
+            serde_json::json!(floats.iter().skip(1).fold(floats[0], |acc, x| acc - x))
+        }
+    }
+}
+
 impl NodeExecutor for MathSubtract {
     fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
-        let numbers: Vec<f64> = inputs
+        let numbers: Vec<Number> = inputs
             .get("numbers")
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or_default();
@@ -49,8 +74,7 @@ impl NodeExecutor for MathSubtract {
             return result;
         }
 
-        let difference = numbers.iter().skip(1).fold(numbers[0], |acc, x| acc - x);
-        result.insert("result".to_string(), serde_json::json!(difference));
+        result.insert("result".to_string(), difference(&numbers));
         result
     }
 }
@@ -74,6 +98,16 @@ mod tests {
         assert_eq!(result.get("result"), Some(&serde_json::json!(5.0)));
     }
 
+    #[test]
+    fn test_subtract_integers() {
+        let executor = MathSubtract::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("numbers".to_string(), serde_json::json!([9007199254740995i64, 2]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!(9007199254740993i64)));
+    }
+
     #[test]
     fn test_factory() {
         let executor = create();
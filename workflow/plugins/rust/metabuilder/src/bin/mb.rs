@@ -0,0 +1,146 @@
+//! `mb`: runs workflow specs from the command line.
+//!
+//! `mb run workflow.yaml --inputs inputs.json` runs a spec (JSON, or YAML by
+//! extension) with the vars in `inputs.json` and prints the outputs of every
+//! node that ran as JSON. `--only node_id`, repeated for a subgraph, runs
+//! just those nodes through `Engine::run_only`; the outputs of the nodes they
+//! read come from `--upstream upstream.json`, an object of node id to output
+//! ports. Secrets are read from `METABUILDER_SECRET_*` environment variables.
+
+use clap::{Parser, Subcommand};
+use metabuilder::metabuilder_core::EnvSecrets;
+use metabuilder::{Engine, Workflow};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "mb", version, about = "Run MetaBuilder workflow specs")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a spec and print the outputs of the nodes that ran
+    Run {
+        /// The spec file, JSON or (with a .yaml or .yml extension) YAML
+        spec: PathBuf,
+        /// Run only this node; repeat to run a subgraph
+        #[arg(long, value_name = "NODE_ID")]
+        only: Vec<String>,
+        /// A JSON object of the run's vars
+        #[arg(long, value_name = "FILE")]
+        inputs: Option<PathBuf>,
+        /// A JSON object of node id to output ports, for the nodes outside
+        /// --only that the selection reads
+        #[arg(long, value_name = "FILE")]
+        upstream: Option<PathBuf>,
+        /// The spec profile to apply
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
+/// Reads `path` as JSON, or an empty object without a path.
+fn read_json<T: serde::de::DeserializeOwned + Default>(path: Option<&Path>) -> Result<T, String> {
+    let Some(path) = path else {
+        return Ok(T::default());
+    };
+    let text = std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    serde_json::from_str(&text).map_err(|e| format!("invalid {}: {}", path.display(), e))
+}
+
+/// Runs `command` and returns the JSON to print.
+fn run(command: Command) -> Result<String, String> {
+    let Command::Run {
+        spec,
+        only,
+        inputs,
+        upstream,
+        profile,
+    } = command;
+    let secrets = EnvSecrets::new();
+    let workflow = Workflow::from_path_with_secrets(&spec, &secrets).map_err(|e| e.to_string())?;
+    let vars: HashMap<String, Value> = read_json(inputs.as_deref())?;
+    let mut engine = Engine::default().with_secrets(secrets);
+    if let Some(profile) = profile {
+        engine = engine.with_profile(profile);
+    }
+
+    let result = if only.is_empty() {
+        if upstream.is_some() {
+            return Err("--upstream needs --only".to_string());
+        }
+        engine.run(&workflow, vars)
+    } else {
+        let upstream = read_json(upstream.as_deref())?;
+        let selected: Vec<&str> = only.iter().map(String::as_str).collect();
+        engine.run_only(&workflow, &selected, upstream, vars)
+    }
+    .map_err(|e| e.to_string())?;
+    let outputs: BTreeMap<_, BTreeMap<_, _>> =
+        result.outputs.into_iter().map(|(id, ports)| (id, ports.into_iter().collect())).collect();
+    Ok(serde_json::to_string_pretty(&outputs).expect("JSON values are always serializable"))
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(outputs) => {
+            println!("{}", outputs);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("mb: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_run_only() {
+        let dir = std::env::temp_dir().join(format!("metabuilder-mb-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("spec.yaml"),
+            concat!(
+                "nodes:\n",
+                "  - {id: sum, type: math.add, params: {numbers: $vars.numbers}}\n",
+                "  - {id: round, type: math.round}\n",
+                "edges:\n",
+                "  - {from: sum, from_port: result, to: round, to_port: value}\n",
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.join("inputs.json"), r#"{"numbers": [1.25, 2.5]}"#).unwrap();
+        std::fs::write(dir.join("upstream.json"), r#"{"sum": {"result": 7.6}}"#).unwrap();
+        let mb = |args: &[&str]| {
+            let args = ["mb", "run"].iter().copied().chain(args.iter().copied());
+            let output = run(Cli::try_parse_from(args).unwrap().command)?;
+            Ok::<Value, String>(serde_json::from_str(&output).unwrap())
+        };
+        let spec = dir.join("spec.yaml");
+        let spec = spec.to_str().unwrap();
+        let inputs = dir.join("inputs.json");
+        let upstream = dir.join("upstream.json");
+
+        let all = mb(&[spec, "--inputs", inputs.to_str().unwrap()]).unwrap();
+        assert_eq!(all["sum"]["result"], json!(3.75));
+        assert_eq!(all["round"]["result"], json!(4.0));
+
+        let only = mb(&[spec, "--only", "round", "--upstream", upstream.to_str().unwrap()]).unwrap();
+        assert_eq!(only, json!({"round": {"result": 8.0}}));
+        assert_eq!(
+            mb(&[spec, "--only", "round"]).unwrap_err(),
+            "Cannot run only round: needs the outputs of sum"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,16 @@
+//! MetaBuilder facade crate.
+//!
+//! Re-exports the workflow engine and the shared runtime types, with the
+//! bundled node categories chosen by cargo features. The default build
+//! includes every category; an embedder that only needs a few builds with
+//! `default-features = false` and names them:
+//!
+//! ```toml
+//! metabuilder = { version = "0.1", default-features = false, features = ["math", "string", "list"] }
+//! ```
+//!
+//! `Registry::with_builtins` then registers just those categories, and
+//! workflows using other node types fail validation with an unknown type.
+
+pub use metabuilder_core;
+pub use workflow::*;
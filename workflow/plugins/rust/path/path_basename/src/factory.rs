@@ -0,0 +1,8 @@
+//! Factory for PathBasename plugin.
+
+use super::PathBasename;
+
+/// Creates a new PathBasename instance.
+pub fn create() -> PathBasename {
+    PathBasename::new()
+}
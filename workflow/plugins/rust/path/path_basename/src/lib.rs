@@ -0,0 +1,97 @@
+//! Workflow plugin: get the last component of a path.
+//!
+//! Returns the last component of `path`, or `""` for a root. With
+//! `strip_extension`, its last extension is dropped, as in the `stem` of
+//! `path.split`.
+
+use metabuilder_core::paths;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// PathBasename implements the NodeExecutor trait for path base names.
+pub struct PathBasename {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl PathBasename {
+    /// Creates a new PathBasename instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "path.basename",
+            category: "path",
+            description: "Get the last component of a path",
+        }
+    }
+}
+
+impl Default for PathBasename {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn basename(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let name = paths::file_name(path);
+    match inputs.get("strip_extension").and_then(Value::as_bool).unwrap_or(false) {
+        true => Ok(paths::split_extension(&name).0.to_string()),
+        false => Ok(name),
+    }
+}
+
+impl NodeExecutor for PathBasename {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match basename(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new PathBasename instance.
+pub fn create() -> PathBasename {
+    PathBasename::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_basename() {
+        let executor = PathBasename::new();
+        assert_eq!(executor.execute(inputs(json!({"path": "C:\\reports\\q1.tar.gz"})), None)["result"], "q1.tar.gz");
+        assert_eq!(executor.execute(inputs(json!({"path": "/var/log/"})), None)["result"], "log");
+        assert_eq!(executor.execute(inputs(json!({"path": "/"})), None)["result"], "");
+        let output = executor.execute(inputs(json!({"path": "out/q1.tar.gz", "strip_extension": true})), None);
+        assert_eq!(output["result"], "q1.tar");
+        assert_eq!(executor.execute(inputs(json!({})), None)["error"], "path is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "path.basename");
+        assert_eq!(executor.category, "path");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for PathDirname plugin.
+
+use super::PathDirname;
+
+/// Creates a new PathDirname instance.
+pub fn create() -> PathDirname {
+    PathDirname::new()
+}
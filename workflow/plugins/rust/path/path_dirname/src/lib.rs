@@ -0,0 +1,87 @@
+//! Workflow plugin: get the directory part of a path.
+//!
+//! Returns `path` without its last component, with `/` separators: `"."`
+//! for a bare name, and the root itself (`"/"` or a drive such as `"C:/"`)
+//! for a root.
+
+use metabuilder_core::paths;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// PathDirname implements the NodeExecutor trait for path directories.
+pub struct PathDirname {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl PathDirname {
+    /// Creates a new PathDirname instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "path.dirname",
+            category: "path",
+            description: "Get the directory part of a path",
+        }
+    }
+}
+
+impl Default for PathDirname {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for PathDirname {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match inputs.get("path").and_then(Value::as_str) {
+            Some(path) => {
+                output.insert("result".to_string(), serde_json::json!(paths::dir_name(path)));
+            }
+            None => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!("path is required"));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new PathDirname instance.
+pub fn create() -> PathDirname {
+    PathDirname::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_dirname() {
+        let executor = PathDirname::new();
+        assert_eq!(executor.execute(inputs(json!({"path": "C:\\reports\\q1.csv"})), None)["result"], "C:/reports");
+        assert_eq!(executor.execute(inputs(json!({"path": "/var/log/"})), None)["result"], "/var");
+        assert_eq!(executor.execute(inputs(json!({"path": "notes.txt"})), None)["result"], ".");
+        assert_eq!(executor.execute(inputs(json!({"path": "/"})), None)["result"], "/");
+        assert_eq!(executor.execute(inputs(json!({})), None)["error"], "path is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "path.dirname");
+        assert_eq!(executor.category, "path");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for PathExtension plugin.
+
+use super::PathExtension;
+
+/// Creates a new PathExtension instance.
+pub fn create() -> PathExtension {
+    PathExtension::new()
+}
@@ -0,0 +1,118 @@
+//! Workflow plugin: read or replace a file extension.
+//!
+//! Returns the extension of `path` without its dot (`""` if there is none).
+//! If `replace` is given, returns `path` with its extension replaced instead;
+//! an empty `replace` removes the extension. Leading dots, as in `.bashrc`,
+//! do not start an extension.
+
+use metabuilder_core::paths;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// PathExtension implements the NodeExecutor trait for file extensions.
+pub struct PathExtension {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl PathExtension {
+    /// Creates a new PathExtension instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "path.extension",
+            category: "path",
+            description: "Read or replace the extension of a path",
+        }
+    }
+}
+
+impl Default for PathExtension {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn path(inputs: &HashMap<String, Value>) -> Result<&str, String> {
+    inputs.get("path").and_then(Value::as_str).ok_or_else(|| "path is required".to_string())
+}
+
+fn extension(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let path = path(inputs)?;
+    let name = paths::file_name(path);
+    let (stem, extension) = paths::split_extension(&name);
+    let replace = match inputs.get("replace") {
+        None | Some(Value::Null) => return Ok(extension.to_string()),
+        Some(replace) => replace.as_str().ok_or("replace must be a string")?.trim_start_matches('.'),
+    };
+    if name.is_empty() {
+        return Err(format!("path has no file name: {}", path));
+    }
+    let mut parts = paths::parts(path);
+    parts.components.pop();
+    parts.components.push(if replace.is_empty() { stem.to_string() } else { format!("{}.{}", stem, replace) });
+    Ok(paths::format(&parts))
+}
+
+impl NodeExecutor for PathExtension {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match extension(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new PathExtension instance.
+pub fn create() -> PathExtension {
+    PathExtension::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_extension() {
+        let executor = PathExtension::new();
+        assert_eq!(executor.execute(inputs(json!({"path": "out/report.CSV"})), None)["result"], "CSV");
+        assert_eq!(executor.execute(inputs(json!({"path": "out/.env"})), None)["result"], "");
+        assert_eq!(executor.execute(inputs(json!({"path": "Makefile"})), None)["result"], "");
+    }
+
+    #[test]
+    fn test_replace() {
+        let executor = PathExtension::new();
+        let output = executor.execute(inputs(json!({"path": "out\\report.csv", "replace": ".json"})), None);
+        assert_eq!(output["result"], "out/report.json");
+        assert_eq!(executor.execute(inputs(json!({"path": "a.tar.gz", "replace": ""})), None)["result"], "a.tar");
+        assert_eq!(executor.execute(inputs(json!({"path": "README", "replace": "md"})), None)["result"], "README.md");
+        let output = executor.execute(inputs(json!({"path": "/", "replace": "md"})), None);
+        assert_eq!(output["error"], "path has no file name: /");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "path.extension");
+        assert_eq!(executor.category, "path");
+    }
+}
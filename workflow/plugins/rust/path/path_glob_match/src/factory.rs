@@ -0,0 +1,8 @@
+//! Factory for PathGlobMatch plugin.
+
+use super::PathGlobMatch;
+
+/// Creates a new PathGlobMatch instance.
+pub fn create() -> PathGlobMatch {
+    PathGlobMatch::new()
+}
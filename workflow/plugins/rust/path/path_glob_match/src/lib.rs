@@ -0,0 +1,114 @@
+//! Workflow plugin: match paths against a glob pattern.
+//!
+//! Matches `path` against `pattern` and returns a boolean, or, given a list
+//! of `paths`, returns the ones that match. `*` matches within a component,
+//! `?` one character, `[a-z]` or `[!a-z]` a character class, and a `**`
+//! component any number of components. Paths are normalized first and either
+//! separator may be used, so patterns behave the same on every platform.
+
+use metabuilder_core::paths;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// PathGlobMatch implements the NodeExecutor trait for glob matching.
+pub struct PathGlobMatch {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl PathGlobMatch {
+    /// Creates a new PathGlobMatch instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "path.glob_match",
+            category: "path",
+            description: "Match paths against a glob pattern",
+        }
+    }
+}
+
+impl Default for PathGlobMatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn glob_match(inputs: &HashMap<String, Value>) -> Result<Value, String> {
+    let pattern = inputs.get("pattern").and_then(Value::as_str).ok_or("pattern is required")?;
+    if let Some(path) = inputs.get("path").and_then(Value::as_str) {
+        return Ok(Value::Bool(paths::glob_match(pattern, path)));
+    }
+    let list = inputs.get("paths").and_then(Value::as_array).ok_or("path or paths is required")?;
+    let mut matched = Vec::new();
+    for path in list {
+        let path = path.as_str().ok_or("paths must be a list of strings")?;
+        if paths::glob_match(pattern, path) {
+            matched.push(serde_json::json!(path));
+        }
+    }
+    Ok(Value::Array(matched))
+}
+
+impl NodeExecutor for PathGlobMatch {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match glob_match(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), result);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new PathGlobMatch instance.
+pub fn create() -> PathGlobMatch {
+    PathGlobMatch::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_glob_match() {
+        let executor = PathGlobMatch::new();
+        let output = executor.execute(inputs(json!({"pattern": "src/**/*.rs", "path": "src\\a\\mod.rs"})), None);
+        assert_eq!(output["result"], true);
+        assert_eq!(executor.execute(inputs(json!({"pattern": "*.rs", "path": "src/lib.rs"})), None)["result"], false);
+    }
+
+    #[test]
+    fn test_filter_paths() {
+        let executor = PathGlobMatch::new();
+        let output = executor.execute(inputs(json!({
+            "pattern": "logs/*.[lt]og",
+            "paths": ["logs/a.log", "logs/b.txt", "logs/old/c.log", "logs/d.tog"],
+        })), None);
+        assert_eq!(output["result"], json!(["logs/a.log", "logs/d.tog"]));
+        assert_eq!(executor.execute(inputs(json!({"pattern": "*"})), None)["error"], "path or paths is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "path.glob_match");
+        assert_eq!(executor.category, "path");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for PathJoin plugin.
+
+use super::PathJoin;
+
+/// Creates a new PathJoin instance.
+pub fn create() -> PathJoin {
+    PathJoin::new()
+}
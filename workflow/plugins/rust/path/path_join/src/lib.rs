@@ -0,0 +1,103 @@
+//! Workflow plugin: join path segments.
+//!
+//! `parts` is a list of path segments joined with `/`. A segment starting at a
+//! root (`/` or a drive such as `C:`) discards everything before it. Both `/`
+//! and `\` are accepted as separators; the result always uses `/`.
+
+use metabuilder_core::paths;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// PathJoin implements the NodeExecutor trait for path joining.
+pub struct PathJoin {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl PathJoin {
+    /// Creates a new PathJoin instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "path.join",
+            category: "path",
+            description: "Join path segments with forward slashes",
+        }
+    }
+}
+
+impl Default for PathJoin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn join(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let parts = inputs.get("parts").and_then(Value::as_array).ok_or("parts must be a list of strings")?;
+    let parts = parts
+        .iter()
+        .map(|part| part.as_str().ok_or("parts must be a list of strings"))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(paths::join(parts))
+}
+
+impl NodeExecutor for PathJoin {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match join(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new PathJoin instance.
+pub fn create() -> PathJoin {
+    PathJoin::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_join() {
+        let executor = PathJoin::new();
+        let output = executor.execute(inputs(json!({"parts": ["data", "in\\2024", "a.csv"]})), None);
+        assert_eq!(output["result"], "data/in/2024/a.csv");
+        assert_eq!(executor.execute(inputs(json!({"parts": ["data", "/tmp", "b"]})), None)["result"], "/tmp/b");
+        assert_eq!(executor.execute(inputs(json!({"parts": []})), None)["result"], ".");
+    }
+
+    #[test]
+    fn test_invalid_parts() {
+        let executor = PathJoin::new();
+        let output = executor.execute(inputs(json!({"parts": ["a", 1]})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "parts must be a list of strings");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "path.join");
+        assert_eq!(executor.category, "path");
+    }
+}
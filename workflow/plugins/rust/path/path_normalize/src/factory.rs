@@ -0,0 +1,8 @@
+//! Factory for PathNormalize plugin.
+
+use super::PathNormalize;
+
+/// Creates a new PathNormalize instance.
+pub fn create() -> PathNormalize {
+    PathNormalize::new()
+}
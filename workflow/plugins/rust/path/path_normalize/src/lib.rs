@@ -0,0 +1,94 @@
+//! Workflow plugin: normalize a path.
+//!
+//! Converts `\` to `/`, collapses repeated separators, and resolves `.` and
+//! `..` lexically, without touching the filesystem. `..` above the root of an
+//! absolute path is dropped; leading `..` in a relative path is kept.
+
+use metabuilder_core::paths;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// PathNormalize implements the NodeExecutor trait for path normalization.
+pub struct PathNormalize {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl PathNormalize {
+    /// Creates a new PathNormalize instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "path.normalize",
+            category: "path",
+            description: "Normalize separators and resolve . and .. in a path",
+        }
+    }
+}
+
+impl Default for PathNormalize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn path(inputs: &HashMap<String, Value>) -> Result<&str, String> {
+    inputs.get("path").and_then(Value::as_str).ok_or_else(|| "path is required".to_string())
+}
+
+fn normalize(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    Ok(paths::normalize(path(inputs)?))
+}
+
+impl NodeExecutor for PathNormalize {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match normalize(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new PathNormalize instance.
+pub fn create() -> PathNormalize {
+    PathNormalize::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize() {
+        let executor = PathNormalize::new();
+        assert_eq!(executor.execute(inputs(json!({"path": "a\\b\\..\\c//./d/"})), None)["result"], "a/c/d");
+        assert_eq!(executor.execute(inputs(json!({"path": "/../etc"})), None)["result"], "/etc");
+        assert_eq!(executor.execute(inputs(json!({"path": "../../x/.."})), None)["result"], "../..");
+        assert_eq!(executor.execute(inputs(json!({})), None)["error"], "path is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "path.normalize");
+        assert_eq!(executor.category, "path");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for PathSplit plugin.
+
+use super::PathSplit;
+
+/// Creates a new PathSplit instance.
+pub fn create() -> PathSplit {
+    PathSplit::new()
+}
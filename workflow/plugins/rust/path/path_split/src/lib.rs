@@ -0,0 +1,115 @@
+//! Workflow plugin: split a path into its pieces.
+//!
+//! Returns `{root, components, dir, name, stem, extension}` for `path`.
+//! `root` is `""` for relative paths, `"/"`, or a drive such as `"C:/"`;
+//! `dir` is the path without its last component (`"."` if there is none).
+
+use metabuilder_core::paths;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// PathSplit implements the NodeExecutor trait for path splitting.
+pub struct PathSplit {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl PathSplit {
+    /// Creates a new PathSplit instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "path.split",
+            category: "path",
+            description: "Split a path into directory, name, stem, and extension",
+        }
+    }
+}
+
+impl Default for PathSplit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn path(inputs: &HashMap<String, Value>) -> Result<&str, String> {
+    inputs.get("path").and_then(Value::as_str).ok_or_else(|| "path is required".to_string())
+}
+
+fn split(inputs: &HashMap<String, Value>) -> Result<Value, String> {
+    let path = path(inputs)?;
+    let parts = paths::parts(path);
+    let name = paths::file_name(path);
+    let (stem, extension) = paths::split_extension(&name);
+    Ok(serde_json::json!({
+        "root": parts.root,
+        "components": parts.components,
+        "dir": paths::dir_name(path),
+        "name": name,
+        "stem": stem,
+        "extension": extension,
+    }))
+}
+
+impl NodeExecutor for PathSplit {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match split(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), result);
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new PathSplit instance.
+pub fn create() -> PathSplit {
+    PathSplit::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_split() {
+        let executor = PathSplit::new();
+        let output = executor.execute(inputs(json!({"path": "C:\\reports\\q1.tar.gz"})), None);
+        assert_eq!(
+            output["result"],
+            json!({
+                "root": "C:/",
+                "components": ["reports", "q1.tar.gz"],
+                "dir": "C:/reports",
+                "name": "q1.tar.gz",
+                "stem": "q1.tar",
+                "extension": "gz",
+            })
+        );
+        assert_eq!(executor.execute(inputs(json!({"path": "notes"})), None)["result"]["dir"], ".");
+        assert_eq!(executor.execute(inputs(json!({"path": "/"})), None)["result"]["name"], "");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "path.split");
+        assert_eq!(executor.category, "path");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RandomChoice plugin.
+
+use super::RandomChoice;
+
+/// Creates a new RandomChoice instance.
+pub fn create() -> RandomChoice {
+    RandomChoice::new()
+}
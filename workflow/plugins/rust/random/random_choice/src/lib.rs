@@ -0,0 +1,126 @@
+//! Workflow plugin: pick a random item from a list.
+//!
+//! Outputs `result`, an item of `list` chosen uniformly at random, and
+//! `index`, its position. An empty list is an `error`. Draws from the
+//! runtime's generator, so a seeded engine replays the same picks.
+
+use metabuilder_core::{Random, Runtime};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RandomChoice implements the NodeExecutor trait for random picks.
+pub struct RandomChoice {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RandomChoice {
+    /// Creates a new RandomChoice instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "random.choice",
+            category: "random",
+            description: "Pick a random item from a list",
+        }
+    }
+}
+
+impl Default for RandomChoice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn choose(inputs: &HashMap<String, Value>, random: &Random) -> Result<(usize, Value), String> {
+    let list = inputs.get("list").and_then(Value::as_array).ok_or("list is required")?;
+    if list.is_empty() {
+        return Err("list is empty".to_string());
+    }
+    let index = random.below(list.len());
+    Ok((index, list[index].clone()))
+}
+
+impl NodeExecutor for RandomChoice {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let chosen = match Runtime::from_any(runtime) {
+            Some(runtime) => choose(&inputs, &runtime.random),
+            None => choose(&inputs, &Random::new()),
+        };
+        match chosen {
+            Ok((index, item)) => {
+                output.insert("result".to_string(), item);
+                output.insert("index".to_string(), json!(index));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RandomChoice instance.
+pub fn create() -> RandomChoice {
+    RandomChoice::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_choice() {
+        let executor = RandomChoice::new();
+        let list = json!(["a", "b", "c"]);
+        for _ in 0..20 {
+            let output = executor.execute(inputs(json!({"list": list})), None);
+            let index = output["index"].as_u64().unwrap() as usize;
+            assert_eq!(output["result"], list[index]);
+        }
+        assert_eq!(executor.execute(inputs(json!({"list": [7]})), None)["result"], 7);
+    }
+
+    #[test]
+    fn test_seeded_runtime_replays() {
+        let executor = RandomChoice::new();
+        let picks = |seed| {
+            let mut runtime = Runtime::new();
+            runtime.random = Arc::new(Random::seeded(seed));
+            let params = json!({"list": (0..100).collect::<Vec<_>>()});
+            let pick = || executor.execute(inputs(params.clone()), Some(&runtime))["result"].clone();
+            (0..10).map(|_| pick()).collect::<Vec<_>>()
+        };
+        assert_eq!(picks(1), picks(1));
+        assert_ne!(picks(1), picks(2));
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = RandomChoice::new();
+        let output = executor.execute(inputs(json!({"list": []})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "list is empty");
+        assert_eq!(executor.execute(inputs(json!({"list": "abc"})), None)["error"], "list is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "random.choice");
+        assert_eq!(executor.category, "random");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RandomSample plugin.
+
+use super::RandomSample;
+
+/// Creates a new RandomSample instance.
+pub fn create() -> RandomSample {
+    RandomSample::new()
+}
@@ -0,0 +1,136 @@
+//! Workflow plugin: pick k distinct items from a list at random.
+//!
+//! Outputs `result`, `count` items of `list` drawn without replacement, in
+//! the order they were drawn, so every item appears at most as often as it
+//! does in `list`. `count` may not exceed the length of the list. Draws from
+//! the runtime's generator, so a seeded engine replays the same samples.
+
+use metabuilder_core::{Random, Runtime};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RandomSample implements the NodeExecutor trait for random samples.
+pub struct RandomSample {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RandomSample {
+    /// Creates a new RandomSample instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "random.sample",
+            category: "random",
+            description: "Pick k distinct items from a list at random",
+        }
+    }
+}
+
+impl Default for RandomSample {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn sample(inputs: &HashMap<String, Value>, random: &Random) -> Result<Vec<Value>, String> {
+    let list = inputs.get("list").and_then(Value::as_array).ok_or("list is required")?;
+    let count = inputs.get("count").and_then(Value::as_u64).ok_or("count must be a non-negative integer")? as usize;
+    if count > list.len() {
+        return Err(format!("count {} exceeds the list's {} items", count, list.len()));
+    }
+    // A partial Fisher-Yates shuffle: the first `count` slots are the sample.
+    let mut items = list.clone();
+    for i in 0..count {
+        let j = i + random.below(items.len() - i);
+        items.swap(i, j);
+    }
+    items.truncate(count);
+    Ok(items)
+}
+
+impl NodeExecutor for RandomSample {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let sampled = match Runtime::from_any(runtime) {
+            Some(runtime) => sample(&inputs, &runtime.random),
+            None => sample(&inputs, &Random::new()),
+        };
+        match sampled {
+            Ok(items) => {
+                output.insert("result".to_string(), Value::Array(items));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RandomSample instance.
+pub fn create() -> RandomSample {
+    RandomSample::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sample_without_replacement() {
+        let executor = RandomSample::new();
+        for _ in 0..20 {
+            let output = executor.execute(inputs(json!({"list": [1, 2, 3, 4, 5], "count": 3})), None);
+            let mut items: Vec<u64> = output["result"].as_array().unwrap().iter().map(|v| v.as_u64().unwrap()).collect();
+            items.sort();
+            items.dedup();
+            assert_eq!(items.len(), 3);
+            assert!(items.iter().all(|n| (1..=5).contains(n)));
+        }
+        assert_eq!(executor.execute(inputs(json!({"list": [1, 2], "count": 0})), None)["result"], json!([]));
+    }
+
+    #[test]
+    fn test_seeded_runtime_replays() {
+        let executor = RandomSample::new();
+        let draw = |seed| {
+            let mut runtime = Runtime::new();
+            runtime.random = Arc::new(Random::seeded(seed));
+            let params = json!({"list": (0..100).collect::<Vec<_>>(), "count": 10});
+            executor.execute(inputs(params), Some(&runtime))["result"].clone()
+        };
+        assert_eq!(draw(5), draw(5));
+        assert_ne!(draw(5), draw(6));
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = RandomSample::new();
+        let output = executor.execute(inputs(json!({"list": [1, 2], "count": 3})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "count 3 exceeds the list's 2 items");
+        let output = executor.execute(inputs(json!({"list": [1], "count": -1})), None);
+        assert_eq!(output["error"], "count must be a non-negative integer");
+        assert_eq!(executor.execute(inputs(json!({"count": 1})), None)["error"], "list is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "random.sample");
+        assert_eq!(executor.category, "random");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RandomShuffle plugin.
+
+use super::RandomShuffle;
+
+/// Creates a new RandomShuffle instance.
+pub fn create() -> RandomShuffle {
+    RandomShuffle::new()
+}
@@ -0,0 +1,112 @@
+//! Workflow plugin: shuffle a list.
+//!
+//! Outputs `result`, the items of `list` in a random order, every order
+//! being equally likely. Draws from the runtime's generator, so a seeded
+//! engine replays the same orders.
+
+use metabuilder_core::{Random, Runtime};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RandomShuffle implements the NodeExecutor trait for shuffling.
+pub struct RandomShuffle {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RandomShuffle {
+    /// Creates a new RandomShuffle instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "random.shuffle",
+            category: "random",
+            description: "Shuffle a list",
+        }
+    }
+}
+
+impl Default for RandomShuffle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for RandomShuffle {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match inputs.get("list").and_then(Value::as_array) {
+            Some(list) => {
+                let mut items = list.clone();
+                match Runtime::from_any(runtime) {
+                    Some(runtime) => runtime.random.shuffle(&mut items),
+                    None => Random::new().shuffle(&mut items),
+                }
+                output.insert("result".to_string(), Value::Array(items));
+            }
+            None => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!("list is required"));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RandomShuffle instance.
+pub fn create() -> RandomShuffle {
+    RandomShuffle::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let executor = RandomShuffle::new();
+        let output = executor.execute(inputs(json!({"list": ["a", "b", "a", "c"]})), None);
+        let mut items: Vec<String> = serde_json::from_value(output["result"].clone()).unwrap();
+        items.sort();
+        assert_eq!(items, ["a", "a", "b", "c"]);
+        assert_eq!(executor.execute(inputs(json!({"list": []})), None)["result"], json!([]));
+    }
+
+    #[test]
+    fn test_seeded_runtime_replays() {
+        let executor = RandomShuffle::new();
+        let shuffle = |seed| {
+            let mut runtime = Runtime::new();
+            runtime.random = Arc::new(Random::seeded(seed));
+            executor.execute(inputs(json!({"list": (0..50).collect::<Vec<_>>()})), Some(&runtime))["result"].clone()
+        };
+        assert_eq!(shuffle(3), shuffle(3));
+        assert_ne!(shuffle(3), shuffle(4));
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = RandomShuffle::new();
+        let output = executor.execute(inputs(json!({"list": {"a": 1}})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "list is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "random.shuffle");
+        assert_eq!(executor.category, "random");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RegexExtract plugin.
+
+use super::RegexExtract;
+
+/// Creates a new RegexExtract instance.
+pub fn create() -> RegexExtract {
+    RegexExtract::new()
+}
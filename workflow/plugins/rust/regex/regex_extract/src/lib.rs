@@ -0,0 +1,167 @@
+//! Workflow plugin: extract regex matches with their capture groups.
+//!
+//! Finds every match of `pattern` in `text` (at most `limit`, if given) and
+//! outputs them in `result` as objects holding the matched text, its `start`
+//! and `end` as character offsets, `groups`, the positional capture groups
+//! from 1 up, and `named`, the named ones by name. Groups that did not take
+//! part in a match are `null`. `count` is the number of matches.
+//!
+//! `flags` may combine `i`, `m`, `s`, and `x` (see
+//! `metabuilder_core::patterns`). The syntax is that of the `regex` crate, so
+//! there are no backreferences or lookaround, and matching takes linear time.
+//! Compiled patterns are cached in the runtime.
+
+use metabuilder_core::{patterns, Runtime};
+use serde_json::{Map, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RegexExtract implements the NodeExecutor trait for extracting regex matches.
+pub struct RegexExtract {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RegexExtract {
+    /// Creates a new RegexExtract instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "regex.extract",
+            category: "regex",
+            description: "Extract regex matches with their capture groups",
+        }
+    }
+}
+
+impl Default for RegexExtract {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn extract(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Vec<Value>, String> {
+    let text = inputs.get("text").and_then(Value::as_str).ok_or("text is required")?;
+    let pattern = inputs.get("pattern").and_then(Value::as_str).ok_or("pattern is required")?;
+    let flags = inputs.get("flags").and_then(Value::as_str).unwrap_or("");
+    let limit = match inputs.get("limit") {
+        None | Some(Value::Null) => usize::MAX,
+        Some(limit) => limit.as_u64().ok_or("limit must be a non-negative integer")? as usize,
+    };
+    let regex = match Runtime::from_any(runtime) {
+        Some(runtime) => runtime.patterns.get(pattern, flags)?,
+        None => Arc::new(patterns::compile(pattern, flags)?),
+    };
+    let chars = |byte: usize| text[..byte].chars().count();
+
+    let mut matches = Vec::new();
+    for captures in regex.captures_iter(text).take(limit) {
+        let whole = captures.get(0).expect("group 0 is always present");
+        let group = |i: usize| captures.get(i).map_or(Value::Null, |m| Value::from(m.as_str()));
+        let groups: Vec<Value> = (1..captures.len()).map(group).collect();
+        let named: Map<String, Value> = regex
+            .capture_names()
+            .enumerate()
+            .filter_map(|(i, name)| name.map(|name| (name.to_string(), group(i))))
+            .collect();
+        matches.push(serde_json::json!({
+            "match": whole.as_str(),
+            "start": chars(whole.start()),
+            "end": chars(whole.end()),
+            "groups": groups,
+            "named": named,
+        }));
+    }
+    Ok(matches)
+}
+
+impl NodeExecutor for RegexExtract {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match extract(&inputs, runtime) {
+            Ok(matches) => {
+                output.insert("count".to_string(), serde_json::json!(matches.len()));
+                output.insert("result".to_string(), Value::Array(matches));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new RegexExtract instance.
+pub fn create() -> RegexExtract {
+    RegexExtract::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_extract() {
+        let executor = RegexExtract::new();
+        let output = executor.execute(inputs(json!({
+            "text": "ERROR db: timeout\nINFO web: ok\nerror café: down",
+            "pattern": r"^(?P<level>error|warn)(-\d+)? (?P<source>\w+): (.*)$",
+            "flags": "im",
+        })), None);
+        assert_eq!(output["count"], 2);
+        assert_eq!(
+            output["result"][0],
+            json!({
+                "match": "ERROR db: timeout",
+                "start": 0,
+                "end": 17,
+                "groups": ["ERROR", null, "db", "timeout"],
+                "named": {"level": "ERROR", "source": "db"},
+            })
+        );
+        assert_eq!(output["result"][1]["named"]["source"], "café");
+        assert_eq!(output["result"][1]["start"], 31);
+        assert_eq!(output["result"][1]["end"], 47);
+    }
+
+    #[test]
+    fn test_limit_and_no_matches() {
+        let executor = RegexExtract::new();
+        let output = executor.execute(inputs(json!({"text": "a1 b2 c3", "pattern": r"\d", "limit": 2})), None);
+        assert_eq!(output["result"], json!([
+            {"match": "1", "start": 1, "end": 2, "groups": [], "named": {}},
+            {"match": "2", "start": 4, "end": 5, "groups": [], "named": {}},
+        ]));
+        let output = executor.execute(inputs(json!({"text": "abc", "pattern": r"\d"})), None);
+        assert_eq!(output["result"], json!([]));
+        assert_eq!(output["count"], 0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let executor = RegexExtract::new();
+        let output = executor.execute(inputs(json!({"text": "abc", "pattern": "(a"})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].as_str().unwrap().starts_with("invalid pattern: "));
+        let output = executor.execute(inputs(json!({"text": "abc", "pattern": "a", "flags": "g"})), None);
+        assert_eq!(output["error"], "unknown flag g");
+        assert_eq!(executor.execute(inputs(json!({"pattern": "a"})), None)["error"], "text is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "regex.extract");
+        assert_eq!(executor.category, "regex");
+    }
+}
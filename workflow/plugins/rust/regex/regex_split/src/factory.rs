@@ -0,0 +1,8 @@
+//! Factory for RegexSplit plugin.
+
+use super::RegexSplit;
+
+/// Creates a new RegexSplit instance.
+pub fn create() -> RegexSplit {
+    RegexSplit::new()
+}
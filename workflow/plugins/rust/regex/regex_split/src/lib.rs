@@ -0,0 +1,132 @@
+//! Workflow plugin: split a string on a regex.
+//!
+//! Splits `text` at every match of `pattern` and outputs the pieces in
+//! `result`, including empty ones (such as before a leading separator), and
+//! their number in `count`. With `limit`, at most that many pieces are
+//! returned, the last holding the rest of the text unsplit. `flags` works as
+//! in `regex.extract`, and compiled patterns are cached in the runtime.
+
+use metabuilder_core::{patterns, Runtime};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RegexSplit implements the NodeExecutor trait for splitting on a regex.
+pub struct RegexSplit {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RegexSplit {
+    /// Creates a new RegexSplit instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "regex.split",
+            category: "regex",
+            description: "Split a string on a regex",
+        }
+    }
+}
+
+impl Default for RegexSplit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn split(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Vec<Value>, String> {
+    let text = inputs.get("text").and_then(Value::as_str).ok_or("text is required")?;
+    let pattern = inputs.get("pattern").and_then(Value::as_str).ok_or("pattern is required")?;
+    let flags = inputs.get("flags").and_then(Value::as_str).unwrap_or("");
+    let limit = match inputs.get("limit") {
+        None | Some(Value::Null) => None,
+        Some(limit) => Some(limit.as_u64().filter(|&n| n > 0).ok_or("limit must be a positive integer")? as usize),
+    };
+    let regex = match Runtime::from_any(runtime) {
+        Some(runtime) => runtime.patterns.get(pattern, flags)?,
+        None => Arc::new(patterns::compile(pattern, flags)?),
+    };
+    Ok(match limit {
+        Some(limit) => regex.splitn(text, limit).map(Value::from).collect(),
+        None => regex.split(text).map(Value::from).collect(),
+    })
+}
+
+impl NodeExecutor for RegexSplit {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+        match split(&inputs, runtime) {
+            Ok(pieces) => {
+                output.insert("count".to_string(), serde_json::json!(pieces.len()));
+                output.insert("result".to_string(), Value::Array(pieces));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+        output
+    }
+}
+
+/// Creates a new RegexSplit instance.
+pub fn create() -> RegexSplit {
+    RegexSplit::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use serde_json::json;
+
+    #[test]
+    fn test_split() {
+        let executor = RegexSplit::new();
+        let output = executor.execute(inputs(json!({"text": "a, b;c  ,d", "pattern": r"\s*[,;]\s*"})), None);
+        assert_eq!(output["result"], json!(["a", "b", "c", "d"]));
+        assert_eq!(output["count"], 4);
+        let output = executor.execute(inputs(json!({"text": "-x--y", "pattern": "-"})), None);
+        assert_eq!(output["result"], json!(["", "x", "", "y"]));
+    }
+
+    #[test]
+    fn test_limit_and_flags() {
+        let executor = RegexSplit::new();
+        let output = executor.execute(
+            inputs(json!({"text": "k1 AND k2 and k3", "pattern": " and ", "flags": "i", "limit": 2})),
+            None,
+        );
+        assert_eq!(output["result"], json!(["k1", "k2 and k3"]));
+        let output = executor.execute(inputs(json!({"text": "abc", "pattern": "b", "limit": 0})), None);
+        assert_eq!(output["error"], "limit must be a positive integer");
+    }
+
+    #[test]
+    fn test_caches_pattern_in_runtime() {
+        let executor = RegexSplit::new();
+        let runtime = Runtime::new();
+        for text in ["1-2", "3-4-5"] {
+            executor.execute(inputs(json!({"text": text, "pattern": "-+"})), Some(&runtime));
+        }
+        assert_eq!(runtime.patterns.len(), 1);
+        let output = executor.execute(inputs(json!({"text": "abc", "pattern": "[b"})), Some(&runtime));
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].as_str().unwrap().starts_with("invalid pattern: "));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "regex.split");
+        assert_eq!(executor.category, "regex");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RemoteFtpGet plugin.
+
+use super::RemoteFtpGet;
+
+/// Creates a new RemoteFtpGet instance.
+pub fn create() -> RemoteFtpGet {
+    RemoteFtpGet::new()
+}
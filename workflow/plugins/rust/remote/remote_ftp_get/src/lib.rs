@@ -0,0 +1,204 @@
+//! Workflow plugin: download a file over FTP/FTPS.
+
+use metabuilder_core::ftp::FtpClient;
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RemoteFtpGet implements the NodeExecutor trait for FTP downloads.
+pub struct RemoteFtpGet {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RemoteFtpGet {
+    /// Creates a new RemoteFtpGet instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "remote.ftp_get",
+            category: "remote",
+            description: "Download a file from an FTP or FTPS server",
+        }
+    }
+}
+
+impl Default for RemoteFtpGet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Connects and logs in, reading the password from the secrets provider when one is named.
+fn open_session(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<FtpClient, String> {
+    let host: String = inputs
+        .get("host")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("host is required")?;
+    let port: u16 = inputs
+        .get("port")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(21);
+    let secure: bool = inputs
+        .get("secure")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(false);
+    let username: String = inputs
+        .get("username")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| "anonymous".to_string());
+    let timeout_ms: u64 = inputs
+        .get("timeout_ms")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(30_000);
+
+    let password = match inputs.get("password_secret").and_then(|v| v.as_str()) {
+        Some(name) => Runtime::from_any(runtime)
+            .ok_or("runtime with secrets provider is required")?
+            .secret(name)
+            .ok_or_else(|| format!("secret not found: {}", name))?,
+        None => "anonymous@".to_string(),
+    };
+
+    let mut client = FtpClient::connect(&host, port, secure, Duration::from_millis(timeout_ms))?;
+    client.login(&username, &password)?;
+    Ok(client)
+}
+
+impl NodeExecutor for RemoteFtpGet {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let remote_path: Option<String> = inputs
+            .get("remote_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let local_path: Option<String> = inputs
+            .get("local_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+        let mut output = HashMap::new();
+
+        let result = remote_path
+            .ok_or_else(|| "remote_path is required".to_string())
+            .and_then(|path| {
+                let mut client = open_session(&inputs, runtime)?;
+                let data = client.retrieve(&path)?;
+                client.quit();
+                Ok(data)
+            });
+
+        let result = result.and_then(|data| match &local_path {
+            Some(p) => std::fs::write(p, &data)
+                .map(|_| (data.len(), None))
+                .map_err(|e| format!("{}: {}", p, e)),
+            None => String::from_utf8(data)
+                .map(|s| (s.len(), Some(s)))
+                .map_err(|_| "remote file is not valid UTF-8; set local_path to save it".to_string()),
+        });
+
+        match result {
+            Ok((size, content)) => {
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert("size".to_string(), serde_json::json!(size));
+                if let Some(c) = content {
+                    output.insert("content".to_string(), serde_json::json!(c));
+                }
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RemoteFtpGet instance.
+pub fn create() -> RemoteFtpGet {
+    RemoteFtpGet::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves a single anonymous RETR of `body` on an ephemeral port.
+    fn fake_server(body: &'static str) -> u16 {
+        let control = TcpListener::bind("127.0.0.1:0").unwrap();
+        let data = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = control.local_addr().unwrap().port();
+        let data_port = data.local_addr().unwrap().port();
+
+        thread::spawn(move || {
+            let (stream, _) = control.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            writer.write_all(b"220 ready\r\n").unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let reply = match line.split_whitespace().next().unwrap_or("") {
+                    "USER" => "230 logged in".to_string(),
+                    "TYPE" => "200 binary".to_string(),
+                    "PASV" => format!("227 Entering Passive Mode (127,0,0,1,{},{})", data_port / 256, data_port % 256),
+                    "RETR" => {
+                        writer.write_all(b"150 opening\r\n").unwrap();
+                        let (mut conn, _) = data.accept().unwrap();
+                        conn.write_all(body.as_bytes()).unwrap();
+                        drop(conn);
+                        "226 done".to_string()
+                    }
+                    "QUIT" => "221 bye".to_string(),
+                    _ => "502 not implemented".to_string(),
+                };
+                writer.write_all(format!("{}\r\n", reply).as_bytes()).unwrap();
+                line.clear();
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_ftp_get() {
+        let port = fake_server("id,name\n1,alice\n");
+        let executor = RemoteFtpGet::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("127.0.0.1"));
+        inputs.insert("port".to_string(), serde_json::json!(port));
+        inputs.insert("remote_path".to_string(), serde_json::json!("export.csv"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&serde_json::json!(true)));
+        assert_eq!(result.get("content"), Some(&serde_json::json!("id,name\n1,alice\n")));
+        assert_eq!(result.get("size"), Some(&serde_json::json!(16)));
+    }
+
+    #[test]
+    fn test_missing_password_secret() {
+        let executor = RemoteFtpGet::new();
+        let runtime = Runtime::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("127.0.0.1"));
+        inputs.insert("remote_path".to_string(), serde_json::json!("export.csv"));
+        inputs.insert("password_secret".to_string(), serde_json::json!("ftp_password"));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("secret not found: ftp_password")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "remote.ftp_get");
+        assert_eq!(executor.category, "remote");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RemoteFtpPut plugin.
+
+use super::RemoteFtpPut;
+
+/// Creates a new RemoteFtpPut instance.
+pub fn create() -> RemoteFtpPut {
+    RemoteFtpPut::new()
+}
@@ -0,0 +1,203 @@
+//! Workflow plugin: upload a file over FTP/FTPS.
+
+use metabuilder_core::ftp::FtpClient;
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RemoteFtpPut implements the NodeExecutor trait for FTP uploads.
+pub struct RemoteFtpPut {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RemoteFtpPut {
+    /// Creates a new RemoteFtpPut instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "remote.ftp_put",
+            category: "remote",
+            description: "Upload a file to an FTP or FTPS server",
+        }
+    }
+}
+
+impl Default for RemoteFtpPut {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Connects and logs in, reading the password from the secrets provider when one is named.
+fn open_session(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<FtpClient, String> {
+    let host: String = inputs
+        .get("host")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("host is required")?;
+    let port: u16 = inputs
+        .get("port")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(21);
+    let secure: bool = inputs
+        .get("secure")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(false);
+    let username: String = inputs
+        .get("username")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| "anonymous".to_string());
+    let timeout_ms: u64 = inputs
+        .get("timeout_ms")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(30_000);
+
+    let password = match inputs.get("password_secret").and_then(|v| v.as_str()) {
+        Some(name) => Runtime::from_any(runtime)
+            .ok_or("runtime with secrets provider is required")?
+            .secret(name)
+            .ok_or_else(|| format!("secret not found: {}", name))?,
+        None => "anonymous@".to_string(),
+    };
+
+    let mut client = FtpClient::connect(&host, port, secure, Duration::from_millis(timeout_ms))?;
+    client.login(&username, &password)?;
+    Ok(client)
+}
+
+impl NodeExecutor for RemoteFtpPut {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let remote_path: Option<String> = inputs
+            .get("remote_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let content: Option<String> = inputs
+            .get("content")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let local_path: Option<String> = inputs
+            .get("local_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+        let mut output = HashMap::new();
+
+        let data = match (content, local_path) {
+            (Some(c), _) => Ok(c.into_bytes()),
+            (None, Some(p)) => std::fs::read(&p).map_err(|e| format!("{}: {}", p, e)),
+            (None, None) => Err("content or local_path is required".to_string()),
+        };
+
+        let result = remote_path
+            .ok_or_else(|| "remote_path is required".to_string())
+            .and_then(|path| {
+                let data = data?;
+                let mut client = open_session(&inputs, runtime)?;
+                client.store(&path, &data)?;
+                client.quit();
+                Ok(data.len())
+            });
+
+        match result {
+            Ok(bytes) => {
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert("bytes_written".to_string(), serde_json::json!(bytes));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RemoteFtpPut instance.
+pub fn create() -> RemoteFtpPut {
+    RemoteFtpPut::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Accepts a single anonymous STOR on an ephemeral port and reports the received bytes.
+    fn fake_server() -> (u16, mpsc::Receiver<Vec<u8>>) {
+        let control = TcpListener::bind("127.0.0.1:0").unwrap();
+        let data = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = control.local_addr().unwrap().port();
+        let data_port = data.local_addr().unwrap().port();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = control.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            writer.write_all(b"220 ready\r\n").unwrap();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 {
+                let reply = match line.split_whitespace().next().unwrap_or("") {
+                    "USER" => "230 logged in".to_string(),
+                    "TYPE" => "200 binary".to_string(),
+                    "PASV" => format!("227 Entering Passive Mode (127,0,0,1,{},{})", data_port / 256, data_port % 256),
+                    "STOR" => {
+                        writer.write_all(b"150 opening\r\n").unwrap();
+                        let (mut conn, _) = data.accept().unwrap();
+                        let mut received = Vec::new();
+                        conn.read_to_end(&mut received).unwrap();
+                        tx.send(received).unwrap();
+                        "226 done".to_string()
+                    }
+                    "QUIT" => "221 bye".to_string(),
+                    _ => "502 not implemented".to_string(),
+                };
+                writer.write_all(format!("{}\r\n", reply).as_bytes()).unwrap();
+                line.clear();
+            }
+        });
+        (port, rx)
+    }
+
+    #[test]
+    fn test_ftp_put() {
+        let (port, received) = fake_server();
+        let executor = RemoteFtpPut::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("127.0.0.1"));
+        inputs.insert("port".to_string(), serde_json::json!(port));
+        inputs.insert("remote_path".to_string(), serde_json::json!("upload.csv"));
+        inputs.insert("content".to_string(), serde_json::json!("a,b\n1,2\n"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&serde_json::json!(true)));
+        assert_eq!(result.get("bytes_written"), Some(&serde_json::json!(8)));
+        assert_eq!(received.recv().unwrap(), b"a,b\n1,2\n");
+    }
+
+    #[test]
+    fn test_missing_content() {
+        let executor = RemoteFtpPut::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("remote_path".to_string(), serde_json::json!("upload.csv"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("content or local_path is required")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "remote.ftp_put");
+        assert_eq!(executor.category, "remote");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RemoteSftpGet plugin.
+
+use super::RemoteSftpGet;
+
+/// Creates a new RemoteSftpGet instance.
+pub fn create() -> RemoteSftpGet {
+    RemoteSftpGet::new()
+}
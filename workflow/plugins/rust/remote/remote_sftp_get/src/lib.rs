@@ -0,0 +1,119 @@
+//! Workflow plugin: download a file over SFTP.
+
+use metabuilder_core::ssh::connect;
+use serde_json::Value;
+use ssh2::Session;
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RemoteSftpGet implements the NodeExecutor trait for SFTP downloads.
+pub struct RemoteSftpGet {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RemoteSftpGet {
+    /// Creates a new RemoteSftpGet instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "remote.sftp_get",
+            category: "remote",
+            description: "Download a file from a remote host over SFTP",
+        }
+    }
+}
+
+impl Default for RemoteSftpGet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Downloads `remote_path` into memory.
+fn download(session: &Session, remote_path: &str) -> Result<Vec<u8>, String> {
+    let sftp = session.sftp().map_err(|e| e.to_string())?;
+    let mut file = sftp.open(Path::new(remote_path)).map_err(|e| e.to_string())?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data).map_err(|e| e.to_string())?;
+    Ok(data)
+}
+
+impl NodeExecutor for RemoteSftpGet {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let remote_path: Option<String> = inputs
+            .get("remote_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let local_path: Option<String> = inputs
+            .get("local_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+        let mut output = HashMap::new();
+
+        let result = remote_path
+            .ok_or_else(|| "remote_path is required".to_string())
+            .and_then(|path| connect(&inputs, runtime).and_then(|session| download(&session, &path)));
+
+        let result = result.and_then(|data| match &local_path {
+            Some(p) => {
+                let mut file = std::fs::File::create(p).map_err(|e| format!("{}: {}", p, e))?;
+                file.write_all(&data).map_err(|e| format!("{}: {}", p, e))?;
+                Ok((data.len(), None))
+            }
+            None => String::from_utf8(data)
+                .map(|s| (s.len(), Some(s)))
+                .map_err(|_| "remote file is not valid UTF-8; set local_path to save it".to_string()),
+        });
+
+        match result {
+            Ok((size, content)) => {
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert("size".to_string(), serde_json::json!(size));
+                if let Some(c) = content {
+                    output.insert("content".to_string(), serde_json::json!(c));
+                }
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RemoteSftpGet instance.
+pub fn create() -> RemoteSftpGet {
+    RemoteSftpGet::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_remote_path() {
+        let executor = RemoteSftpGet::new();
+        let inputs = HashMap::new();
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&serde_json::json!(false)));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("remote_path is required")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "remote.sftp_get");
+        assert_eq!(executor.category, "remote");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for RemoteSftpPut plugin.
+
+use super::RemoteSftpPut;
+
+/// Creates a new RemoteSftpPut instance.
+pub fn create() -> RemoteSftpPut {
+    RemoteSftpPut::new()
+}
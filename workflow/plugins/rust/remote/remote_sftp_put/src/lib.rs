@@ -0,0 +1,145 @@
+//! Workflow plugin: upload a file over SFTP.
+
+use metabuilder_core::ssh::connect;
+use serde_json::Value;
+use ssh2::Session;
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RemoteSftpPut implements the NodeExecutor trait for SFTP uploads.
+pub struct RemoteSftpPut {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RemoteSftpPut {
+    /// Creates a new RemoteSftpPut instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "remote.sftp_put",
+            category: "remote",
+            description: "Upload a file to a remote host over SFTP",
+        }
+    }
+}
+
+impl Default for RemoteSftpPut {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Uploads `data` to `remote_path`, returning the number of bytes written.
+fn upload(session: &Session, remote_path: &str, data: &[u8], mode: i32) -> Result<usize, String> {
+    let sftp = session.sftp().map_err(|e| e.to_string())?;
+    let mut file = sftp.create(Path::new(remote_path)).map_err(|e| e.to_string())?;
+    file.write_all(data).map_err(|e| e.to_string())?;
+    let stat = ssh2::FileStat {
+        size: None,
+        uid: None,
+        gid: None,
+        perm: Some(mode as u32),
+        atime: None,
+        mtime: None,
+    };
+    sftp.setstat(Path::new(remote_path), stat).map_err(|e| e.to_string())?;
+    Ok(data.len())
+}
+
+impl NodeExecutor for RemoteSftpPut {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let remote_path: Option<String> = inputs
+            .get("remote_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let content: Option<String> = inputs
+            .get("content")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let local_path: Option<String> = inputs
+            .get("local_path")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let mode: i32 = inputs
+            .get("mode")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or(0o644);
+
+        let mut output = HashMap::new();
+
+        let data = match (content, local_path) {
+            (Some(c), _) => Ok(c.into_bytes()),
+            (None, Some(p)) => std::fs::read(&p).map_err(|e| format!("{}: {}", p, e)),
+            (None, None) => Err("content or local_path is required".to_string()),
+        };
+
+        let result = remote_path
+            .ok_or_else(|| "remote_path is required".to_string())
+            .and_then(|path| {
+                let data = data?;
+                let session = connect(&inputs, runtime)?;
+                upload(&session, &path, &data, mode)
+            });
+
+        match result {
+            Ok(bytes) => {
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert("bytes_written".to_string(), serde_json::json!(bytes));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RemoteSftpPut instance.
+pub fn create() -> RemoteSftpPut {
+    RemoteSftpPut::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_content() {
+        let executor = RemoteSftpPut::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("remote_path".to_string(), serde_json::json!("/tmp/out.csv"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&serde_json::json!(false)));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("content or local_path is required")));
+    }
+
+    #[test]
+    fn test_missing_runtime() {
+        let executor = RemoteSftpPut::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("localhost"));
+        inputs.insert("username".to_string(), serde_json::json!("deploy"));
+        inputs.insert("key_secret".to_string(), serde_json::json!("deploy_key"));
+        inputs.insert("remote_path".to_string(), serde_json::json!("/tmp/out.csv"));
+        inputs.insert("content".to_string(), serde_json::json!("a,b\n1,2\n"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("runtime with secrets provider is required")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "remote.sftp_put");
+        assert_eq!(executor.category, "remote");
+    }
+}
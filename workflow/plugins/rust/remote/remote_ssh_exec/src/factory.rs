@@ -0,0 +1,8 @@
+//! Factory for RemoteSshExec plugin.
+
+use super::RemoteSshExec;
+
+/// Creates a new RemoteSshExec instance.
+pub fn create() -> RemoteSshExec {
+    RemoteSshExec::new()
+}
@@ -0,0 +1,191 @@
+//! Workflow plugin: run a command over SSH.
+//!
+//! `timeout_ms` bounds connecting and each SSH operation, not the command:
+//! a command may run silently for as long as it needs. `command_timeout_ms`,
+//! if set, limits how long it may run in total, and the node also stops
+//! waiting when the run is cancelled or reaches its deadline.
+
+use metabuilder_core::ssh::connect;
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use ssh2::Session;
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read};
+use std::time::{Duration, Instant};
+
+/// How often to check the channel for output while the command runs.
+const POLL: Duration = Duration::from_millis(10);
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// RemoteSshExec implements the NodeExecutor trait for remote command execution.
+pub struct RemoteSshExec {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl RemoteSshExec {
+    /// Creates a new RemoteSshExec instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "remote.ssh_exec",
+            category: "remote",
+            description: "Run a command on a remote host over SSH",
+        }
+    }
+}
+
+impl Default for RemoteSshExec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads what `stream` has ready into `out`, returning whether it read
+/// anything.
+fn drain(mut stream: impl Read, out: &mut Vec<u8>) -> Result<bool, String> {
+    let mut buf = [0; 16 * 1024];
+    let mut read = false;
+    loop {
+        match stream.read(&mut buf) {
+            Ok(0) => return Ok(read),
+            Ok(n) => {
+                out.extend_from_slice(&buf[..n]);
+                read = true;
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(read),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// Runs `command` on the session, returning stdout, stderr, and the exit
+/// code. Waits for the command to end, for at most `limit` if set, or until
+/// the run is cancelled or reaches its deadline.
+fn exec(
+    session: &Session,
+    command: &str,
+    limit: Option<Duration>,
+    runtime: Option<&Runtime>,
+) -> Result<(String, String, i32), String> {
+    let mut channel = session.channel_session().map_err(|e| e.to_string())?;
+    channel.exec(command).map_err(|e| e.to_string())?;
+
+    // Both streams share the channel's window, so reading one to its end
+    // first stalls a command that fills the other; read them as data comes.
+    let started = Instant::now();
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    session.set_blocking(false);
+    let read = (|| loop {
+        let eof = channel.eof();
+        let progressed = drain(channel.stream(0), &mut stdout)? | drain(channel.stderr(), &mut stderr)?;
+        if eof {
+            return Ok(());
+        }
+        if progressed {
+            continue;
+        }
+        if let Some(limit) = limit.filter(|limit| started.elapsed() >= *limit) {
+            return Err(format!("command did not finish within {} ms", limit.as_millis()));
+        }
+        if let Some(runtime) = runtime {
+            if runtime.cancel.is_cancelled() {
+                return Err("cancelled".to_string());
+            }
+            if runtime.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err("run deadline reached".to_string());
+            }
+        }
+        std::thread::sleep(POLL);
+    })();
+    session.set_blocking(true);
+    read?;
+
+    channel.wait_close().map_err(|e| e.to_string())?;
+    let exit_code = channel.exit_status().map_err(|e| e.to_string())?;
+    Ok((String::from_utf8_lossy(&stdout).into_owned(), String::from_utf8_lossy(&stderr).into_owned(), exit_code))
+}
+
+impl NodeExecutor for RemoteSshExec {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let command: Option<String> = inputs
+            .get("command")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+        let mut output = HashMap::new();
+
+        let limit = inputs
+            .get("command_timeout_ms")
+            .and_then(Value::as_u64)
+            .map(Duration::from_millis);
+        let rt = Runtime::from_any(runtime);
+
+        let result = command
+            .ok_or_else(|| "command is required".to_string())
+            .and_then(|cmd| connect(&inputs, runtime).and_then(|session| exec(&session, &cmd, limit, rt)));
+
+        match result {
+            Ok((stdout, stderr, exit_code)) => {
+                output.insert("success".to_string(), serde_json::json!(exit_code == 0));
+                output.insert("stdout".to_string(), serde_json::json!(stdout));
+                output.insert("stderr".to_string(), serde_json::json!(stderr));
+                output.insert("exit_code".to_string(), serde_json::json!(exit_code));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new RemoteSshExec instance.
+pub fn create() -> RemoteSshExec {
+    RemoteSshExec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_command() {
+        let executor = RemoteSshExec::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("localhost"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("success"), Some(&serde_json::json!(false)));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("command is required")));
+    }
+
+    #[test]
+    fn test_missing_secret() {
+        let executor = RemoteSshExec::new();
+        let runtime = metabuilder_core::Runtime::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("host".to_string(), serde_json::json!("localhost"));
+        inputs.insert("username".to_string(), serde_json::json!("deploy"));
+        inputs.insert("key_secret".to_string(), serde_json::json!("deploy_key"));
+        inputs.insert("command".to_string(), serde_json::json!("uptime"));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("secret not found: deploy_key")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "remote.ssh_exec");
+        assert_eq!(executor.category, "remote");
+    }
+}
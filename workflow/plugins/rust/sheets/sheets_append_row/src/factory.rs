@@ -0,0 +1,8 @@
+//! Factory for SheetsAppendRow plugin.
+
+use super::SheetsAppendRow;
+
+/// Creates a new SheetsAppendRow instance.
+pub fn create() -> SheetsAppendRow {
+    SheetsAppendRow::new()
+}
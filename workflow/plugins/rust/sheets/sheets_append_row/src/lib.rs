@@ -0,0 +1,251 @@
+//! Workflow plugin: append rows to a spreadsheet.
+
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// Google Sheets REST API root; override `base_url` for compatible services.
+const DEFAULT_BASE_URL: &str = "https://sheets.googleapis.com/v4";
+
+/// SheetsAppendRow implements the NodeExecutor trait for appending spreadsheet rows.
+pub struct SheetsAppendRow {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl SheetsAppendRow {
+    /// Creates a new SheetsAppendRow instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "sheets.append_row",
+            category: "sheets",
+            description: "Append one or more rows after the last row of a spreadsheet range",
+        }
+    }
+}
+
+impl Default for SheetsAppendRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Percent-encodes a path segment such as `Sheet1!A:C`.
+fn encode_segment(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Collects the rows to append from `rows` (list of lists) or a single `row`.
+/// Object rows are flattened in `columns` order.
+fn collect_rows(inputs: &HashMap<String, Value>) -> Result<Vec<Value>, String> {
+    let columns: Vec<String> = inputs
+        .get("columns")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    let rows: Vec<Value> = match (inputs.get("rows"), inputs.get("row")) {
+        (Some(Value::Array(rows)), _) => rows.clone(),
+        (None, Some(row)) => vec![row.clone()],
+        _ => return Err("row or rows is required".to_string()),
+    };
+
+    rows.into_iter()
+        .map(|row| match row {
+            Value::Array(_) => Ok(row),
+            Value::Object(map) if !columns.is_empty() => Ok(Value::Array(
+                columns.iter().map(|c| map.get(c).cloned().unwrap_or(Value::Null)).collect(),
+            )),
+            Value::Object(_) => Err("columns is required when rows are objects".to_string()),
+            other => Ok(Value::Array(vec![other])),
+        })
+        .collect()
+}
+
+fn append(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Value, String> {
+    let spreadsheet_id: String = inputs
+        .get("spreadsheet_id")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("spreadsheet_id is required")?;
+    let range: String = inputs
+        .get("range")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("range is required")?;
+    let token_secret: String = inputs
+        .get("token_secret")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("token_secret is required")?;
+    let base_url: String = inputs
+        .get("base_url")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    let value_input: String = inputs
+        .get("value_input")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| "USER_ENTERED".to_string());
+    let timeout_ms: u64 = inputs
+        .get("timeout_ms")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(30_000);
+    let rows = collect_rows(inputs)?;
+
+    let token = Runtime::from_any(runtime)
+        .ok_or("runtime with secrets provider is required")?
+        .secret(&token_secret)
+        .ok_or_else(|| format!("secret not found: {}", token_secret))?;
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .build()
+        .into();
+    let url = format!(
+        "{}/spreadsheets/{}/values/{}:append",
+        base_url.trim_end_matches('/'),
+        encode_segment(&spreadsheet_id),
+        encode_segment(&range)
+    );
+    let payload = serde_json::json!({ "values": rows }).to_string();
+    let mut response = agent
+        .post(&url)
+        .query("valueInputOption", &value_input)
+        .query("insertDataOption", "INSERT_ROWS")
+        .header("Authorization", &format!("Bearer {}", token))
+        .content_type("application/json")
+        .send(payload.as_str())
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+    if status >= 400 {
+        return Err(format!("sheets API returned {}: {}", status, body));
+    }
+    serde_json::from_str(&body).map_err(|e| format!("invalid sheets API response: {}", e))
+}
+
+impl NodeExecutor for SheetsAppendRow {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match append(&inputs, runtime) {
+            Ok(body) => {
+                let updates = body.get("updates").cloned().unwrap_or(Value::Null);
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert(
+                    "updated_range".to_string(),
+                    updates.get("updatedRange").cloned().unwrap_or(Value::Null),
+                );
+                output.insert(
+                    "updated_rows".to_string(),
+                    updates.get("updatedRows").cloned().unwrap_or(serde_json::json!(0)),
+                );
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new SheetsAppendRow instance.
+pub fn create() -> SheetsAppendRow {
+    SheetsAppendRow::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::runtime;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers one HTTP request with `body` and reports the raw request.
+    fn fake_server(body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut line = String::new();
+            let mut length = 0;
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = v.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                line.clear();
+            }
+            let mut payload = vec![0; length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+            tx.send(request).unwrap();
+            let mut stream = stream;
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        });
+        (format!("http://{}", addr), rx)
+    }
+
+    const SECRETS: &[(&str, &str)] = &[("sheets_token", "ya29.token")];
+
+    #[test]
+    fn test_append_object_row() {
+        let (base_url, requests) = fake_server(r#"{"updates":{"updatedRange":"Sheet1!A5:B5","updatedRows":1}}"#);
+        let executor = SheetsAppendRow::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("spreadsheet_id".to_string(), serde_json::json!("abc123"));
+        inputs.insert("range".to_string(), serde_json::json!("Sheet1!A:B"));
+        inputs.insert("token_secret".to_string(), serde_json::json!("sheets_token"));
+        inputs.insert("base_url".to_string(), serde_json::json!(base_url));
+        inputs.insert("columns".to_string(), serde_json::json!(["name", "qty"]));
+        inputs.insert("row".to_string(), serde_json::json!({"qty": 3, "name": "apple"}));
+
+        let result = executor.execute(inputs, Some(&runtime(SECRETS)));
+        assert_eq!(result.get("success"), Some(&serde_json::json!(true)));
+        assert_eq!(result.get("updated_range"), Some(&serde_json::json!("Sheet1!A5:B5")));
+        assert_eq!(result.get("updated_rows"), Some(&serde_json::json!(1)));
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /spreadsheets/abc123/values/Sheet1%21A%3AB:append?valueInputOption=USER_ENTERED"));
+        assert!(request.ends_with(r#"{"values":[["apple",3]]}"#));
+    }
+
+    #[test]
+    fn test_object_rows_need_columns() {
+        let executor = SheetsAppendRow::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("spreadsheet_id".to_string(), serde_json::json!("abc123"));
+        inputs.insert("range".to_string(), serde_json::json!("Sheet1!A:B"));
+        inputs.insert("token_secret".to_string(), serde_json::json!("sheets_token"));
+        inputs.insert("row".to_string(), serde_json::json!({"name": "apple"}));
+
+        let result = executor.execute(inputs, Some(&runtime(SECRETS)));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("columns is required when rows are objects")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "sheets.append_row");
+        assert_eq!(executor.category, "sheets");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for SheetsReadRange plugin.
+
+use super::SheetsReadRange;
+
+/// Creates a new SheetsReadRange instance.
+pub fn create() -> SheetsReadRange {
+    SheetsReadRange::new()
+}
@@ -0,0 +1,244 @@
+//! Workflow plugin: read a range from a spreadsheet.
+
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// Google Sheets REST API root; override `base_url` for compatible services.
+const DEFAULT_BASE_URL: &str = "https://sheets.googleapis.com/v4";
+
+/// SheetsReadRange implements the NodeExecutor trait for reading spreadsheet ranges.
+pub struct SheetsReadRange {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl SheetsReadRange {
+    /// Creates a new SheetsReadRange instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "sheets.read_range",
+            category: "sheets",
+            description: "Read a cell range from a spreadsheet",
+        }
+    }
+}
+
+impl Default for SheetsReadRange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Percent-encodes a path segment such as `Sheet1!A1:C10`.
+fn encode_segment(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Turns a grid into objects keyed by the first row; short rows are padded with empty strings.
+fn rows_to_objects(rows: &[Value]) -> Vec<Value> {
+    let Some((header, body)) = rows.split_first() else {
+        return Vec::new();
+    };
+    let keys: Vec<String> = header
+        .as_array()
+        .map(|cells| cells.iter().map(|c| c.as_str().map(String::from).unwrap_or_else(|| c.to_string())).collect())
+        .unwrap_or_default();
+    body.iter()
+        .map(|row| {
+            let cells = row.as_array().cloned().unwrap_or_default();
+            let object: serde_json::Map<String, Value> = keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| (key.clone(), cells.get(i).cloned().unwrap_or_else(|| serde_json::json!(""))))
+                .collect();
+            Value::Object(object)
+        })
+        .collect()
+}
+
+fn read_range(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Value, String> {
+    let spreadsheet_id: String = inputs
+        .get("spreadsheet_id")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("spreadsheet_id is required")?;
+    let range: String = inputs
+        .get("range")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("range is required")?;
+    let token_secret: String = inputs
+        .get("token_secret")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .ok_or("token_secret is required")?;
+    let base_url: String = inputs
+        .get("base_url")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    let render: String = inputs
+        .get("value_render")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_else(|| "FORMATTED_VALUE".to_string());
+    let timeout_ms: u64 = inputs
+        .get("timeout_ms")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or(30_000);
+
+    let token = Runtime::from_any(runtime)
+        .ok_or("runtime with secrets provider is required")?
+        .secret(&token_secret)
+        .ok_or_else(|| format!("secret not found: {}", token_secret))?;
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .build()
+        .into();
+    let url = format!(
+        "{}/spreadsheets/{}/values/{}",
+        base_url.trim_end_matches('/'),
+        encode_segment(&spreadsheet_id),
+        encode_segment(&range)
+    );
+    let mut response = agent
+        .get(&url)
+        .query("valueRenderOption", &render)
+        .header("Authorization", &format!("Bearer {}", token))
+        .call()
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().map_err(|e| e.to_string())?;
+    if status >= 400 {
+        return Err(format!("sheets API returned {}: {}", status, body));
+    }
+    serde_json::from_str(&body).map_err(|e| format!("invalid sheets API response: {}", e))
+}
+
+impl NodeExecutor for SheetsReadRange {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let header: bool = inputs
+            .get("header")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or(false);
+
+        let mut output = HashMap::new();
+
+        match read_range(&inputs, runtime) {
+            Ok(body) => {
+                let values = body.get("values").cloned().unwrap_or_else(|| serde_json::json!([]));
+                let rows = values.as_array().cloned().unwrap_or_default();
+                let result = if header {
+                    Value::Array(rows_to_objects(&rows))
+                } else {
+                    values
+                };
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert("range".to_string(), body.get("range").cloned().unwrap_or(Value::Null));
+                output.insert("count".to_string(), serde_json::json!(result.as_array().map_or(0, Vec::len)));
+                output.insert("result".to_string(), result);
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("result".to_string(), serde_json::json!([]));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new SheetsReadRange instance.
+pub fn create() -> SheetsReadRange {
+    SheetsReadRange::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::runtime;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Answers one HTTP request with `body` and reports the request line and headers.
+    fn fake_server(body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                head.push_str(&line);
+                line.clear();
+            }
+            tx.send(head).unwrap();
+            let mut stream = stream;
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        });
+        (format!("http://{}", addr), rx)
+    }
+
+    const SECRETS: &[(&str, &str)] = &[("sheets_token", "ya29.token")];
+
+    #[test]
+    fn test_read_range_with_header() {
+        let (base_url, requests) = fake_server(r#"{"range":"Sheet1!A1:B3","values":[["name","qty"],["apple","3"],["pear"]]}"#);
+        let executor = SheetsReadRange::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("spreadsheet_id".to_string(), serde_json::json!("abc123"));
+        inputs.insert("range".to_string(), serde_json::json!("Sheet1!A1:B3"));
+        inputs.insert("token_secret".to_string(), serde_json::json!("sheets_token"));
+        inputs.insert("base_url".to_string(), serde_json::json!(base_url));
+        inputs.insert("header".to_string(), serde_json::json!(true));
+
+        let result = executor.execute(inputs, Some(&runtime(SECRETS)));
+        assert_eq!(result.get("success"), Some(&serde_json::json!(true)));
+        assert_eq!(
+            result.get("result"),
+            Some(&serde_json::json!([{"name": "apple", "qty": "3"}, {"name": "pear", "qty": ""}]))
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /spreadsheets/abc123/values/Sheet1%21A1%3AB3?valueRenderOption=FORMATTED_VALUE"));
+        assert!(request.to_lowercase().contains("authorization: bearer ya29.token"));
+    }
+
+    #[test]
+    fn test_missing_token_secret() {
+        let executor = SheetsReadRange::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("spreadsheet_id".to_string(), serde_json::json!("abc123"));
+        inputs.insert("range".to_string(), serde_json::json!("A1:B2"));
+        inputs.insert("token_secret".to_string(), serde_json::json!("missing"));
+
+        let result = executor.execute(inputs, Some(&runtime(SECRETS)));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("secret not found: missing")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "sheets.read_range");
+        assert_eq!(executor.category, "sheets");
+    }
+}
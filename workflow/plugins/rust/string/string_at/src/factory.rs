@@ -0,0 +1,8 @@
+//! Factory for StringAt plugin.
+
+use super::StringAt;
+
+/// Creates a new StringAt instance.
+pub fn create() -> StringAt {
+    StringAt::new()
+}
@@ -0,0 +1,130 @@
+//! Workflow plugin: character at index.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use metabuilder_core::{text, TextUnit};
+use serde_json::Value;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// StringAt implements the NodeExecutor trait for indexing into strings.
+pub struct StringAt {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl StringAt {
+    /// Creates a new StringAt instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "string.at",
+            category: "string",
+            description: "Get the character or grapheme at an index",
+        }
+    }
+}
+
+impl Default for StringAt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for StringAt {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let string: String = inputs
+            .get("string")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let index: i64 = inputs
+            .get("index")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or(0);
+        let unit: String = inputs
+            .get("unit")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_else(|| "chars".to_string());
+
+        let mut result = HashMap::new();
+
+        match unit.parse::<TextUnit>() {
+            Ok(unit) => {
+                let found = text::at(&string, index, unit);
+                result.insert("result".to_string(), serde_json::json!(found));
+                result.insert("found".to_string(), serde_json::json!(found.is_some()));
+            }
+            Err(e) => {
+                result.insert("result".to_string(), Value::Null);
+                result.insert("found".to_string(), serde_json::json!(false));
+                result.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        result
+    }
+}
+
+/// Creates a new StringAt instance.
+pub fn create() -> StringAt {
+    StringAt::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let executor = StringAt::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!("hello"));
+        inputs.insert("index".to_string(), serde_json::json!(-1));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!("o")));
+        assert_eq!(result.get("found"), Some(&serde_json::json!(true)));
+    }
+
+    #[test]
+    fn test_at_grapheme() {
+        let executor = StringAt::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!("cafe\u{301}"));
+        inputs.insert("index".to_string(), serde_json::json!(3));
+        inputs.insert("unit".to_string(), serde_json::json!("graphemes"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!("e\u{301}")));
+    }
+
+    #[test]
+    fn test_at_out_of_range() {
+        let executor = StringAt::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!("hi"));
+        inputs.insert("index".to_string(), serde_json::json!(5));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&Value::Null));
+        assert_eq!(result.get("found"), Some(&serde_json::json!(false)));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "string.at");
+        assert_eq!(executor.category, "string");
+    }
+}
@@ -1,7 +1,15 @@
 //! Workflow plugin: string length.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use metabuilder_core::{text, TextUnit};
 use serde_json::Value;
-use std::any::Any;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -40,9 +48,23 @@ impl NodeExecutor for StringLength {
             .get("string")
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or_default();
+        let unit: String = inputs
+            .get("unit")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_else(|| "bytes".to_string());
 
         let mut result = HashMap::new();
-        result.insert("result".to_string(), serde_json::json!(string.len()));
+
+        match unit.parse::<TextUnit>() {
+            Ok(unit) => {
+                result.insert("result".to_string(), serde_json::json!(text::length(&string, unit)));
+            }
+            Err(e) => {
+                result.insert("result".to_string(), serde_json::json!(0));
+                result.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
         result
     }
 }
@@ -66,6 +88,30 @@ mod tests {
         assert_eq!(result.get("result"), Some(&serde_json::json!(5)));
     }
 
+    #[test]
+    fn test_length_units() {
+        let executor = StringLength::new();
+        for (unit, expected) in [("bytes", 7), ("chars", 3), ("graphemes", 2)] {
+            let mut inputs = HashMap::new();
+            inputs.insert("string".to_string(), serde_json::json!("e\u{301}\u{1F600}"));
+            inputs.insert("unit".to_string(), serde_json::json!(unit));
+
+            let result = executor.execute(inputs, None);
+            assert_eq!(result.get("result"), Some(&serde_json::json!(expected)), "unit {}", unit);
+        }
+    }
+
+    #[test]
+    fn test_unknown_unit() {
+        let executor = StringLength::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!("hello"));
+        inputs.insert("unit".to_string(), serde_json::json!("words"));
+
+        let result = executor.execute(inputs, None);
+        assert!(result.contains_key("error"));
+    }
+
     #[test]
     fn test_factory() {
         let executor = create();
@@ -0,0 +1,8 @@
+//! Factory for StringNormalizeNewlines plugin.
+
+use super::StringNormalizeNewlines;
+
+/// Creates a new StringNormalizeNewlines instance.
+pub fn create() -> StringNormalizeNewlines {
+    StringNormalizeNewlines::new()
+}
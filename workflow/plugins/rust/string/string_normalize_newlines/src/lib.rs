@@ -0,0 +1,148 @@
+//! Workflow plugin: normalize line endings and the byte order mark.
+//!
+//! Every `\r\n`, lone `\r`, and `\n` in `string` becomes `newline` (`"lf"` by
+//! default, `"crlf"`, or `"cr"`). `bom` is `"keep"` (the default), `"strip"`
+//! to remove a leading U+FEFF, or `"add"` to ensure there is one.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use serde_json::Value;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, format, string::{String, ToString}};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// StringNormalizeNewlines implements the NodeExecutor trait for newline normalization.
+pub struct StringNormalizeNewlines {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl StringNormalizeNewlines {
+    /// Creates a new StringNormalizeNewlines instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "string.normalize_newlines",
+            category: "string",
+            description: "Normalize line endings and byte order marks",
+        }
+    }
+}
+
+impl Default for StringNormalizeNewlines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const BOM: char = '\u{feff}';
+
+fn normalize(inputs: &HashMap<String, Value>) -> Result<String, String> {
+    let string = inputs.get("string").and_then(Value::as_str).ok_or("string is required")?;
+    let newline = match inputs.get("newline").and_then(Value::as_str).unwrap_or("lf") {
+        "lf" => "\n",
+        "crlf" => "\r\n",
+        "cr" => "\r",
+        other => return Err(format!("unknown newline: {} (expected lf, crlf, or cr)", other)),
+    };
+
+    let (has_bom, body) = match string.strip_prefix(BOM) {
+        Some(body) => (true, body),
+        None => (false, string),
+    };
+    let keep_bom = match inputs.get("bom").and_then(Value::as_str).unwrap_or("keep") {
+        "keep" => has_bom,
+        "strip" => false,
+        "add" => true,
+        other => return Err(format!("unknown bom: {} (expected keep, strip, or add)", other)),
+    };
+
+    let mut result = String::with_capacity(string.len());
+    if keep_bom {
+        result.push(BOM);
+    }
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                result.push_str(newline);
+            }
+            '\n' => result.push_str(newline),
+            c => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+impl NodeExecutor for StringNormalizeNewlines {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match normalize(&inputs) {
+            Ok(result) => {
+                output.insert("result".to_string(), serde_json::json!(result));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new StringNormalizeNewlines instance.
+pub fn create() -> StringNormalizeNewlines {
+    StringNormalizeNewlines::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(string: &str, newline: &str, bom: &str) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!(string));
+        inputs.insert("newline".to_string(), serde_json::json!(newline));
+        inputs.insert("bom".to_string(), serde_json::json!(bom));
+        StringNormalizeNewlines::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_normalize_newlines() {
+        assert_eq!(run("a\r\nb\rc\nd", "lf", "keep")["result"], "a\nb\nc\nd");
+        assert_eq!(run("a\nb\r\n", "crlf", "keep")["result"], "a\r\nb\r\n");
+        assert_eq!(run("a\r\n\r\nb", "cr", "keep")["result"], "a\r\rb");
+    }
+
+    #[test]
+    fn test_bom() {
+        assert_eq!(run("\u{feff}a\r\n", "lf", "strip")["result"], "a\n");
+        assert_eq!(run("a", "lf", "add")["result"], "\u{feff}a");
+        assert_eq!(run("\u{feff}a", "lf", "add")["result"], "\u{feff}a");
+        assert_eq!(run("\u{feff}a", "lf", "keep")["result"], "\u{feff}a");
+        assert_eq!(
+            run("a", "lf", "drop").get("error"),
+            Some(&serde_json::json!("unknown bom: drop (expected keep, strip, or add)"))
+        );
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "string.normalize_newlines");
+        assert_eq!(executor.category, "string");
+    }
+}
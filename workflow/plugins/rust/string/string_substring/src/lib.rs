@@ -1,7 +1,15 @@
 //! Workflow plugin: substring.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use metabuilder_core::{text, TextUnit};
 use serde_json::Value;
-use std::any::Any;
+use core::any::Any;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap as HashMap, string::{String, ToString}};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
 
 /// Trait for workflow node executors.
@@ -47,26 +55,26 @@ impl NodeExecutor for StringSubstring {
         let end: Option<i64> = inputs
             .get("end")
             .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let unit: String = inputs
+            .get("unit")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_else(|| "chars".to_string());
 
-        let chars: Vec<char> = string.chars().collect();
-        let len = chars.len() as i64;
-
-        // Handle negative indices
-        let start_idx = if start < 0 { (len + start).max(0) } else { start.min(len) } as usize;
-        let end_idx = match end {
-            Some(e) if e < 0 => (len + e).max(0) as usize,
-            Some(e) => e.min(len) as usize,
-            None => len as usize,
-        };
+        let mut result = HashMap::new();
 
-        let substring: String = if start_idx < end_idx {
-            chars[start_idx..end_idx].iter().collect()
-        } else {
-            String::new()
-        };
+        match unit
+            .parse::<TextUnit>()
+            .and_then(|unit| text::slice(&string, start, end, unit))
+        {
+            Ok(substring) => {
+                result.insert("result".to_string(), serde_json::json!(substring));
+            }
+            Err(e) => {
+                result.insert("result".to_string(), serde_json::json!(""));
+                result.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
 
-        let mut result = HashMap::new();
-        result.insert("result".to_string(), serde_json::json!(substring));
         result
     }
 }
@@ -92,6 +100,29 @@ mod tests {
         assert_eq!(result.get("result"), Some(&serde_json::json!("hello")));
     }
 
+    #[test]
+    fn test_substring_negative() {
+        let executor = StringSubstring::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!("hello world"));
+        inputs.insert("start".to_string(), serde_json::json!(-5));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!("world")));
+    }
+
+    #[test]
+    fn test_substring_graphemes() {
+        let executor = StringSubstring::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("string".to_string(), serde_json::json!("\u{1F1EF}\u{1F1F5}\u{1F1EB}\u{1F1F7}"));
+        inputs.insert("start".to_string(), serde_json::json!(1));
+        inputs.insert("unit".to_string(), serde_json::json!("graphemes"));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("result"), Some(&serde_json::json!("\u{1F1EB}\u{1F1F7}")));
+    }
+
     #[test]
     fn test_factory() {
         let executor = create();
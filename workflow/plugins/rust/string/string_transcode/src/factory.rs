@@ -0,0 +1,8 @@
+//! Factory for StringTranscode plugin.
+
+use super::StringTranscode;
+
+/// Creates a new StringTranscode instance.
+pub fn create() -> StringTranscode {
+    StringTranscode::new()
+}
@@ -0,0 +1,175 @@
+//! Workflow plugin: convert text between character encodings.
+//!
+//! `value` is a bytes value in the `from` encoding, or a string (`from` is
+//! then ignored). The text is re-encoded as `to` and emitted as a bytes
+//! value. Encodings are WHATWG labels (`utf-8`, `utf-16le`, `utf-16be`,
+//! `latin1`, `shift_jis`, ...). With `mode: "strict"` (the default),
+//! malformed input or characters missing from `to` are an error; with
+//! `"lossy"`, they become U+FFFD when decoding and `?` when encoding.
+
+use encoding_rs::{EncoderResult, Encoding, UTF_16BE, UTF_16LE};
+use metabuilder_core::bytes;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// StringTranscode implements the NodeExecutor trait for encoding conversion.
+pub struct StringTranscode {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl StringTranscode {
+    /// Creates a new StringTranscode instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "string.transcode",
+            category: "string",
+            description: "Convert text between character encodings",
+        }
+    }
+}
+
+impl Default for StringTranscode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn encoding(inputs: &HashMap<String, Value>, key: &str) -> Result<&'static Encoding, String> {
+    let label = inputs.get(key).and_then(Value::as_str).unwrap_or("utf-8");
+    Encoding::for_label(label.as_bytes()).ok_or_else(|| format!("unknown {} encoding: {}", key, label))
+}
+
+/// Encodes `text` as `to`. encoding_rs only decodes UTF-16, so it is encoded here.
+fn encode(text: &str, to: &'static Encoding, lossy: bool) -> Result<Vec<u8>, String> {
+    if to == UTF_16LE {
+        return Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect());
+    }
+    if to == UTF_16BE {
+        return Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect());
+    }
+
+    let mut encoder = to.new_encoder();
+    let mut out = Vec::with_capacity(text.len());
+    let mut rest = text;
+    loop {
+        let needed = encoder
+            .max_buffer_length_from_utf8_without_replacement(rest.len())
+            .ok_or("text too long")?;
+        out.reserve(needed);
+        let (result, read) = encoder.encode_from_utf8_to_vec_without_replacement(rest, &mut out, true);
+        rest = &rest[read..];
+        match result {
+            EncoderResult::InputEmpty => return Ok(out),
+            EncoderResult::OutputFull => {}
+            EncoderResult::Unmappable(_) if lossy => out.push(b'?'),
+            EncoderResult::Unmappable(c) => {
+                return Err(format!("character {:?} cannot be encoded as {}", c, to.name()));
+            }
+        }
+    }
+}
+
+fn transcode(inputs: &HashMap<String, Value>) -> Result<Vec<u8>, String> {
+    let lossy = match inputs.get("mode").and_then(Value::as_str).unwrap_or("strict") {
+        "strict" => false,
+        "lossy" => true,
+        other => return Err(format!("unknown mode: {} (expected strict or lossy)", other)),
+    };
+    let to = encoding(inputs, "to")?;
+    let text = match inputs.get("value") {
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => {
+            let from = encoding(inputs, "from")?;
+            let data = bytes::from_value(value)?;
+            if lossy {
+                from.decode_without_bom_handling(&data).0.into_owned()
+            } else {
+                from.decode_without_bom_handling_and_without_replacement(&data)
+                    .ok_or_else(|| format!("value is not valid {}", from.name()))?
+                    .into_owned()
+            }
+        }
+        None => return Err("value is required".to_string()),
+    };
+    encode(&text, to, lossy)
+}
+
+impl NodeExecutor for StringTranscode {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match transcode(&inputs) {
+            Ok(data) => {
+                output.insert("result".to_string(), bytes::to_value(&data));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new StringTranscode instance.
+pub fn create() -> StringTranscode {
+    StringTranscode::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(value: Value, from: &str, to: &str, mode: &str) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("value".to_string(), value);
+        inputs.insert("from".to_string(), serde_json::json!(from));
+        inputs.insert("to".to_string(), serde_json::json!(to));
+        inputs.insert("mode".to_string(), serde_json::json!(mode));
+        StringTranscode::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_transcode_legacy_to_utf8() {
+        let latin1 = bytes::to_value(&[b'c', b'a', b'f', 0xe9]);
+        assert_eq!(run(latin1, "latin1", "utf-8", "strict")["result"], bytes::to_value("café".as_bytes()));
+
+        let sjis = bytes::to_value(&[0x82, 0xa0, 0x82, 0xa2]);
+        assert_eq!(run(sjis, "shift_jis", "utf-16be", "strict")["result"], bytes::to_value(&[0x30, 0x42, 0x30, 0x44]));
+    }
+
+    #[test]
+    fn test_transcode_unmappable() {
+        let result = run(serde_json::json!("a€b"), "utf-8", "iso-8859-2", "strict");
+        assert_eq!(
+            result.get("error"),
+            Some(&serde_json::json!("character '€' cannot be encoded as ISO-8859-2"))
+        );
+
+        let result = run(serde_json::json!("aあb"), "utf-8", "latin1", "lossy");
+        assert_eq!(result.get("result"), Some(&bytes::to_value(b"a?b")));
+    }
+
+    #[test]
+    fn test_transcode_malformed_input() {
+        let result = run(bytes::to_value(&[0xff, 0xfe, 0xfd]), "utf-8", "utf-8", "strict");
+        assert_eq!(result.get("error"), Some(&serde_json::json!("value is not valid UTF-8")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "string.transcode");
+        assert_eq!(executor.category, "string");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for TableFromRows plugin.
+
+use super::TableFromRows;
+
+/// Creates a new TableFromRows instance.
+pub fn create() -> TableFromRows {
+    TableFromRows::new()
+}
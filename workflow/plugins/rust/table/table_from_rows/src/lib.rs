@@ -0,0 +1,110 @@
+//! Workflow plugin: convert JSON rows to a table handle.
+//!
+//! Column types are inferred from the rows. The resulting handle can be
+//! passed to any `table.*` node without converting back to JSON.
+
+use metabuilder_core::Runtime;
+use metabuilder_table::Table;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TableFromRows implements the NodeExecutor trait for building tables.
+pub struct TableFromRows {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TableFromRows {
+    /// Creates a new TableFromRows instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "table.from_rows",
+            category: "table",
+            description: "Convert JSON rows to a table handle",
+        }
+    }
+}
+
+impl Default for TableFromRows {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn from_rows(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Value, String> {
+    let rt = Runtime::from_any(runtime).ok_or("runtime is required for table handles")?;
+    let rows = match inputs.get("rows") {
+        Some(Value::Array(rows)) => rows,
+        _ => return Err("rows must be a list of objects".to_string()),
+    };
+    Ok(Table::from_rows(rows)?.into_handle(rt))
+}
+
+impl NodeExecutor for TableFromRows {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match from_rows(&inputs, runtime) {
+            Ok(handle) => {
+                output.insert("count".to_string(), handle["rows"].clone());
+                output.insert("columns".to_string(), handle["columns"].clone());
+                output.insert("table".to_string(), handle);
+            }
+            Err(e) => {
+                output.insert("table".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new TableFromRows instance.
+pub fn create() -> TableFromRows {
+    TableFromRows::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_rows() {
+        let runtime = Runtime::new();
+        let executor = TableFromRows::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("rows".to_string(), serde_json::json!([{"id": 1}, {"id": 2}]));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("count"), Some(&serde_json::json!(2)));
+        assert_eq!(result.get("columns"), Some(&serde_json::json!(["id"])));
+        let table = Table::from_value(&result["table"], Some(&runtime)).unwrap();
+        assert_eq!(table.num_rows(), 2);
+    }
+
+    #[test]
+    fn test_from_rows_requires_runtime() {
+        let executor = TableFromRows::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("rows".to_string(), serde_json::json!([]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("runtime is required for table handles")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "table.from_rows");
+        assert_eq!(executor.category, "table");
+    }
+}
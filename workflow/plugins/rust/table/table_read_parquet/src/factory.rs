@@ -0,0 +1,8 @@
+//! Factory for TableReadParquet plugin.
+
+use super::TableReadParquet;
+
+/// Creates a new TableReadParquet instance.
+pub fn create() -> TableReadParquet {
+    TableReadParquet::new()
+}
@@ -0,0 +1,192 @@
+//! Workflow plugin: read rows from a Parquet file.
+//!
+//! Only the requested `columns` are decoded, and `offset` and `limit` are
+//! applied while reading, so a node can sample a large file cheaply. With
+//! `as_table`, the rows stay in Arrow form and `table` is a handle for other
+//! `table.*` nodes instead of JSON `rows`.
+
+use arrow_array::RecordBatchReader;
+use metabuilder_core::Runtime;
+use metabuilder_table::Table;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ProjectionMask;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs::File;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TableReadParquet implements the NodeExecutor trait for reading Parquet files.
+pub struct TableReadParquet {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TableReadParquet {
+    /// Creates a new TableReadParquet instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "table.read_parquet",
+            category: "table",
+            description: "Read rows from a Parquet file",
+        }
+    }
+}
+
+impl Default for TableReadParquet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn input<T: serde::de::DeserializeOwned>(inputs: &HashMap<String, Value>, key: &str) -> Option<T> {
+    inputs.get(key).and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+fn read(inputs: &HashMap<String, Value>) -> Result<Table, String> {
+    let path: String = input(inputs, "path").ok_or("path is required")?;
+    let file = File::open(&path).map_err(|e| format!("cannot open {}: {}", path, e))?;
+    let mut builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| e.to_string())?;
+
+    if let Some(columns) = input::<Vec<String>>(inputs, "columns") {
+        let fields = builder.schema().fields();
+        let indices = columns
+            .iter()
+            .map(|name| {
+                fields
+                    .iter()
+                    .position(|f| f.name() == name)
+                    .ok_or_else(|| format!("unknown column: {}", name))
+            })
+            .collect::<Result<Vec<usize>, String>>()?;
+        let mask = ProjectionMask::roots(builder.parquet_schema(), indices);
+        builder = builder.with_projection(mask);
+    }
+    if let Some(offset) = input::<usize>(inputs, "offset") {
+        builder = builder.with_offset(offset);
+    }
+    if let Some(limit) = input::<usize>(inputs, "limit") {
+        builder = builder.with_limit(limit);
+    }
+
+    let reader = builder.build().map_err(|e| e.to_string())?;
+    let schema = reader.schema();
+    let batches = reader.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?;
+    Ok(Table::new(schema, batches))
+}
+
+/// Adds the table to `output`, as a handle or as JSON rows.
+fn emit(table: Table, inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>, output: &mut HashMap<String, Value>) -> Result<(), String> {
+    output.insert("count".to_string(), serde_json::json!(table.num_rows()));
+    output.insert("columns".to_string(), serde_json::json!(table.column_names()));
+    if input::<bool>(inputs, "as_table").unwrap_or(false) {
+        let rt = Runtime::from_any(runtime).ok_or("runtime is required for as_table")?;
+        output.insert("table".to_string(), table.into_handle(rt));
+    } else {
+        output.insert("rows".to_string(), Value::Array(table.to_rows()?));
+    }
+    Ok(())
+}
+
+impl NodeExecutor for TableReadParquet {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        if let Err(e) = read(&inputs).and_then(|table| emit(table, &inputs, runtime, &mut output)) {
+            output.insert("rows".to_string(), Value::Null);
+            output.insert("error".to_string(), serde_json::json!(e));
+        }
+
+        output
+    }
+}
+
+/// Creates a new TableReadParquet instance.
+pub fn create() -> TableReadParquet {
+    TableReadParquet::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+    use parquet::arrow::ArrowWriter;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    /// Writes ids 0..10 with names `n0`..`n9`, two rows per row group.
+    fn sample(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}_{}.parquet", name, std::process::id()));
+        let ids: ArrayRef = Arc::new(Int64Array::from_iter_values(0..10));
+        let names: ArrayRef = Arc::new(StringArray::from_iter_values((0..10).map(|i| format!("n{}", i))));
+        let batch = RecordBatch::try_from_iter([("id", ids), ("name", names)]).unwrap();
+        let properties = parquet::file::properties::WriterProperties::builder()
+            .set_max_row_group_size(2)
+            .build();
+        let mut writer = ArrowWriter::try_new(File::create(&path).unwrap(), batch.schema(), Some(properties)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_parquet_projection_and_limit() {
+        let path = sample("table_read_parquet_projection");
+        let executor = TableReadParquet::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("path".to_string(), serde_json::json!(path.to_str().unwrap()));
+        inputs.insert("columns".to_string(), serde_json::json!(["name"]));
+        inputs.insert("offset".to_string(), serde_json::json!(3));
+        inputs.insert("limit".to_string(), serde_json::json!(2));
+
+        let result = executor.execute(inputs, None);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(result.get("rows"), Some(&serde_json::json!([{"name": "n3"}, {"name": "n4"}])));
+        assert_eq!(result.get("columns"), Some(&serde_json::json!(["name"])));
+        assert_eq!(result.get("count"), Some(&serde_json::json!(2)));
+    }
+
+    #[test]
+    fn test_read_parquet_as_table() {
+        let path = sample("table_read_parquet_as_table");
+        let runtime = Runtime::new();
+        let executor = TableReadParquet::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("path".to_string(), serde_json::json!(path.to_str().unwrap()));
+        inputs.insert("as_table".to_string(), serde_json::json!(true));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(result.get("rows"), None);
+        let handle = result.get("table").unwrap();
+        assert_eq!(handle["rows"], serde_json::json!(10));
+        let table = Table::from_value(handle, Some(&runtime)).unwrap();
+        assert_eq!(table.num_rows(), 10);
+    }
+
+    #[test]
+    fn test_read_parquet_unknown_column() {
+        let path = sample("table_read_parquet_unknown");
+        let executor = TableReadParquet::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("path".to_string(), serde_json::json!(path.to_str().unwrap()));
+        inputs.insert("columns".to_string(), serde_json::json!(["email"]));
+
+        let result = executor.execute(inputs, None);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(result.get("error"), Some(&serde_json::json!("unknown column: email")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "table.read_parquet");
+        assert_eq!(executor.category, "table");
+    }
+}
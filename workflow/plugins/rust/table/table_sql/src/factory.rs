@@ -0,0 +1,8 @@
+//! Factory for TableSql plugin.
+
+use super::TableSql;
+
+/// Creates a new TableSql instance.
+pub fn create() -> TableSql {
+    TableSql::new()
+}
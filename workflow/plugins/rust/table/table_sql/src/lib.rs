@@ -0,0 +1,426 @@
+//! Workflow plugin: run a SQL query over named input tables.
+//!
+//! Each entry of `tables` (a table handle or a list of row objects) is
+//! exposed to an in-memory SQLite database under its name, so one query can
+//! join, filter, and aggregate several inputs. The tables are virtual: SQLite
+//! reads the Arrow batches in place, a cell at a time, so handles are never
+//! copied into the database. Booleans read as 0/1, and nested lists and
+//! objects as JSON text, which SQLite's JSON functions can read.
+//!
+//! Queries use SQLite's dialect and its dynamic typing, so dates stay text,
+//! decimals become REAL, and comparisons follow SQLite's type affinity rules.
+//! Only statements that leave the database unchanged run: writes (including
+//! `VACUUM INTO`) are rejected, and `ATTACH` fails because no database may be
+//! attached, so a query cannot reach the filesystem.
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type};
+use arrow_array::types::{Float32Type, Float64Type};
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_json::writer::JsonArray;
+use arrow_json::WriterBuilder;
+use arrow_schema::{DataType, Field, Schema};
+use base64::Engine as _;
+use metabuilder_core::Runtime;
+use metabuilder_table::Table;
+use rusqlite::ffi;
+use rusqlite::limits::Limit;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::vtab::{self, Context, CreateVTab, Filters, IndexInfo, VTab, VTabConnection, VTabCursor, VTabKind};
+use rusqlite::Connection;
+use serde_json::{Map, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::c_int;
+use std::sync::Arc;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TableSql implements the NodeExecutor trait for SQL queries over tables.
+pub struct TableSql {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TableSql {
+    /// Creates a new TableSql instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "table.sql",
+            category: "table",
+            description: "Run a SQL query over named input tables",
+        }
+    }
+}
+
+impl Default for TableSql {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Integer(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        Value::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    }
+}
+
+fn from_sql(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f).map(Value::Number).unwrap_or(Value::Null),
+        ValueRef::Text(bytes) => Value::String(String::from_utf8_lossy(bytes).into_owned()),
+        ValueRef::Blob(bytes) => Value::String(base64::engine::general_purpose::STANDARD.encode(bytes)),
+    }
+}
+
+/// The value of `column` at `row` as SQLite sees it. Scalars convert
+/// directly; other types go through their JSON form, as `table.to_rows`
+/// would give them.
+fn cell(column: &ArrayRef, row: usize) -> Result<SqlValue, String> {
+    if column.is_null(row) {
+        return Ok(SqlValue::Null);
+    }
+    let value = match column.data_type() {
+        DataType::Boolean => SqlValue::Integer(column.as_boolean().value(row) as i64),
+        DataType::Int8 => SqlValue::Integer(column.as_primitive::<Int8Type>().value(row).into()),
+        DataType::Int16 => SqlValue::Integer(column.as_primitive::<Int16Type>().value(row).into()),
+        DataType::Int32 => SqlValue::Integer(column.as_primitive::<Int32Type>().value(row).into()),
+        DataType::Int64 => SqlValue::Integer(column.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt8 => SqlValue::Integer(column.as_primitive::<UInt8Type>().value(row).into()),
+        DataType::UInt16 => SqlValue::Integer(column.as_primitive::<UInt16Type>().value(row).into()),
+        DataType::UInt32 => SqlValue::Integer(column.as_primitive::<UInt32Type>().value(row).into()),
+        DataType::UInt64 => {
+            let value = column.as_primitive::<UInt64Type>().value(row);
+            i64::try_from(value).map_or(SqlValue::Real(value as f64), SqlValue::Integer)
+        }
+        DataType::Float32 => SqlValue::Real(column.as_primitive::<Float32Type>().value(row).into()),
+        DataType::Float64 => SqlValue::Real(column.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => SqlValue::Text(column.as_string::<i32>().value(row).to_string()),
+        DataType::LargeUtf8 => SqlValue::Text(column.as_string::<i64>().value(row).to_string()),
+        _ => {
+            let field = Field::new("value", column.data_type().clone(), true);
+            let batch = RecordBatch::try_new(Arc::new(Schema::new(vec![field])), vec![column.slice(row, 1)])
+                .map_err(|e| e.to_string())?;
+            let mut writer = WriterBuilder::new().build::<_, JsonArray>(Vec::new());
+            writer.write(&batch).map_err(|e| e.to_string())?;
+            writer.finish().map_err(|e| e.to_string())?;
+            let rows: Value = serde_json::from_slice(&writer.into_inner()).map_err(|e| e.to_string())?;
+            to_sql(&rows[0]["value"])
+        }
+    };
+    Ok(value)
+}
+
+/// A `tables` entry as a read-only SQLite virtual table. The module's aux
+/// data holds every table of the query; `CREATE VIRTUAL TABLE ... USING
+/// arrow(i)` picks the `i`th.
+#[repr(C)]
+struct ArrowTab {
+    base: ffi::sqlite3_vtab,
+    table: Arc<Table>,
+}
+
+unsafe impl<'vtab> VTab<'vtab> for ArrowTab {
+    type Aux = Vec<Arc<Table>>;
+    type Cursor = ArrowCursor<'vtab>;
+
+    fn connect(
+        _: &mut VTabConnection,
+        aux: Option<&Vec<Arc<Table>>>,
+        args: &[&[u8]],
+    ) -> rusqlite::Result<(String, Self)> {
+        let table = args
+            .get(3)
+            .and_then(|arg| std::str::from_utf8(arg).ok())
+            .and_then(|arg| arg.trim().parse::<usize>().ok())
+            .and_then(|index| aux?.get(index))
+            .ok_or_else(|| rusqlite::Error::ModuleError("no such input table".to_string()))?;
+        let columns = table.column_names().iter().map(|c| quote(c)).collect::<Vec<_>>().join(", ");
+        let vtab = ArrowTab {
+            base: ffi::sqlite3_vtab::default(),
+            table: table.clone(),
+        };
+        Ok((format!("CREATE TABLE x({})", columns), vtab))
+    }
+
+    fn best_index(&self, info: &mut IndexInfo) -> rusqlite::Result<()> {
+        let rows = self.table.num_rows();
+        info.set_estimated_rows(rows as i64);
+        info.set_estimated_cost(rows as f64);
+        Ok(())
+    }
+
+    fn open(&'vtab mut self) -> rusqlite::Result<ArrowCursor<'vtab>> {
+        Ok(ArrowCursor {
+            base: ffi::sqlite3_vtab_cursor::default(),
+            table: &self.table,
+            batch: 0,
+            row: 0,
+            rowid: 0,
+        })
+    }
+}
+
+impl CreateVTab<'_> for ArrowTab {
+    const KIND: VTabKind = VTabKind::Default;
+}
+
+/// A scan over the batches of an `ArrowTab`, row by row.
+#[repr(C)]
+struct ArrowCursor<'vtab> {
+    base: ffi::sqlite3_vtab_cursor,
+    table: &'vtab Table,
+    batch: usize,
+    row: usize,
+    rowid: i64,
+}
+
+impl ArrowCursor<'_> {
+    /// Moves past empty batches, to the next row if there is one.
+    fn settle(&mut self) {
+        while self.batch < self.table.batches.len() && self.row >= self.table.batches[self.batch].num_rows() {
+            self.batch += 1;
+            self.row = 0;
+        }
+    }
+}
+
+unsafe impl VTabCursor for ArrowCursor<'_> {
+    fn filter(&mut self, _: c_int, _: Option<&str>, _: &Filters<'_>) -> rusqlite::Result<()> {
+        self.batch = 0;
+        self.row = 0;
+        self.rowid = 0;
+        self.settle();
+        Ok(())
+    }
+
+    fn next(&mut self) -> rusqlite::Result<()> {
+        self.row += 1;
+        self.rowid += 1;
+        self.settle();
+        Ok(())
+    }
+
+    fn eof(&self) -> bool {
+        self.batch >= self.table.batches.len()
+    }
+
+    fn column(&self, ctx: &mut Context, index: c_int) -> rusqlite::Result<()> {
+        let column = self.table.batches[self.batch].column(index as usize);
+        let value = cell(column, self.row).map_err(rusqlite::Error::ModuleError)?;
+        ctx.set_result(&value)
+    }
+
+    fn rowid(&self) -> rusqlite::Result<i64> {
+        Ok(self.rowid)
+    }
+}
+
+/// Returns the result rows and column names.
+fn query(inputs: &HashMap<String, Value>, rt: Option<&Runtime>) -> Result<(Vec<Value>, Vec<String>), String> {
+    let sql = inputs.get("query").and_then(Value::as_str).ok_or("query is required")?;
+    let tables = match inputs.get("tables") {
+        Some(Value::Object(tables)) => tables.clone(),
+        Some(_) => return Err("tables must be an object of name to table".to_string()),
+        None => Map::new(),
+    };
+    let params: Vec<SqlValue> = match inputs.get("params") {
+        Some(Value::Array(params)) => params.iter().map(to_sql).collect(),
+        Some(_) => return Err("params must be a list".to_string()),
+        None => Vec::new(),
+    };
+
+    let mut inputs = Vec::with_capacity(tables.len());
+    for (name, value) in &tables {
+        let table = Table::from_value(value, rt).map_err(|e| format!("table {}: {}", name, e))?;
+        if table.column_names().is_empty() {
+            return Err(format!("table {} has no columns", name));
+        }
+        inputs.push(table);
+    }
+    let conn = Connection::open_in_memory().map_err(|e| e.to_string())?;
+    conn.set_limit(Limit::SQLITE_LIMIT_ATTACHED, 0).map_err(|e| e.to_string())?;
+    conn.create_module(c"arrow", vtab::read_only_module::<ArrowTab>(), Some(inputs))
+        .map_err(|e| e.to_string())?;
+    for (index, name) in tables.keys().enumerate() {
+        conn.execute(&format!("CREATE VIRTUAL TABLE {} USING arrow({})", quote(name), index), [])
+            .map_err(|e| format!("cannot create table {}: {}", name, e))?;
+    }
+
+    let mut statement = conn.prepare(sql).map_err(|e| format!("invalid query: {}", e))?;
+    if !statement.readonly() {
+        return Err("query must not modify the database".to_string());
+    }
+    let columns: Vec<String> = statement.column_names().into_iter().map(String::from).collect();
+    let mut rows = statement
+        .query(rusqlite::params_from_iter(params))
+        .map_err(|e| e.to_string())?;
+    let mut result = Vec::new();
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        let mut object = Map::new();
+        for (index, column) in columns.iter().enumerate() {
+            let value = row.get_ref(index).map_err(|e| e.to_string())?;
+            object.insert(column.clone(), from_sql(value));
+        }
+        result.push(Value::Object(object));
+    }
+    Ok((result, columns))
+}
+
+/// Adds the result to `output`, as a table handle with `as_table` or as JSON rows.
+fn run(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>, output: &mut HashMap<String, Value>) -> Result<(), String> {
+    let rt = Runtime::from_any(runtime);
+    let (rows, columns) = query(inputs, rt)?;
+    output.insert("count".to_string(), serde_json::json!(rows.len()));
+    output.insert("columns".to_string(), serde_json::json!(columns));
+    if inputs.get("as_table").and_then(Value::as_bool).unwrap_or(false) {
+        let rt = rt.ok_or("runtime is required for as_table")?;
+        output.insert("table".to_string(), Table::from_rows(&rows)?.into_handle(rt));
+    } else {
+        output.insert("rows".to_string(), Value::Array(rows));
+    }
+    Ok(())
+}
+
+impl NodeExecutor for TableSql {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        if let Err(e) = run(&inputs, runtime, &mut output) {
+            output.insert("rows".to_string(), Value::Null);
+            output.insert("error".to_string(), serde_json::json!(e));
+        }
+
+        output
+    }
+}
+
+/// Creates a new TableSql instance.
+pub fn create() -> TableSql {
+    TableSql::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_join_and_aggregate() {
+        let runtime = Runtime::new();
+        let orders = Table::from_rows(&[
+            serde_json::json!({"customer": 1, "total": 10.5}),
+            serde_json::json!({"customer": 1, "total": 4.5}),
+            serde_json::json!({"customer": 2, "total": 7}),
+        ])
+        .unwrap()
+        .into_handle(&runtime);
+        let executor = TableSql::new();
+        let mut inputs = HashMap::new();
+        inputs.insert(
+            "tables".to_string(),
+            serde_json::json!({
+                "orders": orders,
+                "customers": [{"id": 1, "name": "ada"}, {"id": 2, "name": "bo"}]
+            }),
+        );
+        inputs.insert(
+            "query".to_string(),
+            serde_json::json!(
+                "SELECT c.name, SUM(o.total) AS spent FROM orders o JOIN customers c ON c.id = o.customer \
+                 GROUP BY c.name HAVING spent > ? ORDER BY spent DESC"
+            ),
+        );
+        inputs.insert("params".to_string(), serde_json::json!([5]));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), None);
+        assert_eq!(
+            result.get("rows"),
+            Some(&serde_json::json!([{"name": "ada", "spent": 15.0}, {"name": "bo", "spent": 7.0}]))
+        );
+        assert_eq!(result.get("columns"), Some(&serde_json::json!(["name", "spent"])));
+    }
+
+    #[test]
+    fn test_sql_invalid_query() {
+        let executor = TableSql::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("tables".to_string(), serde_json::json!({"t": [{"x": 1}]}));
+        inputs.insert("query".to_string(), serde_json::json!("SELECT y FROM t"));
+
+        let result = executor.execute(inputs, None);
+        assert!(result["error"].as_str().unwrap().starts_with("invalid query: no such column: y"));
+    }
+
+    #[test]
+    fn test_sql_nested_and_empty_batches() {
+        let runtime = Runtime::new();
+        let mut table = Table::from_rows(&[
+            serde_json::json!({"id": 1, "tags": ["a", "b"], "ok": true}),
+            serde_json::json!({"id": 2, "tags": [], "ok": false}),
+        ])
+        .unwrap();
+        table.batches.insert(0, table.batches[0].slice(0, 0));
+        let executor = TableSql::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("tables".to_string(), serde_json::json!({"t": table.into_handle(&runtime)}));
+        inputs.insert(
+            "query".to_string(),
+            serde_json::json!("SELECT id, json_array_length(tags) AS n, ok FROM t ORDER BY id"),
+        );
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), None);
+        assert_eq!(
+            result.get("rows"),
+            Some(&serde_json::json!([{"id": 1, "n": 2, "ok": 1}, {"id": 2, "n": 0, "ok": 0}]))
+        );
+    }
+
+    #[test]
+    fn test_sql_rejects_writes() {
+        let dir = std::env::temp_dir().join(format!("metabuilder-table-sql-{}", std::process::id()));
+        let path = dir.join("copy.db");
+        let executor = TableSql::new();
+        let run = |query: String| {
+            let mut inputs = HashMap::new();
+            inputs.insert("tables".to_string(), serde_json::json!({"t": [{"x": 1}]}));
+            inputs.insert("query".to_string(), serde_json::json!(query));
+            executor.execute(inputs, None)["error"].as_str().unwrap_or_default().to_string()
+        };
+
+        assert!(run("DELETE FROM t".to_string()).ends_with("table t may not be modified"));
+        assert_eq!(run("CREATE TABLE u (y)".to_string()), "query must not modify the database");
+        assert_eq!(
+            run(format!("VACUUM INTO '{}'", path.display())),
+            "query must not modify the database"
+        );
+        assert!(run(format!("ATTACH '{}' AS other", path.display())).contains("too many attached databases"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "table.sql");
+        assert_eq!(executor.category, "table");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for TableToRows plugin.
+
+use super::TableToRows;
+
+/// Creates a new TableToRows instance.
+pub fn create() -> TableToRows {
+    TableToRows::new()
+}
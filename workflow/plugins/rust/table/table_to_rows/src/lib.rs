@@ -0,0 +1,126 @@
+//! Workflow plugin: convert a table handle to JSON rows.
+//!
+//! Use at the edge of a table pipeline, where later nodes need plain values.
+//! `limit` caps the number of rows converted.
+
+use metabuilder_core::Runtime;
+use metabuilder_table::Table;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TableToRows implements the NodeExecutor trait for converting tables to JSON.
+pub struct TableToRows {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TableToRows {
+    /// Creates a new TableToRows instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "table.to_rows",
+            category: "table",
+            description: "Convert a table handle to JSON rows",
+        }
+    }
+}
+
+impl Default for TableToRows {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_rows(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<Vec<Value>, String> {
+    let value = inputs.get("table").ok_or("table is required")?;
+    let table = Table::from_value(value, Runtime::from_any(runtime))?;
+    let limit = inputs.get("limit").and_then(Value::as_u64).map(|n| n as usize);
+
+    match limit {
+        Some(limit) if limit < table.num_rows() => {
+            // Slicing a batch is zero-copy, so only the kept rows are converted.
+            let mut batches = Vec::new();
+            let mut remaining = limit;
+            for batch in &table.batches {
+                if remaining == 0 {
+                    break;
+                }
+                let take = remaining.min(batch.num_rows());
+                batches.push(batch.slice(0, take));
+                remaining -= take;
+            }
+            Table::new(table.schema.clone(), batches).to_rows()
+        }
+        _ => table.to_rows(),
+    }
+}
+
+impl NodeExecutor for TableToRows {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match to_rows(&inputs, runtime) {
+            Ok(rows) => {
+                output.insert("count".to_string(), serde_json::json!(rows.len()));
+                output.insert("rows".to_string(), Value::Array(rows));
+            }
+            Err(e) => {
+                output.insert("rows".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new TableToRows instance.
+pub fn create() -> TableToRows {
+    TableToRows::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_rows_with_limit() {
+        let runtime = Runtime::new();
+        let rows: Vec<Value> = (0..5).map(|i| serde_json::json!({"id": i})).collect();
+        let handle = Table::from_rows(&rows).unwrap().into_handle(&runtime);
+        let executor = TableToRows::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("table".to_string(), handle);
+        inputs.insert("limit".to_string(), serde_json::json!(2));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("rows"), Some(&serde_json::json!([{"id": 0}, {"id": 1}])));
+        assert_eq!(result.get("count"), Some(&serde_json::json!(2)));
+    }
+
+    #[test]
+    fn test_to_rows_unknown_handle() {
+        let runtime = Runtime::new();
+        let executor = TableToRows::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("table".to_string(), serde_json::json!({"$table": 4}));
+
+        let result = executor.execute(inputs, Some(&runtime));
+        assert_eq!(result.get("error"), Some(&serde_json::json!("unknown table handle: 4")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "table.to_rows");
+        assert_eq!(executor.category, "table");
+    }
+}
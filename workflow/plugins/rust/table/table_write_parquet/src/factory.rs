@@ -0,0 +1,8 @@
+//! Factory for TableWriteParquet plugin.
+
+use super::TableWriteParquet;
+
+/// Creates a new TableWriteParquet instance.
+pub fn create() -> TableWriteParquet {
+    TableWriteParquet::new()
+}
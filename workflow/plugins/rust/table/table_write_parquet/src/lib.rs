@@ -0,0 +1,144 @@
+//! Workflow plugin: write rows to a Parquet file.
+//!
+//! `table` is a table handle from another `table.*` node, or a list of
+//! objects (also accepted as `rows`). The column types of plain rows are
+//! inferred, so a column whose values mix numbers and strings is an error.
+
+use metabuilder_core::Runtime;
+use metabuilder_table::Table;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs::File;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TableWriteParquet implements the NodeExecutor trait for writing Parquet files.
+pub struct TableWriteParquet {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TableWriteParquet {
+    /// Creates a new TableWriteParquet instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "table.write_parquet",
+            category: "table",
+            description: "Write rows to a Parquet file",
+        }
+    }
+}
+
+impl Default for TableWriteParquet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn compression(name: &str) -> Result<Compression, String> {
+    match name {
+        "none" => Ok(Compression::UNCOMPRESSED),
+        "snappy" => Ok(Compression::SNAPPY),
+        other => Err(format!("unknown compression: {} (expected none or snappy)", other)),
+    }
+}
+
+/// Writes the table to `path`, returning the number of rows written.
+fn write(inputs: &HashMap<String, Value>, runtime: Option<&dyn Any>) -> Result<usize, String> {
+    let path = inputs.get("path").and_then(Value::as_str).ok_or("path is required")?;
+    let value = inputs
+        .get("table")
+        .or(inputs.get("rows"))
+        .ok_or("table or rows is required")?;
+    let table = Table::from_value(value, Runtime::from_any(runtime))?;
+    let compression = compression(inputs.get("compression").and_then(Value::as_str).unwrap_or("snappy"))?;
+
+    let file = File::create(path).map_err(|e| format!("cannot create {}: {}", path, e))?;
+    let properties = WriterProperties::builder().set_compression(compression).build();
+    let mut writer = ArrowWriter::try_new(file, table.schema.clone(), Some(properties)).map_err(|e| e.to_string())?;
+    for batch in &table.batches {
+        writer.write(batch).map_err(|e| e.to_string())?;
+    }
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(table.num_rows())
+}
+
+impl NodeExecutor for TableWriteParquet {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match write(&inputs, runtime) {
+            Ok(count) => {
+                output.insert("success".to_string(), serde_json::json!(true));
+                output.insert("count".to_string(), serde_json::json!(count));
+                output.insert("path".to_string(), inputs.get("path").cloned().unwrap_or(Value::Null));
+            }
+            Err(e) => {
+                output.insert("success".to_string(), serde_json::json!(false));
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new TableWriteParquet instance.
+pub fn create() -> TableWriteParquet {
+    TableWriteParquet::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    #[test]
+    fn test_write_parquet() {
+        let path = std::env::temp_dir().join(format!("table_write_parquet_{}.parquet", std::process::id()));
+        let executor = TableWriteParquet::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("path".to_string(), serde_json::json!(path.to_str().unwrap()));
+        inputs.insert(
+            "rows".to_string(),
+            serde_json::json!([{"id": 1, "name": "ada"}, {"id": 2, "name": null}, {"id": 3}]),
+        );
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), None);
+        assert_eq!(result.get("count"), Some(&serde_json::json!(3)));
+
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 3);
+        assert_eq!(metadata.schema_descr().num_columns(), 2);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_write_parquet_rejects_non_objects() {
+        let executor = TableWriteParquet::new();
+        let mut inputs = HashMap::new();
+        inputs.insert("path".to_string(), serde_json::json!("unused.parquet"));
+        inputs.insert("rows".to_string(), serde_json::json!([{"id": 1}, 2]));
+
+        let result = executor.execute(inputs, None);
+        assert_eq!(result.get("error"), Some(&serde_json::json!("row 1 is not an object")));
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "table.write_parquet");
+        assert_eq!(executor.category, "table");
+    }
+}
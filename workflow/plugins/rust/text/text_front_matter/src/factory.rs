@@ -0,0 +1,8 @@
+//! Factory for TextFrontMatter plugin.
+
+use super::TextFrontMatter;
+
+/// Creates a new TextFrontMatter instance.
+pub fn create() -> TextFrontMatter {
+    TextFrontMatter::new()
+}
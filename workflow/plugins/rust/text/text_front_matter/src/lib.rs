@@ -0,0 +1,156 @@
+//! Workflow plugin: split front matter from a Markdown document.
+//!
+//! Front matter is a block at the very start of `text`, fenced by `---`
+//! lines for YAML or `+++` lines for TOML. Outputs `data`, the parsed front
+//! matter (an empty object if there is none), `body`, the text after the
+//! closing fence, and `format`: `"yaml"`, `"toml"`, or `null`.
+
+use metabuilder_core::{toml, yaml};
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TextFrontMatter implements the NodeExecutor trait for front matter extraction.
+pub struct TextFrontMatter {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TextFrontMatter {
+    /// Creates a new TextFrontMatter instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "text.front_matter",
+            category: "text",
+            description: "Split YAML or TOML front matter from a Markdown body",
+        }
+    }
+}
+
+impl Default for TextFrontMatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parsed front matter, the remaining body, and the front matter format.
+type FrontMatter<'a> = (Value, &'a str, Option<&'static str>);
+
+fn split(inputs: &HashMap<String, Value>) -> Result<FrontMatter<'_>, String> {
+    let text = inputs.get("text").and_then(Value::as_str).ok_or("text is required")?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let first_line = text.lines().next().unwrap_or("");
+    let (fence, format) = match first_line.trim_end() {
+        "---" => ("---", "yaml"),
+        "+++" => ("+++", "toml"),
+        _ => return Ok((Value::Object(Default::default()), text, None)),
+    };
+
+    let start = text.find('\n').map_or(text.len(), |i| i + 1);
+    let mut offset = start;
+    for line in text[start..].split_inclusive('\n') {
+        let fence_line = line.trim_end();
+        // YAML may also close with a document end marker.
+        if fence_line == fence || (format == "yaml" && fence_line == "...") {
+            let source = &text[start..offset];
+            let data = match format {
+                "yaml" => yaml::parse(source),
+                _ => toml::parse(source),
+            }
+            .map_err(|e| format!("invalid {} front matter: {}", format, e))?;
+            let data = match data {
+                Value::Null => Value::Object(Default::default()),
+                Value::Object(map) => Value::Object(map),
+                _ => return Err(format!("{} front matter must be a mapping", format)),
+            };
+            return Ok((data, &text[offset + line.len()..], Some(format)));
+        }
+        offset += line.len();
+    }
+    Err(format!("front matter is not closed with {}", fence))
+}
+
+impl NodeExecutor for TextFrontMatter {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        match split(&inputs) {
+            Ok((data, body, format)) => {
+                output.insert("data".to_string(), data);
+                output.insert("body".to_string(), serde_json::json!(body));
+                output.insert("format".to_string(), serde_json::json!(format));
+            }
+            Err(e) => {
+                output.insert("data".to_string(), Value::Null);
+                output.insert("error".to_string(), serde_json::json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new TextFrontMatter instance.
+pub fn create() -> TextFrontMatter {
+    TextFrontMatter::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str) -> HashMap<String, Value> {
+        let mut inputs = HashMap::new();
+        inputs.insert("text".to_string(), serde_json::json!(text));
+        TextFrontMatter::new().execute(inputs, None)
+    }
+
+    #[test]
+    fn test_yaml_front_matter() {
+        let output = run("---\ntitle: Hello\ntags: [rust, docs]\n---\n# Hello\n\nBody text.\n");
+        assert_eq!(output["data"], serde_json::json!({"title": "Hello", "tags": ["rust", "docs"]}));
+        assert_eq!(output["body"], "# Hello\n\nBody text.\n");
+        assert_eq!(output["format"], "yaml");
+    }
+
+    #[test]
+    fn test_toml_front_matter() {
+        let output = run("\u{feff}+++\r\ntitle = \"Hello\"\r\n[extra]\r\nweight = 3\r\n+++\r\nBody\r\n");
+        assert_eq!(output["data"], serde_json::json!({"title": "Hello", "extra": {"weight": 3}}));
+        assert_eq!(output["body"], "Body\r\n");
+        assert_eq!(output["format"], "toml");
+    }
+
+    #[test]
+    fn test_without_front_matter() {
+        let output = run("# Title\n---\nnot front matter\n");
+        assert_eq!(output["data"], serde_json::json!({}));
+        assert_eq!(output["body"], "# Title\n---\nnot front matter\n");
+        assert_eq!(output["format"], Value::Null);
+        assert_eq!(run("---\n---\nbody")["data"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(run("---\ntitle: x\n")["error"], "front matter is not closed with ---");
+        assert_eq!(run("---\n- a\n---\n")["error"], "yaml front matter must be a mapping");
+        assert_eq!(
+            run("+++\ntitle = \n+++\n")["error"],
+            "invalid toml front matter: string values must be quoted, expected literal string at line 1"
+        );
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "text.front_matter");
+        assert_eq!(executor.category, "text");
+    }
+}
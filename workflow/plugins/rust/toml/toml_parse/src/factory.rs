@@ -0,0 +1,8 @@
+//! Factory for TomlParse plugin.
+
+use super::TomlParse;
+
+/// Creates a new TomlParse instance.
+pub fn create() -> TomlParse {
+    TomlParse::new()
+}
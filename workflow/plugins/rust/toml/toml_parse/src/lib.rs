@@ -0,0 +1,115 @@
+//! Workflow plugin: parse TOML text to an object.
+//!
+//! Reads Cargo.toml-style configuration: parses `string` as a TOML document
+//! with `metabuilder_core::toml`, built on the `toml` crate, and outputs the
+//! object in `result`. Dates and times become RFC 3339 strings, as JSON has
+//! no date type, and `inf` or `nan`, which JSON cannot hold, are an error.
+//! On failure, `error` names the line, which is also output as `line`.
+
+use metabuilder_core::toml;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TomlParse implements the NodeExecutor trait for TOML parsing.
+pub struct TomlParse {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TomlParse {
+    /// Creates a new TomlParse instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "toml.parse",
+            category: "toml",
+            description: "Parse TOML text to an object",
+        }
+    }
+}
+
+impl Default for TomlParse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for TomlParse {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let Some(string) = inputs.get("string").and_then(Value::as_str) else {
+            output.insert("result".to_string(), Value::Null);
+            output.insert("error".to_string(), json!("string is required"));
+            return output;
+        };
+        match toml::parse(string) {
+            Ok(value) => {
+                output.insert("result".to_string(), value);
+            }
+            Err(e) => {
+                let line = e.rsplit_once(" at line ").and_then(|(_, line)| line.parse::<u64>().ok());
+                output.insert("result".to_string(), Value::Null);
+                output.insert("line".to_string(), json!(line));
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new TomlParse instance.
+pub fn create() -> TomlParse {
+    TomlParse::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(string: &str) -> HashMap<String, Value> {
+        TomlParse::new().execute(HashMap::from([("string".to_string(), json!(string))]), None)
+    }
+
+    #[test]
+    fn test_parse() {
+        let output = run(
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n\
+             [dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\n",
+        );
+        assert_eq!(
+            output["result"],
+            json!({
+                "package": {"name": "app", "version": "0.1.0"},
+                "dependencies": {"serde": {"version": "1", "features": ["derive"]}}
+            })
+        );
+        assert_eq!(run("")["result"], json!({}));
+    }
+
+    #[test]
+    fn test_errors() {
+        let output = run("a = 1\na = 2\n");
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "duplicate key at line 2");
+        assert_eq!(output["line"], 2);
+        assert_eq!(run("[limits]\nmax = inf\n")["error"], "inf has no JSON value, found at limits.max");
+        let output = TomlParse::new().execute(HashMap::new(), None);
+        assert_eq!(output["error"], "string is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "toml.parse");
+        assert_eq!(executor.category, "toml");
+    }
+}
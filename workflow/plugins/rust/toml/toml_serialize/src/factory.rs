@@ -0,0 +1,8 @@
+//! Factory for TomlSerialize plugin.
+
+use super::TomlSerialize;
+
+/// Creates a new TomlSerialize instance.
+pub fn create() -> TomlSerialize {
+    TomlSerialize::new()
+}
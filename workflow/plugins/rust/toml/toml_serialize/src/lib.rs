@@ -0,0 +1,106 @@
+//! Workflow plugin: write an object as TOML text.
+//!
+//! Rewrites Cargo.toml-style configuration: outputs `value` in `result` as a
+//! TOML document, keys sorted, plain keys first, then a `[table]` section per
+//! nested object and a `[[table]]` section per item of a list of objects.
+//! `toml.parse` reads the output back unchanged. TOML documents are tables
+//! and have no null, so a `value` that is not an object, or that holds a
+//! `null`, fails with `error`.
+
+use metabuilder_core::toml;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// TomlSerialize implements the NodeExecutor trait for TOML output.
+pub struct TomlSerialize {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl TomlSerialize {
+    /// Creates a new TomlSerialize instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "toml.serialize",
+            category: "toml",
+            description: "Write an object as TOML text",
+        }
+    }
+}
+
+impl Default for TomlSerialize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for TomlSerialize {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let value = inputs.get("value").unwrap_or(&Value::Null);
+        let mut output = HashMap::new();
+
+        match toml::to_string(value) {
+            Ok(text) => {
+                output.insert("result".to_string(), json!(text));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new TomlSerialize instance.
+pub fn create() -> TomlSerialize {
+    TomlSerialize::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(value: Value) -> HashMap<String, Value> {
+        TomlSerialize::new().execute(HashMap::from([("value".to_string(), value)]), None)
+    }
+
+    #[test]
+    fn test_serialize() {
+        let value = json!({
+            "package": {"name": "app", "edition": "2021"},
+            "dependencies": {"serde": {"version": "1", "features": ["derive"]}}
+        });
+        let text = run(value.clone())["result"].clone();
+        assert_eq!(
+            text,
+            "[dependencies.serde]\nfeatures = [\"derive\"]\nversion = \"1\"\n\n\
+             [package]\nedition = \"2021\"\nname = \"app\"\n"
+        );
+        assert_eq!(toml::parse(text.as_str().unwrap()).unwrap(), value);
+    }
+
+    #[test]
+    fn test_errors() {
+        let output = run(json!({"a": null}));
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "TOML has no null value, found at a");
+        assert_eq!(run(json!("text"))["error"], "a TOML document must be an object");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "toml.serialize");
+        assert_eq!(executor.category, "toml");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for UuidGenerate plugin.
+
+use super::UuidGenerate;
+
+/// Creates a new UuidGenerate instance.
+pub fn create() -> UuidGenerate {
+    UuidGenerate::new()
+}
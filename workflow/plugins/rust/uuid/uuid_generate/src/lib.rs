@@ -0,0 +1,145 @@
+//! Workflow plugin: generate a UUID.
+//!
+//! `version` is `4` (the default), fully random, or `7`, which starts with
+//! the current Unix time in milliseconds so ids sort by creation time, as
+//! database keys and correlation ids often should. Outputs `result` in the
+//! lowercase hyphenated form. The random bits come from the runtime's
+//! generator, so a seeded engine replays the same v4 ids.
+
+use metabuilder_core::{Random, Runtime};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::{Builder, Uuid};
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// UuidGenerate implements the NodeExecutor trait for generating UUIDs.
+pub struct UuidGenerate {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl UuidGenerate {
+    /// Creates a new UuidGenerate instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "uuid.generate",
+            category: "uuid",
+            description: "Generate a UUID (v4 or v7)",
+        }
+    }
+}
+
+impl Default for UuidGenerate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn generate(inputs: &HashMap<String, Value>, random: &Random) -> Result<Uuid, String> {
+    match inputs.get("version").and_then(Value::as_u64).unwrap_or(4) {
+        4 => {
+            let mut bytes = [0; 16];
+            random.fill(&mut bytes);
+            Ok(Builder::from_random_bytes(bytes).into_uuid())
+        }
+        7 => {
+            let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+            let mut bytes = [0; 10];
+            random.fill(&mut bytes);
+            Ok(Builder::from_unix_timestamp_millis(millis, &bytes).into_uuid())
+        }
+        _ => Err("version must be 4 or 7".to_string()),
+    }
+}
+
+impl NodeExecutor for UuidGenerate {
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let generated = match Runtime::from_any(runtime) {
+            Some(runtime) => generate(&inputs, &runtime.random),
+            None => generate(&inputs, &Random::new()),
+        };
+        match generated {
+            Ok(uuid) => {
+                output.insert("result".to_string(), json!(uuid.hyphenated().to_string()));
+            }
+            Err(e) => {
+                output.insert("result".to_string(), Value::Null);
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new UuidGenerate instance.
+pub fn create() -> UuidGenerate {
+    UuidGenerate::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metabuilder_core::testing::inputs;
+    use std::sync::Arc;
+
+    fn parse(output: &HashMap<String, Value>) -> Uuid {
+        Uuid::parse_str(output["result"].as_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_v4() {
+        let executor = UuidGenerate::new();
+        let uuid = parse(&executor.execute(inputs(json!({})), None));
+        assert_eq!(uuid.get_version_num(), 4);
+        assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+        assert_ne!(parse(&executor.execute(inputs(json!({"version": 4})), None)), uuid);
+    }
+
+    #[test]
+    fn test_v7_sorts_by_time() {
+        let executor = UuidGenerate::new();
+        let first = parse(&executor.execute(inputs(json!({"version": 7})), None));
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = parse(&executor.execute(inputs(json!({"version": 7})), None));
+        assert_eq!(first.get_version_num(), 7);
+        assert!(first.to_string() < second.to_string());
+    }
+
+    #[test]
+    fn test_seeded_runtime_replays() {
+        let executor = UuidGenerate::new();
+        let generate = |seed| {
+            let mut runtime = Runtime::new();
+            runtime.random = Arc::new(Random::seeded(seed));
+            executor.execute(inputs(json!({})), Some(&runtime))["result"].clone()
+        };
+        assert_eq!(generate(9), generate(9));
+        assert_ne!(generate(9), generate(10));
+    }
+
+    #[test]
+    fn test_errors() {
+        let executor = UuidGenerate::new();
+        let output = executor.execute(inputs(json!({"version": 1})), None);
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "version must be 4 or 7");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "uuid.generate");
+        assert_eq!(executor.category, "uuid");
+    }
+}
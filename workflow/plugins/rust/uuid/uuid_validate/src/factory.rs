@@ -0,0 +1,8 @@
+//! Factory for UuidValidate plugin.
+
+use super::UuidValidate;
+
+/// Creates a new UuidValidate instance.
+pub fn create() -> UuidValidate {
+    UuidValidate::new()
+}
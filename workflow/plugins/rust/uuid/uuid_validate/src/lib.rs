@@ -0,0 +1,124 @@
+//! Workflow plugin: check and parse a UUID.
+//!
+//! Accepts `value` in the hyphenated, simple (32 hex digits), braced, or
+//! `urn:uuid:` form, in any case. Outputs `valid`; for a valid UUID also
+//! `result`, its lowercase hyphenated form, `version` (1 to 8, or 0 for the
+//! nil UUID), and `variant` (`rfc4122`, `ncs`, `microsoft`, or `future`).
+//! An invalid one has `reason` instead, and is not an `error`, so workflows
+//! can branch on `valid`.
+
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use uuid::{Uuid, Variant};
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// UuidValidate implements the NodeExecutor trait for checking UUIDs.
+pub struct UuidValidate {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl UuidValidate {
+    /// Creates a new UuidValidate instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "uuid.validate",
+            category: "uuid",
+            description: "Check and parse a UUID, reporting its version and variant",
+        }
+    }
+}
+
+impl Default for UuidValidate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn variant(uuid: &Uuid) -> &'static str {
+    match uuid.get_variant() {
+        Variant::NCS => "ncs",
+        Variant::RFC4122 => "rfc4122",
+        Variant::Microsoft => "microsoft",
+        _ => "future",
+    }
+}
+
+impl NodeExecutor for UuidValidate {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let Some(value) = inputs.get("value").and_then(Value::as_str) else {
+            output.insert("valid".to_string(), json!(false));
+            output.insert("error".to_string(), json!("value is required"));
+            return output;
+        };
+        match Uuid::try_parse(value) {
+            Ok(uuid) => {
+                output.insert("valid".to_string(), json!(true));
+                output.insert("result".to_string(), json!(uuid.hyphenated().to_string()));
+                output.insert("version".to_string(), json!(uuid.get_version_num()));
+                output.insert("variant".to_string(), json!(variant(&uuid)));
+            }
+            Err(e) => {
+                output.insert("valid".to_string(), json!(false));
+                output.insert("reason".to_string(), json!(e.to_string()));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new UuidValidate instance.
+pub fn create() -> UuidValidate {
+    UuidValidate::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(value: Value) -> HashMap<String, Value> {
+        UuidValidate::new().execute(HashMap::from([("value".to_string(), value)]), None)
+    }
+
+    #[test]
+    fn test_valid() {
+        let output = run(json!("{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}"));
+        assert_eq!(output["valid"], true);
+        assert_eq!(output["result"], "936da01f-9abd-4d9d-80c7-02af85c822a8");
+        assert_eq!(output["version"], 4);
+        assert_eq!(output["variant"], "rfc4122");
+        let output = run(json!("urn:uuid:0190a5b2-7c3e-7d4f-9a10-123456789abc"));
+        assert_eq!(output["version"], 7);
+        let output = run(json!("00000000000000000000000000000000"));
+        assert_eq!(output["version"], 0);
+        assert_eq!(output["variant"], "ncs");
+    }
+
+    #[test]
+    fn test_invalid() {
+        let output = run(json!("936da01f-9abd-4d9d-80c7"));
+        assert_eq!(output["valid"], false);
+        assert!(output["reason"].is_string());
+        assert!(!output.contains_key("error"));
+        let output = run(json!(42));
+        assert_eq!(output["valid"], false);
+        assert_eq!(output["error"], "value is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "uuid.validate");
+        assert_eq!(executor.category, "uuid");
+    }
+}
@@ -92,7 +92,7 @@ mod tests {
 
         let result = executor.execute(inputs, None);
         assert_eq!(result.get("success"), Some(&serde_json::json!(false)));
-        assert!(result.get("error").is_some());
+        assert!(result.contains_key("error"));
     }
 
     #[test]
@@ -0,0 +1,168 @@
+//! Pure workflow nodes for JavaScript.
+//!
+//! Built with `wasm-pack`, this crate is the `metabuilder-wasm` package the
+//! workflow editor uses to preview what a node does with the inputs on its
+//! canvas. It registers the same executors and port schemas as the engine,
+//! from the categories whose nodes compute from their inputs alone (math,
+//! string, logic, list, convert, path, bytes, regex, encode, csv, yaml, and
+//! toml), so a preview gives exactly what a run would.
+//!
+//! Values cross the boundary as JSON text:
+//!
+//! ```js
+//! import init, { Nodes } from "metabuilder-wasm";
+//! await init();
+//! const nodes = new Nodes();
+//! const outputs = JSON.parse(nodes.execute("math.add", JSON.stringify({ numbers: [1, 2] })));
+//! ```
+
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use workflow::{typings, NodeSchema, Registry};
+
+/// Categories whose nodes compute from their inputs alone. The `workflow`
+/// dependency enables just these, but a workspace build unifies features,
+/// so the registry is filtered as well.
+const CATEGORIES: &[&str] = &["math", "string", "logic", "list", "convert", "path", "bytes", "regex", "encode", "csv", "yaml", "toml"];
+
+/// The bundled pure nodes, with their port schemas.
+#[wasm_bindgen]
+pub struct Nodes {
+    registry: Registry,
+    /// Shared by every execution, so regex patterns are compiled once
+    runtime: Runtime,
+}
+
+#[wasm_bindgen]
+impl Nodes {
+    /// Registers every bundled pure node.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Nodes {
+        let mut registry = Registry::new();
+        let builtins = Registry::with_builtins();
+        let pure = |node_type: &&str| {
+            let category = node_type.split('.').next().unwrap_or_default();
+            CATEGORIES.contains(&category) && !builtins.has_side_effects(node_type)
+        };
+        for node_type in builtins.node_types().filter(pure) {
+            if let Some(executor) = builtins.get(node_type) {
+                registry.register(node_type, move |inputs: HashMap<String, Value>, runtime: Option<&dyn Any>| {
+                    executor.execute(inputs, runtime)
+                });
+            }
+            if let Some(schema) = builtins.schema(node_type) {
+                registry.register_schema(node_type, schema.clone());
+            }
+        }
+        Nodes {
+            registry,
+            runtime: Runtime::new(),
+        }
+    }
+
+    /// The node types available, in sorted order.
+    #[wasm_bindgen(js_name = nodeTypes)]
+    pub fn node_types(&self) -> Vec<String> {
+        self.registry.node_types().map(String::from).collect()
+    }
+
+    /// The ports of `node_type` as JSON, `{"inputs": [{"name", "kind"}],
+    /// "outputs": [...], "branching"}`, or `undefined` if they are not fixed.
+    pub fn schema(&self, node_type: &str) -> Option<String> {
+        self.registry.schema(node_type).map(|schema| schema_json(schema).to_string())
+    }
+
+    /// TypeScript declarations for the ports of these nodes (see
+    /// `workflow::typings`).
+    pub fn typings(&self) -> String {
+        typings::typescript(&self.registry)
+    }
+
+    /// Runs `node_type` on `inputs`, a JSON object, and returns its outputs
+    /// as a JSON object. A node that fails reports an `error` output, as in
+    /// a run; unknown node types and malformed inputs throw.
+    pub fn execute(&self, node_type: &str, inputs: &str) -> Result<String, JsError> {
+        self.run(node_type, inputs).map_err(|e| JsError::new(&e))
+    }
+}
+
+impl Default for Nodes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Nodes {
+    fn run(&self, node_type: &str, inputs: &str) -> Result<String, String> {
+        let executor = self
+            .registry
+            .get(node_type)
+            .ok_or_else(|| format!("unknown node type {}", node_type))?;
+        let inputs: HashMap<String, Value> =
+            serde_json::from_str(inputs).map_err(|e| format!("inputs must be a JSON object: {}", e))?;
+        let outputs = executor.execute(inputs, Some(&self.runtime));
+        Ok(serde_json::to_string(&outputs).expect("outputs are always serializable"))
+    }
+}
+
+fn schema_json(schema: &NodeSchema) -> Value {
+    let ports = |ports: &[(String, workflow::PortKind)]| -> Vec<Value> {
+        ports
+            .iter()
+            .map(|(name, kind)| serde_json::json!({"name": name, "kind": kind.to_string()}))
+            .collect()
+    };
+    serde_json::json!({
+        "inputs": ports(&schema.inputs),
+        "outputs": ports(&schema.outputs),
+        "branching": schema.branching,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(nodes: &Nodes, node_type: &str, inputs: Value) -> Value {
+        serde_json::from_str(&nodes.run(node_type, &inputs.to_string()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_pure_nodes_only() {
+        let nodes = Nodes::new();
+        let types = nodes.node_types();
+        assert!(types.iter().any(|t| t == "math.add"));
+        assert!(types.iter().any(|t| t == "regex.split"));
+        assert!(!types.iter().any(|t| t.starts_with("http.") || t.starts_with("var.")));
+    }
+
+    #[test]
+    fn test_execute() {
+        let nodes = Nodes::new();
+        assert_eq!(run(&nodes, "math.add", json!({"numbers": [1, 2]}))["result"], json!(3));
+        let output = run(&nodes, "regex.split", json!({"text": "a(b", "pattern": "("}));
+        assert_eq!(output["result"], Value::Null);
+        assert!(output["error"].is_string());
+        assert_eq!(nodes.run("http.graphql", "{}").unwrap_err(), "unknown node type http.graphql");
+        assert!(nodes.run("math.add", "[1]").unwrap_err().starts_with("inputs must be a JSON object: "));
+    }
+
+    #[test]
+    fn test_schema() {
+        let nodes = Nodes::new();
+        let schema: Value = serde_json::from_str(&nodes.schema("math.add").unwrap()).unwrap();
+        assert!(nodes.typings().contains("export interface MathAddInputs {\n  numbers?: unknown[];\n}\n"));
+        assert_eq!(
+            schema,
+            json!({
+                "inputs": [{"name": "numbers", "kind": "list"}],
+                "outputs": [{"name": "result", "kind": "number"}],
+                "branching": false,
+            })
+        );
+    }
+}
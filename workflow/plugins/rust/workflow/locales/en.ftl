@@ -0,0 +1,25 @@
+# Engine messages in English, the fallback for every other locale.
+#
+# Node descriptions are keyed by node type, with the dot replaced by a dash
+# (`node-math-add` for `math.add`); English ones come from the plugins, so
+# only translations list them.
+
+error-duplicate-node = Duplicate node id: { $id }
+error-unknown-node = Edge { $edge } references unknown node: { $node }
+error-invalid-reference = Node { $node } has invalid reference { $expression }: { $message }
+error-unknown-reference = Node { $node } reference { $expression } names unknown node: { $target }
+error-missing-value = Node { $node } template { $expression } refers to a missing value
+error-unknown-node-type = Node { $node } has unknown type: { $type }
+error-cycle = Cycle detected through nodes: { $nodes }
+error-node-failed = Node { $node } failed: { $message }
+error-cancelled = Run cancelled
+error-checkpoint = Cannot save checkpoint: { $message }
+error-invalid-checkpoint = Cannot resume from checkpoint: { $message }
+error-invalid-input = Invalid input { $input }: { $message }
+error-invalid-output = Invalid output { $output }: { $message }
+error-timeout = Run timed out after { $ms }ms
+error-invalid-profile = Invalid profile { $profile }: { $message }
+error-invalid-selection = Cannot run only { $node }: { $message }
+error-unknown-tenant = Unknown tenant: { $tenant }
+error-audit = Cannot write audit log: { $message }
+error-shutting-down = Engine is shutting down
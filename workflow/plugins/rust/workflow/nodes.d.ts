@@ -0,0 +1,1561 @@
+// Generated from the node schemas by `workflow::typings`. Do not edit.
+
+/** Outputs every node may report. */
+export interface NodeResult {
+  error?: string | null;
+}
+
+/** Inputs of `auth.oauth2_token`. */
+export type AuthOauth2TokenInputs = Record<string, unknown>;
+
+/** Outputs of `auth.oauth2_token`. */
+export type AuthOauth2TokenOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `bytes.concat`. */
+export interface BytesConcatInputs {
+  items?: unknown[];
+}
+
+/** Outputs of `bytes.concat`. */
+export interface BytesConcatOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+}
+
+/** Inputs of `bytes.length`. */
+export interface BytesLengthInputs {
+  bytes?: unknown;
+}
+
+/** Outputs of `bytes.length`. */
+export interface BytesLengthOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `bytes.slice`. */
+export interface BytesSliceInputs {
+  bytes?: unknown;
+  start?: number;
+  end?: number;
+}
+
+/** Outputs of `bytes.slice`. */
+export interface BytesSliceOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+}
+
+/** Inputs of `bytes.to_string`. */
+export interface BytesToStringInputs {
+  bytes?: unknown;
+  encoding?: string;
+  errors?: string;
+}
+
+/** Outputs of `bytes.to_string`. */
+export interface BytesToStringOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `control.delay`. */
+export interface ControlDelayInputs {
+  duration_ms?: number;
+  until?: string;
+  value?: unknown;
+}
+
+/** Outputs of `control.delay`. */
+export interface ControlDelayOutputs extends NodeResult {
+  result?: unknown | null;
+  waited_ms?: number | null;
+}
+
+/** Inputs of `control.if`. */
+export interface ControlIfInputs {
+  condition?: unknown;
+  value?: unknown;
+}
+
+/** Outputs of `control.if`. */
+export interface ControlIfOutputs extends NodeResult {
+  then?: unknown | null;
+  else?: unknown | null;
+  branch?: string | null;
+  result?: boolean | null;
+}
+
+/** Inputs of `control.switch`. */
+export type ControlSwitchInputs = Record<string, unknown>;
+
+/** Outputs of `control.switch`. */
+export type ControlSwitchOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.avro_decode`. */
+export type ConvertAvroDecodeInputs = Record<string, unknown>;
+
+/** Outputs of `convert.avro_decode`. */
+export type ConvertAvroDecodeOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.avro_encode`. */
+export type ConvertAvroEncodeInputs = Record<string, unknown>;
+
+/** Outputs of `convert.avro_encode`. */
+export type ConvertAvroEncodeOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.parse_json`. */
+export interface ConvertParseJsonInputs {
+  string?: string;
+  mode?: string;
+}
+
+/** Outputs of `convert.parse_json`. */
+export interface ConvertParseJsonOutputs extends NodeResult {
+  result?: unknown | null;
+  line?: number | null;
+  column?: number | null;
+  offset?: number | null;
+  snippet?: string | null;
+}
+
+/** Inputs of `convert.proto_decode`. */
+export type ConvertProtoDecodeInputs = Record<string, unknown>;
+
+/** Outputs of `convert.proto_decode`. */
+export type ConvertProtoDecodeOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.proto_encode`. */
+export type ConvertProtoEncodeInputs = Record<string, unknown>;
+
+/** Outputs of `convert.proto_encode`. */
+export type ConvertProtoEncodeOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.to_boolean`. */
+export type ConvertToBooleanInputs = Record<string, unknown>;
+
+/** Outputs of `convert.to_boolean`. */
+export type ConvertToBooleanOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.to_json`. */
+export interface ConvertToJsonInputs {
+  value?: unknown;
+  pretty?: boolean;
+  canonical?: boolean;
+  indent?: unknown;
+  sort_keys?: boolean;
+  ascii?: boolean;
+}
+
+/** Outputs of `convert.to_json`. */
+export interface ConvertToJsonOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `convert.to_list`. */
+export type ConvertToListInputs = Record<string, unknown>;
+
+/** Outputs of `convert.to_list`. */
+export type ConvertToListOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.to_number`. */
+export type ConvertToNumberInputs = Record<string, unknown>;
+
+/** Outputs of `convert.to_number`. */
+export type ConvertToNumberOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.to_object`. */
+export type ConvertToObjectInputs = Record<string, unknown>;
+
+/** Outputs of `convert.to_object`. */
+export type ConvertToObjectOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `convert.to_string`. */
+export type ConvertToStringInputs = Record<string, unknown>;
+
+/** Outputs of `convert.to_string`. */
+export type ConvertToStringOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `crypto.decrypt`. */
+export interface CryptoDecryptInputs {
+  data?: unknown;
+  key_secret?: string;
+  aad?: unknown;
+  as_text?: boolean;
+}
+
+/** Outputs of `crypto.decrypt`. */
+export interface CryptoDecryptOutputs extends NodeResult {
+  result?: unknown | null;
+}
+
+/** Inputs of `crypto.encrypt`. */
+export interface CryptoEncryptInputs {
+  data?: unknown;
+  key_secret?: string;
+  aad?: unknown;
+  encoding?: string;
+}
+
+/** Outputs of `crypto.encrypt`. */
+export interface CryptoEncryptOutputs extends NodeResult {
+  result?: unknown | null;
+}
+
+/** Inputs of `crypto.hash`. */
+export interface CryptoHashInputs {
+  data?: unknown;
+  algorithm?: string;
+}
+
+/** Outputs of `crypto.hash`. */
+export interface CryptoHashOutputs extends NodeResult {
+  result?: string | null;
+  base64?: string | null;
+}
+
+/** Inputs of `crypto.jwt_sign`. */
+export interface CryptoJwtSignInputs {
+  claims?: Record<string, unknown>;
+  algorithm?: string;
+  key_secret?: string;
+  expires_in?: number;
+  key_id?: string;
+}
+
+/** Outputs of `crypto.jwt_sign`. */
+export interface CryptoJwtSignOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `crypto.jwt_verify`. */
+export interface CryptoJwtVerifyInputs {
+  token?: string;
+  algorithm?: string;
+  key_secret?: string;
+  audience?: string;
+  issuer?: string;
+  leeway?: number;
+  require_exp?: boolean;
+}
+
+/** Outputs of `crypto.jwt_verify`. */
+export interface CryptoJwtVerifyOutputs extends NodeResult {
+  valid?: boolean | null;
+  claims?: Record<string, unknown> | null;
+  reason?: string | null;
+}
+
+/** Inputs of `crypto.password_hash`. */
+export interface CryptoPasswordHashInputs {
+  password?: string;
+  algorithm?: string;
+  cost?: number;
+}
+
+/** Outputs of `crypto.password_hash`. */
+export interface CryptoPasswordHashOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `crypto.password_verify`. */
+export interface CryptoPasswordVerifyInputs {
+  password?: string;
+  hash?: string;
+}
+
+/** Outputs of `crypto.password_verify`. */
+export interface CryptoPasswordVerifyOutputs extends NodeResult {
+  valid?: boolean | null;
+}
+
+/** Inputs of `crypto.random_bytes`. */
+export interface CryptoRandomBytesInputs {
+  length?: number;
+  format?: string;
+}
+
+/** Outputs of `crypto.random_bytes`. */
+export interface CryptoRandomBytesOutputs extends NodeResult {
+  result?: unknown | null;
+}
+
+/** Inputs of `csv.parse`. */
+export interface CsvParseInputs {
+  text?: string;
+  delimiter?: string;
+  quote?: string;
+  header?: boolean;
+  infer_types?: boolean;
+  strict?: boolean;
+}
+
+/** Outputs of `csv.parse`. */
+export interface CsvParseOutputs extends NodeResult {
+  result?: unknown[] | null;
+  headers?: unknown[] | null;
+  count?: number | null;
+  errors?: unknown[] | null;
+}
+
+/** Inputs of `csv.serialize`. */
+export interface CsvSerializeInputs {
+  rows?: unknown[];
+  columns?: unknown[];
+  header?: boolean;
+  delimiter?: string;
+  quote?: string;
+  quote_all?: boolean;
+}
+
+/** Outputs of `csv.serialize`. */
+export interface CsvSerializeOutputs extends NodeResult {
+  result?: string | null;
+  count?: number | null;
+}
+
+/** Inputs of `data.hash_value`. */
+export interface DataHashValueInputs {
+  value?: unknown;
+  algorithm?: string;
+}
+
+/** Outputs of `data.hash_value`. */
+export interface DataHashValueOutputs extends NodeResult {
+  result?: string | null;
+  canonical?: string | null;
+}
+
+/** Inputs of `datetime.extract`. */
+export interface DatetimeExtractInputs {
+  value?: unknown;
+  format?: string;
+}
+
+/** Outputs of `datetime.extract`. */
+export interface DatetimeExtractOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+  weekday?: number | null;
+  weekday_name?: string | null;
+  iso_week?: number | null;
+  iso_year?: number | null;
+  day_of_year?: number | null;
+  quarter?: number | null;
+  is_weekend?: boolean | null;
+}
+
+/** Inputs of `datetime.is_after`. */
+export interface DatetimeIsAfterInputs {
+  a?: unknown;
+  b?: unknown;
+  format?: string;
+}
+
+/** Outputs of `datetime.is_after`. */
+export interface DatetimeIsAfterOutputs extends NodeResult {
+  result?: boolean | null;
+}
+
+/** Inputs of `datetime.is_before`. */
+export interface DatetimeIsBeforeInputs {
+  a?: unknown;
+  b?: unknown;
+  format?: string;
+}
+
+/** Outputs of `datetime.is_before`. */
+export interface DatetimeIsBeforeOutputs extends NodeResult {
+  result?: boolean | null;
+}
+
+/** Inputs of `datetime.is_between`. */
+export interface DatetimeIsBetweenInputs {
+  value?: unknown;
+  start?: unknown;
+  end?: unknown;
+  inclusive?: boolean;
+  format?: string;
+}
+
+/** Outputs of `datetime.is_between`. */
+export interface DatetimeIsBetweenOutputs extends NodeResult {
+  result?: boolean | null;
+}
+
+/** Inputs of `datetime.parse`. */
+export interface DatetimeParseInputs {
+  string?: string;
+  format?: string;
+}
+
+/** Outputs of `datetime.parse`. */
+export interface DatetimeParseOutputs extends NodeResult {
+  result?: string | null;
+  epoch?: number | null;
+  epoch_ms?: number | null;
+}
+
+/** Inputs of `datetime.timezone_convert`. */
+export interface DatetimeTimezoneConvertInputs {
+  value?: unknown;
+  from?: string;
+  to?: string;
+  format?: string;
+}
+
+/** Outputs of `datetime.timezone_convert`. */
+export interface DatetimeTimezoneConvertOutputs extends NodeResult {
+  result?: string | null;
+  abbreviation?: string | null;
+}
+
+/** Inputs of `encode.hex_decode`. */
+export interface EncodeHexDecodeInputs {
+  hex?: string;
+}
+
+/** Outputs of `encode.hex_decode`. */
+export interface EncodeHexDecodeOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+}
+
+/** Inputs of `encode.hex_encode`. */
+export interface EncodeHexEncodeInputs {
+  bytes?: unknown;
+  uppercase?: boolean;
+}
+
+/** Outputs of `encode.hex_encode`. */
+export interface EncodeHexEncodeOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `event.emit`. */
+export interface EventEmitInputs {
+  topic?: string;
+  payload?: unknown;
+}
+
+/** Outputs of `event.emit`. */
+export interface EventEmitOutputs extends NodeResult {
+  result?: unknown | null;
+}
+
+/** Inputs of `event.on`. */
+export interface EventOnInputs {
+  topic?: string;
+  count?: number;
+  timeout_ms?: number;
+}
+
+/** Outputs of `event.on`. */
+export interface EventOnOutputs extends NodeResult {
+  events?: unknown[] | null;
+  count?: number | null;
+  result?: unknown | null;
+}
+
+/** Inputs of `file.append`. */
+export interface FileAppendInputs {
+  path?: string;
+  content?: unknown;
+  atomic?: boolean;
+  create_dirs?: boolean;
+}
+
+/** Outputs of `file.append`. */
+export interface FileAppendOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `file.copy`. */
+export interface FileCopyInputs {
+  from?: string;
+  to?: string;
+  overwrite?: boolean;
+  create_dirs?: boolean;
+}
+
+/** Outputs of `file.copy`. */
+export interface FileCopyOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `file.delete`. */
+export interface FileDeleteInputs {
+  path?: string;
+  recursive?: boolean;
+  missing_ok?: boolean;
+}
+
+/** Outputs of `file.delete`. */
+export interface FileDeleteOutputs extends NodeResult {
+  result?: boolean | null;
+}
+
+/** Inputs of `file.detect_mime`. */
+export interface FileDetectMimeInputs {
+  filename?: string;
+  bytes?: unknown;
+}
+
+/** Outputs of `file.detect_mime`. */
+export interface FileDetectMimeOutputs extends NodeResult {
+  result?: string | null;
+  source?: string | null;
+}
+
+/** Inputs of `file.exists`. */
+export interface FileExistsInputs {
+  path?: string;
+}
+
+/** Outputs of `file.exists`. */
+export interface FileExistsOutputs extends NodeResult {
+  result?: boolean | null;
+  kind?: string | null;
+}
+
+/** Inputs of `file.list`. */
+export interface FileListInputs {
+  path?: string;
+  pattern?: string;
+  depth?: number;
+  kind?: string;
+  extensions?: unknown;
+}
+
+/** Outputs of `file.list`. */
+export interface FileListOutputs extends NodeResult {
+  result?: unknown[] | null;
+  count?: number | null;
+}
+
+/** Inputs of `file.move`. */
+export interface FileMoveInputs {
+  from?: string;
+  to?: string;
+  overwrite?: boolean;
+  create_dirs?: boolean;
+}
+
+/** Outputs of `file.move`. */
+export interface FileMoveOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `file.read_lines`. */
+export interface FileReadLinesInputs {
+  path?: string;
+}
+
+/** Outputs of `file.read_lines`. */
+export interface FileReadLinesOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+}
+
+/** Inputs of `file.stat`. */
+export interface FileStatInputs {
+  path?: string;
+}
+
+/** Outputs of `file.stat`. */
+export interface FileStatOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+}
+
+/** Inputs of `file.write`. */
+export interface FileWriteInputs {
+  path?: string;
+  content?: unknown;
+  atomic?: boolean;
+  create_dirs?: boolean;
+}
+
+/** Outputs of `file.write`. */
+export interface FileWriteOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `grpc.call`. */
+export type GrpcCallInputs = Record<string, unknown>;
+
+/** Outputs of `grpc.call`. */
+export type GrpcCallOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `http.graphql`. */
+export type HttpGraphqlInputs = Record<string, unknown>;
+
+/** Outputs of `http.graphql`. */
+export type HttpGraphqlOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `http.paginate`. */
+export type HttpPaginateInputs = Record<string, unknown>;
+
+/** Outputs of `http.paginate`. */
+export type HttpPaginateOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `http.request`. */
+export interface HttpRequestInputs {
+  method?: string;
+  url?: string;
+  headers?: Record<string, unknown>;
+  query?: Record<string, unknown>;
+  body?: unknown;
+  form?: Record<string, unknown>;
+  timeout_ms?: number;
+  connect_timeout_ms?: number;
+  auth?: string;
+  retries?: number;
+  backoff_ms?: number;
+  rate_limit?: number;
+}
+
+/** Outputs of `http.request`. */
+export interface HttpRequestOutputs extends NodeResult {
+  status?: number | null;
+  ok?: boolean | null;
+  headers?: Record<string, unknown> | null;
+  body?: unknown | null;
+  attempts?: number | null;
+}
+
+/** Inputs of `http.soap`. */
+export type HttpSoapInputs = Record<string, unknown>;
+
+/** Outputs of `http.soap`. */
+export type HttpSoapOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `http.verify_signature`. */
+export type HttpVerifySignatureInputs = Record<string, unknown>;
+
+/** Outputs of `http.verify_signature`. */
+export type HttpVerifySignatureOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.at`. */
+export type ListAtInputs = Record<string, unknown>;
+
+/** Outputs of `list.at`. */
+export type ListAtOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.concat`. */
+export type ListConcatInputs = Record<string, unknown>;
+
+/** Outputs of `list.concat`. */
+export type ListConcatOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.contains`. */
+export type ListContainsInputs = Record<string, unknown>;
+
+/** Outputs of `list.contains`. */
+export type ListContainsOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.first`. */
+export type ListFirstInputs = Record<string, unknown>;
+
+/** Outputs of `list.first`. */
+export type ListFirstOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.index_of`. */
+export type ListIndexOfInputs = Record<string, unknown>;
+
+/** Outputs of `list.index_of`. */
+export type ListIndexOfOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.last`. */
+export type ListLastInputs = Record<string, unknown>;
+
+/** Outputs of `list.last`. */
+export type ListLastOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.length`. */
+export type ListLengthInputs = Record<string, unknown>;
+
+/** Outputs of `list.length`. */
+export type ListLengthOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.reverse`. */
+export type ListReverseInputs = Record<string, unknown>;
+
+/** Outputs of `list.reverse`. */
+export type ListReverseOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.slice`. */
+export type ListSliceInputs = Record<string, unknown>;
+
+/** Outputs of `list.slice`. */
+export type ListSliceOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.sort`. */
+export type ListSortInputs = Record<string, unknown>;
+
+/** Outputs of `list.sort`. */
+export type ListSortOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `list.unique`. */
+export type ListUniqueInputs = Record<string, unknown>;
+
+/** Outputs of `list.unique`. */
+export type ListUniqueOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.and`. */
+export type LogicAndInputs = Record<string, unknown>;
+
+/** Outputs of `logic.and`. */
+export type LogicAndOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.equals`. */
+export type LogicEqualsInputs = Record<string, unknown>;
+
+/** Outputs of `logic.equals`. */
+export type LogicEqualsOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.gt`. */
+export interface LogicGtInputs {
+  a?: unknown;
+  b?: unknown;
+}
+
+/** Outputs of `logic.gt`. */
+export interface LogicGtOutputs extends NodeResult {
+  result?: boolean | null;
+}
+
+/** Inputs of `logic.gte`. */
+export type LogicGteInputs = Record<string, unknown>;
+
+/** Outputs of `logic.gte`. */
+export type LogicGteOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.in`. */
+export type LogicInInputs = Record<string, unknown>;
+
+/** Outputs of `logic.in`. */
+export type LogicInOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.lt`. */
+export interface LogicLtInputs {
+  a?: unknown;
+  b?: unknown;
+}
+
+/** Outputs of `logic.lt`. */
+export interface LogicLtOutputs extends NodeResult {
+  result?: boolean | null;
+}
+
+/** Inputs of `logic.lte`. */
+export type LogicLteInputs = Record<string, unknown>;
+
+/** Outputs of `logic.lte`. */
+export type LogicLteOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.not`. */
+export type LogicNotInputs = Record<string, unknown>;
+
+/** Outputs of `logic.not`. */
+export type LogicNotOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.or`. */
+export type LogicOrInputs = Record<string, unknown>;
+
+/** Outputs of `logic.or`. */
+export type LogicOrOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `logic.xor`. */
+export type LogicXorInputs = Record<string, unknown>;
+
+/** Outputs of `logic.xor`. */
+export type LogicXorOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `mail.imap_fetch`. */
+export type MailImapFetchInputs = Record<string, unknown>;
+
+/** Outputs of `mail.imap_fetch`. */
+export type MailImapFetchOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `math.abs`. */
+export interface MathAbsInputs {
+  value?: number;
+}
+
+/** Outputs of `math.abs`. */
+export interface MathAbsOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `math.add`. */
+export interface MathAddInputs {
+  numbers?: unknown[];
+}
+
+/** Outputs of `math.add`. */
+export interface MathAddOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `math.ceil`. */
+export type MathCeilInputs = Record<string, unknown>;
+
+/** Outputs of `math.ceil`. */
+export type MathCeilOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `math.divide`. */
+export interface MathDivideInputs {
+  numbers?: unknown[];
+}
+
+/** Outputs of `math.divide`. */
+export interface MathDivideOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `math.floor`. */
+export type MathFloorInputs = Record<string, unknown>;
+
+/** Outputs of `math.floor`. */
+export type MathFloorOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `math.modulo`. */
+export type MathModuloInputs = Record<string, unknown>;
+
+/** Outputs of `math.modulo`. */
+export type MathModuloOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `math.multiply`. */
+export interface MathMultiplyInputs {
+  numbers?: unknown[];
+}
+
+/** Outputs of `math.multiply`. */
+export interface MathMultiplyOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `math.power`. */
+export type MathPowerInputs = Record<string, unknown>;
+
+/** Outputs of `math.power`. */
+export type MathPowerOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `math.round`. */
+export interface MathRoundInputs {
+  value?: number;
+  decimals?: number;
+}
+
+/** Outputs of `math.round`. */
+export interface MathRoundOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `math.subtract`. */
+export interface MathSubtractInputs {
+  numbers?: unknown[];
+}
+
+/** Outputs of `math.subtract`. */
+export interface MathSubtractOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `path.basename`. */
+export interface PathBasenameInputs {
+  path?: string;
+  strip_extension?: boolean;
+}
+
+/** Outputs of `path.basename`. */
+export interface PathBasenameOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `path.dirname`. */
+export interface PathDirnameInputs {
+  path?: string;
+}
+
+/** Outputs of `path.dirname`. */
+export interface PathDirnameOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `path.extension`. */
+export interface PathExtensionInputs {
+  path?: string;
+  replace?: string;
+}
+
+/** Outputs of `path.extension`. */
+export interface PathExtensionOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `path.glob_match`. */
+export interface PathGlobMatchInputs {
+  pattern?: string;
+  path?: string;
+  paths?: unknown[];
+}
+
+/** Outputs of `path.glob_match`. */
+export interface PathGlobMatchOutputs extends NodeResult {
+  result?: unknown | null;
+}
+
+/** Inputs of `path.join`. */
+export interface PathJoinInputs {
+  parts?: unknown[];
+}
+
+/** Outputs of `path.join`. */
+export interface PathJoinOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `path.normalize`. */
+export interface PathNormalizeInputs {
+  path?: string;
+}
+
+/** Outputs of `path.normalize`. */
+export interface PathNormalizeOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `path.split`. */
+export interface PathSplitInputs {
+  path?: string;
+}
+
+/** Outputs of `path.split`. */
+export interface PathSplitOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+}
+
+/** Inputs of `random.choice`. */
+export interface RandomChoiceInputs {
+  list?: unknown[];
+}
+
+/** Outputs of `random.choice`. */
+export interface RandomChoiceOutputs extends NodeResult {
+  result?: unknown | null;
+  index?: number | null;
+}
+
+/** Inputs of `random.sample`. */
+export interface RandomSampleInputs {
+  list?: unknown[];
+  count?: number;
+}
+
+/** Outputs of `random.sample`. */
+export interface RandomSampleOutputs extends NodeResult {
+  result?: unknown[] | null;
+}
+
+/** Inputs of `random.shuffle`. */
+export interface RandomShuffleInputs {
+  list?: unknown[];
+}
+
+/** Outputs of `random.shuffle`. */
+export interface RandomShuffleOutputs extends NodeResult {
+  result?: unknown[] | null;
+}
+
+/** Inputs of `regex.extract`. */
+export interface RegexExtractInputs {
+  text?: string;
+  pattern?: string;
+  flags?: string;
+  limit?: number;
+}
+
+/** Outputs of `regex.extract`. */
+export interface RegexExtractOutputs extends NodeResult {
+  result?: unknown[] | null;
+  count?: number | null;
+}
+
+/** Inputs of `regex.split`. */
+export interface RegexSplitInputs {
+  text?: string;
+  pattern?: string;
+  flags?: string;
+  limit?: number;
+}
+
+/** Outputs of `regex.split`. */
+export interface RegexSplitOutputs extends NodeResult {
+  result?: unknown[] | null;
+  count?: number | null;
+}
+
+/** Inputs of `remote.ftp_get`. */
+export type RemoteFtpGetInputs = Record<string, unknown>;
+
+/** Outputs of `remote.ftp_get`. */
+export type RemoteFtpGetOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `remote.ftp_put`. */
+export type RemoteFtpPutInputs = Record<string, unknown>;
+
+/** Outputs of `remote.ftp_put`. */
+export type RemoteFtpPutOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `remote.sftp_get`. */
+export type RemoteSftpGetInputs = Record<string, unknown>;
+
+/** Outputs of `remote.sftp_get`. */
+export type RemoteSftpGetOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `remote.sftp_put`. */
+export type RemoteSftpPutInputs = Record<string, unknown>;
+
+/** Outputs of `remote.sftp_put`. */
+export type RemoteSftpPutOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `remote.ssh_exec`. */
+export type RemoteSshExecInputs = Record<string, unknown>;
+
+/** Outputs of `remote.ssh_exec`. */
+export type RemoteSshExecOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `sheets.append_row`. */
+export type SheetsAppendRowInputs = Record<string, unknown>;
+
+/** Outputs of `sheets.append_row`. */
+export type SheetsAppendRowOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `sheets.read_range`. */
+export type SheetsReadRangeInputs = Record<string, unknown>;
+
+/** Outputs of `sheets.read_range`. */
+export type SheetsReadRangeOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.at`. */
+export type StringAtInputs = Record<string, unknown>;
+
+/** Outputs of `string.at`. */
+export type StringAtOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.concat`. */
+export type StringConcatInputs = Record<string, unknown>;
+
+/** Outputs of `string.concat`. */
+export type StringConcatOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.contains`. */
+export type StringContainsInputs = Record<string, unknown>;
+
+/** Outputs of `string.contains`. */
+export type StringContainsOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.ends_with`. */
+export type StringEndsWithInputs = Record<string, unknown>;
+
+/** Outputs of `string.ends_with`. */
+export type StringEndsWithOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.length`. */
+export interface StringLengthInputs {
+  string?: string;
+  unit?: string;
+}
+
+/** Outputs of `string.length`. */
+export interface StringLengthOutputs extends NodeResult {
+  result?: number | null;
+}
+
+/** Inputs of `string.lower`. */
+export interface StringLowerInputs {
+  string?: string;
+}
+
+/** Outputs of `string.lower`. */
+export interface StringLowerOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `string.normalize_newlines`. */
+export type StringNormalizeNewlinesInputs = Record<string, unknown>;
+
+/** Outputs of `string.normalize_newlines`. */
+export type StringNormalizeNewlinesOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.replace`. */
+export type StringReplaceInputs = Record<string, unknown>;
+
+/** Outputs of `string.replace`. */
+export type StringReplaceOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.split`. */
+export type StringSplitInputs = Record<string, unknown>;
+
+/** Outputs of `string.split`. */
+export type StringSplitOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.starts_with`. */
+export type StringStartsWithInputs = Record<string, unknown>;
+
+/** Outputs of `string.starts_with`. */
+export type StringStartsWithOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.substring`. */
+export type StringSubstringInputs = Record<string, unknown>;
+
+/** Outputs of `string.substring`. */
+export type StringSubstringOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.transcode`. */
+export type StringTranscodeInputs = Record<string, unknown>;
+
+/** Outputs of `string.transcode`. */
+export type StringTranscodeOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.trim`. */
+export type StringTrimInputs = Record<string, unknown>;
+
+/** Outputs of `string.trim`. */
+export type StringTrimOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `string.upper`. */
+export interface StringUpperInputs {
+  string?: string;
+}
+
+/** Outputs of `string.upper`. */
+export interface StringUpperOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `table.from_rows`. */
+export type TableFromRowsInputs = Record<string, unknown>;
+
+/** Outputs of `table.from_rows`. */
+export type TableFromRowsOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `table.read_parquet`. */
+export type TableReadParquetInputs = Record<string, unknown>;
+
+/** Outputs of `table.read_parquet`. */
+export type TableReadParquetOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `table.sql`. */
+export type TableSqlInputs = Record<string, unknown>;
+
+/** Outputs of `table.sql`. */
+export type TableSqlOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `table.to_rows`. */
+export type TableToRowsInputs = Record<string, unknown>;
+
+/** Outputs of `table.to_rows`. */
+export type TableToRowsOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `table.write_parquet`. */
+export type TableWriteParquetInputs = Record<string, unknown>;
+
+/** Outputs of `table.write_parquet`. */
+export type TableWriteParquetOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `text.front_matter`. */
+export interface TextFrontMatterInputs {
+  text?: string;
+}
+
+/** Outputs of `text.front_matter`. */
+export interface TextFrontMatterOutputs extends NodeResult {
+  data?: Record<string, unknown> | null;
+  body?: string | null;
+  format?: string | null;
+}
+
+/** Inputs of `toml.parse`. */
+export interface TomlParseInputs {
+  string?: string;
+}
+
+/** Outputs of `toml.parse`. */
+export interface TomlParseOutputs extends NodeResult {
+  result?: Record<string, unknown> | null;
+  line?: number | null;
+}
+
+/** Inputs of `toml.serialize`. */
+export interface TomlSerializeInputs {
+  value?: Record<string, unknown>;
+}
+
+/** Outputs of `toml.serialize`. */
+export interface TomlSerializeOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `uuid.generate`. */
+export interface UuidGenerateInputs {
+  version?: number;
+}
+
+/** Outputs of `uuid.generate`. */
+export interface UuidGenerateOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs of `uuid.validate`. */
+export interface UuidValidateInputs {
+  value?: string;
+}
+
+/** Outputs of `uuid.validate`. */
+export interface UuidValidateOutputs extends NodeResult {
+  valid?: boolean | null;
+  result?: string | null;
+  version?: number | null;
+  variant?: string | null;
+  reason?: string | null;
+}
+
+/** Inputs of `var.clear`. */
+export type VarClearInputs = Record<string, unknown>;
+
+/** Outputs of `var.clear`. */
+export type VarClearOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `var.delete`. */
+export type VarDeleteInputs = Record<string, unknown>;
+
+/** Outputs of `var.delete`. */
+export type VarDeleteOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `var.exists`. */
+export type VarExistsInputs = Record<string, unknown>;
+
+/** Outputs of `var.exists`. */
+export type VarExistsOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `var.get`. */
+export type VarGetInputs = Record<string, unknown>;
+
+/** Outputs of `var.get`. */
+export type VarGetOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `var.keys`. */
+export type VarKeysInputs = Record<string, unknown>;
+
+/** Outputs of `var.keys`. */
+export type VarKeysOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `var.set`. */
+export type VarSetInputs = Record<string, unknown>;
+
+/** Outputs of `var.set`. */
+export type VarSetOutputs = NodeResult & Record<string, unknown>;
+
+/** Inputs of `yaml.parse`. */
+export interface YamlParseInputs {
+  string?: string;
+}
+
+/** Outputs of `yaml.parse`. */
+export interface YamlParseOutputs extends NodeResult {
+  result?: unknown | null;
+  line?: number | null;
+}
+
+/** Inputs of `yaml.serialize`. */
+export interface YamlSerializeInputs {
+  value?: unknown;
+}
+
+/** Outputs of `yaml.serialize`. */
+export interface YamlSerializeOutputs extends NodeResult {
+  result?: string | null;
+}
+
+/** Inputs by node type. */
+export interface NodeInputs {
+  "auth.oauth2_token": AuthOauth2TokenInputs;
+  "bytes.concat": BytesConcatInputs;
+  "bytes.length": BytesLengthInputs;
+  "bytes.slice": BytesSliceInputs;
+  "bytes.to_string": BytesToStringInputs;
+  "control.delay": ControlDelayInputs;
+  "control.if": ControlIfInputs;
+  "control.switch": ControlSwitchInputs;
+  "convert.avro_decode": ConvertAvroDecodeInputs;
+  "convert.avro_encode": ConvertAvroEncodeInputs;
+  "convert.parse_json": ConvertParseJsonInputs;
+  "convert.proto_decode": ConvertProtoDecodeInputs;
+  "convert.proto_encode": ConvertProtoEncodeInputs;
+  "convert.to_boolean": ConvertToBooleanInputs;
+  "convert.to_json": ConvertToJsonInputs;
+  "convert.to_list": ConvertToListInputs;
+  "convert.to_number": ConvertToNumberInputs;
+  "convert.to_object": ConvertToObjectInputs;
+  "convert.to_string": ConvertToStringInputs;
+  "crypto.decrypt": CryptoDecryptInputs;
+  "crypto.encrypt": CryptoEncryptInputs;
+  "crypto.hash": CryptoHashInputs;
+  "crypto.jwt_sign": CryptoJwtSignInputs;
+  "crypto.jwt_verify": CryptoJwtVerifyInputs;
+  "crypto.password_hash": CryptoPasswordHashInputs;
+  "crypto.password_verify": CryptoPasswordVerifyInputs;
+  "crypto.random_bytes": CryptoRandomBytesInputs;
+  "csv.parse": CsvParseInputs;
+  "csv.serialize": CsvSerializeInputs;
+  "data.hash_value": DataHashValueInputs;
+  "datetime.extract": DatetimeExtractInputs;
+  "datetime.is_after": DatetimeIsAfterInputs;
+  "datetime.is_before": DatetimeIsBeforeInputs;
+  "datetime.is_between": DatetimeIsBetweenInputs;
+  "datetime.parse": DatetimeParseInputs;
+  "datetime.timezone_convert": DatetimeTimezoneConvertInputs;
+  "encode.hex_decode": EncodeHexDecodeInputs;
+  "encode.hex_encode": EncodeHexEncodeInputs;
+  "event.emit": EventEmitInputs;
+  "event.on": EventOnInputs;
+  "file.append": FileAppendInputs;
+  "file.copy": FileCopyInputs;
+  "file.delete": FileDeleteInputs;
+  "file.detect_mime": FileDetectMimeInputs;
+  "file.exists": FileExistsInputs;
+  "file.list": FileListInputs;
+  "file.move": FileMoveInputs;
+  "file.read_lines": FileReadLinesInputs;
+  "file.stat": FileStatInputs;
+  "file.write": FileWriteInputs;
+  "grpc.call": GrpcCallInputs;
+  "http.graphql": HttpGraphqlInputs;
+  "http.paginate": HttpPaginateInputs;
+  "http.request": HttpRequestInputs;
+  "http.soap": HttpSoapInputs;
+  "http.verify_signature": HttpVerifySignatureInputs;
+  "list.at": ListAtInputs;
+  "list.concat": ListConcatInputs;
+  "list.contains": ListContainsInputs;
+  "list.first": ListFirstInputs;
+  "list.index_of": ListIndexOfInputs;
+  "list.last": ListLastInputs;
+  "list.length": ListLengthInputs;
+  "list.reverse": ListReverseInputs;
+  "list.slice": ListSliceInputs;
+  "list.sort": ListSortInputs;
+  "list.unique": ListUniqueInputs;
+  "logic.and": LogicAndInputs;
+  "logic.equals": LogicEqualsInputs;
+  "logic.gt": LogicGtInputs;
+  "logic.gte": LogicGteInputs;
+  "logic.in": LogicInInputs;
+  "logic.lt": LogicLtInputs;
+  "logic.lte": LogicLteInputs;
+  "logic.not": LogicNotInputs;
+  "logic.or": LogicOrInputs;
+  "logic.xor": LogicXorInputs;
+  "mail.imap_fetch": MailImapFetchInputs;
+  "math.abs": MathAbsInputs;
+  "math.add": MathAddInputs;
+  "math.ceil": MathCeilInputs;
+  "math.divide": MathDivideInputs;
+  "math.floor": MathFloorInputs;
+  "math.modulo": MathModuloInputs;
+  "math.multiply": MathMultiplyInputs;
+  "math.power": MathPowerInputs;
+  "math.round": MathRoundInputs;
+  "math.subtract": MathSubtractInputs;
+  "path.basename": PathBasenameInputs;
+  "path.dirname": PathDirnameInputs;
+  "path.extension": PathExtensionInputs;
+  "path.glob_match": PathGlobMatchInputs;
+  "path.join": PathJoinInputs;
+  "path.normalize": PathNormalizeInputs;
+  "path.split": PathSplitInputs;
+  "random.choice": RandomChoiceInputs;
+  "random.sample": RandomSampleInputs;
+  "random.shuffle": RandomShuffleInputs;
+  "regex.extract": RegexExtractInputs;
+  "regex.split": RegexSplitInputs;
+  "remote.ftp_get": RemoteFtpGetInputs;
+  "remote.ftp_put": RemoteFtpPutInputs;
+  "remote.sftp_get": RemoteSftpGetInputs;
+  "remote.sftp_put": RemoteSftpPutInputs;
+  "remote.ssh_exec": RemoteSshExecInputs;
+  "sheets.append_row": SheetsAppendRowInputs;
+  "sheets.read_range": SheetsReadRangeInputs;
+  "string.at": StringAtInputs;
+  "string.concat": StringConcatInputs;
+  "string.contains": StringContainsInputs;
+  "string.ends_with": StringEndsWithInputs;
+  "string.length": StringLengthInputs;
+  "string.lower": StringLowerInputs;
+  "string.normalize_newlines": StringNormalizeNewlinesInputs;
+  "string.replace": StringReplaceInputs;
+  "string.split": StringSplitInputs;
+  "string.starts_with": StringStartsWithInputs;
+  "string.substring": StringSubstringInputs;
+  "string.transcode": StringTranscodeInputs;
+  "string.trim": StringTrimInputs;
+  "string.upper": StringUpperInputs;
+  "table.from_rows": TableFromRowsInputs;
+  "table.read_parquet": TableReadParquetInputs;
+  "table.sql": TableSqlInputs;
+  "table.to_rows": TableToRowsInputs;
+  "table.write_parquet": TableWriteParquetInputs;
+  "text.front_matter": TextFrontMatterInputs;
+  "toml.parse": TomlParseInputs;
+  "toml.serialize": TomlSerializeInputs;
+  "uuid.generate": UuidGenerateInputs;
+  "uuid.validate": UuidValidateInputs;
+  "var.clear": VarClearInputs;
+  "var.delete": VarDeleteInputs;
+  "var.exists": VarExistsInputs;
+  "var.get": VarGetInputs;
+  "var.keys": VarKeysInputs;
+  "var.set": VarSetInputs;
+  "yaml.parse": YamlParseInputs;
+  "yaml.serialize": YamlSerializeInputs;
+}
+
+/** Outputs by node type. */
+export interface NodeOutputs {
+  "auth.oauth2_token": AuthOauth2TokenOutputs;
+  "bytes.concat": BytesConcatOutputs;
+  "bytes.length": BytesLengthOutputs;
+  "bytes.slice": BytesSliceOutputs;
+  "bytes.to_string": BytesToStringOutputs;
+  "control.delay": ControlDelayOutputs;
+  "control.if": ControlIfOutputs;
+  "control.switch": ControlSwitchOutputs;
+  "convert.avro_decode": ConvertAvroDecodeOutputs;
+  "convert.avro_encode": ConvertAvroEncodeOutputs;
+  "convert.parse_json": ConvertParseJsonOutputs;
+  "convert.proto_decode": ConvertProtoDecodeOutputs;
+  "convert.proto_encode": ConvertProtoEncodeOutputs;
+  "convert.to_boolean": ConvertToBooleanOutputs;
+  "convert.to_json": ConvertToJsonOutputs;
+  "convert.to_list": ConvertToListOutputs;
+  "convert.to_number": ConvertToNumberOutputs;
+  "convert.to_object": ConvertToObjectOutputs;
+  "convert.to_string": ConvertToStringOutputs;
+  "crypto.decrypt": CryptoDecryptOutputs;
+  "crypto.encrypt": CryptoEncryptOutputs;
+  "crypto.hash": CryptoHashOutputs;
+  "crypto.jwt_sign": CryptoJwtSignOutputs;
+  "crypto.jwt_verify": CryptoJwtVerifyOutputs;
+  "crypto.password_hash": CryptoPasswordHashOutputs;
+  "crypto.password_verify": CryptoPasswordVerifyOutputs;
+  "crypto.random_bytes": CryptoRandomBytesOutputs;
+  "csv.parse": CsvParseOutputs;
+  "csv.serialize": CsvSerializeOutputs;
+  "data.hash_value": DataHashValueOutputs;
+  "datetime.extract": DatetimeExtractOutputs;
+  "datetime.is_after": DatetimeIsAfterOutputs;
+  "datetime.is_before": DatetimeIsBeforeOutputs;
+  "datetime.is_between": DatetimeIsBetweenOutputs;
+  "datetime.parse": DatetimeParseOutputs;
+  "datetime.timezone_convert": DatetimeTimezoneConvertOutputs;
+  "encode.hex_decode": EncodeHexDecodeOutputs;
+  "encode.hex_encode": EncodeHexEncodeOutputs;
+  "event.emit": EventEmitOutputs;
+  "event.on": EventOnOutputs;
+  "file.append": FileAppendOutputs;
+  "file.copy": FileCopyOutputs;
+  "file.delete": FileDeleteOutputs;
+  "file.detect_mime": FileDetectMimeOutputs;
+  "file.exists": FileExistsOutputs;
+  "file.list": FileListOutputs;
+  "file.move": FileMoveOutputs;
+  "file.read_lines": FileReadLinesOutputs;
+  "file.stat": FileStatOutputs;
+  "file.write": FileWriteOutputs;
+  "grpc.call": GrpcCallOutputs;
+  "http.graphql": HttpGraphqlOutputs;
+  "http.paginate": HttpPaginateOutputs;
+  "http.request": HttpRequestOutputs;
+  "http.soap": HttpSoapOutputs;
+  "http.verify_signature": HttpVerifySignatureOutputs;
+  "list.at": ListAtOutputs;
+  "list.concat": ListConcatOutputs;
+  "list.contains": ListContainsOutputs;
+  "list.first": ListFirstOutputs;
+  "list.index_of": ListIndexOfOutputs;
+  "list.last": ListLastOutputs;
+  "list.length": ListLengthOutputs;
+  "list.reverse": ListReverseOutputs;
+  "list.slice": ListSliceOutputs;
+  "list.sort": ListSortOutputs;
+  "list.unique": ListUniqueOutputs;
+  "logic.and": LogicAndOutputs;
+  "logic.equals": LogicEqualsOutputs;
+  "logic.gt": LogicGtOutputs;
+  "logic.gte": LogicGteOutputs;
+  "logic.in": LogicInOutputs;
+  "logic.lt": LogicLtOutputs;
+  "logic.lte": LogicLteOutputs;
+  "logic.not": LogicNotOutputs;
+  "logic.or": LogicOrOutputs;
+  "logic.xor": LogicXorOutputs;
+  "mail.imap_fetch": MailImapFetchOutputs;
+  "math.abs": MathAbsOutputs;
+  "math.add": MathAddOutputs;
+  "math.ceil": MathCeilOutputs;
+  "math.divide": MathDivideOutputs;
+  "math.floor": MathFloorOutputs;
+  "math.modulo": MathModuloOutputs;
+  "math.multiply": MathMultiplyOutputs;
+  "math.power": MathPowerOutputs;
+  "math.round": MathRoundOutputs;
+  "math.subtract": MathSubtractOutputs;
+  "path.basename": PathBasenameOutputs;
+  "path.dirname": PathDirnameOutputs;
+  "path.extension": PathExtensionOutputs;
+  "path.glob_match": PathGlobMatchOutputs;
+  "path.join": PathJoinOutputs;
+  "path.normalize": PathNormalizeOutputs;
+  "path.split": PathSplitOutputs;
+  "random.choice": RandomChoiceOutputs;
+  "random.sample": RandomSampleOutputs;
+  "random.shuffle": RandomShuffleOutputs;
+  "regex.extract": RegexExtractOutputs;
+  "regex.split": RegexSplitOutputs;
+  "remote.ftp_get": RemoteFtpGetOutputs;
+  "remote.ftp_put": RemoteFtpPutOutputs;
+  "remote.sftp_get": RemoteSftpGetOutputs;
+  "remote.sftp_put": RemoteSftpPutOutputs;
+  "remote.ssh_exec": RemoteSshExecOutputs;
+  "sheets.append_row": SheetsAppendRowOutputs;
+  "sheets.read_range": SheetsReadRangeOutputs;
+  "string.at": StringAtOutputs;
+  "string.concat": StringConcatOutputs;
+  "string.contains": StringContainsOutputs;
+  "string.ends_with": StringEndsWithOutputs;
+  "string.length": StringLengthOutputs;
+  "string.lower": StringLowerOutputs;
+  "string.normalize_newlines": StringNormalizeNewlinesOutputs;
+  "string.replace": StringReplaceOutputs;
+  "string.split": StringSplitOutputs;
+  "string.starts_with": StringStartsWithOutputs;
+  "string.substring": StringSubstringOutputs;
+  "string.transcode": StringTranscodeOutputs;
+  "string.trim": StringTrimOutputs;
+  "string.upper": StringUpperOutputs;
+  "table.from_rows": TableFromRowsOutputs;
+  "table.read_parquet": TableReadParquetOutputs;
+  "table.sql": TableSqlOutputs;
+  "table.to_rows": TableToRowsOutputs;
+  "table.write_parquet": TableWriteParquetOutputs;
+  "text.front_matter": TextFrontMatterOutputs;
+  "toml.parse": TomlParseOutputs;
+  "toml.serialize": TomlSerializeOutputs;
+  "uuid.generate": UuidGenerateOutputs;
+  "uuid.validate": UuidValidateOutputs;
+  "var.clear": VarClearOutputs;
+  "var.delete": VarDeleteOutputs;
+  "var.exists": VarExistsOutputs;
+  "var.get": VarGetOutputs;
+  "var.keys": VarKeysOutputs;
+  "var.set": VarSetOutputs;
+  "yaml.parse": YamlParseOutputs;
+  "yaml.serialize": YamlSerializeOutputs;
+}
+
+export type NodeType = keyof NodeInputs;
@@ -0,0 +1,312 @@
+//! Tamper-evident audit trail of nodes with side effects.
+//!
+//! Attach an `AuditLog` with `Engine::with_audit`; every execution of a node
+//! whose type the registry marks as having side effects is appended to it,
+//! at any nesting depth, with the tenant it ran for, when it finished, a hash
+//! of its inputs, and how it ended. Nodes that did not really run (mocked,
+//! stubbed by a dry run, or replayed from a cassette) are not recorded.
+//!
+//! Each entry carries the hash of the one before it, so editing, removing, or
+//! reordering entries breaks the chain and `verify` reports where. A log
+//! opened from a file appends every entry to it as a line of JSON.
+//!
+//! `AuditLog::query` selects entries by tenant, run, node, type, status, and
+//! time range, such as every failed `http.request` for one tenant last week.
+
+use crate::history::Status;
+use metabuilder_core::canonical;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One audited node execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, counting from 1
+    pub seq: u64,
+    /// When the node finished, in milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+    /// The tenant the run was for (see `Engine::run_as`), if any
+    pub tenant: Option<String>,
+    /// `RunMetadata::run_id` of the top-level run; absent from entries
+    /// written before runs were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    pub node: String,
+    pub node_type: String,
+    /// SHA-256 of the canonical JSON of the node's inputs, if they resolved
+    pub inputs_hash: Option<String>,
+    pub status: Status,
+    /// `hash` of the previous entry, empty for the first
+    pub prev_hash: String,
+    /// SHA-256 of the entry's other fields
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// The hash of every field but `hash` itself.
+    fn digest(&self) -> String {
+        let mut value = serde_json::to_value(self).expect("audit entries are always serializable");
+        value.as_object_mut().expect("entries serialize as objects").remove("hash");
+        let digest = Sha256::digest(canonical::to_string(&value).as_bytes());
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Which entries `AuditLog::query` returns. Each criterion left unset
+/// matches every entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditQuery {
+    pub tenant: Option<String>,
+    pub run_id: Option<String>,
+    pub node: Option<String>,
+    pub node_type: Option<String>,
+    pub status: Option<Status>,
+    /// Earliest `timestamp_ms` to include
+    pub since_ms: Option<u64>,
+    /// Latest `timestamp_ms` to include
+    pub until_ms: Option<u64>,
+}
+
+impl AuditQuery {
+    /// A query matching every entry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only entries of runs for `tenant`.
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Only entries of the run with id `run_id`, nested runs included.
+    pub fn run(mut self, run_id: impl Into<String>) -> Self {
+        self.run_id = Some(run_id.into());
+        self
+    }
+
+    /// Only executions of the node with id `node`.
+    pub fn node(mut self, node: impl Into<String>) -> Self {
+        self.node = Some(node.into());
+        self
+    }
+
+    /// Only executions of nodes of type `node_type`.
+    pub fn node_type(mut self, node_type: impl Into<String>) -> Self {
+        self.node_type = Some(node_type.into());
+        self
+    }
+
+    /// Only executions that ended with `status`.
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only entries with `since_ms <= timestamp_ms <= until_ms`.
+    pub fn between(mut self, since_ms: u64, until_ms: u64) -> Self {
+        self.since_ms = Some(since_ms);
+        self.until_ms = Some(until_ms);
+        self
+    }
+
+    /// Whether `entry` meets every criterion.
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        let equal = |want: &Option<String>, have: Option<&str>| want.as_deref().is_none_or(|want| have == Some(want));
+        equal(&self.tenant, entry.tenant.as_deref())
+            && equal(&self.run_id, entry.run_id.as_deref())
+            && equal(&self.node, Some(&entry.node))
+            && equal(&self.node_type, Some(&entry.node_type))
+            && self.status.is_none_or(|status| entry.status == status)
+            && self.since_ms.is_none_or(|since| entry.timestamp_ms >= since)
+            && self.until_ms.is_none_or(|until| entry.timestamp_ms <= until)
+    }
+}
+
+/// Thread-safe, append-only, hash-chained list of `AuditEntry`s.
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    entries: Mutex<Vec<AuditEntry>>,
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// Creates an empty log kept in memory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A log backed by the JSON lines file at `path`, continuing the entries
+    /// already there, which must verify.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(text) => text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("invalid audit entry on line {}: {}", i + 1, e)))
+                .collect::<Result<Vec<AuditEntry>, String>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("cannot read {}: {}", path.display(), e)),
+        };
+        verify_chain(&entries)?;
+        Ok(AuditLog {
+            entries: Mutex::new(entries),
+            path: Some(path),
+        })
+    }
+
+    /// Every entry, oldest first.
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.lock().clone()
+    }
+
+    /// The entries of runs for `tenant`, oldest first.
+    pub fn for_tenant(&self, tenant: &str) -> Vec<AuditEntry> {
+        self.query(&AuditQuery::new().tenant(tenant))
+    }
+
+    /// The entries `query` matches, oldest first.
+    pub fn query(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        self.lock().iter().filter(|e| query.matches(e)).cloned().collect()
+    }
+
+    /// Checks that no entry was altered, removed, or reordered.
+    pub fn verify(&self) -> Result<(), String> {
+        verify_chain(&self.lock())
+    }
+
+    /// Serializes every entry as a JSON array.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&*self.lock()).expect("entries are always serializable")
+    }
+
+    /// Appends an entry for a node execution that just finished.
+    pub(crate) fn record(
+        &self,
+        tenant: Option<&str>,
+        run_id: &str,
+        node: &str,
+        node_type: &str,
+        inputs_hash: Option<String>,
+        status: Status,
+    ) -> Result<(), String> {
+        let mut entries = self.lock();
+        let mut entry = AuditEntry {
+            seq: entries.len() as u64 + 1,
+            timestamp_ms: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64,
+            tenant: tenant.map(str::to_string),
+            run_id: Some(run_id.to_string()),
+            node: node.to_string(),
+            node_type: node_type.to_string(),
+            inputs_hash,
+            status,
+            prev_hash: entries.last().map(|e| e.hash.clone()).unwrap_or_default(),
+            hash: String::new(),
+        };
+        entry.hash = entry.digest();
+        if let Some(path) = &self.path {
+            let line = serde_json::to_string(&entry).expect("audit entries are always serializable");
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| writeln!(file, "{}", line))
+                .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        }
+        entries.push(entry);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<AuditEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn verify_chain(entries: &[AuditEntry]) -> Result<(), String> {
+    let mut prev_hash = "";
+    for (i, entry) in entries.iter().enumerate() {
+        if entry.seq != i as u64 + 1 || entry.prev_hash != prev_hash {
+            return Err(format!("audit entry {} is out of sequence", i + 1));
+        }
+        if entry.hash != entry.digest() {
+            return Err(format!("audit entry {} was modified", entry.seq));
+        }
+        prev_hash = &entry.hash;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain() {
+        let log = AuditLog::new();
+        log.record(Some("acme"), "r1", "send", "email.send", Some("ab".to_string()), Status::Succeeded).unwrap();
+        log.record(None, "r2", "post", "http.request", None, Status::Failed).unwrap();
+        log.record(Some("acme"), "r3", "post", "http.request", None, Status::Handled).unwrap();
+        assert!(log.verify().is_ok());
+        assert_eq!(log.for_tenant("acme").len(), 2);
+
+        let entries = log.entries();
+        assert_eq!(entries[1].prev_hash, entries[0].hash);
+        let mut altered = entries.clone();
+        altered[1].status = Status::Succeeded;
+        assert_eq!(verify_chain(&altered).unwrap_err(), "audit entry 2 was modified");
+        let mut removed = entries.clone();
+        removed.remove(1);
+        assert_eq!(verify_chain(&removed).unwrap_err(), "audit entry 2 is out of sequence");
+    }
+
+    #[test]
+    fn test_query() {
+        let log = AuditLog::new();
+        log.record(Some("acme"), "r1", "send", "email.send", None, Status::Succeeded).unwrap();
+        log.record(Some("acme"), "r1", "post", "http.request", None, Status::Failed).unwrap();
+        log.record(Some("globex"), "r2", "post", "http.request", None, Status::Failed).unwrap();
+        let seqs = |query: AuditQuery| log.query(&query).into_iter().map(|e| e.seq).collect::<Vec<_>>();
+
+        assert_eq!(seqs(AuditQuery::new()), [1, 2, 3]);
+        assert_eq!(seqs(AuditQuery::new().run("r1")), [1, 2]);
+        assert_eq!(seqs(AuditQuery::new().node_type("http.request").status(Status::Failed)), [2, 3]);
+        assert_eq!(seqs(AuditQuery::new().tenant("acme").node("post")), [2]);
+        assert!(seqs(AuditQuery::new().tenant("initech")).is_empty());
+        let at = log.entries()[0].timestamp_ms;
+        assert_eq!(seqs(AuditQuery::new().between(at, u64::MAX)), [1, 2, 3]);
+        assert!(seqs(AuditQuery::new().between(0, at.saturating_sub(1))).is_empty());
+    }
+
+    #[test]
+    fn test_entries_without_run_id_verify() {
+        let log = AuditLog::new();
+        log.record(None, "r1", "send", "email.send", None, Status::Succeeded).unwrap();
+        let mut entry = log.entries().remove(0);
+        entry.run_id = None;
+        entry.hash = entry.digest();
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains("run_id"));
+        assert!(verify_chain(&[serde_json::from_str(&line).unwrap()]).is_ok());
+    }
+
+    #[test]
+    fn test_open_continues_file() {
+        let path = std::env::temp_dir().join(format!("audit_{}.jsonl", std::process::id()));
+        let log = AuditLog::open(&path).unwrap();
+        log.record(None, "r1", "send", "email.send", None, Status::Succeeded).unwrap();
+        let reopened = AuditLog::open(&path).unwrap();
+        reopened.record(None, "r2", "send", "email.send", None, Status::Succeeded).unwrap();
+        assert_eq!(AuditLog::open(&path).unwrap().entries().len(), 2);
+
+        let text = std::fs::read_to_string(&path).unwrap().replace("email.send", "email.draft");
+        std::fs::write(&path, text).unwrap();
+        assert_eq!(AuditLog::open(&path).unwrap_err(), "audit entry 1 was modified");
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,1011 @@
+//! Registration of the bundled plugin crates.
+//!
+//! Each plugin crate declares its own `NodeExecutor` trait, so plugins are
+//! adapted into the registry with closures rather than a shared impl, except
+//! plugins built on `metabuilder_core::AsyncNodeExecutor`, which are
+//! registered as they are. Each category registers only when its cargo
+//! feature is enabled.
+
+// With only some categories enabled, the helpers the others use go unused.
+#![cfg_attr(not(feature = "all-nodes"), allow(unused))]
+
+use crate::examples::Example;
+use crate::registry::Registry;
+use crate::schema::{NodeSchema, PortKind};
+use metabuilder_core::Runtime;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Registers a plugin that receives the full `Runtime`.
+macro_rules! plugin {
+    ($registry:ident, $krate:ident) => {{
+        use $krate::NodeExecutor as _;
+        let node = $krate::create();
+        let node_type = node.node_type;
+        $registry.describe(node_type, node.description);
+        $registry.register(node_type, move |inputs: HashMap<String, Value>, runtime: Option<&dyn Any>| node.execute(inputs, runtime));
+    }};
+}
+
+/// Registers a plugin built on `AsyncNodeExecutor`.
+#[cfg(feature = "async")]
+macro_rules! async_plugin {
+    ($registry:ident, $krate:ident) => {{
+        let node = $krate::create();
+        $registry.describe(node.node_type, node.description);
+        $registry.register_async(node.node_type, node);
+    }};
+}
+
+/// Registers a `var.*` plugin, which reads the variable store directly.
+macro_rules! store_plugin {
+    ($registry:ident, $krate:ident) => {{
+        use $krate::NodeExecutor as _;
+        let node = $krate::create();
+        let node_type = node.node_type;
+        $registry.describe(node_type, node.description);
+        $registry.register(node_type, move |inputs: HashMap<String, Value>, runtime: Option<&dyn Any>| {
+            let store = Runtime::from_any(runtime).map(|rt| &rt.store as &dyn Any);
+            node.execute(inputs, store)
+        });
+    }};
+}
+
+/// Registers every bundled plugin enabled by the crate's category features.
+pub fn register_all(registry: &mut Registry) {
+    #[cfg(feature = "math")]
+    register_math(registry);
+    #[cfg(feature = "string")]
+    register_string(registry);
+    #[cfg(feature = "logic")]
+    register_logic(registry);
+    #[cfg(feature = "list")]
+    register_list(registry);
+    #[cfg(feature = "convert")]
+    register_convert(registry);
+    #[cfg(feature = "var")]
+    register_var(registry);
+    #[cfg(feature = "remote")]
+    register_remote(registry);
+    #[cfg(feature = "mail")]
+    register_mail(registry);
+    #[cfg(feature = "sheets")]
+    register_sheets(registry);
+    #[cfg(feature = "http")]
+    register_http(registry);
+    #[cfg(feature = "auth")]
+    register_auth(registry);
+    #[cfg(feature = "grpc")]
+    register_grpc(registry);
+    #[cfg(feature = "control")]
+    register_control(registry);
+    #[cfg(feature = "table")]
+    register_table(registry);
+    #[cfg(feature = "data")]
+    register_data(registry);
+    #[cfg(feature = "bytes")]
+    register_bytes(registry);
+    #[cfg(feature = "path")]
+    register_path(registry);
+    #[cfg(feature = "file")]
+    register_file(registry);
+    #[cfg(feature = "text")]
+    register_text(registry);
+    #[cfg(feature = "event")]
+    register_event(registry);
+    #[cfg(feature = "datetime")]
+    register_datetime(registry);
+    #[cfg(feature = "regex")]
+    register_regex(registry);
+    #[cfg(feature = "encode")]
+    register_encode(registry);
+    #[cfg(feature = "crypto")]
+    register_crypto(registry);
+    #[cfg(feature = "random")]
+    register_random(registry);
+    #[cfg(feature = "uuid")]
+    register_uuid(registry);
+    #[cfg(feature = "csv")]
+    register_csv(registry);
+    #[cfg(feature = "yaml")]
+    register_yaml(registry);
+    #[cfg(feature = "toml")]
+    register_toml(registry);
+}
+
+#[cfg(feature = "math")]
+fn register_math(registry: &mut Registry) {
+    plugin!(registry, math_add);
+    plugin!(registry, math_subtract);
+    plugin!(registry, math_multiply);
+    plugin!(registry, math_divide);
+    plugin!(registry, math_modulo);
+    plugin!(registry, math_power);
+    plugin!(registry, math_abs);
+    plugin!(registry, math_round);
+    plugin!(registry, math_floor);
+    plugin!(registry, math_ceil);
+}
+
+#[cfg(feature = "string")]
+fn register_string(registry: &mut Registry) {
+    plugin!(registry, string_concat);
+    plugin!(registry, string_split);
+    plugin!(registry, string_replace);
+    plugin!(registry, string_upper);
+    plugin!(registry, string_lower);
+    plugin!(registry, string_trim);
+    plugin!(registry, string_length);
+    plugin!(registry, string_contains);
+    plugin!(registry, string_starts_with);
+    plugin!(registry, string_ends_with);
+    plugin!(registry, string_substring);
+    plugin!(registry, string_at);
+    plugin!(registry, string_transcode);
+    plugin!(registry, string_normalize_newlines);
+}
+
+#[cfg(feature = "logic")]
+fn register_logic(registry: &mut Registry) {
+    plugin!(registry, logic_and);
+    plugin!(registry, logic_or);
+    plugin!(registry, logic_not);
+    plugin!(registry, logic_xor);
+    plugin!(registry, logic_equals);
+    plugin!(registry, logic_gt);
+    plugin!(registry, logic_gte);
+    plugin!(registry, logic_lt);
+    plugin!(registry, logic_lte);
+
+    registry.describe("logic.in", "Check if value is in list");
+    registry.register("logic.in", |inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>| {
+        logic_in::run(&mut HashMap::new(), &inputs).unwrap_or_else(|e| {
+            let mut output = HashMap::new();
+            output.insert("error".to_string(), serde_json::json!(e));
+            output
+        })
+    });
+}
+
+#[cfg(feature = "list")]
+fn register_list(registry: &mut Registry) {
+    plugin!(registry, list_concat);
+    plugin!(registry, list_length);
+    plugin!(registry, list_slice);
+    plugin!(registry, list_reverse);
+    plugin!(registry, list_first);
+    plugin!(registry, list_last);
+    plugin!(registry, list_at);
+    plugin!(registry, list_contains);
+    plugin!(registry, list_index_of);
+    plugin!(registry, list_unique);
+    plugin!(registry, list_sort);
+}
+
+#[cfg(feature = "convert")]
+fn register_convert(registry: &mut Registry) {
+    plugin!(registry, convert_to_string);
+    plugin!(registry, convert_to_number);
+    plugin!(registry, convert_to_boolean);
+    plugin!(registry, convert_to_json);
+    plugin!(registry, convert_parse_json);
+    plugin!(registry, convert_to_list);
+    plugin!(registry, convert_to_object);
+    plugin!(registry, convert_proto_encode);
+    plugin!(registry, convert_proto_decode);
+    plugin!(registry, convert_avro_encode);
+    plugin!(registry, convert_avro_decode);
+}
+
+#[cfg(feature = "var")]
+fn register_var(registry: &mut Registry) {
+    store_plugin!(registry, var_get);
+    store_plugin!(registry, var_set);
+    store_plugin!(registry, var_delete);
+    store_plugin!(registry, var_exists);
+    store_plugin!(registry, var_keys);
+    store_plugin!(registry, var_clear);
+}
+
+#[cfg(feature = "remote")]
+fn register_remote(registry: &mut Registry) {
+    plugin!(registry, remote_ssh_exec);
+    plugin!(registry, remote_sftp_put);
+    plugin!(registry, remote_sftp_get);
+    plugin!(registry, remote_ftp_get);
+    plugin!(registry, remote_ftp_put);
+}
+
+#[cfg(feature = "mail")]
+fn register_mail(registry: &mut Registry) {
+    plugin!(registry, mail_imap_fetch);
+}
+
+#[cfg(feature = "sheets")]
+fn register_sheets(registry: &mut Registry) {
+    plugin!(registry, sheets_read_range);
+    plugin!(registry, sheets_append_row);
+}
+
+#[cfg(feature = "http")]
+fn register_http(registry: &mut Registry) {
+    plugin!(registry, http_verify_signature);
+    plugin!(registry, http_paginate);
+    plugin!(registry, http_graphql);
+    plugin!(registry, http_soap);
+    async_plugin!(registry, http_request);
+}
+
+#[cfg(feature = "auth")]
+fn register_auth(registry: &mut Registry) {
+    plugin!(registry, auth_oauth2_token);
+}
+
+#[cfg(feature = "grpc")]
+fn register_grpc(registry: &mut Registry) {
+    plugin!(registry, grpc_call);
+}
+
+#[cfg(feature = "control")]
+fn register_control(registry: &mut Registry) {
+    plugin!(registry, control_if);
+    plugin!(registry, control_switch);
+    plugin!(registry, control_delay);
+}
+
+#[cfg(feature = "table")]
+fn register_table(registry: &mut Registry) {
+    plugin!(registry, table_read_parquet);
+    plugin!(registry, table_write_parquet);
+    plugin!(registry, table_from_rows);
+    plugin!(registry, table_to_rows);
+    plugin!(registry, table_sql);
+}
+
+#[cfg(feature = "data")]
+fn register_data(registry: &mut Registry) {
+    plugin!(registry, data_hash_value);
+}
+
+#[cfg(feature = "bytes")]
+fn register_bytes(registry: &mut Registry) {
+    plugin!(registry, bytes_length);
+    plugin!(registry, bytes_slice);
+    plugin!(registry, bytes_concat);
+    plugin!(registry, bytes_to_string);
+}
+
+#[cfg(feature = "path")]
+fn register_path(registry: &mut Registry) {
+    plugin!(registry, path_join);
+    plugin!(registry, path_split);
+    plugin!(registry, path_extension);
+    plugin!(registry, path_normalize);
+    plugin!(registry, path_glob_match);
+    plugin!(registry, path_basename);
+    plugin!(registry, path_dirname);
+}
+
+#[cfg(feature = "file")]
+fn register_file(registry: &mut Registry) {
+    plugin!(registry, file_detect_mime);
+    plugin!(registry, file_read_lines);
+    plugin!(registry, file_write);
+    plugin!(registry, file_append);
+    plugin!(registry, file_list);
+    plugin!(registry, file_exists);
+    plugin!(registry, file_stat);
+    plugin!(registry, file_copy);
+    plugin!(registry, file_move);
+    plugin!(registry, file_delete);
+}
+
+#[cfg(feature = "text")]
+fn register_text(registry: &mut Registry) {
+    plugin!(registry, text_front_matter);
+}
+
+#[cfg(feature = "event")]
+fn register_event(registry: &mut Registry) {
+    plugin!(registry, event_emit);
+    plugin!(registry, event_on);
+}
+
+#[cfg(feature = "datetime")]
+fn register_datetime(registry: &mut Registry) {
+    plugin!(registry, datetime_parse);
+    plugin!(registry, datetime_is_before);
+    plugin!(registry, datetime_is_after);
+    plugin!(registry, datetime_is_between);
+    plugin!(registry, datetime_timezone_convert);
+    plugin!(registry, datetime_extract);
+}
+
+#[cfg(feature = "regex")]
+fn register_regex(registry: &mut Registry) {
+    plugin!(registry, regex_extract);
+    plugin!(registry, regex_split);
+}
+
+#[cfg(feature = "encode")]
+fn register_encode(registry: &mut Registry) {
+    plugin!(registry, encode_hex_encode);
+    plugin!(registry, encode_hex_decode);
+}
+
+#[cfg(feature = "crypto")]
+fn register_crypto(registry: &mut Registry) {
+    plugin!(registry, crypto_hash);
+    plugin!(registry, crypto_encrypt);
+    plugin!(registry, crypto_decrypt);
+    plugin!(registry, crypto_jwt_sign);
+    plugin!(registry, crypto_jwt_verify);
+    plugin!(registry, crypto_password_hash);
+    plugin!(registry, crypto_password_verify);
+    plugin!(registry, crypto_random_bytes);
+}
+
+#[cfg(feature = "random")]
+fn register_random(registry: &mut Registry) {
+    plugin!(registry, random_choice);
+    plugin!(registry, random_sample);
+    plugin!(registry, random_shuffle);
+}
+
+#[cfg(feature = "uuid")]
+fn register_uuid(registry: &mut Registry) {
+    plugin!(registry, uuid_generate);
+    plugin!(registry, uuid_validate);
+}
+
+#[cfg(feature = "csv")]
+fn register_csv(registry: &mut Registry) {
+    plugin!(registry, csv_parse);
+    plugin!(registry, csv_serialize);
+}
+
+#[cfg(feature = "yaml")]
+fn register_yaml(registry: &mut Registry) {
+    plugin!(registry, yaml_parse);
+    plugin!(registry, yaml_serialize);
+}
+
+#[cfg(feature = "toml")]
+fn register_toml(registry: &mut Registry) {
+    plugin!(registry, toml_parse);
+    plugin!(registry, toml_serialize);
+}
+
+/// Registers port schemas for the bundled node types whose ports are fixed.
+pub fn register_schemas(registry: &mut Registry) {
+    use PortKind::*;
+    for node_type in ["math.add", "math.subtract", "math.multiply", "math.divide"] {
+        registry.register_schema(node_type, NodeSchema::new().input("numbers", List).output("result", Number));
+    }
+    registry.register_schema("math.abs", NodeSchema::new().input("value", Number).output("result", Number));
+    registry.register_schema(
+        "math.round",
+        NodeSchema::new().input("value", Number).input("decimals", Number).output("result", Number),
+    );
+    registry.register_schema("string.upper", NodeSchema::new().input("string", String).output("result", String));
+    registry.register_schema("string.lower", NodeSchema::new().input("string", String).output("result", String));
+    registry.register_schema(
+        "string.length",
+        NodeSchema::new().input("string", String).input("unit", String).output("result", Number),
+    );
+    for node_type in ["logic.lt", "logic.gt"] {
+        registry.register_schema(node_type, NodeSchema::new().input("a", Any).input("b", Any).output("result", Bool));
+    }
+    registry.register_schema(
+        "control.if",
+        NodeSchema::new()
+            .input("condition", Any)
+            .input("value", Any)
+            .output("then", Any)
+            .output("else", Any)
+            .output("branch", String)
+            .output("result", Bool)
+            .branching(),
+    );
+    registry.register_schema(
+        "control.delay",
+        NodeSchema::new()
+            .input("duration_ms", Number)
+            .input("until", String)
+            .input("value", Any)
+            .output("result", Any)
+            .output("waited_ms", Number),
+    );
+    registry.register_schema(
+        "control.while",
+        NodeSchema::new()
+            .input("condition", String)
+            .input("max_iterations", Number)
+            .input("state", Any)
+            .output("iterations", Number)
+            .output("state", Any)
+            .output("outputs", Object),
+    );
+    registry.register_schema(
+        "workflow.call",
+        NodeSchema::new()
+            .input("workflow", String)
+            .input("path", String)
+            .input("inputs", Object)
+            .output("result", Object)
+            .output("outputs", Object)
+            .output("vars", Object),
+    );
+    registry.register_schema(
+        "convert.to_json",
+        NodeSchema::new()
+            .input("value", Any)
+            .input("pretty", Bool)
+            .input("canonical", Bool)
+            .input("indent", Any)
+            .input("sort_keys", Bool)
+            .input("ascii", Bool)
+            .output("result", String),
+    );
+    registry.register_schema(
+        "convert.parse_json",
+        NodeSchema::new()
+            .input("string", String)
+            .input("mode", String)
+            .output("result", Any)
+            .output("line", Number)
+            .output("column", Number)
+            .output("offset", Number)
+            .output("snippet", String),
+    );
+    registry.register_schema(
+        "data.hash_value",
+        NodeSchema::new()
+            .input("value", Any)
+            .input("algorithm", String)
+            .output("result", String)
+            .output("canonical", String),
+    );
+    registry.register_schema("bytes.length", NodeSchema::new().input("bytes", Any).output("result", Number));
+    registry.register_schema(
+        "bytes.slice",
+        NodeSchema::new()
+            .input("bytes", Any)
+            .input("start", Number)
+            .input("end", Number)
+            .output("result", Object),
+    );
+    registry.register_schema("bytes.concat", NodeSchema::new().input("items", List).output("result", Object));
+    registry.register_schema(
+        "bytes.to_string",
+        NodeSchema::new()
+            .input("bytes", Any)
+            .input("encoding", String)
+            .input("errors", String)
+            .output("result", String),
+    );
+    registry.register_schema(
+        "file.detect_mime",
+        NodeSchema::new()
+            .input("filename", String)
+            .input("bytes", Any)
+            .output("result", String)
+            .output("source", String),
+    );
+    registry.register_schema("file.read_lines", NodeSchema::new().input("path", String).output("result", Object));
+    registry.register_schema(
+        "text.front_matter",
+        NodeSchema::new()
+            .input("text", String)
+            .output("data", Object)
+            .output("body", String)
+            .output("format", String),
+    );
+    registry.register_schema("path.join", NodeSchema::new().input("parts", List).output("result", String));
+    registry.register_schema("path.split", NodeSchema::new().input("path", String).output("result", Object));
+    registry.register_schema(
+        "path.extension",
+        NodeSchema::new().input("path", String).input("replace", String).output("result", String),
+    );
+    registry.register_schema("path.normalize", NodeSchema::new().input("path", String).output("result", String));
+    registry.register_schema(
+        "path.basename",
+        NodeSchema::new().input("path", String).input("strip_extension", Bool).output("result", String),
+    );
+    registry.register_schema("path.dirname", NodeSchema::new().input("path", String).output("result", String));
+    registry.register_schema(
+        "path.glob_match",
+        NodeSchema::new()
+            .input("pattern", String)
+            .input("path", String)
+            .input("paths", List)
+            .output("result", Any),
+    );
+    registry.register_schema(
+        "event.emit",
+        NodeSchema::new().input("topic", String).input("payload", Any).output("result", Any),
+    );
+    registry.register_schema(
+        "event.on",
+        NodeSchema::new()
+            .input("topic", String)
+            .input("count", Number)
+            .input("timeout_ms", Number)
+            .output("events", List)
+            .output("count", Number)
+            .output("result", Any),
+    );
+    registry.register_schema(
+        "datetime.parse",
+        NodeSchema::new()
+            .input("string", String)
+            .input("format", String)
+            .output("result", String)
+            .output("epoch", Number)
+            .output("epoch_ms", Number),
+    );
+    for node_type in ["datetime.is_before", "datetime.is_after"] {
+        registry.register_schema(
+            node_type,
+            NodeSchema::new().input("a", Any).input("b", Any).input("format", String).output("result", Bool),
+        );
+    }
+    registry.register_schema(
+        "datetime.is_between",
+        NodeSchema::new()
+            .input("value", Any)
+            .input("start", Any)
+            .input("end", Any)
+            .input("inclusive", Bool)
+            .input("format", String)
+            .output("result", Bool),
+    );
+    registry.register_schema(
+        "datetime.timezone_convert",
+        NodeSchema::new()
+            .input("value", Any)
+            .input("from", String)
+            .input("to", String)
+            .input("format", String)
+            .output("result", String)
+            .output("abbreviation", String),
+    );
+    registry.register_schema(
+        "datetime.extract",
+        NodeSchema::new()
+            .input("value", Any)
+            .input("format", String)
+            .output("result", Object)
+            .output("weekday", Number)
+            .output("weekday_name", String)
+            .output("iso_week", Number)
+            .output("iso_year", Number)
+            .output("day_of_year", Number)
+            .output("quarter", Number)
+            .output("is_weekend", Bool),
+    );
+    registry.register_schema(
+        "http.request",
+        NodeSchema::new()
+            .input("method", String)
+            .input("url", String)
+            .input("headers", Object)
+            .input("query", Object)
+            .input("body", Any)
+            .input("form", Object)
+            .input("timeout_ms", Number)
+            .input("connect_timeout_ms", Number)
+            .input("auth", String)
+            .input("retries", Number)
+            .input("backoff_ms", Number)
+            .input("rate_limit", Number)
+            .output("status", Number)
+            .output("ok", Bool)
+            .output("headers", Object)
+            .output("body", Any)
+            .output("attempts", Number),
+    );
+    registry.register_schema(
+        "regex.extract",
+        NodeSchema::new()
+            .input("text", String)
+            .input("pattern", String)
+            .input("flags", String)
+            .input("limit", Number)
+            .output("result", List)
+            .output("count", Number),
+    );
+    registry.register_schema(
+        "regex.split",
+        NodeSchema::new()
+            .input("text", String)
+            .input("pattern", String)
+            .input("flags", String)
+            .input("limit", Number)
+            .output("result", List)
+            .output("count", Number),
+    );
+    for node_type in ["file.write", "file.append"] {
+        registry.register_schema(
+            node_type,
+            NodeSchema::new()
+                .input("path", String)
+                .input("content", Any)
+                .input("atomic", Bool)
+                .input("create_dirs", Bool)
+                .output("result", Number),
+        );
+    }
+    registry.register_schema(
+        "file.list",
+        NodeSchema::new()
+            .input("path", String)
+            .input("pattern", String)
+            .input("depth", Number)
+            .input("kind", String)
+            .input("extensions", Any)
+            .output("result", List)
+            .output("count", Number),
+    );
+    registry.register_schema(
+        "file.exists",
+        NodeSchema::new().input("path", String).output("result", Bool).output("kind", String),
+    );
+    registry.register_schema("file.stat", NodeSchema::new().input("path", String).output("result", Object));
+    registry.register_schema(
+        "file.copy",
+        NodeSchema::new()
+            .input("from", String)
+            .input("to", String)
+            .input("overwrite", Bool)
+            .input("create_dirs", Bool)
+            .output("result", Number),
+    );
+    registry.register_schema(
+        "file.move",
+        NodeSchema::new()
+            .input("from", String)
+            .input("to", String)
+            .input("overwrite", Bool)
+            .input("create_dirs", Bool)
+            .output("result", String),
+    );
+    registry.register_schema(
+        "file.delete",
+        NodeSchema::new()
+            .input("path", String)
+            .input("recursive", Bool)
+            .input("missing_ok", Bool)
+            .output("result", Bool),
+    );
+    registry.register_schema(
+        "encode.hex_encode",
+        NodeSchema::new().input("bytes", Any).input("uppercase", Bool).output("result", String),
+    );
+    registry.register_schema("encode.hex_decode", NodeSchema::new().input("hex", String).output("result", Object));
+    registry.register_schema(
+        "crypto.hash",
+        NodeSchema::new()
+            .input("data", Any)
+            .input("algorithm", String)
+            .output("result", String)
+            .output("base64", String),
+    );
+    registry.register_schema(
+        "crypto.encrypt",
+        NodeSchema::new()
+            .input("data", Any)
+            .input("key_secret", String)
+            .input("aad", Any)
+            .input("encoding", String)
+            .output("result", Any),
+    );
+    registry.register_schema(
+        "crypto.decrypt",
+        NodeSchema::new()
+            .input("data", Any)
+            .input("key_secret", String)
+            .input("aad", Any)
+            .input("as_text", Bool)
+            .output("result", Any),
+    );
+    registry.register_schema(
+        "crypto.jwt_sign",
+        NodeSchema::new()
+            .input("claims", Object)
+            .input("algorithm", String)
+            .input("key_secret", String)
+            .input("expires_in", Number)
+            .input("key_id", String)
+            .output("result", String),
+    );
+    registry.register_schema(
+        "crypto.jwt_verify",
+        NodeSchema::new()
+            .input("token", String)
+            .input("algorithm", String)
+            .input("key_secret", String)
+            .input("audience", String)
+            .input("issuer", String)
+            .input("leeway", Number)
+            .input("require_exp", Bool)
+            .output("valid", Bool)
+            .output("claims", Object)
+            .output("reason", String),
+    );
+    registry.register_schema(
+        "crypto.password_hash",
+        NodeSchema::new()
+            .input("password", String)
+            .input("algorithm", String)
+            .input("cost", Number)
+            .output("result", String),
+    );
+    registry.register_schema(
+        "crypto.password_verify",
+        NodeSchema::new().input("password", String).input("hash", String).output("valid", Bool),
+    );
+    registry.register_schema(
+        "crypto.random_bytes",
+        NodeSchema::new().input("length", Number).input("format", String).output("result", Any),
+    );
+    registry.register_schema(
+        "random.choice",
+        NodeSchema::new().input("list", List).output("result", Any).output("index", Number),
+    );
+    registry.register_schema(
+        "random.sample",
+        NodeSchema::new().input("list", List).input("count", Number).output("result", List),
+    );
+    registry.register_schema("random.shuffle", NodeSchema::new().input("list", List).output("result", List));
+    registry.register_schema("uuid.generate", NodeSchema::new().input("version", Number).output("result", String));
+    registry.register_schema(
+        "uuid.validate",
+        NodeSchema::new()
+            .input("value", String)
+            .output("valid", Bool)
+            .output("result", String)
+            .output("version", Number)
+            .output("variant", String)
+            .output("reason", String),
+    );
+    registry.register_schema(
+        "csv.parse",
+        NodeSchema::new()
+            .input("text", String)
+            .input("delimiter", String)
+            .input("quote", String)
+            .input("header", Bool)
+            .input("infer_types", Bool)
+            .input("strict", Bool)
+            .output("result", List)
+            .output("headers", List)
+            .output("count", Number)
+            .output("errors", List),
+    );
+    registry.register_schema(
+        "csv.serialize",
+        NodeSchema::new()
+            .input("rows", List)
+            .input("columns", List)
+            .input("header", Bool)
+            .input("delimiter", String)
+            .input("quote", String)
+            .input("quote_all", Bool)
+            .output("result", String)
+            .output("count", Number),
+    );
+    registry.register_schema(
+        "yaml.parse",
+        NodeSchema::new().input("string", String).output("result", Any).output("line", Number),
+    );
+    registry.register_schema("yaml.serialize", NodeSchema::new().input("value", Any).output("result", String));
+    registry.register_schema(
+        "toml.parse",
+        NodeSchema::new().input("string", String).output("result", Object).output("line", Number),
+    );
+    registry.register_schema("toml.serialize", NodeSchema::new().input("value", Object).output("result", String));
+}
+
+/// Marks the bundled node types that reach outside the run (network,
+/// filesystem, remote hosts), which `Engine::dry_run` stubs out.
+pub fn register_side_effects(registry: &mut Registry) {
+    for node_type in [
+        "remote.ssh_exec",
+        "remote.sftp_put",
+        "remote.sftp_get",
+        "remote.ftp_get",
+        "remote.ftp_put",
+        "mail.imap_fetch",
+        "sheets.read_range",
+        "sheets.append_row",
+        "http.paginate",
+        "http.graphql",
+        "http.soap",
+        "http.request",
+        "auth.oauth2_token",
+        "grpc.call",
+        "convert.avro_encode",
+        "convert.avro_decode",
+        "table.read_parquet",
+        "table.write_parquet",
+        "table.sql",
+        "file.write",
+        "file.append",
+        "file.copy",
+        "file.move",
+        "file.delete",
+    ] {
+        registry.mark_side_effects(node_type);
+    }
+}
+
+/// Marks the bundled node types that `Engine::compile` may fold: those
+/// whose outputs depend on their inputs alone. Node types that read files,
+/// call a registry, or draw random values stay off the list even when their
+/// category is otherwise pure.
+pub fn register_deterministic(registry: &mut Registry) {
+    for node_type in [
+        "math.add",
+        "math.subtract",
+        "math.multiply",
+        "math.divide",
+        "math.modulo",
+        "math.power",
+        "math.abs",
+        "math.round",
+        "math.floor",
+        "math.ceil",
+        "string.concat",
+        "string.split",
+        "string.replace",
+        "string.upper",
+        "string.lower",
+        "string.trim",
+        "string.length",
+        "string.contains",
+        "string.starts_with",
+        "string.ends_with",
+        "string.substring",
+        "string.at",
+        "string.transcode",
+        "string.normalize_newlines",
+        "logic.and",
+        "logic.or",
+        "logic.not",
+        "logic.xor",
+        "logic.equals",
+        "logic.gt",
+        "logic.gte",
+        "logic.lt",
+        "logic.lte",
+        "logic.in",
+        "list.concat",
+        "list.length",
+        "list.slice",
+        "list.reverse",
+        "list.first",
+        "list.last",
+        "list.at",
+        "list.contains",
+        "list.index_of",
+        "list.unique",
+        "list.sort",
+        "convert.to_string",
+        "convert.to_number",
+        "convert.to_boolean",
+        "convert.to_json",
+        "convert.parse_json",
+        "convert.to_list",
+        "convert.to_object",
+        "bytes.length",
+        "bytes.slice",
+        "bytes.concat",
+        "bytes.to_string",
+        "path.join",
+        "path.split",
+        "path.extension",
+        "path.normalize",
+        "path.glob_match",
+        "path.basename",
+        "path.dirname",
+    ] {
+        registry.mark_deterministic(node_type);
+    }
+}
+
+/// Tags the bundled node types of each category with words editors search
+/// for that their names may not contain.
+pub fn register_tags(registry: &mut Registry) {
+    let synonyms: &[(&str, &[&str])] = &[
+        ("math", &["number", "arithmetic"]),
+        ("string", &["text"]),
+        ("logic", &["boolean", "condition"]),
+        ("list", &["array"]),
+        ("convert", &["cast", "parse", "serialize"]),
+        ("var", &["variable", "state"]),
+        ("remote", &["ssh", "ftp", "server"]),
+        ("mail", &["email"]),
+        ("sheets", &["spreadsheet", "google"]),
+        ("http", &["web", "api", "fetch", "url"]),
+        ("auth", &["oauth", "token", "login"]),
+        ("grpc", &["rpc", "protobuf"]),
+        ("control", &["flow", "branch"]),
+        ("table", &["dataframe", "arrow"]),
+        ("bytes", &["binary", "buffer"]),
+        ("path", &["filesystem", "directory"]),
+        ("file", &["filesystem", "disk"]),
+        ("text", &["document", "markdown"]),
+        ("event", &["signal", "trigger"]),
+        ("datetime", &["date", "time", "timestamp"]),
+        ("regex", &["pattern", "match"]),
+        ("encode", &["hex", "binary"]),
+        ("crypto", &["security", "digest", "checksum"]),
+        ("random", &["shuffle", "sample", "pick"]),
+        ("uuid", &["guid", "id", "identifier"]),
+        ("csv", &["tsv", "spreadsheet", "table"]),
+        ("yaml", &["yml", "config"]),
+        ("toml", &["config", "cargo"]),
+    ];
+    let node_types: Vec<String> = registry.node_types().map(String::from).collect();
+    for node_type in node_types {
+        let category = node_type.split('.').next().unwrap_or_default();
+        for (_, tags) in synonyms.iter().filter(|(name, _)| *name == category) {
+            for tag in *tags {
+                registry.tag(node_type.as_str(), *tag);
+            }
+        }
+    }
+}
+
+/// Adds examples of the bundled pure node types, which `Registry::self_test`
+/// runs. Examples of node types whose category is disabled are skipped.
+pub fn register_examples(registry: &mut Registry) {
+    let examples = [
+        ("math.add", json!({"numbers": [1, 2, 3]}), json!({"result": 6})),
+        ("math.subtract", json!({"numbers": [10, 4]}), json!({"result": 6})),
+        ("math.multiply", json!({"numbers": [2, 3, 4]}), json!({"result": 24})),
+        ("math.divide", json!({"numbers": [12, 4]}), json!({"result": 3})),
+        ("math.round", json!({"value": 1.23456, "decimals": 2}), json!({"result": 1.23})),
+        ("math.abs", json!({"value": -5}), json!({"result": 5})),
+        ("string.upper", json!({"string": "hello"}), json!({"result": "HELLO"})),
+        ("string.split", json!({"string": "a,b,c", "separator": ","}), json!({"result": ["a", "b", "c"]})),
+        ("string.replace", json!({"string": "hello world", "old": "world", "new": "rust"}), json!({"result": "hello rust"})),
+        ("string.trim", json!({"string": "  hello  "}), json!({"result": "hello"})),
+        ("logic.in", json!({"value": 2, "list": [1, 2, 3]}), json!({"result": true})),
+        ("logic.in", json!({"value": 4, "list": [1, 2, 3]}), json!({"result": false})),
+        ("list.sort", json!({"list": [3, 1, 2]}), json!({"result": [1, 2, 3]})),
+        ("list.unique", json!({"list": [1, 2, 2, 3, 3, 3]}), json!({"result": [1, 2, 3]})),
+        ("list.slice", json!({"list": [1, 2, 3, 4, 5], "start": 1, "end": 4}), json!({"result": [2, 3, 4]})),
+        ("convert.to_number", json!({"value": "42.5"}), json!({"result": 42.5})),
+        ("path.join", json!({"parts": ["usr", "local", "bin"]}), json!({"result": "usr/local/bin"})),
+        ("path.basename", json!({"path": "reports/q1.csv"}), json!({"result": "q1.csv"})),
+        ("path.dirname", json!({"path": "reports/q1.csv"}), json!({"result": "reports"})),
+        ("regex.split", json!({"text": "a1b22c", "pattern": "[0-9]+"}), json!({"result": ["a", "b", "c"]})),
+        ("encode.hex_encode", json!({"bytes": "hi"}), json!({"result": "6869"})),
+        ("encode.hex_decode", json!({"hex": "6869"}), json!({"result": {"$bytes": "aGk="}})),
+        ("crypto.hash", json!({"data": "abc", "algorithm": "sha1"}), json!({"base64": "qZk+NkcGgWq6PiVxeFDCbJzQ2J0="})),
+        ("csv.parse", json!({"text": "id,name\n1,Ada\n", "infer_types": true}), json!({"result": [{"id": 1, "name": "Ada"}]})),
+        ("csv.serialize", json!({"rows": [{"id": 1, "name": "Ada"}]}), json!({"result": "id,name\n1,Ada\n"})),
+        ("yaml.parse", json!({"string": "ports: [80, 443]\n"}), json!({"result": {"ports": [80, 443]}})),
+        ("yaml.serialize", json!({"value": {"ports": [80]}}), json!({"result": "ports:\n  - 80\n"})),
+        ("toml.parse", json!({"string": "[package]\nname = \"app\"\n"}), json!({"result": {"package": {"name": "app"}}})),
+        ("toml.serialize", json!({"value": {"package": {"name": "app"}}}), json!({"result": "[package]\nname = \"app\"\n"})),
+        (
+            "crypto.password_verify",
+            json!({"password": "hunter2", "hash": "$2b$04$jjNwM2s35kuCSLiKd6SxMOPYlRuwt2y1TCzBLX1dNFe2XbwSVojzi"}),
+            json!({"valid": true}),
+        ),
+        (
+            "uuid.validate",
+            json!({"value": "936DA01F9ABD4D9D80C702AF85C822A8"}),
+            json!({"valid": true, "result": "936da01f-9abd-4d9d-80c7-02af85c822a8", "version": 4, "variant": "rfc4122"}),
+        ),
+    ];
+    for (node_type, inputs, outputs) in examples {
+        if registry.contains(node_type) {
+            registry.add_example(node_type, Example::new(inputs, outputs));
+        }
+    }
+}
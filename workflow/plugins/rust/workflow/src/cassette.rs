@@ -0,0 +1,179 @@
+//! Recorded responses of nodes with side effects, for hermetic tests.
+//!
+//! With `Engine::with_cassette`, every node whose type the registry marks as
+//! having side effects (network, remote, mail, and the like) goes through a
+//! `Cassette`. In `Record` mode the node runs for real and the cassette keeps
+//! its inputs and outputs, rewriting its file after each one. In `Replay`
+//! mode the node does not run: the cassette answers with the outputs recorded
+//! for the same node type and inputs, and fails the node if there are none.
+//! Identical calls replay their recordings in order, the last one repeating.
+
+use crate::redact::Redaction;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Whether a cassette records or replays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Run nodes and keep what they returned
+    Record,
+    /// Answer with recorded outputs instead of running nodes
+    Replay,
+}
+
+/// One recorded node execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub node_type: String,
+    pub inputs: HashMap<String, Value>,
+    pub outputs: HashMap<String, Value>,
+}
+
+/// Recorded interactions, optionally backed by a JSON file.
+#[derive(Debug)]
+pub struct Cassette {
+    mode: CassetteMode,
+    path: Option<PathBuf>,
+    interactions: Mutex<Vec<Interaction>>,
+    /// Per interaction, whether replay has used it
+    played: Mutex<Vec<bool>>,
+}
+
+impl Cassette {
+    /// An empty cassette that records to `path`, replacing any recording
+    /// already there.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::with(CassetteMode::Record, Some(path.into()), Vec::new())
+    }
+
+    /// A cassette that replays the recording saved at `path`.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let text = std::fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let interactions = Self::parse(&text)?;
+        Ok(Self::with(CassetteMode::Replay, Some(path), interactions))
+    }
+
+    /// A cassette kept in memory, replaying `interactions` or recording after
+    /// them.
+    pub fn in_memory(mode: CassetteMode, interactions: Vec<Interaction>) -> Self {
+        Self::with(mode, None, interactions)
+    }
+
+    fn with(mode: CassetteMode, path: Option<PathBuf>, interactions: Vec<Interaction>) -> Self {
+        Cassette {
+            mode,
+            path,
+            played: Mutex::new(vec![false; interactions.len()]),
+            interactions: Mutex::new(interactions),
+        }
+    }
+
+    /// Whether the cassette records or replays.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Parses interactions saved with `to_json`.
+    pub fn parse(text: &str) -> Result<Vec<Interaction>, String> {
+        serde_json::from_str(text).map_err(|e| format!("invalid cassette: {}", e))
+    }
+
+    /// The interactions recorded so far, in order.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Serializes the interactions as pretty JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.interactions()).expect("cassette values are always serializable")
+    }
+
+    /// Records the outputs of `run` for a node of `node_type`, or replays
+    /// the recorded ones without calling it. Recordings hold inputs and
+    /// outputs masked by `redaction`, and replay matches the masked inputs.
+    pub(crate) fn play(
+        &self,
+        node_type: &str,
+        inputs: &HashMap<String, Value>,
+        redaction: &Redaction,
+        run: impl FnOnce() -> HashMap<String, Value>,
+    ) -> Result<HashMap<String, Value>, String> {
+        let inputs = redaction.ports(inputs);
+        match self.mode {
+            CassetteMode::Replay => self.find(node_type, &inputs),
+            CassetteMode::Record => {
+                let outputs = run();
+                self.interactions.lock().unwrap_or_else(|e| e.into_inner()).push(Interaction {
+                    node_type: node_type.to_string(),
+                    inputs,
+                    outputs: redaction.ports(&outputs),
+                });
+                self.save()?;
+                Ok(outputs)
+            }
+        }
+    }
+
+    /// The first unplayed recording matching the call, else the last played one.
+    fn find(&self, node_type: &str, inputs: &HashMap<String, Value>) -> Result<HashMap<String, Value>, String> {
+        let interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        let mut played = self.played.lock().unwrap_or_else(|e| e.into_inner());
+        let matching: Vec<usize> = (0..interactions.len())
+            .filter(|&i| interactions[i].node_type == node_type && interactions[i].inputs == *inputs)
+            .collect();
+        let index = match matching.iter().find(|&&i| !played[i]).or(matching.last()) {
+            Some(&index) => index,
+            None => return Err(format!("no recorded {} call with these inputs", node_type)),
+        };
+        played[index] = true;
+        Ok(interactions[index].outputs.clone())
+    }
+
+    /// Rewrites the backing file, replacing it atomically.
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, self.to_json())
+            .and_then(|_| std::fs::rename(&partial, path))
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn inputs(url: &str) -> HashMap<String, Value> {
+        HashMap::from([("url".to_string(), json!(url))])
+    }
+
+    #[test]
+    fn test_record_then_replay() {
+        let path = std::env::temp_dir().join(format!("cassette_{}.json", std::process::id()));
+        let recorder = Cassette::record(&path);
+        for status in [200, 503] {
+            let outputs = recorder
+                .play("http.request", &inputs("https://x.io"), &Redaction::new(), || HashMap::from([("status".to_string(), json!(status))]))
+                .unwrap();
+            assert_eq!(outputs["status"], json!(status));
+        }
+
+        let player = Cassette::replay(&path).unwrap();
+        let replay = || player.play("http.request", &inputs("https://x.io"), &Redaction::new(), || unreachable!("replay does not run nodes"));
+        assert_eq!(replay().unwrap()["status"], json!(200));
+        assert_eq!(replay().unwrap()["status"], json!(503));
+        assert_eq!(replay().unwrap()["status"], json!(503));
+        assert_eq!(
+            player.play("http.request", &inputs("https://y.io"), &Redaction::new(), HashMap::new).unwrap_err(),
+            "no recorded http.request call with these inputs"
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
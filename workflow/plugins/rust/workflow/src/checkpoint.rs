@@ -0,0 +1,195 @@
+//! Run checkpoints, for resuming interrupted workflows.
+//!
+//! With `Engine::with_checkpoints`, the engine saves the state of a run
+//! (completed nodes with their outputs, skipped nodes, and the var store)
+//! after every level, keyed by run id so concurrent runs keep their own.
+//! Each checkpoint records the run it belongs to, a digest of the workflow,
+//! and the tenant the run is for. `Engine::resume` continues from a saved
+//! checkpoint under the same run id and tenant, running only the nodes it
+//! has not completed, and refuses a checkpoint saved for another workflow.
+//! Table handles do not survive a checkpoint, so nodes after the resume
+//! point cannot read handles created before it.
+
+use metabuilder_core::files;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// The saved state of a partially completed run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// The run saved, which keeps its id when resumed
+    pub run_id: String,
+    /// Digest of the workflow the run is for (see `Workflow::digest`)
+    pub workflow: String,
+    /// The tenant the run is for, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    /// Output ports of every completed node, keyed by node id
+    pub outputs: HashMap<String, HashMap<String, Value>>,
+    /// Contents of the var store
+    pub vars: HashMap<String, Value>,
+    /// Completed node ids in the order they ran
+    pub order: Vec<String>,
+    /// Node ids already skipped as untaken branches
+    pub skipped: Vec<String>,
+}
+
+impl Checkpoint {
+    /// Serializes the checkpoint to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("checkpoint values are always serializable")
+    }
+
+    /// Parses a checkpoint saved with `to_json`.
+    pub fn from_json(text: &str) -> Result<Checkpoint, String> {
+        serde_json::from_str(text).map_err(|e| format!("invalid checkpoint: {}", e))
+    }
+}
+
+/// Where the engine saves checkpoints, one per run.
+pub trait CheckpointStore: Send + Sync {
+    /// Replaces the checkpoint saved for `checkpoint.run_id`, if any.
+    fn save(&self, checkpoint: &Checkpoint) -> Result<(), String>;
+
+    /// The checkpoint saved for run `run_id`, or `None` if there is none.
+    fn load(&self, run_id: &str) -> Result<Option<Checkpoint>, String>;
+
+    /// Ids of the runs with a saved checkpoint, in order.
+    fn runs(&self) -> Result<Vec<String>, String>;
+}
+
+/// Lets the host keep a handle on a store it gives to the engine.
+impl<T: CheckpointStore + ?Sized> CheckpointStore for Arc<T> {
+    fn save(&self, checkpoint: &Checkpoint) -> Result<(), String> {
+        (**self).save(checkpoint)
+    }
+
+    fn load(&self, run_id: &str) -> Result<Option<Checkpoint>, String> {
+        (**self).load(run_id)
+    }
+
+    fn runs(&self) -> Result<Vec<String>, String> {
+        (**self).runs()
+    }
+}
+
+/// Keeps the latest checkpoint of each run in memory.
+#[derive(Debug, Default)]
+pub struct MemoryCheckpoints {
+    saved: Mutex<BTreeMap<String, Checkpoint>>,
+}
+
+impl MemoryCheckpoints {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpoints {
+    fn save(&self, checkpoint: &Checkpoint) -> Result<(), String> {
+        let mut saved = self.saved.lock().unwrap_or_else(|e| e.into_inner());
+        saved.insert(checkpoint.run_id.clone(), checkpoint.clone());
+        Ok(())
+    }
+
+    fn load(&self, run_id: &str) -> Result<Option<Checkpoint>, String> {
+        Ok(self.saved.lock().unwrap_or_else(|e| e.into_inner()).get(run_id).cloned())
+    }
+
+    fn runs(&self) -> Result<Vec<String>, String> {
+        Ok(self.saved.lock().unwrap_or_else(|e| e.into_inner()).keys().cloned().collect())
+    }
+}
+
+/// Writes each run's checkpoint to `<run id>.json` in a directory, replacing
+/// it atomically (see `metabuilder_core::files::write_atomic`) so a crash
+/// mid-write leaves the previous checkpoint intact.
+#[derive(Debug, Clone)]
+pub struct FileCheckpoints {
+    dir: PathBuf,
+}
+
+impl FileCheckpoints {
+    /// Saves checkpoints to files in `dir`, which must exist.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileCheckpoints { dir: dir.into() }
+    }
+
+    fn path(&self, run_id: &str) -> Result<PathBuf, String> {
+        // Run ids come from checkpoints, which may have been edited.
+        if run_id.is_empty() || run_id.starts_with('.') || run_id.contains(['/', '\\']) {
+            return Err(format!("invalid run id: {}", run_id));
+        }
+        Ok(self.dir.join(format!("{}.json", run_id)))
+    }
+}
+
+impl CheckpointStore for FileCheckpoints {
+    fn save(&self, checkpoint: &Checkpoint) -> Result<(), String> {
+        let path = self.path(&checkpoint.run_id)?;
+        files::write_atomic(&path, None, checkpoint.to_json().as_bytes())
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+
+    fn load(&self, run_id: &str) -> Result<Option<Checkpoint>, String> {
+        let path = self.path(run_id)?;
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Checkpoint::from_json(&text).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("cannot read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn runs(&self) -> Result<Vec<String>, String> {
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| format!("cannot read {}: {}", self.dir.display(), e))?;
+        let mut runs = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            // Skips the temporary files of writes in progress, which start with a dot.
+            let run_id = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".json"));
+            if let Some(run_id) = run_id.filter(|id| !id.starts_with('.')) {
+                runs.push(run_id.to_string());
+            }
+        }
+        runs.sort();
+        Ok(runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_file_checkpoints_round_trip() {
+        let dir = std::env::temp_dir().join(format!("checkpoints_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = FileCheckpoints::new(&dir);
+        assert_eq!(store.load("run-1").unwrap(), None);
+
+        let mut first = Checkpoint {
+            run_id: "run-1".to_string(),
+            workflow: "abc".to_string(),
+            ..Checkpoint::default()
+        };
+        first.vars.insert("n".to_string(), json!(1));
+        first.order.push("a".to_string());
+        let second = Checkpoint {
+            run_id: "run-2".to_string(),
+            tenant: Some("acme".to_string()),
+            ..first.clone()
+        };
+        store.save(&first).unwrap();
+        store.save(&second).unwrap();
+        assert_eq!(store.load("run-1").unwrap(), Some(first));
+        assert_eq!(store.load("run-2").unwrap(), Some(second));
+        assert_eq!(store.runs().unwrap(), ["run-1", "run-2"]);
+        assert_eq!(store.load("../run-1").unwrap_err(), "invalid run id: ../run-1");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
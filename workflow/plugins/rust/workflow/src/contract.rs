@@ -0,0 +1,254 @@
+//! Declared inputs and outputs of a workflow.
+//!
+//! A workflow that declares `inputs` only accepts those vars from its
+//! caller, checked against their kinds and filled in from defaults. Declared
+//! `outputs` are read from the finished run with reference expressions and
+//! returned in `RunResult::returned`, so a workflow behaves like a function
+//! when invoked with `workflow.call`:
+//!
+//! ```json
+//! "inputs": { "amount": { "type": "number" }, "currency": { "type": "string", "default": "EUR" } },
+//! "outputs": { "total": { "type": "number", "from": "$nodes.convert.result" } }
+//! ```
+
+use crate::error::EngineError;
+use crate::expr::{self, ResolveError};
+use crate::graph::Workflow;
+use crate::schema::PortKind;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A declared workflow input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InputSpec {
+    #[serde(rename = "type", default = "any")]
+    pub kind: PortKind,
+    /// Value used when the caller omits the input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// Whether the input may be omitted without a default
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl InputSpec {
+    /// A required input of `kind`.
+    pub fn new(kind: PortKind) -> Self {
+        InputSpec {
+            kind,
+            default: None,
+            optional: false,
+            description: None,
+        }
+    }
+
+    /// Sets the value used when the input is omitted.
+    pub fn default(mut self, value: Value) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Allows the input to be omitted.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+}
+
+/// A declared workflow output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputSpec {
+    #[serde(rename = "type", default = "any")]
+    pub kind: PortKind,
+    /// Reference expression or template resolved after the run, such as
+    /// `"$nodes.sum.result"`
+    pub from: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl OutputSpec {
+    /// An output of `kind` read from `from`.
+    pub fn new(kind: PortKind, from: impl Into<Value>) -> Self {
+        OutputSpec {
+            kind,
+            from: from.into(),
+            description: None,
+        }
+    }
+}
+
+fn any() -> PortKind {
+    PortKind::Any
+}
+
+/// Checks caller-supplied `vars` against the workflow's declared inputs and
+/// applies defaults, then adds the workflow's own `vars` the caller left out.
+/// Workflows that declare no inputs accept any vars.
+pub(crate) fn bind_inputs(workflow: &Workflow, vars: HashMap<String, Value>) -> Result<HashMap<String, Value>, EngineError> {
+    check_inputs(workflow, vars).map(|vars| seed_defaults(workflow, vars))
+}
+
+/// Adds the input defaults and spec vars that `vars` leaves out, without
+/// checking `vars` against the declared inputs.
+pub(crate) fn seed_defaults(workflow: &Workflow, mut vars: HashMap<String, Value>) -> HashMap<String, Value> {
+    for (name, spec) in &workflow.inputs {
+        if let Some(default) = &spec.default {
+            vars.entry(name.clone()).or_insert_with(|| default.clone());
+        }
+    }
+    for (name, value) in &workflow.vars {
+        vars.entry(name.clone()).or_insert_with(|| value.clone());
+    }
+    vars
+}
+
+fn check_inputs(workflow: &Workflow, mut vars: HashMap<String, Value>) -> Result<HashMap<String, Value>, EngineError> {
+    if workflow.inputs.is_empty() {
+        return Ok(vars);
+    }
+    let invalid = |input: &str, message: String| EngineError::InvalidInput {
+        input: input.to_string(),
+        message,
+    };
+    if let Some(name) = vars.keys().filter(|name| !workflow.inputs.contains_key(*name)).min() {
+        return Err(invalid(name, "not declared by the workflow".to_string()));
+    }
+    for (name, spec) in &workflow.inputs {
+        match vars.get(name) {
+            Some(value) => check_kind(spec.kind, value).map_err(|message| invalid(name, message))?,
+            None => match &spec.default {
+                Some(default) => {
+                    vars.insert(name.clone(), default.clone());
+                }
+                None if spec.optional => {}
+                None => return Err(invalid(name, "is required".to_string())),
+            },
+        }
+    }
+    Ok(vars)
+}
+
+/// Resolves the workflow's declared outputs against a finished run.
+pub(crate) fn collect_outputs(
+    workflow: &Workflow,
+    outputs: &HashMap<String, HashMap<String, Value>>,
+    store: &HashMap<String, Value>,
+) -> Result<HashMap<String, Value>, EngineError> {
+    let mut returned = HashMap::new();
+    for (name, spec) in &workflow.outputs {
+        let invalid = |message: String| EngineError::InvalidOutput {
+            output: name.clone(),
+            message,
+        };
+        let value = expr::resolve(&spec.from, outputs, store).map_err(|error| match error {
+            ResolveError::Invalid { expression, message } => {
+                invalid(format!("has invalid reference {}: {}", expression, message))
+            }
+            ResolveError::Missing(expression) => invalid(format!("{} refers to a missing value", expression)),
+        })?;
+        check_kind(spec.kind, &value).map_err(invalid)?;
+        returned.insert(name.clone(), value);
+    }
+    Ok(returned)
+}
+
+/// Problems with the contract itself, as `(entry, message)` pairs such as
+/// `("output total", "reads unknown node: convert")`: input defaults that do
+/// not fit their kind and outputs that read missing nodes.
+pub(crate) fn check(workflow: &Workflow) -> Vec<(String, String)> {
+    let mut problems = Vec::new();
+    for (name, spec) in &workflow.inputs {
+        if let Some(Err(message)) = spec.default.as_ref().map(|default| check_kind(spec.kind, default)) {
+            problems.push((format!("input {}", name), format!("default {}", message)));
+        }
+    }
+    for (name, spec) in &workflow.outputs {
+        let from = HashMap::from([(name.clone(), spec.from.clone())]);
+        let message = match expr::references(&from) {
+            Err((expression, message)) => Some(format!("has invalid reference {}: {}", expression, message)),
+            Ok(references) => references
+                .iter()
+                .filter_map(|(_, reference)| reference.node())
+                .find(|id| workflow.get_node(id).is_none())
+                .map(|id| format!("reads unknown node: {}", id)),
+        };
+        problems.extend(message.map(|message| (format!("output {}", name), message)));
+    }
+    problems
+}
+
+fn check_kind(kind: PortKind, value: &Value) -> Result<(), String> {
+    match PortKind::of(value) {
+        _ if kind == PortKind::Any => Ok(()),
+        Some(actual) if actual.fits(kind) => Ok(()),
+        Some(actual) => Err(format!("expected {}, got {}", kind, actual)),
+        None => Err(format!("expected {}, got null", kind)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use serde_json::json;
+
+    #[test]
+    fn test_bind_inputs() {
+        let workflow = Workflow::new()
+            .input("amount", InputSpec::new(PortKind::Number))
+            .input("currency", InputSpec::new(PortKind::String).default(json!("EUR")))
+            .input("note", InputSpec::new(PortKind::String).optional());
+
+        let vars = bind_inputs(&workflow, HashMap::from([("amount".to_string(), json!(5))])).unwrap();
+        assert_eq!(vars, HashMap::from([("amount".to_string(), json!(5)), ("currency".to_string(), json!("EUR"))]));
+
+        let error = bind_inputs(&workflow, HashMap::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid input amount: is required");
+        let error = bind_inputs(&workflow, HashMap::from([("amount".to_string(), json!("5"))])).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid input amount: expected number, got string");
+        let vars = HashMap::from([("amount".to_string(), json!(5)), ("amout".to_string(), json!(5))]);
+        assert_eq!(
+            bind_inputs(&workflow, vars).unwrap_err().to_string(),
+            "Invalid input amout: not declared by the workflow"
+        );
+
+        let workflow = Workflow::new().var("rate", json!(2)).var("unit", json!("kg"));
+        let vars = bind_inputs(&workflow, HashMap::from([("rate".to_string(), json!(3))])).unwrap();
+        assert_eq!(vars, HashMap::from([("rate".to_string(), json!(3)), ("unit".to_string(), json!("kg"))]));
+    }
+
+    #[test]
+    fn test_contract_round_trip() {
+        let text = r#"{
+            "inputs": { "n": { "type": "number", "default": 1 } },
+            "outputs": { "double": { "type": "number", "from": "$nodes.mul.result" } },
+            "nodes": [ { "id": "mul", "type": "math.multiply", "params": { "numbers": ["$n", 2] } } ]
+        }"#;
+        let workflow = Workflow::from_json(text).unwrap();
+        assert_eq!(workflow.inputs["n"], InputSpec::new(PortKind::Number).default(json!(1)));
+        assert_eq!(workflow.outputs["double"], OutputSpec::new(PortKind::Number, "$nodes.mul.result"));
+        assert_eq!(Workflow::from_json(&workflow.to_json()).unwrap(), workflow);
+    }
+
+    #[test]
+    fn test_check() {
+        let workflow = Workflow::new()
+            .input("n", InputSpec::new(PortKind::Number).default(json!("1")))
+            .output("sum", OutputSpec::new(PortKind::Number, "$nodes.add.result"))
+            .output("label", OutputSpec::new(PortKind::String, "{{nodes.mul.result}} items"))
+            .node(Node::new("mul", "math.multiply"));
+        assert_eq!(
+            check(&workflow),
+            vec![
+                ("input n".to_string(), "default expected number, got string".to_string()),
+                ("output sum".to_string(), "reads unknown node: add".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! Node execution spread across worker processes.
+//!
+//! An engine told to `Engine::distribute` a category acts as the coordinator
+//! for nodes of that category: rather than running them itself, it publishes
+//! each as a `Task` on a `Transport`, and a `Worker` in another process (or
+//! on another machine) with the same node types registered takes it, runs it,
+//! and publishes its outputs back. The rest of the graph still runs on the
+//! coordinator, so one workflow can fan its heavy nodes out over many workers.
+//!
+//! Delivery is at least once. A task whose outputs do not arrive within its
+//! lease is published again under the same id, so a worker that crashes
+//! mid-task only delays the node. The id doubles as an idempotency key:
+//! workers claim a task before running it and skip one that is done or held
+//! by a live claim, and nodes see it as the `idempotency_key` context entry
+//! so they can pass it on to the services they call.
+//!
+//! `MemoryTransport` connects threads of one process, mainly for tests;
+//! `RedisTransport` (with the `redis` feature) connects processes through a
+//! Redis server. Inputs and outputs travel as JSON, so values held by handle
+//! (such as tables) cannot cross to a worker.
+
+use crate::registry::Registry;
+use metabuilder_core::correlation::{self, Correlation};
+use metabuilder_core::{Clients, Patterns, Runtime, SecretsProvider};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Runtime context key under which workers pass a task's id to its node.
+pub const IDEMPOTENCY_KEY: &str = "idempotency_key";
+
+/// How long a task may go unanswered before it is published again, unless
+/// set with `Engine::with_task_lease`.
+pub const DEFAULT_LEASE: Duration = Duration::from_secs(30);
+
+/// Times a task is published before its node fails.
+pub const MAX_ATTEMPTS: u32 = 3;
+
+/// One node execution handed to a worker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    /// Unique per node execution, the same across re-deliveries
+    pub id: String,
+    pub node_type: String,
+    pub inputs: HashMap<String, Value>,
+    /// How long a worker's claim on the task lasts, in milliseconds
+    pub lease_ms: u64,
+}
+
+/// Moves tasks from the coordinator to workers and outputs back.
+pub trait Transport: Send + Sync {
+    /// Queues `task` for some worker.
+    fn publish(&self, task: &Task) -> Result<(), String>;
+
+    /// Takes the next queued task, waiting up to `timeout` for one.
+    fn take(&self, timeout: Duration) -> Result<Option<Task>, String>;
+
+    /// Claims task `id` for `lease`, unless another claim on it is live.
+    fn claim(&self, id: &str, lease: Duration) -> Result<bool, String>;
+
+    /// Publishes the outputs of task `id`.
+    fn complete(&self, id: &str, outputs: &HashMap<String, Value>) -> Result<(), String>;
+
+    /// The outputs of task `id`, waiting up to `timeout` for them.
+    fn result(&self, id: &str, timeout: Duration) -> Result<Option<HashMap<String, Value>>, String>;
+}
+
+/// A transport between threads of one process.
+#[derive(Default)]
+pub struct MemoryTransport {
+    state: Mutex<MemoryState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    tasks: VecDeque<Task>,
+    claims: HashMap<String, Instant>,
+    results: HashMap<String, HashMap<String, Value>>,
+}
+
+impl MemoryTransport {
+    /// Creates a transport with no tasks queued.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tasks queued and not yet taken.
+    pub fn pending(&self) -> usize {
+        self.lock().tasks.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Waits up to `timeout` for `ready` to return something.
+    fn wait<T>(&self, timeout: Duration, mut ready: impl FnMut(&mut MemoryState) -> Option<T>) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        loop {
+            if let Some(found) = ready(&mut state) {
+                return Some(found);
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return None;
+            }
+            state = self.changed.wait_timeout(state, left).unwrap_or_else(|e| e.into_inner()).0;
+        }
+    }
+}
+
+impl Transport for MemoryTransport {
+    fn publish(&self, task: &Task) -> Result<(), String> {
+        self.lock().tasks.push_back(task.clone());
+        self.changed.notify_all();
+        Ok(())
+    }
+
+    fn take(&self, timeout: Duration) -> Result<Option<Task>, String> {
+        Ok(self.wait(timeout, |state| state.tasks.pop_front()))
+    }
+
+    fn claim(&self, id: &str, lease: Duration) -> Result<bool, String> {
+        let mut state = self.lock();
+        let now = Instant::now();
+        if state.claims.get(id).is_some_and(|&until| until > now) {
+            return Ok(false);
+        }
+        state.claims.insert(id.to_string(), now + lease);
+        Ok(true)
+    }
+
+    fn complete(&self, id: &str, outputs: &HashMap<String, Value>) -> Result<(), String> {
+        self.lock().results.insert(id.to_string(), outputs.clone());
+        self.changed.notify_all();
+        Ok(())
+    }
+
+    fn result(&self, id: &str, timeout: Duration) -> Result<Option<HashMap<String, Value>>, String> {
+        Ok(self.wait(timeout, |state| state.results.get(id).cloned()))
+    }
+}
+
+/// A transport through a Redis server (6.0 or later).
+///
+/// Tasks queue on the list `{prefix}:tasks`; claims and outputs are kept
+/// under `{prefix}:claim:{id}` and `{prefix}:result:{id}`, outputs for a day.
+/// Completing a task also pushes its outputs onto `{prefix}:done:{id}`, which
+/// the coordinator waits on with BLPOP rather than polling. Connections are
+/// pooled: each call borrows an idle one, or opens one if none is idle, and
+/// returns it when the command succeeds.
+#[cfg(feature = "redis")]
+pub struct RedisTransport {
+    client: redis::Client,
+    prefix: String,
+    idle: Mutex<Vec<redis::Connection>>,
+}
+
+#[cfg(feature = "redis")]
+impl RedisTransport {
+    /// Connects to the server at `url` (such as `redis://127.0.0.1/`),
+    /// keeping keys under `prefix`.
+    pub fn open(url: &str, prefix: impl Into<String>) -> Result<Self, String> {
+        let client = redis::Client::open(url).map_err(|e| format!("invalid redis url {}: {}", url, e))?;
+        Ok(RedisTransport {
+            client,
+            prefix: prefix.into(),
+            idle: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Runs `command` on a pooled connection. A connection whose command
+    /// failed may be broken, so it is dropped rather than reused.
+    fn with_connection<T>(
+        &self,
+        command: impl FnOnce(&mut redis::Connection) -> redis::RedisResult<T>,
+    ) -> Result<T, String> {
+        let idle = self.idle.lock().unwrap_or_else(|e| e.into_inner()).pop();
+        let mut connection = match idle {
+            Some(connection) => connection,
+            None => self.client.get_connection().map_err(|e| format!("cannot connect to redis: {}", e))?,
+        };
+        let result = command(&mut connection).map_err(|e| e.to_string())?;
+        self.idle.lock().unwrap_or_else(|e| e.into_inner()).push(connection);
+        Ok(result)
+    }
+
+    fn key(&self, kind: &str, id: &str) -> String {
+        format!("{}:{}:{}", self.prefix, kind, id)
+    }
+}
+
+#[cfg(feature = "redis")]
+impl Transport for RedisTransport {
+    fn publish(&self, task: &Task) -> Result<(), String> {
+        let payload = serde_json::to_string(task).map_err(|e| e.to_string())?;
+        self.with_connection(|connection| {
+            redis::cmd("LPUSH").arg(format!("{}:tasks", self.prefix)).arg(payload).query::<()>(connection)
+        })
+        .map_err(|e| format!("cannot publish task {}: {}", task.id, e))
+    }
+
+    fn take(&self, timeout: Duration) -> Result<Option<Task>, String> {
+        // BRPOP blocks forever for 0.
+        let popped: Option<(String, String)> = self
+            .with_connection(|connection| {
+                redis::cmd("BRPOP")
+                    .arg(format!("{}:tasks", self.prefix))
+                    .arg(timeout.as_secs_f64().max(0.001))
+                    .query(connection)
+            })
+            .map_err(|e| format!("cannot take task: {}", e))?;
+        popped
+            .map(|(_, payload)| serde_json::from_str(&payload).map_err(|e| format!("invalid task: {}", e)))
+            .transpose()
+    }
+
+    fn claim(&self, id: &str, lease: Duration) -> Result<bool, String> {
+        let set: Option<String> = self
+            .with_connection(|connection| {
+                redis::cmd("SET")
+                    .arg(self.key("claim", id))
+                    .arg(1)
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(lease.as_millis().max(1) as u64)
+                    .query(connection)
+            })
+            .map_err(|e| format!("cannot claim task {}: {}", id, e))?;
+        Ok(set.is_some())
+    }
+
+    fn complete(&self, id: &str, outputs: &HashMap<String, Value>) -> Result<(), String> {
+        const DAY: u64 = 24 * 60 * 60;
+        let payload = serde_json::to_string(outputs).map_err(|e| e.to_string())?;
+        let done = self.key("done", id);
+        self.with_connection(|connection| {
+            redis::pipe()
+                .atomic()
+                .cmd("SET")
+                .arg(self.key("result", id))
+                .arg(&payload)
+                .arg("EX")
+                .arg(DAY)
+                .ignore()
+                .cmd("RPUSH")
+                .arg(&done)
+                .arg(&payload)
+                .ignore()
+                .cmd("EXPIRE")
+                .arg(&done)
+                .arg(DAY)
+                .ignore()
+                .query::<()>(connection)
+        })
+        .map_err(|e| format!("cannot complete task {}: {}", id, e))
+    }
+
+    fn result(&self, id: &str, timeout: Duration) -> Result<Option<HashMap<String, Value>>, String> {
+        let read = |payload: String| {
+            serde_json::from_str(&payload).map(Some).map_err(|e| format!("invalid result of task {}: {}", id, e))
+        };
+        let stored: Option<String> = self
+            .with_connection(|connection| redis::cmd("GET").arg(self.key("result", id)).query(connection))
+            .map_err(|e| format!("cannot read result of task {}: {}", id, e))?;
+        if let Some(payload) = stored {
+            return read(payload);
+        }
+        // BLPOP blocks forever for 0, and takes at least a millisecond.
+        if timeout < Duration::from_millis(1) {
+            return Ok(None);
+        }
+        let popped: Option<(String, String)> = self
+            .with_connection(|connection| {
+                redis::cmd("BLPOP").arg(self.key("done", id)).arg(timeout.as_secs_f64()).query(connection)
+            })
+            .map_err(|e| format!("cannot wait for result of task {}: {}", id, e))?;
+        popped.map_or(Ok(None), |(_, payload)| read(payload))
+    }
+}
+
+/// Runs the tasks published on a transport with the executors of a registry.
+pub struct Worker {
+    registry: Registry,
+    transport: Arc<dyn Transport>,
+    secrets: Option<Arc<dyn SecretsProvider>>,
+    patterns: Arc<Patterns>,
+    clients: Arc<Clients>,
+}
+
+impl Worker {
+    /// A worker running tasks from `transport` with no secrets.
+    pub fn new(registry: Registry, transport: Arc<dyn Transport>) -> Self {
+        Worker {
+            registry,
+            transport,
+            secrets: None,
+            patterns: Arc::new(Patterns::default()),
+            clients: Arc::new(Clients::new()),
+        }
+    }
+
+    /// Sets the secrets provider exposed to the nodes the worker runs.
+    pub fn with_secrets(mut self, secrets: impl SecretsProvider + 'static) -> Self {
+        self.secrets = Some(Arc::new(secrets));
+        self
+    }
+
+    /// Waits up to `timeout` for a task and runs it. Returns whether a task
+    /// was run, rather than skipped as done or claimed elsewhere.
+    pub fn work(&self, timeout: Duration) -> Result<bool, String> {
+        let Some(task) = self.transport.take(timeout)? else {
+            return Ok(false);
+        };
+        if self.transport.result(&task.id, Duration::ZERO)?.is_some()
+            || !self.transport.claim(&task.id, Duration::from_millis(task.lease_ms))?
+        {
+            return Ok(false);
+        }
+        let outputs = match self.registry.get(&task.node_type) {
+            Some(executor) => {
+                let mut runtime = match &self.secrets {
+                    Some(secrets) => Runtime::with_shared_secrets(secrets.clone()),
+                    None => Runtime::new(),
+                };
+                runtime.patterns = self.patterns.clone();
+                runtime.clients = self.clients.clone();
+                runtime.context.insert(IDEMPOTENCY_KEY.to_string(), Value::from(task.id.as_str()));
+                // Task ids are the coordinator's execution ids.
+                let correlation = Correlation::from_execution_id(&task.id);
+                correlation::scope(correlation, || executor.execute(task.inputs, Some(&runtime)))
+            }
+            None => HashMap::from([(
+                "error".to_string(),
+                Value::from(format!("worker has no executor for {}", task.node_type)),
+            )]),
+        };
+        self.transport.complete(&task.id, &outputs)?;
+        Ok(true)
+    }
+
+    /// Runs tasks until `stop` is set, checking it at least once a second.
+    /// Transport errors are retried after a pause.
+    ///
+    /// Setting `stop` drains the worker: the task it is running completes
+    /// before `run` returns, and queued tasks stay for other workers.
+    pub fn run(&self, stop: &AtomicBool) {
+        while !stop.load(Ordering::Relaxed) {
+            if self.work(Duration::from_secs(1)).is_err() {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn task(id: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            node_type: "math.add".to_string(),
+            inputs: HashMap::from([("numbers".to_string(), json!([1, 2]))]),
+            lease_ms: 60_000,
+        }
+    }
+
+    #[test]
+    fn test_worker_runs_task_once() {
+        let transport = Arc::new(MemoryTransport::new());
+        let worker = Worker::new(Registry::with_builtins(), transport.clone());
+        transport.publish(&task("run:sum:0")).unwrap();
+        transport.publish(&task("run:sum:0")).unwrap();
+        assert!(worker.work(Duration::ZERO).unwrap());
+        assert_eq!(transport.result("run:sum:0", Duration::ZERO).unwrap().unwrap()["result"], json!(3));
+        // The re-delivery finds the task done.
+        assert!(!worker.work(Duration::ZERO).unwrap());
+        assert!(!worker.work(Duration::ZERO).unwrap());
+    }
+
+    #[test]
+    fn test_claims() {
+        let transport = MemoryTransport::new();
+        assert!(transport.claim("a", Duration::from_secs(60)).unwrap());
+        assert!(!transport.claim("a", Duration::from_secs(60)).unwrap());
+        assert!(transport.claim("b", Duration::ZERO).unwrap());
+        assert!(transport.claim("b", Duration::ZERO).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_node_type() {
+        let transport = Arc::new(MemoryTransport::new());
+        let worker = Worker::new(Registry::new(), transport.clone());
+        transport.publish(&task("run:sum:0")).unwrap();
+        worker.work(Duration::ZERO).unwrap();
+        let outputs = transport.result("run:sum:0", Duration::ZERO).unwrap().unwrap();
+        assert_eq!(outputs["error"], json!("worker has no executor for math.add"));
+    }
+}
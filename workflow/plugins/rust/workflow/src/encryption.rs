@@ -0,0 +1,164 @@
+//! Encrypted values in workflow specs.
+//!
+//! Any string in a spec may hold an encrypted value in a sops-style envelope,
+//! so specs that carry credentials can be committed:
+//!
+//! ```json
+//! {
+//!   "encryption": { "key_secret": "spec_key" },
+//!   "nodes": [ { "id": "login", "type": "http.request",
+//!     "params": { "password": "ENC[AES256_GCM,data:...,iv:...,tag:...]" } } ]
+//! }
+//! ```
+//!
+//! The value is the JSON text of the plaintext sealed with AES-256-GCM.
+//! `Workflow::from_json_with_secrets` reads the base64 key from the
+//! secret named by `encryption.key_secret` and decrypts every envelope once
+//! the spec is parsed; `encrypt` produces envelopes with the same key. The
+//! loaded `Workflow` holds plaintext, so `Workflow::to_json` does too.
+
+use crate::error::LoadError;
+use crate::graph::Workflow;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use metabuilder_core::SecretsProvider;
+use serde_json::Value;
+
+const PREFIX: &str = "ENC[AES256_GCM,";
+const TAG_LEN: usize = 16;
+
+/// Seals `value` into an envelope with the base64-encoded 256-bit `key`.
+pub fn encrypt(value: &Value, key: &str) -> Result<String, String> {
+    let cipher = cipher(key)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let sealed = cipher
+        .encrypt(&nonce, value.to_string().as_bytes())
+        .map_err(|_| "encryption failed".to_string())?;
+    let (data, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+    Ok(format!(
+        "{}data:{},iv:{},tag:{}]",
+        PREFIX,
+        STANDARD.encode(data),
+        STANDARD.encode(nonce),
+        STANDARD.encode(tag)
+    ))
+}
+
+/// Opens an envelope produced by `encrypt`.
+pub fn decrypt(envelope: &str, key: &str) -> Result<Value, String> {
+    let fields = envelope
+        .strip_prefix(PREFIX)
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(|| "not an encrypted value".to_string())?;
+    let field = |name: &str| {
+        fields
+            .split(',')
+            .find_map(|field| field.strip_prefix(name)?.strip_prefix(':'))
+            .ok_or_else(|| format!("missing {}", name))
+            .and_then(|text| STANDARD.decode(text).map_err(|e| format!("invalid {}: {}", name, e)))
+    };
+    let (mut sealed, iv, tag) = (field("data")?, field("iv")?, field("tag")?);
+    if iv.len() != 12 || tag.len() != TAG_LEN {
+        return Err("invalid iv or tag length".to_string());
+    }
+    sealed.extend_from_slice(&tag);
+    let plaintext = cipher(key)?
+        .decrypt(Nonce::from_slice(&iv), sealed.as_slice())
+        .map_err(|_| "wrong key or tampered value".to_string())?;
+    serde_json::from_slice(&plaintext).map_err(|e| format!("invalid plaintext: {}", e))
+}
+
+/// Whether `value` holds an encrypted envelope anywhere.
+fn contains_encrypted(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s.starts_with(PREFIX),
+        Value::Array(items) => items.iter().any(contains_encrypted),
+        Value::Object(map) => map.values().any(contains_encrypted),
+        _ => false,
+    }
+}
+
+/// Decrypts every envelope in `workflow`, parsed from `text`, with the key
+/// named by the document's `encryption` header.
+pub(crate) fn decrypt_workflow(workflow: Workflow, text: &str, secrets: &dyn SecretsProvider) -> Result<Workflow, LoadError> {
+    let mut document = serde_json::to_value(&workflow).expect("workflow values are always serializable");
+    if !contains_encrypted(&document) {
+        return Ok(workflow);
+    }
+    let header: Value = serde_json::from_str(text).unwrap_or(Value::Null);
+    let name = header
+        .pointer("/encryption/key_secret")
+        .and_then(Value::as_str)
+        .ok_or_else(|| LoadError::Decrypt("encryption.key_secret is required".to_string()))?;
+    let key = secrets
+        .get(name)
+        .ok_or_else(|| LoadError::Decrypt(format!("secret {} is not set", name)))?;
+    decrypt_in_place(&mut document, &key, &mut String::new())?;
+    serde_json::from_value(document).map_err(|e| LoadError::Decrypt(format!("decrypted value does not fit the spec: {}", e)))
+}
+
+/// Replaces envelopes under `value`, tracking the JSON `path` for errors.
+fn decrypt_in_place(value: &mut Value, key: &str, path: &mut String) -> Result<(), LoadError> {
+    let len = path.len();
+    match value {
+        Value::String(s) if s.starts_with(PREFIX) => {
+            *value = decrypt(s, key).map_err(|e| LoadError::Decrypt(format!("{}: {}", path, e)))?;
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                path.push_str(&format!("[{}]", i));
+                decrypt_in_place(item, key, path)?;
+                path.truncate(len);
+            }
+        }
+        Value::Object(map) => {
+            for (name, item) in map.iter_mut() {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(name);
+                decrypt_in_place(item, key, path)?;
+                path.truncate(len);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn cipher(key: &str) -> Result<Aes256Gcm, String> {
+    let key = STANDARD.decode(key.trim()).map_err(|e| format!("invalid key: {}", e))?;
+    if key.len() != 32 {
+        return Err(format!("key must be 32 bytes, got {}", key.len()));
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const KEY: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    #[test]
+    fn test_round_trip() {
+        for value in [json!("hunter2"), json!(42), json!({"user": "ada", "roles": ["admin"]})] {
+            let envelope = encrypt(&value, KEY).unwrap();
+            assert!(envelope.starts_with("ENC[AES256_GCM,data:"));
+            assert_eq!(decrypt(&envelope, KEY).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_wrong_key_and_tampering() {
+        let envelope = encrypt(&json!("secret"), KEY).unwrap();
+        let other = STANDARD.encode([7u8; 32]);
+        assert_eq!(decrypt(&envelope, &other).unwrap_err(), "wrong key or tampered value");
+        let tampered = envelope.replacen("data:", "data:AA", 1);
+        assert!(decrypt(&tampered, KEY).is_err());
+        assert_eq!(encrypt(&json!(1), "c2hvcnQ=").unwrap_err(), "key must be 32 bytes, got 5");
+    }
+}
@@ -0,0 +1,2534 @@
+//! Workflow executor.
+//!
+//! Nodes run one at a time in dependency order by default. With
+//! `Engine::with_max_parallelism`, the graph is split into topological levels
+//! and the nodes of each level run concurrently on a thread pool.
+//!
+//! A node that reports a `branch` output (such as `control.if` or
+//! `control.switch`) only emits the ports of the branch it took, if any.
+//! Edges from its other ports are not taken, and nodes wired to them are
+//! skipped, as are nodes whose upstream nodes were all skipped.
+//!
+//! A failing node normally stops the run. If the node has edges from its
+//! `on_error` port, it instead takes the `on_error` branch: the port carries
+//! `{"node", "type", "message", "outputs", "execution_id"}` to the handler
+//! nodes, and nodes wired to its other ports are skipped.
+//!
+//! Every run has an id unique across processes (`RunMetadata::run_id`), and
+//! every execution of a node an id starting with it, recorded in history and
+//! handed to workers as the task id. While a node runs, both are available
+//! from `metabuilder_core::correlation`, and `http.request` sends them as
+//! headers, so a run can be followed across services.
+//!
+//! `control.while` is run by the engine itself: it repeats the node's `body`
+//! sub-graph until the body's `condition` node reports a falsy `result`.
+//! `workflow.call` is too: it runs another workflow, registered on the engine
+//! by name or loaded from a path, as a nested run. Workflows that declare
+//! inputs and outputs (see `contract`) have them checked on every run.
+//!
+//! `Engine::with_timeout` bounds a whole run, and
+//! `Engine::with_concurrency_limit` caps how many nodes of a category run at
+//! once; nodes over the cap queue or fail according to the `QueuePolicy`.
+//!
+//! A streaming edge (`"stream": true`) carries a stream handle (see
+//! `metabuilder_core::stream`) or a list one chunk at a time. The node it
+//! feeds, and the nodes fed by that node's streaming edges in turn, run once
+//! per chunk as a nested run, so a pipeline over the lines of a large file
+//! never holds them all. Var changes made while streaming carry over from
+//! chunk to chunk and to the rest of the run. Afterwards each streamed node
+//! has the outputs of the last chunk it ran on, plus `chunks`, the number of
+//! chunks it ran on.
+//!
+//! `event.emit` nodes publish on an event bus that a top-level run shares with
+//! the runs nested in it, where `event.on` nodes read them and hosts can
+//! listen in with `Engine::with_listener`.
+//!
+//! `Engine::run_as` runs a workflow for a tenant registered with
+//! `Engine::with_tenant`, isolating its secrets, cache, clients, history,
+//! checkpoints, and concurrency limits from other tenants (see `tenant`).
+//!
+//! `Engine::with_audit` keeps a hash-chained record of every execution of a
+//! node with side effects (see `audit`), and `Engine::with_run_store` keeps
+//! the status and outputs of every run, by run id, alongside its audit
+//! entries (see `runs`).
+//!
+//! Values at paths registered with `Engine::redact` are masked in history
+//! records, listener events, cassette recordings, and run store records (see
+//! `redact`).
+//!
+//! `Engine::with_run_queue` admits top-level runs by the priority and
+//! concurrency class their workflows declare (see `queue`).
+//!
+//! `Engine::distribute` hands the nodes of a category to worker processes
+//! over a transport such as Redis, at least once and under an idempotency
+//! key (see `distributed`).
+//!
+//! `Engine::compile` turns a workflow into a `Plan` that `Engine::run_plan`
+//! runs without validating or ordering the graph again (see `plan`).
+//!
+//! `Engine::with_locale` selects the language `Engine::describe` and
+//! `Locale::error` render node descriptions and errors in (see `i18n`).
+//!
+//! `Engine::shutdown` drains the engine for a deploy: it refuses new runs,
+//! lets those in flight finish within a grace period, and cancels the rest
+//! once their running nodes return, after their last checkpoint (see
+//! `shutdown`). `Engine::cancel` cancels a single run.
+//!
+//! `Engine::with_profile` selects the environment profile (see `profile`)
+//! applied to every workflow the engine runs, including called ones.
+//!
+//! Nodes draw random numbers from a generator the top-level run shares with
+//! the runs nested in it (`metabuilder_core::random`). `Engine::with_seed`
+//! seeds it, so every run of the engine draws the same sequence and can be
+//! replayed; with parallelism, nodes of one level may draw in any order.
+//!
+//! In a dry run (`Engine::dry_run`), nodes whose type the registry marks as
+//! having side effects are not executed; they output a stub instead. Nodes
+//! mocked by id or type with `Engine::mock` always run the mock, which takes
+//! precedence over registered executors, stubs, and built-in handling. With
+//! `Engine::with_cassette`, nodes with side effects are recorded or replayed
+//! (see `cassette`) instead of being stubbed.
+
+mod call;
+mod checkpoints;
+mod delays;
+mod loops;
+
+use self::checkpoints::vars;
+use self::delays::{park, Parked};
+use crate::audit::AuditLog;
+use crate::cassette::{Cassette, CassetteMode};
+use crate::checkpoint::{Checkpoint, CheckpointStore};
+use crate::contract;
+use crate::distributed::{Task, Transport, DEFAULT_LEASE, MAX_ATTEMPTS};
+use crate::error::EngineError;
+use crate::expr::{self, ResolveError};
+use crate::graph::{Edge, Node, Workflow};
+use crate::history::{NodeRecord, RunHistory, Status};
+use crate::i18n::Locale;
+use crate::limits::{self, Limiter, Permit, QueuePolicy, Refused};
+use crate::plan::{Plan, PLAN_VERSION};
+use crate::profile;
+use crate::queue::{Admission, RunQueue, DEFAULT_CLASS};
+use crate::redact::Redaction;
+use crate::registry::Registry;
+use crate::runs::RunStore;
+use crate::shutdown::{self, ActiveRun, Runs, ShutdownReport};
+use crate::tenant::Tenant;
+use crate::validate::Diagnostic;
+use metabuilder_core::correlation::{self, Correlation};
+use metabuilder_core::{canonical, Cache, Cancellation, Clients, Event, EventBus, Listener, MemorySecrets, Patterns, Random, Runtime, SecretsProvider, Stream};
+use serde_json::Value;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Node type of the loop, which the engine runs instead of a registered executor.
+pub(crate) const WHILE: &str = "control.while";
+/// Node type of subworkflow calls, which the engine also runs itself.
+pub(crate) const CALL: &str = "workflow.call";
+/// Port that carries a failed node's error to its handlers.
+pub(crate) const ON_ERROR: &str = "on_error";
+/// How often a node run by a worker checks for its outputs, cancellation,
+/// and the deadline.
+const DISPATCH_POLL: Duration = Duration::from_millis(100);
+
+/// Outputs of a completed run.
+#[derive(Debug, Clone, Default)]
+pub struct RunResult {
+    /// Output ports of every executed node, keyed by node id
+    pub outputs: HashMap<String, HashMap<String, Value>>,
+    /// Final contents of the var store
+    pub vars: HashMap<String, Value>,
+    /// Node ids in the order they ran
+    pub order: Vec<String>,
+    /// Node ids on untaken branches, which did not run
+    pub skipped: Vec<String>,
+    /// Node ids replaced by stubs in a dry run
+    pub stubbed: Vec<String>,
+    /// The workflow's declared outputs
+    pub returned: HashMap<String, Value>,
+    /// Timing and queueing of the run
+    pub metadata: RunMetadata,
+}
+
+/// How a run went, beyond its outputs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunMetadata {
+    /// Unique across processes and shared with the runs nested in this one
+    pub run_id: String,
+    /// Wall-clock time of the run
+    pub elapsed: Duration,
+    /// Nodes that queued for a concurrency slot, with how long they waited
+    pub queued: Vec<(String, Duration)>,
+    /// Most nodes of each limited category running at once, across the engine
+    /// (or the tenant, for categories only the tenant limits)
+    pub peak_in_flight: HashMap<String, usize>,
+}
+
+/// What running one node produced.
+struct Execution {
+    outputs: Result<HashMap<String, Value>, EngineError>,
+    /// The run id, the node id, and a count, unique within the run
+    execution_id: String,
+    /// Hash of the inputs, when recording history
+    inputs_hash: Option<String>,
+    duration: Duration,
+    /// Time spent queued for a concurrency slot, if the node had to wait
+    waited: Option<Duration>,
+    /// Nodes of the category running once this one started, if limited
+    in_flight: Option<usize>,
+}
+
+/// A test double for a node or node type, from its inputs to its outputs.
+type Mock = Arc<dyn Fn(&HashMap<String, Value>) -> Value + Send + Sync>;
+
+/// Runs workflows against a registry of node executors.
+pub struct Engine {
+    registry: Registry,
+    secrets: Arc<dyn SecretsProvider>,
+    cache: Arc<Cache>,
+    patterns: Arc<Patterns>,
+    clients: Arc<Clients>,
+    locale: Arc<Locale>,
+    draining: Arc<Cancellation>,
+    pool: Option<Arc<rayon::ThreadPool>>,
+    workflows: HashMap<String, Arc<Workflow>>,
+    checkpoints: Option<Arc<dyn CheckpointStore>>,
+    history: Option<Arc<RunHistory>>,
+    audit: Option<Arc<AuditLog>>,
+    run_store: Option<Arc<RunStore>>,
+    dry_run: bool,
+    stubs: HashMap<String, HashMap<String, Value>>,
+    mocks: HashMap<String, Mock>,
+    cassette: Option<Arc<Cassette>>,
+    redaction: Arc<Redaction>,
+    timeout: Option<Duration>,
+    limits: HashMap<String, Limiter>,
+    queue_policy: QueuePolicy,
+    profile: Option<String>,
+    listeners: Vec<(String, Listener)>,
+    tenants: HashMap<String, Arc<Tenant>>,
+    queue: Option<Arc<RunQueue>>,
+    remotes: HashMap<String, Arc<dyn Transport>>,
+    task_lease: Duration,
+    runs: Arc<Runs>,
+    seed: Option<u64>,
+}
+
+/// What a top-level run shares with the runs nested in it.
+struct Scope {
+    /// When the run must finish
+    deadline: Option<Instant>,
+    events: Arc<EventBus>,
+    random: Arc<Random>,
+    /// The tenant the run is for, if any
+    tenant: Option<Arc<Tenant>>,
+    /// Unique across processes, the prefix of the run's execution ids
+    run_id: String,
+    /// Set by `Engine::cancel` or a shutdown to stop the run
+    cancel: Arc<Cancellation>,
+    /// Node executions so far, counted for their ids
+    executions: AtomicU64,
+    /// Keeps the run in flight for `Engine::shutdown`
+    _active: ActiveRun,
+}
+
+/// Permits for a node's concurrency slots, how long it queued for them (if
+/// it had to), and how many nodes of its category were running once the last
+/// one was granted.
+type Slots<'a> = (Vec<Permit<'a>>, Option<Duration>, Option<usize>);
+
+impl Engine {
+    /// Creates an engine using `registry` and no secrets.
+    pub fn new(registry: Registry) -> Self {
+        Engine {
+            registry,
+            secrets: Arc::new(MemorySecrets::new()),
+            cache: Arc::new(Cache::new()),
+            patterns: Arc::new(Patterns::default()),
+            clients: Arc::new(Clients::new()),
+            locale: Arc::new(Locale::english()),
+            draining: Arc::new(Cancellation::new()),
+            pool: None,
+            workflows: HashMap::new(),
+            checkpoints: None,
+            history: None,
+            audit: None,
+            run_store: None,
+            dry_run: false,
+            stubs: HashMap::new(),
+            mocks: HashMap::new(),
+            cassette: None,
+            redaction: Arc::new(Redaction::new()),
+            timeout: None,
+            limits: HashMap::new(),
+            queue_policy: QueuePolicy::Wait,
+            profile: None,
+            listeners: Vec::new(),
+            tenants: HashMap::new(),
+            queue: None,
+            remotes: HashMap::new(),
+            task_lease: DEFAULT_LEASE,
+            runs: Arc::new(Runs::default()),
+            seed: None,
+        }
+    }
+
+    /// Runs up to `max` independent nodes at once. `1` (the default) runs
+    /// nodes sequentially.
+    ///
+    /// Nodes in the same level see the var store as it was when the level
+    /// started; `var.*` effects are applied afterwards in declaration order.
+    pub fn with_max_parallelism(mut self, max: usize) -> Self {
+        // If the pool cannot be started, keep running sequentially.
+        self.pool = match max {
+            0 | 1 => None,
+            n => rayon::ThreadPoolBuilder::new().num_threads(n).build().ok().map(Arc::new),
+        };
+        self
+    }
+
+    /// Maximum number of nodes run at once.
+    pub fn max_parallelism(&self) -> usize {
+        self.pool.as_ref().map_or(1, |pool| pool.current_num_threads())
+    }
+
+    /// Fails runs that take longer than `limit` with `EngineError::Timeout`.
+    /// The deadline covers nested runs and is checked before each level
+    /// starts and while nodes queue for a concurrency slot; a node that is
+    /// already running is not interrupted, though waits such as
+    /// `control.delay` end at the deadline with an error.
+    pub fn with_timeout(mut self, limit: Duration) -> Self {
+        self.timeout = Some(limit);
+        self
+    }
+
+    /// Runs at most `max` nodes of `category` (such as `"http"` for every
+    /// `http.*` type) at once, across all runs of this engine.
+    pub fn with_concurrency_limit(mut self, category: impl Into<String>, max: usize) -> Self {
+        self.limits.insert(category.into(), Limiter::new(max));
+        self
+    }
+
+    /// Sets what nodes do when their category is at its limit: wait for a
+    /// slot (the default) or fail.
+    pub fn with_queue_policy(mut self, policy: QueuePolicy) -> Self {
+        self.queue_policy = policy;
+        self
+    }
+
+    /// Runs workflows under profile `name` (see `profile`). Workflows that
+    /// define profiles must define this one; others run unchanged.
+    pub fn with_profile(mut self, name: impl Into<String>) -> Self {
+        self.profile = Some(name.into());
+        self
+    }
+
+    /// Calls `listener` for every event published on `topic` (or on every
+    /// topic for `"*"`) during runs of this engine, on the publishing thread.
+    pub fn with_listener(mut self, topic: impl Into<String>, listener: impl Fn(&Event) + Send + Sync + 'static) -> Self {
+        self.listeners.push((topic.into(), Arc::new(listener)));
+        self
+    }
+
+    /// Sets the secrets provider exposed to nodes through the runtime.
+    pub fn with_secrets(mut self, secrets: impl SecretsProvider + 'static) -> Self {
+        self.secrets = Arc::new(secrets);
+        self
+    }
+
+    /// Holds each top-level run until `queue` admits it, by the workflow's
+    /// priority and concurrency class (see `queue`).
+    pub fn with_run_queue(mut self, queue: Arc<RunQueue>) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Runs nodes of `category` on the workers taking tasks from `transport`
+    /// instead of in this process (see `distributed`). Mocks, dry-run stubs,
+    /// and cassette replays still take precedence.
+    pub fn distribute(mut self, category: impl Into<String>, transport: Arc<dyn Transport>) -> Self {
+        self.remotes.insert(category.into(), transport);
+        self
+    }
+
+    /// Publishes a distributed node's task again if its outputs have not
+    /// arrived within `lease` (30 seconds by default), up to 3 times in all.
+    pub fn with_task_lease(mut self, lease: Duration) -> Self {
+        self.task_lease = lease;
+        self
+    }
+
+    /// Registers tenant `id`, whose workflows run with `run_as`.
+    pub fn with_tenant(mut self, id: impl Into<String>, tenant: Tenant) -> Self {
+        let id = id.into();
+        self.tenants.insert(id.clone(), Arc::new(Tenant { id, ..tenant }));
+        self
+    }
+
+    /// The tenant registered as `id`.
+    pub fn tenant(&self, id: &str) -> Option<&Tenant> {
+        self.tenants.get(id).map(Arc::as_ref)
+    }
+
+    /// Registers `workflow` under `name` for `workflow.call` nodes.
+    pub fn with_workflow(mut self, name: impl Into<String>, workflow: Workflow) -> Self {
+        self.workflows.insert(name.into(), Arc::new(workflow));
+        self
+    }
+
+    /// The cache shared by every run of this engine, e.g. for OAuth2 tokens.
+    pub fn cache(&self) -> &Arc<Cache> {
+        &self.cache
+    }
+
+    /// Keeps up to `capacity` compiled regular expressions (256 by default)
+    /// for the `regex.*` nodes of every run of this engine.
+    pub fn with_pattern_cache(mut self, capacity: usize) -> Self {
+        self.patterns = Arc::new(Patterns::new(capacity));
+        self
+    }
+
+    /// Seeds the random numbers of every run with `seed`, so runs that
+    /// use `crypto.random_bytes` and the like can be replayed exactly.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Renders errors and node descriptions in `locale` (English by
+    /// default; see `i18n`).
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Arc::new(locale);
+        self
+    }
+
+    /// The locale errors and node descriptions are rendered in.
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// The description of `node_type` in the engine's locale.
+    pub fn describe(&self, node_type: &str) -> Option<String> {
+        self.locale.describe(&self.registry, node_type)
+    }
+
+    /// Saves a `Checkpoint` to `store` after every level of a top-level run,
+    /// keyed by run id, so an interrupted run can continue with `resume`.
+    pub fn with_checkpoints(mut self, store: impl CheckpointStore + 'static) -> Self {
+        self.checkpoints = Some(Arc::new(store));
+        self
+    }
+
+    /// Records every node executed by top-level runs in `history`.
+    pub fn with_history(mut self, history: Arc<RunHistory>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Appends every execution of a node with side effects, in any run, to
+    /// `audit` (see `audit`). A run fails if its entry cannot be written.
+    pub fn with_audit(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Records every top-level run in `store`, by run id, with its status
+    /// and redacted outputs (see `runs`).
+    pub fn with_run_store(mut self, store: Arc<RunStore>) -> Self {
+        self.run_store = Some(store);
+        self
+    }
+
+    /// Masks values at paths matching `pattern`, such as `*.password` or
+    /// `headers.authorization`, in history records, event payloads passed to
+    /// listeners, cassette recordings, and run store records (see `redact`).
+    pub fn redact(mut self, pattern: &str) -> Self {
+        self.redaction = Arc::new(self.redaction.as_ref().clone().pattern(pattern));
+        self
+    }
+
+    /// Runs pure nodes normally but replaces nodes with side effects (see
+    /// `Registry::mark_side_effects`) with stubs, so a workflow's wiring can
+    /// be checked without calling out to anything. Nodes without a stub from
+    /// `with_stub` output `{"dry_run": true}`.
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Sets the outputs a dry run uses in place of a node, given its id or
+    /// its type. An id takes precedence over a type.
+    pub fn with_stub(mut self, node: impl Into<String>, outputs: HashMap<String, Value>) -> Self {
+        self.stubs.insert(node.into(), outputs);
+        self
+    }
+
+    /// Records the nodes with side effects that runs execute in `cassette`,
+    /// or replays them from it, depending on its mode.
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Replaces the node with id `node`, or every node of type `node`, with
+    /// `mock` for tests, whether or not the type is registered. An id takes
+    /// precedence over a type. The mock returns the node's outputs: an object
+    /// is used as the output ports, any other value becomes `result`.
+    pub fn mock<F>(&mut self, node: impl Into<String>, mock: F) -> &mut Self
+    where
+        F: Fn(&HashMap<String, Value>) -> Value + Send + Sync + 'static,
+    {
+        self.mocks.insert(node.into(), Arc::new(mock));
+        self
+    }
+
+    /// Mocks a node id or type with canned `outputs`, whatever its inputs.
+    pub fn mock_outputs(&mut self, node: impl Into<String>, outputs: Value) -> &mut Self {
+        self.mock(node, move |_| outputs.clone())
+    }
+
+    /// Mocks the node ids and types of a JSON object mapping each to its
+    /// canned outputs, such as a fixture file shared by several tests:
+    /// `{"http.request": {"status": 200, "body": "ok"}, "roll": 4}`.
+    pub fn load_mocks(&mut self, text: &str) -> Result<&mut Self, String> {
+        let mocks: serde_json::Map<String, Value> = serde_json::from_str(text).map_err(|e| format!("invalid mocks: {}", e))?;
+        for (node, outputs) in mocks {
+            self.mock_outputs(node, outputs);
+        }
+        Ok(self)
+    }
+
+    /// Ids of the top-level runs in flight.
+    pub fn in_flight(&self) -> Vec<String> {
+        self.runs.in_flight().into_iter().collect()
+    }
+
+    /// Cancels run `run_id` from another thread, returning whether it was in
+    /// flight. Its nodes that wait (such as `http.request` between retries)
+    /// and its parked delays stop at once, and no further node of it starts;
+    /// other runs carry on.
+    pub fn cancel(&self, run_id: &str) -> bool {
+        self.runs.cancel(run_id)
+    }
+
+    /// Stops taking new runs, waits up to `grace` for the runs in flight to
+    /// finish, and then cancels the rest, returning once every run has
+    /// stopped (see `shutdown`). Runs started afterwards fail with
+    /// `EngineError::ShuttingDown`.
+    pub fn shutdown(&self, grace: Duration) -> ShutdownReport {
+        shutdown::shutdown(&self.runs, grace, || self.draining.cancel())
+    }
+
+    /// Whether `shutdown` has been called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.runs.is_closed()
+    }
+
+    /// The registry used to resolve node types.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Mutable access for registering or overriding executors.
+    pub fn registry_mut(&mut self) -> &mut Registry {
+        &mut self.registry
+    }
+
+    /// Executes every node in dependency order, seeding the var store with `initial_vars`.
+    ///
+    /// Stops at the first node that reports an `error` output; when several
+    /// nodes of a level fail, the first in declaration order is reported.
+    ///
+    /// If the workflow declares inputs, `initial_vars` must match them.
+    pub fn run(&self, workflow: &Workflow, initial_vars: HashMap<String, Value>) -> Result<RunResult, EngineError> {
+        let (workflow, initial_vars) = self.profiled(workflow, initial_vars)?;
+        let initial_vars = contract::bind_inputs(&workflow, initial_vars)?;
+        let _admission = self.admit(&workflow)?;
+        self.run_nested(&workflow, vars(initial_vars), 0, &self.scope()?)
+    }
+
+    /// Like `run`, but for tenant `tenant`: the run uses the tenant's
+    /// secrets, cache, clients, history, and concurrency limits.
+    pub fn run_as(
+        &self,
+        tenant: &str,
+        workflow: &Workflow,
+        initial_vars: HashMap<String, Value>,
+    ) -> Result<RunResult, EngineError> {
+        let tenant = self.tenants.get(tenant).ok_or_else(|| EngineError::UnknownTenant(tenant.to_string()))?;
+        let (workflow, initial_vars) = self.profiled(workflow, initial_vars)?;
+        let initial_vars = contract::bind_inputs(&workflow, initial_vars)?;
+        let _admission = self.admit(&workflow)?;
+        let scope = Scope {
+            tenant: Some(tenant.clone()),
+            ..self.scope()?
+        };
+        self.run_nested(&workflow, vars(initial_vars), 0, &scope)
+    }
+
+    /// Runs only the `selected` nodes of `workflow`, to debug one node or part
+    /// of a graph. Nodes outside the selection are not run: those the
+    /// selection reads take their output ports from `upstream`, keyed by node
+    /// id, and the rest are left out. The var store starts from `vars` plus
+    /// the spec's vars and input defaults; declared inputs and outputs are
+    /// not checked. The result holds the outputs of the selected nodes only.
+    pub fn run_only(
+        &self,
+        workflow: &Workflow,
+        selected: &[&str],
+        upstream: HashMap<String, HashMap<String, Value>>,
+        vars: HashMap<String, Value>,
+    ) -> Result<RunResult, EngineError> {
+        let (workflow, vars) = self.profiled(workflow, vars)?;
+        let dependencies = workflow.upstream()?;
+        let invalid = |node: &str, message: String| EngineError::InvalidSelection {
+            node: node.to_string(),
+            message,
+        };
+        let mut state = Checkpoint::default();
+        let mut ids = selected.to_vec();
+        for &id in selected {
+            let index = workflow
+                .nodes
+                .iter()
+                .position(|n| n.id == id)
+                .ok_or_else(|| invalid(id, "no such node".to_string()))?;
+            for &i in &dependencies[index] {
+                let dependency = workflow.nodes[i].id.as_str();
+                if ids.contains(&dependency) {
+                    continue;
+                }
+                let outputs = upstream
+                    .get(dependency)
+                    .ok_or_else(|| invalid(id, format!("needs the outputs of {}", dependency)))?;
+                state.outputs.insert(dependency.to_string(), outputs.clone());
+                ids.push(dependency);
+            }
+        }
+        state.vars = contract::seed_defaults(&workflow, vars);
+        let _admission = self.admit(&workflow)?;
+
+        let mut result = self.run_nested(&workflow.subgraph(&ids), state, 0, &self.scope()?)?;
+        result.outputs.retain(|id, _| selected.contains(&id.as_str()));
+        Ok(result)
+    }
+
+    /// Compiles `workflow` into a `Plan` for `run_plan` (see `plan`), under
+    /// the selected profile, failing with every problem `Workflow::validate`
+    /// finds. Nodes replaced by a mock are never folded.
+    pub fn compile(&self, workflow: &Workflow) -> Result<Plan, Vec<Diagnostic>> {
+        let invalid = |error: EngineError| vec![Diagnostic::Invalid(error)];
+        let profile = self.profile.clone().filter(|_| !workflow.profiles.is_empty());
+        let workflow = match &profile {
+            Some(name) => profile::apply(workflow, name).map_err(invalid)?.into_owned(),
+            None => workflow.clone(),
+        };
+        let diagnostics: Vec<Diagnostic> = workflow
+            .validate(&self.registry)
+            .into_iter()
+            .filter(|diagnostic| match diagnostic {
+                Diagnostic::UnknownNodeType { node, node_type } => {
+                    self.mocks.get(node).or_else(|| self.mocks.get(node_type)).is_none()
+                }
+                _ => true,
+            })
+            .collect();
+        if !diagnostics.is_empty() {
+            return Err(diagnostics);
+        }
+        let order = workflow.topological_order().map_err(invalid)?.into_iter().map(String::from).collect();
+        let levels = workflow
+            .topological_levels()
+            .map_err(invalid)?
+            .into_iter()
+            .map(|level| level.into_iter().map(String::from).collect())
+            .collect();
+
+        let mut folded = BTreeMap::new();
+        for node in &workflow.nodes {
+            let constant = expr::references(&node.params).is_ok_and(|references| references.is_empty());
+            if !constant
+                || !self.registry.is_deterministic(&node.node_type)
+                || self.mock_for(node).is_some()
+                || workflow.incoming(&node.id).next().is_some()
+            {
+                continue;
+            }
+            let (Some(executor), Ok(inputs)) = (
+                self.registry.get(&node.node_type),
+                resolve_inputs(&workflow, node, &HashMap::new(), &HashMap::new()),
+            ) else {
+                continue;
+            };
+            // A node that fails is left to fail at run time.
+            let outputs = executor.execute(inputs, Some(&Runtime::new()));
+            if failure(node, &outputs).is_none() {
+                folded.insert(node.id.clone(), outputs);
+            }
+        }
+        Ok(Plan {
+            version: PLAN_VERSION,
+            profile,
+            workflow,
+            order,
+            levels,
+            folded,
+        })
+    }
+
+    /// Runs a `Plan` from `compile` like `run` runs its workflow, starting
+    /// with the outputs of its folded nodes, which come first in
+    /// `RunResult::order`.
+    pub fn run_plan(&self, plan: &Plan, initial_vars: HashMap<String, Value>) -> Result<RunResult, EngineError> {
+        let workflow = &plan.workflow;
+        if !workflow.profiles.is_empty() && plan.profile != self.profile {
+            return Err(EngineError::InvalidProfile {
+                profile: self.profile.clone().unwrap_or_else(|| "(none)".to_string()),
+                message: match &plan.profile {
+                    Some(name) => format!("the plan was compiled for profile {}", name),
+                    None => "the plan was compiled without a profile".to_string(),
+                },
+            });
+        }
+        let initial_vars = match &plan.profile {
+            Some(name) => profile::seed_vars(workflow, name, initial_vars),
+            None => initial_vars,
+        };
+        let initial_vars = contract::bind_inputs(workflow, initial_vars)?;
+        let _admission = self.admit(workflow)?;
+        let mut state = vars(initial_vars);
+        for (id, outputs) in &plan.folded {
+            state.outputs.insert(id.clone(), outputs.clone());
+            state.order.push(id.clone());
+        }
+        let levels = match self.pool {
+            Some(_) => plan.levels.iter().map(|level| level.iter().map(String::as_str).collect()).collect(),
+            None => plan.order.iter().map(|id| vec![id.as_str()]).collect(),
+        };
+        self.run_levels(workflow, levels, state, 0, &self.scope()?)
+    }
+
+    /// Applies the selected profile, if any, to `workflow` and `vars`.
+    fn profiled<'a>(
+        &self,
+        workflow: &'a Workflow,
+        vars: HashMap<String, Value>,
+    ) -> Result<(Cow<'a, Workflow>, HashMap<String, Value>), EngineError> {
+        match &self.profile {
+            Some(name) => Ok((profile::apply(workflow, name)?, profile::seed_vars(workflow, name, vars))),
+            None => Ok((Cow::Borrowed(workflow), vars)),
+        }
+    }
+
+    /// Waits for the run queue, if any, to admit a run of `workflow`.
+    fn admit(&self, workflow: &Workflow) -> Result<Option<Admission<'_>>, EngineError> {
+        let Some(queue) = &self.queue else {
+            return Ok(None);
+        };
+        let class = workflow.class.as_deref().unwrap_or(DEFAULT_CLASS);
+        queue.admit(workflow.priority, class, &self.draining).map(Some)
+    }
+
+    /// The scope of a top-level run starting now, unless the engine is
+    /// shutting down.
+    fn scope(&self) -> Result<Scope, EngineError> {
+        self.scope_for(run_id())
+    }
+
+    /// The scope of a top-level run with id `run_id`, unless the engine is
+    /// shutting down.
+    fn scope_for(&self, run_id: String) -> Result<Scope, EngineError> {
+        let cancel = Arc::new(Cancellation::new());
+        let active = self.runs.begin(&run_id, cancel.clone())?;
+        let events = EventBus::new();
+        for (topic, listener) in &self.listeners {
+            let listener = match self.redaction.is_empty() {
+                true => listener.clone(),
+                false => {
+                    let (redaction, listener) = (self.redaction.clone(), listener.clone());
+                    Arc::new(move |event: &Event| {
+                        let mut event = event.clone();
+                        redaction.apply(&mut event.payload);
+                        listener(&event)
+                    }) as Listener
+                }
+            };
+            events.subscribe(topic.clone(), listener);
+        }
+        Ok(Scope {
+            deadline: self.timeout.map(|limit| Instant::now() + limit),
+            events: Arc::new(events),
+            random: Arc::new(self.seed.map_or_else(Random::new, Random::seeded)),
+            tenant: None,
+            run_id,
+            cancel,
+            executions: AtomicU64::new(0),
+            _active: active,
+        })
+    }
+
+    fn timed_out(&self) -> EngineError {
+        EngineError::Timeout(self.timeout.unwrap_or_default())
+    }
+
+    /// Runs `workflow` from `state`, `depth` nested runs (`workflow.call`s
+    /// or loop bodies) deep, within the top-level run's `scope`.
+    fn run_nested(
+        &self,
+        workflow: &Workflow,
+        state: Checkpoint,
+        depth: usize,
+        scope: &Scope,
+    ) -> Result<RunResult, EngineError> {
+        let levels: Vec<Vec<&str>> = match self.pool {
+            Some(_) => workflow.topological_levels()?,
+            None => workflow.topological_order()?.into_iter().map(|id| vec![id]).collect(),
+        };
+        self.run_levels(workflow, levels, state, depth, scope)
+    }
+
+    /// Runs the nodes of `workflow` in the order of `levels`, recording a
+    /// top-level run in the run store, if any.
+    fn run_levels<'w>(
+        &self,
+        workflow: &'w Workflow,
+        levels: Vec<Vec<&'w str>>,
+        state: Checkpoint,
+        depth: usize,
+        scope: &Scope,
+    ) -> Result<RunResult, EngineError> {
+        let Some(store) = self.run_store.as_ref().filter(|_| depth == 0) else {
+            return self.run_graph(workflow, levels, state, depth, scope);
+        };
+        let tenant = scope.tenant.as_ref().map(|tenant| tenant.id.as_str());
+        store.begin(&scope.run_id, tenant, workflow.digest());
+        let result = self.run_graph(workflow, levels, state, depth, scope);
+        store.finish(&scope.run_id, &result, scope.cancel.is_cancelled(), &self.redaction);
+        result
+    }
+
+    /// Runs the nodes of `workflow` in the order of `levels`, each once the
+    /// nodes it depends on have finished.
+    fn run_graph<'w>(
+        &self,
+        workflow: &'w Workflow,
+        levels: Vec<Vec<&'w str>>,
+        state: Checkpoint,
+        depth: usize,
+        scope: &Scope,
+    ) -> Result<RunResult, EngineError> {
+        let started = Instant::now();
+        // Nodes already completed are not run again, so their types need no executor.
+        for node in workflow.nodes.iter().filter(|n| !state.outputs.contains_key(&n.id)) {
+            let builtin = node.node_type == WHILE || node.node_type == CALL;
+            if !builtin && !self.registry.contains(&node.node_type) && self.mock_for(node).is_none() {
+                return Err(EngineError::UnknownNodeType {
+                    node: node.id.clone(),
+                    node_type: node.node_type.clone(),
+                });
+            }
+        }
+
+        let (secrets, cache, clients, history) = match &scope.tenant {
+            Some(tenant) => (&tenant.secrets, &tenant.cache, &tenant.clients, tenant.history.as_ref()),
+            None => (&self.secrets, &self.cache, &self.clients, self.history.as_ref()),
+        };
+        let mut runtime = Runtime::with_shared_secrets(secrets.clone());
+        runtime.store = state.vars;
+        runtime.cache = cache.clone();
+        runtime.patterns = self.patterns.clone();
+        runtime.clients = clients.clone();
+        runtime.cancel = scope.cancel.clone();
+        runtime.events = scope.events.clone();
+        runtime.random = scope.random.clone();
+        runtime.deadline = scope.deadline;
+        let mut result = RunResult {
+            outputs: state.outputs,
+            vars: HashMap::new(),
+            order: state.order,
+            skipped: state.skipped,
+            stubbed: Vec::new(),
+            returned: HashMap::new(),
+            metadata: RunMetadata {
+                run_id: scope.run_id.clone(),
+                ..RunMetadata::default()
+            },
+        };
+        let upstream = workflow.upstream()?;
+        let mut skipped: HashSet<&str> = workflow
+            .nodes
+            .iter()
+            .map(|n| n.id.as_str())
+            .filter(|id| result.skipped.iter().any(|s| s == id))
+            .collect();
+        let history = history.filter(|_| depth == 0);
+        let run = history.map_or(0, |history| history.begin_run());
+        let digest = self.checkpoints.as_ref().filter(|_| depth == 0).map(|_| workflow.digest());
+
+        // Each pass runs the pending nodes whose dependencies have all finished:
+        // every one of them with parallelism, which is the next level unless a
+        // delay is parked, and otherwise the first. Delays are parked instead
+        // (see `delays`), so the nodes that do not wait on them carry on.
+        let mut pending: Vec<&str> = levels.into_iter().flatten().collect();
+        let mut parked: Vec<Parked> = Vec::new();
+        while !pending.is_empty() || !parked.is_empty() {
+            // Once the run is cancelled, every parked delay ends at once.
+            let cancelled = scope.cancel.is_cancelled();
+            let now = Instant::now();
+            let (due, waiting) = parked.into_iter().partition(|delay| cancelled || delay.wake <= now);
+            parked = waiting;
+            let mut completed: Vec<(&Node, Execution)> = due.into_iter().map(Parked::finish).collect();
+            let expired = scope.deadline.is_some_and(|deadline| now >= deadline);
+            if completed.is_empty() && cancelled {
+                return Err(EngineError::Cancelled);
+            }
+            if completed.is_empty() && expired {
+                return Err(self.timed_out());
+            }
+
+            let mut nodes: Vec<&Node> = Vec::new();
+            let mut i = 0;
+            while i < pending.len() && !cancelled && !expired && (self.pool.is_some() || nodes.is_empty()) {
+                let id = pending[i];
+                if result.outputs.contains_key(id) || skipped.contains(id) {
+                    pending.remove(i);
+                    continue;
+                }
+                let index = workflow.nodes.iter().position(|n| n.id == id).expect("ordered ids come from the workflow");
+                let node = &workflow.nodes[index];
+                let dependencies: Vec<&str> = upstream[index].iter().map(|&i| workflow.nodes[i].id.as_str()).collect();
+                if !dependencies.iter().all(|d| result.outputs.contains_key(*d) || skipped.contains(d)) {
+                    i += 1;
+                    continue;
+                }
+                pending.remove(i);
+                if is_skipped(workflow, node, &dependencies, &result.outputs, &skipped) {
+                    skipped.insert(id);
+                    result.skipped.push(id.to_string());
+                } else if workflow.incoming(id).any(|edge| edge.stream) {
+                    self.run_stream(workflow, node, &mut result, &mut runtime, depth, scope)?;
+                } else if self.parks(node) {
+                    let execution_id = format!("{}:{}:{}", scope.run_id, node.id, scope.executions.fetch_add(1, Ordering::Relaxed));
+                    let inputs = resolve_inputs(workflow, node, &result.outputs, &runtime.store);
+                    let inputs_hash = inputs.as_ref().ok().filter(|_| history.is_some()).map(hash_inputs);
+                    match park(node, execution_id, inputs, inputs_hash, scope.deadline) {
+                        Ok(delay) => parked.push(delay),
+                        Err(execution) => completed.push((node, *execution)),
+                    }
+                } else {
+                    nodes.push(node);
+                }
+            }
+            if nodes.is_empty() && completed.is_empty() {
+                if let Some(wake) = parked.iter().map(|delay| delay.wake).min() {
+                    scope.cancel.sleep(wake.saturating_duration_since(Instant::now()));
+                }
+                continue;
+            }
+            let execute = |node: &&Node| {
+                let execution_id = format!("{}:{}:{}", scope.run_id, node.id, scope.executions.fetch_add(1, Ordering::Relaxed));
+                let (_permits, waited, in_flight) = match self.acquire(node, scope) {
+                    Ok(slots) => slots,
+                    Err(error) => {
+                        return Execution {
+                            outputs: Err(error),
+                            execution_id,
+                            inputs_hash: None,
+                            duration: Duration::ZERO,
+                            waited: None,
+                            in_flight: None,
+                        }
+                    }
+                };
+                let started = Instant::now();
+                let inputs = resolve_inputs(workflow, node, &result.outputs, &runtime.store);
+                let inputs_hash = match &inputs {
+                    Ok(inputs) if history.is_some() || (self.audit.is_some() && self.is_audited(node)) => Some(hash_inputs(inputs)),
+                    _ => None,
+                };
+                let correlation = Correlation {
+                    run_id: scope.run_id.clone(),
+                    execution_id: execution_id.clone(),
+                };
+                let outputs = correlation::scope(correlation, || inputs.and_then(|inputs| match (self.mock_for(node), node.node_type.as_str()) {
+                    (Some(mock), _) => Ok(mocked(mock, &inputs)),
+                    (None, WHILE) => self.run_while(node, inputs, &runtime.store, depth, scope),
+                    (None, CALL) => self.run_call(node, inputs, depth, scope),
+                    _ if self.is_stubbed(node) => Ok(self.stub(node)),
+                    _ => {
+                        let executor = self.registry.get(&node.node_type).expect("types checked above");
+                        let run = |inputs: HashMap<String, Value>| match self.remotes.get(limits::category(&node.node_type)) {
+                            Some(transport) => self.dispatch(transport.as_ref(), node, &execution_id, inputs, scope),
+                            None => Ok(executor.execute(inputs, Some(&runtime))),
+                        };
+                        match self.cassette_for(node) {
+                            Some(cassette) => {
+                                // A failed dispatch is recorded as the node failing.
+                                let mut dispatch_error = None;
+                                let outputs = cassette
+                                    .play(&node.node_type, &inputs, &self.redaction, || {
+                                        run(inputs.clone()).unwrap_or_else(|error| {
+                                            let outputs = HashMap::from([("error".to_string(), Value::from(error.to_string()))]);
+                                            dispatch_error = Some(error);
+                                            outputs
+                                        })
+                                    })
+                                    .map_err(|message| EngineError::NodeFailed {
+                                        node: node.id.clone(),
+                                        message,
+                                    });
+                                match dispatch_error {
+                                    Some(error) => Err(error),
+                                    None => outputs,
+                                }
+                            }
+                            None => run(inputs),
+                        }
+                    }
+                }));
+                Execution {
+                    outputs,
+                    execution_id,
+                    inputs_hash,
+                    duration: started.elapsed(),
+                    waited,
+                    in_flight,
+                }
+            };
+            let executed: Vec<Execution> = match &self.pool {
+                Some(pool) if nodes.len() > 1 => pool.install(|| nodes.par_iter().map(execute).collect()),
+                _ => nodes.iter().map(execute).collect(),
+            };
+            completed.extend(nodes.into_iter().zip(executed));
+            completed.sort_by_cached_key(|(node, _)| workflow.nodes.iter().position(|n| n.id == node.id));
+
+            for (node, execution) in completed {
+                let Execution {
+                    outputs,
+                    execution_id,
+                    inputs_hash,
+                    duration,
+                    waited,
+                    in_flight,
+                } = execution;
+                if let Some(waited) = waited {
+                    result.metadata.queued.push((node.id.clone(), waited));
+                }
+                if let Some(in_flight) = in_flight {
+                    let peak = result.metadata.peak_in_flight.entry(limits::category(&node.node_type).to_string()).or_default();
+                    *peak = (*peak).max(in_flight);
+                }
+                let (outputs, failure) = match outputs {
+                    Ok(outputs) => {
+                        let failure = failure(node, &outputs);
+                        (outputs, failure)
+                    }
+                    Err(error) => (HashMap::new(), Some(error)),
+                };
+                let handled = workflow.outgoing(&node.id).any(|edge| edge.from_port == ON_ERROR);
+                let status = match &failure {
+                    None => Status::Succeeded,
+                    Some(_) if handled => Status::Handled,
+                    Some(_) => Status::Failed,
+                };
+                if let Some(audit) = self.audit.as_ref().filter(|_| self.is_audited(node)) {
+                    let tenant = scope.tenant.as_ref().map(|tenant| tenant.id.as_str());
+                    audit
+                        .record(tenant, &scope.run_id, &node.id, &node.node_type, inputs_hash.clone(), status)
+                        .map_err(EngineError::Audit)?;
+                }
+                if let Some(history) = history {
+                    history.push(NodeRecord {
+                        run,
+                        node: node.id.clone(),
+                        node_type: node.node_type.clone(),
+                        status,
+                        execution_id: execution_id.clone(),
+                        attempt: history.attempt(&node.id),
+                        inputs_hash,
+                        outputs: self.redaction.ports(&outputs),
+                        error: failure.as_ref().map(|e| match e {
+                            EngineError::NodeFailed { message, .. } => message.clone(),
+                            other => other.to_string(),
+                        }),
+                        duration_ms: duration.as_millis() as u64,
+                    });
+                }
+                let outputs = match failure {
+                    None => outputs,
+                    Some(error) if handled && !matches!(error, EngineError::Timeout(_)) => {
+                        error_outputs(node, &execution_id, &error, outputs)
+                    }
+                    Some(error) => return Err(error),
+                };
+
+                apply_var_effects(&node.node_type, &outputs, &mut runtime.store);
+                if self.is_stubbed(node) {
+                    result.stubbed.push(node.id.clone());
+                }
+                result.outputs.insert(node.id.clone(), outputs);
+                result.order.push(node.id.clone());
+            }
+
+            if let Some(digest) = &digest {
+                self.save_checkpoint(digest, &result, &runtime.store, scope)?;
+            }
+        }
+
+        result.returned = contract::collect_outputs(workflow, &result.outputs, &runtime.store)?;
+        result.vars = runtime.store;
+        result.metadata.elapsed = started.elapsed();
+        Ok(result)
+    }
+
+    /// Runs the nodes fed by the streaming edges into `head`, and the nodes
+    /// fed by their streaming edges in turn, once per chunk of the stream.
+    fn run_stream(
+        &self,
+        workflow: &Workflow,
+        head: &Node,
+        result: &mut RunResult,
+        runtime: &mut Runtime,
+        depth: usize,
+        scope: &Scope,
+    ) -> Result<(), EngineError> {
+        let failed = |node: &str, message: String| EngineError::NodeFailed {
+            node: node.to_string(),
+            message,
+        };
+        let sources: Vec<&Edge> = workflow.incoming(&head.id).filter(|edge| edge.stream).collect();
+        let [source] = sources[..] else {
+            return Err(failed(&head.id, "has more than one streaming input".to_string()));
+        };
+        let members = streamed_nodes(workflow, &head.id)?;
+        for &member in &members[1..] {
+            if let Some(edge) = workflow.incoming(member).find(|edge| edge.stream && !members.contains(&edge.from.as_str())) {
+                return Err(failed(member, format!("streams from {}, outside the stream of {}", edge.from, head.id)));
+            }
+        }
+
+        // Nodes the streamed ones read from outside the stream have run already.
+        let upstream = workflow.upstream()?;
+        let mut ids = members.clone();
+        let mut outputs = HashMap::new();
+        for &member in &members {
+            let index = workflow.nodes.iter().position(|n| n.id == member).expect("streamed ids come from the workflow");
+            for &i in &upstream[index] {
+                let dependency = workflow.nodes[i].id.as_str();
+                if ids.contains(&dependency) {
+                    continue;
+                }
+                let done = result.outputs.get(dependency).ok_or_else(|| {
+                    failed(member, format!("reads {}, which has not run when the stream starts", dependency))
+                })?;
+                outputs.insert(dependency.to_string(), done.clone());
+                ids.push(dependency);
+            }
+        }
+
+        let value = result.outputs[&source.from].get(&source.from_port).cloned().unwrap_or(Value::Null);
+        let chunks: Box<dyn Iterator<Item = Result<Value, String>>> = match Stream::from_handle(&runtime.handles, &value) {
+            Some(stream) => Box::new(std::iter::from_fn(move || stream.next_chunk())),
+            None => match value {
+                Value::Array(items) => Box::new(items.into_iter().map(Ok)),
+                other => return Err(failed(&head.id, format!("{} is not a stream or a list: {}", source.to_port, other))),
+            },
+        };
+        // Each chunk run sees its chunk as a plain value on the streaming edges.
+        let mut streamed = workflow.subgraph(&ids);
+        for edge in &mut streamed.edges {
+            edge.stream = false;
+        }
+        let mut last: HashMap<&str, HashMap<String, Value>> = HashMap::new();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for chunk in chunks {
+            let chunk = chunk.map_err(|message| failed(&source.from, message))?;
+            if scope.cancel.is_cancelled() {
+                return Err(EngineError::Cancelled);
+            }
+            if scope.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(self.timed_out());
+            }
+            let mut state = Checkpoint {
+                outputs: outputs.clone(),
+                vars: std::mem::take(&mut runtime.store),
+                ..Checkpoint::default()
+            };
+            state.outputs.entry(source.from.clone()).or_default().insert(source.from_port.clone(), chunk);
+            let mut run = self.run_nested(&streamed, state, depth + 1, scope)?;
+            runtime.store = run.vars;
+            for id in run.stubbed {
+                if !result.stubbed.contains(&id) {
+                    result.stubbed.push(id);
+                }
+            }
+            for &member in &members {
+                if let Some(outputs) = run.outputs.remove(member).filter(|_| run.order.iter().any(|id| id == member)) {
+                    last.insert(member, outputs);
+                    *counts.entry(member).or_default() += 1;
+                }
+            }
+        }
+
+        for member in members {
+            let mut outputs = last.remove(member).unwrap_or_default();
+            outputs.insert("chunks".to_string(), Value::from(counts.get(member).copied().unwrap_or(0)));
+            result.outputs.insert(member.to_string(), outputs);
+            result.order.push(member.to_string());
+        }
+        Ok(())
+    }
+
+    /// Takes a concurrency slot for `node` from its tenant's limit and then
+    /// the engine's, where its category is limited.
+    fn acquire<'a>(&'a self, node: &Node, scope: &'a Scope) -> Result<Slots<'a>, EngineError> {
+        let category = limits::category(&node.node_type);
+        let tenant = scope.tenant.as_ref().and_then(|tenant| tenant.limits.get(category));
+        let (mut permits, mut queued, mut running) = (Vec::new(), None, None);
+        for limiter in tenant.into_iter().chain(self.limits.get(category)) {
+            let (permit, waited, in_flight) = match limiter.acquire(self.queue_policy, scope.deadline) {
+                Ok(slot) => slot,
+                Err(Refused::Full) => {
+                    return Err(EngineError::NodeFailed {
+                        node: node.id.clone(),
+                        message: format!("concurrency limit for {} reached", category),
+                    })
+                }
+                Err(Refused::TimedOut) => return Err(self.timed_out()),
+            };
+            permits.push(permit);
+            queued = match (queued, waited) {
+                (Some(queued), Some(waited)) => Some(queued + waited),
+                (queued, waited) => queued.or(waited),
+            };
+            running = Some(in_flight);
+        }
+        Ok((permits, queued, running))
+    }
+
+    /// Runs `node` on a worker through `transport` and waits for its outputs,
+    /// publishing its task again whenever the lease runs out.
+    fn dispatch(
+        &self,
+        transport: &dyn Transport,
+        node: &Node,
+        execution_id: &str,
+        inputs: HashMap<String, Value>,
+        scope: &Scope,
+    ) -> Result<HashMap<String, Value>, EngineError> {
+        let failed = |message: String| EngineError::NodeFailed {
+            node: node.id.clone(),
+            message,
+        };
+        let task = Task {
+            id: execution_id.to_string(),
+            node_type: node.node_type.clone(),
+            inputs,
+            lease_ms: self.task_lease.as_millis() as u64,
+        };
+        for _ in 0..MAX_ATTEMPTS {
+            transport.publish(&task).map_err(failed)?;
+            let expires = Instant::now() + self.task_lease;
+            loop {
+                if scope.cancel.is_cancelled() {
+                    return Err(EngineError::Cancelled);
+                }
+                let now = Instant::now();
+                if scope.deadline.is_some_and(|deadline| now >= deadline) {
+                    return Err(self.timed_out());
+                }
+                if now >= expires {
+                    break;
+                }
+                let wait = (expires - now).min(DISPATCH_POLL);
+                if let Some(outputs) = transport.result(&task.id, wait).map_err(failed)? {
+                    return Ok(outputs);
+                }
+            }
+        }
+        Err(failed(format!("no worker completed task {} after {} attempts", task.id, MAX_ATTEMPTS)))
+    }
+
+    /// Whether `node` is replaced by a stub in this run.
+    fn is_stubbed(&self, node: &Node) -> bool {
+        self.dry_run
+            && self.registry.has_side_effects(&node.node_type)
+            && self.mock_for(node).is_none()
+            && self.cassette_for(node).is_none()
+    }
+
+    /// Whether running `node` really performs its side effects, which the
+    /// audit log records.
+    fn is_audited(&self, node: &Node) -> bool {
+        self.registry.has_side_effects(&node.node_type)
+            && self.mock_for(node).is_none()
+            && !self.is_stubbed(node)
+            && self.cassette_for(node).is_none_or(|cassette| cassette.mode() == CassetteMode::Record)
+    }
+
+    /// The cassette recording or replaying `node`, if it has side effects.
+    fn cassette_for(&self, node: &Node) -> Option<&Cassette> {
+        self.cassette.as_deref().filter(|_| self.registry.has_side_effects(&node.node_type))
+    }
+
+    /// The mock replacing `node`, by id or else by type.
+    fn mock_for(&self, node: &Node) -> Option<&Mock> {
+        self.mocks.get(&node.id).or_else(|| self.mocks.get(&node.node_type))
+    }
+
+    /// The outputs a dry run uses in place of `node`.
+    fn stub(&self, node: &Node) -> HashMap<String, Value> {
+        match self.stubs.get(&node.id).or_else(|| self.stubs.get(&node.node_type)) {
+            Some(outputs) => outputs.clone(),
+            None => HashMap::from([("dry_run".to_string(), Value::Bool(true))]),
+        }
+    }
+}
+
+/// A run id unlikely to repeat in any process: the process id, the start
+/// time, and a per-process count.
+fn run_id() -> String {
+    static RUNS: AtomicU64 = AtomicU64::new(0);
+    let started = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    format!("{:x}-{:x}-{}", std::process::id(), started, RUNS.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Turns a node's `error` output into `EngineError::NodeFailed`.
+fn failure(node: &Node, outputs: &HashMap<String, Value>) -> Option<EngineError> {
+    outputs.get("error").filter(|e| !e.is_null()).map(|error| EngineError::NodeFailed {
+        node: node.id.clone(),
+        message: error.as_str().map(String::from).unwrap_or_else(|| error.to_string()),
+    })
+}
+
+/// Outputs of a failed node whose error is handled: only the `on_error` branch
+/// is taken, carrying the node's other outputs (such as error details) along.
+fn error_outputs(
+    node: &Node,
+    execution_id: &str,
+    error: &EngineError,
+    mut failed: HashMap<String, Value>,
+) -> HashMap<String, Value> {
+    let message = match error {
+        EngineError::NodeFailed { message, .. } => message.clone(),
+        other => other.to_string(),
+    };
+    failed.remove("error");
+    let mut outputs = HashMap::new();
+    outputs.insert("branch".to_string(), Value::from(ON_ERROR));
+    outputs.insert(
+        ON_ERROR.to_string(),
+        serde_json::json!({
+            "node": node.id,
+            "type": node.node_type,
+            "message": message,
+            "outputs": failed,
+            "execution_id": execution_id,
+        }),
+    );
+    outputs
+}
+
+/// Runs `mock` and turns its value into output ports.
+fn mocked(mock: &Mock, inputs: &HashMap<String, Value>) -> HashMap<String, Value> {
+    match mock(inputs) {
+        Value::Object(outputs) => outputs.into_iter().collect(),
+        result => HashMap::from([("result".to_string(), result)]),
+    }
+}
+
+/// `head` and the nodes fed by streaming edges from it, directly or in turn,
+/// in dependency order.
+fn streamed_nodes<'a>(workflow: &'a Workflow, head: &'a str) -> Result<Vec<&'a str>, EngineError> {
+    let mut members = vec![head];
+    let mut next = 0;
+    while let Some(&id) = members.get(next) {
+        for edge in workflow.outgoing(id).filter(|edge| edge.stream) {
+            if !members.contains(&edge.to.as_str()) {
+                members.push(&edge.to);
+            }
+        }
+        next += 1;
+    }
+    Ok(workflow.topological_order()?.into_iter().filter(|id| members.contains(id)).collect())
+}
+
+/// SHA-256 of the canonical JSON of a node's inputs, as lowercase hex.
+fn hash_inputs(inputs: &HashMap<String, Value>) -> String {
+    let object: serde_json::Map<String, Value> = inputs.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let digest = Sha256::digest(canonical::to_string(&Value::Object(object)).as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new(Registry::with_builtins())
+    }
+}
+
+/// A node is skipped when one of its incoming edges leaves a branch that was
+/// not taken (including the `on_error` port of a node that succeeded), or
+/// when it has upstream nodes and all of them were skipped.
+fn is_skipped(
+    workflow: &Workflow,
+    node: &Node,
+    dependencies: &[&str],
+    outputs: &HashMap<String, HashMap<String, Value>>,
+    skipped: &HashSet<&str>,
+) -> bool {
+    let untaken = workflow.incoming(&node.id).any(|edge| {
+        outputs
+            .get(&edge.from)
+            .is_some_and(|ports| {
+                (ports.contains_key("branch") || edge.from_port == ON_ERROR) && !ports.contains_key(&edge.from_port)
+            })
+    });
+    untaken || (!dependencies.is_empty() && dependencies.iter().all(|id| skipped.contains(id)))
+}
+
+/// Builds a node's inputs: params first (with `$` references and `{{ }}`
+/// templates resolved), then values carried by incoming edges, which take
+/// precedence.
+fn resolve_inputs(
+    workflow: &Workflow,
+    node: &Node,
+    outputs: &HashMap<String, HashMap<String, Value>>,
+    store: &HashMap<String, Value>,
+) -> Result<HashMap<String, Value>, EngineError> {
+    let mut inputs: HashMap<String, Value> = node
+        .params
+        .iter()
+        .map(|(name, value)| {
+            let resolved = expr::resolve(value, outputs, store).map_err(|error| match error {
+                ResolveError::Invalid { expression, message } => EngineError::InvalidReference {
+                    node: node.id.clone(),
+                    expression,
+                    message,
+                },
+                ResolveError::Missing(expression) => EngineError::MissingValue {
+                    node: node.id.clone(),
+                    expression,
+                },
+            })?;
+            Ok((name.clone(), resolved))
+        })
+        .collect::<Result<_, EngineError>>()?;
+
+    for edge in workflow.incoming(&node.id) {
+        let value = outputs
+            .get(&edge.from)
+            .and_then(|ports| ports.get(&edge.from_port))
+            .cloned()
+            .unwrap_or(Value::Null);
+        inputs.insert(edge.to_port.clone(), value);
+    }
+    Ok(inputs)
+}
+
+/// `var.*` plugins report the mutation they want; the engine applies it.
+fn apply_var_effects(node_type: &str, outputs: &HashMap<String, Value>, store: &mut HashMap<String, Value>) {
+    let succeeded = outputs.get("success") == Some(&Value::Bool(true));
+    let key = outputs.get("key").and_then(Value::as_str);
+    match (node_type, key) {
+        ("var.set", Some(key)) if succeeded => {
+            let value = outputs.get("value").cloned().unwrap_or(Value::Null);
+            store.insert(key.to_string(), value);
+        }
+        ("var.delete", Some(key)) if succeeded => {
+            store.remove(key);
+        }
+        ("var.clear", _) if succeeded => store.clear(),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+    use serde_json::json;
+
+    #[test]
+    fn test_run_chain() {
+        let workflow = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!([1.25, 2.5])))
+            .node(Node::new("round", "math.round").param("decimals", json!(1)))
+            .edge(Edge::new("sum", "result", "round", "value"));
+
+        let result = Engine::default().run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.order, vec!["sum", "round"]);
+        assert_eq!(result.outputs["round"]["result"], json!(3.8));
+    }
+
+    #[test]
+    fn test_var_store_round_trip() {
+        let workflow = Workflow::new()
+            .node(Node::new("upper", "string.upper").param("string", json!("$name")))
+            .node(Node::new("save", "var.set").param("key", json!("greeting")))
+            .node(Node::new("load", "var.get").param("key", json!("greeting")))
+            .edge(Edge::new("upper", "result", "save", "value"))
+            .edge(Edge::new("save", "success", "load", "after"));
+
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), json!("ada"));
+
+        let result = Engine::default().run(&workflow, vars).unwrap();
+        assert_eq!(result.outputs["load"]["result"], json!("ADA"));
+        assert_eq!(result.vars["greeting"], json!("ADA"));
+    }
+
+    #[test]
+    fn test_reference_expressions() {
+        let workflow = Workflow::new()
+            .node(Node::new("round", "math.round").param("value", json!("$nodes.sum.result")))
+            .node(Node::new("sum", "math.add").param("numbers", json!(["$vars.a", "$b", 0.25])));
+
+        let mut vars = HashMap::new();
+        vars.insert("a".to_string(), json!(1));
+        vars.insert("b".to_string(), json!(2));
+
+        let result = Engine::default().run(&workflow, vars).unwrap();
+        assert_eq!(result.order, vec!["sum", "round"]);
+        assert_eq!(result.outputs["round"]["result"], json!(3.0));
+    }
+
+    #[test]
+    fn test_literal_dollar_params() {
+        let hash = "$2b$04$jjNwM2s35kuCSLiKd6SxMOPYlRuwt2y1TCzBLX1dNFe2XbwSVojzi";
+        let workflow = Workflow::new()
+            .node(Node::new("hash", "string.upper").param("string", json!(hash)))
+            .node(Node::new("price", "string.upper").param("string", json!("$100 or ${HOME}")));
+        let result = Engine::default().run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["hash"]["result"], json!(hash.to_uppercase()));
+        assert_eq!(result.outputs["price"]["result"], json!("$100 OR ${HOME}"));
+
+        let workflow = Workflow::new().node(Node::new("bad", "string.upper").param("string", json!("$vars")));
+        let err = Engine::default().run(&workflow, HashMap::new()).unwrap_err();
+        assert!(matches!(err, EngineError::InvalidReference { ref node, .. } if node == "bad"));
+    }
+
+    #[test]
+    fn test_template_params() {
+        let workflow = Workflow::new()
+            .node(Node::new("count", "list.length").param("list", json!([1, 2, 3])))
+            .node(Node::new("greet", "string.upper").param("string", json!("hi {{vars.name}}, {{nodes.count.result}} new")))
+            .node(Node::new("missing", "string.upper").param("string", json!("{{vars.nope}}")));
+
+        let mut vars = HashMap::new();
+        vars.insert("name".to_string(), json!("ada"));
+
+        let err = Engine::default().run(&workflow, vars.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            EngineError::MissingValue {
+                node: "missing".to_string(),
+                expression: "{{vars.nope}}".to_string()
+            }
+        );
+
+        let workflow = Workflow {
+            nodes: workflow.nodes[..2].to_vec(),
+            ..Workflow::default()
+        };
+        let result = Engine::default().run(&workflow, vars).unwrap();
+        assert_eq!(result.outputs["greet"]["result"], json!("HI ADA, 3 NEW"));
+    }
+
+    #[test]
+    fn test_if_skips_untaken_branch() {
+        let workflow = Workflow::new()
+            .node(Node::new("check", "control.if").param("condition", json!("$vars.big")).param("value", json!(-2)))
+            .node(Node::new("abs", "math.abs"))
+            .node(Node::new("double", "math.multiply").param("numbers", json!(["$nodes.abs.result", 2])))
+            .node(Node::new("negate", "math.multiply").param("numbers", json!([-1])))
+            .node(Node::new("join", "list.concat").param("lists", json!([[], []])))
+            .edge(Edge::new("check", "then", "abs", "value"))
+            .edge(Edge::new("check", "else", "negate", "value"))
+            .edge(Edge::new("double", "result", "join", "a"))
+            .edge(Edge::new("negate", "result", "join", "b"));
+
+        for parallelism in [1, 4] {
+            let engine = Engine::default().with_max_parallelism(parallelism);
+            let mut vars = HashMap::new();
+            vars.insert("big".to_string(), json!(true));
+            let result = engine.run(&workflow, vars).unwrap();
+            assert_eq!(result.order, vec!["check", "abs", "double", "join"]);
+            assert_eq!(result.skipped, vec!["negate"]);
+            assert_eq!(result.outputs["double"]["result"], json!(4.0));
+            assert!(!result.outputs.contains_key("negate"));
+
+            let mut vars = HashMap::new();
+            vars.insert("big".to_string(), json!(false));
+            let result = engine.run(&workflow, vars).unwrap();
+            assert_eq!(result.skipped, vec!["abs", "double"]);
+            assert!(result.order.contains(&"join".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_switch_routes_to_case() {
+        let workflow = Workflow::new()
+            .node(
+                Node::new("route", "control.switch")
+                    .param("value", json!("$vars.kind"))
+                    .param("cases", json!([{"port": "text", "value": "text"}, "number"])),
+            )
+            .node(Node::new("upper", "string.upper"))
+            .node(Node::new("abs", "math.abs").param("value", json!(-1)))
+            .node(Node::new("fallback", "string.lower"))
+            .edge(Edge::new("route", "text", "upper", "string"))
+            .edge(Edge::new("route", "case_1", "abs", "trigger"))
+            .edge(Edge::new("route", "default", "fallback", "string"));
+
+        let mut vars = HashMap::new();
+        vars.insert("kind".to_string(), json!("text"));
+        let result = Engine::default().run(&workflow, vars).unwrap();
+        assert_eq!(result.outputs["upper"]["result"], json!("TEXT"));
+        assert_eq!(result.skipped, vec!["abs", "fallback"]);
+
+        let mut vars = HashMap::new();
+        vars.insert("kind".to_string(), json!("date"));
+        let result = Engine::default().run(&workflow, vars).unwrap();
+        assert_eq!(result.outputs["fallback"]["result"], json!("date"));
+        assert_eq!(result.skipped, vec!["upper", "abs"]);
+    }
+
+    #[test]
+    fn test_streaming_edges() {
+        let path = std::env::temp_dir().join(format!("workflow_stream_{}.txt", std::process::id()));
+        std::fs::write(&path, "1\n-2\n3\n").unwrap();
+        let workflow = Workflow::new()
+            .node(Node::new("read", "file.read_lines").param("path", json!(path.to_str().unwrap())))
+            .node(Node::new("parse", "convert.to_number"))
+            .node(Node::new("abs", "math.abs"))
+            .node(Node::new("keep", "var.set").param("key", json!("latest")))
+            .node(Node::new("report", "math.multiply").param("numbers", json!(["$nodes.abs.chunks", "$vars.latest"])))
+            .edge(Edge::new("read", "result", "parse", "value").streaming())
+            .edge(Edge::new("parse", "result", "abs", "value").streaming())
+            .edge(Edge::new("abs", "result", "keep", "value").streaming());
+
+        for parallelism in [1, 4] {
+            let result = Engine::default().with_max_parallelism(parallelism).run(&workflow, HashMap::new()).unwrap();
+            assert_eq!(result.order, vec!["read", "parse", "abs", "keep", "report"]);
+            assert_eq!(result.outputs["abs"]["result"], json!(3.0));
+            assert_eq!(result.outputs["abs"]["chunks"], json!(3));
+            assert_eq!(result.outputs["report"]["result"], json!(9.0));
+        }
+        std::fs::remove_file(path).unwrap();
+
+        let lists = Workflow::new()
+            .node(Node::new("split", "string.split").param("string", json!("a,b,c")).param("separator", json!(",")))
+            .node(Node::new("upper", "string.upper"))
+            .edge(Edge::new("split", "result", "upper", "string").streaming());
+        let result = Engine::default().run(&lists, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["upper"], HashMap::from([("result".to_string(), json!("C")), ("chunks".to_string(), json!(3))]));
+
+        let mut scalar = lists.clone();
+        scalar.nodes[0] = Node::new("split", "math.abs").param("value", json!(-5));
+        scalar.edges[0].from_port = "result".to_string();
+        let err = Engine::default().run(&scalar, HashMap::new()).unwrap_err();
+        assert_eq!(err.to_string(), "Node upper failed: string is not a stream or a list: 5.0");
+    }
+
+    #[test]
+    fn test_table_handles() {
+        let workflow = Workflow::new()
+            .node(Node::new("load", "table.from_rows").param("rows", json!([{"id": 1}, {"id": 2}, {"id": 3}])))
+            .node(Node::new("sample", "table.to_rows").param("limit", json!(2)))
+            .edge(Edge::new("load", "table", "sample", "table"));
+
+        let result = Engine::default().run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["load"]["table"]["rows"], json!(3));
+        assert_eq!(result.outputs["sample"]["rows"], json!([{"id": 1}, {"id": 2}]));
+    }
+
+    #[test]
+    fn test_event_bus() {
+        use std::sync::Mutex;
+
+        // The loop reports its progress to a logging branch running beside it.
+        let body = Workflow::new()
+            .node(Node::new("double", "math.multiply").param("numbers", json!(["$vars.state", 2])))
+            .node(Node::new("save", "var.set").param("key", json!("state")))
+            .node(Node::new("report", "event.emit").param("topic", json!("progress")))
+            .node(Node::new("more", "logic.lt").param("b", json!(20)))
+            .edge(Edge::new("double", "result", "save", "value"))
+            .edge(Edge::new("double", "result", "report", "payload"))
+            .edge(Edge::new("double", "result", "more", "a"));
+        let workflow = Workflow::new()
+            .node(
+                Node::new("loop", WHILE)
+                    .param("condition", json!("more"))
+                    .param("state", json!(3))
+                    .param("max_iterations", json!(10))
+                    .body(body),
+            )
+            .node(
+                Node::new("log", "event.on")
+                    .param("topic", json!("progress"))
+                    .param("count", json!(3))
+                    .param("timeout_ms", json!(5_000)),
+            );
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let engine = Engine::default()
+            .with_max_parallelism(2)
+            .with_listener("progress", move |event| sink.lock().unwrap().push(event.payload.clone()));
+
+        for _ in 0..2 {
+            let result = engine.run(&workflow, HashMap::new()).unwrap();
+            assert_eq!(result.outputs["log"]["events"], json!([6, 12, 24]));
+        }
+        assert_eq!(seen.lock().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_run_only() {
+        let workflow = Workflow::new()
+            .var("factor", json!(3))
+            .node(Node::new("fetch", "http.request").param("url", json!("https://example.com")))
+            .node(Node::new("scale", "math.multiply").param("numbers", json!(["$nodes.fetch.status", "$vars.factor"])))
+            .node(Node::new("report", "math.abs"))
+            .node(Node::new("notify", "string.upper").param("string", json!("done")))
+            .edge(Edge::new("scale", "result", "report", "value"));
+        let mut upstream = HashMap::new();
+        upstream.insert("fetch".to_string(), HashMap::from([("status".to_string(), json!(-200))]));
+
+        let engine = Engine::default();
+        let result = engine.run_only(&workflow, &["scale", "report"], upstream.clone(), HashMap::new()).unwrap();
+        assert_eq!(result.order, vec!["scale", "report"]);
+        assert_eq!(result.outputs.len(), 2);
+        assert_eq!(result.outputs["report"]["result"], json!(600.0));
+
+        let err = engine.run_only(&workflow, &["report"], upstream.clone(), HashMap::new()).unwrap_err();
+        assert_eq!(err.to_string(), "Cannot run only report: needs the outputs of scale");
+        let err = engine.run_only(&workflow, &["missing"], upstream, HashMap::new()).unwrap_err();
+        assert_eq!(err.to_string(), "Cannot run only missing: no such node");
+    }
+
+    #[test]
+    fn test_unknown_node_type() {
+        let workflow = Workflow::new().node(Node::new("x", "math.nope"));
+
+        let err = Engine::default().run(&workflow, HashMap::new()).unwrap_err();
+        assert_eq!(
+            err,
+            EngineError::UnknownNodeType {
+                node: "x".to_string(),
+                node_type: "math.nope".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_node_failure_stops_run() {
+        let workflow = Workflow::new()
+            .node(Node::new("div", "math.divide").param("numbers", json!([1, 0])))
+            .node(Node::new("after", "math.abs"))
+            .edge(Edge::new("div", "result", "after", "value"));
+
+        let err = Engine::default().run(&workflow, HashMap::new()).unwrap_err();
+        assert_eq!(
+            err,
+            EngineError::NodeFailed {
+                node: "div".to_string(),
+                message: "division by zero".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_on_error_routes_to_handler() {
+        let workflow = Workflow::new()
+            .node(Node::new("div", "math.divide").param("numbers", json!([1, "$vars.divisor"])))
+            .node(Node::new("after", "math.abs"))
+            .node(Node::new("handler", "string.upper").param("string", json!("$nodes.div.on_error.message")))
+            .node(Node::new("log", "string.length").param("string", json!("$nodes.div.on_error.node")))
+            .edge(Edge::new("div", "result", "after", "value"))
+            .edge(Edge::new("div", ON_ERROR, "handler", "error"))
+            .edge(Edge::new("div", ON_ERROR, "log", "error"));
+
+        for parallelism in [1, 4] {
+            let engine = Engine::default().with_max_parallelism(parallelism);
+            let mut vars = HashMap::new();
+            vars.insert("divisor".to_string(), json!(0));
+            let result = engine.run(&workflow, vars.clone()).unwrap();
+            let mut payload = result.outputs["div"][ON_ERROR].clone();
+            let execution_id = payload["execution_id"].take();
+            assert!(execution_id.as_str().unwrap().starts_with(&format!("{}:div:", result.metadata.run_id)));
+            assert_eq!(
+                payload,
+                json!({"node": "div", "type": "math.divide", "message": "division by zero", "outputs": {"result": 0}, "execution_id": null})
+            );
+            assert_eq!(result.outputs["handler"]["result"], json!("DIVISION BY ZERO"));
+            assert_eq!(result.outputs["log"]["result"], json!(3));
+            assert_eq!(result.skipped, vec!["after"]);
+
+            vars.insert("divisor".to_string(), json!(-2));
+            let result = engine.run(&workflow, vars).unwrap();
+            assert_eq!(result.outputs["after"]["result"], json!(0.5));
+            assert_eq!(result.skipped, vec!["handler", "log"]);
+        }
+    }
+
+    #[test]
+    fn test_delay_cancellation() {
+        let workflow = Workflow::new()
+            .node(Node::new("wait", "control.delay").param("duration_ms", json!(60_000)))
+            .node(Node::new("quick", "math.abs").param("value", json!(-1)))
+            .node(Node::new("after", "math.abs"))
+            .edge(Edge::new("wait", "result", "after", "value"));
+        let mut short = workflow.clone();
+        short.nodes[0].params.insert("duration_ms".to_string(), json!(100));
+        let engine = Engine::default();
+
+        let (cancelled, finished) = std::thread::scope(|threads| {
+            let long = threads.spawn(|| engine.run(&workflow, HashMap::new()));
+            let run_id = loop {
+                match engine.in_flight().pop() {
+                    Some(run_id) => break run_id,
+                    None => std::thread::sleep(Duration::from_millis(1)),
+                }
+            };
+            let short = threads.spawn(|| engine.run(&short, HashMap::new()));
+            assert!(engine.cancel(&run_id));
+            (long.join().unwrap(), short.join().unwrap())
+        });
+        assert_eq!(cancelled.unwrap_err().to_string(), "Node wait failed: cancelled");
+        // The other run is not cancelled, and its parked delay lets `quick` run first.
+        assert_eq!(finished.unwrap().order, vec!["quick", "wait", "after"]);
+        assert!(!engine.cancel("no-such-run"));
+    }
+
+    #[test]
+    fn test_delays_are_parked() {
+        let mut workflow = Workflow::new();
+        for i in 0..4 {
+            workflow = workflow.node(Node::new(format!("wait{}", i), "control.delay").param("duration_ms", json!(100)));
+        }
+        // Without parallelism, the four delays still wait at the same time.
+        let started = Instant::now();
+        let result = Engine::default().run(&workflow, HashMap::new()).unwrap();
+        assert!(started.elapsed() < Duration::from_millis(350));
+        assert_eq!(result.order, vec!["wait0", "wait1", "wait2", "wait3"]);
+    }
+
+    #[test]
+    fn test_parallel_fan_out() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Barrier;
+
+        // Each branch waits until all four are running, which only succeeds concurrently.
+        let barrier = Arc::new(Barrier::new(4));
+        let running = Arc::new(AtomicUsize::new(0));
+        let mut engine = Engine::new(Registry::with_builtins()).with_max_parallelism(4);
+        let (b, r) = (barrier.clone(), running.clone());
+        engine.registry_mut().register(
+            "test.wait",
+            move |inputs: HashMap<String, Value>, _runtime: Option<&dyn std::any::Any>| {
+                r.fetch_add(1, Ordering::SeqCst);
+                b.wait();
+                inputs
+            },
+        );
+
+        let mut workflow = Workflow::new().node(Node::new("source", "math.add").param("numbers", json!([1, 2])));
+        for i in 0..4 {
+            let id = format!("branch{}", i);
+            workflow = workflow
+                .node(Node::new(&id, "test.wait"))
+                .edge(Edge::new("source", "result", &id, "value"));
+        }
+
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(engine.max_parallelism(), 4);
+        assert_eq!(running.load(Ordering::SeqCst), 4);
+        assert_eq!(result.order, vec!["source", "branch0", "branch1", "branch2", "branch3"]);
+        assert_eq!(result.outputs["branch3"]["value"], json!(3));
+    }
+
+    #[test]
+    fn test_custom_executor() {
+        let mut engine = Engine::new(Registry::new());
+        engine.registry_mut().register(
+            "test.echo",
+            |inputs: HashMap<String, Value>, _runtime: Option<&dyn std::any::Any>| inputs,
+        );
+        let workflow = Workflow::new().node(Node::new("echo", "test.echo").param("x", json!(1)));
+
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["echo"]["x"], json!(1));
+    }
+
+    #[test]
+    fn test_run_history() {
+        let history = Arc::new(RunHistory::new());
+        let engine = Engine::default().with_history(history.clone());
+        let workflow = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!([1, 2])))
+            .node(Node::new("div", "math.divide").param("numbers", json!(["$nodes.sum.result", "$vars.divisor"])));
+
+        let mut vars = HashMap::new();
+        vars.insert("divisor".to_string(), json!(0));
+        assert!(engine.run(&workflow, vars.clone()).is_err());
+        assert!(engine.run(&workflow, vars.clone()).is_err());
+        vars.insert("divisor".to_string(), json!(3));
+        engine.run(&workflow, vars).unwrap();
+
+        let records = history.nodes();
+        let summary: Vec<(u64, &str, Status, u32)> =
+            records.iter().map(|r| (r.run, r.node.as_str(), r.status, r.attempt)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "sum", Status::Succeeded, 1),
+                (1, "div", Status::Failed, 1),
+                (2, "sum", Status::Succeeded, 1),
+                (2, "div", Status::Failed, 2),
+                (3, "sum", Status::Succeeded, 1),
+                (3, "div", Status::Succeeded, 3),
+            ]
+        );
+        assert_eq!(records[0].inputs_hash, records[2].inputs_hash);
+        assert_ne!(records[1].inputs_hash, records[5].inputs_hash);
+        let failures = history.failures();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].error.as_deref(), Some("division by zero"));
+
+        let exported: Vec<NodeRecord> = serde_json::from_str(&history.to_json()).unwrap();
+        assert_eq!(exported, records);
+        assert!(history.to_json().contains("\"status\":\"failed\""));
+    }
+
+    #[test]
+    fn test_dry_run_stubs_side_effects() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut registry = Registry::with_builtins();
+        let counter = calls.clone();
+        registry.register("test.post", move |_: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            HashMap::from([("status".to_string(), json!(500))])
+        });
+        registry.mark_side_effects("test.post");
+        let workflow = Workflow::new()
+            .node(Node::new("body", "string.upper").param("string", json!("ping")))
+            .node(Node::new("post", "test.post").param("body", json!("$nodes.body.result")))
+            .node(Node::new("again", "test.post"))
+            .node(Node::new("status", "math.add").param("numbers", json!(["$nodes.post.status", 1])));
+
+        let engine = Engine::new(registry)
+            .dry_run()
+            .with_stub("test.post", HashMap::from([("status".to_string(), json!(0))]))
+            .with_stub("post", HashMap::from([("status".to_string(), json!(200))]));
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(result.stubbed, vec!["again", "post"]);
+        assert_eq!(result.outputs["body"]["result"], json!("PING"));
+        assert_eq!(result.outputs["again"]["status"], json!(0));
+        assert_eq!(result.outputs["status"]["result"], json!(201));
+
+        let result = Engine::default().dry_run().run(&Workflow::new().node(Node::new("q", "http.graphql")), HashMap::new());
+        assert_eq!(result.unwrap().outputs["q"]["dry_run"], json!(true));
+    }
+
+    #[test]
+    fn test_cassette_record_and_replay() {
+        use crate::cassette::CassetteMode;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let engine = |cassette: Arc<Cassette>| {
+            let mut registry = Registry::with_builtins();
+            let counter = calls.clone();
+            registry.register("test.fetch", move |inputs: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                HashMap::from([("body".to_string(), json!(format!("{} #{}", inputs["url"].as_str().unwrap(), n)))])
+            });
+            registry.mark_side_effects("test.fetch");
+            Engine::new(registry).with_cassette(cassette)
+        };
+        let workflow = Workflow::new()
+            .node(Node::new("fetch", "test.fetch").param("url", json!("https://x.io")))
+            .node(Node::new("shout", "string.upper").param("string", json!("$nodes.fetch.body")));
+
+        let recorder = Arc::new(Cassette::in_memory(CassetteMode::Record, Vec::new()));
+        let recorded = engine(recorder.clone()).run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(recorded.outputs["shout"]["result"], json!("HTTPS://X.IO #0"));
+        assert_eq!(recorder.interactions().len(), 1);
+
+        let player = engine(Arc::new(Cassette::in_memory(CassetteMode::Replay, recorder.interactions())));
+        let replayed = player.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(replayed.outputs, recorded.outputs);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let mut changed = workflow.clone();
+        changed.nodes[0].params.insert("url".to_string(), json!("https://y.io"));
+        assert_eq!(
+            player.run(&changed, HashMap::new()).unwrap_err().to_string(),
+            "Node fetch failed: no recorded test.fetch call with these inputs"
+        );
+    }
+
+    #[test]
+    fn test_mock_node_types() {
+        let mut engine = Engine::default().dry_run();
+        engine
+            .mock("http.graphql", |inputs| json!({"data": {"echo": inputs["variables"]}, "status": 200}))
+            .mock("test.random", |_| json!(4));
+        let workflow = Workflow::new()
+            .node(Node::new("query", "http.graphql").param("variables", json!({"id": 7})))
+            .node(Node::new("roll", "test.random"))
+            .node(Node::new("sum", "math.add").param("numbers", json!(["$nodes.query.data.echo.id", "$nodes.roll.result"])));
+
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["query"]["status"], json!(200));
+        assert_eq!(result.outputs["sum"]["result"], json!(11));
+        assert!(result.stubbed.is_empty());
+    }
+
+    #[test]
+    fn test_mock_node_ids() {
+        let workflow = Workflow::new()
+            .node(Node::new("orders", "http.request"))
+            .node(Node::new("users", "http.request"))
+            .node(Node::new("double", "math.multiply").param("numbers", json!(["$nodes.orders.count", 2])))
+            .node(Node::new("check", "logic.gt").param("a", json!("$nodes.double.result")).param("b", json!(10)));
+        let mut engine = Engine::default();
+        engine
+            .load_mocks(r#"{"http.request": {"status": 404}, "orders": {"status": 200, "count": 7}}"#)
+            .unwrap()
+            .mock_outputs("check", json!("stubbed"));
+
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["orders"]["count"], json!(7));
+        assert_eq!(result.outputs["users"]["status"], json!(404));
+        assert_eq!(result.outputs["double"]["result"], json!(14));
+        assert_eq!(result.outputs["check"]["result"], json!("stubbed"));
+        assert!(engine.load_mocks("[1]").is_err());
+    }
+
+    #[test]
+    fn test_workflow_contract() {
+        use crate::contract::{InputSpec, OutputSpec};
+        use crate::schema::PortKind;
+
+        let double = Workflow::new()
+            .input("n", InputSpec::new(PortKind::Number))
+            .output("doubled", OutputSpec::new(PortKind::Number, "$nodes.mul.result"))
+            .node(Node::new("mul", "math.multiply").param("numbers", json!(["$n", 2])));
+        let engine = Engine::default().with_workflow("double", double.clone());
+
+        let result = engine.run(&double, HashMap::from([("n".to_string(), json!(4))])).unwrap();
+        assert_eq!(result.returned["doubled"], json!(8));
+
+        let caller = Workflow::new()
+            .node(Node::new("call", "workflow.call").param("workflow", json!("double")).param("inputs", json!({"n": 5})))
+            .node(Node::new("bad", "workflow.call").param("workflow", json!("double")).param("inputs", json!({"n": "5"})));
+        let error = engine.run(&caller, HashMap::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Node bad failed: Invalid input n: expected number, got string");
+        let caller = Workflow { nodes: caller.nodes[..1].to_vec(), ..caller };
+        let call = &engine.run(&caller, HashMap::new()).unwrap().outputs["call"];
+        assert_eq!(call["result"], json!({"doubled": 10}));
+        assert!(!call.contains_key("outputs") && !call.contains_key("vars"));
+    }
+
+    #[test]
+    fn test_concurrency_limit_and_timeout() {
+        let mut engine = Engine::default()
+            .with_max_parallelism(4)
+            .with_concurrency_limit("test", 1)
+            .with_timeout(Duration::from_secs(5));
+        engine.registry_mut().register("test.slow", |inputs: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            std::thread::sleep(Duration::from_millis(20));
+            inputs
+        });
+        let workflow = Workflow::new()
+            .node(Node::new("a", "test.slow"))
+            .node(Node::new("b", "test.slow"))
+            .node(Node::new("c", "test.slow"));
+
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.metadata.peak_in_flight["test"], 1);
+        assert_eq!(result.metadata.queued.len(), 2);
+        assert!(result.metadata.elapsed >= Duration::from_millis(60));
+
+        let engine = engine.with_queue_policy(QueuePolicy::Fail);
+        let error = engine.run(&workflow, HashMap::new()).unwrap_err();
+        assert!(error.to_string().ends_with("failed: concurrency limit for test reached"), "{}", error);
+
+        let engine = engine.with_queue_policy(QueuePolicy::Wait).with_timeout(Duration::from_millis(30));
+        let workflow = Workflow::new()
+            .node(Node::new("first", "test.slow"))
+            .node(Node::new("second", "test.slow").param("after", json!("$nodes.first")))
+            .node(Node::new("third", "test.slow").param("after", json!("$nodes.second")));
+        let error = engine.run(&workflow, HashMap::new()).unwrap_err();
+        assert_eq!(error, EngineError::Timeout(Duration::from_millis(30)));
+    }
+
+    #[cfg(feature = "control")]
+    #[test]
+    fn test_delay_ends_at_timeout() {
+        let engine = Engine::default().with_timeout(Duration::from_millis(30));
+        let workflow = Workflow::new().node(Node::new("wait", "control.delay").param("duration_ms", json!(60_000)));
+        let started = Instant::now();
+        let error = engine.run(&workflow, HashMap::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Node wait failed: run deadline reached");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_tenants() {
+        let secrets = |key: &str| {
+            let mut secrets = MemorySecrets::new();
+            secrets.insert("api_key", key);
+            secrets
+        };
+        let acme_history = Arc::new(RunHistory::new());
+        let mut engine = Engine::default()
+            .with_max_parallelism(4)
+            .with_history(Arc::new(RunHistory::new()))
+            .with_tenant("acme", Tenant::new().with_secrets(secrets("acme-key")).with_history(acme_history.clone()))
+            .with_tenant("globex", Tenant::new().with_concurrency_limit("test", 1));
+        engine.registry_mut().register("test.peek", |_: HashMap<String, Value>, runtime: Option<&dyn std::any::Any>| {
+            let runtime = Runtime::from_any(runtime).unwrap();
+            let visits = runtime.cache.get("visits").and_then(|v| v.as_u64()).unwrap_or(0) + 1;
+            runtime.cache.insert("visits", json!(visits));
+            std::thread::sleep(Duration::from_millis(20));
+            HashMap::from([("key".to_string(), json!(runtime.secret("api_key"))), ("visits".to_string(), json!(visits))])
+        });
+        let workflow = Workflow::new().node(Node::new("peek", "test.peek"));
+
+        let acme = engine.run_as("acme", &workflow, HashMap::new()).unwrap();
+        assert_eq!(acme.outputs["peek"]["key"], json!("acme-key"));
+        assert_eq!(engine.run_as("acme", &workflow, HashMap::new()).unwrap().outputs["peek"]["visits"], json!(2));
+        let globex = engine.run_as("globex", &workflow, HashMap::new()).unwrap();
+        assert_eq!((&globex.outputs["peek"]["key"], &globex.outputs["peek"]["visits"]), (&json!(null), &json!(1)));
+        assert_eq!(acme_history.nodes().len(), 2);
+        assert_eq!(engine.tenant("acme").unwrap().cache().get("visits"), Some(json!(2)));
+        assert_eq!(engine.cache().get("visits"), None);
+
+        let pair = Workflow::new().node(Node::new("a", "test.peek")).node(Node::new("b", "test.peek"));
+        assert_eq!(engine.run_as("globex", &pair, HashMap::new()).unwrap().metadata.queued.len(), 1);
+        assert!(engine.run_as("acme", &pair, HashMap::new()).unwrap().metadata.queued.is_empty());
+        assert_eq!(
+            engine.run_as("initech", &workflow, HashMap::new()).unwrap_err(),
+            EngineError::UnknownTenant("initech".to_string())
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_tenant_rate_limits() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+            }
+        });
+        let engine = Engine::default().with_tenant("acme", Tenant::new()).with_tenant("globex", Tenant::new());
+        // One request a second to the host.
+        let workflow =
+            Workflow::new().node(Node::new("get", "http.request").param("url", json!(url)).param("rate_limit", json!(1)));
+        let timed = |tenant: &str| {
+            let started = Instant::now();
+            assert_eq!(engine.run_as(tenant, &workflow, HashMap::new()).unwrap().outputs["get"]["status"], json!(200));
+            started.elapsed()
+        };
+
+        timed("acme");
+        assert!(timed("globex") < Duration::from_millis(500));
+        assert!(timed("acme") >= Duration::from_millis(500));
+        assert_eq!(engine.tenant("acme").unwrap().clients().len(), 2);
+        assert!(engine.clients.is_empty());
+    }
+
+    #[test]
+    fn test_audit_log() {
+        use crate::audit::{AuditLog, AuditQuery};
+
+        let audit = Arc::new(AuditLog::new());
+        let mut engine = Engine::default().with_audit(audit.clone()).with_tenant("acme", Tenant::new());
+        engine.registry_mut().register("test.send", |inputs: HashMap<String, Value>, _: Option<&dyn std::any::Any>| inputs);
+        engine.registry_mut().mark_side_effects("test.send");
+        let workflow = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!([1, 2])))
+            .node(Node::new("send", "test.send").param("to", json!("$nodes.sum.result")));
+
+        engine.run(&workflow, HashMap::new()).unwrap();
+        let run_id = engine.run_as("acme", &workflow, HashMap::new()).unwrap().metadata.run_id;
+        engine.mock_outputs("send", json!({}));
+        engine.run(&workflow, HashMap::new()).unwrap();
+
+        let entries = audit.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].node.as_str(), entries[0].tenant.as_deref()), ("send", None));
+        assert_eq!(entries[1].tenant.as_deref(), Some("acme"));
+        assert_eq!(entries[0].inputs_hash, Some(hash_inputs(&HashMap::from([("to".to_string(), json!(3))]))));
+        assert_eq!(entries[1].prev_hash, entries[0].hash);
+        assert_eq!(audit.query(&AuditQuery::new().run(run_id)), entries[1..]);
+        assert!(audit.verify().is_ok());
+    }
+
+    #[test]
+    fn test_run_store() {
+        use crate::audit::AuditLog;
+        use crate::runs::{RunStatus, RunStore};
+
+        let audit = Arc::new(AuditLog::new());
+        let store = Arc::new(RunStore::new().with_audit(audit.clone()));
+        let mut engine = Engine::default()
+            .with_audit(audit)
+            .with_run_store(store.clone())
+            .with_tenant("acme", Tenant::new())
+            .redact("token");
+        engine.registry_mut().register("test.login", |_: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            HashMap::from([("token".to_string(), json!("secret-token"))])
+        });
+        engine.registry_mut().register("test.fail", |_: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            HashMap::from([("error".to_string(), json!("refused"))])
+        });
+        engine.registry_mut().mark_side_effects("test.login");
+        let workflow = Workflow::new()
+            .node(Node::new("login", "test.login"))
+            .node(Node::new("sum", "math.add").param("numbers", json!([1, 2])));
+        let failing = Workflow::new().node(Node::new("fail", "test.fail"));
+
+        let run_id = engine.run_as("acme", &workflow, HashMap::new()).unwrap().metadata.run_id;
+        engine.run(&failing, HashMap::new()).unwrap_err();
+
+        let run = store.get(&run_id).unwrap();
+        assert_eq!((run.status, run.tenant.as_deref()), (RunStatus::Succeeded, Some("acme")));
+        assert_eq!(run.workflow, workflow.digest());
+        assert_eq!(run.outputs["sum"]["result"], json!(3));
+        assert_eq!(run.outputs["login"]["token"], json!("[REDACTED]"));
+        assert!(run.finished_ms.is_some_and(|finished| finished >= run.started_ms));
+        let failed = store.with_status(RunStatus::Failed);
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].error.as_deref().unwrap().contains("refused"));
+        assert_eq!(store.runs().len(), 2);
+        let entries = store.audit(&run_id);
+        assert_eq!((entries.len(), entries[0].node.as_str()), (1, "login"));
+        assert!(store.audit(&failed[0].run_id).is_empty());
+    }
+
+    #[test]
+    fn test_redaction() {
+        use crate::cassette::Cassette;
+        use std::sync::Mutex;
+
+        let history = Arc::new(RunHistory::new());
+        let cassette = Arc::new(Cassette::in_memory(CassetteMode::Record, Vec::new()));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = events.clone();
+        let mut engine = Engine::default()
+            .with_history(history.clone())
+            .with_cassette(cassette.clone())
+            .with_listener("*", move |event| seen.lock().unwrap().push(event.payload.clone()))
+            .redact("*.password")
+            .redact("token");
+        engine.registry_mut().register("test.login", |inputs: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            HashMap::from([("token".to_string(), json!("secret-token")), ("user".to_string(), inputs["user"].clone())])
+        });
+        engine.registry_mut().mark_side_effects("test.login");
+        let workflow = Workflow::new()
+            .node(Node::new("login", "test.login").param("user", json!({"name": "ada", "password": "hunter2"})))
+            .node(Node::new("emit", "event.emit").param("topic", json!("login")).param("payload", json!("$nodes.login")));
+
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["login"]["token"], json!("secret-token"));
+        let masked = json!({"token": "[REDACTED]", "user": {"name": "ada", "password": "[REDACTED]"}});
+        assert_eq!(json!(history.nodes()[0].outputs), masked);
+        assert_eq!(events.lock().unwrap()[0], masked);
+        let recorded = &cassette.interactions()[0];
+        assert_eq!(recorded.inputs["user"]["password"], json!("[REDACTED]"));
+        assert_eq!(json!(recorded.outputs), masked);
+    }
+
+    #[test]
+    fn test_profiles() {
+        use crate::profile::Profile;
+
+        let workflow = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!(["$base", 1])))
+            .profile("dev", Profile::new().var("base", json!(1)))
+            .profile("prod", Profile::new().var("base", json!(10)).param("sum", "numbers", json!(["$base", 100])));
+
+        let run = |engine: Engine, vars: HashMap<String, Value>| engine.run(&workflow, vars).map(|r| r.outputs["sum"]["result"].clone());
+        assert_eq!(run(Engine::default().with_profile("dev"), HashMap::new()).unwrap(), json!(2));
+        assert_eq!(run(Engine::default().with_profile("prod"), HashMap::new()).unwrap(), json!(110));
+        let vars = HashMap::from([("base".to_string(), json!(5))]);
+        assert_eq!(run(Engine::default().with_profile("prod"), vars).unwrap(), json!(105));
+        assert_eq!(
+            run(Engine::default().with_profile("qa"), HashMap::new()).unwrap_err().to_string(),
+            "Invalid profile qa: not defined by the workflow"
+        );
+    }
+
+    #[test]
+    fn test_compile_plan() {
+        use crate::plan::Plan;
+        use crate::profile::Profile;
+
+        let workflow = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!([1, 2])))
+            .node(Node::new("scaled", "math.multiply").param("numbers", json!(["$scale", 2])))
+            .node(Node::new("round", "math.round"))
+            .edge(Edge::new("sum", "result", "round", "value"))
+            .profile("prod", Profile::new().var("scale", json!(10)).param("sum", "numbers", json!([4, 5])));
+        let engine = Engine::default().with_profile("prod").with_max_parallelism(2);
+        let plan = Plan::from_json(&engine.compile(&workflow).unwrap().to_json()).unwrap();
+        assert_eq!(plan.folded().collect::<Vec<_>>(), ["sum"]);
+        assert_eq!(plan.profile(), Some("prod"));
+
+        let result = engine.run_plan(&plan, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["round"]["result"], json!(9.0));
+        assert_eq!(result.outputs["scaled"]["result"], json!(20));
+        assert_eq!(result.order[0], "sum");
+        let vars = HashMap::from([("scale".to_string(), json!(3))]);
+        assert_eq!(engine.run_plan(&plan, vars).unwrap().outputs["scaled"]["result"], json!(6));
+        assert_eq!(
+            Engine::default().run_plan(&plan, HashMap::new()).unwrap_err().to_string(),
+            "Invalid profile (none): the plan was compiled for profile prod"
+        );
+
+        let registry = Workflow::new().node(
+            Node::new("encode", "convert.avro_encode")
+                .param("value", json!({"id": 1}))
+                .param("registry_url", json!("http://registry.invalid"))
+                .param("subject", json!("users-value")),
+        );
+        assert_eq!(Engine::default().compile(&registry).unwrap().folded().count(), 0);
+
+        let broken = Workflow::new().node(Node::new("send", "test.send"));
+        assert_eq!(
+            Engine::default().compile(&broken).unwrap_err(),
+            [Diagnostic::UnknownNodeType {
+                node: "send".to_string(),
+                node_type: "test.send".to_string()
+            }]
+        );
+        let mut mocked = Engine::default();
+        mocked.mock_outputs("test.send", json!({"sent": true}));
+        assert!(mocked.compile(&broken).is_ok());
+
+        let stale = plan.to_json().replacen("\"version\":1", "\"version\":0", 1);
+        assert!(matches!(Plan::from_json(&stale), Err(crate::error::LoadError::Version(_))));
+    }
+
+    #[test]
+    fn test_distribute() {
+        use crate::distributed::{MemoryTransport, Transport, Worker, IDEMPOTENCY_KEY};
+        use std::sync::atomic::AtomicBool;
+
+        let transport = Arc::new(MemoryTransport::new());
+        // The coordinator checks node types, so both sides register them.
+        let mut registry = Registry::with_builtins();
+        registry.register("math.key", |_: HashMap<String, Value>, runtime: Option<&dyn std::any::Any>| {
+            let runtime = runtime.and_then(|r| r.downcast_ref::<Runtime>()).unwrap();
+            HashMap::from([("result".to_string(), runtime.context[IDEMPOTENCY_KEY].clone())])
+        });
+        let coordinator = registry.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker = {
+            let (worker, stop) = (Worker::new(registry, transport.clone()), stop.clone());
+            std::thread::spawn(move || worker.run(&stop))
+        };
+        let workflow = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!([1, 2])))
+            .node(Node::new("upper", "string.upper").param("string", json!("ok")))
+            .node(Node::new("key", "math.key"))
+            .edge(Edge::new("sum", "result", "key", "after"));
+
+        let engine = Engine::new(coordinator.clone()).distribute("math", transport.clone());
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        stop.store(true, Ordering::Relaxed);
+        worker.join().unwrap();
+        assert_eq!(result.outputs["sum"]["result"], json!(3));
+        assert_eq!(result.outputs["upper"]["result"], json!("OK"));
+        let key = result.outputs["key"]["result"].as_str().unwrap();
+        assert!(key.starts_with(&format!("{}:key:", result.metadata.run_id)), "{}", key);
+        assert_eq!(transport.pending(), 0);
+
+        // Without a worker, the task is published again each lease until the
+        // node fails.
+        let engine = Engine::new(coordinator).distribute("math", transport.clone()).with_task_lease(Duration::from_millis(20));
+        let error = engine.run(&workflow, HashMap::new()).unwrap_err();
+        assert!(matches!(&error, EngineError::NodeFailed { node, message } if node == "sum" && message.contains("after 3 attempts")));
+        assert_eq!(transport.pending(), 3);
+        let task = transport.take(Duration::ZERO).unwrap().unwrap();
+        assert_eq!(transport.take(Duration::ZERO).unwrap().unwrap().id, task.id);
+    }
+
+    #[test]
+    fn test_shutdown() {
+        let engine = Arc::new(Engine::default());
+        let slow = Workflow::new().node(Node::new("wait", "control.delay").param("duration_ms", json!(60_000)));
+        let quick = Workflow::new().node(Node::new("wait", "control.delay").param("duration_ms", json!(20)));
+        let runs: Vec<_> = [slow, quick]
+            .into_iter()
+            .map(|workflow| {
+                let engine = engine.clone();
+                std::thread::spawn(move || engine.run(&workflow, HashMap::new()))
+            })
+            .collect();
+        while engine.runs.wait(Some(Instant::now())).len() < 2 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let report = engine.shutdown(Duration::from_millis(200));
+        assert_eq!(report.drained.len(), 1);
+        assert_eq!(report.interrupted.len(), 1);
+        let results: Vec<_> = runs.into_iter().map(|run| run.join().unwrap()).collect();
+        // The delay stops waiting when cancelled.
+        assert!(matches!(&results[0], Err(EngineError::NodeFailed { message, .. }) if message == "cancelled"));
+        assert_eq!(results[1].as_ref().unwrap().metadata.run_id, report.drained[0]);
+        assert!(engine.is_shutting_down());
+        assert_eq!(engine.run(&Workflow::new(), HashMap::new()).unwrap_err(), EngineError::ShuttingDown);
+    }
+
+    #[test]
+    fn test_shutdown_and_resume() {
+        use crate::checkpoint::{CheckpointStore, MemoryCheckpoints};
+
+        let store = Arc::new(MemoryCheckpoints::new());
+        // The first engine's waits last until cancelled; the second's return at once.
+        let engine = |blocking: bool| {
+            let mut engine = Engine::default().with_checkpoints(store.clone());
+            engine.registry_mut().register("test.wait", move |inputs: HashMap<String, Value>, runtime: Option<&dyn std::any::Any>| {
+                let runtime = Runtime::from_any(runtime).unwrap();
+                if blocking && !runtime.cancel.sleep(Duration::from_secs(60)) {
+                    return HashMap::from([("error".to_string(), json!("cancelled"))]);
+                }
+                inputs
+            });
+            engine
+        };
+        let workflows: Vec<Workflow> = [1, 10]
+            .into_iter()
+            .map(|n| {
+                Workflow::new()
+                    .node(Node::new("first", "math.add").param("numbers", json!([n, 1])))
+                    .node(Node::new("wait", "test.wait").param("x", json!("$nodes.first.result")))
+            })
+            .collect();
+
+        let interrupted = Arc::new(engine(true));
+        let runs: Vec<_> = workflows
+            .iter()
+            .cloned()
+            .map(|workflow| {
+                let engine = interrupted.clone();
+                std::thread::spawn(move || engine.run(&workflow, HashMap::new()))
+            })
+            .collect();
+        while store.runs().unwrap().len() < 2 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let report = interrupted.shutdown(Duration::ZERO);
+        assert_eq!(report.interrupted.len(), 2);
+        for run in runs {
+            assert!(matches!(run.join().unwrap(), Err(EngineError::NodeFailed { message, .. }) if message == "cancelled"));
+        }
+
+        let restarted = engine(false);
+        let mut results = Vec::new();
+        for run_id in &report.interrupted {
+            let checkpoint = store.load(run_id).unwrap().unwrap();
+            assert_eq!(checkpoint.order, vec!["first"]);
+            let workflow = workflows.iter().find(|w| w.digest() == checkpoint.workflow).unwrap();
+            let result = restarted.resume_run(workflow, run_id).unwrap();
+            assert_eq!(&result.metadata.run_id, run_id);
+            assert_eq!(result.order, vec!["first", "wait"]);
+            results.push(result.outputs["wait"]["x"].clone());
+        }
+        results.sort_by_key(|x| x.as_i64());
+        assert_eq!(results, vec![json!(2), json!(11)]);
+    }
+
+    #[test]
+    fn test_correlation() {
+        let mut registry = Registry::with_builtins();
+        registry.register("test.ids", |_: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            let ids = correlation::current().unwrap();
+            HashMap::from([("run".to_string(), json!(ids.run_id)), ("execution".to_string(), json!(ids.execution_id))])
+        });
+        let workflow = Workflow::new()
+            .node(Node::new("a", "test.ids"))
+            .node(Node::new("b", "test.ids"));
+        let history = Arc::new(RunHistory::new());
+        let engine = Engine::new(registry).with_max_parallelism(2).with_history(history.clone());
+
+        let first = engine.run(&workflow, HashMap::new()).unwrap();
+        let second = engine.run(&workflow, HashMap::new()).unwrap();
+        let run_id = &first.metadata.run_id;
+        assert!(!run_id.is_empty());
+        assert_ne!(run_id, &second.metadata.run_id);
+        assert_eq!(&first.outputs["a"]["run"], run_id);
+        let (a, b) = (&first.outputs["a"]["execution"], &first.outputs["b"]["execution"]);
+        assert!(a.as_str().unwrap().starts_with(&format!("{}:a:", run_id)));
+        assert_ne!(a, b);
+        let records = history.nodes();
+        let record = records.iter().find(|r| r.run == 1 && r.node == "a").unwrap();
+        assert_eq!(&record.execution_id, a);
+        assert_eq!(correlation::current(), None);
+    }
+
+    #[test]
+    fn test_seed() {
+        let engine = |seed: u64| {
+            let mut registry = Registry::with_builtins();
+            registry.register("test.draw", |_: HashMap<String, Value>, runtime: Option<&dyn std::any::Any>| {
+                let random = &Runtime::from_any(runtime).unwrap().random;
+                HashMap::from([("result".to_string(), json!(random.bytes(8)))])
+            });
+            Engine::new(registry).with_seed(seed)
+        };
+        let workflow = Workflow::new()
+            .node(Node::new("a", "test.draw"))
+            .node(Node::new("b", "test.draw"))
+            .edge(Edge::new("a", "result", "b", "previous"));
+        let draws = |engine: &Engine| {
+            let result = engine.run(&workflow, HashMap::new()).unwrap();
+            (result.outputs["a"]["result"].clone(), result.outputs["b"]["result"].clone())
+        };
+
+        let seeded = engine(7);
+        let (a, b) = draws(&seeded);
+        assert_ne!(a, b);
+        // Every run starts the sequence over.
+        assert_eq!(draws(&seeded), (a.clone(), b));
+        assert_ne!(draws(&engine(8)).0, a);
+    }
+
+    #[test]
+    fn test_locale() {
+        let german = Locale::from_ftl("de", "node-math-add = Zahlen addieren\nerror-unknown-tenant = Unbekannter Mandant: { $tenant }\n");
+        let engine = Engine::default().with_locale(german.unwrap());
+        assert_eq!(engine.describe("math.add").as_deref(), Some("Zahlen addieren"));
+        assert_eq!(engine.describe("logic.in").as_deref(), Some("Check if value is in list"));
+        let error = engine.run_as("acme", &Workflow::new(), HashMap::new()).unwrap_err();
+        assert_eq!(engine.locale().error(&error), "Unbekannter Mandant: acme");
+        assert_eq!(Engine::default().locale().error(&error), error.to_string());
+    }
+}
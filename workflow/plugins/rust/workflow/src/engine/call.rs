@@ -0,0 +1,123 @@
+//! `workflow.call` nodes, which the engine runs itself: the callee runs as a
+//! nested workflow within the caller's top-level run.
+
+use super::checkpoints::vars;
+use super::{Engine, Scope};
+use crate::contract;
+use crate::error::EngineError;
+use crate::graph::{Node, Workflow};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Nested `workflow.call`s allowed before a run fails, so a workflow that
+/// calls itself cannot recurse forever.
+const MAX_CALL_DEPTH: usize = 32;
+
+impl Engine {
+    /// Runs a `workflow.call` node: the workflow registered as `workflow`, or
+    /// the definition at `path`, with the `inputs` object as its var store.
+    ///
+    /// The callee does not see the caller's vars, and `inputs` must match
+    /// its declared inputs, if any. A callee that declares outputs returns
+    /// only those, as `result`; others expose their node `outputs` and final
+    /// `vars` instead. A failing callee node fails the call.
+    pub(super) fn run_call(
+        &self,
+        node: &Node,
+        inputs: HashMap<String, Value>,
+        depth: usize,
+        scope: &Scope,
+    ) -> Result<HashMap<String, Value>, EngineError> {
+        let failed = |message: String| EngineError::NodeFailed {
+            node: node.id.clone(),
+            message,
+        };
+        if depth >= MAX_CALL_DEPTH {
+            return Err(failed(format!("workflow calls nested deeper than {}", MAX_CALL_DEPTH)));
+        }
+        let callee = match (inputs.get("workflow").and_then(Value::as_str), inputs.get("path").and_then(Value::as_str)) {
+            (Some(name), _) => self
+                .workflows
+                .get(name)
+                .cloned()
+                .ok_or_else(|| failed(format!("unknown workflow: {}", name)))?,
+            (None, Some(path)) => Arc::new(Workflow::from_path(path).map_err(|e| failed(e.to_string()))?),
+            (None, None) => return Err(failed("workflow or path is required".to_string())),
+        };
+        let callee_vars = match inputs.get("inputs") {
+            Some(Value::Object(vars)) => vars.clone().into_iter().collect(),
+            None | Some(Value::Null) => HashMap::new(),
+            Some(_) => return Err(failed("inputs must be an object".to_string())),
+        };
+
+        let (callee, callee_vars) = self.profiled(&callee, callee_vars).map_err(|e| failed(e.to_string()))?;
+        let callee_vars = contract::bind_inputs(&callee, callee_vars).map_err(|e| failed(e.to_string()))?;
+
+        let run = self.run_nested(&callee, vars(callee_vars), depth + 1, scope).map_err(|e| match e {
+            EngineError::Timeout(_) => e,
+            e => failed(e.to_string()),
+        })?;
+        let mut outputs = HashMap::new();
+        if callee.outputs.is_empty() {
+            outputs.insert("outputs".to_string(), serde_json::to_value(run.outputs).unwrap_or(Value::Null));
+            outputs.insert("vars".to_string(), serde_json::to_value(run.vars).unwrap_or(Value::Null));
+        }
+        outputs.insert("result".to_string(), serde_json::to_value(run.returned).unwrap_or(Value::Null));
+        Ok(outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::CALL;
+    use crate::graph::Edge;
+    use serde_json::json;
+
+    #[test]
+    fn test_workflow_call() {
+        let double = Workflow::new()
+            .node(Node::new("double", "math.multiply").param("numbers", json!(["$vars.n", 2])))
+            .node(Node::new("save", "var.set").param("key", json!("doubled")))
+            .edge(Edge::new("double", "result", "save", "value"));
+        let workflow = Workflow::new()
+            .node(
+                Node::new("call", CALL)
+                    .param("workflow", json!("double"))
+                    .param("inputs", json!({"n": "$vars.start"})),
+            )
+            .node(Node::new("report", "math.abs").param("value", json!("$nodes.call.outputs.double.result")));
+        let engine = Engine::default().with_workflow("double", double);
+
+        let mut vars = HashMap::new();
+        vars.insert("start".to_string(), json!(-21));
+        let result = engine.run(&workflow, vars.clone()).unwrap();
+        assert_eq!(result.outputs["call"]["vars"], json!({"n": -21, "doubled": -42}));
+        assert_eq!(result.outputs["report"]["result"], json!(42.0));
+        assert!(!result.vars.contains_key("doubled"));
+
+        let path = std::env::temp_dir().join(format!("workflow_call_{}.json", std::process::id()));
+        std::fs::write(&path, engine.workflows["double"].to_json()).unwrap();
+        let mut by_path = workflow.clone();
+        by_path.nodes[0].params.remove("workflow");
+        by_path.nodes[0].params.insert("path".to_string(), json!(path.to_str().unwrap()));
+        let result = engine.run(&by_path, vars.clone()).unwrap();
+        assert_eq!(result.outputs["report"]["result"], json!(42.0));
+        std::fs::remove_file(path).unwrap();
+
+        let mut unknown = workflow.clone();
+        unknown.nodes[0].params.insert("workflow".to_string(), json!("triple"));
+        let err = engine.run(&unknown, HashMap::new()).unwrap_err();
+        assert_eq!(err.to_string(), "Node call failed: unknown workflow: triple");
+    }
+
+    #[test]
+    fn test_workflow_call_recursion_limit() {
+        let looping = Workflow::new().node(Node::new("again", CALL).param("workflow", json!("self")));
+        let engine = Engine::default().with_workflow("self", looping.clone());
+
+        let err = engine.run(&looping, HashMap::new()).unwrap_err();
+        assert!(err.to_string().ends_with("workflow calls nested deeper than 32"));
+    }
+}
@@ -0,0 +1,181 @@
+//! Saving and resuming runs from checkpoints (see `checkpoint`).
+
+use super::{Engine, RunResult, Scope};
+use crate::checkpoint::Checkpoint;
+use crate::error::EngineError;
+use crate::graph::Workflow;
+use serde_json::Value;
+use std::collections::HashMap;
+
+impl Engine {
+    /// Continues a run of `workflow` from `checkpoint`: nodes it completed or
+    /// skipped are not run again, and the var store is restored from it. The
+    /// run keeps its id and runs for the tenant the checkpoint names.
+    ///
+    /// Fails with `EngineError::InvalidCheckpoint` if the checkpoint was saved
+    /// for a different workflow (or under another profile), or if the run is
+    /// still in flight.
+    pub fn resume(&self, workflow: &Workflow, checkpoint: Checkpoint) -> Result<RunResult, EngineError> {
+        let (workflow, _) = self.profiled(workflow, HashMap::new())?;
+        if checkpoint.workflow != workflow.digest() {
+            return Err(EngineError::InvalidCheckpoint(format!(
+                "run {} was saved for a different workflow",
+                checkpoint.run_id
+            )));
+        }
+        let tenant = match &checkpoint.tenant {
+            Some(id) => Some(self.tenants.get(id).ok_or_else(|| EngineError::UnknownTenant(id.clone()))?.clone()),
+            None => None,
+        };
+        let _admission = self.admit(&workflow)?;
+        let scope = Scope {
+            tenant,
+            ..self.scope_for(checkpoint.run_id.clone())?
+        };
+        self.run_nested(&workflow, checkpoint, 0, &scope)
+    }
+
+    /// Like `resume`, with the checkpoint saved for run `run_id` in the
+    /// engine's checkpoint store.
+    pub fn resume_run(&self, workflow: &Workflow, run_id: &str) -> Result<RunResult, EngineError> {
+        let store = self
+            .checkpoints
+            .as_ref()
+            .ok_or_else(|| EngineError::InvalidCheckpoint("the engine has no checkpoint store".to_string()))?;
+        match store.load(run_id).map_err(EngineError::InvalidCheckpoint)? {
+            Some(checkpoint) => self.resume(workflow, checkpoint),
+            None => Err(EngineError::InvalidCheckpoint(format!("no checkpoint saved for run {}", run_id))),
+        }
+    }
+
+    /// Saves the state of a top-level run of the workflow with `digest` after
+    /// a level.
+    pub(super) fn save_checkpoint(
+        &self,
+        digest: &str,
+        result: &RunResult,
+        vars: &HashMap<String, Value>,
+        scope: &Scope,
+    ) -> Result<(), EngineError> {
+        let Some(store) = &self.checkpoints else {
+            return Ok(());
+        };
+        let checkpoint = Checkpoint {
+            run_id: scope.run_id.clone(),
+            workflow: digest.to_string(),
+            tenant: scope.tenant.as_ref().map(|tenant| tenant.id.clone()),
+            outputs: result.outputs.clone(),
+            vars: vars.clone(),
+            order: result.order.clone(),
+            skipped: result.skipped.clone(),
+        };
+        store.save(&checkpoint).map_err(EngineError::Checkpoint)
+    }
+}
+
+/// The starting state of a fresh run.
+pub(super) fn vars(vars: HashMap<String, Value>) -> Checkpoint {
+    Checkpoint {
+        vars,
+        ..Checkpoint::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Edge, Node};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        use crate::checkpoint::{CheckpointStore, MemoryCheckpoints};
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let broken = Arc::new(AtomicBool::new(true));
+        let store = Arc::new(MemoryCheckpoints::new());
+        let mut engine = Engine::default().with_checkpoints(store.clone());
+        let counter = runs.clone();
+        engine.registry_mut().register("test.count", move |inputs: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            inputs
+        });
+        let flaky = broken.clone();
+        engine.registry_mut().register("test.flaky", move |mut inputs: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            if flaky.load(Ordering::SeqCst) {
+                inputs.insert("error".to_string(), json!("service unavailable"));
+            }
+            inputs
+        });
+        let workflow = Workflow::new()
+            .node(Node::new("first", "test.count").param("x", json!(1)))
+            .node(Node::new("save", "var.set").param("key", json!("seen")).param("value", json!("$nodes.first.x")))
+            .node(Node::new("second", "test.flaky").param("x", json!("$vars.seen")))
+            .edge(Edge::new("save", "success", "second", "after_save"));
+
+        assert!(engine.run(&workflow, HashMap::new()).is_err());
+        let saved = store.runs().unwrap();
+        assert_eq!(saved.len(), 1);
+        let checkpoint = store.load(&saved[0]).unwrap().unwrap();
+        assert_eq!(checkpoint.order, vec!["first", "save"]);
+        assert_eq!(checkpoint.vars["seen"], json!(1));
+
+        broken.store(false, Ordering::SeqCst);
+        let checkpoint = Checkpoint::from_json(&checkpoint.to_json()).unwrap();
+        let result = engine.resume(&workflow, checkpoint).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(result.order, vec!["first", "save", "second"]);
+        assert_eq!(result.outputs["second"]["x"], json!(1));
+        assert_eq!(result.metadata.run_id, saved[0]);
+        assert_eq!(store.load(&saved[0]).unwrap().unwrap().order, result.order);
+        assert_eq!(store.runs().unwrap(), saved);
+    }
+
+    #[test]
+    fn test_resume_for_tenant() {
+        use crate::checkpoint::{CheckpointStore, MemoryCheckpoints};
+        use crate::tenant::Tenant;
+        use metabuilder_core::{MemorySecrets, Runtime};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let broken = Arc::new(AtomicBool::new(true));
+        let store = Arc::new(MemoryCheckpoints::new());
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("api_key", "acme-key");
+        let mut engine = Engine::default()
+            .with_checkpoints(store.clone())
+            .with_tenant("acme", Tenant::new().with_secrets(secrets));
+        let flaky = broken.clone();
+        engine.registry_mut().register("test.key", move |_: HashMap<String, Value>, runtime: Option<&dyn std::any::Any>| {
+            let runtime = Runtime::from_any(runtime).unwrap();
+            let mut outputs = HashMap::from([("key".to_string(), json!(runtime.secret("api_key")))]);
+            if flaky.load(Ordering::SeqCst) {
+                outputs.insert("error".to_string(), json!("service unavailable"));
+            }
+            outputs
+        });
+        let workflow = Workflow::new()
+            .node(Node::new("first", "var.set").param("key", json!("n")).param("value", json!(1)))
+            .node(Node::new("second", "test.key"))
+            .edge(Edge::new("first", "success", "second", "after_first"));
+
+        assert!(engine.run_as("acme", &workflow, HashMap::new()).is_err());
+        let checkpoint = store.load(&store.runs().unwrap()[0]).unwrap().unwrap();
+        assert_eq!(checkpoint.tenant.as_deref(), Some("acme"));
+        assert_eq!(checkpoint.workflow, workflow.digest());
+        assert_eq!(checkpoint.order, vec!["first"]);
+
+        let changed = workflow.clone().node(Node::new("third", "test.key"));
+        assert_eq!(
+            engine.resume(&changed, checkpoint.clone()).unwrap_err(),
+            EngineError::InvalidCheckpoint(format!("run {} was saved for a different workflow", checkpoint.run_id))
+        );
+
+        broken.store(false, Ordering::SeqCst);
+        let result = engine.resume_run(&workflow, &checkpoint.run_id).unwrap();
+        assert_eq!(result.metadata.run_id, checkpoint.run_id);
+        assert_eq!(result.outputs["second"]["key"], json!("acme-key"));
+    }
+}
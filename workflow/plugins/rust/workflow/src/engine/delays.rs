@@ -0,0 +1,118 @@
+//! `control.delay` nodes, which the engine parks instead of running.
+//!
+//! A parked delay holds no thread. The run goes on with the nodes that do
+//! not depend on it and, when no other node is ready, sleeps until the
+//! earliest parked delay is due or the run is cancelled.
+
+use super::{Engine, Execution};
+use crate::error::EngineError;
+use crate::graph::Node;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "control")]
+use control_delay::duration;
+
+/// Node type of the delays the engine parks.
+pub(crate) const DELAY: &str = "control.delay";
+
+/// A delay waiting to be due.
+pub(super) struct Parked<'w> {
+    pub(super) node: &'w Node,
+    execution_id: String,
+    inputs_hash: Option<String>,
+    /// Passed through as `result` once the delay is due
+    value: Value,
+    started: Instant,
+    /// When the wait ends
+    pub(super) wake: Instant,
+    /// Whether `wake` is the run's deadline, before the delay would be due
+    cut_short: bool,
+}
+
+impl Engine {
+    /// Whether the engine parks `node` rather than run it.
+    pub(super) fn parks(&self, node: &Node) -> bool {
+        cfg!(feature = "control") && node.node_type == DELAY && self.mock_for(node).is_none()
+    }
+}
+
+/// Parks delay `node` given its resolved `inputs`, or returns its failed
+/// execution if the inputs are invalid. The wait ends at `deadline` if that
+/// comes first.
+pub(super) fn park(
+    node: &Node,
+    execution_id: String,
+    inputs: Result<HashMap<String, Value>, EngineError>,
+    inputs_hash: Option<String>,
+    deadline: Option<Instant>,
+) -> Result<Parked<'_>, Box<Execution>> {
+    let failed = |execution_id: String, outputs| Execution {
+        outputs,
+        execution_id,
+        inputs_hash: None,
+        duration: Duration::ZERO,
+        waited: None,
+        in_flight: None,
+    };
+    let mut inputs = match inputs {
+        Ok(inputs) => inputs,
+        Err(error) => return Err(Box::new(failed(execution_id, Err(error)))),
+    };
+    let wait = match duration(&inputs) {
+        Ok(wait) => wait,
+        Err(message) => {
+            let outputs = HashMap::from([("result".to_string(), Value::Null), ("error".to_string(), json!(message))]);
+            return Err(Box::new(failed(execution_id, Ok(outputs))));
+        }
+    };
+    let started = Instant::now();
+    let due = started + wait;
+    let wake = deadline.map_or(due, |deadline| deadline.min(due));
+    Ok(Parked {
+        node,
+        execution_id,
+        inputs_hash,
+        value: inputs.remove("value").unwrap_or(Value::Null),
+        started,
+        wake,
+        cut_short: wake < due,
+    })
+}
+
+impl<'w> Parked<'w> {
+    /// Ends the wait, which fails as cancelled if the delay is not yet due.
+    pub(super) fn finish(self) -> (&'w Node, Execution) {
+        let error = match Instant::now() {
+            now if now < self.wake => Some("cancelled"),
+            _ if self.cut_short => Some("run deadline reached"),
+            _ => None,
+        };
+        let waited = self.started.elapsed();
+        let mut outputs = HashMap::from([("waited_ms".to_string(), json!(waited.as_millis() as u64))]);
+        match error {
+            Some(error) => {
+                outputs.insert("result".to_string(), Value::Null);
+                outputs.insert("error".to_string(), json!(error));
+            }
+            None => {
+                outputs.insert("result".to_string(), self.value);
+            }
+        }
+        let execution = Execution {
+            outputs: Ok(outputs),
+            execution_id: self.execution_id,
+            inputs_hash: self.inputs_hash,
+            duration: waited,
+            waited: None,
+            in_flight: None,
+        };
+        (self.node, execution)
+    }
+}
+
+#[cfg(not(feature = "control"))]
+fn duration(_: &HashMap<String, Value>) -> Result<Duration, String> {
+    unreachable!("delays are only parked with the control feature")
+}
@@ -0,0 +1,160 @@
+//! `control.while` loops, which the engine runs itself: the loop's body is a
+//! nested workflow, run once per iteration within the same top-level run.
+
+use super::checkpoints::vars;
+use super::{Engine, Scope};
+use crate::error::EngineError;
+use crate::graph::Node;
+use serde_json::Value;
+use std::collections::HashMap;
+
+impl Engine {
+    /// Runs a `control.while` node's body until its condition node's
+    /// `result` is falsy.
+    ///
+    /// Each iteration runs with the outer var store plus `state` (the loop
+    /// state, seeded from the `state` input) and `iteration` (counting from
+    /// 0). A body that sets the `state` var passes it to the next iteration.
+    /// Other var changes stay inside the body.
+    pub(super) fn run_while(
+        &self,
+        node: &Node,
+        inputs: HashMap<String, Value>,
+        store: &HashMap<String, Value>,
+        depth: usize,
+        scope: &Scope,
+    ) -> Result<HashMap<String, Value>, EngineError> {
+        let failed = |message: String| EngineError::NodeFailed {
+            node: node.id.clone(),
+            message,
+        };
+        let body = node.body.as_ref().ok_or_else(|| failed("body is required".to_string()))?;
+        let condition = inputs
+            .get("condition")
+            .and_then(Value::as_str)
+            .ok_or_else(|| failed("condition is required".to_string()))?;
+        if body.get_node(condition).is_none() {
+            return Err(failed(format!("condition node {} is not in the body", condition)));
+        }
+        let max_iterations = inputs
+            .get("max_iterations")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| failed("max_iterations is required".to_string()))?;
+
+        let mut state = inputs.get("state").cloned().unwrap_or(Value::Null);
+        for iteration in 0..max_iterations {
+            let mut vars = store.clone();
+            vars.insert("state".to_string(), state);
+            vars.insert("iteration".to_string(), Value::from(iteration));
+            let run = self.run_nested(body, self::vars(vars), depth + 1, scope)?;
+            state = run.vars.get("state").cloned().unwrap_or(Value::Null);
+
+            let proceed = run
+                .outputs
+                .get(condition)
+                .and_then(|ports| ports.get("result"))
+                .is_some_and(truthy);
+            if !proceed {
+                let mut outputs = HashMap::new();
+                outputs.insert("iterations".to_string(), Value::from(iteration + 1));
+                outputs.insert("state".to_string(), state);
+                outputs.insert("outputs".to_string(), serde_json::to_value(run.outputs).unwrap_or(Value::Null));
+                return Ok(outputs);
+            }
+        }
+        Err(failed(format!("loop did not finish within max_iterations ({})", max_iterations)))
+    }
+}
+
+/// Truthiness used by loop conditions, matching `control.if`.
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|f| f != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Null => false,
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(o) => !o.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::WHILE;
+    use crate::graph::{Edge, Workflow};
+    use crate::history::RunHistory;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_while_loop() {
+        // Doubles the state until it reaches 100.
+        let body = Workflow::new()
+            .node(Node::new("double", "math.multiply").param("numbers", json!(["$vars.state", 2])))
+            .node(Node::new("save", "var.set").param("key", json!("state")))
+            .node(Node::new("more", "logic.lt").param("b", json!(100)))
+            .edge(Edge::new("double", "result", "save", "value"))
+            .edge(Edge::new("double", "result", "more", "a"));
+        let workflow = Workflow::new()
+            .node(
+                Node::new("loop", WHILE)
+                    .param("condition", json!("more"))
+                    .param("state", json!("$vars.start"))
+                    .param("max_iterations", json!(10))
+                    .body(body),
+            )
+            .node(Node::new("report", "math.abs").param("value", json!("$nodes.loop.state")));
+
+        let mut vars = HashMap::new();
+        vars.insert("start".to_string(), json!(3));
+        let result = Engine::default().run(&workflow, vars.clone()).unwrap();
+        assert_eq!(result.outputs["loop"]["iterations"], json!(6));
+        assert_eq!(result.outputs["loop"]["state"], json!(192));
+        assert_eq!(result.outputs["loop"]["outputs"]["more"]["result"], json!(false));
+        assert_eq!(result.outputs["report"]["result"], json!(192.0));
+        assert!(!result.vars.contains_key("state"));
+
+        let mut capped = workflow.clone();
+        capped.nodes[0].params.insert("max_iterations".to_string(), json!(3));
+        let err = Engine::default().run(&capped, vars).unwrap_err();
+        assert_eq!(
+            err,
+            EngineError::NodeFailed {
+                node: "loop".to_string(),
+                message: "loop did not finish within max_iterations (3)".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_while_loop_is_one_run() {
+        use crate::checkpoint::{CheckpointStore, MemoryCheckpoints};
+
+        let body = Workflow::new()
+            .node(Node::new("next", "math.add").param("numbers", json!(["$vars.state", 1])))
+            .node(Node::new("save", "var.set").param("key", json!("state")))
+            .node(Node::new("more", "logic.lt").param("b", json!(3)))
+            .edge(Edge::new("next", "result", "save", "value"))
+            .edge(Edge::new("next", "result", "more", "a"));
+        let workflow = Workflow::new()
+            .node(
+                Node::new("loop", WHILE)
+                    .param("condition", json!("more"))
+                    .param("state", json!(0))
+                    .param("max_iterations", json!(10))
+                    .body(body),
+            )
+            .node(Node::new("report", "math.abs").param("value", json!("$nodes.loop.state")));
+
+        let history = Arc::new(RunHistory::new());
+        let store = Arc::new(MemoryCheckpoints::new());
+        let engine = Engine::default().with_history(history.clone()).with_checkpoints(store.clone());
+        let result = engine.run(&workflow, HashMap::new()).unwrap();
+        assert_eq!(result.outputs["loop"]["iterations"], json!(3));
+
+        let summary: Vec<(u64, String)> = history.nodes().into_iter().map(|r| (r.run, r.node)).collect();
+        assert_eq!(summary, vec![(1, "loop".to_string()), (1, "report".to_string())]);
+        assert_eq!(store.load(&result.metadata.run_id).unwrap().unwrap().order, vec!["loop", "report"]);
+    }
+}
@@ -0,0 +1,159 @@
+//! Engine and loader error types.
+
+use std::time::Duration;
+
+/// Errors that stop a workflow run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EngineError {
+    /// Two nodes share an id.
+    DuplicateNode(String),
+    /// An edge references a node that does not exist.
+    UnknownNode { edge: String, node: String },
+    /// A param expression cannot be parsed.
+    InvalidReference { node: String, expression: String, message: String },
+    /// A param expression reads a node that does not exist.
+    UnknownReference { node: String, expression: String, target: String },
+    /// A `{{ }}` placeholder refers to a value that does not exist.
+    MissingValue { node: String, expression: String },
+    /// A node's type is not in the registry.
+    UnknownNodeType { node: String, node_type: String },
+    /// The graph contains a cycle through these nodes.
+    Cycle(Vec<String>),
+    /// A node reported an `error` output.
+    NodeFailed { node: String, message: String },
+    /// The run was cancelled through `Engine::cancel` or a shutdown.
+    Cancelled,
+    /// The checkpoint store failed to save.
+    Checkpoint(String),
+    /// A checkpoint passed to `Engine::resume` is for another workflow or
+    /// an unknown tenant.
+    InvalidCheckpoint(String),
+    /// A caller-supplied var does not match the workflow's declared inputs.
+    InvalidInput { input: String, message: String },
+    /// A declared output is missing or has the wrong kind after the run.
+    InvalidOutput { output: String, message: String },
+    /// The run took longer than `Engine::with_timeout` allows.
+    Timeout(Duration),
+    /// The selected profile is missing or overrides a node that does not exist.
+    InvalidProfile { profile: String, message: String },
+    /// A partial run selects a node that does not exist or lacks the outputs
+    /// of one it reads.
+    InvalidSelection { node: String, message: String },
+    /// `Engine::run_as` names a tenant that is not registered.
+    UnknownTenant(String),
+    /// The audit log failed to record a node execution.
+    Audit(String),
+    /// The run started after `Engine::shutdown`.
+    ShuttingDown,
+}
+
+impl std::fmt::Display for EngineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineError::DuplicateNode(id) => write!(f, "Duplicate node id: {}", id),
+            EngineError::UnknownNode { edge, node } => write!(f, "Edge {} references unknown node: {}", edge, node),
+            EngineError::InvalidReference {
+                node,
+                expression,
+                message,
+            } => write!(f, "Node {} has invalid reference {}: {}", node, expression, message),
+            EngineError::UnknownReference {
+                node,
+                expression,
+                target,
+            } => write!(f, "Node {} reference {} names unknown node: {}", node, expression, target),
+            EngineError::MissingValue { node, expression } => {
+                write!(f, "Node {} template {} refers to a missing value", node, expression)
+            }
+            EngineError::UnknownNodeType { node, node_type } => {
+                write!(f, "Node {} has unknown type: {}", node, node_type)
+            }
+            EngineError::Cycle(nodes) => write!(f, "Cycle detected through nodes: {}", nodes.join(", ")),
+            EngineError::NodeFailed { node, message } => write!(f, "Node {} failed: {}", node, message),
+            EngineError::Cancelled => write!(f, "Run cancelled"),
+            EngineError::Checkpoint(message) => write!(f, "Cannot save checkpoint: {}", message),
+            EngineError::InvalidCheckpoint(message) => write!(f, "Cannot resume from checkpoint: {}", message),
+            EngineError::InvalidInput { input, message } => write!(f, "Invalid input {}: {}", input, message),
+            EngineError::InvalidOutput { output, message } => write!(f, "Invalid output {}: {}", output, message),
+            EngineError::Timeout(limit) => write!(f, "Run timed out after {}ms", limit.as_millis()),
+            EngineError::InvalidProfile { profile, message } => write!(f, "Invalid profile {}: {}", profile, message),
+            EngineError::InvalidSelection { node, message } => write!(f, "Cannot run only {}: {}", node, message),
+            EngineError::UnknownTenant(tenant) => write!(f, "Unknown tenant: {}", tenant),
+            EngineError::Audit(message) => write!(f, "Cannot write audit log: {}", message),
+            EngineError::ShuttingDown => write!(f, "Engine is shutting down"),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+/// Errors raised while loading a workflow definition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io { path: String, message: String },
+    /// The document is not valid JSON.
+    Syntax { line: usize, column: usize, message: String },
+    /// A `.yaml` or `.yml` spec is not valid YAML, or holds values JSON cannot.
+    Yaml(String),
+    /// A node entry has missing, unknown, or mistyped fields.
+    Node {
+        index: usize,
+        id: Option<String>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// An edge entry has missing, unknown, or mistyped fields.
+    Edge {
+        index: usize,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The graph is well-formed JSON but structurally invalid.
+    Invalid(EngineError),
+    /// An encrypted value could not be decrypted.
+    Decrypt(String),
+    /// An included fragment could not be read or expanded.
+    Include { path: String, message: String },
+    /// A node that uses a macro could not be expanded.
+    Macro { node: String, message: String },
+    /// The spec's format version is invalid, too new, or fails to migrate.
+    Version(String),
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io { path, message } => write!(f, "Cannot read {}: {}", path, message),
+            LoadError::Syntax { line, column, message } => {
+                write!(f, "Invalid JSON at line {} column {}: {}", line, column, message)
+            }
+            LoadError::Yaml(message) => write!(f, "Invalid YAML: {}", message),
+            LoadError::Node {
+                index,
+                id,
+                line,
+                column,
+                message,
+            } => match id {
+                Some(id) => write!(f, "Invalid node {} ({:?}) at line {} column {}: {}", index, id, line, column, message),
+                None => write!(f, "Invalid node {} at line {} column {}: {}", index, line, column, message),
+            },
+            LoadError::Edge {
+                index,
+                line,
+                column,
+                message,
+            } => write!(f, "Invalid edge {} at line {} column {}: {}", index, line, column, message),
+            LoadError::Invalid(e) => write!(f, "Invalid workflow: {}", e),
+            LoadError::Decrypt(message) => write!(f, "Cannot decrypt spec: {}", message),
+            LoadError::Include { path, message } => write!(f, "Cannot include {}: {}", path, message),
+            LoadError::Macro { node, message } => write!(f, "Cannot expand node {}: {}", node, message),
+            LoadError::Version(message) => write!(f, "Cannot migrate spec: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
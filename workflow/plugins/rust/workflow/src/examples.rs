@@ -0,0 +1,135 @@
+//! Executable examples of node types.
+//!
+//! An example pairs inputs with the outputs a node type gives for them. It
+//! documents the node for editors, and `Registry::self_test` runs every
+//! example against the registered executors, so the examples double as
+//! regression tests. An example checks only the outputs it lists, and
+//! numbers compare by value, so `3` matches `3.0`.
+
+use metabuilder_core::Runtime;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Inputs of a node type and the outputs it gives for them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub inputs: HashMap<String, Value>,
+    pub outputs: HashMap<String, Value>,
+}
+
+impl Example {
+    /// Creates an example from JSON objects of inputs and outputs.
+    ///
+    /// # Panics
+    ///
+    /// If `inputs` or `outputs` is not an object.
+    pub fn new(inputs: Value, outputs: Value) -> Self {
+        Example {
+            inputs: serde_json::from_value(inputs).expect("example inputs must be an object"),
+            outputs: serde_json::from_value(outputs).expect("example outputs must be an object"),
+        }
+    }
+}
+
+/// An example whose node type gave other outputs than expected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExampleFailure {
+    pub node_type: String,
+    /// Position of the example among those of the node type
+    pub index: usize,
+    pub output: String,
+    pub expected: Value,
+    /// `None` if the node type gave no such output or is not registered
+    pub actual: Option<Value>,
+}
+
+impl std::fmt::Display for ExampleFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} example {}: expected {} = {}, got ",
+            self.node_type, self.index, self.output, self.expected
+        )?;
+        match &self.actual {
+            Some(actual) => write!(f, "{}", actual),
+            None => f.write_str("nothing"),
+        }
+    }
+}
+
+/// Whether `actual` equals `expected`, comparing numbers by value.
+fn matches(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| matches(a, b)),
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len() && a.iter().all(|(key, a)| b.get(key).is_some_and(|b| matches(a, b)))
+        }
+        _ => expected == actual,
+    }
+}
+
+/// Runs `example` of `node_type` with a fresh runtime.
+pub(crate) fn run(
+    registry: &crate::registry::Registry,
+    node_type: &str,
+    index: usize,
+    example: &Example,
+) -> Vec<ExampleFailure> {
+    let failure = |output: &str, expected: &Value, actual: Option<&Value>| ExampleFailure {
+        node_type: node_type.to_string(),
+        index,
+        output: output.to_string(),
+        expected: expected.clone(),
+        actual: actual.cloned(),
+    };
+    let Some(executor) = registry.get(node_type) else {
+        return example.outputs.iter().map(|(output, expected)| failure(output, expected, None)).collect();
+    };
+    let runtime = Runtime::new();
+    let outputs = executor.execute(example.inputs.clone(), Some(&runtime as &dyn Any));
+    let mut failures: Vec<ExampleFailure> = example
+        .outputs
+        .iter()
+        .filter(|(output, expected)| !outputs.get(*output).is_some_and(|actual| matches(expected, actual)))
+        .map(|(output, expected)| failure(output, expected, outputs.get(output)))
+        .collect();
+    failures.sort_by(|a, b| a.output.cmp(&b.output));
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::Registry;
+    use serde_json::json;
+
+    fn double(inputs: HashMap<String, Value>, _: Option<&dyn Any>) -> HashMap<String, Value> {
+        let value = inputs.get("value").and_then(Value::as_f64).unwrap_or_default();
+        HashMap::from([("result".to_string(), json!(value * 2.0)), ("unit".to_string(), json!("x"))])
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches(&json!([1, {"a": 2}]), &json!([1.0, {"a": 2.0}])));
+        assert!(!matches(&json!({"a": 1}), &json!({"a": 1, "b": 2})));
+        assert!(!matches(&json!("1"), &json!(1)));
+    }
+
+    #[test]
+    fn test_self_test() {
+        let mut registry = Registry::new();
+        registry.register("math.double", double);
+        registry.add_example("math.double", Example::new(json!({"value": 2}), json!({"result": 4})));
+        assert_eq!(registry.self_test(), Ok(1));
+
+        registry.add_example("math.double", Example::new(json!({"value": 3}), json!({"result": 7, "unit": "x"})));
+        registry.add_example("math.triple", Example::new(json!({"value": 1}), json!({"result": 3})));
+        let failures = registry.self_test().unwrap_err();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].to_string(), "math.double example 1: expected result = 7, got 6.0");
+        assert_eq!(failures[1].to_string(), "math.triple example 0: expected result = 3, got nothing");
+        assert_eq!(registry.examples("math.double").len(), 2);
+    }
+}
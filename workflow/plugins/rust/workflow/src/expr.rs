@@ -0,0 +1,447 @@
+//! Reference expressions in node params.
+//!
+//! A param string starting with `$` is replaced by the value it references
+//! before the node runs:
+//!
+//! - `$vars.user_id` (or the short form `$user_id`) reads the var store
+//! - `$nodes.fetch.result.items[0].name` reads an upstream node's output port
+//!
+//! Names are letters, digits, `_`, and `-`, starting with a letter or `_`.
+//! Paths continue with `.key`, `[index]` (negative counts from the end), or
+//! `["quoted key"]`. Missing values resolve to `null`, like an unconnected
+//! edge, but a reference that does not parse, such as `$vars` or `$user id`,
+//! is an error. A `$` not followed by a letter or `_`, as in `"$100"` or a
+//! bcrypt hash, is literal text, and a leading `$$` escapes a dollar sign
+//! anywhere else.
+//!
+//! Other strings may interpolate the same paths without the `$`:
+//! `"Hello {{vars.name}}, you have {{nodes.count.result}} items"`. Strings
+//! are inserted as-is and other values as JSON; a string that is a single
+//! placeholder keeps the value's type. `\{{` produces a literal `{{`. Unlike
+//! `$` references, a placeholder whose value is missing is an error.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Where a reference starts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Root {
+    Vars,
+    Nodes,
+}
+
+/// Why a param value could not be resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    /// A `$` reference that does not parse: the expression and why.
+    Invalid { expression: String, message: String },
+    /// A `{{ }}` placeholder whose value is missing.
+    Missing(String),
+}
+
+/// One step of a reference path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Key(String),
+    Index(i64),
+}
+
+/// A parsed `$...` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub root: Root,
+    pub path: Vec<Segment>,
+}
+
+impl Reference {
+    /// Parses the text after the leading `$`.
+    pub fn parse(expr: &str) -> Result<Reference, String> {
+        let segments = parse_path(expr)?;
+        match segments.split_first() {
+            Some((Segment::Key(root), rest)) if root == "nodes" => match rest.first() {
+                Some(Segment::Key(_)) => Ok(Reference {
+                    root: Root::Nodes,
+                    path: rest.to_vec(),
+                }),
+                _ => Err("expected a node id after $nodes".to_string()),
+            },
+            Some((Segment::Key(root), rest)) if root == "vars" => match rest.first() {
+                Some(Segment::Key(_)) => Ok(Reference {
+                    root: Root::Vars,
+                    path: rest.to_vec(),
+                }),
+                _ => Err("expected a var name after $vars".to_string()),
+            },
+            Some((Segment::Key(_), _)) => Ok(Reference {
+                root: Root::Vars,
+                path: segments,
+            }),
+            _ => Err("expected a name after $".to_string()),
+        }
+    }
+
+    /// The upstream node this reference reads, if any.
+    pub fn node(&self) -> Option<&str> {
+        match (&self.root, self.path.first()) {
+            (Root::Nodes, Some(Segment::Key(id))) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Looks the reference up in node outputs or the var store; missing values are `null`.
+    pub fn resolve(&self, outputs: &HashMap<String, HashMap<String, Value>>, store: &HashMap<String, Value>) -> Value {
+        self.lookup(outputs, store).unwrap_or(Value::Null)
+    }
+
+    /// Looks the reference up, returning `None` when any step is missing.
+    pub fn lookup(&self, outputs: &HashMap<String, HashMap<String, Value>>, store: &HashMap<String, Value>) -> Option<Value> {
+        let start = match (&self.root, self.path.as_slice()) {
+            (Root::Nodes, [Segment::Key(id), Segment::Key(port), rest @ ..]) => {
+                outputs.get(id).and_then(|ports| ports.get(port)).map(|v| (v, rest))
+            }
+            // `$nodes.fetch` alone yields every output port of the node.
+            (Root::Nodes, [Segment::Key(id)]) => {
+                return outputs
+                    .get(id)
+                    .map(|ports| Value::Object(ports.clone().into_iter().collect()))
+            }
+            (Root::Vars, [Segment::Key(name), rest @ ..]) => store.get(name).map(|v| (v, rest)),
+            _ => None,
+        };
+        start
+            .and_then(|(value, rest)| rest.iter().try_fold(value, step))
+            .cloned()
+    }
+}
+
+fn step<'a>(value: &'a Value, segment: &Segment) -> Option<&'a Value> {
+    match (value, segment) {
+        (Value::Object(map), Segment::Key(key)) => map.get(key),
+        (Value::Array(items), Segment::Index(i)) => {
+            let index = if *i < 0 { items.len() as i64 + i } else { *i };
+            usize::try_from(index).ok().and_then(|i| items.get(i))
+        }
+        _ => None,
+    }
+}
+
+fn parse_path(expr: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut chars = expr.chars().peekable();
+    let mut expect_key = true;
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '.' if !expect_key => {
+                chars.next();
+                expect_key = true;
+            }
+            '[' => {
+                chars.next();
+                let segment = match chars.peek() {
+                    Some(&quote @ ('"' | '\'')) => {
+                        chars.next();
+                        let key: String = chars.by_ref().take_while(|&c| c != quote).collect();
+                        Segment::Key(key)
+                    }
+                    _ => {
+                        let mut digits = String::new();
+                        loop {
+                            match chars.next() {
+                                Some(']') => break,
+                                Some(c) => digits.push(c),
+                                None => return Err("unclosed [".to_string()),
+                            }
+                        }
+                        let index = digits
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("invalid index [{}]", digits))?;
+                        segments.push(Segment::Index(index));
+                        expect_key = false;
+                        continue;
+                    }
+                };
+                if chars.next() != Some(']') {
+                    return Err("unclosed [".to_string());
+                }
+                segments.push(segment);
+                expect_key = false;
+            }
+            _ if expect_key => {
+                let mut key = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '-') {
+                        break;
+                    }
+                    key.push(c);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return Err(format!("empty name in ${}", expr));
+                }
+                segments.push(Segment::Key(key));
+                expect_key = false;
+            }
+            _ => return Err(format!("unexpected '{}' in ${}", c, expr)),
+        }
+    }
+    if expect_key {
+        return Err(format!("incomplete path ${}", expr));
+    }
+    Ok(segments)
+}
+
+/// Whether the text after a leading `$` is meant as a reference: it starts
+/// like a name. Anything else, such as `$100` or `${HOME}`, is literal text.
+fn is_reference(expr: &str) -> bool {
+    expr.starts_with(|c: char| c.is_alphabetic() || c == '_')
+}
+
+/// A piece of an interpolated string.
+#[derive(Debug, Clone, PartialEq)]
+enum Part<'a> {
+    Text(String),
+    Placeholder(&'a str),
+}
+
+/// Splits `s` into text and `{{ path }}` placeholders.
+fn template_parts(s: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        if rest[..start].ends_with('\\') {
+            text.push_str(&rest[..start - 1]);
+            text.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        text.push_str(&rest[..start]);
+        let end = rest[start..].find("}}").ok_or_else(|| format!("unclosed {{{{ in \"{}\"", s))?;
+        if !text.is_empty() {
+            parts.push(Part::Text(std::mem::take(&mut text)));
+        }
+        parts.push(Part::Placeholder(rest[start + 2..start + end].trim()));
+        rest = &rest[start + end + 2..];
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    Ok(parts)
+}
+
+/// Calls `f` with the display form and parse result of every reference in `value`.
+fn visit(value: &Value, f: &mut impl FnMut(String, Result<Reference, String>)) {
+    match value {
+        Value::String(s) => match s.strip_prefix('$') {
+            Some(expr) if is_reference(expr) => f(s.clone(), Reference::parse(expr)),
+            Some(_) => {}
+            None if s.contains("{{") => match template_parts(s) {
+                Ok(parts) => {
+                    for part in parts {
+                        if let Part::Placeholder(expr) = part {
+                            f(format!("{{{{{}}}}}", expr), Reference::parse(expr));
+                        }
+                    }
+                }
+                Err(message) => f(s.clone(), Err(message)),
+            },
+            None => {}
+        },
+        Value::Array(items) => items.iter().for_each(|v| visit(v, f)),
+        Value::Object(map) => map.values().for_each(|v| visit(v, f)),
+        _ => {}
+    }
+}
+
+/// Parses every reference and placeholder in `params`, returning
+/// `(expression, reference)` pairs or the first `(expression, error)`.
+pub fn references(params: &HashMap<String, Value>) -> Result<Vec<(String, Reference)>, (String, String)> {
+    let mut found = Vec::new();
+    let mut error = None;
+    for value in params.values() {
+        visit(value, &mut |expression, parsed| match parsed {
+            Ok(reference) => found.push((expression, reference)),
+            Err(message) => {
+                error.get_or_insert((expression, message));
+            }
+        });
+    }
+    match error {
+        Some(error) => Err(error),
+        None => Ok(found),
+    }
+}
+
+/// Fills in the placeholders of a template string.
+fn interpolate(
+    s: &str,
+    outputs: &HashMap<String, HashMap<String, Value>>,
+    store: &HashMap<String, Value>,
+) -> Result<Value, ResolveError> {
+    let parts = template_parts(s).map_err(|message| ResolveError::Invalid {
+        expression: s.to_string(),
+        message,
+    })?;
+    let lookup = |expr: &str| {
+        Reference::parse(expr)
+            .ok()
+            .and_then(|r| r.lookup(outputs, store))
+            .ok_or_else(|| ResolveError::Missing(format!("{{{{{}}}}}", expr)))
+    };
+
+    if let [Part::Placeholder(expr)] = parts.as_slice() {
+        return lookup(expr);
+    }
+    let mut out = String::new();
+    for part in parts {
+        match part {
+            Part::Text(text) => out.push_str(&text),
+            Part::Placeholder(expr) => match lookup(expr)? {
+                Value::String(s) => out.push_str(&s),
+                other => out.push_str(&other.to_string()),
+            },
+        }
+    }
+    Ok(Value::String(out))
+}
+
+/// Replaces every reference and placeholder in `value`, recursing into lists
+/// and objects. Fails when a reference does not parse or a template value is
+/// missing.
+pub fn resolve(
+    value: &Value,
+    outputs: &HashMap<String, HashMap<String, Value>>,
+    store: &HashMap<String, Value>,
+) -> Result<Value, ResolveError> {
+    Ok(match value {
+        Value::String(s) => match s.strip_prefix('$') {
+            Some(escaped) if escaped.starts_with('$') => Value::String(escaped.to_string()),
+            Some(expr) if is_reference(expr) => match Reference::parse(expr) {
+                Ok(reference) => reference.resolve(outputs, store),
+                Err(message) => {
+                    return Err(ResolveError::Invalid {
+                        expression: s.clone(),
+                        message,
+                    })
+                }
+            },
+            Some(_) => value.clone(),
+            None if s.contains("{{") => interpolate(s, outputs, store)?,
+            None => value.clone(),
+        },
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|v| resolve(v, outputs, store))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| resolve(v, outputs, store).map(|v| (k.clone(), v)))
+                .collect::<Result<_, _>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn outputs() -> HashMap<String, HashMap<String, Value>> {
+        let mut ports = HashMap::new();
+        ports.insert("result".to_string(), json!({"items": [{"name": "first"}, {"name": "last"}]}));
+        let mut outputs = HashMap::new();
+        outputs.insert("fetch".to_string(), ports);
+        outputs
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Reference::parse("nodes.fetch.result.items[0][\"full name\"]").unwrap(),
+            Reference {
+                root: Root::Nodes,
+                path: vec![
+                    Segment::Key("fetch".to_string()),
+                    Segment::Key("result".to_string()),
+                    Segment::Key("items".to_string()),
+                    Segment::Index(0),
+                    Segment::Key("full name".to_string()),
+                ],
+            }
+        );
+        assert_eq!(Reference::parse("user_id").unwrap().root, Root::Vars);
+        assert!(Reference::parse("nodes").is_err());
+        assert!(Reference::parse("vars.").is_err());
+        assert!(Reference::parse("items[x]").is_err());
+        assert!(Reference::parse("items[0").is_err());
+        assert!(Reference::parse("user id").is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut store = HashMap::new();
+        store.insert("user_id".to_string(), json!(42));
+
+        let resolve = |value: Value| resolve(&value, &outputs(), &store).unwrap();
+        assert_eq!(resolve(json!("$nodes.fetch.result.items[0].name")), json!("first"));
+        assert_eq!(resolve(json!("$nodes.fetch.result.items[-1].name")), json!("last"));
+        assert_eq!(resolve(json!({"id": "$vars.user_id", "raw": ["$user_id"]})), json!({"id": 42, "raw": [42]}));
+        assert_eq!(resolve(json!("$nodes.fetch.result.missing")), Value::Null);
+        assert_eq!(resolve(json!("$$5.00")), json!("$5.00"));
+    }
+
+    #[test]
+    fn test_literal_dollars() {
+        let store = HashMap::from([("HOME".to_string(), json!("/root"))]);
+        let resolve = |value: Value| resolve(&value, &outputs(), &store);
+        let hash = "$2b$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW";
+        assert_eq!(resolve(json!(hash)), Ok(json!(hash)));
+        assert_eq!(resolve(json!(["$100", "$ 5", "${HOME}", "$"])), Ok(json!(["$100", "$ 5", "${HOME}", "$"])));
+        assert_eq!(resolve(json!("$HOME")), Ok(json!("/root")));
+        assert_eq!(
+            resolve(json!("$vars")),
+            Err(ResolveError::Invalid {
+                expression: "$vars".to_string(),
+                message: "expected a var name after $vars".to_string()
+            })
+        );
+        assert!(matches!(resolve(json!("$HOME dir")), Err(ResolveError::Invalid { .. })));
+
+        let params = HashMap::from([("hash".to_string(), json!([hash, "$100"]))]);
+        assert_eq!(references(&params).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_templates() {
+        let mut store = HashMap::new();
+        store.insert("name".to_string(), json!("Ada"));
+        store.insert("count".to_string(), json!(3));
+
+        let resolve = |value: Value| resolve(&value, &outputs(), &store);
+        assert_eq!(
+            resolve(json!("Hello {{vars.name}}, you have {{ count }} items from {{nodes.fetch.result.items[0].name}}")),
+            Ok(json!("Hello Ada, you have 3 items from first"))
+        );
+        assert_eq!(resolve(json!("{{vars.count}}")), Ok(json!(3)));
+        assert_eq!(resolve(json!("\\{{literal}} {{name}}")), Ok(json!("{{literal}} Ada")));
+        assert_eq!(resolve(json!("Hi {{vars.missing}}")), Err(ResolveError::Missing("{{vars.missing}}".to_string())));
+        assert!(template_parts("Hi {{name").is_err());
+    }
+
+    #[test]
+    fn test_references() {
+        let mut params = HashMap::new();
+        params.insert("a".to_string(), json!(["$nodes.fetch.result", "$$literal", "$vars.x", "n={{nodes.count.result}}"]));
+
+        let refs = references(&params).unwrap();
+        let nodes: Vec<_> = refs.iter().filter_map(|(_, r)| r.node()).collect();
+        assert_eq!(nodes, vec!["fetch", "count"]);
+        assert_eq!(refs[2].0, "{{nodes.count.result}}");
+    }
+}
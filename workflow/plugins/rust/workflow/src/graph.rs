@@ -0,0 +1,401 @@
+//! Workflow graph definitions.
+
+use crate::contract::{InputSpec, OutputSpec};
+use crate::error::EngineError;
+use crate::expr;
+use crate::profile::Profile;
+use metabuilder_core::canonical;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// A node instance in a workflow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Node {
+    /// Unique id within the workflow
+    pub id: String,
+    /// Registered node type, e.g. `"math.add"`
+    #[serde(rename = "type")]
+    pub node_type: String,
+    /// Static inputs; strings starting with `$` are reference expressions (see `expr`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, Value>,
+    /// Sub-graph run by engine-level nodes such as `control.while`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Workflow>,
+}
+
+impl Node {
+    /// Creates a node with no params.
+    pub fn new(id: impl Into<String>, node_type: impl Into<String>) -> Self {
+        Node {
+            id: id.into(),
+            node_type: node_type.into(),
+            params: HashMap::new(),
+            body: None,
+        }
+    }
+
+    /// Sets a static input.
+    pub fn param(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.params.insert(name.into(), value);
+        self
+    }
+
+    /// Sets the sub-graph of a `control.while` node.
+    pub fn body(mut self, body: Workflow) -> Self {
+        self.body = Some(body);
+        self
+    }
+}
+
+/// Connects an output port of one node to an input port of another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Edge {
+    pub from: String,
+    pub from_port: String,
+    pub to: String,
+    pub to_port: String,
+    /// Feeds the port one chunk at a time (see `Engine`'s streaming edges)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
+}
+
+impl Edge {
+    /// Creates an edge from `from.from_port` to `to.to_port`.
+    pub fn new(
+        from: impl Into<String>,
+        from_port: impl Into<String>,
+        to: impl Into<String>,
+        to_port: impl Into<String>,
+    ) -> Self {
+        Edge {
+            from: from.into(),
+            from_port: from_port.into(),
+            to: to.into(),
+            to_port: to_port.into(),
+            stream: false,
+        }
+    }
+
+    /// Makes this a streaming edge.
+    pub fn streaming(mut self) -> Self {
+        self.stream = true;
+        self
+    }
+}
+
+/// A directed graph of nodes wired by edges.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workflow {
+    /// Vars the workflow accepts from its caller (see `contract`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub inputs: BTreeMap<String, InputSpec>,
+    /// Values the workflow returns to its caller (see `contract`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub outputs: BTreeMap<String, OutputSpec>,
+    /// Vars seeded into every run, below those the caller passes
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, Value>,
+    /// Per-environment overlays selected with `Engine::with_profile` (see `profile`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Admission order in a `RunQueue`, higher first (see `queue`)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i32,
+    /// The `RunQueue` concurrency class runs count against, `"default"` if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    pub nodes: Vec<Node>,
+    #[serde(default)]
+    pub edges: Vec<Edge>,
+}
+
+impl Workflow {
+    /// Creates an empty workflow.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a node.
+    pub fn node(mut self, node: Node) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Adds an edge.
+    pub fn edge(mut self, edge: Edge) -> Self {
+        self.edges.push(edge);
+        self
+    }
+
+    /// Sets a var seeded into every run.
+    pub fn var(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.vars.insert(name.into(), value);
+        self
+    }
+
+    /// Sets the run queue priority; higher runs first.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the run queue concurrency class.
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    /// Declares an input.
+    pub fn input(mut self, name: impl Into<String>, spec: InputSpec) -> Self {
+        self.inputs.insert(name.into(), spec);
+        self
+    }
+
+    /// Declares an output.
+    pub fn output(mut self, name: impl Into<String>, spec: OutputSpec) -> Self {
+        self.outputs.insert(name.into(), spec);
+        self
+    }
+
+    /// Defines a profile.
+    pub fn profile(mut self, name: impl Into<String>, profile: Profile) -> Self {
+        self.profiles.insert(name.into(), profile);
+        self
+    }
+
+    /// Looks up a node by id.
+    pub fn get_node(&self, id: &str) -> Option<&Node> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// SHA-256 of the workflow's canonical JSON, in hex, which changes with
+    /// any edit to its nodes, edges, or declarations.
+    pub fn digest(&self) -> String {
+        let document = serde_json::to_value(self).expect("workflow values are always serializable");
+        let digest = Sha256::digest(canonical::to_string(&document).as_bytes());
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// The nodes in `ids`, in declaration order, with the edges between them
+    /// and the workflow's vars.
+    pub(crate) fn subgraph(&self, ids: &[&str]) -> Workflow {
+        let keep = |id: &String| ids.contains(&id.as_str());
+        Workflow {
+            vars: self.vars.clone(),
+            nodes: self.nodes.iter().filter(|n| keep(&n.id)).cloned().collect(),
+            edges: self.edges.iter().filter(|e| keep(&e.from) && keep(&e.to)).cloned().collect(),
+            ..Workflow::new()
+        }
+    }
+
+    /// Edges feeding into `id`.
+    pub fn incoming<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a Edge> + 'a {
+        self.edges.iter().filter(move |e| e.to == id)
+    }
+
+    /// Edges leaving `id`.
+    pub fn outgoing<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a Edge> + 'a {
+        self.edges.iter().filter(move |e| e.from == id)
+    }
+
+    /// For each node (by index), the indices of the nodes it depends on
+    /// through edges or `$nodes.*` param references.
+    ///
+    /// Also checks that ids are unique and that every edge and reference
+    /// names an existing node.
+    pub(crate) fn upstream(&self) -> Result<Vec<Vec<usize>>, EngineError> {
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if index.insert(node.id.as_str(), i).is_some() {
+                return Err(EngineError::DuplicateNode(node.id.clone()));
+            }
+        }
+
+        let mut upstream: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+        // Several ports between the same pair of nodes count as one dependency.
+        let mut depend = |from: usize, to: usize| {
+            if seen.insert((from, to)) {
+                upstream[to].push(from);
+            }
+        };
+
+        for edge in &self.edges {
+            let lookup = |id: &str| {
+                index.get(id).copied().ok_or_else(|| EngineError::UnknownNode {
+                    edge: format!("{}.{} -> {}.{}", edge.from, edge.from_port, edge.to, edge.to_port),
+                    node: id.to_string(),
+                })
+            };
+            let (from, to) = (lookup(&edge.from)?, lookup(&edge.to)?);
+            depend(from, to);
+        }
+
+        for (to, node) in self.nodes.iter().enumerate() {
+            let references = expr::references(&node.params).map_err(|(expression, message)| {
+                EngineError::InvalidReference {
+                    node: node.id.clone(),
+                    expression,
+                    message,
+                }
+            })?;
+            for (expression, reference) in references {
+                let Some(target) = reference.node() else { continue };
+                let from = index.get(target).copied().ok_or_else(|| EngineError::UnknownReference {
+                    node: node.id.clone(),
+                    expression: expression.clone(),
+                    target: target.to_string(),
+                })?;
+                depend(from, to);
+            }
+        }
+        Ok(upstream)
+    }
+
+    /// Node ids in dependency order (Kahn's algorithm, ties broken by declaration order).
+    ///
+    /// Also checks that ids are unique and that every edge and reference
+    /// names an existing node.
+    pub fn topological_order(&self) -> Result<Vec<&str>, EngineError> {
+        let upstream = self.upstream()?;
+        let mut in_degree: Vec<usize> = upstream.iter().map(Vec::len).collect();
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+        for (to, froms) in upstream.iter().enumerate() {
+            for &from in froms {
+                dependents[from].push(to);
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..self.nodes.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(i) = ready.pop_front() {
+            order.push(self.nodes[i].id.as_str());
+            for &next in &dependents[i] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if order.len() < self.nodes.len() {
+            let cyclic = (0..self.nodes.len())
+                .filter(|&i| in_degree[i] > 0)
+                .map(|i| self.nodes[i].id.clone())
+                .collect();
+            return Err(EngineError::Cycle(cyclic));
+        }
+        Ok(order)
+    }
+
+    /// Groups nodes into dependency levels: every node's upstream nodes are in
+    /// earlier levels, so nodes within a level can run concurrently. Each
+    /// level keeps declaration order.
+    pub fn topological_levels(&self) -> Result<Vec<Vec<&str>>, EngineError> {
+        let upstream = self.upstream()?;
+        let order = self.topological_order()?;
+        let position: HashMap<&str, usize> = self.nodes.iter().enumerate().map(|(i, n)| (n.id.as_str(), i)).collect();
+
+        let mut depth = vec![0usize; self.nodes.len()];
+        for id in order {
+            let i = position[id];
+            depth[i] = upstream[i].iter().map(|&from| depth[from] + 1).max().unwrap_or(0);
+        }
+
+        let mut levels: Vec<Vec<&str>> = vec![Vec::new(); depth.iter().max().map_or(0, |d| d + 1)];
+        for (i, node) in self.nodes.iter().enumerate() {
+            levels[depth[i]].push(node.id.as_str());
+        }
+        Ok(levels)
+    }
+}
+
+fn is_zero(n: &i32) -> bool {
+    *n == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_topological_order() {
+        let workflow = Workflow::new()
+            .node(Node::new("c", "math.add"))
+            .node(Node::new("a", "math.add"))
+            .node(Node::new("b", "math.add"))
+            .edge(Edge::new("a", "result", "b", "value"))
+            .edge(Edge::new("b", "result", "c", "value"));
+
+        assert_eq!(workflow.topological_order().unwrap(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_cycle_detected() {
+        let workflow = Workflow::new()
+            .node(Node::new("a", "math.add"))
+            .node(Node::new("b", "math.add"))
+            .edge(Edge::new("a", "result", "b", "value"))
+            .edge(Edge::new("b", "result", "a", "value"));
+
+        assert_eq!(
+            workflow.topological_order(),
+            Err(EngineError::Cycle(vec!["a".to_string(), "b".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_topological_levels() {
+        let workflow = Workflow::new()
+            .node(Node::new("join", "list.concat"))
+            .node(Node::new("left", "math.add"))
+            .node(Node::new("right", "math.add"))
+            .node(Node::new("source", "math.add"))
+            .edge(Edge::new("source", "result", "left", "value"))
+            .edge(Edge::new("source", "result", "right", "value"))
+            .edge(Edge::new("left", "result", "join", "a"))
+            .edge(Edge::new("right", "result", "join", "b"));
+
+        assert_eq!(
+            workflow.topological_levels().unwrap(),
+            vec![vec!["source"], vec!["left", "right"], vec!["join"]]
+        );
+    }
+
+    #[test]
+    fn test_reference_dependencies() {
+        let workflow = Workflow::new()
+            .node(Node::new("greet", "string.concat").param("strings", json!(["hi ", "$nodes.fetch.result.name"])))
+            .node(Node::new("fetch", "var.get"));
+
+        assert_eq!(workflow.topological_order().unwrap(), vec!["fetch", "greet"]);
+
+        let broken = Workflow::new().node(Node::new("greet", "string.upper").param("string", json!("$nodes.nope.result")));
+        assert_eq!(
+            broken.topological_order(),
+            Err(EngineError::UnknownReference {
+                node: "greet".to_string(),
+                expression: "$nodes.nope.result".to_string(),
+                target: "nope".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_edge_node() {
+        let workflow = Workflow::new()
+            .node(Node::new("a", "math.add"))
+            .edge(Edge::new("a", "result", "missing", "value"));
+
+        assert!(matches!(
+            workflow.topological_order(),
+            Err(EngineError::UnknownNode { node, .. }) if node == "missing"
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+//! Append-only log of node executions, for auditing runs.
+//!
+//! Attach a `RunHistory` with `Engine::with_history`; every node run by a
+//! top-level run is recorded, including the one that failed the run. Nodes
+//! inside loop bodies and called workflows are not recorded individually.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// How a node execution ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Succeeded,
+    /// The node failed and stopped the run.
+    Failed,
+    /// The node failed and its error was routed to `on_error` handlers.
+    Handled,
+}
+
+/// One node execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeRecord {
+    /// Run number within this history, counting from 1
+    pub run: u64,
+    pub node: String,
+    pub node_type: String,
+    pub status: Status,
+    /// The run id, node id, and count the engine gave this execution
+    #[serde(default)]
+    pub execution_id: String,
+    /// 1 plus the node's failures recorded since its last success, so a
+    /// node re-run by `Engine::resume` counts up
+    pub attempt: u32,
+    /// SHA-256 of the canonical JSON of the node's inputs, if they resolved
+    pub inputs_hash: Option<String>,
+    pub outputs: HashMap<String, Value>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Thread-safe, append-only list of `NodeRecord`s.
+#[derive(Debug, Default)]
+pub struct RunHistory {
+    runs: AtomicU64,
+    records: Mutex<Vec<NodeRecord>>,
+}
+
+impl RunHistory {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every record, oldest first.
+    pub fn nodes(&self) -> Vec<NodeRecord> {
+        self.lock().clone()
+    }
+
+    /// Records of failed nodes, including handled failures, oldest first.
+    pub fn failures(&self) -> Vec<NodeRecord> {
+        self.lock().iter().filter(|r| r.status != Status::Succeeded).cloned().collect()
+    }
+
+    /// Serializes every record as a JSON array.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&*self.lock()).expect("records are always serializable")
+    }
+
+    /// Starts a run, returning its number.
+    pub(crate) fn begin_run(&self) -> u64 {
+        self.runs.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// The attempt number of the next execution of `node`.
+    pub(crate) fn attempt(&self, node: &str) -> u32 {
+        let failures = self
+            .lock()
+            .iter()
+            .rev()
+            .filter(|r| r.node == node)
+            .take_while(|r| r.status == Status::Failed)
+            .count();
+        failures as u32 + 1
+    }
+
+    pub(crate) fn push(&self, record: NodeRecord) {
+        self.lock().push(record);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<NodeRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,229 @@
+//! Localized engine messages and node descriptions.
+//!
+//! A `Locale` renders `EngineError`s and node descriptions from Fluent
+//! messages (see `locales/en.ftl` for the keys and their arguments). English
+//! is built in; hosts load other languages from `.ftl` sources with
+//! `Locale::from_ftl`, and anything a translation leaves out falls back to
+//! English. An engine renders with the locale given to
+//! `Engine::with_locale`, so one process can serve editors in several
+//! languages from separate engines.
+//!
+//! Messages that nodes report (the `message` of `NodeFailed`) come from the
+//! plugins and are passed through as they are.
+
+use crate::error::EngineError;
+use crate::registry::Registry;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+/// The built-in English messages.
+const ENGLISH: &str = include_str!("../locales/en.ftl");
+
+/// Messages for one language, falling back to English.
+pub struct Locale {
+    tag: String,
+    bundle: FluentBundle<FluentResource>,
+    /// English, unless this is the English locale
+    fallback: Option<Box<Locale>>,
+}
+
+impl std::fmt::Debug for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Locale").field("tag", &self.tag).finish()
+    }
+}
+
+fn bundle(tag: &str, source: &str) -> Result<FluentBundle<FluentResource>, String> {
+    let langid: LanguageIdentifier = tag.parse().map_err(|_| format!("invalid language tag {}", tag))?;
+    let resource = FluentResource::try_new(source.to_string())
+        .map_err(|(_, errors)| format!("invalid Fluent source for {}: {:?}", tag, errors))?;
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Placeables hold node ids and paths, not prose, so they need no
+    // bidirectional isolation marks.
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .map_err(|errors| format!("invalid Fluent source for {}: {:?}", tag, errors))?;
+    Ok(bundle)
+}
+
+impl Locale {
+    /// The built-in English messages.
+    pub fn english() -> Self {
+        Locale {
+            tag: "en".to_string(),
+            bundle: bundle("en", ENGLISH).expect("the English messages are valid"),
+            fallback: None,
+        }
+    }
+
+    /// Messages for the language `tag` (such as `"de"` or `"pt-BR"`) from a
+    /// Fluent `source`, falling back to English for keys it lacks.
+    pub fn from_ftl(tag: &str, source: &str) -> Result<Self, String> {
+        Ok(Locale {
+            tag: tag.to_string(),
+            bundle: bundle(tag, source)?,
+            fallback: Some(Box::new(Locale::english())),
+        })
+    }
+
+    /// The language tag of the locale.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Formats message `key` with `args`, or `None` if neither this locale
+    /// nor English has it.
+    pub fn message(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        let pattern = self.bundle.get_message(key).and_then(|message| message.value());
+        let Some(pattern) = pattern else {
+            return self.fallback.as_ref().and_then(|english| english.message(key, args));
+        };
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, *value);
+        }
+        let mut errors = Vec::new();
+        Some(self.bundle.format_pattern(pattern, Some(&fluent_args), &mut errors).into_owned())
+    }
+
+    /// The description of `node_type` in this language, or the English one
+    /// the registry holds.
+    pub fn describe(&self, registry: &Registry, node_type: &str) -> Option<String> {
+        let key = format!("node-{}", node_type.replace('.', "-"));
+        self.message(&key, &[]).or_else(|| registry.description(node_type).map(String::from))
+    }
+
+    /// Renders `error` in this language.
+    pub fn error(&self, error: &EngineError) -> String {
+        let (key, args): (&str, Vec<(&str, String)>) = match error {
+            EngineError::DuplicateNode(id) => ("error-duplicate-node", vec![("id", id.clone())]),
+            EngineError::UnknownNode { edge, node } => {
+                ("error-unknown-node", vec![("edge", edge.clone()), ("node", node.clone())])
+            }
+            EngineError::InvalidReference {
+                node,
+                expression,
+                message,
+            } => (
+                "error-invalid-reference",
+                vec![("node", node.clone()), ("expression", expression.clone()), ("message", message.clone())],
+            ),
+            EngineError::UnknownReference {
+                node,
+                expression,
+                target,
+            } => (
+                "error-unknown-reference",
+                vec![("node", node.clone()), ("expression", expression.clone()), ("target", target.clone())],
+            ),
+            EngineError::MissingValue { node, expression } => {
+                ("error-missing-value", vec![("node", node.clone()), ("expression", expression.clone())])
+            }
+            EngineError::UnknownNodeType { node, node_type } => {
+                ("error-unknown-node-type", vec![("node", node.clone()), ("type", node_type.clone())])
+            }
+            EngineError::Cycle(nodes) => ("error-cycle", vec![("nodes", nodes.join(", "))]),
+            EngineError::NodeFailed { node, message } => {
+                ("error-node-failed", vec![("node", node.clone()), ("message", message.clone())])
+            }
+            EngineError::Cancelled => ("error-cancelled", vec![]),
+            EngineError::Checkpoint(message) => ("error-checkpoint", vec![("message", message.clone())]),
+            EngineError::InvalidCheckpoint(message) => ("error-invalid-checkpoint", vec![("message", message.clone())]),
+            EngineError::InvalidInput { input, message } => {
+                ("error-invalid-input", vec![("input", input.clone()), ("message", message.clone())])
+            }
+            EngineError::InvalidOutput { output, message } => {
+                ("error-invalid-output", vec![("output", output.clone()), ("message", message.clone())])
+            }
+            EngineError::Timeout(limit) => ("error-timeout", vec![("ms", limit.as_millis().to_string())]),
+            EngineError::InvalidProfile { profile, message } => {
+                ("error-invalid-profile", vec![("profile", profile.clone()), ("message", message.clone())])
+            }
+            EngineError::InvalidSelection { node, message } => {
+                ("error-invalid-selection", vec![("node", node.clone()), ("message", message.clone())])
+            }
+            EngineError::UnknownTenant(tenant) => ("error-unknown-tenant", vec![("tenant", tenant.clone())]),
+            EngineError::Audit(message) => ("error-audit", vec![("message", message.clone())]),
+            EngineError::ShuttingDown => ("error-shutting-down", vec![]),
+        };
+        let args: Vec<(&str, &str)> = args.iter().map(|(name, value)| (*name, value.as_str())).collect();
+        self.message(key, &args).unwrap_or_else(|| error.to_string())
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn errors() -> Vec<EngineError> {
+        let s = |text: &str| text.to_string();
+        vec![
+            EngineError::DuplicateNode(s("a")),
+            EngineError::UnknownNode { edge: s("e1"), node: s("b") },
+            EngineError::InvalidReference { node: s("a"), expression: s("$x."), message: s("bad") },
+            EngineError::UnknownReference { node: s("a"), expression: s("$b.out"), target: s("b") },
+            EngineError::MissingValue { node: s("a"), expression: s("{{ vars.x }}") },
+            EngineError::UnknownNodeType { node: s("a"), node_type: s("math.nope") },
+            EngineError::Cycle(vec![s("a"), s("b")]),
+            EngineError::NodeFailed { node: s("a"), message: s("boom") },
+            EngineError::Cancelled,
+            EngineError::Checkpoint(s("disk full")),
+            EngineError::InvalidCheckpoint(s("workflow changed")),
+            EngineError::InvalidInput { input: s("n"), message: s("not a number") },
+            EngineError::InvalidOutput { output: s("r"), message: s("missing") },
+            EngineError::Timeout(Duration::from_millis(1500)),
+            EngineError::InvalidProfile { profile: s("dev"), message: s("not defined") },
+            EngineError::InvalidSelection { node: s("a"), message: s("no outputs") },
+            EngineError::UnknownTenant(s("acme")),
+            EngineError::Audit(s("locked")),
+            EngineError::ShuttingDown,
+        ]
+    }
+
+    #[test]
+    fn test_english_matches_display() {
+        let english = Locale::english();
+        for error in errors() {
+            assert_eq!(english.error(&error), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_translation_with_fallback() {
+        let german = Locale::from_ftl(
+            "de",
+            "error-node-failed = Knoten { $node } ist fehlgeschlagen: { $message }\n\
+             node-math-add = Zahlen addieren\n",
+        )
+        .unwrap();
+        assert_eq!(german.tag(), "de");
+        let failed = EngineError::NodeFailed {
+            node: "sum".to_string(),
+            message: "boom".to_string(),
+        };
+        assert_eq!(german.error(&failed), "Knoten sum ist fehlgeschlagen: boom");
+        assert_eq!(german.error(&EngineError::Cancelled), "Run cancelled");
+
+        let mut registry = Registry::new();
+        registry.describe("math.add", "Add numbers");
+        registry.describe("math.subtract", "Subtract numbers");
+        assert_eq!(german.describe(&registry, "math.add").as_deref(), Some("Zahlen addieren"));
+        assert_eq!(german.describe(&registry, "math.subtract").as_deref(), Some("Subtract numbers"));
+        assert_eq!(german.describe(&registry, "math.nope"), None);
+    }
+
+    #[test]
+    fn test_invalid_sources() {
+        assert!(Locale::from_ftl("de", "error-cancelled = {").unwrap_err().starts_with("invalid Fluent source for de"));
+        assert_eq!(Locale::from_ftl("not a tag!", "").unwrap_err(), "invalid language tag not a tag!");
+    }
+}
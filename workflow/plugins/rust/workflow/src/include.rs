@@ -0,0 +1,244 @@
+//! Shared spec fragments.
+//!
+//! A spec's `include` list pulls in fragment files, each holding any of
+//! `nodes`, `edges`, `vars`, `inputs`, `outputs`, `profiles`, and `macros`,
+//! plus nested `include`s and a format `version` (see `migrate`). Fragments are parameterized: `${name}`
+//! placeholders in their strings are replaced by the `params` of the include,
+//! falling back to the defaults the fragment declares in its own `params`:
+//!
+//! ```json
+//! "include": [
+//!   "fragments/notify.json",
+//!   { "path": "fragments/fetch.json", "params": { "prefix": "orders_", "url": "https://api.example.com/orders" } }
+//! ]
+//! ```
+//!
+//! A string that is a single placeholder takes the param's value as is;
+//! otherwise the value is interpolated as text. Ids can be parameterized the
+//! same way (`"id": "${prefix}fetch"`) so a fragment can be included twice.
+//!
+//! Fragment nodes and edges come before the including spec's own. For the
+//! other blocks, the including spec wins when both define the same name.
+//! Paths are relative to the including file.
+
+use crate::error::LoadError;
+use crate::migrate;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+const MERGED_LISTS: [&str; 2] = ["nodes", "edges"];
+const MERGED_MAPS: [&str; 5] = ["vars", "inputs", "outputs", "profiles", "macros"];
+
+/// Includes nested deeper than this are rejected.
+const MAX_DEPTH: usize = 16;
+
+/// Expands the `include` list of `document`, resolving paths against `base`.
+/// Returns `None` when the document includes nothing.
+pub(crate) fn expand(document: &Value, base: &Path) -> Result<Option<Value>, LoadError> {
+    if document.get("include").is_none() {
+        return Ok(None);
+    }
+    let mut document = document.clone();
+    expand_into(&mut document, base, &mut Vec::new())?;
+    Ok(Some(document))
+}
+
+/// Replaces the `include` list of `document` with the fragments it names.
+/// `stack` holds the files being expanded, to reject cycles.
+fn expand_into(document: &mut Value, base: &Path, stack: &mut Vec<PathBuf>) -> Result<(), LoadError> {
+    let Some(map) = document.as_object_mut() else {
+        return Ok(());
+    };
+    let includes = match map.remove("include") {
+        None => return Ok(()),
+        Some(Value::Array(includes)) => includes,
+        Some(_) => return Err(error(base, "include must be a list")),
+    };
+    for include in includes.into_iter().rev() {
+        let (path, params) = match include {
+            Value::String(path) => (path, Map::new()),
+            Value::Object(mut entry) => match entry.remove("path") {
+                Some(Value::String(path)) => match entry.remove("params") {
+                    None => (path, Map::new()),
+                    Some(Value::Object(params)) => (path, params),
+                    Some(_) => return Err(error(Path::new(&path), "params must be an object")),
+                },
+                _ => return Err(error(base, "include entries need a path")),
+            },
+            _ => return Err(error(base, "include entries must be paths or objects")),
+        };
+        let path = base.join(path);
+        let fragment = load(&path, params, stack)?;
+        merge(map, fragment);
+    }
+    Ok(())
+}
+
+/// Reads the fragment at `path`, substitutes `params`, and expands its own
+/// includes.
+fn load(path: &Path, params: Map<String, Value>, stack: &mut Vec<PathBuf>) -> Result<Map<String, Value>, LoadError> {
+    let canonical = path.canonicalize().map_err(|e| error(path, &e.to_string()))?;
+    if stack.contains(&canonical) {
+        return Err(error(path, "includes itself"));
+    }
+    if stack.len() >= MAX_DEPTH {
+        return Err(error(path, &format!("includes nest deeper than {}", MAX_DEPTH)));
+    }
+    let text = std::fs::read_to_string(path).map_err(|e| error(path, &e.to_string()))?;
+    let document: Value = serde_json::from_str(&text).map_err(|e| error(path, &e.to_string()))?;
+    let upgraded = migrate::upgrade(&document).map_err(|e| error(path, &e.to_string()))?;
+    let Value::Object(mut fragment) = upgraded.unwrap_or(document) else {
+        return Err(error(path, "a fragment must be a JSON object"));
+    };
+    fragment.remove("version");
+    if let Some(key) = fragment
+        .keys()
+        .find(|key| !["include", "params"].contains(&key.as_str()) && !MERGED_LISTS.contains(&key.as_str()) && !MERGED_MAPS.contains(&key.as_str()))
+    {
+        return Err(error(path, &format!("unknown field {}", key)));
+    }
+
+    let mut values = match fragment.remove("params") {
+        None => Map::new(),
+        Some(Value::Object(defaults)) => defaults,
+        Some(_) => return Err(error(path, "params must be an object")),
+    };
+    values.extend(params);
+    let mut fragment = Value::Object(fragment);
+    substitute(&mut fragment, &values).map_err(|message| error(path, &message))?;
+
+    stack.push(canonical);
+    let base = path.parent().unwrap_or(Path::new(""));
+    expand_into(&mut fragment, base, stack)?;
+    stack.pop();
+    match fragment {
+        Value::Object(fragment) => Ok(fragment),
+        _ => unreachable!("fragments are objects"),
+    }
+}
+
+/// Merges `fragment` into `document`: lists are prepended, and map entries
+/// are added unless `document` already has them.
+fn merge(document: &mut Map<String, Value>, fragment: Map<String, Value>) {
+    for (key, value) in fragment {
+        match (document.get_mut(&key), value) {
+            (Some(Value::Array(own)), Value::Array(mut items)) if MERGED_LISTS.contains(&key.as_str()) => {
+                items.append(own);
+                *own = items;
+            }
+            (Some(Value::Object(own)), Value::Object(entries)) => {
+                for (name, entry) in entries {
+                    own.entry(name).or_insert(entry);
+                }
+            }
+            (Some(_), _) => {}
+            (None, value) => {
+                document.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Replaces `${name}` placeholders in every string under `value`.
+pub(crate) fn substitute(value: &mut Value, params: &Map<String, Value>) -> Result<(), String> {
+    match value {
+        Value::String(text) if text.contains("${") => *value = interpolate(text, params)?,
+        Value::Array(items) => items.iter_mut().try_for_each(|item| substitute(item, params))?,
+        Value::Object(map) => map.values_mut().try_for_each(|item| substitute(item, params))?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate(text: &str, params: &Map<String, Value>) -> Result<Value, String> {
+    let lookup = |name: &str| params.get(name).ok_or_else(|| format!("param {} is not set", name));
+    if let Some(name) = text.strip_prefix("${").and_then(|rest| rest.strip_suffix('}')) {
+        if !name.contains(['{', '}']) {
+            return lookup(name).cloned();
+        }
+    }
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..].find('}').ok_or_else(|| format!("unterminated placeholder in {:?}", text))? + start;
+        out.push_str(&rest[..start]);
+        match lookup(&rest[start + 2..end])? {
+            Value::String(s) => out.push_str(s),
+            other => out.push_str(&other.to_string()),
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(Value::String(out))
+}
+
+fn error(path: &Path, message: &str) -> LoadError {
+    LoadError::Include {
+        path: path.display().to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(dir: &Path, name: &str, value: Value) {
+        std::fs::write(dir.join(name), value.to_string()).unwrap();
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("workflow_include_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(dir.join("fragments")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_expand() {
+        let dir = temp_dir("expand");
+        write(
+            &dir,
+            "fragments/fetch.json",
+            json!({
+                "params": {"retries": 3},
+                "include": ["log.json"],
+                "vars": {"timeout": 30, "region": "eu"},
+                "nodes": [{"id": "${prefix}fetch", "type": "http.request",
+                           "params": {"url": "${url}", "retries": "${retries}", "label": "fetch ${prefix} x${retries}"}}]
+            }),
+        );
+        write(&dir, "fragments/log.json", json!({"nodes": [{"id": "log", "type": "log.info"}]}));
+        let document = json!({
+            "include": [{"path": "fragments/fetch.json", "params": {"prefix": "orders_", "url": "https://x.io"}}],
+            "vars": {"region": "us"},
+            "nodes": [{"id": "main", "type": "math.add"}]
+        });
+
+        let expanded = expand(&document, &dir).unwrap().unwrap();
+        let ids: Vec<&str> = expanded["nodes"].as_array().unwrap().iter().map(|n| n["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["log", "orders_fetch", "main"]);
+        assert_eq!(
+            expanded["nodes"][1]["params"],
+            json!({"url": "https://x.io", "retries": 3, "label": "fetch orders_ x3"})
+        );
+        assert_eq!(expanded["vars"], json!({"region": "us", "timeout": 30}));
+        assert!(expanded.get("include").is_none());
+        assert_eq!(expand(&json!({"nodes": []}), &dir).unwrap(), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let dir = temp_dir("errors");
+        write(&dir, "fragments/a.json", json!({"include": ["b.json"]}));
+        write(&dir, "fragments/b.json", json!({"include": ["a.json"]}));
+        write(&dir, "fragments/param.json", json!({"nodes": [{"id": "${missing}"}]}));
+        let message = |include: Value| expand(&json!({"include": [include]}), &dir).unwrap_err().to_string();
+
+        assert!(message(json!("fragments/a.json")).ends_with("a.json: includes itself"));
+        assert!(message(json!("fragments/param.json")).ends_with("param.json: param missing is not set"));
+        assert!(message(json!("fragments/none.json")).starts_with("Cannot include"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+//! MetaBuilder workflow engine.
+//!
+//! Defines the `Workflow` graph (nodes wired by port-to-port edges) and an
+//! `Engine` that runs it against a `Registry` of node executors, passing a
+//! shared `Runtime` with the var store and secrets to every node.
+
+pub mod audit;
+mod builtins;
+pub mod cassette;
+pub mod checkpoint;
+pub mod contract;
+pub mod distributed;
+pub mod encryption;
+pub mod engine;
+pub mod error;
+pub mod examples;
+pub mod expr;
+pub mod graph;
+pub mod history;
+pub mod i18n;
+mod include;
+pub mod limits;
+pub mod loader;
+mod macros;
+pub mod migrate;
+pub mod plan;
+pub mod profile;
+pub mod queue;
+pub mod redact;
+pub mod registry;
+pub mod reload;
+pub mod runs;
+pub mod schema;
+pub mod search;
+pub mod shutdown;
+pub mod tenant;
+pub mod typings;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
+pub mod validate;
+#[cfg(feature = "webhooks")]
+pub mod webhook;
+
+pub use audit::{AuditEntry, AuditLog, AuditQuery};
+pub use cassette::{Cassette, CassetteMode, Interaction};
+pub use checkpoint::{Checkpoint, CheckpointStore, FileCheckpoints, MemoryCheckpoints};
+pub use contract::{InputSpec, OutputSpec};
+#[cfg(feature = "redis")]
+pub use distributed::RedisTransport;
+pub use distributed::{MemoryTransport, Task, Transport, Worker};
+pub use engine::{Engine, RunMetadata, RunResult};
+pub use error::{EngineError, LoadError};
+pub use examples::{Example, ExampleFailure};
+pub use graph::{Edge, Node, Workflow};
+pub use history::{NodeRecord, RunHistory, Status};
+pub use i18n::Locale;
+pub use limits::QueuePolicy;
+pub use plan::Plan;
+pub use profile::Profile;
+pub use queue::{QueueStatus, RunQueue};
+pub use redact::Redaction;
+pub use registry::Registry;
+pub use reload::{LiveWorkflow, WatchHandle};
+pub use runs::{RunRecord, RunStatus, RunStore};
+#[cfg(feature = "scheduler")]
+pub use scheduler::{JobStatus, Overlap, Schedule, Scheduler, SchedulerHandle};
+pub use schema::{NodeSchema, PortKind};
+pub use search::SearchResult;
+pub use shutdown::ShutdownReport;
+pub use tenant::Tenant;
+pub use validate::Diagnostic;
+#[cfg(feature = "webhooks")]
+pub use webhook::WebhookServer;
@@ -0,0 +1,112 @@
+//! Per-category concurrency limits.
+//!
+//! A limit caps how many nodes of one category (the node type up to its
+//! first `.`, such as `http`) run at once across every run of an engine.
+//! It only comes into play when nodes run in parallel.
+
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// What a node does when its category is at its limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueuePolicy {
+    /// Wait for a slot, up to the run timeout (the default).
+    #[default]
+    Wait,
+    /// Fail the node at once, which its `on_error` port can handle.
+    Fail,
+}
+
+/// Why a node could not get a slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Refused {
+    /// The category was full and the policy is `Fail`.
+    Full,
+    /// The run deadline passed while waiting.
+    TimedOut,
+}
+
+/// A counting semaphore for one category.
+pub(crate) struct Limiter {
+    max: usize,
+    in_flight: Mutex<usize>,
+    freed: Condvar,
+}
+
+/// A slot held while a node runs, released on drop.
+pub(crate) struct Permit<'a> {
+    limiter: &'a Limiter,
+}
+
+/// A granted slot, how long the node queued for it (if it had to), and how
+/// many nodes of the category were running once it was granted.
+pub(crate) type Slot<'a> = (Permit<'a>, Option<Duration>, usize);
+
+impl Limiter {
+    pub(crate) fn new(max: usize) -> Self {
+        Limiter {
+            max: max.max(1),
+            in_flight: Mutex::new(0),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Takes a slot, queueing according to `policy` until `deadline`.
+    pub(crate) fn acquire(&self, policy: QueuePolicy, deadline: Option<Instant>) -> Result<Slot<'_>, Refused> {
+        let started = Instant::now();
+        let mut queued = false;
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        while *in_flight >= self.max {
+            queued = true;
+            if policy == QueuePolicy::Fail {
+                return Err(Refused::Full);
+            }
+            in_flight = match deadline {
+                Some(deadline) => {
+                    let left = deadline.checked_duration_since(Instant::now()).ok_or(Refused::TimedOut)?;
+                    self.freed.wait_timeout(in_flight, left).unwrap_or_else(|e| e.into_inner()).0
+                }
+                None => self.freed.wait(in_flight).unwrap_or_else(|e| e.into_inner()),
+            };
+        }
+        *in_flight += 1;
+        let waited = queued.then(|| started.elapsed());
+        Ok((Permit { limiter: self }, waited, *in_flight))
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.limiter.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        *in_flight -= 1;
+        self.limiter.freed.notify_one();
+    }
+}
+
+/// The category of a node type: everything before the first `.`.
+pub(crate) fn category(node_type: &str) -> &str {
+    node_type.split('.').next().unwrap_or(node_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limiter_policies() {
+        let limiter = Limiter::new(1);
+        let (permit, waited, in_flight) = limiter.acquire(QueuePolicy::Wait, None).unwrap();
+        assert_eq!((waited, in_flight), (None, 1));
+        assert_eq!(limiter.acquire(QueuePolicy::Fail, None).err(), Some(Refused::Full));
+        let soon = Instant::now() + Duration::from_millis(20);
+        assert_eq!(limiter.acquire(QueuePolicy::Wait, Some(soon)).err(), Some(Refused::TimedOut));
+        drop(permit);
+        assert!(limiter.acquire(QueuePolicy::Fail, None).is_ok());
+    }
+
+    #[test]
+    fn test_category() {
+        assert_eq!(category("http.graphql"), "http");
+        assert_eq!(category("custom"), "custom");
+    }
+}
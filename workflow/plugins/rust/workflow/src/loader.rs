@@ -0,0 +1,308 @@
+//! JSON workflow definition loader.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "nodes": [
+//!     { "id": "sum", "type": "math.add", "params": { "numbers": [1, 2] } },
+//!     { "id": "round", "type": "math.round" }
+//!   ],
+//!   "edges": [
+//!     { "from": "sum", "from_port": "result", "to": "round", "to_port": "value" }
+//!   ]
+//! }
+//! ```
+
+use crate::encryption;
+use crate::error::{EngineError, LoadError};
+use crate::graph::{Edge, Node, Workflow};
+use crate::include;
+use crate::macros;
+use crate::migrate;
+use metabuilder_core::{MemorySecrets, SecretsProvider};
+use serde_json::Value;
+use std::path::Path;
+
+impl Workflow {
+    /// Parses a workflow from JSON text.
+    pub fn from_json(text: &str) -> Result<Workflow, LoadError> {
+        Self::from_json_with_secrets(text, &MemorySecrets::new())
+    }
+
+    /// Parses a workflow from JSON text, decrypting encrypted values with
+    /// the key from `secrets` (see `encryption`). Included fragments are
+    /// resolved against the current directory.
+    pub fn from_json_with_secrets(text: &str, secrets: &dyn SecretsProvider) -> Result<Workflow, LoadError> {
+        parse(text, Path::new(""), secrets)
+    }
+
+    /// Reads and parses a workflow file: JSON, or YAML if its extension is
+    /// `.yaml` or `.yml`.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Workflow, LoadError> {
+        Self::from_path_with_secrets(path, &MemorySecrets::new())
+    }
+
+    /// Reads and parses a workflow file like `from_path`, decrypting
+    /// encrypted values with the key from `secrets`. A YAML spec is converted
+    /// to JSON first, and errors past YAML parsing give positions in the
+    /// pretty-printed JSON.
+    pub fn from_path_with_secrets(path: impl AsRef<Path>, secrets: &dyn SecretsProvider) -> Result<Workflow, LoadError> {
+        let path = path.as_ref();
+        let mut text = std::fs::read_to_string(path).map_err(|e| LoadError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        if matches!(path.extension().and_then(|e| e.to_str()), Some("yaml" | "yml")) {
+            let document = metabuilder_core::yaml::parse(&text).map_err(LoadError::Yaml)?;
+            text = serde_json::to_string_pretty(&document).expect("JSON values are always serializable");
+        }
+        parse(&text, path.parent().unwrap_or(Path::new("")), secrets)
+    }
+
+    /// Serializes the workflow back to pretty-printed JSON, stamped with the
+    /// current format version.
+    pub fn to_json(&self) -> String {
+        let mut document = serde_json::to_value(self).expect("workflow values are always serializable");
+        document["version"] = Value::from(migrate::FORMAT_VERSION);
+        serde_json::to_string_pretty(&document).expect("JSON values are always serializable")
+    }
+}
+
+/// Parses `text`, upgrading it to the current format (see `migrate`), then
+/// expanding its includes (see `include`) against `base` and its macros (see
+/// `macros`).
+fn parse(text: &str, base: &Path, secrets: &dyn SecretsProvider) -> Result<Workflow, LoadError> {
+    let parsed: Value = serde_json::from_str(text).map_err(|e| locate(text, e))?;
+    let upgraded = migrate::upgrade(&parsed)?;
+    let document = upgraded.as_ref().unwrap_or(&parsed);
+    let included = include::expand(document, base)?;
+    let expanded = match macros::expand(included.as_ref().unwrap_or(document))? {
+        Some(expanded) => Some(expanded),
+        None => included.or(upgraded),
+    };
+    let workflow: Workflow = match expanded {
+        // Errors in the expanded spec are located in its pretty-printed form.
+        Some(expanded) => {
+            let expanded = serde_json::to_string_pretty(&expanded).expect("JSON values are always serializable");
+            serde_json::from_str(&expanded).map_err(|e| locate(&expanded, e))?
+        }
+        None => serde_json::from_str(text).map_err(|e| locate(text, e))?,
+    };
+    let workflow = encryption::decrypt_workflow(workflow, text, secrets)?;
+    check_structure(&workflow)?;
+    Ok(workflow)
+}
+
+/// Turns a serde error into a `LoadError`, naming the node or edge at fault.
+fn locate(text: &str, err: serde_json::Error) -> LoadError {
+    let (line, column) = (err.line(), err.column());
+    if err.is_syntax() || err.is_eof() {
+        return LoadError::Syntax {
+            line,
+            column,
+            message: strip_position(&err),
+        };
+    }
+
+    // The document is valid JSON, so re-check each element to find the culprit.
+    let document: Value = serde_json::from_str(text).unwrap_or(Value::Null);
+    let elements = |key: &str| document.get(key).and_then(Value::as_array).cloned().unwrap_or_default();
+
+    for (index, node) in elements("nodes").iter().enumerate() {
+        if let Err(e) = serde_json::from_value::<Node>(node.clone()) {
+            return LoadError::Node {
+                index,
+                id: node.get("id").and_then(Value::as_str).map(String::from),
+                line,
+                column,
+                message: strip_position(&e),
+            };
+        }
+    }
+    for (index, edge) in elements("edges").iter().enumerate() {
+        if let Err(e) = serde_json::from_value::<Edge>(edge.clone()) {
+            return LoadError::Edge {
+                index,
+                line,
+                column,
+                message: strip_position(&e),
+            };
+        }
+    }
+
+    LoadError::Syntax {
+        line,
+        column,
+        message: strip_position(&err),
+    }
+}
+
+/// serde_json appends " at line X column Y"; positions are reported separately.
+fn strip_position(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    match message.rfind(" at line ") {
+        Some(i) => message[..i].to_string(),
+        None => message,
+    }
+}
+
+/// Rejects duplicate ids, edges to missing nodes, and profiles overriding
+/// missing nodes. Cycles are left to the engine.
+fn check_structure(workflow: &Workflow) -> Result<(), LoadError> {
+    match workflow.topological_order() {
+        Err(EngineError::Cycle(_)) | Ok(_) => workflow.check_profiles().map_err(LoadError::Invalid),
+        Err(e) => Err(LoadError::Invalid(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_load() {
+        let text = r#"{
+            "nodes": [
+                {"id": "sum", "type": "math.add", "params": {"numbers": [1, 2]}},
+                {"id": "round", "type": "math.round"}
+            ],
+            "edges": [{"from": "sum", "from_port": "result", "to": "round", "to_port": "value"}]
+        }"#;
+
+        let workflow = Workflow::from_json(text).unwrap();
+        assert_eq!(workflow.nodes[0].params["numbers"], json!([1, 2]));
+        assert_eq!(workflow.edges[0], Edge::new("sum", "result", "round", "value"));
+        assert_eq!(Workflow::from_json(&workflow.to_json()).unwrap(), workflow);
+        assert!(workflow.to_json().contains(&format!("\"version\": {}", migrate::FORMAT_VERSION)));
+    }
+
+    #[test]
+    fn test_load_yaml() {
+        let dir = std::env::temp_dir().join(format!("metabuilder-loader-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("spec.yaml");
+        std::fs::write(
+            &path,
+            "nodes:\n  - {id: sum, type: math.add, params: {numbers: [1, 2]}}\n  - {id: round, type: math.round}\n\
+             edges:\n  - {from: sum, from_port: result, to: round, to_port: value}\n",
+        )
+        .unwrap();
+        let workflow = Workflow::from_path(&path).unwrap();
+        assert_eq!(workflow.nodes[0].params["numbers"], json!([1, 2]));
+        assert_eq!(workflow.edges[0], Edge::new("sum", "result", "round", "value"));
+
+        std::fs::write(&path, "nodes: [\n").unwrap();
+        assert!(matches!(Workflow::from_path(&path), Err(LoadError::Yaml(_))));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let text = format!(r#"{{"version": {}, "nodes": []}}"#, migrate::FORMAT_VERSION + 1);
+        assert!(matches!(Workflow::from_json(&text), Err(LoadError::Version(_))));
+    }
+
+    #[test]
+    fn test_load_loop_body() {
+        let text = r#"{
+            "nodes": [{
+                "id": "loop", "type": "control.while",
+                "params": {"condition": "more", "max_iterations": 5},
+                "body": {"nodes": [{"id": "more", "type": "logic.lt", "params": {"a": "$vars.state", "b": 3}}]}
+            }]
+        }"#;
+
+        let workflow = Workflow::from_json(text).unwrap();
+        let body = workflow.nodes[0].body.as_ref().unwrap();
+        assert_eq!(body.nodes[0].id, "more");
+        assert_eq!(Workflow::from_json(&workflow.to_json()).unwrap(), workflow);
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let text = "{\n  \"nodes\": [\n    {\"id\": \"a\",}\n  ]\n}";
+
+        match Workflow::from_json(text).unwrap_err() {
+            LoadError::Syntax { line, .. } => assert_eq!(line, 3),
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_node_error_names_node() {
+        let text = "{\n  \"nodes\": [\n    {\"id\": \"ok\", \"type\": \"math.add\"},\n    {\"id\": \"bad\", \"tpye\": \"math.add\"}\n  ]\n}";
+
+        let err = Workflow::from_json(text).unwrap_err();
+        match &err {
+            LoadError::Node { index, id, line, .. } => {
+                assert_eq!(*index, 1);
+                assert_eq!(id.as_deref(), Some("bad"));
+                assert_eq!(*line, 4);
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(err.to_string().starts_with("Invalid node 1 (\"bad\") at line 4"));
+    }
+
+    #[test]
+    fn test_edge_to_missing_node() {
+        let text = r#"{"nodes": [{"id": "a", "type": "math.add"}],
+                       "edges": [{"from": "a", "from_port": "result", "to": "b", "to_port": "value"}]}"#;
+
+        assert!(matches!(
+            Workflow::from_json(text),
+            Err(LoadError::Invalid(EngineError::UnknownNode { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_load_includes() {
+        let dir = std::env::temp_dir().join(format!("workflow_loader_include_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let fragment = json!({"params": {"n": 1}, "nodes": [{"id": "${id}", "type": "math.add", "params": {"numbers": ["${n}", 1]}}]});
+        std::fs::write(dir.join("add.json"), fragment.to_string()).unwrap();
+        let spec = json!({
+            "include": [{"path": "add.json", "params": {"id": "first"}}, {"path": "add.json", "params": {"id": "second", "n": 5}}],
+            "nodes": [{"id": "round", "type": "math.round"}],
+            "edges": [{"from": "second", "from_port": "result", "to": "round", "to_port": "value"}]
+        });
+        std::fs::write(dir.join("spec.json"), spec.to_string()).unwrap();
+
+        let workflow = Workflow::from_path(dir.join("spec.json")).unwrap();
+        let ids: Vec<&str> = workflow.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, ["first", "second", "round"]);
+        assert_eq!(workflow.nodes[1].params["numbers"], json!([5, 1]));
+
+        let unset = json!({"include": ["add.json"]}).to_string();
+        std::fs::write(dir.join("unset.json"), unset).unwrap();
+        assert!(matches!(
+            Workflow::from_path(dir.join("unset.json")),
+            Err(LoadError::Include { .. })
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_encrypted() {
+        let key = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+        let password = encryption::encrypt(&json!("hunter2"), key).unwrap();
+        let text = json!({
+            "encryption": {"key_secret": "spec_key"},
+            "nodes": [{"id": "login", "type": "http.request", "params": {"auth": {"user": "ada", "password": password}}}]
+        })
+        .to_string();
+
+        let mut secrets = MemorySecrets::new();
+        secrets.insert("spec_key", key);
+        let workflow = Workflow::from_json_with_secrets(&text, &secrets).unwrap();
+        assert_eq!(workflow.nodes[0].params["auth"], json!({"user": "ada", "password": "hunter2"}));
+
+        assert_eq!(Workflow::from_json(&text).unwrap_err().to_string(), "Cannot decrypt spec: secret spec_key is not set");
+        secrets.insert("spec_key", "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=");
+        assert_eq!(
+            Workflow::from_json_with_secrets(&text, &secrets).unwrap_err().to_string(),
+            "Cannot decrypt spec: nodes[0].params.auth.password: wrong key or tampered value"
+        );
+    }
+}
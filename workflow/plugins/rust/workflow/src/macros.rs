@@ -0,0 +1,243 @@
+//! Node group macros.
+//!
+//! A spec's `macros` block defines small reusable node groups. A node with
+//! `use` instead of `type` expands to a copy of the group when the spec is
+//! loaded, with `${name}` placeholders filled from its `params` (falling
+//! back to the macro's `params` defaults):
+//!
+//! ```json
+//! "macros": {
+//!   "fetch_json": {
+//!     "params": { "method": "GET" },
+//!     "nodes": [
+//!       { "id": "get", "type": "http.request", "params": { "url": "${url}", "method": "${method}" } },
+//!       { "id": "parse", "type": "convert.parse_json", "params": { "string": "$nodes.get.body" } }
+//!     ]
+//!   }
+//! },
+//! "nodes": [ { "id": "orders", "use": "fetch_json", "params": { "url": "https://api.example.com/orders" } } ]
+//! ```
+//!
+//! The group's `output` node (the last by default) takes the id of the `use`
+//! node, so `$nodes.orders.result` reads it; the others are renamed to
+//! `orders_get` and so on, with edges and `$nodes` references inside the
+//! group renamed to match. Edges into the `use` node go to the group's
+//! `input` node (the first by default). Macros cannot use other macros.
+
+use crate::error::LoadError;
+use crate::include;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Expands the `use` nodes of `document` with its `macros`. Returns `None`
+/// when the document defines no macros.
+pub(crate) fn expand(document: &Value) -> Result<Option<Value>, LoadError> {
+    let uses_macro = |node: &Value| node.get("use").is_some();
+    let nodes = document.get("nodes").and_then(Value::as_array);
+    if document.get("macros").is_none() && !nodes.is_some_and(|nodes| nodes.iter().any(uses_macro)) {
+        return Ok(None);
+    }
+    let mut document = document.clone();
+    let map = document.as_object_mut().expect("a document with nodes is an object");
+    let macros = match map.remove("macros") {
+        None => Map::new(),
+        Some(Value::Object(macros)) => macros,
+        Some(_) => return Err(error("macros", "macros must be an object")),
+    };
+
+    let mut expanded = Vec::new();
+    let mut redirects = HashMap::new();
+    let mut edges = Vec::new();
+    for node in map.remove("nodes").and_then(|nodes| nodes.as_array().cloned()).unwrap_or_default() {
+        if !uses_macro(&node) {
+            expanded.push(node);
+            continue;
+        }
+        let group = instantiate(&node, &macros)?;
+        if let Some(input) = group.input {
+            redirects.insert(group.id, input);
+        }
+        expanded.extend(group.nodes);
+        edges.extend(group.edges);
+    }
+
+    let own_edges = map.remove("edges").and_then(|edges| edges.as_array().cloned()).unwrap_or_default();
+    for mut edge in own_edges {
+        if let Some(input) = edge.get("to").and_then(Value::as_str).and_then(|to| redirects.get(to)) {
+            edge["to"] = Value::from(input.as_str());
+        }
+        edges.push(edge);
+    }
+    map.insert("nodes".to_string(), Value::Array(expanded));
+    map.insert("edges".to_string(), Value::Array(edges));
+    Ok(Some(document))
+}
+
+/// One expanded `use` node.
+struct Group {
+    id: String,
+    nodes: Vec<Value>,
+    edges: Vec<Value>,
+    /// The renamed input node, if it is not the output node
+    input: Option<String>,
+}
+
+fn instantiate(node: &Value, macros: &Map<String, Value>) -> Result<Group, LoadError> {
+    let id = node.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+    let fail = |message: &str| error(&id, message);
+    if let Some(key) = node.as_object().into_iter().flat_map(|node| node.keys()).find(|key| !["id", "use", "params"].contains(&key.as_str())) {
+        return Err(fail(&format!("unknown field {} next to use", key)));
+    }
+    let name = node["use"].as_str().ok_or_else(|| fail("use must be a macro name"))?;
+    let definition = macros.get(name).ok_or_else(|| fail(&format!("unknown macro {}", name)))?;
+
+    let mut values = match definition.get("params") {
+        None => Map::new(),
+        Some(Value::Object(defaults)) => defaults.clone(),
+        Some(_) => return Err(fail(&format!("params of macro {} must be an object", name))),
+    };
+    match node.get("params") {
+        None => {}
+        Some(Value::Object(params)) => values.extend(params.clone()),
+        Some(_) => return Err(fail("params must be an object")),
+    }
+    let list = |key: &str| definition.get(key).and_then(Value::as_array).cloned().unwrap_or_default();
+    let (mut nodes, mut edges) = (list("nodes"), list("edges"));
+    let ids: Vec<String> = nodes.iter().filter_map(|n| n.get("id").and_then(Value::as_str)).map(String::from).collect();
+    if ids.is_empty() || ids.len() != nodes.len() {
+        return Err(fail(&format!("macro {} needs nodes with ids", name)));
+    }
+    if nodes.iter().any(|n| n.get("use").is_some()) {
+        return Err(fail("macros cannot use other macros"));
+    }
+    let endpoint = |key: &str, default: &String| match definition.get(key) {
+        None => Ok(default.clone()),
+        Some(Value::String(local)) if ids.contains(local) => Ok(local.clone()),
+        Some(other) => Err(fail(&format!("{} of macro {} is not one of its nodes: {}", key, name, other))),
+    };
+    let input = endpoint("input", &ids[0])?;
+    let output = endpoint("output", &ids[ids.len() - 1])?;
+
+    let renames: HashMap<String, String> = ids
+        .iter()
+        .map(|local| {
+            let renamed = if *local == output { id.clone() } else { format!("{}_{}", id, local) };
+            (local.clone(), renamed)
+        })
+        .collect();
+    for node in &mut nodes {
+        include::substitute(node, &values).map_err(|message| fail(&message))?;
+        let local = node["id"].as_str().unwrap_or_default().to_string();
+        node["id"] = Value::from(renames[&local].as_str());
+        if let Some(params) = node.get_mut("params") {
+            rename_references(params, &renames);
+        }
+    }
+    for edge in &mut edges {
+        include::substitute(edge, &values).map_err(|message| fail(&message))?;
+        for end in ["from", "to"] {
+            if let Some(renamed) = edge.get(end).and_then(Value::as_str).and_then(|local| renames.get(local)) {
+                edge[end] = Value::from(renamed.as_str());
+            }
+        }
+    }
+    let input = (input != output).then(|| renames[&input].clone());
+    Ok(Group { id, nodes, edges, input })
+}
+
+/// Renames node ids in `$nodes.<id>` references and `{{nodes.<id>}}`
+/// placeholders in every string under `value`.
+fn rename_references(value: &mut Value, renames: &HashMap<String, String>) {
+    match value {
+        Value::String(text) if text.contains("nodes.") => *text = rename_in(text, renames),
+        Value::Array(items) => items.iter_mut().for_each(|item| rename_references(item, renames)),
+        Value::Object(map) => map.values_mut().for_each(|item| rename_references(item, renames)),
+        _ => {}
+    }
+}
+
+fn rename_in(text: &str, renames: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("nodes.") {
+        let at_boundary = rest[..start].chars().next_back().is_none_or(|c| matches!(c, '$' | '{' | ' '));
+        let after = &rest[start + "nodes.".len()..];
+        let len = after.find(|c: char| matches!(c, '.' | '[' | '}') || c.is_whitespace()).unwrap_or(after.len());
+        out.push_str(&rest[..start + "nodes.".len()]);
+        match renames.get(&after[..len]).filter(|_| at_boundary) {
+            Some(renamed) => out.push_str(renamed),
+            None => out.push_str(&after[..len]),
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn error(node: &str, message: &str) -> LoadError {
+    LoadError::Macro {
+        node: node.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(nodes: Value) -> Value {
+        json!({
+            "macros": {
+                "fetch_json": {
+                    "params": {"method": "GET"},
+                    "nodes": [
+                        {"id": "get", "type": "http.request", "params": {"url": "${url}", "method": "${method}"}},
+                        {"id": "parse", "type": "convert.parse_json", "params": {"string": "$nodes.get.body", "label": "{{nodes.get.status}} of ${url}"}}
+                    ]
+                }
+            },
+            "nodes": nodes,
+            "edges": [{"from": "start", "from_port": "result", "to": "orders", "to_port": "headers"}]
+        })
+    }
+
+    #[test]
+    fn test_expand() {
+        let document = spec(json!([
+            {"id": "start", "type": "var.get"},
+            {"id": "orders", "use": "fetch_json", "params": {"url": "https://x.io"}}
+        ]));
+
+        let expanded = expand(&document).unwrap().unwrap();
+        assert_eq!(
+            expanded["nodes"],
+            json!([
+                {"id": "start", "type": "var.get"},
+                {"id": "orders_get", "type": "http.request", "params": {"url": "https://x.io", "method": "GET"}},
+                {"id": "orders", "type": "convert.parse_json",
+                 "params": {"string": "$nodes.orders_get.body", "label": "{{nodes.orders_get.status}} of https://x.io"}}
+            ])
+        );
+        assert_eq!(expanded["edges"][0]["to"], json!("orders_get"));
+        assert!(expanded.get("macros").is_none());
+        assert_eq!(expand(&json!({"nodes": []})).unwrap(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let message = |node: Value| expand(&spec(json!([node]))).unwrap_err().to_string();
+        assert_eq!(
+            message(json!({"id": "a", "use": "fetch_xml"})),
+            "Cannot expand node a: unknown macro fetch_xml"
+        );
+        assert_eq!(
+            message(json!({"id": "a", "use": "fetch_json"})),
+            "Cannot expand node a: param url is not set"
+        );
+        assert_eq!(
+            message(json!({"id": "a", "use": "fetch_json", "type": "x"})),
+            "Cannot expand node a: unknown field type next to use"
+        );
+    }
+}
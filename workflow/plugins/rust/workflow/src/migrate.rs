@@ -0,0 +1,114 @@
+//! Spec format versions.
+//!
+//! A spec may state the format it was written for with a top-level
+//! `"version"`; specs without one are version 1, and `Workflow::to_json`
+//! stamps the current `FORMAT_VERSION`. When the format changes, the version
+//! goes up and a migration rewriting the previous version's JSON is appended
+//! to `MIGRATIONS`, so the loader upgrades older specs (and included
+//! fragments) step by step before reading them. Specs newer than this
+//! loader are rejected rather than misread.
+
+use crate::error::LoadError;
+use serde_json::{Map, Value};
+
+/// Rewrites a spec of one format version into the next.
+type Migration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+/// `MIGRATIONS[i]` upgrades version `i + 1` to version `i + 2`.
+const MIGRATIONS: &[Migration] = &[];
+
+/// The format version this loader reads and `Workflow::to_json` writes.
+pub const FORMAT_VERSION: u64 = MIGRATIONS.len() as u64 + 1;
+
+/// Upgrades `document` to `FORMAT_VERSION`. Returns `None` when it is
+/// already current.
+pub(crate) fn upgrade(document: &Value) -> Result<Option<Value>, LoadError> {
+    upgrade_with(document, MIGRATIONS)
+}
+
+fn upgrade_with(document: &Value, migrations: &[Migration]) -> Result<Option<Value>, LoadError> {
+    let current = migrations.len() as u64 + 1;
+    let version = match document.get("version") {
+        None => 1,
+        Some(version) => match version.as_u64() {
+            Some(version) if version >= 1 => version,
+            _ => return Err(LoadError::Version(format!("version must be a positive integer, got {}", version))),
+        },
+    };
+    if version == current {
+        return Ok(None);
+    }
+    if version > current {
+        return Err(LoadError::Version(format!(
+            "format version {} is newer than this loader supports ({})",
+            version, current
+        )));
+    }
+    let mut document = document.clone();
+    let map = document
+        .as_object_mut()
+        .ok_or_else(|| LoadError::Version("a spec must be a JSON object".to_string()))?;
+    for (from, migrate) in (version..).zip(&migrations[version as usize - 1..]) {
+        migrate(map).map_err(|message| LoadError::Version(format!("from version {}: {}", from, message)))?;
+    }
+    map.insert("version".to_string(), Value::from(current));
+    Ok(Some(document))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Version 2 renamed edge ports from `out`/`in` to `from_port`/`to_port`.
+    fn rename_ports(spec: &mut Map<String, Value>) -> Result<(), String> {
+        for edge in spec.get_mut("edges").and_then(Value::as_array_mut).into_iter().flatten() {
+            let edge = edge.as_object_mut().ok_or("edges must be objects")?;
+            for (old, new) in [("out", "from_port"), ("in", "to_port")] {
+                if let Some(port) = edge.remove(old) {
+                    edge.insert(new.to_string(), port);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Version 3 moved `globals` to `vars`.
+    fn rename_globals(spec: &mut Map<String, Value>) -> Result<(), String> {
+        if let Some(globals) = spec.remove("globals") {
+            spec.insert("vars".to_string(), globals);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade() {
+        let migrations: &[Migration] = &[rename_ports, rename_globals];
+        let v1 = json!({"globals": {"n": 1}, "edges": [{"from": "a", "out": "result", "to": "b", "in": "value"}]});
+        assert_eq!(
+            upgrade_with(&v1, migrations).unwrap(),
+            Some(json!({
+                "version": 3,
+                "vars": {"n": 1},
+                "edges": [{"from": "a", "from_port": "result", "to": "b", "to_port": "value"}]
+            }))
+        );
+        let v2 = json!({"version": 2, "globals": {}, "edges": [{"from": "a", "out": "x"}]});
+        assert_eq!(upgrade_with(&v2, migrations).unwrap().unwrap()["edges"][0]["out"], json!("x"));
+        assert_eq!(upgrade_with(&json!({"version": 3}), migrations).unwrap(), None);
+    }
+
+    #[test]
+    fn test_unsupported_versions() {
+        let message = |document: Value| upgrade(&document).unwrap_err().to_string();
+        assert_eq!(upgrade(&json!({"nodes": []})).unwrap(), None);
+        assert_eq!(
+            message(json!({"version": FORMAT_VERSION + 1})),
+            format!("Cannot migrate spec: format version {} is newer than this loader supports ({})", FORMAT_VERSION + 1, FORMAT_VERSION)
+        );
+        assert_eq!(
+            message(json!({"version": "2"})),
+            "Cannot migrate spec: version must be a positive integer, got \"2\""
+        );
+    }
+}
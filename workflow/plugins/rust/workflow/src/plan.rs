@@ -0,0 +1,76 @@
+//! Workflows compiled ahead of time for repeated runs.
+//!
+//! `Engine::compile` does once what every run of a spec would otherwise
+//! redo: it applies the engine's profile and inlines the profile's vars,
+//! validates the graph against the registry, and works out the order (and,
+//! for parallel engines, the levels) nodes run in. It also folds constants:
+//! nodes the registry marks deterministic whose params hold no references
+//! are run at compile time, and their outputs are stored in the plan.
+//!
+//! `Engine::run_plan` then starts from the plan directly, skipping parsing,
+//! migration, macro expansion, and validation. Plans serialize to JSON, so a
+//! build step can compile specs and ship the plans instead. A plan is tied
+//! to the profile it was compiled for, and its folded nodes do not appear in
+//! run history.
+
+use crate::error::LoadError;
+use crate::graph::Workflow;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Version of the plan format, checked when a plan is loaded.
+pub const PLAN_VERSION: u64 = 1;
+
+/// A validated workflow with its execution order worked out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    pub(crate) version: u64,
+    /// The profile applied at compile time, if any
+    pub(crate) profile: Option<String>,
+    pub(crate) workflow: Workflow,
+    /// Node ids in the order a sequential engine runs them
+    pub(crate) order: Vec<String>,
+    /// Node ids in the levels a parallel engine runs them in
+    pub(crate) levels: Vec<Vec<String>>,
+    /// Outputs of the nodes folded at compile time, by node id
+    pub(crate) folded: BTreeMap<String, HashMap<String, Value>>,
+}
+
+impl Plan {
+    /// The workflow the plan runs, with its profile applied.
+    pub fn workflow(&self) -> &Workflow {
+        &self.workflow
+    }
+
+    /// The profile the plan was compiled for, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Ids of the nodes folded at compile time.
+    pub fn folded(&self) -> impl Iterator<Item = &str> {
+        self.folded.keys().map(String::as_str)
+    }
+
+    /// Serializes the plan as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("plans are always serializable")
+    }
+
+    /// Loads a plan serialized by `to_json`.
+    pub fn from_json(text: &str) -> Result<Plan, LoadError> {
+        let plan: Plan = serde_json::from_str(text).map_err(|e| LoadError::Syntax {
+            line: e.line(),
+            column: e.column(),
+            message: e.to_string(),
+        })?;
+        if plan.version != PLAN_VERSION {
+            return Err(LoadError::Version(format!(
+                "plan version {} is not {}; compile the spec again",
+                plan.version, PLAN_VERSION
+            )));
+        }
+        Ok(plan)
+    }
+}
@@ -0,0 +1,152 @@
+//! Environment profiles.
+//!
+//! A profile overlays a workflow for one environment: it replaces node
+//! params by node id and supplies vars, so one spec can run against
+//! development and production endpoints alike:
+//!
+//! ```json
+//! "profiles": {
+//!   "dev": { "vars": { "region": "local" }, "nodes": { "fetch": { "url": "http://localhost:8080" } } },
+//!   "prod": { "vars": { "region": "eu-west-1" } }
+//! }
+//! ```
+//!
+//! Profile vars fill in what the caller does not pass, ahead of input
+//! defaults; vars passed to `Engine::run` still win.
+
+use crate::error::EngineError;
+use crate::graph::Workflow;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+/// Overrides applied when a workflow runs under a profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Vars seeded into the run
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, Value>,
+    /// Params replaced per node id, one param at a time
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub nodes: BTreeMap<String, HashMap<String, Value>>,
+}
+
+impl Profile {
+    /// Creates an empty profile.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a var.
+    pub fn var(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.vars.insert(name.into(), value);
+        self
+    }
+
+    /// Replaces param `name` of node `node`.
+    pub fn param(mut self, node: impl Into<String>, name: impl Into<String>, value: Value) -> Self {
+        self.nodes.entry(node.into()).or_default().insert(name.into(), value);
+        self
+    }
+}
+
+impl Workflow {
+    /// The workflow with profile `name` applied, for callers that want to
+    /// inspect or save the overlaid spec. A workflow without profiles is
+    /// returned unchanged.
+    pub fn for_profile(&self, name: &str) -> Result<Workflow, EngineError> {
+        Ok(apply(self, name)?.into_owned())
+    }
+
+    /// Checks that every profile overrides only nodes that exist.
+    pub(crate) fn check_profiles(&self) -> Result<(), EngineError> {
+        for (name, profile) in &self.profiles {
+            if let Some(node) = profile.nodes.keys().find(|id| self.get_node(id).is_none()) {
+                return Err(invalid(name, format!("overrides unknown node: {}", node)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Applies profile `name` to `workflow`. Workflows that define no profiles
+/// run as they are, so one engine can select a profile for all its runs.
+pub(crate) fn apply<'a>(workflow: &'a Workflow, name: &str) -> Result<Cow<'a, Workflow>, EngineError> {
+    if workflow.profiles.is_empty() {
+        return Ok(Cow::Borrowed(workflow));
+    }
+    let profile = workflow
+        .profiles
+        .get(name)
+        .ok_or_else(|| invalid(name, "not defined by the workflow".to_string()))?;
+    workflow.check_profiles()?;
+    let mut overlaid = workflow.clone();
+    for node in &mut overlaid.nodes {
+        if let Some(params) = profile.nodes.get(&node.id) {
+            node.params.extend(params.clone());
+        }
+    }
+    Ok(Cow::Owned(overlaid))
+}
+
+/// Seeds `vars` with the vars of profile `name` that the caller left out.
+pub(crate) fn seed_vars(workflow: &Workflow, name: &str, mut vars: HashMap<String, Value>) -> HashMap<String, Value> {
+    if let Some(profile) = workflow.profiles.get(name) {
+        for (var, value) in &profile.vars {
+            vars.entry(var.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    vars
+}
+
+fn invalid(profile: &str, message: String) -> EngineError {
+    EngineError::InvalidProfile {
+        profile: profile.to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use serde_json::json;
+
+    fn workflow() -> Workflow {
+        Workflow::new()
+            .node(Node::new("fetch", "http.request").param("url", json!("https://api.example.com")).param("retries", json!(3)))
+            .profile("dev", Profile::new().var("region", json!("local")).param("fetch", "url", json!("http://localhost")))
+    }
+
+    #[test]
+    fn test_apply() {
+        let workflow = workflow();
+        let dev = workflow.for_profile("dev").unwrap();
+        assert_eq!(dev.nodes[0].params["url"], json!("http://localhost"));
+        assert_eq!(dev.nodes[0].params["retries"], json!(3));
+        assert_eq!(
+            workflow.for_profile("prod").unwrap_err().to_string(),
+            "Invalid profile prod: not defined by the workflow"
+        );
+        assert_eq!(Workflow::new().for_profile("prod").unwrap(), Workflow::new());
+    }
+
+    #[test]
+    fn test_seed_vars() {
+        let vars = seed_vars(&workflow(), "dev", HashMap::new());
+        assert_eq!(vars, HashMap::from([("region".to_string(), json!("local"))]));
+        let vars = seed_vars(&workflow(), "dev", HashMap::from([("region".to_string(), json!("eu"))]));
+        assert_eq!(vars["region"], json!("eu"));
+    }
+
+    #[test]
+    fn test_unknown_node() {
+        let workflow = workflow().profile("qa", Profile::new().param("fecth", "url", json!("x")));
+        assert_eq!(
+            workflow.check_profiles().unwrap_err().to_string(),
+            "Invalid profile qa: overrides unknown node: fecth"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Admission of top-level runs by priority and concurrency class.
+//!
+//! An engine given a `RunQueue` with `Engine::with_run_queue` holds each
+//! top-level run until the queue admits it. A workflow declares its
+//! `priority` (higher first, 0 by default) and its concurrency `class` (such
+//! as `"batch"`, `"default"` when unset). The queue runs at most `max_runs`
+//! at once overall and at most the class limit of each class, and whenever a
+//! slot frees up it goes to the waiting run with the highest priority, the
+//! earliest among equals. Urgent runs thus overtake queued batch runs, though
+//! runs already going are never interrupted, and a class at its limit only
+//! holds back its own runs, so a flood of one kind cannot starve the others.
+
+use crate::error::EngineError;
+use metabuilder_core::Cancellation;
+use std::collections::HashMap;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// The class of workflows that do not declare one.
+pub const DEFAULT_CLASS: &str = "default";
+
+/// How often waiting runs check for cancellation.
+const POLL: Duration = Duration::from_millis(50);
+
+/// Limits on how many runs go at once, overall and per class.
+#[derive(Debug)]
+pub struct RunQueue {
+    max_runs: usize,
+    class_limits: HashMap<String, usize>,
+    state: Mutex<QueueState>,
+    freed: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    running: HashMap<String, usize>,
+    /// Waiting runs as (priority, ticket, class), tickets counting up
+    waiting: Vec<(i32, u64, String)>,
+    next_ticket: u64,
+}
+
+impl QueueState {
+    fn total(&self) -> usize {
+        self.running.values().sum()
+    }
+}
+
+/// How many runs are going and waiting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueueStatus {
+    /// Runs going, by class
+    pub running: HashMap<String, usize>,
+    /// Runs waiting, by class
+    pub waiting: HashMap<String, usize>,
+}
+
+/// A run's slot, released on drop.
+pub(crate) struct Admission<'a> {
+    queue: &'a RunQueue,
+    class: String,
+}
+
+impl RunQueue {
+    /// A queue running at most `max_runs` runs at once.
+    pub fn new(max_runs: usize) -> Self {
+        RunQueue {
+            max_runs: max_runs.max(1),
+            class_limits: HashMap::new(),
+            state: Mutex::new(QueueState::default()),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Runs at most `max` runs of `class` at once.
+    pub fn with_class_limit(mut self, class: impl Into<String>, max: usize) -> Self {
+        self.class_limits.insert(class.into(), max.max(1));
+        self
+    }
+
+    /// The runs going and waiting now.
+    pub fn status(&self) -> QueueStatus {
+        let state = self.lock();
+        let mut waiting = HashMap::new();
+        for (_, _, class) in &state.waiting {
+            *waiting.entry(class.clone()).or_default() += 1;
+        }
+        QueueStatus {
+            running: state.running.iter().filter(|(_, &n)| n > 0).map(|(c, &n)| (c.clone(), n)).collect(),
+            waiting,
+        }
+    }
+
+    /// Waits until a run of `priority` and `class` may start, or fails if
+    /// the run is cancelled first.
+    pub(crate) fn admit(&self, priority: i32, class: &str, cancel: &Cancellation) -> Result<Admission<'_>, EngineError> {
+        let mut state = self.lock();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiting.push((priority, ticket, class.to_string()));
+        while self.next(&state) != Some(ticket) {
+            if cancel.is_cancelled() {
+                state.waiting.retain(|&(_, t, _)| t != ticket);
+                drop(state);
+                self.freed.notify_all();
+                return Err(EngineError::Cancelled);
+            }
+            state = self.freed.wait_timeout(state, POLL).unwrap_or_else(|e| e.into_inner()).0;
+        }
+        state.waiting.retain(|&(_, t, _)| t != ticket);
+        *state.running.entry(class.to_string()).or_default() += 1;
+        drop(state);
+        // The next run in line may be able to start too.
+        self.freed.notify_all();
+        Ok(Admission {
+            queue: self,
+            class: class.to_string(),
+        })
+    }
+
+    /// The ticket of the waiting run that may start now, if any.
+    fn next(&self, state: &QueueState) -> Option<u64> {
+        if state.total() >= self.max_runs {
+            return None;
+        }
+        state
+            .waiting
+            .iter()
+            .filter(|(_, _, class)| match self.class_limits.get(class) {
+                Some(&max) => state.running.get(class).copied().unwrap_or(0) < max,
+                None => true,
+            })
+            .max_by_key(|&&(priority, ticket, _)| (priority, std::cmp::Reverse(ticket)))
+            .map(|&(_, ticket, _)| ticket)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for Admission<'_> {
+    fn drop(&mut self) {
+        let mut state = self.queue.lock();
+        if let Some(running) = state.running.get_mut(&self.class) {
+            *running -= 1;
+        }
+        drop(state);
+        self.queue.freed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn wait_for(queue: &RunQueue, waiting: usize) {
+        while queue.status().waiting.values().sum::<usize>() < waiting {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let queue = Arc::new(RunQueue::new(1));
+        let cancel = Arc::new(Cancellation::new());
+        let first = queue.admit(0, DEFAULT_CLASS, &cancel).unwrap();
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut threads = Vec::new();
+        for (waiting, (name, priority)) in [("batch", 0), ("urgent", 10), ("normal", 5)].into_iter().enumerate() {
+            let (shared, cancel, order) = (queue.clone(), cancel.clone(), order.clone());
+            threads.push(std::thread::spawn(move || {
+                let _admission = shared.admit(priority, DEFAULT_CLASS, &cancel).unwrap();
+                order.lock().unwrap().push(name);
+            }));
+            wait_for(&queue, waiting + 1);
+        }
+        drop(first);
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), ["urgent", "normal", "batch"]);
+    }
+
+    #[test]
+    fn test_class_limits() {
+        let queue = Arc::new(RunQueue::new(3).with_class_limit("batch", 1));
+        let cancel = Arc::new(Cancellation::new());
+        let batch = queue.admit(0, "batch", &cancel).unwrap();
+        let waiting = {
+            let (shared, cancel) = (queue.clone(), cancel.clone());
+            std::thread::spawn(move || shared.admit(0, "batch", &cancel).map(|_| ()))
+        };
+        wait_for(&queue, 1);
+        // A full batch class does not hold back other classes.
+        let _urgent = queue.admit(-1, "urgent", &cancel).unwrap();
+        assert_eq!(queue.status().running, HashMap::from([("batch".to_string(), 1), ("urgent".to_string(), 1)]));
+
+        cancel.cancel();
+        assert_eq!(waiting.join().unwrap().err(), Some(EngineError::Cancelled));
+        drop(batch);
+        assert_eq!(queue.status().waiting, HashMap::new());
+    }
+}
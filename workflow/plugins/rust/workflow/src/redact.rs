@@ -0,0 +1,150 @@
+//! Masking of sensitive values in what a run reports.
+//!
+//! Hosts register path patterns with `Engine::redact`. Values at matching
+//! paths are replaced by `MASK` in run history records, in event payloads
+//! passed to listeners, and in cassette recordings, while nodes and the
+//! `RunResult` still see the real values.
+//!
+//! A pattern is a dot-separated path such as `headers.authorization`, where
+//! `*` stands for any one key or list index. Patterns match at any depth
+//! (`password` masks every `password` field, `*.password` every one nested
+//! in an object), and keys compare case-insensitively, as header names do.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// What masked values are replaced by.
+pub const MASK: &str = "[REDACTED]";
+
+/// A set of path patterns whose values are masked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Redaction {
+    patterns: Vec<Vec<String>>,
+}
+
+impl Redaction {
+    /// A redaction that masks nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `pattern`.
+    pub fn pattern(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.split('.').map(str::to_ascii_lowercase).collect());
+        self
+    }
+
+    /// Whether no patterns are registered.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Masks the values in `value` at matching paths.
+    pub fn apply(&self, value: &mut Value) {
+        if !self.is_empty() {
+            self.walk(value, &mut Vec::new());
+        }
+    }
+
+    /// Masks the values in a node's ports, whose names are the first path
+    /// segment.
+    pub fn apply_ports(&self, ports: &mut HashMap<String, Value>) {
+        if self.is_empty() {
+            return;
+        }
+        let mut path = Vec::new();
+        for (name, value) in ports.iter_mut() {
+            path.push(name.to_ascii_lowercase());
+            self.visit(value, &mut path);
+            path.pop();
+        }
+    }
+
+    /// A masked copy of `ports`.
+    pub fn ports(&self, ports: &HashMap<String, Value>) -> HashMap<String, Value> {
+        let mut ports = ports.clone();
+        self.apply_ports(&mut ports);
+        ports
+    }
+
+    fn visit(&self, value: &mut Value, path: &mut Vec<String>) {
+        if self.matches(path) {
+            *value = Value::from(MASK);
+        } else {
+            self.walk(value, path);
+        }
+    }
+
+    fn walk(&self, value: &mut Value, path: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    path.push(key.to_ascii_lowercase());
+                    self.visit(item, path);
+                    path.pop();
+                }
+            }
+            Value::Array(items) => {
+                for (i, item) in items.iter_mut().enumerate() {
+                    path.push(i.to_string());
+                    self.visit(item, path);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether some pattern matches the end of `path`.
+    fn matches(&self, path: &[String]) -> bool {
+        self.patterns.iter().any(|pattern| {
+            path.len() >= pattern.len()
+                && pattern
+                    .iter()
+                    .zip(&path[path.len() - pattern.len()..])
+                    .all(|(expected, key)| expected == "*" || expected == key)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply() {
+        let redaction = Redaction::new().pattern("*.password").pattern("headers.authorization").pattern("cards.*.number");
+        let mut value = json!({
+            "password": "top-level",
+            "user": {"name": "ada", "password": "hunter2"},
+            "request": {"headers": {"Authorization": "Bearer x", "accept": "json"}},
+            "cards": [{"number": "4111", "expiry": "12/30"}]
+        });
+        redaction.apply(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "password": "top-level",
+                "user": {"name": "ada", "password": MASK},
+                "request": {"headers": {"Authorization": MASK, "accept": "json"}},
+                "cards": [{"number": MASK, "expiry": "12/30"}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_ports() {
+        let redaction = Redaction::new().pattern("token").pattern("headers.authorization");
+        let ports = HashMap::from([
+            ("token".to_string(), json!("abc")),
+            ("headers".to_string(), json!({"authorization": "Basic x"})),
+            ("status".to_string(), json!(200)),
+        ]);
+        let masked = redaction.ports(&ports);
+        assert_eq!(masked["token"], json!(MASK));
+        assert_eq!(masked["headers"], json!({"authorization": MASK}));
+        assert_eq!(masked["status"], json!(200));
+        assert_eq!(Redaction::new().ports(&ports), ports);
+    }
+}
@@ -0,0 +1,246 @@
+//! Node type registry.
+
+use crate::examples::{Example, ExampleFailure};
+use crate::schema::NodeSchema;
+use crate::search::SearchResult;
+use metabuilder_core::NodeExecutor;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// Maps node types (e.g. `"math.add"`) to their executors and, optionally,
+/// their port schemas, descriptions, search tags, examples, whether they
+/// have side effects, and whether they are deterministic.
+#[derive(Clone, Default)]
+pub struct Registry {
+    executors: BTreeMap<String, Arc<dyn NodeExecutor>>,
+    schemas: BTreeMap<String, NodeSchema>,
+    descriptions: BTreeMap<String, String>,
+    tags: BTreeMap<String, BTreeSet<String>>,
+    examples: BTreeMap<String, Vec<Example>>,
+    side_effects: BTreeSet<String>,
+    deterministic: BTreeSet<String>,
+}
+
+impl Registry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry containing every bundled plugin in the enabled
+    /// categories.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        crate::builtins::register_all(&mut registry);
+        crate::builtins::register_schemas(&mut registry);
+        crate::builtins::register_side_effects(&mut registry);
+        crate::builtins::register_deterministic(&mut registry);
+        crate::builtins::register_tags(&mut registry);
+        crate::builtins::register_examples(&mut registry);
+        registry
+    }
+
+    /// Registers (or replaces) the executor for `node_type`.
+    pub fn register(&mut self, node_type: impl Into<String>, executor: impl NodeExecutor + 'static) {
+        self.executors.insert(node_type.into(), Arc::new(executor));
+    }
+
+    /// Registers (or replaces) an async executor for `node_type`. The engine
+    /// runs it on the shared reactor (see `metabuilder_core::reactor`),
+    /// blocking only the thread of the node that awaits it.
+    #[cfg(feature = "async")]
+    pub fn register_async(
+        &mut self,
+        node_type: impl Into<String>,
+        executor: impl metabuilder_core::AsyncNodeExecutor + 'static,
+    ) {
+        self.register(node_type, metabuilder_core::reactor::Blocking(executor));
+    }
+
+    /// Looks up the executor for `node_type`.
+    pub fn get(&self, node_type: &str) -> Option<Arc<dyn NodeExecutor>> {
+        self.executors.get(node_type).cloned()
+    }
+
+    /// Declares the ports of `node_type` for `Workflow::validate`.
+    pub fn register_schema(&mut self, node_type: impl Into<String>, schema: NodeSchema) {
+        self.schemas.insert(node_type.into(), schema);
+    }
+
+    /// Looks up the port schema of `node_type`.
+    pub fn schema(&self, node_type: &str) -> Option<&NodeSchema> {
+        self.schemas.get(node_type)
+    }
+
+    /// Sets the one-line description of `node_type` shown by editors, in
+    /// English; `Locale::describe` translates it.
+    pub fn describe(&mut self, node_type: impl Into<String>, description: impl Into<String>) {
+        self.descriptions.insert(node_type.into(), description.into());
+    }
+
+    /// Looks up the description of `node_type`.
+    pub fn description(&self, node_type: &str) -> Option<&str> {
+        self.descriptions.get(node_type).map(String::as_str)
+    }
+
+    /// Adds a search tag, such as a synonym, to `node_type`.
+    pub fn tag(&mut self, node_type: impl Into<String>, tag: impl Into<String>) {
+        self.tags.entry(node_type.into()).or_default().insert(tag.into());
+    }
+
+    /// Search tags of `node_type` in sorted order.
+    pub fn tags(&self, node_type: &str) -> impl Iterator<Item = &str> {
+        self.tags.get(node_type).into_iter().flatten().map(String::as_str)
+    }
+
+    /// Node types matching `query`, best first; see `crate::search`.
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        crate::search::search(self, query)
+    }
+
+    /// Adds an executable example of `node_type`; see `crate::examples`.
+    pub fn add_example(&mut self, node_type: impl Into<String>, example: Example) {
+        self.examples.entry(node_type.into()).or_default().push(example);
+    }
+
+    /// Examples of `node_type` in the order they were added.
+    pub fn examples(&self, node_type: &str) -> &[Example] {
+        self.examples.get(node_type).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Runs every example, returning how many passed, or every output that
+    /// differed from its example.
+    pub fn self_test(&self) -> Result<usize, Vec<ExampleFailure>> {
+        let mut count = 0;
+        let mut failures = Vec::new();
+        for (node_type, examples) in &self.examples {
+            for (index, example) in examples.iter().enumerate() {
+                failures.extend(crate::examples::run(self, node_type, index, example));
+                count += 1;
+            }
+        }
+        if failures.is_empty() {
+            Ok(count)
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Declares that `node_type` touches the outside world (network, files,
+    /// processes), so `Engine::dry_run` replaces it with a stub.
+    pub fn mark_side_effects(&mut self, node_type: impl Into<String>) {
+        self.side_effects.insert(node_type.into());
+    }
+
+    /// Returns true if `node_type` was declared with `mark_side_effects`.
+    /// Node types are pure unless declared otherwise.
+    pub fn has_side_effects(&self, node_type: &str) -> bool {
+        self.side_effects.contains(node_type)
+    }
+
+    /// Declares that `node_type` always gives the same outputs for the same
+    /// inputs and reads nothing outside them, so `Engine::compile` may run it
+    /// ahead of time.
+    pub fn mark_deterministic(&mut self, node_type: impl Into<String>) {
+        self.deterministic.insert(node_type.into());
+    }
+
+    /// Returns true if `node_type` was declared with `mark_deterministic`.
+    /// Node types are not deterministic unless declared, and side effects
+    /// take precedence over the declaration.
+    pub fn is_deterministic(&self, node_type: &str) -> bool {
+        self.deterministic.contains(node_type) && !self.has_side_effects(node_type)
+    }
+
+    /// Returns true if `node_type` is registered.
+    pub fn contains(&self, node_type: &str) -> bool {
+        self.executors.contains_key(node_type)
+    }
+
+    /// Registered node types in sorted order.
+    pub fn node_types(&self) -> impl Iterator<Item = &str> {
+        self.executors.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtins_registered() {
+        let registry = Registry::with_builtins();
+        for node_type in ["math.add", "string.at", "logic.in", "var.set", "http.verify_signature"] {
+            assert!(registry.contains(node_type), "missing {}", node_type);
+        }
+    }
+
+    #[test]
+    fn test_side_effects() {
+        let registry = Registry::with_builtins();
+        assert!(registry.has_side_effects("http.graphql"));
+        assert!(registry.has_side_effects("remote.ssh_exec"));
+        assert!(!registry.has_side_effects("math.add"));
+        assert!(!registry.has_side_effects("http.verify_signature"));
+    }
+
+    #[test]
+    fn test_network_nodes_have_side_effects() {
+        // Any bundled node that takes a URL or host, or links an HTTP client,
+        // reaches the network and must be stubbed by dry runs.
+        let markers = ["ureq::", "hyper::", "TcpStream", "\"url\"", "_url\"", "\"host\"", "\"endpoint\""];
+        let registry = Registry::with_builtins();
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut scanned = 0;
+        for node_type in registry.node_types() {
+            let (category, name) = node_type.split_once('.').unwrap();
+            let Ok(entries) = std::fs::read_dir(root.join(category).join(format!("{}_{}", category, name)).join("src"))
+            else {
+                continue;
+            };
+            scanned += 1;
+            let source: String = entries.filter_map(|entry| std::fs::read_to_string(entry.ok()?.path()).ok()).collect();
+            if markers.iter().any(|marker| source.contains(marker)) {
+                assert!(registry.has_side_effects(node_type), "{} reaches the network", node_type);
+            }
+        }
+        assert!(scanned > 50);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let mut registry = Registry::with_builtins();
+        assert!(registry.is_deterministic("math.add"));
+        assert!(registry.is_deterministic("convert.parse_json"));
+        assert!(!registry.is_deterministic("convert.avro_encode"));
+        assert!(!registry.is_deterministic("convert.proto_decode"));
+        assert!(!registry.is_deterministic("random.choice"));
+        registry.mark_side_effects("math.add");
+        assert!(!registry.is_deterministic("math.add"));
+    }
+
+    #[test]
+    fn test_search_builtins() {
+        let registry = Registry::with_builtins();
+        assert!(registry.tags("string.split").any(|tag| tag == "text"));
+        assert_eq!(registry.search("split text")[0].node_type, "string.split");
+        assert_eq!(registry.search("http request")[0].node_type, "http.request");
+    }
+
+    #[test]
+    fn test_builtin_examples() {
+        let registry = Registry::with_builtins();
+        assert!(!registry.examples("math.add").is_empty());
+        let passed = registry.self_test().unwrap_or_else(|failures| {
+            panic!("{}", failures.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))
+        });
+        assert!(passed > 10);
+    }
+
+    #[test]
+    fn test_descriptions() {
+        let registry = Registry::with_builtins();
+        assert_eq!(registry.description("var.set"), Some("Set variable in workflow store"));
+        assert_eq!(registry.description("math.nope"), None);
+    }
+}
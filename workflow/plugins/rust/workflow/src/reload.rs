@@ -0,0 +1,235 @@
+//! Hot reloading of workflow specs for long-running hosts.
+//!
+//! A `LiveWorkflow` holds the current definition of a spec file. `reload`
+//! reads the file again when it has changed and swaps the new definition in
+//! only if it loads and validates cleanly against the registry; otherwise the
+//! previous definition stays and the error is kept in `last_error`. Runs take
+//! the definition with `current` when they start, so a swap affects new runs
+//! while runs in flight finish on the version they started with.
+//!
+//! `watch` polls the file on a background thread. Changes are detected by
+//! the spec file's modification time, so edits to included fragments are
+//! picked up on the next change to the spec itself or a `force_reload`.
+//! `WebhookServer::route_live` and `Scheduler::add_live` serve live
+//! workflows.
+
+use crate::engine::Engine;
+use crate::graph::Workflow;
+use crate::registry::Registry;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+/// A workflow definition that can be swapped while it is being served.
+#[derive(Debug)]
+pub struct LiveWorkflow {
+    path: Option<PathBuf>,
+    current: RwLock<Arc<Workflow>>,
+    state: Mutex<ReloadState>,
+}
+
+#[derive(Debug, Default)]
+struct ReloadState {
+    /// Modification time of the file last read, valid or not
+    modified: Option<SystemTime>,
+    /// Definitions swapped in since the first load
+    reloads: u64,
+    last_error: Option<String>,
+}
+
+impl LiveWorkflow {
+    /// Loads the spec at `path`, which must validate against `registry`.
+    pub fn open(path: impl Into<PathBuf>, registry: &Registry) -> Result<Self, String> {
+        let path = path.into();
+        let modified = modified(&path);
+        let workflow = load(&path, registry)?;
+        Ok(LiveWorkflow {
+            path: Some(path),
+            current: RwLock::new(Arc::new(workflow)),
+            state: Mutex::new(ReloadState {
+                modified,
+                ..ReloadState::default()
+            }),
+        })
+    }
+
+    /// A definition that is not backed by a file and never reloads.
+    pub fn fixed(workflow: Workflow) -> Self {
+        LiveWorkflow {
+            path: None,
+            current: RwLock::new(Arc::new(workflow)),
+            state: Mutex::new(ReloadState::default()),
+        }
+    }
+
+    /// The definition new runs should use.
+    pub fn current(&self) -> Arc<Workflow> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Reloads the spec if its file changed since it was last read. Returns
+    /// whether a new definition was swapped in; an invalid spec is an error
+    /// and leaves the current definition in place.
+    pub fn reload(&self, registry: &Registry) -> Result<bool, String> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let modified = modified(path);
+        if modified.is_some() && modified == self.lock().modified {
+            return Ok(false);
+        }
+        self.reload_from(path, modified, registry).map(|_| true)
+    }
+
+    /// Reloads the spec whether or not its file changed, such as after
+    /// editing a fragment it includes.
+    pub fn force_reload(&self, registry: &Registry) -> Result<(), String> {
+        match &self.path {
+            Some(path) => self.reload_from(path, modified(path), registry),
+            None => Ok(()),
+        }
+    }
+
+    fn reload_from(&self, path: &Path, modified: Option<SystemTime>, registry: &Registry) -> Result<(), String> {
+        let loaded = load(path, registry);
+        let mut state = self.lock();
+        state.modified = modified;
+        match loaded {
+            Ok(workflow) => {
+                *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(workflow);
+                state.reloads += 1;
+                state.last_error = None;
+                Ok(())
+            }
+            Err(message) => {
+                state.last_error = Some(message.clone());
+                Err(message)
+            }
+        }
+    }
+
+    /// How many times a new definition has been swapped in.
+    pub fn reloads(&self) -> u64 {
+        self.lock().reloads
+    }
+
+    /// Why the last reload was rejected, until a later one succeeds.
+    pub fn last_error(&self) -> Option<String> {
+        self.lock().last_error.clone()
+    }
+
+    /// Checks the spec for changes every `interval` on a background thread,
+    /// validating against the registry of `engine`.
+    pub fn watch(self: &Arc<Self>, engine: Arc<Engine>, interval: Duration) -> WatchHandle {
+        let (live, stop) = (self.clone(), Arc::new(AtomicBool::new(false)));
+        let stopped = stop.clone();
+        let thread = std::thread::spawn(move || {
+            while !stopped.load(Ordering::SeqCst) {
+                std::thread::park_timeout(interval);
+                // Rejected specs are kept in `last_error`.
+                let _ = live.reload(engine.registry());
+            }
+        });
+        WatchHandle { stop, thread }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReloadState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A running `LiveWorkflow::watch`; dropping it leaves the watch running.
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl WatchHandle {
+    /// Stops watching and waits for the watch thread to exit.
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        self.thread.thread().unpark();
+        let _ = self.thread.join();
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Loads and validates the spec at `path`.
+fn load(path: &Path, registry: &Registry) -> Result<Workflow, String> {
+    let workflow = Workflow::from_path(path).map_err(|e| e.to_string())?;
+    let diagnostics = workflow.validate(registry);
+    if !diagnostics.is_empty() {
+        let messages: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+        return Err(format!("{}: {}", path.display(), messages.join("; ")));
+    }
+    Ok(workflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    /// Writes a spec summing `numbers`, stamped `version` seconds after the
+    /// epoch so every write has a distinct modification time.
+    fn write(path: &Path, numbers: serde_json::Value, version: u64) {
+        let spec = json!({"nodes": [{"id": "sum", "type": "math.add", "params": {"numbers": numbers}}]});
+        std::fs::write(path, spec.to_string()).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(version)).unwrap();
+    }
+
+    #[test]
+    fn test_reload() {
+        let dir = std::env::temp_dir().join(format!("workflow_reload_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("spec.json");
+        let engine = Engine::default();
+        write(&path, json!([1, 2]), 1);
+        let live = LiveWorkflow::open(&path, engine.registry()).unwrap();
+        let sum = |workflow: &Workflow| engine.run(workflow, HashMap::new()).unwrap().outputs["sum"]["result"].clone();
+
+        let in_flight = live.current();
+        assert!(!live.reload(engine.registry()).unwrap());
+        write(&path, json!([3, 4]), 2);
+        assert!(live.reload(engine.registry()).unwrap());
+        assert_eq!(sum(&live.current()), json!(7));
+        assert_eq!(sum(&in_flight), json!(3));
+
+        std::fs::write(&path, r#"{"nodes": [{"id": "sum", "type": "math.nope"}]}"#).unwrap();
+        live.force_reload(engine.registry()).unwrap_err();
+        assert!(live.last_error().unwrap().ends_with("Node sum has unknown type: math.nope"));
+        assert_eq!(sum(&live.current()), json!(7));
+        assert_eq!(live.reloads(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_watch() {
+        let dir = std::env::temp_dir().join(format!("workflow_watch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("spec.json");
+        let engine = Arc::new(Engine::default());
+        write(&path, json!([1]), 1);
+        let live = Arc::new(LiveWorkflow::open(&path, engine.registry()).unwrap());
+        let watch = live.watch(engine, Duration::from_millis(10));
+
+        write(&path, json!([1, 1]), 2);
+        for _ in 0..500 {
+            if live.reloads() == 1 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        watch.stop();
+        assert_eq!(live.reloads(), 1);
+        assert_eq!(live.current().nodes[0].params["numbers"], json!([1, 1]));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,158 @@
+//! Past and current runs, looked up by run id.
+//!
+//! Attach a `RunStore` with `Engine::with_run_store`; every top-level run is
+//! recorded when it starts and again when it ends, under its run id, with
+//! the tenant it ran for, a digest of its workflow, its status, and, once it
+//! ends, the outputs of its nodes or the error that stopped it. A resumed
+//! run keeps its id, so its record is updated in place.
+//!
+//! A store given the engine's audit log with `RunStore::with_audit` also
+//! returns the audit entries of a run (see `audit`).
+
+use crate::audit::{AuditEntry, AuditLog, AuditQuery};
+use crate::engine::RunResult;
+use crate::error::EngineError;
+use crate::redact::Redaction;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where a run stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    Succeeded,
+    Failed,
+    /// Stopped by `Engine::cancel` or a shutdown.
+    Cancelled,
+}
+
+/// One top-level run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub run_id: String,
+    /// The tenant the run is for (see `Engine::run_as`), if any
+    pub tenant: Option<String>,
+    /// Digest of the workflow run (see `Workflow::digest`)
+    pub workflow: String,
+    pub status: RunStatus,
+    /// When the run first started, in milliseconds since the Unix epoch
+    pub started_ms: u64,
+    /// When the run last ended, if it has
+    pub finished_ms: Option<u64>,
+    /// Output ports of every node that ran, redacted, once the run succeeds
+    pub outputs: HashMap<String, HashMap<String, Value>>,
+    /// The workflow's declared outputs, once the run succeeds
+    pub returned: HashMap<String, Value>,
+    /// What stopped the run, if it failed or was cancelled
+    pub error: Option<String>,
+}
+
+/// Thread-safe record of runs, by run id.
+#[derive(Debug, Default)]
+pub struct RunStore {
+    runs: Mutex<BTreeMap<String, RunRecord>>,
+    audit: Option<Arc<AuditLog>>,
+}
+
+impl RunStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up audit entries in `audit`, the log given to `Engine::with_audit`.
+    pub fn with_audit(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// The run with id `run_id`, if it was recorded.
+    pub fn get(&self, run_id: &str) -> Option<RunRecord> {
+        self.lock().get(run_id).cloned()
+    }
+
+    /// Every run, ordered by run id.
+    pub fn runs(&self) -> Vec<RunRecord> {
+        self.lock().values().cloned().collect()
+    }
+
+    /// The runs with `status`.
+    pub fn with_status(&self, status: RunStatus) -> Vec<RunRecord> {
+        self.lock().values().filter(|run| run.status == status).cloned().collect()
+    }
+
+    /// The audit entries of run `run_id`, nested runs included, or none if
+    /// the store has no audit log.
+    pub fn audit(&self, run_id: &str) -> Vec<AuditEntry> {
+        match &self.audit {
+            Some(audit) => audit.query(&AuditQuery::new().run(run_id)),
+            None => Vec::new(),
+        }
+    }
+
+    /// Serializes every run as a JSON array, for export.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.runs()).expect("run records are always serializable")
+    }
+
+    /// Records run `run_id` as running.
+    pub(crate) fn begin(&self, run_id: &str, tenant: Option<&str>, workflow: String) {
+        let mut runs = self.lock();
+        let started_ms = runs.get(run_id).map_or_else(now_ms, |run| run.started_ms);
+        runs.insert(
+            run_id.to_string(),
+            RunRecord {
+                run_id: run_id.to_string(),
+                tenant: tenant.map(str::to_string),
+                workflow,
+                status: RunStatus::Running,
+                started_ms,
+                finished_ms: None,
+                outputs: HashMap::new(),
+                returned: HashMap::new(),
+                error: None,
+            },
+        );
+    }
+
+    /// Records how run `run_id` ended, and whether it was `cancelled`.
+    pub(crate) fn finish(
+        &self,
+        run_id: &str,
+        result: &Result<RunResult, EngineError>,
+        cancelled: bool,
+        redaction: &Redaction,
+    ) {
+        let mut runs = self.lock();
+        let Some(run) = runs.get_mut(run_id) else {
+            return;
+        };
+        run.finished_ms = Some(now_ms());
+        match result {
+            Ok(result) => {
+                run.status = RunStatus::Succeeded;
+                run.outputs = result.outputs.iter().map(|(id, ports)| (id.clone(), redaction.ports(ports))).collect();
+                run.returned = redaction.ports(&result.returned);
+            }
+            Err(error) => {
+                run.status = match cancelled || matches!(error, EngineError::Cancelled) {
+                    true => RunStatus::Cancelled,
+                    false => RunStatus::Failed,
+                };
+                run.error = Some(error.to_string());
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, RunRecord>> {
+        self.runs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
@@ -0,0 +1,490 @@
+//! Cron-style scheduling of workflow runs.
+//!
+//! A `Scheduler` binds cron expressions to workflows and, once started on a
+//! tokio runtime, runs each one on schedule with the shared `Engine`. Runs
+//! execute on tokio's blocking pool, since the engine is synchronous. What
+//! happens when a run is due while the previous one is still going is set
+//! per job with `Overlap`, and `Scheduler::status` reports how each job's
+//! runs went.
+//!
+//! Expressions have five fields (minute, hour, day of month, month, day of
+//! week) or six with a leading seconds field, and are evaluated in UTC. Each
+//! field takes `*`, values, ranges, steps, and lists (`*/15`, `1-5`,
+//! `MON,WED`); `@hourly`, `@daily`, `@weekly`, `@monthly`, and `@yearly` are
+//! shorthands. As in Vixie cron, when both day fields are restricted a day
+//! matching either one is due; a day field starting with `*` (such as `*/2`)
+//! is not restricted, so it narrows the other day field instead.
+
+use crate::engine::Engine;
+use crate::graph::Workflow;
+use crate::reload::LiveWorkflow;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeDelta, Timelike, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    source: String,
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day-of-month and day-of-week fields did not start with `*`
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Schedules further ahead than this many years never fire (such as `0 0 30 2 *`).
+const HORIZON_YEARS: i32 = 5;
+
+impl Schedule {
+    /// Parses a five- or six-field cron expression or an `@` shorthand.
+    pub fn parse(expression: &str) -> Result<Schedule, String> {
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let (seconds, rest) = match fields.len() {
+            5 => ("0", &fields[..]),
+            6 => (fields[0], &fields[1..]),
+            n => return Err(format!("cron expression needs 5 or 6 fields, got {}: {}", n, expression)),
+        };
+        let field = |name: &str, spec: &str, min: u32, max: u32, names: &[&str]| {
+            parse_field(spec, min, max, names).map_err(|e| format!("invalid {} field {:?}: {}", name, spec, e))
+        };
+        let mut weekdays = field("day of week", rest[4], 0, 7, &WEEKDAYS)?;
+        // Both 0 and 7 mean Sunday.
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        // Vixie cron only checks the first character, so `*/2` is unrestricted.
+        let unrestricted = |spec: &str| spec.starts_with('*') || spec.starts_with('?');
+        Ok(Schedule {
+            source: expression.trim().to_string(),
+            seconds: field("seconds", seconds, 0, 59, &[])?,
+            minutes: field("minute", rest[0], 0, 59, &[])?,
+            hours: field("hour", rest[1], 0, 23, &[])?,
+            days: field("day of month", rest[2], 1, 31, &[])?,
+            months: field("month", rest[3], 1, 12, &MONTHS)?,
+            weekdays,
+            days_restricted: !unrestricted(rest[2]),
+            weekdays_restricted: !unrestricted(rest[4]),
+        })
+    }
+
+    /// The first time strictly after `after` that the schedule is due, or
+    /// `None` if it never is within the next few years.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.naive_utc().with_nanosecond(0)? + TimeDelta::seconds(1);
+        let horizon = start.year() + HORIZON_YEARS;
+        let mut t = start;
+        while t.year() <= horizon {
+            let date = t.date();
+            t = if !bit(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 { (t.year() + 1, 1) } else { (t.year(), t.month() + 1) };
+                midnight(NaiveDate::from_ymd_opt(year, month, 1)?)
+            } else if !self.day_matches(date) {
+                midnight(date.succ_opt()?)
+            } else if !bit(self.hours, t.hour()) {
+                date.and_hms_opt(t.hour(), 0, 0)? + TimeDelta::hours(1)
+            } else if !bit(self.minutes, t.minute()) {
+                date.and_hms_opt(t.hour(), t.minute(), 0)? + TimeDelta::minutes(1)
+            } else if !bit(self.seconds, t.second()) {
+                t + TimeDelta::seconds(1)
+            } else {
+                return Some(t.and_utc());
+            };
+        }
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = bit(self.days, date.day());
+        let weekday = bit(self.weekdays, date.weekday().num_days_from_sunday());
+        if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+impl std::fmt::Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+fn bit(mask: u64, n: u32) -> bool {
+    mask & (1 << n) != 0
+}
+
+fn midnight(date: NaiveDate) -> NaiveDateTime {
+    date.and_hms_opt(0, 0, 0).expect("midnight exists")
+}
+
+/// Parses one field into a bit mask of the values it allows.
+fn parse_field(spec: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let value = |text: &str| -> Result<u32, String> {
+        let offset = if names.len() == 12 { 1 } else { 0 };
+        let n = match names.iter().position(|name| name.eq_ignore_ascii_case(text)) {
+            Some(index) => index as u32 + offset,
+            None => text.parse::<u32>().map_err(|_| format!("{} is not a number", text))?,
+        };
+        if n < min || n > max {
+            return Err(format!("{} is outside {}-{}", n, min, max));
+        }
+        Ok(n)
+    };
+    let mut mask = 0u64;
+    for part in spec.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, Some(step)),
+                _ => return Err(format!("invalid step {}", step)),
+            },
+            None => (part, None),
+        };
+        let (low, high) = match range {
+            "*" | "?" => (min, max),
+            _ => match range.split_once('-') {
+                Some((low, high)) => (value(low)?, value(high)?),
+                // `5/15` means from 5 to the end in steps of 15.
+                None if step.is_some() => (value(range)?, max),
+                None => {
+                    let n = value(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if low > high {
+            return Err(format!("range {}-{} is backwards", low, high));
+        }
+        for n in (low..=high).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+/// What to do when a job is due while its previous run is still going.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overlap {
+    /// Skip the new run (the default).
+    #[default]
+    Skip,
+    /// Start the new run once the previous one finishes.
+    Queue,
+    /// Start the new run alongside the previous one.
+    Parallel,
+}
+
+/// How a scheduled job's runs went.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JobStatus {
+    /// When the job is next due, once the scheduler is started
+    pub next_run: Option<DateTime<Utc>>,
+    pub last_started: Option<DateTime<Utc>>,
+    pub last_finished: Option<DateTime<Utc>>,
+    /// Outcome of the last finished run: `Err` holds the run's error message
+    pub last_result: Option<Result<(), String>>,
+    /// Runs started so far
+    pub runs: u64,
+    /// Runs skipped because of `Overlap::Skip`
+    pub skipped: u64,
+    /// Runs in progress
+    pub running: usize,
+    /// Runs waiting for the previous one under `Overlap::Queue`
+    pub queued: usize,
+}
+
+struct Job {
+    name: String,
+    schedule: Schedule,
+    workflow: Arc<LiveWorkflow>,
+    vars: HashMap<String, Value>,
+    overlap: Overlap,
+    status: Mutex<JobStatus>,
+    /// Held by the running run of an `Overlap::Queue` job.
+    turn: tokio::sync::Mutex<()>,
+}
+
+impl Job {
+    fn update<T>(&self, f: impl FnOnce(&mut JobStatus) -> T) -> T {
+        f(&mut self.status.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Runs workflows on cron schedules.
+pub struct Scheduler {
+    engine: Arc<Engine>,
+    jobs: Vec<Arc<Job>>,
+}
+
+/// Running schedule loops; dropping the handle leaves them running.
+pub struct SchedulerHandle {
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    /// Stops scheduling new runs. Runs already in progress finish.
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+}
+
+impl Scheduler {
+    /// Creates a scheduler that runs workflows with `engine`.
+    pub fn new(engine: Arc<Engine>) -> Self {
+        Scheduler { engine, jobs: Vec::new() }
+    }
+
+    /// Schedules `workflow` to run with `vars` whenever `cron` is due.
+    pub fn add(
+        &mut self,
+        name: impl Into<String>,
+        cron: &str,
+        workflow: Workflow,
+        vars: HashMap<String, Value>,
+        overlap: Overlap,
+    ) -> Result<(), String> {
+        self.add_live(name, cron, Arc::new(LiveWorkflow::fixed(workflow)), vars, overlap)
+    }
+
+    /// Like `add`, but each run uses the definition `workflow` holds when it
+    /// starts, so reloads apply to later runs (see `reload`).
+    pub fn add_live(
+        &mut self,
+        name: impl Into<String>,
+        cron: &str,
+        workflow: Arc<LiveWorkflow>,
+        vars: HashMap<String, Value>,
+        overlap: Overlap,
+    ) -> Result<(), String> {
+        let name = name.into();
+        if self.jobs.iter().any(|job| job.name == name) {
+            return Err(format!("job {} is already scheduled", name));
+        }
+        self.jobs.push(Arc::new(Job {
+            name,
+            schedule: Schedule::parse(cron)?,
+            workflow,
+            vars,
+            overlap,
+            status: Mutex::new(JobStatus::default()),
+            turn: tokio::sync::Mutex::new(()),
+        }));
+        Ok(())
+    }
+
+    /// The status of job `name`.
+    pub fn status(&self, name: &str) -> Option<JobStatus> {
+        let job = self.jobs.iter().find(|job| job.name == name)?;
+        Some(job.update(|status| status.clone()))
+    }
+
+    /// Starts one schedule loop per job on the current tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a tokio runtime.
+    pub fn start(&self) -> SchedulerHandle {
+        let tasks = self
+            .jobs
+            .iter()
+            .map(|job| {
+                let (engine, job) = (self.engine.clone(), job.clone());
+                tokio::spawn(async move {
+                    let mut after = Utc::now();
+                    while let Some(next) = job.schedule.next_after(after) {
+                        job.update(|status| status.next_run = Some(next));
+                        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                        tokio::time::sleep(wait).await;
+                        fire(&engine, &job);
+                        // Timers may wake early; never fire the same tick twice.
+                        after = next.max(Utc::now());
+                    }
+                    job.update(|status| status.next_run = None);
+                })
+            })
+            .collect();
+        SchedulerHandle { tasks }
+    }
+}
+
+/// Starts a run of `job` now, subject to its overlap policy.
+fn fire(engine: &Arc<Engine>, job: &Arc<Job>) {
+    let (engine, job) = (engine.clone(), job.clone());
+    match job.overlap {
+        Overlap::Skip => {
+            let busy = job.update(|status| {
+                if status.running > 0 {
+                    status.skipped += 1;
+                }
+                status.running > 0
+            });
+            if !busy {
+                job.update(begin);
+                tokio::spawn(async move { execute(engine, job).await });
+            }
+        }
+        Overlap::Queue => {
+            job.update(|status| status.queued += 1);
+            tokio::spawn(async move {
+                let turn = job.turn.lock().await;
+                job.update(|status| {
+                    status.queued -= 1;
+                    begin(status);
+                });
+                execute(engine, job.clone()).await;
+                drop(turn);
+            });
+        }
+        Overlap::Parallel => {
+            job.update(begin);
+            tokio::spawn(async move { execute(engine, job).await });
+        }
+    }
+}
+
+fn begin(status: &mut JobStatus) {
+    status.running += 1;
+    status.runs += 1;
+    status.last_started = Some(Utc::now());
+}
+
+/// Runs the job's workflow on the blocking pool and records the outcome.
+async fn execute(engine: Arc<Engine>, job: Arc<Job>) {
+    let (workflow, vars) = (job.workflow.current(), job.vars.clone());
+    let outcome = tokio::task::spawn_blocking(move || engine.run(&workflow, vars))
+        .await
+        .map_err(|e| format!("run panicked: {}", e))
+        .and_then(|run| run.map(|_| ()).map_err(|e| e.to_string()));
+    job.update(|status| {
+        status.running -= 1;
+        status.last_finished = Some(Utc::now());
+        status.last_result = Some(outcome);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use crate::registry::Registry;
+    use std::time::Duration;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        let schedule = Schedule::parse(expression).unwrap();
+        schedule.next_after(at(after)).map_or("never".to_string(), |t| t.to_rfc3339())
+    }
+
+    #[test]
+    fn test_next_after() {
+        assert_eq!(next("*/15 * * * *", "2024-03-10T10:07:30Z"), "2024-03-10T10:15:00+00:00");
+        assert_eq!(next("0 9 * * MON-FRI", "2024-03-08T09:00:00Z"), "2024-03-11T09:00:00+00:00");
+        assert_eq!(next("@monthly", "2024-12-31T23:59:59Z"), "2025-01-01T00:00:00+00:00");
+        assert_eq!(next("30 */10 * * * *", "2024-01-01T00:00:30Z"), "2024-01-01T00:10:30+00:00");
+        assert_eq!(next("0 0 29 2 *", "2024-03-01T00:00:00Z"), "2028-02-29T00:00:00+00:00");
+        // Either day field may match when both are restricted.
+        assert_eq!(next("0 0 13 * 5", "2024-09-01T00:00:00Z"), "2024-09-06T00:00:00+00:00");
+        assert_eq!(next("0 12 * * 7", "2024-09-01T12:00:00Z"), "2024-09-08T12:00:00+00:00");
+        assert_eq!(next("0 0 30 2 *", "2024-01-01T00:00:00Z"), "never");
+    }
+
+    #[test]
+    fn test_day_fields_starting_with_star() {
+        // `*/1` is `*`: only Fridays, not every day.
+        assert_eq!(next("0 0 */1 * 5", "2024-09-01T00:00:00Z"), next("0 0 * * 5", "2024-09-01T00:00:00Z"));
+        assert_eq!(next("0 0 */1 * 5", "2024-09-01T00:00:00Z"), "2024-09-06T00:00:00+00:00");
+        assert_eq!(next("0 0 13 * */1", "2024-09-01T00:00:00Z"), "2024-09-13T00:00:00+00:00");
+        // A stepped `*` narrows the other field: odd days that are Mondays.
+        assert_eq!(next("0 0 */2 * MON", "2024-09-01T00:00:00Z"), "2024-09-09T00:00:00+00:00");
+        assert_eq!(next("0 0 1 * */2", "2024-08-31T00:00:00Z"), "2024-09-01T00:00:00+00:00");
+        assert_eq!(next("0 0 1 * */2", "2024-09-01T00:00:00Z"), "2024-10-01T00:00:00+00:00");
+        assert_eq!(next("0 0 ? * 5", "2024-09-01T00:00:00Z"), "2024-09-06T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Schedule::parse("* * *").unwrap_err(),
+            "cron expression needs 5 or 6 fields, got 3: * * *"
+        );
+        assert_eq!(
+            Schedule::parse("61 * * * *").unwrap_err(),
+            "invalid minute field \"61\": 61 is outside 0-59"
+        );
+        assert_eq!(
+            Schedule::parse("* * * FOO *").unwrap_err(),
+            "invalid month field \"FOO\": FOO is not a number"
+        );
+        assert!(Schedule::parse("*/0 * * * *").is_err());
+        assert!(Schedule::parse("5-1 * * * *").is_err());
+    }
+
+    /// Fires a job three times in quick succession and returns its status
+    /// once every run has finished, with the most runs seen at once.
+    fn fire_three_times(overlap: Overlap) -> (JobStatus, usize) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut registry = Registry::with_builtins();
+        let (counter, max) = (running.clone(), peak.clone());
+        registry.register("test.slow", move |inputs: HashMap<String, Value>, _: Option<&dyn std::any::Any>| {
+            max.fetch_max(counter.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(30));
+            counter.fetch_sub(1, Ordering::SeqCst);
+            inputs
+        });
+        let mut scheduler = Scheduler::new(Arc::new(Engine::new(registry)));
+        let workflow = Workflow::new().node(Node::new("slow", "test.slow"));
+        scheduler.add("job", "@hourly", workflow, HashMap::new(), overlap).unwrap();
+        assert!(scheduler.add("job", "@daily", Workflow::new(), HashMap::new(), overlap).is_err());
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+        runtime.block_on(async {
+            for _ in 0..3 {
+                fire(&scheduler.engine, &scheduler.jobs[0]);
+                tokio::task::yield_now().await;
+            }
+            while scheduler.status("job").is_some_and(|s| s.running + s.queued > 0) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        });
+        (scheduler.status("job").unwrap(), peak.load(Ordering::SeqCst))
+    }
+
+    #[test]
+    fn test_overlap_policies() {
+        let (status, peak) = fire_three_times(Overlap::Skip);
+        assert_eq!((status.runs, status.skipped, peak), (1, 2, 1));
+        assert_eq!(status.last_result, Some(Ok(())));
+
+        let (status, peak) = fire_three_times(Overlap::Queue);
+        assert_eq!((status.runs, status.skipped, peak), (3, 0, 1));
+
+        let (status, _) = fire_three_times(Overlap::Parallel);
+        assert_eq!((status.runs, status.skipped), (3, 0));
+    }
+}
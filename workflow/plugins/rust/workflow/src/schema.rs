@@ -0,0 +1,93 @@
+//! Port schemas for node types, used by `Workflow::validate`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The kind of value a port carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PortKind {
+    Any,
+    Bool,
+    Number,
+    String,
+    List,
+    Object,
+}
+
+impl PortKind {
+    /// Whether a value of kind `self` may feed an input of kind `input`.
+    pub fn fits(self, input: PortKind) -> bool {
+        self == PortKind::Any || input == PortKind::Any || self == input
+    }
+
+    /// The kind of `value`, or `None` for `null`.
+    pub fn of(value: &Value) -> Option<PortKind> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(PortKind::Bool),
+            Value::Number(_) => Some(PortKind::Number),
+            Value::String(_) => Some(PortKind::String),
+            Value::Array(_) => Some(PortKind::List),
+            Value::Object(_) => Some(PortKind::Object),
+        }
+    }
+}
+
+impl std::fmt::Display for PortKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PortKind::Any => "any",
+            PortKind::Bool => "bool",
+            PortKind::Number => "number",
+            PortKind::String => "string",
+            PortKind::List => "list",
+            PortKind::Object => "object",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The input and output ports of a node type.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeSchema {
+    pub inputs: Vec<(String, PortKind)>,
+    pub outputs: Vec<(String, PortKind)>,
+    /// Whether the node reports a `branch` and emits only the taken ports
+    pub branching: bool,
+}
+
+impl NodeSchema {
+    /// Creates a schema with no ports.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an input port.
+    pub fn input(mut self, name: impl Into<String>, kind: PortKind) -> Self {
+        self.inputs.push((name.into(), kind));
+        self
+    }
+
+    /// Adds an output port.
+    pub fn output(mut self, name: impl Into<String>, kind: PortKind) -> Self {
+        self.outputs.push((name.into(), kind));
+        self
+    }
+
+    /// Marks the node as branching, like `control.if`.
+    pub fn branching(mut self) -> Self {
+        self.branching = true;
+        self
+    }
+
+    /// The kind of input port `name`, if the node has it.
+    pub fn input_kind(&self, name: &str) -> Option<PortKind> {
+        self.inputs.iter().find(|(port, _)| port == name).map(|(_, kind)| *kind)
+    }
+
+    /// The kind of output port `name`, if the node has it.
+    pub fn output_kind(&self, name: &str) -> Option<PortKind> {
+        self.outputs.iter().find(|(port, _)| port == name).map(|(_, kind)| *kind)
+    }
+}
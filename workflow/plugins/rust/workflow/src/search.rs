@@ -0,0 +1,181 @@
+//! Fuzzy search over registered node types, for editor palettes and CLIs.
+//!
+//! A query is split into terms, and a node type matches when every term
+//! matches a word of its type, tags, description, or port names: exactly, as
+//! a prefix, inside a word, or (for terms of four letters or more) with one
+//! typo. Matches in the node type count most and matches in port names
+//! least, so `"split text"` ranks `string.split` (tagged `text`) above nodes
+//! that merely take a `text` input.
+
+use crate::registry::Registry;
+
+/// A node type found by `Registry::search`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub node_type: String,
+    /// Higher is a better match
+    pub score: f64,
+}
+
+/// Weights of the places a term can match.
+const NODE_TYPE: f64 = 4.0;
+const TAG: f64 = 3.0;
+const DESCRIPTION: f64 = 2.0;
+const PORT: f64 = 1.0;
+
+/// Lowercase words of `text`, split at anything but letters and digits.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Whether `a` becomes `b` with at most one insertion, deletion, or
+/// substitution.
+fn one_edit(a: &str, b: &str) -> bool {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (short, long) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+    let prefix = short.iter().zip(long.iter()).take_while(|(x, y)| x == y).count();
+    let skip = if short.len() == long.len() { 1 } else { 0 };
+    short.get(prefix + skip..).unwrap_or_default() == long.get(prefix + 1..).unwrap_or_default()
+}
+
+/// How well `term` matches `word`, from 0 (not at all) to 1 (exactly).
+fn closeness(term: &str, word: &str) -> f64 {
+    if word == term {
+        1.0
+    } else if word.starts_with(term) {
+        0.8
+    } else if word.contains(term) {
+        0.6
+    } else if term.chars().count() >= 4 && one_edit(term, word) {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+/// Best closeness of `term` to any of `words`.
+fn best<'a>(term: &str, words: impl IntoIterator<Item = &'a String>) -> f64 {
+    words.into_iter().map(|word| closeness(term, word)).fold(0.0, f64::max)
+}
+
+/// Node types of `registry` matching `query`, best first.
+pub(crate) fn search(registry: &Registry, query: &str) -> Vec<SearchResult> {
+    let terms: Vec<String> = words(query).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut results = Vec::new();
+    for node_type in registry.node_types() {
+        let fields: [(f64, Vec<String>); 4] = [
+            (NODE_TYPE, words(node_type).collect()),
+            (TAG, registry.tags(node_type).flat_map(words).collect()),
+            (DESCRIPTION, registry.description(node_type).map(|d| words(d).collect()).unwrap_or_default()),
+            (
+                PORT,
+                registry
+                    .schema(node_type)
+                    .map(|schema| {
+                        schema
+                            .inputs
+                            .iter()
+                            .chain(&schema.outputs)
+                            .flat_map(|(port, _)| words(port))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            ),
+        ];
+        let mut score = 0.0;
+        for term in &terms {
+            let term_score = fields
+                .iter()
+                .map(|(weight, words)| weight * best(term, words))
+                .fold(0.0, f64::max);
+            if term_score == 0.0 {
+                score = 0.0;
+                break;
+            }
+            score += term_score;
+        }
+        if score > 0.0 {
+            results.push(SearchResult {
+                node_type: node_type.to_string(),
+                score,
+            });
+        }
+    }
+    // Node types iterate in sorted order and the sort is stable, so ties stay
+    // alphabetical.
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{NodeSchema, PortKind};
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    fn noop(_: HashMap<String, Value>, _: Option<&dyn std::any::Any>) -> HashMap<String, Value> {
+        HashMap::new()
+    }
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        for (node_type, description) in [
+            ("string.split", "Split string by separator"),
+            ("regex.split", "Split text by a regular expression"),
+            ("string.upper", "Convert string to uppercase"),
+            ("list.sort", "Sort a list"),
+        ] {
+            registry.register(node_type, noop);
+            registry.describe(node_type, description);
+        }
+        registry.tag("string.split", "text");
+        registry.tag("string.upper", "text");
+        registry.register_schema("list.sort", NodeSchema::new().input("text_key", PortKind::String));
+        registry
+    }
+
+    fn found(query: &str) -> Vec<String> {
+        registry().search(query).into_iter().map(|result| result.node_type).collect()
+    }
+
+    #[test]
+    fn test_ranking() {
+        assert_eq!(found("split text"), ["string.split", "regex.split"]);
+        assert_eq!(found("text"), ["string.split", "string.upper", "regex.split", "list.sort"]);
+        assert_eq!(found("SPLIT"), ["regex.split", "string.split"]);
+    }
+
+    #[test]
+    fn test_fuzzy() {
+        // Prefix, inner substring, and one typo.
+        assert_eq!(found("upp"), ["string.upper"]);
+        assert_eq!(found("case"), ["string.upper"]);
+        assert_eq!(found("srot"), Vec::<String>::new());
+        assert_eq!(found("sortt"), ["list.sort"]);
+        assert_eq!(found("regx"), ["regex.split"]);
+    }
+
+    #[test]
+    fn test_no_match() {
+        assert!(found("").is_empty());
+        assert!(found("split zebra").is_empty());
+    }
+
+    #[test]
+    fn test_one_edit() {
+        assert!(one_edit("sort", "sorts"));
+        assert!(one_edit("sort", "sart"));
+        assert!(one_edit("regx", "regex"));
+        assert!(!one_edit("srot", "sort"));
+        assert!(!one_edit("ab", "abcd"));
+    }
+}
@@ -0,0 +1,161 @@
+//! Graceful shutdown of an engine.
+//!
+//! `Engine::shutdown` stops the engine taking new top-level runs, which then
+//! fail with `EngineError::ShuttingDown`, and waits up to a grace period for
+//! the runs in flight to finish. Runs still going after it are cancelled:
+//! they start no further node and stop once the nodes running have returned,
+//! so a run with a checkpoint store has saved every node it completed. The
+//! report names the runs that finished and those that were interrupted, by
+//! run id, and the next process resumes each interrupted run from its own
+//! checkpoint with `Engine::resume_run`.
+
+use crate::error::EngineError;
+use metabuilder_core::Cancellation;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// What happened to the runs in flight when an engine shut down.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShutdownReport {
+    /// Runs that finished within the grace period
+    pub drained: Vec<String>,
+    /// Runs cancelled when the grace period ran out
+    pub interrupted: Vec<String>,
+}
+
+#[derive(Default)]
+struct State {
+    closed: bool,
+    /// Runs in flight, with the flags that cancel them
+    active: BTreeMap<String, Arc<Cancellation>>,
+}
+
+/// The top-level runs of an engine in flight, by run id.
+#[derive(Default)]
+pub(crate) struct Runs {
+    state: Mutex<State>,
+    finished: Condvar,
+}
+
+impl Runs {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Registers run `run_id`, cancelled through `cancel`, until the returned
+    /// guard drops, unless the engine is shutting down or the run is already
+    /// in flight.
+    pub(crate) fn begin(self: &Arc<Self>, run_id: &str, cancel: Arc<Cancellation>) -> Result<ActiveRun, EngineError> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(EngineError::ShuttingDown);
+        }
+        if state.active.contains_key(run_id) {
+            return Err(EngineError::InvalidCheckpoint(format!("run {} is already in flight", run_id)));
+        }
+        state.active.insert(run_id.to_string(), cancel);
+        Ok(ActiveRun {
+            runs: self.clone(),
+            run_id: run_id.to_string(),
+        })
+    }
+
+    /// Refuses further runs, returning those in flight.
+    pub(crate) fn close(&self) -> BTreeSet<String> {
+        let mut state = self.lock();
+        state.closed = true;
+        state.active.keys().cloned().collect()
+    }
+
+    /// Ids of the runs in flight.
+    pub(crate) fn in_flight(&self) -> BTreeSet<String> {
+        self.lock().active.keys().cloned().collect()
+    }
+
+    /// Cancels run `run_id`, returning whether it is in flight.
+    pub(crate) fn cancel(&self, run_id: &str) -> bool {
+        let cancel = self.lock().active.get(run_id).cloned();
+        cancel.inspect(|cancel| cancel.cancel()).is_some()
+    }
+
+    /// Waits until no run is in flight or `deadline` passes, returning the
+    /// runs still in flight.
+    pub(crate) fn wait(&self, deadline: Option<Instant>) -> BTreeSet<String> {
+        let mut state = self.lock();
+        while !state.active.is_empty() {
+            state = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    self.finished.wait_timeout(state, deadline - now).unwrap_or_else(|e| e.into_inner()).0
+                }
+                None => self.finished.wait(state).unwrap_or_else(|e| e.into_inner()),
+            };
+        }
+        state.active.keys().cloned().collect()
+    }
+
+    /// Whether `close` has been called.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+}
+
+/// A top-level run in flight, which ends when this drops.
+pub(crate) struct ActiveRun {
+    runs: Arc<Runs>,
+    run_id: String,
+}
+
+impl Drop for ActiveRun {
+    fn drop(&mut self) {
+        self.runs.lock().active.remove(&self.run_id);
+        self.runs.finished.notify_all();
+    }
+}
+
+/// Shuts `runs` down, cancelling the runs still in flight after `grace`,
+/// and calling `cancel` if there are any.
+pub(crate) fn shutdown(runs: &Runs, grace: Duration, cancel: impl FnOnce()) -> ShutdownReport {
+    let in_flight = runs.close();
+    let interrupted = runs.wait(Some(Instant::now() + grace));
+    if !interrupted.is_empty() {
+        for run_id in &interrupted {
+            runs.cancel(run_id);
+        }
+        cancel();
+        runs.wait(None);
+    }
+    ShutdownReport {
+        drained: in_flight.difference(&interrupted).cloned().collect(),
+        interrupted: interrupted.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shutdown() {
+        let runs = Arc::new(Runs::default());
+        let quick = runs.begin("quick", Arc::default()).unwrap();
+        let flag = Arc::new(Cancellation::new());
+        let slow = runs.begin("slow", flag.clone()).unwrap();
+        let finisher = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            drop(quick);
+        });
+        let slow = Mutex::new(Some(slow));
+        let report = shutdown(&runs, Duration::from_millis(200), || drop(slow.lock().unwrap().take()));
+        finisher.join().unwrap();
+        assert_eq!(report.drained, ["quick"]);
+        assert_eq!(report.interrupted, ["slow"]);
+        assert!(flag.is_cancelled());
+        assert!(runs.is_closed());
+        assert!(matches!(runs.begin("late", Arc::default()), Err(EngineError::ShuttingDown)));
+    }
+}
@@ -0,0 +1,76 @@
+//! Per-tenant isolation within one engine.
+//!
+//! An engine that serves several customers registers each with
+//! `Engine::with_tenant` and runs their workflows with `Engine::run_as`. A
+//! tenant's runs, including the loops and workflow calls nested in them, see
+//! only the tenant's own secrets, cache, and clients (so connection pools and
+//! `http.request` rate limits are per tenant), are recorded in the tenant's
+//! history rather than the engine's, and checkpoint under the tenant's id. Its concurrency limits cap the tenant's
+//! own nodes and apply on top of the engine-wide ones, so one tenant cannot
+//! take every slot of a category.
+
+use crate::history::RunHistory;
+use crate::limits::Limiter;
+use metabuilder_core::{Cache, Clients, MemorySecrets, SecretsProvider};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// What one tenant's runs use in place of the engine's own.
+pub struct Tenant {
+    /// Set when the tenant is registered
+    pub(crate) id: String,
+    pub(crate) secrets: Arc<dyn SecretsProvider>,
+    pub(crate) cache: Arc<Cache>,
+    pub(crate) clients: Arc<Clients>,
+    pub(crate) history: Option<Arc<RunHistory>>,
+    pub(crate) limits: HashMap<String, Limiter>,
+}
+
+impl Tenant {
+    /// A tenant with no secrets, an empty cache, no clients, no history, and
+    /// no limits of its own.
+    pub fn new() -> Self {
+        Tenant {
+            id: String::new(),
+            secrets: Arc::new(MemorySecrets::new()),
+            cache: Arc::new(Cache::new()),
+            clients: Arc::new(Clients::new()),
+            history: None,
+            limits: HashMap::new(),
+        }
+    }
+
+    /// Sets the secrets provider exposed to the tenant's nodes.
+    pub fn with_secrets(mut self, secrets: impl SecretsProvider + 'static) -> Self {
+        self.secrets = Arc::new(secrets);
+        self
+    }
+
+    /// Records every node executed by the tenant's top-level runs in `history`.
+    pub fn with_history(mut self, history: Arc<RunHistory>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Runs at most `max` of the tenant's nodes of `category` at once.
+    pub fn with_concurrency_limit(mut self, category: impl Into<String>, max: usize) -> Self {
+        self.limits.insert(category.into(), Limiter::new(max));
+        self
+    }
+
+    /// The cache shared by the tenant's runs.
+    pub fn cache(&self) -> &Arc<Cache> {
+        &self.cache
+    }
+
+    /// The clients, such as connection pools, shared by the tenant's runs.
+    pub fn clients(&self) -> &Arc<Clients> {
+        &self.clients
+    }
+}
+
+impl Default for Tenant {
+    fn default() -> Self {
+        Self::new()
+    }
+}
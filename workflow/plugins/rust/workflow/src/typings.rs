@@ -0,0 +1,147 @@
+//! TypeScript typings for node ports, generated from the registry.
+//!
+//! `typescript` writes a declaration file with an inputs and an outputs
+//! interface per node type, keyed by node type in `NodeInputs` and
+//! `NodeOutputs`, so an editor can offer exactly the ports the executors
+//! take. Ports come from the registered schemas; node types without one get
+//! open records. Schemas do not say which inputs are required, so every port
+//! is optional, and outputs may also be `null`, as they are when a node
+//! fails. Every node may report an `error` output.
+//!
+//! The typings for the bundled nodes are checked in as `nodes.d.ts`, and a
+//! test fails when they fall behind the schemas; run it with
+//! `UPDATE_TYPINGS=1` to write them again.
+
+use crate::registry::Registry;
+use crate::schema::PortKind;
+use std::fmt::Write;
+
+/// The TypeScript type of values of `kind`.
+fn ts_type(kind: PortKind) -> &'static str {
+    match kind {
+        PortKind::Any => "unknown",
+        PortKind::Bool => "boolean",
+        PortKind::Number => "number",
+        PortKind::String => "string",
+        PortKind::List => "unknown[]",
+        PortKind::Object => "Record<string, unknown>",
+    }
+}
+
+/// `datetime.timezone_convert` becomes `DatetimeTimezoneConvert`.
+fn type_name(node_type: &str) -> String {
+    node_type
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part[..1].to_ascii_uppercase() + &part[1..])
+        .collect()
+}
+
+/// Port names that are not identifiers are quoted.
+fn property(name: &str) -> String {
+    let identifier = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        name.to_string()
+    } else {
+        serde_json::to_string(name).expect("strings are always serializable")
+    }
+}
+
+/// Declarations for the ports of every node type in `registry`.
+pub fn typescript(registry: &Registry) -> String {
+    let mut out = String::from(
+        "// Generated from the node schemas by `workflow::typings`. Do not edit.\n\n\
+         /** Outputs every node may report. */\n\
+         export interface NodeResult {\n  error?: string | null;\n}\n",
+    );
+    let mut inputs = String::new();
+    let mut outputs = String::new();
+    for node_type in registry.node_types() {
+        let name = type_name(node_type);
+        match registry.schema(node_type) {
+            Some(schema) => {
+                let _ = write!(out, "\n/** Inputs of `{}`. */\nexport interface {}Inputs {{\n", node_type, name);
+                for (port, kind) in &schema.inputs {
+                    let _ = writeln!(out, "  {}?: {};", property(port), ts_type(*kind));
+                }
+                let _ = write!(
+                    out,
+                    "}}\n\n/** Outputs of `{}`. */\nexport interface {}Outputs extends NodeResult {{\n",
+                    node_type, name
+                );
+                for (port, kind) in &schema.outputs {
+                    let _ = writeln!(out, "  {}?: {} | null;", property(port), ts_type(*kind));
+                }
+                out.push_str("}\n");
+            }
+            None => {
+                let _ = write!(
+                    out,
+                    "\n/** Inputs of `{0}`. */\nexport type {1}Inputs = Record<string, unknown>;\n\n\
+                     /** Outputs of `{0}`. */\nexport type {1}Outputs = NodeResult & Record<string, unknown>;\n",
+                    node_type, name
+                );
+            }
+        }
+        let _ = writeln!(inputs, "  \"{}\": {}Inputs;", node_type, name);
+        let _ = writeln!(outputs, "  \"{}\": {}Outputs;", node_type, name);
+    }
+    let _ = write!(
+        out,
+        "\n/** Inputs by node type. */\nexport interface NodeInputs {{\n{}}}\n\n\
+         /** Outputs by node type. */\nexport interface NodeOutputs {{\n{}}}\n\n\
+         export type NodeType = keyof NodeInputs;\n",
+        inputs, outputs
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::NodeSchema;
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    fn noop(_: HashMap<String, Value>, _: Option<&dyn std::any::Any>) -> HashMap<String, Value> {
+        HashMap::new()
+    }
+
+    #[test]
+    fn test_typescript() {
+        let mut registry = Registry::new();
+        registry.register("text.front_matter", noop);
+        registry.register("x.raw", noop);
+        registry.register_schema(
+            "text.front_matter",
+            NodeSchema::new()
+                .input("text", PortKind::String)
+                .input("max-depth", PortKind::Number)
+                .output("data", PortKind::Object)
+                .output("body", PortKind::Any),
+        );
+        let typings = typescript(&registry);
+        assert!(typings.contains(
+            "export interface TextFrontMatterInputs {\n  text?: string;\n  \"max-depth\"?: number;\n}\n"
+        ));
+        assert!(typings.contains(
+            "export interface TextFrontMatterOutputs extends NodeResult {\n  \
+             data?: Record<string, unknown> | null;\n  body?: unknown | null;\n}\n"
+        ));
+        assert!(typings.contains("export type XRawInputs = Record<string, unknown>;\n"));
+        assert!(typings.contains("export interface NodeInputs {\n  \"text.front_matter\": TextFrontMatterInputs;\n  \"x.raw\": XRawInputs;\n}\n"));
+    }
+
+    #[cfg(feature = "all-nodes")]
+    #[test]
+    fn test_bundled_typings_are_current() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/nodes.d.ts");
+        let typings = typescript(&Registry::with_builtins());
+        if std::env::var_os("UPDATE_TYPINGS").is_some() {
+            std::fs::write(path, &typings).unwrap();
+        }
+        let checked_in = std::fs::read_to_string(path).unwrap_or_default();
+        assert!(checked_in == typings, "nodes.d.ts is out of date; run this test with UPDATE_TYPINGS=1");
+    }
+}
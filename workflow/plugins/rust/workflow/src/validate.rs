@@ -0,0 +1,216 @@
+//! Static checks of a workflow against a registry, run before execution.
+
+use crate::contract;
+use crate::engine::{CALL, ON_ERROR, WHILE};
+use crate::error::EngineError;
+use crate::graph::{Edge, Workflow};
+use crate::registry::Registry;
+use crate::schema::PortKind;
+use std::collections::HashMap;
+
+/// A problem found by `Workflow::validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    /// The graph is structurally invalid (duplicate ids, unknown nodes, cycles).
+    Invalid(EngineError),
+    /// A node's type is not in the registry.
+    UnknownNodeType { node: String, node_type: String },
+    /// An edge names a port that its node's schema does not declare.
+    UnknownPort { edge: String, node: String, port: String },
+    /// An edge connects ports whose schemas declare different kinds.
+    TypeMismatch { edge: String, from: PortKind, to: PortKind },
+    /// The node is wired to a branch port that is never emitted, so it is
+    /// always skipped.
+    Unreachable(String),
+    /// A declared input or output is inconsistent, such as an output that
+    /// reads a node that does not exist.
+    Contract { entry: String, message: String },
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Diagnostic::Invalid(e) => write!(f, "{}", e),
+            Diagnostic::UnknownNodeType { node, node_type } => {
+                write!(f, "Node {} has unknown type: {}", node, node_type)
+            }
+            Diagnostic::UnknownPort { edge, node, port } => {
+                write!(f, "Edge {} uses port {} that node {} does not have", edge, port, node)
+            }
+            Diagnostic::TypeMismatch { edge, from, to } => {
+                write!(f, "Edge {} connects a {} output to a {} input", edge, from, to)
+            }
+            Diagnostic::Unreachable(node) => write!(f, "Node {} can never run", node),
+            Diagnostic::Contract { entry, message } => write!(f, "Workflow {} {}", entry, message),
+        }
+    }
+}
+
+fn label(edge: &Edge) -> String {
+    format!("{}.{} -> {}.{}", edge.from, edge.from_port, edge.to, edge.to_port)
+}
+
+impl Workflow {
+    /// Checks the workflow without running it, returning every problem found.
+    ///
+    /// Port checks apply only to node types with a schema in `registry`.
+    pub fn validate(&self, registry: &Registry) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = contract::check(self)
+            .into_iter()
+            .map(|(entry, message)| Diagnostic::Contract { entry, message })
+            .collect();
+        for node in &self.nodes {
+            let engine_node = node.node_type == WHILE || node.node_type == CALL;
+            if !engine_node && !registry.contains(&node.node_type) {
+                diagnostics.push(Diagnostic::UnknownNodeType {
+                    node: node.id.clone(),
+                    node_type: node.node_type.clone(),
+                });
+            }
+        }
+        let upstream = match self.upstream() {
+            Ok(upstream) => upstream,
+            Err(e) => {
+                diagnostics.push(Diagnostic::Invalid(e));
+                return diagnostics;
+            }
+        };
+
+        let types: HashMap<&str, &str> = self.nodes.iter().map(|n| (n.id.as_str(), n.node_type.as_str())).collect();
+        let mut never_taken = Vec::new();
+        for edge in &self.edges {
+            let from = registry.schema(types[edge.from.as_str()]);
+            let to = registry.schema(types[edge.to.as_str()]);
+            let from_kind = match from {
+                _ if edge.from_port == ON_ERROR => Some(PortKind::Object),
+                Some(schema) => {
+                    let kind = schema.output_kind(&edge.from_port);
+                    if kind.is_none() {
+                        diagnostics.push(Diagnostic::UnknownPort {
+                            edge: label(edge),
+                            node: edge.from.clone(),
+                            port: edge.from_port.clone(),
+                        });
+                        if schema.branching {
+                            never_taken.push(edge);
+                        }
+                    }
+                    kind
+                }
+                None => None,
+            };
+            let to_kind = to.and_then(|schema| {
+                let kind = schema.input_kind(&edge.to_port);
+                if kind.is_none() {
+                    diagnostics.push(Diagnostic::UnknownPort {
+                        edge: label(edge),
+                        node: edge.to.clone(),
+                        port: edge.to_port.clone(),
+                    });
+                }
+                kind
+            });
+            // A streaming edge carries chunks, whose kind no schema describes.
+            if let (Some(from), Some(to), false) = (from_kind, to_kind, edge.stream) {
+                if !from.fits(to) {
+                    diagnostics.push(Diagnostic::TypeMismatch {
+                        edge: label(edge),
+                        from,
+                        to,
+                    });
+                }
+            }
+        }
+
+        let order = match self.topological_order() {
+            Ok(order) => order,
+            Err(e) => {
+                diagnostics.push(Diagnostic::Invalid(e));
+                return diagnostics;
+            }
+        };
+        // Mirrors the engine's skipping rule with the edges that can never be taken.
+        let position: HashMap<&str, usize> = self.nodes.iter().enumerate().map(|(i, n)| (n.id.as_str(), i)).collect();
+        let mut unreachable = vec![false; self.nodes.len()];
+        for id in order {
+            let i = position[id];
+            let cut = never_taken.iter().any(|edge| edge.to == id && !unreachable[position[edge.from.as_str()]]);
+            let stranded = !upstream[i].is_empty() && upstream[i].iter().all(|&from| unreachable[from]);
+            if cut || stranded {
+                unreachable[i] = true;
+                diagnostics.push(Diagnostic::Unreachable(id.to_string()));
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_clean_workflow() {
+        let workflow = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!([1, 2])))
+            .node(Node::new("round", "math.round"))
+            .edge(Edge::new("sum", "result", "round", "value"));
+
+        assert_eq!(workflow.validate(&Registry::with_builtins()), Vec::new());
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let workflow = Workflow::new()
+            .node(Node::new("check", "control.if").param("condition", json!(true)))
+            .node(Node::new("len", "string.length"))
+            .node(Node::new("round", "math.round"))
+            .node(Node::new("after", "math.abs").param("value", json!("$nodes.round.result")))
+            .node(Node::new("odd", "math.nope"))
+            .edge(Edge::new("check", "maybe", "round", "value"))
+            .edge(Edge::new("len", "result", "round", "decimals"))
+            .edge(Edge::new("check", "then", "len", "text"))
+            .edge(Edge::new("len", "result", "odd", "x"));
+        let diagnostics = workflow.validate(&Registry::with_builtins());
+        let messages: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Node odd has unknown type: math.nope",
+                "Edge check.maybe -> round.value uses port maybe that node check does not have",
+                "Edge check.then -> len.text uses port text that node len does not have",
+                "Node round can never run",
+                "Node after can never run",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_type_mismatch_and_cycle() {
+        let workflow = Workflow::new()
+            .node(Node::new("upper", "string.upper"))
+            .node(Node::new("abs", "math.abs"))
+            .edge(Edge::new("upper", "result", "abs", "value"))
+            .edge(Edge::new("abs", "result", "upper", "string"));
+
+        let diagnostics = workflow.validate(&Registry::with_builtins());
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::TypeMismatch {
+                    edge: "upper.result -> abs.value".to_string(),
+                    from: PortKind::String,
+                    to: PortKind::Number,
+                },
+                Diagnostic::TypeMismatch {
+                    edge: "abs.result -> upper.string".to_string(),
+                    from: PortKind::Number,
+                    to: PortKind::String,
+                },
+                Diagnostic::Invalid(EngineError::Cycle(vec!["upper".to_string(), "abs".to_string()])),
+            ]
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! HTTP webhook triggers.
+//!
+//! A `WebhookServer` maps routes to workflows. Each request runs its
+//! workflow with these vars:
+//!
+//! - `body`: the request body, parsed as JSON when it is JSON and a string otherwise
+//! - `headers`: header names (lowercase) to values, repeated headers joined by `, `
+//! - `query`: query parameters
+//! - `params`: path parameters, such as `id` for the route `/orders/{id}`
+//! - `method` and `path`
+//!
+//! A workflow that declares inputs (see `contract`) receives only the ones
+//! it declares. The response is `200` with the workflow's declared outputs as
+//! a JSON object, or its node `outputs` if it declares none. Failed runs
+//! answer `{"error": message}` with `400` for invalid inputs, `504` for
+//! timeouts, and `500` otherwise.
+
+use crate::engine::Engine;
+use crate::error::EngineError;
+use crate::graph::Workflow;
+use crate::reload::LiveWorkflow;
+use axum::body::Bytes;
+use axum::extract::{Query, RawPathParams, State};
+use axum::http::{HeaderMap, Method, StatusCode, Uri};
+use axum::routing::{on, MethodFilter};
+use axum::{Json, Router};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Serves workflows over HTTP.
+pub struct WebhookServer {
+    engine: Arc<Engine>,
+    router: Router,
+}
+
+impl WebhookServer {
+    /// Creates a server with no routes that runs workflows with `engine`.
+    pub fn new(engine: Arc<Engine>) -> Self {
+        WebhookServer {
+            engine,
+            router: Router::new(),
+        }
+    }
+
+    /// Runs `workflow` for `method` requests to `path`, such as
+    /// `("POST", "/hooks/github")`. Path segments like `{id}` match any
+    /// value and are passed in the `params` var.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `method` is not a standard HTTP method or the route
+    /// overlaps one already added.
+    pub fn route(self, method: &str, path: &str, workflow: Workflow) -> Self {
+        self.route_live(method, path, Arc::new(LiveWorkflow::fixed(workflow)))
+    }
+
+    /// Like `route`, but each request runs the definition `workflow` holds
+    /// when it arrives, so reloads apply to later requests (see `reload`).
+    pub fn route_live(mut self, method: &str, path: &str, workflow: Arc<LiveWorkflow>) -> Self {
+        let method = method
+            .parse::<Method>()
+            .ok()
+            .and_then(|method| MethodFilter::try_from(method).ok())
+            .unwrap_or_else(|| panic!("unsupported method: {}", method));
+        let target = Arc::new(Target {
+            engine: self.engine.clone(),
+            workflow,
+        });
+        self.router = self.router.route(path, on(method, handle).with_state(target));
+        self
+    }
+
+    /// The routes as an axum `Router`, to merge into an existing service.
+    pub fn into_router(self) -> Router {
+        self.router
+    }
+
+    /// Serves requests on `listener` until the task is dropped or the
+    /// listener fails.
+    pub async fn serve(self, listener: tokio::net::TcpListener) -> std::io::Result<()> {
+        axum::serve(listener, self.router).await
+    }
+}
+
+/// The engine and workflow behind one route.
+struct Target {
+    engine: Arc<Engine>,
+    workflow: Arc<LiveWorkflow>,
+}
+
+async fn handle(
+    State(target): State<Arc<Target>>,
+    method: Method,
+    uri: Uri,
+    params: RawPathParams,
+    Query(query): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<Value>) {
+    let mut vars = HashMap::new();
+    vars.insert("body".to_string(), parse_body(&body));
+    vars.insert("headers".to_string(), header_object(&headers));
+    vars.insert("query".to_string(), serde_json::to_value(query).unwrap_or(Value::Null));
+    let params: Map<String, Value> = params.iter().map(|(name, value)| (name.to_string(), Value::from(value))).collect();
+    vars.insert("params".to_string(), Value::Object(params));
+    vars.insert("method".to_string(), Value::from(method.as_str()));
+    vars.insert("path".to_string(), Value::from(uri.path()));
+    let workflow = target.workflow.current();
+    if !workflow.inputs.is_empty() {
+        vars.retain(|name, _| workflow.inputs.contains_key(name));
+    }
+
+    let run = tokio::task::spawn_blocking(move || {
+        let result = target.engine.run(&workflow, vars)?;
+        Ok(match workflow.outputs.is_empty() {
+            true => serde_json::to_value(result.outputs).unwrap_or(Value::Null),
+            false => serde_json::to_value(result.returned).unwrap_or(Value::Null),
+        })
+    })
+    .await;
+    match run {
+        Ok(Ok(outputs)) => (StatusCode::OK, Json(outputs)),
+        Ok(Err(error)) => (status_of(&error), Json(serde_json::json!({"error": error.to_string()}))),
+        Err(panicked) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": panicked.to_string()})),
+        ),
+    }
+}
+
+fn status_of(error: &EngineError) -> StatusCode {
+    match error {
+        EngineError::InvalidInput { .. } => StatusCode::BAD_REQUEST,
+        EngineError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// The body as JSON if it parses, else as text; an empty body is `null`.
+fn parse_body(body: &[u8]) -> Value {
+    if body.is_empty() {
+        return Value::Null;
+    }
+    serde_json::from_slice(body).unwrap_or_else(|_| Value::from(String::from_utf8_lossy(body)))
+}
+
+fn header_object(headers: &HeaderMap) -> Value {
+    let mut object = Map::new();
+    for name in headers.keys() {
+        let values: Vec<String> = headers
+            .get_all(name)
+            .iter()
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+            .collect();
+        object.insert(name.as_str().to_string(), Value::from(values.join(", ")));
+    }
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{InputSpec, OutputSpec};
+    use crate::graph::Node;
+    use crate::schema::PortKind;
+    use serde_json::json;
+    use std::io::{Read, Write};
+
+    /// Sends a raw HTTP/1.1 request and returns the status and JSON body.
+    fn request(addr: std::net::SocketAddr, head: &str, body: &str) -> (u16, Value) {
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{}\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            head,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        (status, serde_json::from_str(body).unwrap_or(Value::Null))
+    }
+
+    #[test]
+    fn test_routes() {
+        let echo = Workflow::new()
+            .node(Node::new("sum", "math.add").param("numbers", json!(["$body.a", "$body.b"])))
+            .node(Node::new("agent", "string.upper").param("string", json!("$headers[\"x-agent\"]")))
+            .node(Node::new("name", "string.upper").param("string", json!("$query.name")))
+            .node(Node::new("id", "string.upper").param("string", json!("$params.id")));
+        let typed = Workflow::new()
+            .input("body", InputSpec::new(PortKind::Number))
+            .output("doubled", OutputSpec::new(PortKind::Number, "$nodes.mul.result"))
+            .node(Node::new("mul", "math.multiply").param("numbers", json!(["$body", 2])));
+        let server = WebhookServer::new(Arc::new(Engine::default()))
+            .route("POST", "/echo/{id}", echo)
+            .route("PUT", "/double", typed);
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(server.serve(listener));
+            let responses = tokio::task::spawn_blocking(move || {
+                [
+                    request(addr, "POST /echo/x1?name=ada HTTP/1.1\r\nX-Agent: bot", r#"{"a": 1, "b": 2}"#),
+                    request(addr, "PUT /double HTTP/1.1", "21"),
+                    request(addr, "PUT /double HTTP/1.1", r#""x""#),
+                    request(addr, "GET /echo/x1 HTTP/1.1", ""),
+                ]
+            })
+            .await
+            .unwrap();
+
+            let (status, body) = &responses[0];
+            assert_eq!(*status, 200);
+            assert_eq!(body["sum"]["result"], json!(3));
+            assert_eq!(body["agent"]["result"], json!("BOT"));
+            assert_eq!(body["name"]["result"], json!("ADA"));
+            assert_eq!(body["id"]["result"], json!("X1"));
+            assert_eq!(responses[1], (200, json!({"doubled": 42})));
+            assert_eq!(responses[2].0, 400);
+            assert_eq!(responses[2].1["error"], json!("Invalid input body: expected number, got string"));
+            assert_eq!(responses[3].0, 405);
+        });
+    }
+}
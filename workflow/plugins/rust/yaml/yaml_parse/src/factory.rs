@@ -0,0 +1,8 @@
+//! Factory for YamlParse plugin.
+
+use super::YamlParse;
+
+/// Creates a new YamlParse instance.
+pub fn create() -> YamlParse {
+    YamlParse::new()
+}
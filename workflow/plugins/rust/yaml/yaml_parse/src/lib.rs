@@ -0,0 +1,112 @@
+//! Workflow plugin: parse YAML text to a value.
+//!
+//! The counterpart of `convert.parse_json` for configuration files: parses
+//! `string` as one YAML 1.2 document with `metabuilder_core::yaml`, anchors,
+//! aliases, and `<<` merge keys included, and outputs the value in `result`.
+//! What JSON cannot hold, such as several documents, custom tags, or
+//! `.inf`, is an error rather than a guess. On failure, `error` names the
+//! line, which is also output as `line`.
+
+use metabuilder_core::yaml;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// YamlParse implements the NodeExecutor trait for YAML parsing.
+pub struct YamlParse {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl YamlParse {
+    /// Creates a new YamlParse instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "yaml.parse",
+            category: "yaml",
+            description: "Parse YAML text to a value",
+        }
+    }
+}
+
+impl Default for YamlParse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for YamlParse {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let mut output = HashMap::new();
+
+        let Some(string) = inputs.get("string").and_then(Value::as_str) else {
+            output.insert("result".to_string(), Value::Null);
+            output.insert("error".to_string(), json!("string is required"));
+            return output;
+        };
+        match yaml::parse(string) {
+            Ok(value) => {
+                output.insert("result".to_string(), value);
+            }
+            Err(e) => {
+                let line = e.rsplit_once(" at line ").and_then(|(_, line)| line.parse::<u64>().ok());
+                output.insert("result".to_string(), Value::Null);
+                output.insert("line".to_string(), json!(line));
+                output.insert("error".to_string(), json!(e));
+            }
+        }
+
+        output
+    }
+}
+
+/// Creates a new YamlParse instance.
+pub fn create() -> YamlParse {
+    YamlParse::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(string: &str) -> HashMap<String, Value> {
+        YamlParse::new().execute(HashMap::from([("string".to_string(), json!(string))]), None)
+    }
+
+    #[test]
+    fn test_parse() {
+        let output = run("name: app\nreplicas: 3\nports:\n  - 80\n  - 443\nenv: {DEBUG: false}\n");
+        assert_eq!(
+            output["result"],
+            json!({"name": "app", "replicas": 3, "ports": [80, 443], "env": {"DEBUG": false}})
+        );
+        assert_eq!(run("")["result"], Value::Null);
+        let output = run("base: &base {image: app}\nweb:\n  <<: *base\n  port: 80\n");
+        assert_eq!(output["result"]["web"], json!({"image": "app", "port": 80}));
+    }
+
+    #[test]
+    fn test_errors() {
+        let output = run("a: 1\nb: 2\nb: 3\n");
+        assert_eq!(output["result"], Value::Null);
+        assert_eq!(output["error"], "duplicate key b at line 3");
+        assert_eq!(output["line"], 3);
+        assert_eq!(run("a: !env HOME\n")["error"], "unsupported tag !env at line 1");
+        let output = YamlParse::new().execute(HashMap::new(), None);
+        assert_eq!(output["error"], "string is required");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "yaml.parse");
+        assert_eq!(executor.category, "yaml");
+    }
+}
@@ -0,0 +1,8 @@
+//! Factory for YamlSerialize plugin.
+
+use super::YamlSerialize;
+
+/// Creates a new YamlSerialize instance.
+pub fn create() -> YamlSerialize {
+    YamlSerialize::new()
+}
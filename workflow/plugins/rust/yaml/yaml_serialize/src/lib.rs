@@ -0,0 +1,82 @@
+//! Workflow plugin: write a value as YAML text.
+//!
+//! The counterpart of `convert.to_json` for configuration files: outputs
+//! `value` in `result` as a block-style YAML document indented by two
+//! spaces, keys sorted, with strings quoted only where they would otherwise
+//! read as another type (YAML 1.1 booleans such as `on` included) and
+//! `null` written as `~`. `yaml.parse` reads the output back unchanged.
+
+use metabuilder_core::yaml;
+use serde_json::{json, Value};
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Trait for workflow node executors.
+pub trait NodeExecutor {
+    /// Execute the node with given inputs and optional runtime context.
+    fn execute(&self, inputs: HashMap<String, Value>, runtime: Option<&dyn Any>) -> HashMap<String, Value>;
+}
+
+/// YamlSerialize implements the NodeExecutor trait for YAML output.
+pub struct YamlSerialize {
+    pub node_type: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+}
+
+impl YamlSerialize {
+    /// Creates a new YamlSerialize instance.
+    pub fn new() -> Self {
+        Self {
+            node_type: "yaml.serialize",
+            category: "yaml",
+            description: "Write a value as YAML text",
+        }
+    }
+}
+
+impl Default for YamlSerialize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeExecutor for YamlSerialize {
+    fn execute(&self, inputs: HashMap<String, Value>, _runtime: Option<&dyn Any>) -> HashMap<String, Value> {
+        let value = inputs.get("value").unwrap_or(&Value::Null);
+        let mut output = HashMap::new();
+        output.insert("result".to_string(), json!(yaml::to_string(value)));
+        output
+    }
+}
+
+/// Creates a new YamlSerialize instance.
+pub fn create() -> YamlSerialize {
+    YamlSerialize::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(value: Value) -> Value {
+        YamlSerialize::new().execute(HashMap::from([("value".to_string(), value)]), None)["result"].clone()
+    }
+
+    #[test]
+    fn test_serialize() {
+        let value = json!({"name": "app", "ports": [80, 443], "env": {"DEBUG": false, "MODE": "on"}});
+        let text = run(value.clone());
+        assert_eq!(text, "env:\n  DEBUG: false\n  MODE: \"on\"\nname: app\nports:\n  - 80\n  - 443\n");
+        assert_eq!(yaml::parse(text.as_str().unwrap()).unwrap(), value);
+        assert_eq!(run(json!({"flag": "on", "none": null})), "flag: \"on\"\nnone: ~\n");
+        assert_eq!(run(Value::Null), "~\n");
+    }
+
+    #[test]
+    fn test_factory() {
+        let executor = create();
+        assert_eq!(executor.node_type, "yaml.serialize");
+        assert_eq!(executor.category, "yaml");
+    }
+}